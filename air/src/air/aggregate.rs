@@ -0,0 +1,255 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    Air, AirContext, Assertion, ColumnGroup, EvaluationFrame, PeriodicColumn, ProofOptions,
+    TraceInfo,
+};
+use core::ops::Range;
+use crypto::{Digest, ElementHasher};
+use math::{FieldElement, ToElements};
+use utils::collections::{BTreeMap, Vec};
+
+// AGGREGATE PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs for an [AggregateAir]; contains public inputs for each aggregated statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregatePublicInputs<P> {
+    inputs: Vec<P>,
+}
+
+impl<P> AggregatePublicInputs<P> {
+    /// Returns public inputs for an aggregate of statements with the specified public inputs.
+    ///
+    /// # Panics
+    /// Panics if `inputs` is empty.
+    pub fn new(inputs: Vec<P>) -> Self {
+        assert!(!inputs.is_empty(), "at least one statement must be aggregated");
+        Self { inputs }
+    }
+
+    /// Returns public inputs of the aggregated statements.
+    pub fn inputs(&self) -> &[P] {
+        &self.inputs
+    }
+
+    /// Returns the number of aggregated statements.
+    pub fn num_statements(&self) -> usize {
+        self.inputs.len()
+    }
+}
+
+impl<B: FieldElement, P: ToElements<B>> ToElements<B> for AggregatePublicInputs<P> {
+    fn to_elements(&self) -> Vec<B> {
+        self.inputs.iter().flat_map(|inputs| inputs.to_elements()).collect()
+    }
+}
+
+// AGGREGATE AIR
+// ================================================================================================
+
+/// AIR for an aggregate of several statements about the same computation.
+///
+/// An aggregate AIR combines `n` instances of AIR `A`, each instantiated with its own public
+/// inputs, into a single AIR. The execution trace of the aggregate consists of execution traces
+/// of all statements placed side by side: columns of the first statement come first, followed by
+/// columns of the second statement etc. Transition constraints and assertions of each instance
+/// are placed against the columns of the corresponding statement.
+///
+/// A proof generated for an aggregate AIR uses a single constraint composition polynomial and a
+/// single FRI instance for all statements, and thus, it is significantly smaller than the sum of
+/// proofs for the individual statements. Out-of-domain consistency of constraints of all
+/// statements is checked by the verifier as a part of the regular verification procedure.
+///
+/// All aggregated statements must have traces of the same width, length, and metadata, and must
+/// be proven using the same proof options. Custom transition divisors, column groups, row hash
+/// strategies, and fixed numbers of constraint composition columns of the instances are carried
+/// over to the aggregate; column groups of all instances are merged into a single group.
+/// Currently, only AIRs with a single trace segment can be aggregated.
+pub struct AggregateAir<A: Air> {
+    context: AirContext<A::BaseField>,
+    instances: Vec<A>,
+    instance_width: usize,
+    constraint_ranges: Vec<Range<usize>>,
+    main_periodic_column_ranges: Vec<Range<usize>>,
+}
+
+impl<A: Air> AggregateAir<A> {
+    /// Returns AIR instances of the aggregated statements.
+    pub fn instances(&self) -> &[A] {
+        &self.instances
+    }
+}
+
+impl<A: Air> Air for AggregateAir<A> {
+    type BaseField = A::BaseField;
+    type PublicInputs = AggregatePublicInputs<A::PublicInputs>;
+
+    /// Returns a new aggregate AIR with an instance of `A` for each set of public inputs.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `trace_info` describes a multi-segment execution trace.
+    /// * Trace width is not divisible by the number of aggregated statements.
+    /// * Instances of `A` require different numbers of transition exemptions, different row hash
+    ///   strategies, or different numbers of constraint composition columns.
+    /// * Instances of `A` define column groups with different blowup factors, or only some of the
+    ///   instances define a column group.
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        assert!(
+            !trace_info.is_multi_segment(),
+            "only statements with single-segment traces can be aggregated"
+        );
+        let num_statements = pub_inputs.num_statements();
+        assert_eq!(
+            trace_info.width() % num_statements,
+            0,
+            "trace width {} is not divisible by the number of aggregated statements {}",
+            trace_info.width(),
+            num_statements
+        );
+        let instance_width = trace_info.width() / num_statements;
+        let instance_info =
+            TraceInfo::with_meta(instance_width, trace_info.length(), trace_info.meta().clone());
+
+        let instances: Vec<A> = pub_inputs
+            .inputs
+            .into_iter()
+            .map(|inputs| A::new(instance_info.clone(), inputs, options.clone()))
+            .collect();
+
+        // concatenate constraint degrees of all instances, and keep track of which constraints
+        // and periodic columns belong to which instance; custom transition divisors and column
+        // group columns are shifted to the positions of the corresponding constraints and
+        // columns in the aggregate
+        let mut degrees = Vec::new();
+        let mut num_assertions = 0;
+        let mut custom_divisors = BTreeMap::new();
+        let mut column_group_columns = Vec::new();
+        let mut constraint_ranges = Vec::with_capacity(num_statements);
+        let mut main_periodic_column_ranges = Vec::with_capacity(num_statements);
+        let mut num_main_periodic_columns = 0;
+        for (i, instance) in instances.iter().enumerate() {
+            let context = instance.context();
+            for (&idx, divisor) in context.custom_transition_divisors.iter() {
+                custom_divisors.insert(degrees.len() + idx, divisor.clone());
+            }
+            if let Some(group) = context.column_group() {
+                let offset = i * instance_width;
+                column_group_columns.extend(group.columns().iter().map(|&c| offset + c));
+            }
+            let start = degrees.len();
+            degrees.extend_from_slice(&context.main_transition_constraint_degrees);
+            constraint_ranges.push(start..degrees.len());
+            num_assertions += context.num_main_assertions;
+
+            // only periodic columns associated with the main trace segment are passed to
+            // transition constraints of the main segment, and polynomials of these columns are
+            // placed in front of the polynomials of auxiliary periodic columns of all instances
+            let num_columns = instance.num_main_periodic_columns();
+            main_periodic_column_ranges
+                .push(num_main_periodic_columns..num_main_periodic_columns + num_columns);
+            num_main_periodic_columns += num_columns;
+        }
+
+        let first = instances[0].context();
+        assert!(
+            instances.iter().all(|i| {
+                let context = i.context();
+                context.num_transition_exemptions() == first.num_transition_exemptions()
+                    && context.row_hash_strategy() == first.row_hash_strategy()
+                    && context.fixed_num_composition_columns == first.fixed_num_composition_columns
+                    && context.column_group().map(|g| g.blowup_factor())
+                        == first.column_group().map(|g| g.blowup_factor())
+            }),
+            "all aggregated statements must require the same number of transition exemptions, \
+            row hash strategy, number of composition columns, and column group blowup factor"
+        );
+
+        // custom divisors are set via the context so that the constraint evaluation domain is
+        // extended as needed; the column group and the number of composition columns are set
+        // afterwards, as they are validated against the resulting domain and divisors
+        let mut context = AirContext::new(trace_info, degrees, num_assertions, options);
+        for (idx, divisor) in custom_divisors {
+            context = context.set_main_transition_divisor(idx, divisor);
+        }
+        let mut context = context
+            .set_num_transition_exemptions(first.num_transition_exemptions())
+            .set_row_hash_strategy(first.row_hash_strategy());
+        if let Some(group) = first.column_group() {
+            let group = ColumnGroup::new(column_group_columns, group.blowup_factor());
+            context = context.set_column_group(group);
+        }
+        if let Some(num_columns) = first.fixed_num_composition_columns {
+            context = context.set_num_constraint_composition_columns(num_columns);
+        }
+
+        Self {
+            context,
+            instances,
+            instance_width,
+            constraint_ranges,
+            main_periodic_column_ranges,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        // each instance is evaluated over a frame consisting of the columns of its statement; the
+        // same frame is reused for all instances so that it is allocated only once per step
+        let mut instance_frame = EvaluationFrame::new(self.instance_width);
+        for (i, instance) in self.instances.iter().enumerate() {
+            let columns = i * self.instance_width..(i + 1) * self.instance_width;
+            instance_frame.current_mut().copy_from_slice(&frame.current()[columns.clone()]);
+            instance_frame.next_mut().copy_from_slice(&frame.next()[columns]);
+            instance.evaluate_transition(
+                &instance_frame,
+                &periodic_values[self.main_periodic_column_ranges[i].clone()],
+                &mut result[self.constraint_ranges[i].clone()],
+            );
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut result = Vec::new();
+        for (i, instance) in self.instances.iter().enumerate() {
+            for mut assertion in instance.get_assertions() {
+                assertion.column += i * self.instance_width;
+                result.push(assertion);
+            }
+        }
+        result
+    }
+
+    /// Returns a commitment to public inputs of all aggregated statements if `A` commits to its
+    /// public inputs; the commitment is a hash of the commitments to public inputs of individual
+    /// statements.
+    fn get_pub_inputs_commitment<H: ElementHasher<BaseField = Self::BaseField>>(
+        pub_inputs: &Self::PublicInputs,
+    ) -> Option<H::Digest> {
+        let commitments: Option<Vec<H::Digest>> =
+            pub_inputs.inputs.iter().map(A::get_pub_inputs_commitment::<H>).collect();
+        commitments.map(|commitments| {
+            let bytes: Vec<u8> = commitments.iter().flat_map(|c| c.as_bytes()).collect();
+            H::hash(&bytes)
+        })
+    }
+
+    fn get_periodic_columns(&self) -> Vec<PeriodicColumn<Self::BaseField>> {
+        self.instances
+            .iter()
+            .flat_map(|instance| instance.get_periodic_columns())
+            .collect()
+    }
+}
@@ -25,11 +25,32 @@ impl<E: FieldElement> AuxTraceRandElements<E> {
         Self(Vec::new())
     }
 
+    /// Returns the number of auxiliary segments for which random elements have been added.
+    pub fn num_segments(&self) -> usize {
+        self.0.len()
+    }
+
     /// Returns a list of random elements for an auxiliary segment with the specified index.
+    ///
+    /// # Panics
+    /// Panics if random elements for the specified segment have not been added.
     pub fn get_segment_elements(&self, aux_segment_idx: usize) -> &[E] {
         &self.0[aux_segment_idx]
     }
 
+    /// Returns a reader over random elements of the auxiliary segment with the specified index.
+    ///
+    /// This is the preferred way of accessing segment randomness when it is shared by several
+    /// arguments (e.g., a permutation argument followed by a LogUp challenge), as the reader hands
+    /// out elements in the order in which they were allocated rather than relying on manual
+    /// index arithmetic.
+    ///
+    /// # Panics
+    /// Panics if random elements for the specified segment have not been added.
+    pub fn segment_reader(&self, aux_segment_idx: usize) -> AuxRandElementsReader<'_, E> {
+        AuxRandElementsReader::new(self.get_segment_elements(aux_segment_idx))
+    }
+
     /// Adds random elements for a new auxiliary segment to this set of random elements.
     pub fn add_segment_elements(&mut self, rand_elements: Vec<E>) {
         self.0.push(rand_elements);
@@ -42,6 +63,75 @@ impl<E: FieldElement> Default for AuxTraceRandElements<E> {
     }
 }
 
+// AUXILIARY SEGMENT RANDOMNESS READER
+// ================================================================================================
+
+/// Sequential reader over random elements of a single auxiliary trace segment.
+///
+/// Arguments which require randomness (e.g., permutation arguments, range checks, or LogUp
+/// challenges) consume elements from the reader in the same order in which they declared them
+/// when the number of random elements for the segment was computed. The same reader can be used
+/// both when building an auxiliary trace segment and when evaluating constraints against it,
+/// which guarantees that both sides agree on which element is used for which purpose.
+///
+/// The reader also makes sure that the segment randomness is consumed exactly as declared:
+/// reading past the end of the available elements panics, and [AuxRandElementsReader::finish()]
+/// can be used to assert that no elements were left unused.
+#[derive(Debug, Clone)]
+pub struct AuxRandElementsReader<'a, E: FieldElement> {
+    elements: &'a [E],
+    pos: usize,
+}
+
+impl<'a, E: FieldElement> AuxRandElementsReader<'a, E> {
+    /// Returns a new reader over the provided random elements.
+    pub fn new(elements: &'a [E]) -> Self {
+        Self { elements, pos: 0 }
+    }
+
+    /// Returns the next random element.
+    ///
+    /// # Panics
+    /// Panics if all random elements have already been read.
+    pub fn read_element(&mut self) -> E {
+        self.read_elements(1)[0]
+    }
+
+    /// Returns the next `num_elements` random elements.
+    ///
+    /// # Panics
+    /// Panics if fewer than `num_elements` random elements remain in the reader.
+    pub fn read_elements(&mut self, num_elements: usize) -> &'a [E] {
+        assert!(
+            num_elements <= self.num_remaining(),
+            "expected at least {} remaining random elements, but only {} remain",
+            num_elements,
+            self.num_remaining()
+        );
+        let result = &self.elements[self.pos..self.pos + num_elements];
+        self.pos += num_elements;
+        result
+    }
+
+    /// Returns the number of random elements which have not been read yet.
+    pub fn num_remaining(&self) -> usize {
+        self.elements.len() - self.pos
+    }
+
+    /// Consumes this reader.
+    ///
+    /// # Panics
+    /// Panics if not all random elements have been read.
+    pub fn finish(self) {
+        assert_eq!(
+            0,
+            self.num_remaining(),
+            "{} random elements were not consumed",
+            self.num_remaining()
+        );
+    }
+}
+
 // CONSTRAINT COMPOSITION COEFFICIENTS
 // ================================================================================================
 /// Coefficients used in construction of constraint composition polynomial.
@@ -100,13 +190,13 @@ pub struct ConstraintCompositionCoefficients<E: FieldElement> {
 /// https://eprint.iacr.org/2022/1216 and it relies on two points:
 ///
 /// 1. The evaluation proofs for each trace polynomial at $z$ and $g \cdot z$ can be batched using
-/// the non-normalized Lagrange kernel over the set $\{z, g \cdot z\}$. This, however, requires
-/// that the FRI protocol is run with rate $\rho^{+} := \frac{\kappa + 2}{\nu}$ where $\kappa$ and
-/// $\nu$ are the length of the execution trace and the LDE domain size, respectively.
+///    the non-normalized Lagrange kernel over the set $\{z, g \cdot z\}$. This, however, requires
+///    that the FRI protocol is run with rate $\rho^{+} := \frac{\kappa + 2}{\nu}$ where $\kappa$ and
+///    $\nu$ are the length of the execution trace and the LDE domain size, respectively.
 /// 2. The resulting $Y(x)$ do not need to be degree adjusted but the soundness error of the
-/// protocol needs to be updated. For most combinations of batching parameters, this leads to a
-/// negligible increase in soundness error. The formula for the updated error can be found in
-/// Theorem 8 of https://eprint.iacr.org/2022/1216.
+///    protocol needs to be updated. For most combinations of batching parameters, this leads to a
+///    negligible increase in soundness error. The formula for the updated error can be found in
+///    Theorem 8 of https://eprint.iacr.org/2022/1216.
 #[derive(Debug, Clone)]
 pub struct DeepCompositionCoefficients<E: FieldElement> {
     /// Trace polynomial composition coefficients $\alpha_i$.
@@ -0,0 +1,77 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use utils::collections::Vec;
+
+// COLUMN GROUP
+// ================================================================================================
+/// A group of main trace segment columns committed to at a lower blowup factor than the rest of
+/// the execution trace.
+///
+/// Columns which are constrained only by low-degree constraints (e.g., bookkeeping columns) do not
+/// need to be extended over the full LDE domain for constraint evaluation. Such columns can be
+/// placed into a column group: the prover then extends them over a smaller domain (defined by the
+/// blowup factor of the group), commits to them in a separate Merkle tree, and proves their
+/// low-degree via a separate FRI instance. To retain the same security level, the group is
+/// queried at more positions than the rest of the trace (see
+/// [AirContext::num_column_group_queries()](crate::AirContext::num_column_group_queries)).
+///
+/// A column group can be attached to an AIR via
+/// [AirContext::set_column_group()](crate::AirContext::set_column_group).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnGroup {
+    columns: Vec<usize>,
+    blowup_factor: usize,
+}
+
+impl ColumnGroup {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new column group consisting of the main trace columns at the specified indexes
+    /// and extended using the specified blowup factor.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `columns` is empty or column indexes are not in strictly increasing order.
+    /// * `blowup_factor` is not a power of two or is smaller than 2.
+    pub fn new(columns: Vec<usize>, blowup_factor: usize) -> Self {
+        assert!(!columns.is_empty(), "a column group must contain at least one column");
+        assert!(
+            columns.windows(2).all(|pair| pair[0] < pair[1]),
+            "column group indexes must be in strictly increasing order"
+        );
+        assert!(
+            blowup_factor.is_power_of_two() && blowup_factor >= 2,
+            "column group blowup factor must be a power of two greater than 1, but was {blowup_factor}"
+        );
+        ColumnGroup {
+            columns,
+            blowup_factor,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns indexes of the main trace columns in this group.
+    pub fn columns(&self) -> &[usize] {
+        &self.columns
+    }
+
+    /// Returns the number of columns in this group.
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the blowup factor used to extend the columns of this group.
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+
+    /// Returns true if the main trace column at the specified index belongs to this group.
+    pub fn contains(&self, column: usize) -> bool {
+        self.columns.binary_search(&column).is_ok()
+    }
+}
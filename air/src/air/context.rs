@@ -3,10 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{
+    air::TransitionConstraintDegree, deep, ColumnGroup, ConstraintDivisor, ProofOptions,
+    RowHashStrategy, TraceInfo,
+};
 use core::cmp;
-use math::StarkField;
-use utils::collections::Vec;
+use fri::FriOptions;
+use math::{FieldElement, StarkField};
+use utils::collections::{BTreeMap, Vec};
 
 // AIR CONTEXT
 // ================================================================================================
@@ -23,6 +27,10 @@ pub struct AirContext<B: StarkField> {
     pub(super) trace_domain_generator: B,
     pub(super) lde_domain_generator: B,
     pub(super) num_transition_exemptions: usize,
+    pub(super) custom_transition_divisors: BTreeMap<usize, ConstraintDivisor<B>>,
+    pub(super) column_group: Option<ColumnGroup>,
+    pub(super) fixed_num_composition_columns: Option<usize>,
+    pub(super) row_hash_strategy: RowHashStrategy,
 }
 
 impl<B: StarkField> AirContext<B> {
@@ -36,12 +44,16 @@ impl<B: StarkField> AirContext<B> {
     /// [Air::evaluate_transition()](crate::Air::evaluate_transition) function are expected to be
     /// in the order defined by this list.
     ///
+    /// The blowup factor of the constraint evaluation domain is derived from the degrees of
+    /// transition constraints, and may be greater than the blowup factor specified by the provided
+    /// `options`. In such cases, the prover evaluates constraints over a domain larger than the
+    /// LDE domain, while the trace and constraint commitments and the FRI protocol are still
+    /// computed over the LDE domain.
+    ///
     /// # Panics
     /// Panics if
     /// * `transition_constraint_degrees` is an empty vector.
     /// * `num_assertions` is zero.
-    /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
-    ///   of the specified transition constraints.
     /// * `trace_info` describes a multi-segment execution trace.
     pub fn new(
         trace_info: TraceInfo,
@@ -83,8 +95,6 @@ impl<B: StarkField> AirContext<B> {
     /// * `trace_info.is_multi_segment() == false` but:
     ///   - `aux_transition_constraint_degrees` is a non-empty vector.
     ///   - `num_aux_assertions` is greater than zero.
-    /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
-    ///   of the specified transition constraints.
     pub fn new_multi_segment(
         trace_info: TraceInfo,
         main_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -134,13 +144,6 @@ impl<B: StarkField> AirContext<B> {
             }
         }
 
-        assert!(
-            options.blowup_factor() >= ce_blowup_factor,
-            "blowup factor too small; expected at least {}, but was {}",
-            ce_blowup_factor,
-            options.blowup_factor()
-        );
-
         let trace_length = trace_info.length();
         let lde_domain_size = trace_length * options.blowup_factor();
 
@@ -155,6 +158,10 @@ impl<B: StarkField> AirContext<B> {
             trace_domain_generator: B::get_root_of_unity(trace_length.ilog2()),
             lde_domain_generator: B::get_root_of_unity(lde_domain_size.ilog2()),
             num_transition_exemptions: 1,
+            custom_transition_divisors: BTreeMap::new(),
+            column_group: None,
+            fixed_num_composition_columns: None,
+            row_hash_strategy: RowHashStrategy::default(),
         }
     }
 
@@ -227,11 +234,67 @@ impl<B: StarkField> AirContext<B> {
         self.num_transition_exemptions
     }
 
-    /// Returns the number of columns needed to store the constraint composition polynomial.
+    /// Returns the divisor of the transition constraint at the specified index.
+    ///
+    /// The index is zero-based across all transition constraints: constraints placed against the
+    /// main trace segment come first, followed by constraints placed against auxiliary trace
+    /// segments. Unless a custom divisor was set for the constraint via
+    /// [AirContext::set_main_transition_divisor()] or [AirContext::set_aux_transition_divisor()],
+    /// this returns the default transition constraint divisor (see
+    /// [ConstraintDivisor::from_transition()]).
+    ///
+    /// # Panics
+    /// Panics if `constraint_idx` is greater than or equal to the number of transition
+    /// constraints.
+    pub fn get_transition_constraint_divisor(&self, constraint_idx: usize) -> ConstraintDivisor<B> {
+        assert!(
+            constraint_idx < self.num_transition_constraints(),
+            "constraint index must be smaller than {}, but was {}",
+            self.num_transition_constraints(),
+            constraint_idx
+        );
+        match self.custom_transition_divisors.get(&constraint_idx) {
+            Some(divisor) => divisor.clone(),
+            None => {
+                ConstraintDivisor::from_transition(self.trace_len(), self.num_transition_exemptions)
+            }
+        }
+    }
+
+    /// Returns true if a custom divisor was set for at least one of the transition constraints.
+    pub fn has_custom_transition_divisors(&self) -> bool {
+        !self.custom_transition_divisors.is_empty()
+    }
+
+    /// Returns the number of columns into which the constraint composition polynomial is split.
+    ///
+    /// Unless a fixed number of columns was set via
+    /// [set_num_constraint_composition_columns()](Self::set_num_constraint_composition_columns),
+    /// this is equal to [min_num_constraint_composition_columns()](Self::min_num_constraint_composition_columns).
+    pub fn num_constraint_composition_columns(&self) -> usize {
+        self.fixed_num_composition_columns
+            .unwrap_or_else(|| self.min_num_constraint_composition_columns())
+    }
+
+    /// Returns the number of field elements drawn from the public coin to derive constraint
+    /// composition and DEEP composition coefficients.
+    ///
+    /// This depends on the [BatchingMethod](crate::BatchingMethod) specified in proof options,
+    /// and can be used to audit transcript costs (e.g., of recursive verifiers) before any proofs
+    /// are generated.
+    pub fn num_coefficient_draws(&self) -> usize {
+        let batching_method = self.options.batching_method();
+        let num_constraints = self.num_transition_constraints() + self.num_assertions();
+        let num_deep_terms = self.trace_info.width() + self.num_constraint_composition_columns();
+        batching_method.num_draws(num_constraints) + batching_method.num_draws(num_deep_terms)
+    }
+
+    /// Returns the minimum number of columns needed to store the constraint composition
+    /// polynomial.
     ///
     /// This is the maximum of:
     /// 1. The maximum evaluation degree over all transition constraints minus the degree
-    /// of the transition constraint divisor divided by trace length.
+    ///    of the divisor of the respective transition constraint divided by trace length.
     /// 2. `1`, because the constraint composition polynomial requires at least one column.
     ///
     /// Since the degree of a constraint `C(x)` can be well approximated by
@@ -248,32 +311,189 @@ impl<B: StarkField> AirContext<B> {
     /// This means that if the highest constraint degree is equal to `5`, the constraint
     /// composition polynomial will require four columns and if the highest constraint degree is
     /// equal to `7`, it will require six columns to store.
-    pub fn num_constraint_composition_columns(&self) -> usize {
-        let mut highest_constraint_degree = 0_usize;
-        for degree in self
+    pub fn min_num_constraint_composition_columns(&self) -> usize {
+        let trace_length = self.trace_len();
+        let default_divisor_degree = trace_length - self.num_transition_exemptions();
+
+        let mut highest_quotient_degree = 0_usize;
+        for (i, degree) in self
             .main_transition_constraint_degrees
             .iter()
             .chain(self.aux_transition_constraint_degrees.iter())
+            .enumerate()
         {
-            let eval_degree = degree.get_evaluation_degree(self.trace_len());
-            if eval_degree > highest_constraint_degree {
-                highest_constraint_degree = eval_degree
+            let eval_degree = degree.get_evaluation_degree(trace_length);
+            let divisor_degree = match self.custom_transition_divisors.get(&i) {
+                Some(divisor) => divisor.degree(),
+                None => default_divisor_degree,
+            };
+            let quotient_degree = eval_degree - divisor_degree;
+            if quotient_degree > highest_quotient_degree {
+                highest_quotient_degree = quotient_degree
             }
         }
-        let trace_length = self.trace_len();
-        let transition_divisior_degree = trace_length - self.num_transition_exemptions();
 
         // we use the identity: ceil(a/b) = (a + b - 1)/b
-        let num_constraint_col =
-            (highest_constraint_degree - transition_divisior_degree + trace_length - 1)
-                / trace_length;
+        let num_constraint_col = (highest_quotient_degree + trace_length - 1) / trace_length;
 
         cmp::max(num_constraint_col, 1)
     }
 
+    /// Returns the group of main trace columns committed to at a lower blowup factor, if one was
+    /// set for this context.
+    pub fn column_group(&self) -> Option<&ColumnGroup> {
+        self.column_group.as_ref()
+    }
+
+    /// Returns the size of the domain over which columns of the column group are extended, or
+    /// None if no column group was set for this context.
+    ///
+    /// This is equal to `trace_length * column_group_blowup_factor`.
+    pub fn column_group_domain_size(&self) -> Option<usize> {
+        self.column_group
+            .as_ref()
+            .map(|group| self.trace_info.length() * group.blowup_factor())
+    }
+
+    /// Returns the number of queries against the column group domain, or None if no column group
+    /// was set for this context.
+    ///
+    /// Each query against the column group domain contributes fewer bits of security than a query
+    /// against the LDE domain (since the group is extended using a smaller blowup factor). Thus,
+    /// the number of queries is increased to `ceil(num_queries * log2(b) / log2(b_g))`, where `b`
+    /// is the blowup factor specified by the proof options and `b_g` is the blowup factor of the
+    /// column group.
+    pub fn num_column_group_queries(&self) -> Option<usize> {
+        self.column_group.as_ref().map(|group| {
+            let query_bits =
+                self.options.num_queries() * self.options.blowup_factor().ilog2() as usize;
+            let bits_per_query = group.blowup_factor().ilog2() as usize;
+            (query_bits + bits_per_query - 1) / bits_per_query
+        })
+    }
+
+    /// Returns options of the FRI instance used to prove low-degree of the column group, or None
+    /// if no column group was set for this context.
+    ///
+    /// These are the same as the FRI options implied by the proof options, except that the blowup
+    /// factor is set to the blowup factor of the column group.
+    pub fn column_group_fri_options(&self) -> Option<FriOptions> {
+        self.column_group.as_ref().map(|group| {
+            let fri_options = self.options.to_fri_options();
+            FriOptions::new(
+                group.blowup_factor(),
+                fri_options.folding_factor(),
+                fri_options.remainder_max_degree(),
+            )
+        })
+    }
+
+    /// Returns the strategy used to hash rows of the extended execution trace into leaves of trace
+    /// commitment Merkle trees.
+    pub fn row_hash_strategy(&self) -> RowHashStrategy {
+        self.row_hash_strategy
+    }
+
+    /// Returns true if `z` can be used as the out-of-domain point for this computation.
+    ///
+    /// A point is rejected if it lies in the trace domain, in the LDE domain, or in the column
+    /// group domain (see [deep::is_valid_ood_point()](crate::deep::is_valid_ood_point)), or if
+    /// any of the custom transition constraint divisors vanishes at it.
+    pub fn is_valid_ood_point<E: FieldElement<BaseField = B>>(&self, z: E) -> bool {
+        // the LDE and column group domains are cosets of subgroups shifted by the same offset;
+        // thus, the smaller one is contained in the larger one
+        let domain_size =
+            cmp::max(self.lde_domain_size(), self.column_group_domain_size().unwrap_or(0));
+        let offset = self.options.domain_offset();
+        deep::is_valid_ood_point(z, self.trace_len(), domain_size, offset)
+            && self.custom_transition_divisors.values().all(|divisor| !divisor.vanishes_at(z))
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Sets a group of main trace columns to be committed to at a lower blowup factor than the
+    /// rest of the execution trace.
+    ///
+    /// See [ColumnGroup] for more info.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Any of the columns in the group is not a column of the main trace segment.
+    /// * The group contains all columns of the main trace segment.
+    /// * The blowup factor of the group is not smaller than the blowup factor specified by the
+    ///   options of this context.
+    /// * The blowup factor of the group is smaller than the blowup factor needed to evaluate
+    ///   transition constraints of this context.
+    /// * The column group domain is too small to accommodate the required number of queries.
+    pub fn set_column_group(mut self, group: ColumnGroup) -> Self {
+        let main_trace_width = self.trace_info.layout().main_trace_width();
+        assert!(
+            group.columns().iter().all(|&column| column < main_trace_width),
+            "column group indexes must be smaller than main trace width {main_trace_width}"
+        );
+        assert!(
+            group.num_columns() < main_trace_width,
+            "a column group cannot contain all columns of the main trace segment"
+        );
+        assert!(
+            group.blowup_factor() < self.options.blowup_factor(),
+            "column group blowup factor must be smaller than {}, but was {}",
+            self.options.blowup_factor(),
+            group.blowup_factor()
+        );
+        assert!(
+            group.blowup_factor() >= self.ce_blowup_factor,
+            "column group blowup factor must be at least {}, but was {}",
+            self.ce_blowup_factor,
+            group.blowup_factor()
+        );
+
+        self.column_group = Some(group);
+        let num_queries = self.num_column_group_queries().expect("column group not set");
+        let domain_size = self.column_group_domain_size().expect("column group not set");
+        assert!(
+            num_queries < domain_size,
+            "column group domain of {domain_size} elements is too small for {num_queries} queries"
+        );
+        self
+    }
+
+    /// Sets the strategy used to hash rows of the extended execution trace into leaves of trace
+    /// commitment Merkle trees.
+    ///
+    /// See [RowHashStrategy] for more info.
+    ///
+    /// # Panics
+    /// Panics if the strategy is [RowHashStrategy::ColumnGroups] with group size of zero.
+    pub fn set_row_hash_strategy(mut self, strategy: RowHashStrategy) -> Self {
+        if let RowHashStrategy::ColumnGroups(group_size) = strategy {
+            assert!(group_size > 0, "column group size must be greater than zero");
+        }
+        self.row_hash_strategy = strategy;
+        self
+    }
+
+    /// Sets the number of columns into which the constraint composition polynomial is split.
+    ///
+    /// By default, the composition polynomial is split into the smallest number of columns
+    /// needed to store it. Verifiers which expect a fixed number of composition columns (e.g.,
+    /// recursive verifier circuits) can use this method to pad the composition polynomial with
+    /// columns of zero polynomials; each additional column increases the size of the proof by
+    /// one field element per query.
+    ///
+    /// Since transition exemptions and custom transition divisors may increase the minimum number
+    /// of composition columns, this method should be called after these are set.
+    ///
+    /// # Panics
+    /// Panics if `n` is smaller than the minimum number of columns needed to store the constraint
+    /// composition polynomial.
+    pub fn set_num_constraint_composition_columns(mut self, n: usize) -> Self {
+        self.fixed_num_composition_columns = Some(n);
+        self.check_num_constraint_composition_columns();
+        self
+    }
+
     /// Sets the number of transition exemptions for this context.
     ///
     /// # Panics
@@ -283,6 +503,8 @@ impl<B: StarkField> AirContext<B> {
     /// * Given the combination of transition constraints degrees and the blowup factor in this
     ///   context, the number of exemptions is too larger for a valid computation of the constraint
     ///   composition polynomial.
+    /// * A fixed number of constraint composition columns was set, and it is too small to store
+    ///   the resulting constraint composition polynomial.
     pub fn set_num_transition_exemptions(mut self, n: usize) -> Self {
         assert!(n > 0, "number of transition exemptions must be greater than zero");
         // exemptions which are for more than half the trace plus one are probably a mistake
@@ -299,11 +521,16 @@ impl<B: StarkField> AirContext<B> {
         // degree of the divisor which results in an increase of the resulting constraint composition
         // polynomial.Thus we need to check that the number of exemption points is not too large
         // given the above.
-        for degree in self
+        for (i, degree) in self
             .main_transition_constraint_degrees
             .iter()
             .chain(self.aux_transition_constraint_degrees.iter())
+            .enumerate()
         {
+            // exemptions do not affect constraints with custom divisors
+            if self.custom_transition_divisors.contains_key(&i) {
+                continue;
+            }
             let eval_degree = degree.get_evaluation_degree(self.trace_len());
             let max_constraint_composition_degree = self.ce_domain_size() - 1;
             let max_exemptions = max_constraint_composition_degree + self.trace_len() - eval_degree;
@@ -314,6 +541,132 @@ impl<B: StarkField> AirContext<B> {
         }
 
         self.num_transition_exemptions = n;
+        self.check_num_constraint_composition_columns();
+        self
+    }
+
+    /// Sets a custom divisor for the transition constraint placed against the main trace segment
+    /// at the specified index.
+    ///
+    /// By default, all transition constraints must hold on all steps of the execution trace
+    /// except for the last few exempted steps. A custom divisor can be used to specify that a
+    /// constraint must hold only on a subset of steps (e.g., on every 16th step) - see
+    /// [ConstraintDivisor::from_periodic_steps()]. This removes the need to guard such
+    /// constraints with selector columns, and thus, does not increase constraint degrees.
+    ///
+    /// Custom divisors are not affected by the number of transition exemptions.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `constraint_idx` is greater than or equal to the number of main transition constraints.
+    /// * The numerator of the divisor consists of more than one term.
+    /// * Degree of the divisor is greater than the evaluation degree of the constraint.
+    /// * A fixed number of constraint composition columns was set, and it is too small to store
+    ///   the resulting constraint composition polynomial.
+    pub fn set_main_transition_divisor(
+        self,
+        constraint_idx: usize,
+        divisor: ConstraintDivisor<B>,
+    ) -> Self {
+        assert!(
+            constraint_idx < self.num_main_transition_constraints(),
+            "main transition constraint index must be smaller than {}, but was {}",
+            self.num_main_transition_constraints(),
+            constraint_idx
+        );
+        self.set_transition_divisor(constraint_idx, divisor)
+    }
+
+    /// Sets a custom divisor for the transition constraint placed against auxiliary trace segments
+    /// at the specified index.
+    ///
+    /// The index is zero-based across all auxiliary transition constraints. See
+    /// [AirContext::set_main_transition_divisor()] for more info.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `constraint_idx` is greater than or equal to the number of auxiliary transition
+    ///   constraints.
+    /// * The numerator of the divisor consists of more than one term.
+    /// * Degree of the divisor is greater than the evaluation degree of the constraint.
+    /// * A fixed number of constraint composition columns was set, and it is too small to store
+    ///   the resulting constraint composition polynomial.
+    pub fn set_aux_transition_divisor(
+        self,
+        constraint_idx: usize,
+        divisor: ConstraintDivisor<B>,
+    ) -> Self {
+        assert!(
+            constraint_idx < self.num_aux_transition_constraints(),
+            "auxiliary transition constraint index must be smaller than {}, but was {}",
+            self.num_aux_transition_constraints(),
+            constraint_idx
+        );
+        let constraint_idx = self.num_main_transition_constraints() + constraint_idx;
+        self.set_transition_divisor(constraint_idx, divisor)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets a custom divisor for a transition constraint at the specified index; the index is
+    /// zero-based across all transition constraints.
+    fn set_transition_divisor(
+        mut self,
+        constraint_idx: usize,
+        divisor: ConstraintDivisor<B>,
+    ) -> Self {
+        assert_eq!(
+            divisor.numerator().len(),
+            1,
+            "custom transition constraint divisors must have a single-term numerator"
+        );
+
+        let trace_length = self.trace_len();
+        let eval_degree = self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .nth(constraint_idx)
+            .expect("invalid constraint index")
+            .get_evaluation_degree(trace_length);
+        assert!(
+            divisor.degree() <= eval_degree,
+            "divisor degree cannot exceed constraint evaluation degree {}, but was {}",
+            eval_degree,
+            divisor.degree()
+        );
+
+        // make sure the constraint evaluation domain is large enough to interpolate the quotient
+        // of the constraint by its divisor; we use the identity: ceil(a/b) = (a + b - 1)/b
+        let quotient_degree = eval_degree - divisor.degree();
+        let min_blowup_factor = (quotient_degree + trace_length) / trace_length;
+        let min_blowup_factor =
+            cmp::max(min_blowup_factor.next_power_of_two(), ProofOptions::MIN_BLOWUP_FACTOR);
+        self.ce_blowup_factor = cmp::max(self.ce_blowup_factor, min_blowup_factor);
+        if let Some(group) = self.column_group.as_ref() {
+            assert!(
+                group.blowup_factor() >= self.ce_blowup_factor,
+                "column group blowup factor too small; expected at least {}, but was {}",
+                self.ce_blowup_factor,
+                group.blowup_factor()
+            );
+        }
+
+        self.custom_transition_divisors.insert(constraint_idx, divisor);
+        self.check_num_constraint_composition_columns();
         self
     }
+
+    /// Makes sure that the fixed number of composition columns (if one was set) is sufficient to
+    /// store the constraint composition polynomial.
+    fn check_num_constraint_composition_columns(&self) {
+        if let Some(n) = self.fixed_num_composition_columns {
+            let min_num_columns = self.min_num_constraint_composition_columns();
+            assert!(
+                n >= min_num_columns,
+                "number of constraint composition columns must be at least {min_num_columns}, but was {n}"
+            );
+        }
+    }
 }
@@ -5,7 +5,7 @@
 
 use crate::air::Assertion;
 use core::fmt::{Display, Formatter};
-use math::{FieldElement, StarkField};
+use math::{polynom::SparsePolynomial, FieldElement, StarkField};
 use utils::collections::Vec;
 
 // CONSTRAINT DIVISOR
@@ -21,7 +21,9 @@ use utils::collections::Vec;
 /// numerator: `[(a, 1), (b, 2)]`, exemptions: `[3]`.
 ///
 /// A divisor cannot be instantiated directly, and instead must be created either for an
-/// [Assertion] or for a transition constraint.
+/// [Assertion] or for a transition constraint. Transition constraints which need to hold only on
+/// a subset of steps (e.g., on every 16th step) can use divisors built via
+/// [ConstraintDivisor::from_periodic_steps()].
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConstraintDivisor<B: StarkField> {
     pub(super) numerator: Vec<(usize, B)>,
@@ -64,6 +66,44 @@ impl<B: StarkField> ConstraintDivisor<B> {
         Self::new(vec![(trace_length, B::ONE)], exemptions)
     }
 
+    /// Builds a divisor for transition constraints which must hold only on steps `first_step`,
+    /// `first_step + stride`, `first_step + 2 * stride` etc. of the execution trace.
+    ///
+    /// The divisor polynomial is defined as:
+    ///
+    /// $$
+    /// z(x) = x^k - g^{a \cdot k}
+    /// $$
+    ///
+    /// where $g$ is the generator of the trace domain, $k = n / stride$ is the number of steps on
+    /// which the constraints must hold, $n$ is the length of the execution trace, and $a$ is the
+    /// `first_step`. In other words, the constraints must hold on a coset of the subgroup of size
+    /// $k$ of the trace domain.
+    ///
+    /// Such divisors can be attached to individual transition constraints via
+    /// [AirContext::set_main_transition_divisor()](crate::AirContext::set_main_transition_divisor)
+    /// and [AirContext::set_aux_transition_divisor()](crate::AirContext::set_aux_transition_divisor)
+    /// methods.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `stride` is not a power of two, or is greater than `trace_length`.
+    /// * `first_step` is greater than or equal to `stride`.
+    pub fn from_periodic_steps(trace_length: usize, first_step: usize, stride: usize) -> Self {
+        assert!(stride.is_power_of_two(), "stride must be a power of two, but was {stride}");
+        assert!(
+            stride <= trace_length,
+            "stride cannot exceed trace length {trace_length}, but was {stride}"
+        );
+        assert!(
+            first_step < stride,
+            "first step must be smaller than stride {stride}, but was {first_step}"
+        );
+        let num_steps = trace_length / stride;
+        let offset = get_trace_domain_value_at::<B>(trace_length, num_steps * first_step);
+        Self::new(vec![(num_steps, offset)], vec![])
+    }
+
     /// Builds a divisor for a boundary constraint described by the assertion.
     ///
     /// For boundary constraints, the divisor polynomial is defined as:
@@ -109,6 +149,14 @@ impl<B: StarkField> ConstraintDivisor<B> {
         &self.numerator
     }
 
+    /// Returns the numerator of this constraint divisor expanded into a sparse polynomial.
+    ///
+    /// The numerator is a product of terms $(x^a - b)$, and thus, even for divisors of a very
+    /// high degree, the expanded polynomial has only a few non-zero coefficients.
+    pub fn numerator_poly(&self) -> SparsePolynomial<B> {
+        SparsePolynomial::from_binomials(&self.numerator)
+    }
+
     /// Returns exemption points (the denominator portion) of this constraints divisor.
     pub fn exemptions(&self) -> &[B] {
         &self.exemptions
@@ -128,7 +176,7 @@ impl<B: StarkField> ConstraintDivisor<B> {
         // compute the numerator value
         let mut numerator = E::ONE;
         for (degree, constant) in self.numerator.iter() {
-            let v = x.exp((*degree as u32).into());
+            let v = x.exp((*degree as u64).into());
             let v = v - E::from(*constant);
             numerator *= v;
         }
@@ -139,6 +187,18 @@ impl<B: StarkField> ConstraintDivisor<B> {
         numerator / denominator
     }
 
+    /// Returns true if the divisor polynomial evaluates to zero at the provided `x` coordinate.
+    ///
+    /// That is, returns true if the numerator of the divisor evaluates to zero at `x`, and `x` is
+    /// not one of the exemption points.
+    pub fn vanishes_at<E: FieldElement<BaseField = B>>(&self, x: E) -> bool {
+        let numerator_is_zero = self
+            .numerator
+            .iter()
+            .any(|&(degree, constant)| x.exp((degree as u64).into()) == E::from(constant));
+        numerator_is_zero && self.exemptions.iter().all(|&e| x != E::from(e))
+    }
+
     /// Evaluates the denominator of this divisor (the exemption points) at the provided `x`
     /// coordinate.
     #[inline(always)]
@@ -226,6 +286,70 @@ mod tests {
         assert_eq!(expected, div.evaluate_at(BaseElement::new(4)));
     }
 
+    #[test]
+    fn constraint_divisor_numerator_poly() {
+        // (x^4 - 1) * (x^2 - 2) = x^6 - 2 * x^4 - x^2 + 2
+        let div = ConstraintDivisor::new(
+            vec![(4, BaseElement::ONE), (2, BaseElement::new(2))],
+            vec![BaseElement::ONE],
+        );
+        let poly = div.numerator_poly();
+        assert_eq!(
+            &[
+                (0, BaseElement::new(2)),
+                (2, -BaseElement::ONE),
+                (4, -BaseElement::new(2)),
+                (6, BaseElement::ONE)
+            ],
+            poly.terms()
+        );
+
+        let x = BaseElement::new(5);
+        assert_eq!(div.evaluate_at(x) * div.evaluate_exemptions_at(x), poly.eval(x));
+    }
+
+    #[test]
+    fn constraint_divisor_from_periodic_steps() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // constraints should hold on steps 3, 7, 11, 15
+        let divisor = ConstraintDivisor::<BaseElement>::from_periodic_steps(n, 3, 4);
+        assert_eq!(4, divisor.degree());
+        assert_eq!(vec![(4, g.exp(12))], divisor.numerator());
+
+        for i in 0..n {
+            let x = g.exp((i as u64).into());
+            assert_eq!(i % 4 == 3, divisor.vanishes_at(x));
+        }
+
+        // transition divisors should not vanish at exemption points
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(n, 2);
+        for i in 0..n {
+            let x = g.exp((i as u64).into());
+            assert_eq!(i < n - 2, divisor.vanishes_at(x));
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn constraint_divisor_long_trace() {
+        // trace lengths above 2^32 require exponents which do not fit into 32 bits
+        let log_n = 36;
+        let n = 1_usize << log_n;
+        let g = BaseElement::get_root_of_unity(log_n);
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(n, 1);
+        assert_eq!(vec![g.exp((n as u64 - 1).into())], divisor.exemptions);
+
+        // compute x^n via repeated squaring to avoid relying on exponentiation by n
+        let x = BaseElement::new(5);
+        let x_n = (0..log_n).fold(x, |acc, _| acc.square());
+        assert_eq!((x_n - BaseElement::ONE) / (x - divisor.exemptions[0]), divisor.evaluate_at(x));
+
+        assert!(divisor.vanishes_at(g));
+        assert!(!divisor.vanishes_at(divisor.exemptions[0]));
+    }
+
     #[test]
     fn constraint_divisor_equivalence() {
         let n = 8_usize;
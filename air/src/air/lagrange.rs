@@ -0,0 +1,149 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ConstraintDivisor;
+use math::{polynom, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// LAGRANGE KERNEL EVALUATION FRAME
+// ================================================================================================
+/// Evaluations of a Lagrange kernel column needed to evaluate Lagrange kernel constraints at a
+/// single point.
+///
+/// A Lagrange kernel column contains evaluations of $eq(x, r)$ over the boolean hypercube, where
+/// $x_i$ is the $i$-th least significant bit of the row index and $r$ is a vector of $v$ random
+/// elements; thus, the length of the column is $2^v$. For a point $z$, the frame contains
+/// evaluations of the column polynomial $c(x)$ at:
+///
+/// $$
+/// z, g \cdot z, g^2 \cdot z, g^4 \cdot z, ..., g^{2^{v - 1}} \cdot z
+/// $$
+///
+/// where $g$ is the generator of the trace domain. The column is fully defined by the following
+/// constraints:
+/// * A boundary constraint $c(1) = \prod_{i = 0}^{v - 1} (1 - r_i)$ (see
+///   [lagrange_kernel_boundary_value()]).
+/// * For each $i$ in $[0, v)$, a transition constraint
+///   $(1 - r_i) \cdot c(g^{2^i} \cdot x) - r_i \cdot c(x) = 0$, which holds on all rows with
+///   indexes divisible by $2^{i + 1}$ (see [LagrangeKernelEvaluationFrame::evaluate_transitions()]
+///   and [lagrange_kernel_transition_divisor()]).
+///
+/// These helpers are exposed so that external verifiers (e.g., recursive verifiers) can replicate
+/// Lagrange kernel checks exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LagrangeKernelEvaluationFrame<E: FieldElement> {
+    frame: Vec<E>,
+}
+
+impl<E: FieldElement> LagrangeKernelEvaluationFrame<E> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new frame instantiated with the provided evaluations.
+    ///
+    /// # Panics
+    /// Panics if `frame` is empty.
+    pub fn new(frame: Vec<E>) -> Self {
+        assert!(!frame.is_empty(), "Lagrange kernel frame cannot be empty");
+        Self { frame }
+    }
+
+    /// Returns a new frame built by evaluating the Lagrange kernel column polynomial at `z` and
+    /// at $g^{2^i} \cdot z$ for all $i$ in $[0, v)$, where $g$ is the generator of the trace
+    /// domain.
+    ///
+    /// The polynomial is specified by its coefficients in reverse order; the length of the
+    /// trace (and thus, $v$) is inferred from the number of coefficients.
+    ///
+    /// # Panics
+    /// Panics if the number of coefficients is not a power of two.
+    pub fn from_lagrange_kernel_column_poly(poly: &[E], z: E) -> Self {
+        assert!(
+            poly.len().is_power_of_two(),
+            "number of polynomial coefficients must be a power of two, but was {}",
+            poly.len()
+        );
+        let log_trace_len = poly.len().ilog2();
+        let mut g = E::BaseField::get_root_of_unity(log_trace_len);
+
+        let mut frame = Vec::with_capacity(log_trace_len as usize + 1);
+        frame.push(polynom::eval(poly, z));
+        for _ in 0..log_trace_len {
+            frame.push(polynom::eval(poly, z.mul_base(g)));
+            g = g.square();
+        }
+        Self { frame }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the evaluations contained in this frame.
+    pub fn inner(&self) -> &[E] {
+        &self.frame
+    }
+
+    /// Returns the number of random elements $v$ defining the Lagrange kernel for this frame.
+    pub fn num_rand_elements(&self) -> usize {
+        self.frame.len() - 1
+    }
+
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates Lagrange kernel transition constraints against this frame and writes the results
+    /// into `result`; the value of the $i$-th constraint is:
+    ///
+    /// $$
+    /// (1 - r_i) \cdot c(g^{2^i} \cdot z) - r_i \cdot c(z)
+    /// $$
+    ///
+    /// The $i$-th constraint must be divided by [lagrange_kernel_transition_divisor()] for $i$.
+    ///
+    /// # Panics
+    /// Panics if the number of `rand_elements` or the length of `result` differs from the number
+    /// of random elements for this frame.
+    pub fn evaluate_transitions(&self, rand_elements: &[E], result: &mut [E]) {
+        let num_rand_elements = self.num_rand_elements();
+        assert_eq!(
+            num_rand_elements,
+            rand_elements.len(),
+            "expected {num_rand_elements} random elements, but received {}",
+            rand_elements.len()
+        );
+        assert_eq!(
+            num_rand_elements,
+            result.len(),
+            "expected result slice of length {num_rand_elements}, but was {}",
+            result.len()
+        );
+
+        let c = self.frame[0];
+        for ((result, &r), &c_next) in result.iter_mut().zip(rand_elements).zip(&self.frame[1..]) {
+            *result = (E::ONE - r) * c_next - r * c;
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the value of a Lagrange kernel column in the first row, which is
+/// $eq(0, r) = \prod_{i = 0}^{v - 1} (1 - r_i)$.
+pub fn lagrange_kernel_boundary_value<E: FieldElement>(rand_elements: &[E]) -> E {
+    rand_elements.iter().fold(E::ONE, |acc, &r| acc * (E::ONE - r))
+}
+
+/// Returns the divisor of the $i$-th Lagrange kernel transition constraint for a trace of the
+/// specified length; the constraint must hold on all rows with indexes divisible by $2^{i + 1}$.
+///
+/// # Panics
+/// Panics if $2^{i + 1}$ is greater than `trace_length`.
+pub fn lagrange_kernel_transition_divisor<B: StarkField>(
+    trace_length: usize,
+    constraint_idx: usize,
+) -> ConstraintDivisor<B> {
+    ConstraintDivisor::from_periodic_steps(trace_length, 0, 2 << constraint_idx)
+}
@@ -3,17 +3,31 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::ProofOptions;
-use crypto::{RandomCoin, RandomCoinError};
-use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
+use crate::{BatchingMethod, ProofOptions};
+use crypto::{ElementHasher, RandomCoin, RandomCoinError};
+use math::{
+    fft, get_power_series, polynom, ExtensibleField, ExtensionOf, FieldElement, StarkField,
+    ToElements,
+};
 use utils::collections::{BTreeMap, Vec};
 
 mod trace_info;
+#[cfg(feature = "protobuf")]
+pub(crate) use trace_info::NUM_AUX_SEGMENTS;
 pub use trace_info::{TraceInfo, TraceLayout};
 
+mod trace_meta;
+pub use trace_meta::{TraceMetaValue, TraceMetadata};
+
 mod context;
 pub use context::AirContext;
 
+mod column_group;
+pub use column_group::ColumnGroup;
+
+mod row_hash;
+pub use row_hash::RowHashStrategy;
+
 mod assertions;
 pub use assertions::Assertion;
 
@@ -21,16 +35,38 @@ mod boundary;
 pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup, BoundaryConstraints};
 
 mod transition;
-pub use transition::{EvaluationFrame, TransitionConstraintDegree, TransitionConstraints};
+pub use transition::{
+    EvaluationFrame, SelectorColumns, SelectorFlag, TransitionConstraintDegree,
+    TransitionConstraints,
+};
 
 mod coefficients;
 pub use coefficients::{
-    AuxTraceRandElements, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+    AuxRandElementsReader, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DeepCompositionCoefficients,
 };
 
 mod divisor;
 pub use divisor::ConstraintDivisor;
 
+mod periodic;
+pub use periodic::PeriodicColumn;
+
+mod permutation;
+pub use permutation::{PermutationArg, PermutationArgBuilder};
+
+mod lagrange;
+pub use lagrange::{
+    lagrange_kernel_boundary_value, lagrange_kernel_transition_divisor,
+    LagrangeKernelEvaluationFrame,
+};
+
+mod range_check;
+pub use range_check::{RangeCheck, RangeCheckBuilder, MIN_RANGE_CHECK_TRACE_LENGTH};
+
+mod aggregate;
+pub use aggregate::{AggregateAir, AggregatePublicInputs};
+
 #[cfg(test)]
 mod tests;
 
@@ -39,6 +75,10 @@ mod tests;
 
 const MIN_CYCLE_LENGTH: usize = 2;
 
+/// Maximum number of times a random value is drawn from a public coin before giving up on drawing
+/// a valid value.
+const MAX_DRAW_ATTEMPTS: usize = 1000;
+
 // AIR TRAIT
 // ================================================================================================
 /// Describes algebraic intermediate representation of a computation.
@@ -145,6 +185,30 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// step of the computation will be supplied to the [Air::evaluate_transition()] method via the
 /// `periodic_values` parameter.
 ///
+/// If a periodic column needs to start its cycle at a step other than `0`, or if it is used only
+/// by constraints against auxiliary trace segments, you can override [Air::get_periodic_columns()]
+/// method instead, and describe each column via a [PeriodicColumn] struct. Periodic columns
+/// associated with the main trace segment are supplied to both [Air::evaluate_transition()] and
+/// [Air::evaluate_aux_transition()] methods, while columns associated with auxiliary trace segments
+/// are supplied only to [Air::evaluate_aux_transition()] method. In the latter case, values of the
+/// main segment columns always come first, followed by values of the auxiliary segment columns.
+///
+/// ### Selectors
+/// Computations which execute different operations at different steps (e.g., virtual machines)
+/// usually guard transition constraints by flags derived from binary selector columns. A set of
+/// such columns can be described via [SelectorColumns] struct, which provides constraints
+/// enforcing that the columns contain only binary values, as well as a decoder for selectors
+/// packed into several columns. Guards themselves can be described via [SelectorFlag] struct,
+/// which also accounts for the increase in constraint degree caused by the guard.
+///
+/// ### Public inputs commitment
+/// By default, public inputs are serialized into field elements via [ToElements] trait, and these
+/// elements are used to seed the random coin of the protocol. For computations with large public
+/// inputs (e.g., full program bytecode), you can override [Air::get_pub_inputs_commitment()]
+/// method to hash public inputs into a single digest instead. In this case, the random coin is
+/// seeded with the proof context and then reseeded with the digest, and the verifier recomputes
+/// the digest from the public inputs it was provided with.
+///
 /// ### Randomized AIR
 /// Randomized AIR is a powerful extension of AIR which enables, among other things, multiset and
 /// permutation checks similar to the ones available in PLONKish systems. These, in turn, allow
@@ -285,6 +349,26 @@ pub trait Air: Send + Sync {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a commitment to the specified public inputs, or None if public inputs should be
+    /// used to seed the random coin of the protocol directly.
+    ///
+    /// When a commitment is returned, the random coin is seeded with the proof context and then
+    /// reseeded with the commitment; in this case, [ToElements::to_elements()] is never invoked
+    /// on the public inputs. This is useful when public inputs are large, as the commitment can
+    /// be computed in any way supported by the hash function (e.g., by hashing serialized bytes
+    /// of public inputs, or by building a Merkle tree from them).
+    ///
+    /// The default implementation of this method returns None.
+    #[allow(unused_variables)]
+    fn get_pub_inputs_commitment<H: ElementHasher<BaseField = Self::BaseField>>(
+        pub_inputs: &Self::PublicInputs,
+    ) -> Option<H::Digest>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
     /// Returns values for all periodic columns used in the computation.
     ///
     /// These values will be used to compute column values at specific states of the computation
@@ -298,18 +382,47 @@ pub trait Air: Send + Sync {
         Vec::new()
     }
 
+    /// Returns descriptions of all periodic columns used in the computation.
+    ///
+    /// The default implementation of this method converts values returned from the
+    /// [get_periodic_column_values()](Air::get_periodic_column_values) method into periodic
+    /// columns with zero offsets associated with the main trace segment. This method should be
+    /// overridden for computations which rely on shifted periodic columns, or on periodic columns
+    /// used only by auxiliary trace constraints.
+    fn get_periodic_columns(&self) -> Vec<PeriodicColumn<Self::BaseField>> {
+        self.get_periodic_column_values().into_iter().map(PeriodicColumn::new).collect()
+    }
+
+    /// Returns the number of periodic columns associated with the main trace segment.
+    ///
+    /// Polynomials for these columns are located at the beginning of the vector returned from the
+    /// [get_periodic_column_polys()](Air::get_periodic_column_polys) method.
+    fn num_main_periodic_columns(&self) -> usize {
+        self.get_periodic_columns().iter().filter(|column| !column.is_aux()).count()
+    }
+
     /// Returns polynomial for all periodic columns.
     ///
-    /// These polynomials are interpolated from the values returned from the
-    /// [get_periodic_column_values()](Air::get_periodic_column_values) method.
+    /// These polynomials are interpolated from the values of columns returned from the
+    /// [get_periodic_columns()](Air::get_periodic_columns) method, taking column offsets into
+    /// account. Polynomials for columns associated with the main trace segment come first,
+    /// followed by polynomials for columns associated with auxiliary trace segments; within each
+    /// group, the columns are ordered in the same way as they were defined.
     fn get_periodic_column_polys(&self) -> Vec<Vec<Self::BaseField>> {
         // cache inverse twiddles for each cycle length so that we don't have to re-build them
         // for columns with identical cycle lengths
         let mut twiddle_map = BTreeMap::new();
+
+        // put columns associated with the main trace segment in front of the auxiliary ones
+        let (main_columns, aux_columns): (Vec<_>, Vec<_>) =
+            self.get_periodic_columns().into_iter().partition(|column| !column.is_aux());
+
         // iterate over all periodic columns and convert column values into polynomials
-        self.get_periodic_column_values()
+        main_columns
             .into_iter()
-            .map(|mut column| {
+            .chain(aux_columns)
+            .map(|column| {
+                let mut column = column.shifted_values();
                 let cycle_length = column.len();
                 assert!(
                     cycle_length >= MIN_CYCLE_LENGTH,
@@ -367,6 +480,113 @@ pub trait Air: Send + Sync {
         )
     }
 
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates constraints of this AIR at point `z` over the specified evaluation frames.
+    ///
+    /// The returned value is a random linear combination of all transition and boundary
+    /// constraints evaluated at `z` and divided by their respective divisors, where the
+    /// coefficients of the linear combination are specified by `composition_coefficients`. This
+    /// is exactly the value which the verifier computes from the out-of-domain frames sent by the
+    /// prover, and thus, it can be used by external tools (e.g., recursive circuit generators) to
+    /// reproduce the out-of-domain consistency check.
+    ///
+    /// `aux_frame` must be provided if and only if the trace has auxiliary segments.
+    fn evaluate_constraints_at<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        z: E,
+        main_frame: &EvaluationFrame<E>,
+        aux_frame: Option<&EvaluationFrame<E>>,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        composition_coefficients: &ConstraintCompositionCoefficients<E>,
+    ) -> E {
+        self.evaluate_constraints_at_with_periodic_polys(
+            z,
+            &self.get_periodic_column_polys(),
+            main_frame,
+            aux_frame,
+            aux_rand_elements,
+            composition_coefficients,
+        )
+    }
+
+    /// Evaluates constraints of this AIR at point `z` using the provided polynomials of periodic
+    /// columns.
+    ///
+    /// This is the same as [Air::evaluate_constraints_at()], but periodic column polynomials are
+    /// expected to be precomputed via [Air::get_periodic_column_polys()] rather than interpolated
+    /// on each call.
+    fn evaluate_constraints_at_with_periodic_polys<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        z: E,
+        periodic_column_polys: &[Vec<Self::BaseField>],
+        main_frame: &EvaluationFrame<E>,
+        aux_frame: Option<&EvaluationFrame<E>>,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        composition_coefficients: &ConstraintCompositionCoefficients<E>,
+    ) -> E {
+        // 1 ----- evaluate transition constraints ------------------------------------------------
+
+        // initialize a buffer to hold transition constraint evaluations
+        let t_constraints = self.get_transition_constraints(&composition_coefficients.transition);
+
+        // compute values of periodic columns at z
+        let periodic_values = periodic_column_polys
+            .iter()
+            .map(|poly| {
+                let num_cycles = self.trace_length() / poly.len();
+                let x = z.exp_vartime((num_cycles as u64).into());
+                polynom::eval(poly, x)
+            })
+            .collect::<Vec<_>>();
+
+        // evaluate transition constraints for the main trace segment; only values of periodic
+        // columns associated with the main segment are passed to these constraints
+        let num_main_periodic_columns = self.num_main_periodic_columns();
+        let mut t_evaluations1 = E::zeroed_vector(t_constraints.num_main_constraints());
+        self.evaluate_transition(
+            main_frame,
+            &periodic_values[..num_main_periodic_columns],
+            &mut t_evaluations1,
+        );
+
+        // evaluate transition constraints for auxiliary trace segments (if any)
+        let mut t_evaluations2 = E::zeroed_vector(t_constraints.num_aux_constraints());
+        if let Some(aux_frame) = aux_frame {
+            self.evaluate_aux_transition(
+                main_frame,
+                aux_frame,
+                &periodic_values,
+                aux_rand_elements,
+                &mut t_evaluations2,
+            );
+        }
+
+        // merge all constraint evaluations into a single value by computing their random linear
+        // combination using the composition coefficients. this also divides the result by the
+        // divisor of transition constraints.
+        let mut result =
+            t_constraints.combine_evaluations::<E>(&t_evaluations1, &t_evaluations2, z);
+
+        // 2 ----- evaluate boundary constraints --------------------------------------------------
+
+        // get boundary constraints grouped by common divisor, evaluate constraints in each group,
+        // and add their combination to the result
+        let b_constraints =
+            self.get_boundary_constraints(aux_rand_elements, &composition_coefficients.boundary);
+        for group in b_constraints.main_constraints().iter() {
+            result += group.evaluate_at(main_frame.current(), z);
+        }
+        if let Some(aux_frame) = aux_frame {
+            for group in b_constraints.aux_constraints().iter() {
+                result += group.evaluate_at(aux_frame.current(), z);
+            }
+        }
+
+        result
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -419,7 +639,7 @@ pub trait Air: Send + Sync {
     /// hightest transition constraint degree. For example, if the hightest transition
     /// constraint degree = 3, `ce_blowup_factor` will be set to 4.
     ///
-    /// `ce_blowup_factor` is guaranteed to be smaller than or equal to the `lde_blowup_factor`.
+    /// `ce_blowup_factor` is independent of the `lde_blowup_factor`, and may be greater than it.
     fn ce_blowup_factor(&self) -> usize {
         self.context().ce_blowup_factor
     }
@@ -431,9 +651,17 @@ pub trait Air: Send + Sync {
         self.context().ce_domain_size()
     }
 
+    /// Returns the number of columns into which the constraint composition polynomial of the
+    /// computation described by this AIR is split.
+    ///
+    /// This number is fully determined by the AIR and the proof options, and thus, can be used to
+    /// size data structures (e.g., of recursive verifiers) before any proofs are generated.
+    fn num_constraint_composition_columns(&self) -> usize {
+        self.context().num_constraint_composition_columns()
+    }
+
     /// Returns low-degree extension domain blowup factor for the computation described by this
-    /// AIR. This is guaranteed to be a power of two, and is always either equal to or greater
-    /// than ce_blowup_factor.
+    /// AIR. This is guaranteed to be a power of two, but may be smaller than ce_blowup_factor.
     fn lde_blowup_factor(&self) -> usize {
         self.context().options.blowup_factor()
     }
@@ -466,7 +694,11 @@ pub trait Air: Send + Sync {
     /// Returns a vector of field elements required for construction of an auxiliary trace segment
     /// with the specified index.
     ///
-    /// The elements are drawn uniformly at random from the provided public coin.
+    /// The elements are drawn uniformly at random from the provided public coin. Zero elements
+    /// are rejected, and a new element is drawn from the coin in their place.
+    ///
+    /// When the elements are shared by several arguments, both trace builders and constraint
+    /// evaluators should consume them via an [AuxRandElementsReader] in the same order.
     fn get_aux_trace_segment_random_elements<E, R>(
         &self,
         aux_segment_idx: usize,
@@ -480,7 +712,7 @@ pub trait Air: Send + Sync {
             self.trace_info().layout().get_aux_segment_rand_elements(aux_segment_idx);
         let mut result = Vec::with_capacity(num_elements);
         for _ in 0..num_elements {
-            result.push(public_coin.draw()?);
+            result.push(draw_valid(public_coin, |element: E| element != E::ZERO)?);
         }
         Ok(result)
     }
@@ -490,6 +722,10 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combination during construction of constraint
     /// composition polynomial.
+    ///
+    /// When [BatchingMethod::Algebraic] is specified in proof options, a single value $\alpha$ is
+    /// drawn from the public coin, and the coefficients are set to $1, \alpha, \alpha^2, ...$ with
+    /// transition constraint coefficients followed by boundary constraint coefficients.
     fn get_constraint_composition_coefficients<E, R>(
         &self,
         public_coin: &mut R,
@@ -498,15 +734,14 @@ pub trait Air: Send + Sync {
         E: FieldElement<BaseField = Self::BaseField>,
         R: RandomCoin<BaseField = Self::BaseField>,
     {
-        let mut t_coefficients = Vec::new();
-        for _ in 0..self.context().num_transition_constraints() {
-            t_coefficients.push(public_coin.draw()?);
-        }
-
-        let mut b_coefficients = Vec::new();
-        for _ in 0..self.context().num_assertions() {
-            b_coefficients.push(public_coin.draw()?);
-        }
+        let num_transition = self.context().num_transition_constraints();
+        let num_boundary = self.context().num_assertions();
+        let mut t_coefficients = draw_coefficients(
+            public_coin,
+            self.options().batching_method(),
+            num_transition + num_boundary,
+        )?;
+        let b_coefficients = t_coefficients.split_off(num_transition);
 
         Ok(ConstraintCompositionCoefficients {
             transition: t_coefficients,
@@ -516,6 +751,10 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combinations during construction of DEEP
     /// composition polynomial.
+    ///
+    /// When [BatchingMethod::Algebraic] is specified in proof options, a single value $\beta$ is
+    /// drawn from the public coin, and the coefficients are set to $1, \beta, \beta^2, ...$ with
+    /// trace polynomial coefficients followed by constraint column polynomial coefficients.
     fn get_deep_composition_coefficients<E, R>(
         &self,
         public_coin: &mut R,
@@ -524,19 +763,79 @@ pub trait Air: Send + Sync {
         E: FieldElement<BaseField = Self::BaseField>,
         R: RandomCoin<BaseField = Self::BaseField>,
     {
-        let mut t_coefficients = Vec::new();
-        for _ in 0..self.trace_info().width() {
-            t_coefficients.push(public_coin.draw()?);
-        }
-
-        let mut c_coefficients = Vec::new();
-        for _ in 0..self.context().num_constraint_composition_columns() {
-            c_coefficients.push(public_coin.draw()?);
-        }
+        let num_trace = self.trace_info().width();
+        let num_constraints = self.context().num_constraint_composition_columns();
+        let mut t_coefficients = draw_coefficients(
+            public_coin,
+            self.options().batching_method(),
+            num_trace + num_constraints,
+        )?;
+        let c_coefficients = t_coefficients.split_off(num_trace);
 
         Ok(DeepCompositionCoefficients {
             trace: t_coefficients,
             constraints: c_coefficients,
         })
     }
+
+    // OUT-OF-DOMAIN POINT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an out-of-domain point $z$ drawn from the provided public coin.
+    ///
+    /// Points which are not valid out-of-domain points for this computation (see
+    /// [AirContext::is_valid_ood_point()]) are rejected, and a new point is drawn from the coin.
+    /// Every draw updates the state of the coin, and thus, the prover and the verifier reject
+    /// and redraw the same points.
+    ///
+    /// # Errors
+    /// Returns an error if a valid point could not be drawn after 1000 draws.
+    fn get_ood_point<E, R>(&self, public_coin: &mut R) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        R: RandomCoin<BaseField = Self::BaseField>,
+    {
+        draw_valid(public_coin, |z| self.context().is_valid_ood_point(z))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws the specified number of composition coefficients from the public coin using the
+/// specified batching method.
+fn draw_coefficients<E, R>(
+    public_coin: &mut R,
+    batching_method: BatchingMethod,
+    num_coefficients: usize,
+) -> Result<Vec<E>, RandomCoinError>
+where
+    E: FieldElement,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    match batching_method {
+        BatchingMethod::Linear => public_coin.draw_many(num_coefficients),
+        BatchingMethod::Algebraic => {
+            let alpha = public_coin.draw()?;
+            Ok(get_power_series(alpha, num_coefficients))
+        }
+    }
+}
+
+/// Draws values from the public coin until a value satisfying the `is_valid` predicate is found.
+///
+/// Returns an error if a valid value could not be drawn after [MAX_DRAW_ATTEMPTS] draws.
+fn draw_valid<E, R, F>(public_coin: &mut R, is_valid: F) -> Result<E, RandomCoinError>
+where
+    E: FieldElement,
+    R: RandomCoin<BaseField = E::BaseField>,
+    F: Fn(E) -> bool,
+{
+    for _ in 0..MAX_DRAW_ATTEMPTS {
+        let value = public_coin.draw()?;
+        if is_valid(value) {
+            return Ok(value);
+        }
+    }
+    Err(RandomCoinError::FailedToDrawFieldElement(MAX_DRAW_ATTEMPTS))
 }
@@ -0,0 +1,102 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::StarkField;
+use utils::collections::Vec;
+
+// PERIODIC COLUMN
+// ================================================================================================
+/// Description of a periodic column of a computation.
+///
+/// A periodic column is defined by a cycle of values which repeats throughout the execution trace.
+/// In addition to the values themselves, a column can specify:
+/// * A cycle offset - i.e., the step at which the first value of the cycle appears in the trace.
+///   For example, a column with values `[1, 0, 0, 0]` and offset `1` will have values
+///   `0, 1, 0, 0, 0, 1, 0, 0 ...` at steps `0, 1, 2, 3, 4, 5, 6, 7 ...`. This removes the need to
+///   define several shifted copies of the same periodic table.
+/// * Whether the column is used only by constraints against auxiliary trace segments. Values of
+///   such columns are not passed to [Air::evaluate_transition()](crate::Air::evaluate_transition),
+///   and are passed only to [Air::evaluate_aux_transition()](crate::Air::evaluate_aux_transition).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodicColumn<B: StarkField> {
+    values: Vec<B>,
+    offset: usize,
+    is_aux: bool,
+}
+
+impl<B: StarkField> PeriodicColumn<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new periodic column with the specified cycle values.
+    ///
+    /// The returned column has offset 0 and is associated with the main trace segment.
+    pub fn new(values: Vec<B>) -> Self {
+        Self {
+            values,
+            offset: 0,
+            is_aux: false,
+        }
+    }
+
+    /// Sets the step at which the first value of the cycle appears in the execution trace.
+    ///
+    /// # Panics
+    /// Panics if `offset` is greater than or equal to the number of values in the cycle.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        assert!(
+            offset < self.values.len(),
+            "offset of a periodic column must be smaller than {}, but was {}",
+            self.values.len(),
+            offset
+        );
+        self.offset = offset;
+        self
+    }
+
+    /// Associates this column with auxiliary trace segments.
+    ///
+    /// Values of this column will be available only when evaluating constraints against
+    /// auxiliary trace segments.
+    pub fn for_aux_segments(mut self) -> Self {
+        self.is_aux = true;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the values of a single cycle of this column.
+    pub fn values(&self) -> &[B] {
+        &self.values
+    }
+
+    /// Returns the length of the cycle of this column.
+    pub fn cycle_length(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the step at which the first value of the cycle appears in the execution trace.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns true if this column is associated with auxiliary trace segments.
+    pub fn is_aux(&self) -> bool {
+        self.is_aux
+    }
+
+    /// Returns the values of this column at steps `0, 1, ..., cycle_length - 1` of the execution
+    /// trace (i.e., cycle values rotated by the column offset).
+    pub fn shifted_values(&self) -> Vec<B> {
+        let n = self.values.len();
+        (0..n).map(|i| self.values[(i + n - self.offset) % n]).collect()
+    }
+}
+
+impl<B: StarkField> From<Vec<B>> for PeriodicColumn<B> {
+    fn from(values: Vec<B>) -> Self {
+        Self::new(values)
+    }
+}
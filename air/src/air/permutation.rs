@@ -0,0 +1,276 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Assertion, EvaluationFrame, TransitionConstraintDegree};
+use math::{batch_inversion, ExtensionOf, FieldElement};
+use utils::collections::Vec;
+
+// PERMUTATION ARGUMENT BUILDER
+// ================================================================================================
+/// A builder of a [PermutationArg].
+///
+/// A permutation (or multiset equality) argument is described by two sets of column tuples in the
+/// main segment of an execution trace: the left set and the right set. The argument enforces that
+/// the multiset of all left tuples across all rows of the trace is equal to the multiset of all
+/// right tuples across all rows of the trace.
+///
+/// For example, to enforce that values in column 0 are a permutation of values in column 1, the
+/// argument can be built as follows:
+///
+/// ```
+/// # use winter_air::PermutationArgBuilder;
+/// let arg = PermutationArgBuilder::new(0).with_left(vec![0]).with_right(vec![1]).build();
+/// ```
+///
+/// And to enforce that pairs of values in columns (0, 1) and (2, 3) are a permutation of pairs
+/// of values in columns (4, 5):
+///
+/// ```
+/// # use winter_air::PermutationArgBuilder;
+/// let arg = PermutationArgBuilder::new(0)
+///     .with_left(vec![0, 1])
+///     .with_left(vec![2, 3])
+///     .with_right(vec![4, 5])
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermutationArgBuilder {
+    aux_column: usize,
+    left: Vec<Vec<usize>>,
+    right: Vec<Vec<usize>>,
+}
+
+impl PermutationArgBuilder {
+    /// Returns a new builder for a permutation argument which places its running product column
+    /// at the specified index of the auxiliary trace.
+    ///
+    /// The index is zero-based across all columns of all auxiliary trace segments.
+    pub fn new(aux_column: usize) -> Self {
+        Self {
+            aux_column,
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+
+    /// Adds a tuple of main trace columns to the left side of the argument.
+    pub fn with_left(mut self, columns: Vec<usize>) -> Self {
+        self.left.push(columns);
+        self
+    }
+
+    /// Adds a tuple of main trace columns to the right side of the argument.
+    pub fn with_right(mut self, columns: Vec<usize>) -> Self {
+        self.right.push(columns);
+        self
+    }
+
+    /// Returns a permutation argument described by this builder.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Either side of the argument does not contain any tuples.
+    /// * Any of the tuples is empty.
+    /// * Tuples in the argument have different widths.
+    pub fn build(self) -> PermutationArg {
+        assert!(!self.left.is_empty(), "left side of a permutation argument cannot be empty");
+        assert!(!self.right.is_empty(), "right side of a permutation argument cannot be empty");
+
+        let tuple_width = self.left[0].len();
+        assert!(tuple_width > 0, "permutation argument tuples cannot be empty");
+        for tuple in self.left.iter().chain(self.right.iter()) {
+            assert_eq!(
+                tuple_width,
+                tuple.len(),
+                "all permutation argument tuples must have width {}, but a tuple of width {} was found",
+                tuple_width,
+                tuple.len()
+            );
+        }
+
+        PermutationArg {
+            aux_column: self.aux_column,
+            left: self.left,
+            right: self.right,
+        }
+    }
+}
+
+// PERMUTATION ARGUMENT
+// ================================================================================================
+/// A permutation (multiset equality) argument between two sets of column tuples in the main
+/// segment of an execution trace.
+///
+/// The argument is enforced via a running product column $p$ in the auxiliary trace, such that
+/// $p_0 = 1$ and:
+/// $$
+/// p_{i+1} = p_i \cdot \frac{\prod_{l}{(\gamma + \sum_j{\alpha_j \cdot l_{i,j}})}}
+///     {\prod_{r}{(\gamma + \sum_j{\alpha_j \cdot r_{i,j}})}}
+/// $$
+/// where $l$ and $r$ range over left and right tuples respectively, $l_{i,j}$ is the value of the
+/// $j$-th column of tuple $l$ at row $i$, and $\alpha_j$ and $\gamma$ are random elements drawn
+/// by the verifier. If the final value of the column is $1$, the multisets are equal with high
+/// probability.
+///
+/// Since transition constraints are not enforced on the last step of the trace, values in the
+/// last row of the trace are not included into the argument.
+///
+/// This struct provides everything needed to include the argument into a computation:
+/// * [PermutationArg::build_aux_column()] builds the running product column.
+/// * [PermutationArg::evaluate_transition()] evaluates the transition constraint for the column,
+///   and [PermutationArg::transition_constraint_degree()] returns its degree.
+/// * [PermutationArg::get_assertions()] returns boundary assertions for the column.
+///
+/// The argument requires [PermutationArg::num_rand_elements()] random elements: $\alpha_j$ for
+/// each column in a tuple, followed by $\gamma$.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermutationArg {
+    aux_column: usize,
+    left: Vec<Vec<usize>>,
+    right: Vec<Vec<usize>>,
+}
+
+impl PermutationArg {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the running product column in the auxiliary trace.
+    pub fn aux_column(&self) -> usize {
+        self.aux_column
+    }
+
+    /// Returns the number of columns in each tuple of this argument.
+    pub fn tuple_width(&self) -> usize {
+        self.left[0].len()
+    }
+
+    /// Returns the number of random elements required by this argument.
+    pub fn num_rand_elements(&self) -> usize {
+        self.tuple_width() + 1
+    }
+
+    /// Returns the degree of the transition constraint enforced against the running product
+    /// column.
+    pub fn transition_constraint_degree(&self) -> TransitionConstraintDegree {
+        TransitionConstraintDegree::new(1 + core::cmp::max(self.left.len(), self.right.len()))
+    }
+
+    // AUXILIARY COLUMN BUILDER
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the running product column for this argument.
+    ///
+    /// `main_trace` must contain all columns of the main trace segment, and `rand_elements` must
+    /// contain at least [PermutationArg::num_rand_elements()] elements.
+    ///
+    /// # Panics
+    /// Panics if `rand_elements` contains fewer than the required number of elements.
+    pub fn build_aux_column<E: FieldElement>(
+        &self,
+        main_trace: &[&[E::BaseField]],
+        rand_elements: &[E],
+    ) -> Vec<E> {
+        self.check_rand_elements(rand_elements);
+        let trace_length = main_trace[0].len();
+
+        // compute numerators and denominators of running product updates for all rows
+        let (numerators, denominators): (Vec<E>, Vec<E>) = (0..trace_length)
+            .map(|i| {
+                let value = |column: usize| E::from(main_trace[column][i]);
+                (
+                    product_of_tuples(&self.left, value, rand_elements),
+                    product_of_tuples(&self.right, value, rand_elements),
+                )
+            })
+            .unzip();
+        let inv_denominators = batch_inversion(&denominators);
+
+        // build the running product column
+        let mut result = Vec::with_capacity(trace_length);
+        result.push(E::ONE);
+        for i in 0..trace_length - 1 {
+            result.push(result[i] * numerators[i] * inv_denominators[i]);
+        }
+        result
+    }
+
+    // CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraint of this argument against the provided frames.
+    ///
+    /// The constraint is defined as:
+    /// $$
+    /// p_{i+1} \cdot \prod_{r}{(\gamma + \sum_j{\alpha_j \cdot r_{i,j}})} -
+    /// p_i \cdot \prod_{l}{(\gamma + \sum_j{\alpha_j \cdot l_{i,j}})} = 0
+    /// $$
+    ///
+    /// # Panics
+    /// Panics if `rand_elements` contains fewer than the required number of elements.
+    pub fn evaluate_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        rand_elements: &[E],
+    ) -> E
+    where
+        F: FieldElement,
+        E: FieldElement + ExtensionOf<F>,
+    {
+        self.check_rand_elements(rand_elements);
+        let value = |column: usize| E::from(main_frame.current()[column]);
+        let p_current = aux_frame.current()[self.aux_column];
+        let p_next = aux_frame.next()[self.aux_column];
+
+        p_next * product_of_tuples(&self.right, value, rand_elements)
+            - p_current * product_of_tuples(&self.left, value, rand_elements)
+    }
+
+    /// Returns boundary assertions for the running product column of this argument.
+    ///
+    /// These assertions enforce that the first and the last values of the column are ONE.
+    pub fn get_assertions<E: FieldElement>(&self, trace_length: usize) -> Vec<Assertion<E>> {
+        vec![
+            Assertion::single(self.aux_column, 0, E::ONE),
+            Assertion::single(self.aux_column, trace_length - 1, E::ONE),
+        ]
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn check_rand_elements<E>(&self, rand_elements: &[E]) {
+        assert!(
+            rand_elements.len() >= self.num_rand_elements(),
+            "permutation argument requires at least {} random elements, but {} were provided",
+            self.num_rand_elements(),
+            rand_elements.len()
+        );
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Compresses each of the tuples into a single value using random elements α_j, adds γ to the
+/// result, and returns the product of all such values.
+///
+/// `value` is expected to return the value of the main trace column at the specified index in the
+/// row for which the product is computed.
+fn product_of_tuples<E, V>(tuples: &[Vec<usize>], value: V, rand_elements: &[E]) -> E
+where
+    E: FieldElement,
+    V: Fn(usize) -> E,
+{
+    let (alphas, gamma) = rand_elements.split_at(tuples[0].len());
+    let gamma = gamma[0];
+    tuples.iter().fold(E::ONE, |result, tuple| {
+        let compressed = tuple
+            .iter()
+            .zip(alphas)
+            .fold(gamma, |acc, (&column, &alpha)| acc + alpha * value(column));
+        result * compressed
+    })
+}
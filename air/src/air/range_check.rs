@@ -0,0 +1,400 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Assertion, EvaluationFrame, TransitionConstraintDegree};
+use math::{batch_inversion, ExtensionOf, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bits in a single range-checked limb.
+const LIMB_BITS: u32 = 16;
+
+/// Number of values in the range table; all values in `[0, 2^16)` are included into the table.
+const TABLE_SIZE: usize = 1 << LIMB_BITS;
+
+/// Minimum length of an execution trace which can accommodate the range table. The table must
+/// fit into all rows but the last one, and the trace length must be a power of two.
+pub const MIN_RANGE_CHECK_TRACE_LENGTH: usize = 2 * TABLE_SIZE;
+
+// RANGE CHECK BUILDER
+// ================================================================================================
+/// A builder of a [RangeCheck].
+///
+/// A range check enforces that values in a set of main trace columns are 16-bit or 32-bit
+/// unsigned integers. To build a range check, the following needs to be specified:
+/// * A main trace column which will hold the range table (all values in `[0, 2^16)`).
+/// * A main trace column which will hold multiplicities of the values in the range table.
+/// * An auxiliary trace column which will hold the running sum of the LogUp argument.
+/// * Main trace columns to be range-checked. For 32-bit columns, two extra main trace columns
+///   must be specified to hold the low and the high 16-bit limbs of the values.
+///
+/// For example:
+///
+/// ```
+/// # use winter_air::RangeCheckBuilder;
+/// // column 0 contains u16 values, column 1 contains u32 values decomposed into columns 2 and 3;
+/// // columns 4 and 5 hold the range table and its multiplicities
+/// let range_check = RangeCheckBuilder::new(4, 5, 0).check_u16(0).check_u32(1, [2, 3]).build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeCheckBuilder {
+    table_column: usize,
+    multiplicity_column: usize,
+    aux_column: usize,
+    u16_columns: Vec<usize>,
+    u32_columns: Vec<(usize, [usize; 2])>,
+}
+
+impl RangeCheckBuilder {
+    /// Returns a new builder for a range check which places the range table and its multiplicities
+    /// into the specified main trace columns, and the running sum of the LogUp argument into the
+    /// specified auxiliary trace column.
+    ///
+    /// The auxiliary column index is zero-based across all columns of all auxiliary trace
+    /// segments.
+    pub fn new(table_column: usize, multiplicity_column: usize, aux_column: usize) -> Self {
+        Self {
+            table_column,
+            multiplicity_column,
+            aux_column,
+            u16_columns: Vec::new(),
+            u32_columns: Vec::new(),
+        }
+    }
+
+    /// Declares that values in the specified main trace column are 16-bit unsigned integers.
+    pub fn check_u16(mut self, column: usize) -> Self {
+        self.u16_columns.push(column);
+        self
+    }
+
+    /// Declares that values in the specified main trace column are 32-bit unsigned integers.
+    ///
+    /// `limbs` specifies the main trace columns which will hold the low and the high 16-bit limbs
+    /// of the values respectively.
+    pub fn check_u32(mut self, column: usize, limbs: [usize; 2]) -> Self {
+        self.u32_columns.push((column, limbs));
+        self
+    }
+
+    /// Returns a range check described by this builder.
+    ///
+    /// # Panics
+    /// Panics if no columns to be range-checked were specified.
+    pub fn build(self) -> RangeCheck {
+        assert!(
+            !self.u16_columns.is_empty() || !self.u32_columns.is_empty(),
+            "at least one column must be range-checked"
+        );
+
+        // build the list of columns to be looked up in the range table
+        let mut lookup_columns = self.u16_columns;
+        for (_, limbs) in self.u32_columns.iter() {
+            lookup_columns.extend_from_slice(limbs);
+        }
+
+        RangeCheck {
+            table_column: self.table_column,
+            multiplicity_column: self.multiplicity_column,
+            aux_column: self.aux_column,
+            lookup_columns,
+            u32_columns: self.u32_columns,
+        }
+    }
+}
+
+// RANGE CHECK
+// ================================================================================================
+/// A range check enforcing that values in a set of main trace columns are 16-bit or 32-bit
+/// unsigned integers.
+///
+/// The range check is implemented via a LogUp argument against a range table $t$ containing all
+/// values in $[0, 2^{16})$ with multiplicities $m$. The range table is enforced by requiring that
+/// the table starts with $0$, ends with $2^{16} - 1$, and increments by either $0$ or $1$ at every
+/// step. The running sum column $s$ in the auxiliary trace is defined as $s_0 = 0$ and:
+/// $$
+/// s_{i+1} = s_i + \sum_{k}{\frac{1}{\alpha - v_{i,k}}} - \frac{m_i}{\alpha - t_i}
+/// $$
+/// where $v_{i,k}$ are the values being range-checked at row $i$, and $\alpha$ is a random element
+/// drawn by the verifier. If the last value of the running sum is $0$, all values are in the range
+/// table with high probability. 32-bit values are range-checked by decomposing them into two
+/// 16-bit limbs.
+///
+/// Since transition constraints are not enforced on the last step of the trace, values in the
+/// last row of the trace are not range-checked. Also, since the range table must fit into all
+/// but the last row of the trace, the trace must be at least [MIN_RANGE_CHECK_TRACE_LENGTH] steps
+/// long.
+///
+/// This struct provides everything needed to include the range check into a computation:
+/// * [RangeCheck::fill_main_columns()] fills the limb, range table, and multiplicity columns of
+///   the main trace.
+/// * [RangeCheck::build_aux_column()] builds the running sum column.
+/// * [RangeCheck::evaluate_main_transition()] and [RangeCheck::evaluate_aux_transition()]
+///   evaluate the transition constraints, and [RangeCheck::main_constraint_degrees()] and
+///   [RangeCheck::aux_constraint_degree()] return their degrees.
+/// * [RangeCheck::get_main_assertions()] and [RangeCheck::get_aux_assertions()] return boundary
+///   assertions.
+///
+/// The range check requires a single random element $\alpha$.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeCheck {
+    table_column: usize,
+    multiplicity_column: usize,
+    aux_column: usize,
+    lookup_columns: Vec<usize>,
+    u32_columns: Vec<(usize, [usize; 2])>,
+}
+
+impl RangeCheck {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the main trace column holding the range table.
+    pub fn table_column(&self) -> usize {
+        self.table_column
+    }
+
+    /// Returns the index of the main trace column holding multiplicities of the range table
+    /// values.
+    pub fn multiplicity_column(&self) -> usize {
+        self.multiplicity_column
+    }
+
+    /// Returns the index of the running sum column in the auxiliary trace.
+    pub fn aux_column(&self) -> usize {
+        self.aux_column
+    }
+
+    /// Returns the number of random elements required by this range check.
+    pub fn num_rand_elements(&self) -> usize {
+        1
+    }
+
+    /// Returns the number of transition constraints placed against the main trace segment.
+    ///
+    /// This includes one constraint for the range table and one decomposition constraint for
+    /// every 32-bit column.
+    pub fn num_main_constraints(&self) -> usize {
+        1 + self.u32_columns.len()
+    }
+
+    /// Returns degrees of the transition constraints placed against the main trace segment.
+    pub fn main_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        let mut result = vec![TransitionConstraintDegree::new(2)];
+        result.extend(self.u32_columns.iter().map(|_| TransitionConstraintDegree::new(1)));
+        result
+    }
+
+    /// Returns the degree of the transition constraint placed against the running sum column.
+    pub fn aux_constraint_degree(&self) -> TransitionConstraintDegree {
+        TransitionConstraintDegree::new(self.lookup_columns.len() + 2)
+    }
+
+    // TRACE BUILDERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Fills the limb columns of all 32-bit columns, as well as the range table and multiplicity
+    /// columns of the provided main trace.
+    ///
+    /// All other columns of the trace are expected to be already filled.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The trace is shorter than [MIN_RANGE_CHECK_TRACE_LENGTH].
+    /// * Any of the range-checked values in all but the last row of the trace does not fit into
+    ///   the declared number of bits.
+    pub fn fill_main_columns<B: StarkField>(&self, main_trace: &mut [Vec<B>]) {
+        let trace_length = main_trace[self.table_column].len();
+        assert!(
+            trace_length >= MIN_RANGE_CHECK_TRACE_LENGTH,
+            "range checks require traces of at least {MIN_RANGE_CHECK_TRACE_LENGTH} steps, but trace length was {trace_length}"
+        );
+
+        // decompose 32-bit values into 16-bit limbs
+        // values in the last row are not range-checked, and thus, are allowed to be out of range
+        for &(column, [lo_column, hi_column]) in self.u32_columns.iter() {
+            let (lo_limbs, hi_limbs) = main_trace[column]
+                .iter()
+                .enumerate()
+                .map(|(row, &value)| match as_small_int(value, 2 * LIMB_BITS) {
+                    Some(value) => {
+                        (B::from(value & (TABLE_SIZE as u64 - 1)), B::from(value >> LIMB_BITS))
+                    }
+                    None if row == trace_length - 1 => (B::ZERO, B::ZERO),
+                    None => panic!("value {value} in column {column} at step {row} is not a u32"),
+                })
+                .unzip();
+            main_trace[lo_column] = lo_limbs;
+            main_trace[hi_column] = hi_limbs;
+        }
+
+        // count how many times each of the values is looked up in the range table
+        let mut multiplicities = vec![0u64; TABLE_SIZE];
+        for &column in self.lookup_columns.iter() {
+            for (row, &value) in main_trace[column][..trace_length - 1].iter().enumerate() {
+                match as_small_int(value, LIMB_BITS) {
+                    Some(value) => multiplicities[value as usize] += 1,
+                    None => panic!("value {value} in column {column} at step {row} is not a u16"),
+                }
+            }
+        }
+
+        // fill the range table and its multiplicities; the table is placed at the start of the
+        // trace and the last value of the table is repeated until the end of the trace
+        for (row, value) in main_trace[self.table_column].iter_mut().enumerate() {
+            *value = B::from(core::cmp::min(row, TABLE_SIZE - 1) as u64);
+        }
+        for (row, value) in main_trace[self.multiplicity_column].iter_mut().enumerate() {
+            *value = multiplicities.get(row).map_or(B::ZERO, |&m| B::from(m));
+        }
+    }
+
+    /// Returns the running sum column for this range check.
+    ///
+    /// `main_trace` must contain all columns of the main trace segment (including the columns
+    /// filled via [RangeCheck::fill_main_columns()]), and `rand_elements` must contain at least
+    /// one element.
+    pub fn build_aux_column<E: FieldElement>(
+        &self,
+        main_trace: &[&[E::BaseField]],
+        rand_elements: &[E],
+    ) -> Vec<E> {
+        let alpha = rand_elements[0];
+        let trace_length = main_trace[self.table_column].len();
+        let num_lookups = self.lookup_columns.len();
+
+        // compute inverses of (α - v) for all looked up values and for all range table values
+        let denominators = (0..trace_length - 1)
+            .flat_map(|row| {
+                self.lookup_columns
+                    .iter()
+                    .chain(core::iter::once(&self.table_column))
+                    .map(move |&column| alpha - E::from(main_trace[column][row]))
+            })
+            .collect::<Vec<_>>();
+        let inv_denominators = batch_inversion(&denominators);
+
+        // build the running sum column
+        let mut result = Vec::with_capacity(trace_length);
+        result.push(E::ZERO);
+        for (row, inv_values) in inv_denominators.chunks(num_lookups + 1).enumerate() {
+            let (inv_lookups, inv_table) = inv_values.split_at(num_lookups);
+            let multiplicity = E::from(main_trace[self.multiplicity_column][row]);
+            let delta =
+                inv_lookups.iter().fold(E::ZERO, |acc, &v| acc + v) - multiplicity * inv_table[0];
+            result.push(result[row] + delta);
+        }
+        result
+    }
+
+    // CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates transition constraints placed against the main trace segment and writes the
+    /// results into `result`.
+    ///
+    /// The first constraint enforces that the range table increments by either 0 or 1; the
+    /// remaining constraints enforce that 32-bit values are decomposed into limbs correctly.
+    pub fn evaluate_main_transition<E: FieldElement>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        let delta = next[self.table_column] - current[self.table_column];
+        result[0] = delta * (delta - E::ONE);
+
+        let limb_shift = E::from(TABLE_SIZE as u32);
+        for (result, &(column, [lo_column, hi_column])) in
+            result[1..].iter_mut().zip(self.u32_columns.iter())
+        {
+            *result = current[column] - (current[lo_column] + limb_shift * current[hi_column]);
+        }
+    }
+
+    /// Evaluates the transition constraint placed against the running sum column.
+    ///
+    /// The constraint is defined as:
+    /// $$
+    /// (s_{i+1} - s_i) \cdot D = (\alpha - t_i) \cdot \sum_k{\prod_{j \ne k}{(\alpha - v_{i,j})}} -
+    ///     m_i \cdot \prod_k{(\alpha - v_{i,k})}
+    /// $$
+    /// where $D = (\alpha - t_i) \cdot \prod_k{(\alpha - v_{i,k})}$.
+    pub fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        rand_elements: &[E],
+    ) -> E
+    where
+        F: FieldElement,
+        E: FieldElement + ExtensionOf<F>,
+    {
+        let alpha = rand_elements[0];
+        let main_current = main_frame.current();
+        let delta = aux_frame.next()[self.aux_column] - aux_frame.current()[self.aux_column];
+
+        let lookup_value = |k: usize| alpha - E::from(main_current[self.lookup_columns[k]]);
+        let table_value = alpha - E::from(main_current[self.table_column]);
+        let multiplicity = E::from(main_current[self.multiplicity_column]);
+
+        let num_lookups = self.lookup_columns.len();
+        let lookups_product = (0..num_lookups).fold(E::ONE, |acc, k| acc * lookup_value(k));
+        let lookups_sum = (0..num_lookups).fold(E::ZERO, |acc, k| {
+            acc + (0..num_lookups)
+                .filter(|&j| j != k)
+                .fold(E::ONE, |acc, j| acc * lookup_value(j))
+        });
+
+        delta * lookups_product * table_value - table_value * lookups_sum
+            + multiplicity * lookups_product
+    }
+
+    /// Returns boundary assertions placed against the main trace segment.
+    ///
+    /// These assertions enforce that the range table starts with 0 and ends with 2^16 - 1.
+    pub fn get_main_assertions<B: StarkField>(&self, trace_length: usize) -> Vec<Assertion<B>> {
+        vec![
+            Assertion::single(self.table_column, 0, B::ZERO),
+            Assertion::single(
+                self.table_column,
+                trace_length - 1,
+                B::from((TABLE_SIZE - 1) as u64),
+            ),
+        ]
+    }
+
+    /// Returns boundary assertions placed against the running sum column.
+    ///
+    /// These assertions enforce that the first and the last values of the column are ZERO.
+    pub fn get_aux_assertions<E: FieldElement>(&self, trace_length: usize) -> Vec<Assertion<E>> {
+        vec![
+            Assertion::single(self.aux_column, 0, E::ZERO),
+            Assertion::single(self.aux_column, trace_length - 1, E::ZERO),
+        ]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the integer representation of the provided value if it fits into the specified number
+/// of bits, and None otherwise.
+fn as_small_int<B: StarkField>(value: B, num_bits: u32) -> Option<u64> {
+    let value = value.as_int();
+    if value >= B::PositiveInteger::from(1u64 << num_bits) {
+        return None;
+    }
+
+    let one = B::PositiveInteger::from(1u32);
+    let result = (0..num_bits)
+        .filter(|&i| (value >> i) & one == one)
+        .fold(0u64, |acc, i| acc | (1 << i));
+    Some(result)
+}
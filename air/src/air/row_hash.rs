@@ -0,0 +1,58 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crypto::ElementHasher;
+use math::FieldElement;
+
+// ROW HASH STRATEGY
+// ================================================================================================
+/// Defines how rows of an extended execution trace are hashed into leaves of trace commitment
+/// Merkle trees.
+///
+/// By default, all values in a row are hashed together in a single invocation of the hash
+/// function. Other strategies can be used to make leaf encoding match the encoding expected by
+/// an external verifier (e.g., a recursive verifier circuit).
+///
+/// The strategy applies to commitments to all trace segments (including the column group, if
+/// one is defined), but not to the commitment to constraint evaluations. A strategy can be
+/// attached to an AIR via
+/// [AirContext::set_row_hash_strategy()](crate::AirContext::set_row_hash_strategy).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RowHashStrategy {
+    /// All values in a row are hashed together: $H(r_0, ..., r_{n-1})$.
+    #[default]
+    FullRow,
+    /// A row is split into consecutive groups of the specified number of columns (the last group
+    /// may contain fewer columns), and each group is hashed separately. The resulting digests
+    /// are then merged sequentially from left to right: $H(H(d_0, d_1), d_2)$ etc. A row
+    /// consisting of a single group is hashed into the digest of that group.
+    ColumnGroups(usize),
+    /// All values in a row are hashed together, and the resulting digest is then merged with the
+    /// specified domain separation tag using [Hasher::merge_with_int()](crypto::Hasher).
+    DomainSeparated(u64),
+}
+
+impl RowHashStrategy {
+    /// Hashes the provided row of trace values into a single digest according to this strategy.
+    ///
+    /// # Panics
+    /// Panics if this is a [RowHashStrategy::ColumnGroups] strategy with group size of zero.
+    pub fn hash_row<H, E>(&self, row: &[E]) -> H::Digest
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        E: FieldElement,
+    {
+        match *self {
+            Self::FullRow => H::hash_elements(row),
+            Self::ColumnGroups(group_size) => {
+                assert!(group_size > 0, "column group size must be greater than zero");
+                let mut groups = row.chunks(group_size);
+                let first = H::hash_elements(groups.next().unwrap_or(&[]));
+                groups.fold(first, |acc, group| H::merge(&[acc, H::hash_elements(group)]))
+            }
+            Self::DomainSeparated(tag) => H::merge_with_int(H::hash_elements(row), tag),
+        }
+    }
+}
@@ -4,12 +4,19 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    lagrange_kernel_boundary_value, lagrange_kernel_transition_divisor, AggregateAir,
+    AggregatePublicInputs, Air, AirContext, Assertion, ColumnGroup, ConstraintDivisor,
+    EvaluationFrame, LagrangeKernelEvaluationFrame, PeriodicColumn, PermutationArgBuilder,
+    ProofOptions, RangeCheckBuilder, RowHashStrategy, SelectorColumns, SelectorFlag, TraceInfo,
+    TraceMetadata, TransitionConstraintDegree, TransitionConstraints,
 };
-use crate::{AuxTraceRandElements, FieldExtension};
-use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
+use crate::{
+    AuxRandElementsReader, AuxTraceRandElements, BatchingMethod, ConstraintCompositionCoefficients,
+    FieldExtension, MIN_RANGE_CHECK_TRACE_LENGTH,
+};
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, ElementHasher, Hasher, RandomCoin};
 use math::{fields::f64::BaseElement, get_power_series, polynom, FieldElement, StarkField};
+use rand_utils::{rand_value, rand_vector};
 use utils::collections::{BTreeMap, Vec};
 
 // PERIODIC COLUMNS
@@ -30,6 +37,44 @@ fn get_periodic_column_polys() {
     assert_eq!(build_periodic_column_poly(&col2), column_polys[1]);
 }
 
+#[test]
+fn get_periodic_column_polys_with_offsets() {
+    let col1 = vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::ZERO, BaseElement::ZERO];
+    let col2 = vec![BaseElement::new(2), BaseElement::new(3)];
+    let col3 =
+        vec![BaseElement::new(4), BaseElement::new(5), BaseElement::new(6), BaseElement::ONE];
+    let columns = vec![
+        PeriodicColumn::new(col1.clone()).with_offset(1).for_aux_segments(),
+        PeriodicColumn::new(col2.clone()),
+        PeriodicColumn::new(col3.clone()).with_offset(3),
+    ];
+    let air = MockAir::with_periodic_column_descriptors(columns, 16);
+    assert_eq!(2, air.num_main_periodic_columns());
+
+    // main segment columns come first, and values of shifted columns are rotated by the offset
+    let column_polys = air.get_periodic_column_polys();
+    assert_eq!(3, column_polys.len());
+    assert_eq!(build_periodic_column_poly(&col2), column_polys[0]);
+    let shifted_col3 = vec![col3[1], col3[2], col3[3], col3[0]];
+    assert_eq!(build_periodic_column_poly(&shifted_col3), column_polys[1]);
+    let shifted_col1 = vec![col1[3], col1[0], col1[1], col1[2]];
+    assert_eq!(build_periodic_column_poly(&shifted_col1), column_polys[2]);
+
+    // the first value of a shifted cycle should appear at the step defined by the offset
+    let g = BaseElement::get_root_of_unity(16_u32.ilog2());
+    for step in 0..16_u64 {
+        let x = g.exp(step);
+        let expected = col1[((step + 4 - 1) % 4) as usize];
+        assert_eq!(expected, polynom::eval(&column_polys[2], x.exp(4_u32.into())));
+    }
+}
+
+#[test]
+#[should_panic(expected = "offset of a periodic column must be smaller than 2, but was 2")]
+fn periodic_column_offset_too_large() {
+    PeriodicColumn::new(vec![BaseElement::ONE, BaseElement::ZERO]).with_offset(2);
+}
+
 #[test]
 #[should_panic(expected = "number of values in a periodic column must be at least 2, but was 1")]
 fn get_periodic_column_polys_num_values_too_small() {
@@ -186,32 +231,595 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&5], constraint.cc().clone());
 }
 
+// CONSTRAINT EVALUATION
+// ================================================================================================
+
+#[test]
+fn evaluate_constraints_at() {
+    let value: BaseElement = rand_value();
+    let air = MockAir::with_assertions(vec![Assertion::single(1, 0, value)], 16);
+
+    let z: BaseElement = rand_value();
+    let main_frame = EvaluationFrame::from_rows(rand_vector(4), rand_vector(4));
+    let coefficients = ConstraintCompositionCoefficients {
+        transition: rand_vector(1),
+        boundary: rand_vector(1),
+    };
+
+    // the mock AIR has no non-zero transition constraints; thus, the result is the single
+    // boundary constraint divided by its divisor (x - 1)
+    let expected =
+        coefficients.boundary[0] * (main_frame.current()[1] - value) / (z - BaseElement::ONE);
+    let result = air.evaluate_constraints_at(
+        z,
+        &main_frame,
+        None,
+        &AuxTraceRandElements::new(),
+        &coefficients,
+    );
+    assert_eq!(expected, result);
+}
+
+// TRANSITION CONSTRAINT DIVISORS
+// ================================================================================================
+
+#[test]
+fn get_transition_constraints_with_custom_divisors() {
+    let trace_length = 16;
+    let periodic_divisor = ConstraintDivisor::from_periodic_steps(trace_length, 3, 4);
+    let context = build_context_with_degrees::<BaseElement>(trace_length, vec![2, 2, 2])
+        .set_main_transition_divisor(1, periodic_divisor.clone())
+        .set_main_transition_divisor(2, periodic_divisor.clone());
+
+    let default_divisor = ConstraintDivisor::from_transition(trace_length, 1);
+    assert_eq!(default_divisor, context.get_transition_constraint_divisor(0));
+    assert_eq!(periodic_divisor, context.get_transition_constraint_divisor(1));
+    assert_eq!(periodic_divisor, context.get_transition_constraint_divisor(2));
+
+    // constraints with identical divisors should be merged into the same group
+    let coefficients = rand_vector::<BaseElement>(3);
+    let constraints = TransitionConstraints::new(&context, &coefficients);
+    assert_eq!(2, constraints.num_divisors());
+    assert_eq!(&default_divisor, constraints.divisor());
+    assert_eq!(&periodic_divisor, &constraints.divisors()[1]);
+    assert_eq!(&[0, 1, 1], constraints.main_constraint_divisors());
+
+    // each group should be divided by its own divisor
+    let x = rand_value::<BaseElement>();
+    let evaluations = rand_vector::<BaseElement>(3);
+    let expected = coefficients[0] * evaluations[0] / default_divisor.evaluate_at(x)
+        + (coefficients[1] * evaluations[1] + coefficients[2] * evaluations[2])
+            / periodic_divisor.evaluate_at(x);
+    assert_eq!(expected, constraints.combine_evaluations::<BaseElement>(&evaluations, &[], x));
+}
+
+#[test]
+fn custom_transition_divisor_composition_columns() {
+    let trace_length = 16;
+    let context = build_context_with_degrees::<BaseElement>(trace_length, vec![2]);
+    assert_eq!(2, context.ce_blowup_factor);
+    assert_eq!(1, context.num_constraint_composition_columns());
+
+    // quotient degree is 2 * 15 - 4 = 26, so it requires two composition columns
+    let divisor = ConstraintDivisor::from_periodic_steps(trace_length, 0, 4);
+    let context = context.set_main_transition_divisor(0, divisor);
+    assert_eq!(2, context.ce_blowup_factor);
+    assert_eq!(2, context.num_constraint_composition_columns());
+
+    // exemptions should not apply to constraints with custom divisors
+    let context = context.set_num_transition_exemptions(2);
+    assert_eq!(2, context.num_constraint_composition_columns());
+}
+
+#[test]
+fn fixed_num_composition_columns() {
+    let trace_length = 16;
+    let context = build_context_with_degrees::<BaseElement>(trace_length, vec![2]);
+    assert_eq!(1, context.min_num_constraint_composition_columns());
+
+    let context = context.set_num_constraint_composition_columns(4);
+    assert_eq!(1, context.min_num_constraint_composition_columns());
+    assert_eq!(4, context.num_constraint_composition_columns());
+}
+
+#[test]
+#[should_panic(expected = "number of constraint composition columns must be at least 2, but was 1")]
+fn fixed_num_composition_columns_too_small() {
+    let trace_length = 16;
+    let divisor = ConstraintDivisor::from_periodic_steps(trace_length, 0, 4);
+    let _ = build_context_with_degrees::<BaseElement>(trace_length, vec![2])
+        .set_num_constraint_composition_columns(1)
+        .set_main_transition_divisor(0, divisor);
+}
+
+#[test]
+fn custom_transition_divisor_blowup_factor_exceeds_lde_blowup() {
+    // the constraint evaluation domain may be larger than the LDE domain
+    let trace_length = 16;
+    let options = ProofOptions::new(32, 2, 0, FieldExtension::None, 4, 31);
+    let trace_info = TraceInfo::new(4, trace_length);
+    let degrees = vec![TransitionConstraintDegree::new(3)];
+    let divisor = ConstraintDivisor::from_periodic_steps(trace_length, 0, 16);
+    let context = AirContext::<BaseElement>::new(trace_info, degrees, 1, options)
+        .set_main_transition_divisor(0, divisor);
+    assert_eq!(trace_length * 4, context.ce_domain_size());
+    assert_eq!(trace_length * 2, context.lde_domain_size());
+}
+
+// SELECTORS
+// ================================================================================================
+
+#[test]
+fn selector_flags() {
+    let row = [BaseElement::ONE, BaseElement::ZERO, BaseElement::new(7), BaseElement::ONE];
+
+    assert_eq!(BaseElement::ONE, SelectorFlag::new().evaluate(&row));
+    assert_eq!(BaseElement::ONE, SelectorFlag::new().and(0).and_not(1).evaluate(&row));
+    assert_eq!(BaseElement::ZERO, SelectorFlag::new().and(0).and(1).evaluate(&row));
+    assert_eq!(BaseElement::ZERO, SelectorFlag::new().and_not(3).evaluate(&row));
+
+    let flag = SelectorFlag::new().and(0).and_not(1);
+    assert_eq!(BaseElement::new(5), flag.guard(&row, BaseElement::new(5)));
+    assert_eq!(2, flag.degree());
+    assert_eq!(
+        TransitionConstraintDegree::with_cycles(5, vec![8]),
+        flag.guard_degree(TransitionConstraintDegree::with_cycles(3, vec![8]))
+    );
+}
+
+#[test]
+fn selector_columns_binary_constraints() {
+    let selectors = SelectorColumns::new(vec![0, 2, 3]);
+    assert_eq!(3, selectors.num_binary_constraints());
+    assert_eq!(
+        vec![TransitionConstraintDegree::new(2); 3],
+        selectors.binary_constraint_degrees()
+    );
+
+    let row = [BaseElement::new(3), BaseElement::new(9), BaseElement::ZERO, BaseElement::ONE];
+    let mut result = [BaseElement::ZERO; 3];
+    selectors.evaluate_binary_constraints(&row, &mut result);
+    assert_eq!([BaseElement::new(6), BaseElement::ZERO, BaseElement::ZERO], result);
+}
+
+#[test]
+fn selector_columns_decode() {
+    let selectors = SelectorColumns::new(vec![1, 2, 3]);
+    for value in 0..8 {
+        let flag = selectors.decode(value);
+        assert_eq!(3, flag.degree());
+        for encoded in 0..8 {
+            let row = [
+                BaseElement::new(9),
+                BaseElement::from((encoded & 1) as u8),
+                BaseElement::from(((encoded >> 1) & 1) as u8),
+                BaseElement::from(((encoded >> 2) & 1) as u8),
+            ];
+            let expected = if encoded == value {
+                BaseElement::ONE
+            } else {
+                BaseElement::ZERO
+            };
+            assert_eq!(expected, flag.evaluate(&row));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "value 8 cannot be encoded with 3 selector columns")]
+fn selector_columns_decode_value_too_large() {
+    let _ = SelectorColumns::new(vec![0, 1, 2]).decode(8);
+}
+
+#[test]
+#[should_panic(expected = "selector column 1 was specified more than once")]
+fn selector_columns_duplicate_column() {
+    let _ = SelectorColumns::new(vec![1, 0, 1]);
+}
+
+// PERMUTATION ARGUMENT
+// ================================================================================================
+
+#[test]
+fn permutation_arg() {
+    // pairs of values in columns (2, 3) are a permutation of pairs of values in columns (0, 1)
+    // over the first 7 rows; values in the last row are not included into the argument
+    let trace_length = 8;
+    let left = (0..trace_length as u64).map(|i| (i, i * i)).collect::<Vec<_>>();
+    let mut right = left[..trace_length - 1].iter().rev().copied().collect::<Vec<_>>();
+    right.push((100, 200));
+    let mut main_trace = [
+        left.iter().map(|p| BaseElement::new(p.0)).collect::<Vec<_>>(),
+        left.iter().map(|p| BaseElement::new(p.1)).collect::<Vec<_>>(),
+        right.iter().map(|p| BaseElement::new(p.0)).collect::<Vec<_>>(),
+        right.iter().map(|p| BaseElement::new(p.1)).collect::<Vec<_>>(),
+    ];
+
+    let arg = PermutationArgBuilder::new(0)
+        .with_left(vec![0, 1])
+        .with_right(vec![2, 3])
+        .build();
+    assert_eq!(3, arg.num_rand_elements());
+    assert_eq!(TransitionConstraintDegree::new(2), arg.transition_constraint_degree());
+
+    let rand_elements = rand_vector::<BaseElement>(arg.num_rand_elements());
+    let columns = main_trace.iter().map(|c| c.as_slice()).collect::<Vec<_>>();
+    let aux_column = arg.build_aux_column(&columns, &rand_elements);
+    assert_eq!(BaseElement::ONE, aux_column[0]);
+    assert_eq!(BaseElement::ONE, aux_column[trace_length - 1]);
+
+    // transition constraint should evaluate to zero on all steps but the last one
+    let mut main_frame = EvaluationFrame::new(4);
+    let mut aux_frame = EvaluationFrame::new(1);
+    for step in 0..trace_length - 1 {
+        for (i, column) in main_trace.iter().enumerate() {
+            main_frame.current_mut()[i] = column[step];
+            main_frame.next_mut()[i] = column[step + 1];
+        }
+        aux_frame.current_mut()[0] = aux_column[step];
+        aux_frame.next_mut()[0] = aux_column[step + 1];
+        let evaluation = arg.evaluate_transition(&main_frame, &aux_frame, &rand_elements);
+        assert_eq!(BaseElement::ZERO, evaluation);
+    }
+
+    let assertions = arg.get_assertions::<BaseElement>(trace_length);
+    assert_eq!(
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(0, trace_length - 1, BaseElement::ONE)
+        ],
+        assertions
+    );
+
+    // if the columns are not a permutation of each other, the last value should not be ONE
+    main_trace[2][0] = BaseElement::new(42);
+    let columns = main_trace.iter().map(|c| c.as_slice()).collect::<Vec<_>>();
+    let aux_column = arg.build_aux_column(&columns, &rand_elements);
+    assert_ne!(BaseElement::ONE, aux_column[trace_length - 1]);
+}
+
+#[test]
+#[should_panic(
+    expected = "all permutation argument tuples must have width 2, but a tuple of width 1 was found"
+)]
+fn permutation_arg_tuple_width_mismatch() {
+    let _ = PermutationArgBuilder::new(0).with_left(vec![0, 1]).with_right(vec![2]).build();
+}
+
+// RANGE CHECKS
+// ================================================================================================
+
+#[test]
+fn range_check() {
+    // column 0 contains u16 values, column 1 contains u32 values decomposed into columns 2 and 3;
+    // columns 4 and 5 hold the range table and its multiplicities
+    let trace_length = MIN_RANGE_CHECK_TRACE_LENGTH;
+    let range_check = RangeCheckBuilder::new(4, 5, 0).check_u16(0).check_u32(1, [2, 3]).build();
+    assert_eq!(2, range_check.num_main_constraints());
+    assert_eq!(
+        vec![TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(1)],
+        range_check.main_constraint_degrees()
+    );
+    assert_eq!(TransitionConstraintDegree::new(5), range_check.aux_constraint_degree());
+
+    let mut main_trace = vec![vec![BaseElement::ZERO; trace_length]; 6];
+    main_trace[0] =
+        (0..trace_length as u64).map(|i| BaseElement::new((i * 7919) % 65536)).collect();
+    main_trace[1] = (0..trace_length as u64)
+        .map(|i| BaseElement::new((i * 104729 * 7919) % (1 << 32)))
+        .collect();
+    range_check.fill_main_columns(&mut main_trace);
+    assert_eq!(BaseElement::new(65535), main_trace[4][trace_length - 1]);
+
+    let rand_elements = rand_vector::<BaseElement>(range_check.num_rand_elements());
+    let columns = main_trace.iter().map(|c| c.as_slice()).collect::<Vec<_>>();
+    let aux_column = range_check.build_aux_column(&columns, &rand_elements);
+    assert_eq!(BaseElement::ZERO, aux_column[0]);
+    assert_eq!(BaseElement::ZERO, aux_column[trace_length - 1]);
+
+    // transition constraints should evaluate to zeros on all steps but the last one
+    let mut main_frame = EvaluationFrame::new(6);
+    let mut aux_frame = EvaluationFrame::new(1);
+    let mut main_result = [BaseElement::ONE; 2];
+    for step in (0..trace_length - 1).step_by(97).chain([65534, 65535, 65536, trace_length - 2]) {
+        for (i, column) in main_trace.iter().enumerate() {
+            main_frame.current_mut()[i] = column[step];
+            main_frame.next_mut()[i] = column[step + 1];
+        }
+        aux_frame.current_mut()[0] = aux_column[step];
+        aux_frame.next_mut()[0] = aux_column[step + 1];
+
+        range_check.evaluate_main_transition(&main_frame, &mut main_result);
+        assert_eq!([BaseElement::ZERO; 2], main_result);
+        let evaluation =
+            range_check.evaluate_aux_transition(&main_frame, &aux_frame, &rand_elements);
+        assert_eq!(BaseElement::ZERO, evaluation);
+    }
+
+    // if a value is out of range, the running sum should not end with zero
+    main_trace[0][5] = BaseElement::new(65536);
+    let columns = main_trace.iter().map(|c| c.as_slice()).collect::<Vec<_>>();
+    let aux_column = range_check.build_aux_column(&columns, &rand_elements);
+    assert_ne!(BaseElement::ZERO, aux_column[trace_length - 1]);
+}
+
+#[test]
+#[should_panic(expected = "value 65536 in column 0 at step 3 is not a u16")]
+fn range_check_value_out_of_range() {
+    let trace_length = MIN_RANGE_CHECK_TRACE_LENGTH;
+    let range_check = RangeCheckBuilder::new(1, 2, 0).check_u16(0).build();
+    let mut main_trace = vec![vec![BaseElement::ZERO; trace_length]; 3];
+    main_trace[0][3] = BaseElement::new(65536);
+    range_check.fill_main_columns(&mut main_trace);
+}
+
+// AUXILIARY SEGMENT RANDOMNESS
+// ================================================================================================
+
+#[test]
+fn aux_rand_elements_reader() {
+    let segment_elements: Vec<BaseElement> = rand_vector(4);
+    let mut rand_elements = AuxTraceRandElements::new();
+    rand_elements.add_segment_elements(segment_elements.clone());
+    assert_eq!(1, rand_elements.num_segments());
+
+    // elements are handed out in order
+    let mut reader = rand_elements.segment_reader(0);
+    assert_eq!(&segment_elements[..3], reader.read_elements(3));
+    assert_eq!(1, reader.num_remaining());
+    assert_eq!(segment_elements[3], reader.read_element());
+    reader.finish();
+}
+
+#[test]
+#[should_panic(expected = "expected at least 2 remaining random elements, but only 1 remain")]
+fn aux_rand_elements_reader_exhausted() {
+    let segment_elements: Vec<BaseElement> = rand_vector(3);
+    let mut reader = AuxRandElementsReader::new(&segment_elements);
+    reader.read_elements(2);
+    reader.read_elements(2);
+}
+
+#[test]
+#[should_panic(expected = "1 random elements were not consumed")]
+fn aux_rand_elements_reader_unconsumed() {
+    let segment_elements: Vec<BaseElement> = rand_vector(3);
+    let mut reader = AuxRandElementsReader::new(&segment_elements);
+    reader.read_elements(2);
+    reader.finish();
+}
+
+// COMPOSITION COEFFICIENTS
+// ================================================================================================
+
+#[test]
+fn get_composition_coefficients_algebraic() {
+    let assertions = vec![
+        Assertion::single(0, 0, BaseElement::ONE),
+        Assertion::single(1, 0, BaseElement::ONE),
+        Assertion::single(2, 0, BaseElement::ONE),
+    ];
+    let mut air = MockAir::with_assertions(assertions, 16);
+    air.context.options = air.options().clone().with_batching_method(BatchingMethod::Algebraic);
+
+    // constraint composition coefficients are powers of a single random value
+    let mut prng = build_prng();
+    let alpha: BaseElement = prng.draw().unwrap();
+    let coefficients = air.get_constraint_composition_coefficients(&mut build_prng()).unwrap();
+    let expected = get_power_series(alpha, 4);
+    assert_eq!(expected[..1], coefficients.transition);
+    assert_eq!(expected[1..], coefficients.boundary);
+
+    // DEEP composition coefficients are powers of a single random value
+    let num_columns = air.context().num_constraint_composition_columns();
+    let coefficients = air.get_deep_composition_coefficients(&mut build_prng()).unwrap();
+    let expected = get_power_series(alpha, 4 + num_columns);
+    assert_eq!(expected[..4], coefficients.trace);
+    assert_eq!(expected[4..], coefficients.constraints);
+
+    // with linear batching, each coefficient is drawn independently
+    air.context.options = air.options().clone().with_batching_method(BatchingMethod::Linear);
+    let coefficients: ConstraintCompositionCoefficients<BaseElement> =
+        air.get_constraint_composition_coefficients(&mut build_prng()).unwrap();
+    assert_eq!(alpha, coefficients.transition[0]);
+    assert_ne!(alpha.square(), coefficients.boundary[1]);
+}
+
+#[test]
+fn composition_coefficient_draws() {
+    let assertions = vec![
+        Assertion::single(0, 0, BaseElement::ONE),
+        Assertion::single(1, 0, BaseElement::ONE),
+        Assertion::single(2, 0, BaseElement::ONE),
+    ];
+    let mut air = MockAir::with_assertions(assertions, 16);
+    let num_columns = air.context().num_constraint_composition_columns();
+
+    // with linear batching, a value is drawn for every constraint and every DEEP term
+    assert_eq!(1 + 3 + 4 + num_columns, air.context().num_coefficient_draws());
+
+    // with algebraic batching, a single value is drawn for each composition polynomial
+    air.context.options = air.options().clone().with_batching_method(BatchingMethod::Algebraic);
+    assert_eq!(2, air.context().num_coefficient_draws());
+}
+
+// ROW HASH STRATEGIES
+// ================================================================================================
+
+#[test]
+fn row_hash_strategies() {
+    type H = Blake3_256<BaseElement>;
+    let row: Vec<BaseElement> = rand_vector(5);
+    let full = H::hash_elements(&row);
+    assert_eq!(full, RowHashStrategy::FullRow.hash_row::<H, _>(&row));
+
+    // a single group covering the entire row is the same as hashing the full row
+    assert_eq!(full, RowHashStrategy::ColumnGroups(5).hash_row::<H, _>(&row));
+    assert_eq!(full, RowHashStrategy::ColumnGroups(8).hash_row::<H, _>(&row));
+
+    // group digests are merged from left to right
+    let d0 = H::hash_elements(&row[..2]);
+    let d1 = H::hash_elements(&row[2..4]);
+    let d2 = H::hash_elements(&row[4..]);
+    let expected = H::merge(&[H::merge(&[d0, d1]), d2]);
+    assert_eq!(expected, RowHashStrategy::ColumnGroups(2).hash_row::<H, _>(&row));
+
+    let expected = H::merge_with_int(full, 42);
+    assert_eq!(expected, RowHashStrategy::DomainSeparated(42).hash_row::<H, _>(&row));
+}
+
+#[test]
+#[should_panic(expected = "column group size must be greater than zero")]
+fn row_hash_strategy_empty_groups() {
+    let _ = build_context_with_degrees::<BaseElement>(16, vec![2])
+        .set_row_hash_strategy(RowHashStrategy::ColumnGroups(0));
+}
+
+// LAGRANGE KERNEL
+// ================================================================================================
+
+#[test]
+fn lagrange_kernel_constraints() {
+    let trace_length: usize = 16;
+    let rand_elements: Vec<BaseElement> = rand_vector(4);
+
+    // build the Lagrange kernel column directly from its definition and interpolate it
+    let column: Vec<BaseElement> = (0..trace_length)
+        .map(|row_idx| {
+            rand_elements.iter().enumerate().fold(BaseElement::ONE, |acc, (bit_idx, &r)| {
+                if row_idx & (1 << bit_idx) == 0 {
+                    acc * (BaseElement::ONE - r)
+                } else {
+                    acc * r
+                }
+            })
+        })
+        .collect();
+    let poly = build_periodic_column_poly(&column);
+    let g = BaseElement::get_root_of_unity(trace_length.ilog2());
+    let domain = get_power_series(g, trace_length);
+    assert_eq!(
+        lagrange_kernel_boundary_value(&rand_elements),
+        polynom::eval(&poly, BaseElement::ONE)
+    );
+
+    // transition constraints hold on the rows on which they are enforced
+    let mut result = vec![BaseElement::ZERO; 4];
+    for (row_idx, &x) in domain.iter().enumerate() {
+        let frame = LagrangeKernelEvaluationFrame::from_lagrange_kernel_column_poly(&poly, x);
+        assert_eq!(4, frame.num_rand_elements());
+        frame.evaluate_transitions(&rand_elements, &mut result);
+        for (i, &value) in result.iter().enumerate() {
+            let divisor = lagrange_kernel_transition_divisor::<BaseElement>(trace_length, i);
+            assert_eq!(row_idx % (2 << i) == 0, divisor.vanishes_at(x));
+            if divisor.vanishes_at(x) {
+                assert_eq!(BaseElement::ZERO, value);
+            }
+        }
+    }
+
+    // and do not hold at a random point
+    let frame =
+        LagrangeKernelEvaluationFrame::from_lagrange_kernel_column_poly(&poly, rand_value());
+    frame.evaluate_transitions(&rand_elements, &mut result);
+    assert!(result.iter().all(|&value| value != BaseElement::ZERO));
+}
+
+// AGGREGATE AIR
+// ================================================================================================
+
+#[test]
+fn aggregate_air() {
+    let trace_length = 16;
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let instance = DivisorAir::new(TraceInfo::new(4, trace_length), (), options.clone());
+    let pub_inputs = AggregatePublicInputs::new(vec![(), ()]);
+    let air = AggregateAir::<DivisorAir>::new(TraceInfo::new(8, trace_length), pub_inputs, options);
+    let context = air.context();
+
+    // custom divisors are shifted to the constraints of each instance, and the constraint
+    // evaluation domain is extended in the same way as for a single instance
+    let divisor = DivisorAir::divisor(trace_length);
+    let default_divisor = ConstraintDivisor::from_transition(trace_length, 1);
+    assert_eq!(default_divisor, context.get_transition_constraint_divisor(0));
+    assert_eq!(divisor, context.get_transition_constraint_divisor(1));
+    assert_eq!(default_divisor, context.get_transition_constraint_divisor(2));
+    assert_eq!(divisor, context.get_transition_constraint_divisor(3));
+    assert_eq!(instance.context().ce_domain_size(), context.ce_domain_size());
+    assert_eq!(trace_length * 4, context.ce_domain_size());
+
+    // column groups are merged, and the other settings are carried over
+    assert_eq!(Some(&ColumnGroup::new(vec![3, 7], 4)), context.column_group());
+    assert_eq!(RowHashStrategy::DomainSeparated(7), context.row_hash_strategy());
+    assert_eq!(3, context.num_constraint_composition_columns());
+
+    // auxiliary periodic columns of all instances are placed after the main periodic columns
+    assert_eq!(4, air.get_periodic_columns().len());
+    assert_eq!(2, air.num_main_periodic_columns());
+
+    // each instance is evaluated over its own columns and main periodic values
+    let frame = EvaluationFrame::from_rows(rand_vector(8), rand_vector(8));
+    let periodic_values = rand_vector::<BaseElement>(2);
+    let mut result = vec![BaseElement::ZERO; 4];
+    air.evaluate_transition(&frame, &periodic_values, &mut result);
+    for i in 0..2 {
+        let columns = i * 4..(i + 1) * 4;
+        let instance_frame = EvaluationFrame::from_rows(
+            frame.current()[columns.clone()].to_vec(),
+            frame.next()[columns].to_vec(),
+        );
+        let mut expected = vec![BaseElement::ZERO; 2];
+        air.instances()[i].evaluate_transition(
+            &instance_frame,
+            &periodic_values[i..i + 1],
+            &mut expected,
+        );
+        assert_eq!(expected, result[i * 2..(i + 1) * 2]);
+    }
+}
+
 // MOCK AIR
 // ================================================================================================
 
 struct MockAir {
     context: AirContext<BaseElement>,
     assertions: Vec<Assertion<BaseElement>>,
-    periodic_columns: Vec<Vec<BaseElement>>,
+    periodic_columns: Vec<PeriodicColumn<BaseElement>>,
 }
 
 impl MockAir {
     pub fn with_periodic_columns(
         column_values: Vec<Vec<BaseElement>>,
         trace_length: usize,
+    ) -> Self {
+        let columns = column_values.into_iter().map(PeriodicColumn::new).collect();
+        Self::with_periodic_column_descriptors(columns, trace_length)
+    }
+
+    pub fn with_periodic_column_descriptors(
+        columns: Vec<PeriodicColumn<BaseElement>>,
+        trace_length: usize,
     ) -> Self {
         let mut result = Self::new(
-            TraceInfo::with_meta(4, trace_length, vec![1]),
+            TraceInfo::with_meta(
+                4,
+                trace_length,
+                TraceMetadata::new().with("num_assertions", 1_u64),
+            ),
             (),
             ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
         );
-        result.periodic_columns = column_values;
+        result.periodic_columns = columns;
         result
     }
 
     pub fn with_assertions(assertions: Vec<Assertion<BaseElement>>, trace_length: usize) -> Self {
         let mut result = Self::new(
-            TraceInfo::with_meta(4, trace_length, vec![assertions.len() as u8]),
+            TraceInfo::with_meta(
+                4,
+                trace_length,
+                TraceMetadata::new().with("num_assertions", assertions.len() as u64),
+            ),
             (),
             ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
         );
@@ -225,7 +833,7 @@ impl Air for MockAir {
     type PublicInputs = ();
 
     fn new(trace_info: TraceInfo, _pub_inputs: (), _options: ProofOptions) -> Self {
-        let num_assertions = trace_info.meta()[0] as usize;
+        let num_assertions = trace_info.meta().get_u64("num_assertions").unwrap() as usize;
         let context = build_context(trace_info.length(), trace_info.width(), num_assertions);
         MockAir {
             context,
@@ -238,7 +846,7 @@ impl Air for MockAir {
         &self.context
     }
 
-    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+    fn get_periodic_columns(&self) -> Vec<PeriodicColumn<Self::BaseField>> {
         self.periodic_columns.clone()
     }
 
@@ -255,6 +863,63 @@ impl Air for MockAir {
     }
 }
 
+/// An AIR with a custom transition divisor, a column group, an auxiliary periodic column defined
+/// before the main one, and non-default row hash strategy and number of composition columns.
+struct DivisorAir {
+    context: AirContext<BaseElement>,
+}
+
+impl DivisorAir {
+    fn divisor(trace_length: usize) -> ConstraintDivisor<BaseElement> {
+        ConstraintDivisor::from_periodic_steps(trace_length, 0, 16)
+    }
+}
+
+impl Air for DivisorAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let trace_length = trace_info.length();
+        let degrees = vec![TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(3)];
+        let context = AirContext::new(trace_info, degrees, 1, options)
+            .set_main_transition_divisor(1, Self::divisor(trace_length))
+            .set_row_hash_strategy(RowHashStrategy::DomainSeparated(7))
+            .set_column_group(ColumnGroup::new(vec![3], 4))
+            .set_num_constraint_composition_columns(3);
+        DivisorAir { context }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn get_periodic_columns(&self) -> Vec<PeriodicColumn<Self::BaseField>> {
+        let values = vec![BaseElement::ONE, BaseElement::ZERO];
+        vec![
+            PeriodicColumn::new(values.clone()).for_aux_segments(),
+            PeriodicColumn::new(values),
+        ]
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ONE)]
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        assert_eq!(1, periodic_values.len());
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - current[1] * periodic_values[0];
+        result[1] = next[2] - current[2] * current[3] * current[3];
+    }
+}
+
 // UTILITY FUNCTIONS
 // ================================================================================================
 
@@ -269,6 +934,16 @@ pub fn build_context<B: StarkField>(
     AirContext::new(trace_info, t_degrees, num_assertions, options)
 }
 
+pub fn build_context_with_degrees<B: StarkField>(
+    trace_length: usize,
+    degrees: Vec<usize>,
+) -> AirContext<B> {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let t_degrees = degrees.into_iter().map(TransitionConstraintDegree::new).collect();
+    let trace_info = TraceInfo::new(4, trace_length);
+    AirContext::new(trace_info, t_degrees, 1, options)
+}
+
 pub fn build_prng() -> DefaultRandomCoin<Blake3_256<BaseElement>> {
     RandomCoin::new(&[BaseElement::ZERO; 32])
 }
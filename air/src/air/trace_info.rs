@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::TraceMetadata;
+use crate::TraceLayoutError;
 use math::{StarkField, ToElements};
 use utils::{
     collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
@@ -13,7 +15,7 @@ use utils::{
 // ================================================================================================
 
 /// Number of allowed auxiliary trace segments.
-const NUM_AUX_SEGMENTS: usize = 1;
+pub(crate) const NUM_AUX_SEGMENTS: usize = 1;
 
 // TRACE INFO
 // ================================================================================================
@@ -21,24 +23,25 @@ const NUM_AUX_SEGMENTS: usize = 1;
 ///
 /// Trace info consists of trace layout info, length, and optional custom metadata. Trace layout
 /// specifies the number of columns for all trace segments. Currently, a trace can consist of at
-/// most two segments. Metadata is just a vector of bytes and can store any values up to 64KB in
-/// size.
+/// most two segments. Metadata is a typed key-value map (see [TraceMetadata]) whose encoding can
+/// be up to 64KB in size.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceInfo {
     layout: TraceLayout,
     length: usize,
-    meta: Vec<u8>,
+    meta: TraceMetadata,
 }
 
 impl TraceInfo {
     /// Smallest allowed execution trace length; currently set at 8.
     pub const MIN_TRACE_LENGTH: usize = 8;
-    /// Maximum number of columns in an execution trace (across all segments); currently set at 255.
-    pub const MAX_TRACE_WIDTH: usize = 255;
-    /// Maximum number of bytes in trace metadata; currently set at 65535.
-    pub const MAX_META_LENGTH: usize = 65535;
-    /// Maximum number of random elements per auxiliary trace segment; currently set to 255.
-    pub const MAX_RAND_SEGMENT_ELEMENTS: usize = 255;
+    /// Maximum number of columns in an execution trace (across all segments); currently set at
+    /// 65535.
+    pub const MAX_TRACE_WIDTH: usize = u16::MAX as usize;
+    /// Maximum number of bytes in the encoding of trace metadata; currently set at 65535.
+    pub const MAX_META_LENGTH: usize = TraceMetadata::MAX_ENCODED_LENGTH;
+    /// Maximum number of random elements per auxiliary trace segment; currently set to 65535.
+    pub const MAX_RAND_SEGMENT_ELEMENTS: usize = u16::MAX as usize;
 
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
@@ -49,10 +52,10 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
-        Self::with_meta(width, length, vec![])
+        Self::with_meta(width, length, TraceMetadata::new())
     }
 
     /// Creates a new [TraceInfo] from the specified trace width, length, and metadata.
@@ -61,10 +64,9 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
-    /// * Length of `meta` is greater than 65535;
-    pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
+    pub fn with_meta(width: usize, length: usize, meta: TraceMetadata) -> Self {
         assert!(width > 0, "trace width must be greater than 0");
         let layout = TraceLayout::new(width, [0], [0]);
         Self::new_multi_segment(layout, length, meta)
@@ -75,9 +77,9 @@ impl TraceInfo {
     /// # Panics
     /// Panics if:
     /// * The width of the first trace segment is zero.
-    /// * Total width of all trace segments is greater than 255.
+    /// * Total width of all trace segments is greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
-    pub fn new_multi_segment(layout: TraceLayout, length: usize, meta: Vec<u8>) -> Self {
+    pub fn new_multi_segment(layout: TraceLayout, length: usize, meta: TraceMetadata) -> Self {
         assert!(
             length >= Self::MIN_TRACE_LENGTH,
             "trace length must be at least {}, but was {}",
@@ -88,12 +90,6 @@ impl TraceInfo {
             length.is_power_of_two(),
             "trace length must be a power of two, but was {length}"
         );
-        assert!(
-            meta.len() <= Self::MAX_META_LENGTH,
-            "number of metadata bytes cannot be greater than {}, but was {}",
-            Self::MAX_META_LENGTH,
-            meta.len()
-        );
         TraceInfo {
             layout,
             length,
@@ -113,7 +109,7 @@ impl TraceInfo {
 
     /// Returns the total number of columns in an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 65535.
     pub fn width(&self) -> usize {
         self.layout.main_trace_width() + self.layout().aux_trace_width()
     }
@@ -126,7 +122,7 @@ impl TraceInfo {
     }
 
     /// Returns execution trace metadata.
-    pub fn meta(&self) -> &[u8] {
+    pub fn meta(&self) -> &TraceMetadata {
         &self.meta
     }
 
@@ -163,68 +159,81 @@ pub struct TraceLayout {
 }
 
 impl TraceLayout {
-    // CONSTRUCTOR
+    // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new [TraceLayout] instantiated with the provided info.
     ///
     /// # Panics
-    /// Panics if:
+    /// Panics if the layout is not valid (see [TraceLayout::try_new()]).
+    pub fn new(
+        main_width: usize,
+        aux_widths: [usize; NUM_AUX_SEGMENTS],
+        aux_rands: [usize; NUM_AUX_SEGMENTS],
+    ) -> Self {
+        Self::try_new(main_width, aux_widths, aux_rands).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns a new [TraceLayout] instantiated with the provided info.
+    ///
+    /// # Errors
+    /// Returns an error if:
     /// * Width of the main trace segment is set to zero.
-    /// * Sum of all segment widths exceeds 255.
+    /// * Sum of all segment widths exceeds 65535.
     /// * A zero entry in auxiliary segment width array is followed by a non-zero entry.
     /// * Number of random elements for an auxiliary trace segment of non-zero width is set to zero.
     /// * Number of random elements for an auxiliary trace segment of zero width is set to non-zero.
-    /// * Number of random elements for any auxiliary trace segment is greater than 255.
-    pub fn new(
+    /// * Number of random elements for any auxiliary trace segment is greater than 65535.
+    pub fn try_new(
         main_width: usize,
         aux_widths: [usize; NUM_AUX_SEGMENTS],
         aux_rands: [usize; NUM_AUX_SEGMENTS],
-    ) -> Self {
+    ) -> Result<Self, TraceLayoutError> {
         // validate trace segment widths
-        assert!(main_width > 0, "main trace segment must consist of at least one column");
-        let full_width = main_width + aux_widths.iter().sum::<usize>();
-        assert!(
-            full_width <= TraceInfo::MAX_TRACE_WIDTH,
-            "total number of columns in the trace cannot be greater than {}, but was {}",
-            TraceInfo::MAX_TRACE_WIDTH,
-            full_width
-        );
+        if main_width == 0 {
+            return Err(TraceLayoutError::EmptyMainSegment);
+        }
+        let full_width = aux_widths
+            .iter()
+            .try_fold(main_width, |acc, &width| acc.checked_add(width))
+            .unwrap_or(usize::MAX);
+        if full_width > TraceInfo::MAX_TRACE_WIDTH {
+            return Err(TraceLayoutError::TraceTooWide(TraceInfo::MAX_TRACE_WIDTH, full_width));
+        }
 
         // validate number of random elements required by each segment
         let mut was_zero_width = false;
         let mut num_aux_segments = 0;
-        for (&width, &num_rand_elements) in aux_widths.iter().zip(aux_rands.iter()) {
+        for (i, (&width, &num_rand_elements)) in aux_widths.iter().zip(aux_rands.iter()).enumerate()
+        {
             if width != 0 {
-                assert!(
-                    !was_zero_width,
-                    "a non-empty trace segment cannot follow an empty segment"
-                );
-                assert!(
-                    num_rand_elements > 0,
-                    "number of random elements for a non-empty trace segment must be greater than zero"
-                );
+                if was_zero_width {
+                    return Err(TraceLayoutError::NonEmptySegmentAfterEmpty(i));
+                }
+                if num_rand_elements == 0 {
+                    return Err(TraceLayoutError::MissingRandElements(i));
+                }
                 num_aux_segments += 1;
             } else {
-                assert!(
-                    num_rand_elements == 0,
-                    "number of random elements for an empty trace segment must be zero"
-                );
+                if num_rand_elements != 0 {
+                    return Err(TraceLayoutError::UnexpectedRandElements(i));
+                }
                 was_zero_width = true;
             }
-            assert!(
-                num_rand_elements <= TraceInfo::MAX_RAND_SEGMENT_ELEMENTS,
-                "number of random elements required by a segment cannot exceed {}, but was {}",
-                TraceInfo::MAX_RAND_SEGMENT_ELEMENTS,
-                num_rand_elements
-            );
+            if num_rand_elements > TraceInfo::MAX_RAND_SEGMENT_ELEMENTS {
+                return Err(TraceLayoutError::TooManyRandElements(
+                    i,
+                    TraceInfo::MAX_RAND_SEGMENT_ELEMENTS,
+                    num_rand_elements,
+                ));
+            }
         }
 
-        Self {
+        Ok(Self {
             main_segment_width: main_width,
             aux_segment_widths: aux_widths,
             aux_segment_rands: aux_rands,
             num_aux_segments,
-        }
+        })
     }
 
     // PUBLIC ACCESSORS
@@ -232,7 +241,7 @@ impl TraceLayout {
 
     /// Returns the number of columns in the main segment of an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 65535.
     pub fn main_trace_width(&self) -> usize {
         self.main_segment_width
     }
@@ -271,20 +280,22 @@ impl<E: StarkField> ToElements<E> for TraceLayout {
         let mut result = Vec::new();
 
         // main segment width, number of auxiliary segments, and parameters of the first auxiliary
-        // segment (if present) go into the first field element; we assume that each parameter can
-        // be encoded in 8 bits (which is enforced by the constructor)
-        let mut buf = self.main_segment_width as u32;
-        buf = (buf << 8) | self.num_aux_segments as u32;
+        // segment (if present) go into the first field element; we assume that the number of
+        // auxiliary segments can be encoded in 8 bits and all other parameters can be encoded in
+        // 16 bits (which is enforced by the constructor). thus, the element requires at most 56
+        // bits, and fits into all supported fields.
+        let mut buf = self.main_segment_width as u64;
+        buf = (buf << 8) | self.num_aux_segments as u64;
         if self.num_aux_segments == 1 {
-            buf = (buf << 8) | self.aux_segment_widths[0] as u32;
-            buf = (buf << 8) | self.aux_segment_rands[0] as u32;
+            buf = (buf << 16) | self.aux_segment_widths[0] as u64;
+            buf = (buf << 16) | self.aux_segment_rands[0] as u64;
         }
         result.push(E::from(buf));
 
         // parameters of all subsequent auxiliary segments go into additional elements
         for i in 1..self.num_aux_segments {
-            buf = self.aux_segment_widths[i] as u32;
-            buf = (buf << 8) | self.aux_segment_rands[i] as u32;
+            buf = self.aux_segment_widths[i] as u64;
+            buf = (buf << 16) | self.aux_segment_rands[i] as u64;
             result.push(E::from(buf));
         }
 
@@ -295,17 +306,13 @@ impl<E: StarkField> ToElements<E> for TraceLayout {
 impl Serializable for TraceLayout {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_u8(self.main_segment_width as u8);
+        // all parameters fit into 16 bits (which is enforced by the constructor)
+        target.write_u16(self.main_segment_width as u16);
         for &w in self.aux_segment_widths.iter() {
-            debug_assert!(w <= u8::MAX as usize, "aux segment width does not fit into u8 value");
-            target.write_u8(w as u8);
+            target.write_u16(w as u16);
         }
         for &rc in self.aux_segment_rands.iter() {
-            debug_assert!(
-                rc <= u8::MAX as usize,
-                "aux segment random element count does not fit into u8 value"
-            );
-            target.write_u8(rc as u8);
+            target.write_u16(rc as u16);
         }
     }
 }
@@ -317,61 +324,18 @@ impl Deserializable for TraceLayout {
     /// Returns an error of a valid [TraceLayout] struct could not be read from the specified
     /// `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let main_width = source.read_u8()? as usize;
-        if main_width == 0 {
-            return Err(DeserializationError::InvalidValue(
-                "main trace segment width must be greater than zero".to_string(),
-            ));
-        }
-
-        // read and validate auxiliary trace segment widths
-        let mut was_zero_width = false;
+        let main_width = source.read_u16()? as usize;
         let mut aux_widths = [0; NUM_AUX_SEGMENTS];
         for width in aux_widths.iter_mut() {
-            *width = source.read_u8()? as usize;
-            if *width != 0 {
-                if was_zero_width {
-                    return Err(DeserializationError::InvalidValue(
-                        "a non-empty trace segment cannot follow an empty segment".to_string(),
-                    ));
-                }
-            } else {
-                was_zero_width = true;
-            }
-        }
-
-        let full_trace_width = main_width + aux_widths.iter().sum::<usize>();
-        if full_trace_width >= TraceInfo::MAX_TRACE_WIDTH {
-            return Err(DeserializationError::InvalidValue(format!(
-                "full trace width cannot be greater than {}, but was {}",
-                TraceInfo::MAX_TRACE_WIDTH,
-                full_trace_width
-            )));
+            *width = source.read_u16()? as usize;
         }
-
-        // read and validate number of random elements for each auxiliary trace segment
         let mut aux_rands = [0; NUM_AUX_SEGMENTS];
-        for (num_rand_elements, &width) in aux_rands.iter_mut().zip(aux_widths.iter()) {
-            *num_rand_elements = source.read_u8()? as usize;
-            if width == 0 && *num_rand_elements != 0 {
-                return Err(DeserializationError::InvalidValue(
-                    "an empty trace segment cannot require random elements".to_string(),
-                ));
-            } else if width != 0 && *num_rand_elements == 0 {
-                return Err(DeserializationError::InvalidValue(
-                    "a non-empty trace segment must require at least one random element"
-                        .to_string(),
-                ));
-            } else if *num_rand_elements > TraceInfo::MAX_RAND_SEGMENT_ELEMENTS {
-                return Err(DeserializationError::InvalidValue(format!(
-                    "number of random elements required by a segment cannot exceed {}, but was {}",
-                    TraceInfo::MAX_RAND_SEGMENT_ELEMENTS,
-                    *num_rand_elements
-                )));
-            }
+        for num_rand_elements in aux_rands.iter_mut() {
+            *num_rand_elements = source.read_u16()? as usize;
         }
 
-        Ok(TraceLayout::new(main_width, aux_widths, aux_rands))
+        TraceLayout::try_new(main_width, aux_widths, aux_rands)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
     }
 }
 
@@ -380,8 +344,10 @@ impl Deserializable for TraceLayout {
 
 #[cfg(test)]
 mod tests {
-    use super::{ToElements, TraceLayout};
+    use super::{ToElements, TraceInfo, TraceLayout};
+    use crate::TraceLayoutError;
     use math::fields::f64::BaseElement;
+    use utils::{Deserializable, Serializable};
 
     #[test]
     fn trace_layout_to_elements() {
@@ -389,7 +355,7 @@ mod tests {
         let main_width = 20;
         let num_aux_segments = 0;
 
-        let expected = u32::from_le_bytes([num_aux_segments, main_width as u8, 0, 0]);
+        let expected = u64::from_le_bytes([num_aux_segments, main_width as u8, 0, 0, 0, 0, 0, 0]);
         let expected = vec![BaseElement::from(expected)];
 
         let layout = TraceLayout::new(main_width, [0], [0]);
@@ -401,11 +367,67 @@ mod tests {
         let aux_width = 9;
         let aux_rands = 12;
 
-        let expected = u32::from_le_bytes([aux_rands, aux_width, num_aux_segments, main_width]);
+        let expected =
+            u64::from_le_bytes([aux_rands, 0, aux_width, 0, num_aux_segments, main_width, 0, 0]);
         let expected = vec![BaseElement::from(expected)];
 
         let layout =
             TraceLayout::new(main_width as usize, [aux_width as usize], [aux_rands as usize]);
         assert_eq!(expected, layout.to_elements());
+
+        // --- test wide trace with one auxiliary segment ---------------------
+        let layout = TraceLayout::new(300, [400], [500]);
+        let expected = (((300_u64 << 8 | 1) << 16 | 400) << 16) | 500;
+        assert_eq!(vec![BaseElement::from(expected)], layout.to_elements());
+    }
+
+    #[test]
+    fn trace_layout_serialization() {
+        for layout in [
+            TraceLayout::new(20, [0], [0]),
+            TraceLayout::new(20, [9], [12]),
+            TraceLayout::new(300, [400], [500]),
+            TraceLayout::new(
+                TraceInfo::MAX_TRACE_WIDTH - 1,
+                [1],
+                [TraceInfo::MAX_RAND_SEGMENT_ELEMENTS],
+            ),
+        ] {
+            let bytes = layout.to_bytes();
+            assert_eq!(layout, TraceLayout::read_from_bytes(&bytes).unwrap());
+        }
+
+        // invalid layouts are rejected during deserialization
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&u16::MAX.to_le_bytes());
+        bytes.extend_from_slice(&1_u16.to_le_bytes());
+        bytes.extend_from_slice(&1_u16.to_le_bytes());
+        assert!(TraceLayout::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn trace_layout_validation() {
+        assert_eq!(Err(TraceLayoutError::EmptyMainSegment), TraceLayout::try_new(0, [0], [0]));
+        assert_eq!(
+            Err(TraceLayoutError::TraceTooWide(TraceInfo::MAX_TRACE_WIDTH, 65536)),
+            TraceLayout::try_new(65000, [536], [1])
+        );
+        assert_eq!(
+            Err(TraceLayoutError::MissingRandElements(0)),
+            TraceLayout::try_new(8, [4], [0])
+        );
+        assert_eq!(
+            Err(TraceLayoutError::UnexpectedRandElements(0)),
+            TraceLayout::try_new(8, [0], [4])
+        );
+        assert_eq!(
+            Err(TraceLayoutError::TooManyRandElements(
+                0,
+                TraceInfo::MAX_RAND_SEGMENT_ELEMENTS,
+                65536
+            )),
+            TraceLayout::try_new(8, [4], [65536])
+        );
+        assert!(TraceLayout::try_new(TraceInfo::MAX_TRACE_WIDTH, [0], [0]).is_ok());
     }
 }
@@ -0,0 +1,449 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Type tags used in the canonical encoding of metadata values.
+const BOOL_TAG: u8 = 0;
+const U64_TAG: u8 = 1;
+const BYTES_TAG: u8 = 2;
+const STRING_TAG: u8 = 3;
+
+// TRACE METADATA
+// ================================================================================================
+/// Custom metadata of an execution trace.
+///
+/// Metadata is a map of typed values (see [TraceMetaValue]) keyed by strings, and can be used to
+/// convey structured context about the execution trace (e.g., a program hash or a VM version) to
+/// the verifier. Metadata is included into the proof context, and thus, is bound into the
+/// transcript of the protocol.
+///
+/// Metadata has a canonical binary encoding: entries are encoded in the order of their keys, and
+/// each entry is encoded as the key (prefixed by its length) followed by the value (prefixed by
+/// its type tag). The encoding of all entries is limited to [TraceMetadata::MAX_ENCODED_LENGTH]
+/// bytes, and each key is limited to [TraceMetadata::MAX_KEY_LENGTH] bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceMetadata {
+    entries: BTreeMap<String, TraceMetaValue>,
+    encoded_length: usize,
+}
+
+impl TraceMetadata {
+    /// Maximum number of bytes in the encoding of all metadata entries; currently set at 65535.
+    pub const MAX_ENCODED_LENGTH: usize = 65535;
+    /// Maximum number of bytes in a metadata key; currently set at 255.
+    pub const MAX_KEY_LENGTH: usize = 255;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns new empty metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns this metadata with the specified value inserted under the specified key.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [TraceMetadata::insert()].
+    pub fn with<V: Into<TraceMetaValue>>(mut self, key: &str, value: V) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of entries in this metadata.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this metadata does not contain any entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of bytes in the canonical encoding of this metadata.
+    pub fn encoded_len(&self) -> usize {
+        self.encoded_length
+    }
+
+    /// Returns the value stored under the specified key, or None if there is no such value.
+    pub fn get(&self, key: &str) -> Option<&TraceMetaValue> {
+        self.entries.get(key)
+    }
+
+    /// Returns the boolean value stored under the specified key, or None if there is no such
+    /// value or the value is not a boolean.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key) {
+            Some(TraceMetaValue::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer value stored under the specified key, or None if there is no such
+    /// value or the value is not an integer.
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        match self.get(key) {
+            Some(TraceMetaValue::U64(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte string stored under the specified key, or None if there is no such value
+    /// or the value is not a byte string.
+    pub fn get_bytes(&self, key: &str) -> Option<&[u8]> {
+        match self.get(key) {
+            Some(TraceMetaValue::Bytes(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string stored under the specified key, or None if there is no such value or
+    /// the value is not a string.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(TraceMetaValue::String(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over all entries of this metadata in the order of their keys.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TraceMetaValue)> {
+        self.entries.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    // PUBLIC MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts the specified value under the specified key, replacing the previous value stored
+    /// under this key (if any).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The key is empty or is longer than 255 bytes.
+    /// * The encoding of the metadata would exceed 65535 bytes after the value is inserted.
+    pub fn insert<V: Into<TraceMetaValue>>(&mut self, key: &str, value: V) {
+        assert!(!key.is_empty(), "metadata key cannot be empty");
+        assert!(
+            key.len() <= Self::MAX_KEY_LENGTH,
+            "metadata key cannot be longer than {} bytes, but was {} bytes",
+            Self::MAX_KEY_LENGTH,
+            key.len()
+        );
+
+        let value = value.into();
+        let mut encoded_length = self.encoded_length + entry_encoded_len(key, &value);
+        if let Some(old_value) = self.entries.get(key) {
+            encoded_length -= entry_encoded_len(key, old_value);
+        }
+        assert!(
+            encoded_length <= Self::MAX_ENCODED_LENGTH,
+            "encoded metadata cannot be longer than {} bytes, but was {} bytes",
+            Self::MAX_ENCODED_LENGTH,
+            encoded_length
+        );
+
+        self.entries.insert(key.to_string(), value);
+        self.encoded_length = encoded_length;
+    }
+
+    // CANONICAL ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the canonical encoding of all entries of this metadata.
+    ///
+    /// The encoding of empty metadata is an empty vector.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.encoded_length);
+        for (key, value) in self.entries.iter() {
+            result.write_u8(key.len() as u8);
+            result.write_bytes(key.as_bytes());
+            value.write_into(&mut result);
+        }
+        result
+    }
+
+    /// Parses metadata from its canonical encoding.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a canonical encoding of metadata. This
+    /// includes the cases when entries are not sorted by key or contain duplicate keys.
+    pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, DeserializationError> {
+        if bytes.len() > Self::MAX_ENCODED_LENGTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "encoded metadata cannot be longer than {} bytes, but was {} bytes",
+                Self::MAX_ENCODED_LENGTH,
+                bytes.len()
+            )));
+        }
+
+        let mut source = SliceReader::new(bytes);
+        let mut result = Self::new();
+        let mut last_key: Option<String> = None;
+        while source.has_more_bytes() {
+            let key_len = source.read_u8()? as usize;
+            if key_len == 0 {
+                return Err(DeserializationError::InvalidValue(
+                    "metadata key cannot be empty".to_string(),
+                ));
+            }
+            let key = read_string(&mut source, key_len)?;
+            if let Some(last_key) = last_key {
+                if key <= last_key {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "metadata keys must be sorted and unique, but key '{key}' follows '{last_key}'"
+                    )));
+                }
+            }
+            let value = TraceMetaValue::read_from(&mut source)?;
+            result.encoded_length += entry_encoded_len(&key, &value);
+            result.entries.insert(key.clone(), value);
+            last_key = Some(key);
+        }
+
+        Ok(result)
+    }
+}
+
+impl Serializable for TraceMetadata {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// The canonical encoding of the metadata is prefixed with its length.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(self.encoded_length as u16);
+        target.write_bytes(&self.to_canonical_bytes());
+    }
+}
+
+impl Deserializable for TraceMetadata {
+    /// Reads metadata from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid canonical encoding of metadata could not be read from the
+    /// specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_bytes = source.read_u16()? as usize;
+        let bytes = source.read_vec(num_bytes)?;
+        Self::from_canonical_bytes(&bytes)
+    }
+}
+
+// TRACE METADATA VALUE
+// ================================================================================================
+/// A value which can be stored in [TraceMetadata].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceMetaValue {
+    /// A boolean value.
+    Bool(bool),
+    /// An unsigned 64-bit integer.
+    U64(u64),
+    /// A byte string of at most 65535 bytes.
+    Bytes(Vec<u8>),
+    /// A UTF-8 string of at most 65535 bytes.
+    String(String),
+}
+
+impl TraceMetaValue {
+    /// Returns the number of bytes in the canonical encoding of this value (including its type
+    /// tag).
+    fn encoded_len(&self) -> usize {
+        match self {
+            Self::Bool(_) => 2,
+            Self::U64(_) => 9,
+            Self::Bytes(value) => 3 + value.len(),
+            Self::String(value) => 3 + value.len(),
+        }
+    }
+}
+
+impl Serializable for TraceMetaValue {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Bool(value) => {
+                target.write_u8(BOOL_TAG);
+                target.write_bool(*value);
+            }
+            Self::U64(value) => {
+                target.write_u8(U64_TAG);
+                target.write_u64(*value);
+            }
+            Self::Bytes(value) => {
+                target.write_u8(BYTES_TAG);
+                target.write_u16(value.len() as u16);
+                target.write_bytes(value);
+            }
+            Self::String(value) => {
+                target.write_u8(STRING_TAG);
+                target.write_u16(value.len() as u16);
+                target.write_bytes(value.as_bytes());
+            }
+        }
+    }
+}
+
+impl Deserializable for TraceMetaValue {
+    /// Reads a metadata value from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid value could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            BOOL_TAG => Ok(Self::Bool(source.read_bool()?)),
+            U64_TAG => Ok(Self::U64(source.read_u64()?)),
+            BYTES_TAG => {
+                let len = source.read_u16()? as usize;
+                Ok(Self::Bytes(source.read_vec(len)?))
+            }
+            STRING_TAG => {
+                let len = source.read_u16()? as usize;
+                Ok(Self::String(read_string(source, len)?))
+            }
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid metadata value type tag: {tag}"
+            ))),
+        }
+    }
+}
+
+impl From<bool> for TraceMetaValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<u64> for TraceMetaValue {
+    fn from(value: u64) -> Self {
+        Self::U64(value)
+    }
+}
+
+impl From<Vec<u8>> for TraceMetaValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+impl From<&[u8]> for TraceMetaValue {
+    fn from(value: &[u8]) -> Self {
+        Self::Bytes(value.to_vec())
+    }
+}
+
+impl From<String> for TraceMetaValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for TraceMetaValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of bytes in the canonical encoding of a metadata entry.
+fn entry_encoded_len(key: &str, value: &TraceMetaValue) -> usize {
+    1 + key.len() + value.encoded_len()
+}
+
+/// Reads a UTF-8 string of the specified length from the `source`.
+fn read_string<R: ByteReader>(source: &mut R, len: usize) -> Result<String, DeserializationError> {
+    let bytes = source.read_vec(len)?;
+    String::from_utf8(bytes).map_err(|_| {
+        DeserializationError::InvalidValue("metadata string is not valid UTF-8".to_string())
+    })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{TraceMetaValue, TraceMetadata};
+    use utils::{Deserializable, Serializable};
+
+    #[test]
+    fn trace_metadata_serialization() {
+        let meta = TraceMetadata::new()
+            .with("version", 3_u64)
+            .with("program", "fib")
+            .with("hash", vec![1_u8, 2, 3])
+            .with("debug", false);
+
+        assert_eq!(4, meta.len());
+        assert_eq!(Some(3), meta.get_u64("version"));
+        assert_eq!(Some("fib"), meta.get_str("program"));
+        assert_eq!(Some(&[1_u8, 2, 3][..]), meta.get_bytes("hash"));
+        assert_eq!(Some(false), meta.get_bool("debug"));
+        assert_eq!(None, meta.get_u64("program"));
+        assert_eq!(None, meta.get("missing"));
+
+        let bytes = meta.to_bytes();
+        assert_eq!(meta.encoded_len() + 2, bytes.len());
+        assert_eq!(meta, TraceMetadata::read_from_bytes(&bytes).unwrap());
+
+        // empty metadata is encoded as an empty byte string
+        let meta = TraceMetadata::new();
+        assert!(meta.to_canonical_bytes().is_empty());
+        assert_eq!(meta, TraceMetadata::read_from_bytes(&meta.to_bytes()).unwrap());
+    }
+
+    #[test]
+    fn trace_metadata_canonical_encoding() {
+        // encoding does not depend on the order of insertion
+        let meta1 = TraceMetadata::new().with("a", 1_u64).with("b", true);
+        let meta2 = TraceMetadata::new().with("b", true).with("a", 1_u64);
+        assert_eq!(meta1.to_canonical_bytes(), meta2.to_canonical_bytes());
+
+        // replacing a value updates the encoded length
+        let meta3 = meta1.clone().with("a", "abc");
+        assert_eq!(Some(&TraceMetaValue::String("abc".into())), meta3.get("a"));
+        assert_eq!(meta3.to_canonical_bytes().len(), meta3.encoded_len());
+
+        // entries which are not sorted by key are rejected
+        let mut bytes = vec![1_u8, b'b'];
+        TraceMetaValue::Bool(true).write_into(&mut bytes);
+        bytes.extend_from_slice(&[1_u8, b'a']);
+        TraceMetaValue::U64(1).write_into(&mut bytes);
+        assert!(TraceMetadata::from_canonical_bytes(&bytes).is_err());
+
+        // duplicate keys are rejected
+        let mut bytes = meta1.to_canonical_bytes();
+        bytes.extend_from_slice(&[1_u8, b'b']);
+        TraceMetaValue::Bool(true).write_into(&mut bytes);
+        assert!(TraceMetadata::from_canonical_bytes(&bytes).is_err());
+
+        // unknown value tags are rejected
+        assert!(TraceMetadata::from_canonical_bytes(&[1_u8, b'a', 9]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "encoded metadata cannot be longer than 65535 bytes")]
+    fn trace_metadata_too_long() {
+        let value = vec![0_u8; 40000];
+        let _ = TraceMetadata::new().with("a", value.clone()).with("b", value);
+    }
+
+    #[test]
+    #[should_panic(expected = "metadata key cannot be longer than 255 bytes")]
+    fn trace_metadata_key_too_long() {
+        let key = "k".repeat(256);
+        let _ = TraceMetadata::new().with(&key, true);
+    }
+}
@@ -117,4 +117,16 @@ impl TransitionConstraintDegree {
         let degree_bound = self.base + self.cycles.len() - 1;
         cmp::max(degree_bound.next_power_of_two(), ProofOptions::MIN_BLOWUP_FACTOR)
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a degree descriptor for a constraint obtained by multiplying a constraint of this
+    /// degree by `degree` trace columns.
+    pub(super) fn mul_base(self, degree: usize) -> Self {
+        TransitionConstraintDegree {
+            base: self.base + degree,
+            cycles: self.cycles,
+        }
+    }
 }
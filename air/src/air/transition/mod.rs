@@ -11,6 +11,9 @@ pub use frame::EvaluationFrame;
 mod degree;
 pub use degree::TransitionConstraintDegree;
 
+mod selectors;
+pub use selectors::{SelectorColumns, SelectorFlag};
+
 // CONSTANTS
 // ================================================================================================
 
@@ -25,13 +28,17 @@ const MIN_CYCLE_LENGTH: usize = 2;
 ///   trace segments (if any).
 /// - Groupings of random composition constraint coefficients separately for the main trace segment
 ///   and for auxiliary tace segment.
-/// - Divisor of transition constraints for a computation.
+/// - Divisors of transition constraints for a computation. By default, all transition constraints
+///   share the same divisor, but individual constraints may have custom divisors (see
+///   [AirContext::set_main_transition_divisor()]).
 pub struct TransitionConstraints<E: FieldElement> {
     main_constraint_coef: Vec<E>,
     main_constraint_degrees: Vec<TransitionConstraintDegree>,
+    main_constraint_divisors: Vec<usize>,
     aux_constraint_coef: Vec<E>,
     aux_constraint_degrees: Vec<TransitionConstraintDegree>,
-    divisor: ConstraintDivisor<E::BaseField>,
+    aux_constraint_divisors: Vec<usize>,
+    divisors: Vec<ConstraintDivisor<E::BaseField>>,
 }
 
 impl<E: FieldElement> TransitionConstraints<E> {
@@ -50,23 +57,46 @@ impl<E: FieldElement> TransitionConstraints<E> {
             "number of transition constraints must match the number of composition coefficient tuples"
         );
 
-        // build constraint divisor; the same divisor applies to all transition constraints
-        let divisor = ConstraintDivisor::from_transition(
+        // build the default constraint divisor; unless a custom divisor was specified for a
+        // constraint, this divisor applies to the constraint. the default divisor is always the
+        // first one in the list of divisors.
+        let mut divisors = vec![ConstraintDivisor::from_transition(
             context.trace_len(),
             context.num_transition_exemptions(),
-        );
+        )];
+
+        // assign each constraint to a divisor, merging constraints with identical divisors into
+        // the same group
+        let mut constraint_divisors = Vec::with_capacity(context.num_transition_constraints());
+        for i in 0..context.num_transition_constraints() {
+            let divisor_idx = match context.custom_transition_divisors.get(&i) {
+                Some(divisor) => match divisors.iter().position(|d| d == divisor) {
+                    Some(idx) => idx,
+                    None => {
+                        divisors.push(divisor.clone());
+                        divisors.len() - 1
+                    }
+                },
+                None => 0,
+            };
+            constraint_divisors.push(divisor_idx);
+        }
 
         let main_constraint_degrees = context.main_transition_constraint_degrees.clone();
         let aux_constraint_degrees = context.aux_transition_constraint_degrees.clone();
 
+        let num_main_constraints = context.main_transition_constraint_degrees.len();
         let (main_constraint_coef, aux_constraint_coef) =
-            composition_coefficients.split_at(context.main_transition_constraint_degrees.len());
+            composition_coefficients.split_at(num_main_constraints);
+        let aux_constraint_divisors = constraint_divisors.split_off(num_main_constraints);
         Self {
             main_constraint_coef: main_constraint_coef.to_vec(),
             main_constraint_degrees,
+            main_constraint_divisors: constraint_divisors,
             aux_constraint_coef: aux_constraint_coef.to_vec(),
             aux_constraint_degrees,
-            divisor,
+            aux_constraint_divisors,
+            divisors,
         }
     }
 
@@ -115,9 +145,10 @@ impl<E: FieldElement> TransitionConstraints<E> {
         self.aux_constraint_coef.clone()
     }
 
-    /// Returns a divisor for transition constraints.
+    /// Returns the default divisor for transition constraints.
     ///
-    /// All transition constraints have the same divisor which has the form:
+    /// Unless a custom divisor was specified for a constraint, transition constraints have the
+    /// same divisor which has the form:
     /// $$
     /// z(x) = \frac{x^n - 1}{x - g^{n - 1}}
     /// $$
@@ -127,14 +158,40 @@ impl<E: FieldElement> TransitionConstraints<E> {
     /// This divisor specifies that transition constraints must hold on all steps of the
     /// execution trace except for the last one.
     pub fn divisor(&self) -> &ConstraintDivisor<E::BaseField> {
-        &self.divisor
+        &self.divisors[0]
+    }
+
+    /// Returns a list of all distinct divisors of transition constraints.
+    ///
+    /// The first divisor in the list is always the default divisor (see
+    /// [TransitionConstraints::divisor()]); the remaining divisors are custom divisors specified
+    /// for individual constraints.
+    pub fn divisors(&self) -> &[ConstraintDivisor<E::BaseField>] {
+        &self.divisors
+    }
+
+    /// Returns the number of distinct divisors of transition constraints.
+    pub fn num_divisors(&self) -> usize {
+        self.divisors.len()
+    }
+
+    /// Returns indexes of divisors (in the list returned from [TransitionConstraints::divisors()])
+    /// for constraints applied against the main trace segment.
+    pub fn main_constraint_divisors(&self) -> &[usize] {
+        &self.main_constraint_divisors
+    }
+
+    /// Returns indexes of divisors (in the list returned from [TransitionConstraints::divisors()])
+    /// for constraints applied against auxiliary trace segments.
+    pub fn aux_constraint_divisors(&self) -> &[usize] {
+        &self.aux_constraint_divisors
     }
 
     // CONSTRAINT COMPOSITION
     // --------------------------------------------------------------------------------------------
 
     /// Computes a linear combination of all transition constraint evaluations and divides the
-    /// result by transition constraint divisor.
+    /// result by transition constraint divisors.
     ///
     /// A transition constraint is described by a rational function of the form $\frac{C(x)}{z(x)}$,
     /// where:
@@ -143,30 +200,43 @@ impl<E: FieldElement> TransitionConstraints<E> {
     ///
     /// Thus, this function computes a linear combination of $C(x)$ evaluations.
     ///
-    /// Since, the divisor polynomial is the same for all transition constraints (see
+    /// Since, the divisor polynomial is usually the same for all transition constraints (see
     /// [ConstraintDivisor::from_transition]), we can divide the linear combination by the
-    /// divisor rather than dividing each individual $C(x)$ evaluation. This requires executing only
-    /// one division at the end.
+    /// divisor rather than dividing each individual $C(x)$ evaluation. When some constraints have
+    /// custom divisors, constraints are merged into groups with identical divisors, and only one
+    /// division per group is executed.
     pub fn combine_evaluations<F>(&self, main_evaluations: &[F], aux_evaluations: &[E], x: F) -> E
     where
         F: FieldElement<BaseField = E::BaseField>,
         E: ExtensionOf<F>,
     {
+        let mut group_results = vec![E::ZERO; self.divisors.len()];
+
         // merge constraint evaluations for the main trace segment
-        let mut result = main_evaluations
+        for ((&const_eval, &coef), &divisor_idx) in main_evaluations
             .iter()
             .zip(self.main_constraint_coef.iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef.mul_base(const_eval));
-
-        if !self.aux_constraint_coef.is_empty() {
-            result += aux_evaluations
-                .iter()
-                .zip(self.aux_constraint_coef.iter())
-                .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef * const_eval);
-        };
-        // divide out the evaluation of divisor at x and return the result
-        let z = E::from(self.divisor.evaluate_at(x));
-
-        result / z
+            .zip(self.main_constraint_divisors.iter())
+        {
+            group_results[divisor_idx] += coef.mul_base(const_eval);
+        }
+
+        // merge constraint evaluations for auxiliary trace segments
+        for ((&const_eval, &coef), &divisor_idx) in aux_evaluations
+            .iter()
+            .zip(self.aux_constraint_coef.iter())
+            .zip(self.aux_constraint_divisors.iter())
+        {
+            group_results[divisor_idx] += coef * const_eval;
+        }
+
+        // divide out the evaluation of each divisor at x and return the sum of the results
+        group_results.into_iter().zip(self.divisors.iter()).fold(
+            E::ZERO,
+            |result, (value, divisor)| {
+                let z = E::from(divisor.evaluate_at(x));
+                result + value / z
+            },
+        )
     }
 }
@@ -0,0 +1,210 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{FieldElement, TransitionConstraintDegree, Vec};
+
+// SELECTOR COLUMNS
+// ================================================================================================
+/// A set of binary selector columns in the main segment of an execution trace.
+///
+/// Selector columns are used to turn constraints on and off at specific steps of a computation.
+/// For example, a VM-style AIR may use selector columns to specify which operation is executed
+/// at a given step, and then guard constraints of each operation by a flag derived from these
+/// columns (see [SelectorFlag]).
+///
+/// For the guards to be sound, each selector column must contain only binary values. This struct
+/// provides a set of "selector is binary" constraints (one constraint of degree 2 per column) which
+/// can be included into the list of transition constraints of a computation. Since transition
+/// constraints are evaluated against the current row of all but the last few steps of the trace,
+/// values of selector columns in the last rows of the trace are not constrained.
+///
+/// Selector columns can also be interpreted as bits of a packed selector (e.g., an operation
+/// code). In this case, [SelectorColumns::decode()] returns a flag which is set to ONE only when
+/// the packed value is equal to the specified value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorColumns {
+    columns: Vec<usize>,
+}
+
+impl SelectorColumns {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new set of selector columns located at the specified indexes of the main trace
+    /// segment.
+    ///
+    /// When the columns are interpreted as a packed selector, the first column is treated as the
+    /// least significant bit.
+    ///
+    /// # Panics
+    /// Panics if `columns` is empty or contains duplicate column indexes.
+    pub fn new(columns: Vec<usize>) -> Self {
+        assert!(!columns.is_empty(), "at least one selector column must be specified");
+        for (i, column) in columns.iter().enumerate() {
+            assert!(
+                !columns[..i].contains(column),
+                "selector column {column} was specified more than once"
+            );
+        }
+        Self { columns }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns indexes of selector columns in the main trace segment.
+    pub fn columns(&self) -> &[usize] {
+        &self.columns
+    }
+
+    /// Returns the number of selector columns in this set.
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    // BINARY CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of constraints needed to enforce that all selector columns contain only
+    /// binary values.
+    ///
+    /// This is the same as the number of selector columns.
+    pub fn num_binary_constraints(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns degrees of the constraints which enforce that all selector columns contain only
+    /// binary values.
+    ///
+    /// The constraints are defined as $s^2 - s = 0$, and thus, all have degree 2.
+    pub fn binary_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        self.columns.iter().map(|_| TransitionConstraintDegree::new(2)).collect()
+    }
+
+    /// Evaluates constraints enforcing that all selector columns contain only binary values
+    /// against the provided row of the main trace segment, and writes the results into `result`.
+    ///
+    /// Evaluation of the constraint for the column at index `i` of this set is written into
+    /// `result[i]`.
+    ///
+    /// # Panics
+    /// Panics if `result` is shorter than the number of selector columns.
+    pub fn evaluate_binary_constraints<E: FieldElement>(&self, row: &[E], result: &mut [E]) {
+        assert!(
+            result.len() >= self.columns.len(),
+            "result slice must contain at least {} elements, but contained {}",
+            self.columns.len(),
+            result.len()
+        );
+        for (&column, result) in self.columns.iter().zip(result.iter_mut()) {
+            let value = row[column];
+            *result = value.square() - value;
+        }
+    }
+
+    // PACKED SELECTOR DECODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a flag which is set to ONE when selector columns, interpreted as bits of a packed
+    /// selector, encode the specified `value`, and to ZERO otherwise.
+    ///
+    /// The degree of the returned flag is equal to the number of selector columns.
+    ///
+    /// # Panics
+    /// Panics if `value` cannot be encoded with the number of bits equal to the number of
+    /// selector columns.
+    pub fn decode(&self, value: usize) -> SelectorFlag {
+        assert!(
+            self.columns.len() >= usize::BITS as usize || value >> self.columns.len() == 0,
+            "value {value} cannot be encoded with {} selector columns",
+            self.columns.len()
+        );
+        self.columns.iter().enumerate().fold(SelectorFlag::new(), |flag, (i, &column)| {
+            if (value >> i) & 1 == 1 {
+                flag.and(column)
+            } else {
+                flag.and_not(column)
+            }
+        })
+    }
+}
+
+// SELECTOR FLAG
+// ================================================================================================
+/// A flag defined as a product of binary selector columns (or their negations) in the main segment
+/// of an execution trace.
+///
+/// For example, a flag which should be set when column 0 is ONE and column 1 is ZERO is defined as
+/// `SelectorFlag::new().and(0).and_not(1)`, and evaluates to $s_0 \cdot (1 - s_1)$.
+///
+/// A flag can be used to guard a transition constraint so that the constraint is enforced only at
+/// the steps where the flag is set. Since guarding a constraint increases its degree by the degree
+/// of the flag, [SelectorFlag::guard_degree()] can be used to compute the degree of the guarded
+/// constraint.
+///
+/// Flags are sound only if the underlying columns contain binary values; this can be enforced via
+/// constraints provided by [SelectorColumns].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectorFlag {
+    terms: Vec<(usize, bool)>,
+}
+
+impl SelectorFlag {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new flag which is always set (i.e., the flag evaluates to ONE on all rows).
+    pub fn new() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    /// Returns this flag updated to be set only when the selector column at the specified index
+    /// is ONE.
+    pub fn and(mut self, column: usize) -> Self {
+        self.terms.push((column, true));
+        self
+    }
+
+    /// Returns this flag updated to be set only when the selector column at the specified index
+    /// is ZERO.
+    pub fn and_not(mut self, column: usize) -> Self {
+        self.terms.push((column, false));
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the degree of this flag.
+    ///
+    /// This is the same as the number of selector columns involved in computing the flag.
+    pub fn degree(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns the degree of a transition constraint with the specified `degree` after it has
+    /// been guarded by this flag.
+    pub fn guard_degree(&self, degree: TransitionConstraintDegree) -> TransitionConstraintDegree {
+        degree.mul_base(self.degree())
+    }
+
+    // EVALUATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates this flag against the provided row of the main trace segment.
+    pub fn evaluate<E: FieldElement>(&self, row: &[E]) -> E {
+        self.terms.iter().fold(E::ONE, |result, &(column, is_set)| {
+            if is_set {
+                result * row[column]
+            } else {
+                result * (E::ONE - row[column])
+            }
+        })
+    }
+
+    /// Evaluates this flag against the provided row of the main trace segment, and returns the
+    /// specified constraint evaluation multiplied by the flag value.
+    pub fn guard<E: FieldElement>(&self, row: &[E], evaluation: E) -> E {
+        self.evaluate(row) * evaluation
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! DEEP quotient computations.
+//!
+//! The DEEP composition polynomial is a random linear combination of quotients of the form
+//! $(f(x) - f(z)) / (x - z)$, where $f(x)$ is a trace polynomial or a column of the constraint
+//! composition polynomial, and $z$ is an out-of-domain point (for trace polynomials, quotients
+//! are also computed at $z \cdot g$, where $g$ is the generator of the trace domain).
+//!
+//! This module also contains helpers for working with out-of-domain points and evaluations
+//! which are shared by the prover and the verifier.
+//!
+//! Functions in this module compute such quotients either in coefficient form or over an LDE
+//! domain. Since all computations are performed in a finite field, quotients computed by these
+//! functions are exactly the same as the quotients computed by the prover when building the DEEP
+//! composition polynomial. Thus, alternative provers (e.g., ones which build the DEEP composition
+//! polynomial on a GPU) can use these functions to produce results identical to the default
+//! prover.
+
+use math::{batch_inversion, get_power_series_with_offset, polynom, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// OUT-OF-DOMAIN POINTS
+// ================================================================================================
+
+/// Returns the out-of-domain points at which trace polynomials are evaluated for the specified
+/// out-of-domain point `z` and trace length.
+///
+/// The points are $z$ and $z \cdot g$, where $g$ is the generator of the trace domain; the latter
+/// defines the "next" computation state in relation to point $z$.
+///
+/// # Panics
+/// Panics if `trace_length` is not a power of two.
+pub fn get_ood_points<E: FieldElement>(z: E, trace_length: usize) -> [E; 2] {
+    assert!(
+        trace_length.is_power_of_two(),
+        "trace length must be a power of two, but was {trace_length}"
+    );
+    let g = E::BaseField::get_root_of_unity(trace_length.ilog2());
+    [z, z.mul_base(g)]
+}
+
+/// Returns true if `z` can be used as an out-of-domain point for a computation with the specified
+/// trace length and an evaluation domain of size `domain_size` shifted by `domain_offset`.
+///
+/// A point is rejected if it lies in the trace domain (in which case transition and boundary
+/// constraint divisors vanish at $z$), or in the evaluation domain (in which case DEEP quotients
+/// are undefined at one of the positions of the domain). Since the trace domain is a subgroup,
+/// and the evaluation domain is a coset of a subgroup, the same holds for $z \cdot g$.
+///
+/// # Panics
+/// Panics if `trace_length` or `domain_size` is not a power of two.
+pub fn is_valid_ood_point<E: FieldElement>(
+    z: E,
+    trace_length: usize,
+    domain_size: usize,
+    domain_offset: E::BaseField,
+) -> bool {
+    assert!(
+        trace_length.is_power_of_two(),
+        "trace length must be a power of two, but was {trace_length}"
+    );
+    assert!(
+        domain_size.is_power_of_two(),
+        "domain size must be a power of two, but was {domain_size}"
+    );
+    let in_trace_domain = z.exp_vartime((trace_length as u64).into()) == E::ONE;
+    let offset = E::from(domain_offset.exp_vartime((domain_size as u64).into()));
+    let in_evaluation_domain = z.exp_vartime((domain_size as u64).into()) == offset;
+    !in_trace_domain && !in_evaluation_domain
+}
+
+/// Returns the evaluation of the constraint composition polynomial at `z` given evaluations of
+/// its columns at `z` for a computation with the specified trace length.
+///
+/// The result is computed as $\sum_{i=0}^{m-1} z^{i \cdot n} \cdot H_i(z)$, where $H_i(z)$ is
+/// the evaluation of the $i$th column, $n$ is the trace length, and $m$ is the number of columns.
+/// Powers of $z$ are computed incrementally, and thus, the exponent $i \cdot n$ is never
+/// materialized; this keeps the computation correct for arbitrarily long traces.
+pub fn combine_composition_evaluations<E: FieldElement>(
+    evaluations: &[E],
+    z: E,
+    trace_length: usize,
+) -> E {
+    let z_n = z.exp_vartime((trace_length as u64).into());
+    let mut weight = E::ONE;
+    let mut result = E::ZERO;
+    for &value in evaluations {
+        result += weight * value;
+        weight *= z_n;
+    }
+    result
+}
+
+// QUOTIENT COMPUTATIONS
+// ================================================================================================
+
+/// Returns the quotient $(f(x) - f(z)) / (x - z)$ in coefficient form, where $f(x)$ is the
+/// polynomial `poly` (in coefficient form), $z$ is the point `z`, and $f(z)$ is `value_at_z`.
+///
+/// The returned vector has the same length as `poly`; since the degree of the quotient is one
+/// smaller than the degree of $f(x)$, the last coefficient of the result is always zero.
+///
+/// If `value_at_z` is not the evaluation of $f(x)$ at $z$, the remainder of the division is
+/// discarded.
+pub fn compute_quotient_poly<B, E>(poly: &[B], value_at_z: E, z: E) -> Vec<E>
+where
+    B: FieldElement,
+    E: FieldElement + From<B>,
+{
+    let mut result = poly.iter().map(|&c| E::from(c)).collect::<Vec<_>>();
+    compute_quotient_poly_in_place(&mut result, value_at_z, z);
+    result
+}
+
+/// Replaces the polynomial `poly` (in coefficient form) with the quotient
+/// $(f(x) - f(z)) / (x - z)$, where $f(x)$ is the polynomial `poly`, $z$ is the point `z`, and
+/// $f(z)$ is `value_at_z`.
+///
+/// This is an in-place version of [compute_quotient_poly()].
+pub fn compute_quotient_poly_in_place<E: FieldElement>(poly: &mut [E], value_at_z: E, z: E) {
+    poly[0] -= value_at_z;
+    polynom::syn_div_in_place(poly, 1, z);
+}
+
+/// Returns evaluations of the quotient $(f(x) - f(z)) / (x - z)$ over the domain of size
+/// `evaluations.len()` shifted by `domain_offset`.
+///
+/// `evaluations` are expected to contain evaluations of $f(x)$ over the same domain (in natural
+/// order), $z$ is the point `z`, and $f(z)$ is `value_at_z`. All denominators are inverted using
+/// a single batch inversion.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `evaluations` is not a power of two.
+/// * `z` is in the specified domain.
+pub fn compute_quotient_evaluations<B, E>(
+    evaluations: &[B],
+    value_at_z: E,
+    z: E,
+    domain_offset: E::BaseField,
+) -> Vec<E>
+where
+    B: FieldElement,
+    E: FieldElement + From<B>,
+{
+    let domain_size = evaluations.len();
+    assert!(
+        domain_size.is_power_of_two(),
+        "domain size must be a power of two, but was {domain_size}"
+    );
+    let g = E::BaseField::get_root_of_unity(domain_size.ilog2());
+    let denominators = get_power_series_with_offset(g, domain_offset, domain_size)
+        .into_iter()
+        .map(|x| E::from(x) - z)
+        .collect::<Vec<_>>();
+    assert!(
+        denominators.iter().all(|&d| d != E::ZERO),
+        "point z must not be in the evaluation domain"
+    );
+
+    batch_inversion(&denominators)
+        .into_iter()
+        .zip(evaluations)
+        .map(|(inv_denominator, &value)| (E::from(value) - value_at_z) * inv_denominator)
+        .collect()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::{
+        fft,
+        fields::{f128::BaseElement, QuadExtension},
+    };
+    use rand_utils::rand_vector;
+
+    #[test]
+    fn deep_quotients() {
+        let trace_length = 16;
+        let blowup_factor = 4;
+        let offset = BaseElement::GENERATOR;
+        let poly: Vec<BaseElement> = rand_vector(trace_length);
+        let z = QuadExtension::<BaseElement>::new(BaseElement::new(7), BaseElement::new(11));
+
+        let [z, next_z] = get_ood_points(z, trace_length);
+        let g = BaseElement::get_root_of_unity(trace_length.ilog2());
+        assert_eq!(z * QuadExtension::from(g), next_z);
+
+        for point in [z, next_z] {
+            let value_at_z = polynom::eval(&poly, point);
+
+            // the quotient has degree one smaller than the polynomial and the division is exact
+            let quotient = compute_quotient_poly(&poly, value_at_z, point);
+            assert_eq!(trace_length, quotient.len());
+            assert_eq!(QuadExtension::ZERO, quotient[trace_length - 1]);
+            let x = QuadExtension::new(BaseElement::new(3), BaseElement::new(5));
+            assert_eq!(
+                polynom::eval(&poly, x) - value_at_z,
+                polynom::eval(&quotient, x) * (x - point)
+            );
+
+            // evaluations of the quotient over the LDE domain are the same regardless of whether
+            // they are computed in coefficient or evaluation form
+            let twiddles = fft::get_twiddles::<BaseElement>(trace_length);
+            let poly_evaluations =
+                fft::evaluate_poly_with_offset(&poly, &twiddles, offset, blowup_factor);
+            let expected =
+                fft::evaluate_poly_with_offset(&quotient, &twiddles, offset, blowup_factor);
+            assert_eq!(
+                expected,
+                compute_quotient_evaluations(&poly_evaluations, value_at_z, point, offset)
+            );
+        }
+    }
+
+    #[test]
+    fn ood_point_validity() {
+        type E = QuadExtension<BaseElement>;
+        let trace_length = 16;
+        let domain_size = 64;
+        let offset = BaseElement::GENERATOR;
+        let is_valid = |z: E| is_valid_ood_point(z, trace_length, domain_size, offset);
+
+        // points in the trace domain are rejected
+        let g = BaseElement::get_root_of_unity(trace_length.ilog2());
+        assert!(!is_valid(E::ONE));
+        assert!(!is_valid(E::from(g.exp(5))));
+
+        // points in the evaluation domain are rejected
+        let g = BaseElement::get_root_of_unity(domain_size.ilog2());
+        assert!(!is_valid(E::from(offset)));
+        assert!(!is_valid(E::from(offset * g.exp(37))));
+
+        assert!(is_valid(E::ZERO));
+        assert!(is_valid(E::new(BaseElement::new(7), BaseElement::new(11))));
+        assert!(is_valid(E::from(offset * offset)));
+    }
+
+    #[test]
+    fn composition_evaluations() {
+        type E = QuadExtension<BaseElement>;
+        let trace_length = 16;
+        let num_columns = 3;
+        let z = E::new(BaseElement::new(7), BaseElement::new(11));
+
+        // combining column evaluations is the same as evaluating the full composition polynomial
+        let poly: Vec<E> = rand_vector(trace_length * num_columns);
+        let evaluations = poly
+            .chunks(trace_length)
+            .map(|column| polynom::eval(column, z))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            polynom::eval(&poly, z),
+            combine_composition_evaluations(&evaluations, z, trace_length)
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn composition_evaluations_long_trace() {
+        type E = QuadExtension<BaseElement>;
+        let z = E::new(BaseElement::new(7), BaseElement::new(11));
+        let evaluations: Vec<E> = rand_vector(8);
+
+        // for these trace lengths, i * n exceeds u32::MAX for some of the columns
+        for trace_length in [1_usize << 31, 1 << 32, 1 << 40] {
+            let expected = evaluations.iter().enumerate().fold(E::ZERO, |result, (i, &value)| {
+                result + z.exp_vartime((i as u128) * (trace_length as u128)) * value
+            });
+            assert_eq!(expected, combine_composition_evaluations(&evaluations, z, trace_length));
+        }
+    }
+}
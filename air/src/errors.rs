@@ -42,3 +42,56 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+// TRACE LAYOUT ERROR
+// ================================================================================================
+/// Represents an error returned when a trace layout is not valid.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TraceLayoutError {
+    /// This error occurs when the main trace segment of a layout has no columns.
+    EmptyMainSegment,
+    /// This error occurs when the total number of columns across all trace segments exceeds the
+    /// maximum allowed trace width. The error specifies the maximum and the actual widths.
+    TraceTooWide(usize, usize),
+    /// This error occurs when a non-empty auxiliary trace segment follows an empty segment. The
+    /// error specifies the index of the non-empty auxiliary segment.
+    NonEmptySegmentAfterEmpty(usize),
+    /// This error occurs when a non-empty auxiliary trace segment requires no random elements.
+    /// The error specifies the index of the auxiliary segment.
+    MissingRandElements(usize),
+    /// This error occurs when an empty auxiliary trace segment requires random elements. The
+    /// error specifies the index of the auxiliary segment.
+    UnexpectedRandElements(usize),
+    /// This error occurs when an auxiliary trace segment requires more random elements than
+    /// allowed. The error specifies the index of the auxiliary segment, the maximum, and the
+    /// actual number of random elements.
+    TooManyRandElements(usize, usize, usize),
+}
+
+impl fmt::Display for TraceLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyMainSegment => {
+                write!(f, "main trace segment must consist of at least one column")
+            }
+            Self::TraceTooWide(max, actual) => {
+                write!(f, "total number of columns in the trace cannot be greater than {max}, but was {actual}")
+            }
+            Self::NonEmptySegmentAfterEmpty(segment) => {
+                write!(
+                    f,
+                    "auxiliary trace segment {segment} is not empty but follows an empty segment"
+                )
+            }
+            Self::MissingRandElements(segment) => {
+                write!(f, "number of random elements for non-empty auxiliary trace segment {segment} must be greater than zero")
+            }
+            Self::UnexpectedRandElements(segment) => {
+                write!(f, "number of random elements for empty auxiliary trace segment {segment} must be zero")
+            }
+            Self::TooManyRandElements(segment, max, actual) => {
+                write!(f, "number of random elements required by auxiliary trace segment {segment} cannot exceed {max}, but was {actual}")
+            }
+        }
+    }
+}
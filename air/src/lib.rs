@@ -35,16 +35,24 @@ extern crate alloc;
 
 pub mod proof;
 
+pub mod deep;
+
 mod errors;
-pub use errors::AssertionError;
+pub use errors::{AssertionError, TraceLayoutError};
 
 mod options;
-pub use options::{FieldExtension, ProofOptions};
+pub use options::{
+    BatchingMethod, ConstraintLeafEncoding, FieldExtension, OodConstraintBatching, ProofOptions,
+};
 
 mod air;
 pub use air::{
-    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
-    BoundaryConstraints, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, TraceInfo, TraceLayout,
-    TransitionConstraintDegree, TransitionConstraints,
+    lagrange_kernel_boundary_value, lagrange_kernel_transition_divisor, AggregateAir,
+    AggregatePublicInputs, Air, AirContext, Assertion, AuxRandElementsReader, AuxTraceRandElements,
+    BoundaryConstraint, BoundaryConstraintGroup, BoundaryConstraints, ColumnGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
+    EvaluationFrame, LagrangeKernelEvaluationFrame, PeriodicColumn, PermutationArg,
+    PermutationArgBuilder, RangeCheck, RangeCheckBuilder, RowHashStrategy, SelectorColumns,
+    SelectorFlag, TraceInfo, TraceLayout, TraceMetaValue, TraceMetadata,
+    TransitionConstraintDegree, TransitionConstraints, MIN_RANGE_CHECK_TRACE_LENGTH,
 };
@@ -3,12 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crypto::ElementHasher;
 use fri::FriOptions;
-use math::{StarkField, ToElements};
+use math::{FieldElement, StarkField, ToElements};
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 // CONSTANTS
 // ================================================================================================
 
@@ -43,6 +49,7 @@ const FRI_MAX_REMAINDER_DEGREE: usize = 255;
 /// as much as 50%.
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum FieldExtension {
     /// Composition polynomial is constructed in the base field.
     None = 1,
@@ -52,6 +59,86 @@ pub enum FieldExtension {
     Cubic = 3,
 }
 
+/// Defines how random coefficients for constraint composition and DEEP composition polynomials
+/// are drawn from the public coin.
+///
+/// With linear batching, an independent random coefficient is drawn for each constraint (or each
+/// trace and constraint composition column). With algebraic batching, a single random value
+/// $\alpha$ is drawn for the constraint composition polynomial and a single random value $\beta$
+/// is drawn for the DEEP composition polynomial, and all coefficients are set to successive powers
+/// of these values (i.e., $1, \alpha, \alpha^2, ...$).
+///
+/// Algebraic batching reduces the number of values drawn from the public coin, which makes
+/// verification of proofs in recursive settings much cheaper. However, it also increases the
+/// soundness error of batching by a factor roughly equal to the number of batched terms.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum BatchingMethod {
+    /// An independent random coefficient is drawn for each batched term.
+    #[default]
+    Linear = 0,
+    /// Coefficients are successive powers of a single random value.
+    Algebraic = 1,
+}
+
+/// Defines how evaluations of constraint composition columns at the out-of-domain point are sent
+/// to the verifier.
+///
+/// With per-column batching, the prover sends evaluations $H_i(z)$ of all constraint composition
+/// column polynomials at the out-of-domain point $z$, and the verifier combines them into the
+/// evaluation of the composition polynomial as $H(z) = \sum_i z^{i \cdot n} H_i(z)$, where $n$
+/// is the trace length. Each column is then included into the DEEP composition polynomial
+/// separately.
+///
+/// With combined batching, the prover sends only $H(z)$. The columns are first combined into a
+/// single polynomial $G(x) = \sum_i z^{i \cdot n} H_i(x)$, such that $G(z) = H(z)$, and only $G$
+/// is included into the DEEP composition polynomial. This makes proofs smaller by
+/// `num_columns - 1` field elements, but requires the verifier to combine column values at every
+/// queried position.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum OodConstraintBatching {
+    /// An evaluation of each constraint composition column is sent separately.
+    #[default]
+    PerColumn = 0,
+    /// A single evaluation of the combined constraint composition polynomial is sent.
+    Combined = 1,
+}
+
+/// Defines how values of constraint composition columns at a single point of the LDE domain are
+/// packed into a leaf of the constraint commitment Merkle tree.
+///
+/// Values of the columns are elements of the extension field used by the protocol, and each value
+/// is represented by `d` base field elements $(c_0, ..., c_{d-1})$, where `d` is the extension
+/// degree. With interleaved layout, base field elements of each value are placed next to each
+/// other: $(c_0(v_0), c_1(v_0), c_0(v_1), c_1(v_1), ...)$. With concatenated layout, the first
+/// coordinates of all values are placed first, followed by the second coordinates of all values,
+/// and so on: $(c_0(v_0), c_0(v_1), ..., c_1(v_0), c_1(v_1), ...)$. When no field extension is
+/// used, both layouts are the same.
+///
+/// Padded encodings additionally append zeros to the leaf so that the number of base field
+/// elements in it is a power of two.
+///
+/// Values of queried columns included in a proof are the same regardless of the encoding; only
+/// the way leaves are hashed is affected. This is useful for matching the leaf encoding expected
+/// by external verifiers (e.g., recursive verifier circuits).
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ConstraintLeafEncoding {
+    /// Base field elements of each value are placed next to each other.
+    #[default]
+    Interleaved = 0,
+    /// Coordinates of all values are grouped by their position in the value.
+    Concatenated = 1,
+    /// Same as [ConstraintLeafEncoding::Interleaved] but padded with zeros to a power of two.
+    InterleavedPadded = 2,
+    /// Same as [ConstraintLeafEncoding::Concatenated] but padded with zeros to a power of two.
+    ConcatenatedPadded = 3,
+}
+
 /// STARK protocol parameters.
 ///
 /// These parameters have a direct impact on proof soundness, proof generation time, and proof
@@ -65,16 +152,37 @@ pub enum FieldExtension {
 /// 3. Blowup factor - higher values increase proof soundness, but also increase proof generation
 ///    time and proof size. However, higher blowup factors require fewer queries for the same
 ///    security level. Thus, it is frequently possible to increase blowup factor and at the same
-///    time decrease the number of queries in such a way that the proofs become smaller.
+///    time decrease the number of queries in such a way that the proofs become smaller. The
+///    blowup factor does not need to accommodate degrees of transition constraints, and thus,
+///    a small blowup factor can be combined with a larger number of queries to speed up proof
+///    generation.
 /// 4. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, conjectured proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
+/// 5. Batching method - specifies how composition coefficients are drawn (see [BatchingMethod]).
+///    Defaults to [BatchingMethod::Linear] and can be changed via
+///    [ProofOptions::with_batching_method()].
+/// 6. Constraint leaf encoding - specifies how constraint composition column values are packed
+///    into leaves of the constraint commitment (see [ConstraintLeafEncoding]). Defaults to
+///    [ConstraintLeafEncoding::Interleaved] and can be changed via
+///    [ProofOptions::with_constraint_leaf_encoding()].
+/// 7. Skipping of the first FRI layer commitment - when enabled, the prover does not commit to
+///    the first FRI layer, and the verifier derives values of this layer from the opened trace
+///    and constraint evaluations. This reduces the number of Merkle trees in a proof by one, but
+///    requires opening trace and constraint commitments at `fri_folding_factor` times more
+///    positions. Disabled by default, and can be enabled via
+///    [ProofOptions::with_skip_first_fri_layer_commitment()].
+/// 8. OOD constraint batching - specifies whether out-of-domain evaluations of constraint
+///    composition columns are sent individually or as a single combined value (see
+///    [OodConstraintBatching]). Defaults to [OodConstraintBatching::PerColumn] and can be changed
+///    via [ProofOptions::with_ood_constraint_batching()].
 ///
 /// Another important parameter in defining STARK security level, which is not a part of [ProofOptions]
 /// is the hash function used in the protocol. The soundness of a STARK proof is limited by the
 /// collision resistance of the hash function used by the protocol. For example, if a hash function
 /// with 128-bit collision resistance is used, soundness of a STARK proof cannot exceed 128 bits.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ProofOptions {
     num_queries: u8,
     blowup_factor: u8,
@@ -82,6 +190,10 @@ pub struct ProofOptions {
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_remainder_max_degree: u8,
+    batching_method: BatchingMethod,
+    constraint_leaf_encoding: ConstraintLeafEncoding,
+    skip_first_fri_layer_commitment: bool,
+    ood_constraint_batching: OodConstraintBatching,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -92,11 +204,14 @@ impl ProofOptions {
 
     /// Smallest allowed blowup factor which is currently set to 2.
     ///
-    /// The smallest allowed blowup factor for a given computation is derived from degrees of
-    /// constraints defined for that computation and may be greater than 2. But no computation may
-    /// have a blowup factor smaller than 2.
+    /// The blowup factor is independent of degrees of constraints defined for a computation, and
+    /// thus, any computation may use a blowup factor of 2. But no computation may have a blowup
+    /// factor smaller than 2.
     pub const MIN_BLOWUP_FACTOR: usize = MIN_BLOWUP_FACTOR;
 
+    /// Largest allowed blowup factor which is currently set to 128.
+    pub const MAX_BLOWUP_FACTOR: usize = MAX_BLOWUP_FACTOR;
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [ProofOptions] struct constructed from the specified parameters.
@@ -108,7 +223,6 @@ impl ProofOptions {
     /// - `grinding_factor` is greater than 32.
     /// - `fri_folding_factor` is not 2, 4, 8, or 16.
     /// - `fri_remainder_max_degree` is greater than 255 or is not a power of two minus 1.
-    #[rustfmt::skip]
     pub fn new(
         num_queries: usize,
         blowup_factor: usize,
@@ -117,28 +231,15 @@ impl ProofOptions {
         fri_folding_factor: usize,
         fri_remainder_max_degree: usize,
     ) -> ProofOptions {
-        // TODO: return errors instead of panicking
-        assert!(num_queries > 0, "number of queries must be greater than 0");
-        assert!(num_queries <= MAX_NUM_QUERIES, "number of queries cannot be greater than {MAX_NUM_QUERIES}");
-
-        assert!(blowup_factor.is_power_of_two(), "blowup factor must be a power of 2");
-        assert!(blowup_factor >= MIN_BLOWUP_FACTOR, "blowup factor cannot be smaller than {MIN_BLOWUP_FACTOR}");
-        assert!(blowup_factor <= MAX_BLOWUP_FACTOR, "blowup factor cannot be greater than {MAX_BLOWUP_FACTOR}");
-
-        assert!(grinding_factor <= MAX_GRINDING_FACTOR, "grinding factor cannot be greater than {MAX_GRINDING_FACTOR}");
-
-        assert!(fri_folding_factor.is_power_of_two(), "FRI folding factor must be a power of 2");
-        assert!(fri_folding_factor >= FRI_MIN_FOLDING_FACTOR, "FRI folding factor cannot be smaller than {FRI_MIN_FOLDING_FACTOR}");
-        assert!(fri_folding_factor <= FRI_MAX_FOLDING_FACTOR, "FRI folding factor cannot be greater than {FRI_MAX_FOLDING_FACTOR}");
-
-        assert!(
-            (fri_remainder_max_degree + 1).is_power_of_two(),
-            "FRI polynomial remainder degree must be one less than a power of two"
-        );
-        assert!(
-            fri_remainder_max_degree <= FRI_MAX_REMAINDER_DEGREE,
-            "FRI polynomial remainder degree cannot be greater than {FRI_MAX_REMAINDER_DEGREE}"
-        );
+        if let Err(msg) = validate_options(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            fri_folding_factor,
+            fri_remainder_max_degree,
+        ) {
+            panic!("{msg}");
+        }
 
         ProofOptions {
             num_queries: num_queries as u8,
@@ -147,9 +248,46 @@ impl ProofOptions {
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
             fri_remainder_max_degree: fri_remainder_max_degree as u8,
+            batching_method: BatchingMethod::Linear,
+            constraint_leaf_encoding: ConstraintLeafEncoding::Interleaved,
+            skip_first_fri_layer_commitment: false,
+            ood_constraint_batching: OodConstraintBatching::PerColumn,
         }
     }
 
+    /// Returns these proof options updated to use the specified method for drawing constraint
+    /// composition and DEEP composition coefficients.
+    pub fn with_batching_method(mut self, batching_method: BatchingMethod) -> Self {
+        self.batching_method = batching_method;
+        self
+    }
+
+    /// Returns these proof options updated to use the specified encoding for leaves of the
+    /// constraint commitment.
+    pub fn with_constraint_leaf_encoding(mut self, encoding: ConstraintLeafEncoding) -> Self {
+        self.constraint_leaf_encoding = encoding;
+        self
+    }
+
+    /// Returns these proof options updated to skip (or not) the commitment to the first FRI
+    /// layer.
+    ///
+    /// When the commitment is skipped, query positions are drawn from the domain of the second
+    /// FRI layer, and trace and constraint commitments are opened at all positions of the LDE
+    /// domain which fold into these positions. The verifier then computes the values of the
+    /// first FRI layer from the opened values directly.
+    pub fn with_skip_first_fri_layer_commitment(mut self, skip: bool) -> Self {
+        self.skip_first_fri_layer_commitment = skip;
+        self
+    }
+
+    /// Returns these proof options updated to send out-of-domain evaluations of constraint
+    /// composition columns as specified by `batching`.
+    pub fn with_ood_constraint_batching(mut self, batching: OodConstraintBatching) -> Self {
+        self.ood_constraint_batching = batching;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -158,16 +296,37 @@ impl ProofOptions {
     /// This directly impacts proof soundness as each additional query adds roughly
     /// `log2(blowup_factor)` bits of security to a proof. However, each additional query also
     /// increases proof size.
+    ///
+    /// Query positions are drawn from the public coin without duplicates, and thus, the number
+    /// of queried positions is always equal to this value.
     pub fn num_queries(&self) -> usize {
         self.num_queries as usize
     }
 
+    /// Returns the number of positions in the LDE domain at which trace and constraint
+    /// commitments are opened.
+    ///
+    /// This is the same as the number of queries, unless the commitment to the first FRI layer
+    /// is skipped, in which case every query opens `fri_folding_factor` positions.
+    pub fn num_query_positions(&self) -> usize {
+        if self.skip_first_fri_layer_commitment {
+            self.num_queries() * self.fri_folding_factor as usize
+        } else {
+            self.num_queries()
+        }
+    }
+
     /// Returns trace blowup factor for a STARK proof.
     ///
     /// This is the factor by which the execution trace is extended during low-degree extension. It
     /// has a direct impact on proof soundness as each query adds roughly `log2(blowup_factor)`
     /// bits of security to a proof. However, higher blowup factors also increases prover runtime,
     /// and may increase proof size.
+    ///
+    /// The blowup factor defines the domain over which trace and constraint commitments are built
+    /// and over which the FRI protocol is executed. It may be smaller than the blowup factor
+    /// needed to evaluate transition constraints; in this case, constraints are evaluated over a
+    /// larger domain, and a smaller blowup factor can be offset by a larger number of queries.
     pub fn blowup_factor(&self) -> usize {
         self.blowup_factor as usize
     }
@@ -192,6 +351,28 @@ impl ProofOptions {
         self.field_extension
     }
 
+    /// Returns the method used for drawing constraint composition and DEEP composition
+    /// coefficients.
+    pub fn batching_method(&self) -> BatchingMethod {
+        self.batching_method
+    }
+
+    /// Returns the encoding used for leaves of the constraint commitment.
+    pub fn constraint_leaf_encoding(&self) -> ConstraintLeafEncoding {
+        self.constraint_leaf_encoding
+    }
+
+    /// Returns true if the commitment to the first FRI layer is skipped.
+    pub fn skip_first_fri_layer_commitment(&self) -> bool {
+        self.skip_first_fri_layer_commitment
+    }
+
+    /// Returns the way out-of-domain evaluations of constraint composition columns are sent to
+    /// the verifier.
+    pub fn ood_constraint_batching(&self) -> OodConstraintBatching {
+        self.ood_constraint_batching
+    }
+
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
@@ -205,15 +386,21 @@ impl ProofOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let remainder_max_degree = self.fri_remainder_max_degree as usize;
         FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+            .with_skip_first_layer_commitment(self.skip_first_fri_layer_commitment)
     }
 }
 
 impl<E: StarkField> ToElements<E> for ProofOptions {
     fn to_elements(&self) -> Vec<E> {
-        // encode field extension and FRI parameters into a single field element
-        let mut buf = self.field_extension as u32;
-        buf = (buf << 8) | self.fri_folding_factor as u32;
-        buf = (buf << 8) | self.fri_remainder_max_degree as u32;
+        // encode OOD constraint batching, FRI layer skipping flag, constraint leaf encoding,
+        // batching method, field extension, and FRI parameters into a single field element
+        let mut buf = self.ood_constraint_batching as u64;
+        buf = (buf << 8) | self.skip_first_fri_layer_commitment as u64;
+        buf = (buf << 8) | self.constraint_leaf_encoding as u64;
+        buf = (buf << 8) | self.batching_method as u64;
+        buf = (buf << 8) | self.field_extension as u64;
+        buf = (buf << 8) | self.fri_folding_factor as u64;
+        buf = (buf << 8) | self.fri_remainder_max_degree as u64;
 
         vec![
             E::from(buf),
@@ -233,6 +420,10 @@ impl Serializable for ProofOptions {
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_remainder_max_degree);
+        target.write(self.batching_method);
+        target.write(self.constraint_leaf_encoding);
+        target.write_bool(self.skip_first_fri_layer_commitment);
+        target.write(self.ood_constraint_batching);
     }
 }
 
@@ -242,14 +433,33 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_queries = source.read_u8()? as usize;
+        let blowup_factor = source.read_u8()? as usize;
+        let grinding_factor = source.read_u8()? as u32;
+        let field_extension = FieldExtension::read_from(source)?;
+        let fri_folding_factor = source.read_u8()? as usize;
+        let fri_remainder_max_degree = source.read_u8()? as usize;
+        validate_options(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            fri_folding_factor,
+            fri_remainder_max_degree,
+        )
+        .map_err(DeserializationError::InvalidValue)?;
+
         Ok(ProofOptions::new(
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-            source.read_u8()? as u32,
-            FieldExtension::read_from(source)?,
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-        ))
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor,
+            fri_remainder_max_degree,
+        )
+        .with_batching_method(BatchingMethod::read_from(source)?)
+        .with_constraint_leaf_encoding(ConstraintLeafEncoding::read_from(source)?)
+        .with_skip_first_fri_layer_commitment(source.read_bool()?)
+        .with_ood_constraint_batching(OodConstraintBatching::read_from(source)?))
     }
 }
 
@@ -293,13 +503,201 @@ impl Deserializable for FieldExtension {
     }
 }
 
+// BATCHING METHOD IMPLEMENTATION
+// ================================================================================================
+
+impl BatchingMethod {
+    /// Returns the number of field elements drawn from the public coin to derive the specified
+    /// number of batching coefficients using this method.
+    pub fn num_draws(&self, num_coefficients: usize) -> usize {
+        match self {
+            Self::Linear => num_coefficients,
+            Self::Algebraic => 1,
+        }
+    }
+}
+
+impl Serializable for BatchingMethod {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for BatchingMethod {
+    /// Reads a batching method enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(BatchingMethod::Linear),
+            1 => Ok(BatchingMethod::Algebraic),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as BatchingMethod enum"
+            ))),
+        }
+    }
+}
+
+// OOD CONSTRAINT BATCHING IMPLEMENTATION
+// ================================================================================================
+
+impl Serializable for OodConstraintBatching {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for OodConstraintBatching {
+    /// Reads an OOD constraint batching enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(OodConstraintBatching::PerColumn),
+            1 => Ok(OodConstraintBatching::Combined),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as OodConstraintBatching enum"
+            ))),
+        }
+    }
+}
+
+// CONSTRAINT LEAF ENCODING IMPLEMENTATION
+// ================================================================================================
+
+impl ConstraintLeafEncoding {
+    /// Returns `true` if coordinates of values are grouped by their position in the value.
+    pub fn is_concatenated(&self) -> bool {
+        matches!(self, Self::Concatenated | Self::ConcatenatedPadded)
+    }
+
+    /// Returns `true` if leaves are padded with zeros to a power of two base field elements.
+    pub fn is_padded(&self) -> bool {
+        matches!(self, Self::InterleavedPadded | Self::ConcatenatedPadded)
+    }
+
+    /// Returns the base field elements of a leaf built from the specified values of constraint
+    /// composition columns.
+    pub fn encode_leaf<E: FieldElement>(&self, values: &[E]) -> Vec<E::BaseField> {
+        let elements = E::slice_as_base_elements(values);
+        let mut result = if self.is_concatenated() {
+            let degree = E::EXTENSION_DEGREE;
+            (0..degree)
+                .flat_map(|i| elements.iter().skip(i).step_by(degree).copied())
+                .collect::<Vec<_>>()
+        } else {
+            elements.to_vec()
+        };
+        if self.is_padded() {
+            result.resize(result.len().next_power_of_two(), E::BaseField::ZERO);
+        }
+        result
+    }
+
+    /// Hashes a leaf built from the specified values of constraint composition columns.
+    ///
+    /// For [ConstraintLeafEncoding::Interleaved] encoding, this is the same as hashing the values
+    /// directly.
+    pub fn hash_leaf<H, E>(&self, values: &[E]) -> H::Digest
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        E: FieldElement,
+    {
+        match self {
+            Self::Interleaved => H::hash_elements(values),
+            _ => H::hash_elements(&self.encode_leaf(values)),
+        }
+    }
+}
+
+impl Serializable for ConstraintLeafEncoding {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for ConstraintLeafEncoding {
+    /// Reads a constraint leaf encoding enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(ConstraintLeafEncoding::Interleaved),
+            1 => Ok(ConstraintLeafEncoding::Concatenated),
+            2 => Ok(ConstraintLeafEncoding::InterleavedPadded),
+            3 => Ok(ConstraintLeafEncoding::ConcatenatedPadded),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as ConstraintLeafEncoding enum"
+            ))),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that the specified proof parameters are valid, and returns an error message describing
+/// the first invalid parameter otherwise.
+fn validate_options(
+    num_queries: usize,
+    blowup_factor: usize,
+    grinding_factor: u32,
+    fri_folding_factor: usize,
+    fri_remainder_max_degree: usize,
+) -> Result<(), String> {
+    if num_queries == 0 {
+        return Err("number of queries must be greater than 0".to_string());
+    }
+    if num_queries > MAX_NUM_QUERIES {
+        return Err(format!("number of queries cannot be greater than {MAX_NUM_QUERIES}"));
+    }
+
+    if !blowup_factor.is_power_of_two() {
+        return Err("blowup factor must be a power of 2".to_string());
+    }
+    if blowup_factor < MIN_BLOWUP_FACTOR {
+        return Err(format!("blowup factor cannot be smaller than {MIN_BLOWUP_FACTOR}"));
+    }
+    if blowup_factor > MAX_BLOWUP_FACTOR {
+        return Err(format!("blowup factor cannot be greater than {MAX_BLOWUP_FACTOR}"));
+    }
+
+    if grinding_factor > MAX_GRINDING_FACTOR {
+        return Err(format!("grinding factor cannot be greater than {MAX_GRINDING_FACTOR}"));
+    }
+
+    if !fri_folding_factor.is_power_of_two() {
+        return Err("FRI folding factor must be a power of 2".to_string());
+    }
+    if fri_folding_factor < FRI_MIN_FOLDING_FACTOR {
+        return Err(format!("FRI folding factor cannot be smaller than {FRI_MIN_FOLDING_FACTOR}"));
+    }
+    if fri_folding_factor > FRI_MAX_FOLDING_FACTOR {
+        return Err(format!("FRI folding factor cannot be greater than {FRI_MAX_FOLDING_FACTOR}"));
+    }
+
+    if !(fri_remainder_max_degree + 1).is_power_of_two() {
+        return Err(
+            "FRI polynomial remainder degree must be one less than a power of two".to_string()
+        );
+    }
+    if fri_remainder_max_degree > FRI_MAX_REMAINDER_DEGREE {
+        return Err(format!(
+            "FRI polynomial remainder degree cannot be greater than {FRI_MAX_REMAINDER_DEGREE}"
+        ));
+    }
+
+    Ok(())
+}
+
 // TESTS
 // ================================================================================================
 
 #[cfg(test)]
 mod tests {
-    use super::{FieldExtension, ProofOptions, ToElements};
-    use math::fields::f64::BaseElement;
+    use super::{
+        BatchingMethod, ConstraintLeafEncoding, FieldExtension, OodConstraintBatching,
+        ProofOptions, ToElements,
+    };
+    use math::fields::{f64::BaseElement, QuadExtension};
+    use utils::{Deserializable, Serializable};
 
     #[test]
     fn proof_options_to_elements() {
@@ -318,7 +716,7 @@ mod tests {
         ]);
         let expected = vec![
             BaseElement::from(ext_fri),
-            BaseElement::from(grinding_factor as u32),
+            BaseElement::from(grinding_factor),
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
         ];
@@ -332,5 +730,115 @@ mod tests {
             fri_remainder_max_degree as usize,
         );
         assert_eq!(expected, options.to_elements());
+
+        // batching method is encoded in the most significant byte of the first element
+        let ext_fri = u32::from_le_bytes([
+            fri_remainder_max_degree,
+            fri_folding_factor,
+            field_extension as u8,
+            BatchingMethod::Algebraic as u8,
+        ]);
+        let options = options.with_batching_method(BatchingMethod::Algebraic);
+        assert_eq!(BaseElement::from(ext_fri), options.to_elements()[0]);
+
+        // constraint leaf encoding is encoded in the byte above the batching method
+        let ext_fri = u64::from_le_bytes([
+            fri_remainder_max_degree,
+            fri_folding_factor,
+            field_extension as u8,
+            BatchingMethod::Algebraic as u8,
+            ConstraintLeafEncoding::ConcatenatedPadded as u8,
+            0,
+            0,
+            0,
+        ]);
+        let options =
+            options.with_constraint_leaf_encoding(ConstraintLeafEncoding::ConcatenatedPadded);
+        assert_eq!(BaseElement::from(ext_fri), options.to_elements()[0]);
+
+        // FRI layer skipping flag is encoded in the byte above the constraint leaf encoding
+        let ext_fri = u64::from_le_bytes([
+            fri_remainder_max_degree,
+            fri_folding_factor,
+            field_extension as u8,
+            BatchingMethod::Algebraic as u8,
+            ConstraintLeafEncoding::ConcatenatedPadded as u8,
+            1,
+            0,
+            0,
+        ]);
+        let options = options.with_skip_first_fri_layer_commitment(true);
+        assert_eq!(BaseElement::from(ext_fri), options.to_elements()[0]);
+
+        // OOD constraint batching is encoded in the byte above the FRI layer skipping flag
+        let ext_fri = u64::from_le_bytes([
+            fri_remainder_max_degree,
+            fri_folding_factor,
+            field_extension as u8,
+            BatchingMethod::Algebraic as u8,
+            ConstraintLeafEncoding::ConcatenatedPadded as u8,
+            1,
+            OodConstraintBatching::Combined as u8,
+            0,
+        ]);
+        let options = options.with_ood_constraint_batching(OodConstraintBatching::Combined);
+        assert_eq!(BaseElement::from(ext_fri), options.to_elements()[0]);
+    }
+
+    #[test]
+    fn proof_options_serialization() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        let options = options
+            .with_batching_method(BatchingMethod::Algebraic)
+            .with_constraint_leaf_encoding(ConstraintLeafEncoding::InterleavedPadded)
+            .with_skip_first_fri_layer_commitment(true)
+            .with_ood_constraint_batching(OodConstraintBatching::Combined);
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        assert_eq!(240, options.num_query_positions());
+    }
+
+    #[test]
+    fn constraint_leaf_encodings() {
+        type E = QuadExtension<BaseElement>;
+        let [a0, a1, b0, b1, c0, c1] = [1, 2, 3, 4, 5, 6].map(BaseElement::new);
+        let values = [E::new(a0, a1), E::new(b0, b1), E::new(c0, c1)];
+        let zero = BaseElement::new(0);
+
+        let encoding = ConstraintLeafEncoding::Interleaved;
+        assert_eq!(vec![a0, a1, b0, b1, c0, c1], encoding.encode_leaf(&values));
+
+        let encoding = ConstraintLeafEncoding::Concatenated;
+        assert_eq!(vec![a0, b0, c0, a1, b1, c1], encoding.encode_leaf(&values));
+
+        let encoding = ConstraintLeafEncoding::InterleavedPadded;
+        assert_eq!(vec![a0, a1, b0, b1, c0, c1, zero, zero], encoding.encode_leaf(&values));
+
+        let encoding = ConstraintLeafEncoding::ConcatenatedPadded;
+        assert_eq!(vec![a0, b0, c0, a1, b1, c1, zero, zero], encoding.encode_leaf(&values));
+    }
+
+    #[test]
+    fn proof_options_read_invalid() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        let bytes = options.to_bytes();
+
+        // blowup factor which is not a power of two
+        let mut invalid = bytes.clone();
+        invalid[1] = 7;
+        assert!(ProofOptions::read_from_bytes(&invalid).is_err());
+
+        // grinding factor which is too large
+        let mut invalid = bytes.clone();
+        invalid[2] = 33;
+        assert!(ProofOptions::read_from_bytes(&invalid).is_err());
+
+        // FRI folding factor which is not a power of two
+        let mut invalid = bytes;
+        invalid[4] = 9;
+        assert!(ProofOptions::read_from_bytes(&invalid).is_err());
     }
 }
@@ -0,0 +1,158 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::StarkProof;
+use crypto::Hasher;
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// VERIFICATION ARTIFACTS
+// ================================================================================================
+/// Commitments contained in a STARK proof.
+///
+/// Verification artifacts include all commitments made by the prover during the commit phase of
+/// the protocol, together with a digest of the out-of-domain evaluation frame. These can be
+/// extracted from a proof via [StarkProof::verification_artifacts()], and can be serialized
+/// independently of the proof. This is useful for protocols which need to pin or cross-reference
+/// commitments of a proof without storing or transmitting the full proof.
+///
+/// Verification artifacts are not sufficient to verify a proof; they only identify the
+/// commitments made by the prover.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationArtifacts<H: Hasher> {
+    /// Commitments to the extended execution trace segments (main segment first, followed by
+    /// auxiliary segments).
+    pub trace_roots: Vec<H::Digest>,
+    /// Commitment to the extended column group; this is present only for computations which
+    /// define a column group.
+    pub column_group_root: Option<H::Digest>,
+    /// Commitment to the evaluations of the constraint composition polynomial.
+    pub constraint_root: H::Digest,
+    /// Commitments to FRI layers (including the commitment to the remainder) in the order in
+    /// which they appear in the proof; for computations which define a column group, commitments
+    /// of the column group FRI instance follow commitments of the main FRI instance.
+    pub fri_roots: Vec<H::Digest>,
+    /// Digest of the serialized out-of-domain evaluation frame.
+    pub ood_digest: H::Digest,
+}
+
+impl<H: Hasher> VerificationArtifacts<H> {
+    /// Serializes these artifacts into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.write_into(&mut result);
+        result
+    }
+
+    /// Returns verification artifacts read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if valid verification artifacts could not be read from the specified
+    /// `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let artifacts = Self::read_from(&mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(artifacts)
+    }
+}
+
+impl<H: Hasher> Serializable for VerificationArtifacts<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        assert!(self.trace_roots.len() <= u8::MAX as usize);
+        target.write_u8(self.trace_roots.len() as u8);
+        H::Digest::write_batch_into(&self.trace_roots, target);
+        target.write_bool(self.column_group_root.is_some());
+        if let Some(root) = self.column_group_root {
+            root.write_into(target);
+        }
+        self.constraint_root.write_into(target);
+        assert!(self.fri_roots.len() <= u8::MAX as usize);
+        target.write_u8(self.fri_roots.len() as u8);
+        H::Digest::write_batch_into(&self.fri_roots, target);
+        self.ood_digest.write_into(target);
+    }
+}
+
+impl<H: Hasher> Deserializable for VerificationArtifacts<H> {
+    /// Reads verification artifacts from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if valid verification artifacts could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_trace_roots = source.read_u8()? as usize;
+        let trace_roots = H::Digest::read_batch_from(source, num_trace_roots)?;
+        let column_group_root = if source.read_bool()? {
+            Some(H::Digest::read_from(source)?)
+        } else {
+            None
+        };
+        let constraint_root = H::Digest::read_from(source)?;
+        let num_fri_roots = source.read_u8()? as usize;
+        let fri_roots = H::Digest::read_batch_from(source, num_fri_roots)?;
+        let ood_digest = H::Digest::read_from(source)?;
+
+        Ok(VerificationArtifacts {
+            trace_roots,
+            column_group_root,
+            constraint_root,
+            fri_roots,
+            ood_digest,
+        })
+    }
+}
+
+// STARK PROOF EXTENSIONS
+// ================================================================================================
+
+impl StarkProof {
+    /// Returns the commitments contained in this proof together with a digest of its
+    /// out-of-domain evaluation frame.
+    ///
+    /// The out-of-domain digest is computed by hashing the serialized out-of-domain frame using
+    /// hash function `H`.
+    ///
+    /// # Errors
+    /// Returns an error if commitments contained in this proof could not be parsed into digests
+    /// of hash function `H`.
+    pub fn verification_artifacts<H: Hasher>(
+        &self,
+    ) -> Result<VerificationArtifacts<H>, DeserializationError> {
+        let mut reader = SliceReader::new(&self.commitments.0);
+
+        // the column group root (if any) immediately follows the main trace segment root
+        let num_trace_segments = self.context.trace_layout().num_segments();
+        let mut trace_roots = H::Digest::read_batch_from(&mut reader, 1)?;
+        let column_group_root = match self.column_group {
+            Some(_) => Some(H::Digest::read_from(&mut reader)?),
+            None => None,
+        };
+        trace_roots.append(&mut H::Digest::read_batch_from(&mut reader, num_trace_segments - 1)?);
+        let constraint_root = H::Digest::read_from(&mut reader)?;
+
+        // all remaining commitments are FRI layer commitments
+        let mut fri_roots = Vec::new();
+        while reader.has_more_bytes() {
+            fri_roots.push(H::Digest::read_from(&mut reader)?);
+        }
+        if fri_roots.is_empty() {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+
+        Ok(VerificationArtifacts {
+            trace_roots,
+            column_group_root,
+            constraint_root,
+            fri_roots,
+            ood_digest: H::hash(&self.ood_frame.to_bytes()),
+        })
+    }
+}
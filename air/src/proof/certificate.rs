@@ -0,0 +1,174 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColumnGroupProof, Commitments, Context, OodFrame, Queries, StarkProof};
+use fri::FriProof;
+use utils::{
+    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// PROOF CERTIFICATE
+// ================================================================================================
+/// The commitment part of a STARK proof.
+///
+/// A certificate contains the proof context, all commitments made by the prover, the out-of-domain
+/// evaluation frame, and the proof-of-work nonce. It is usually much smaller than the rest of the
+/// proof, and is sufficient to check consistency of the out-of-domain evaluations and the
+/// proof-of-work; query decommitments and FRI layers needed to complete verification are contained
+/// in [ProofOpenings].
+///
+/// A certificate and openings can be obtained from a proof using [StarkProof::split()], and can
+/// be joined back into a proof using [StarkProof::join()].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofCertificate {
+    /// Basic metadata about the execution of the computation described by the proof.
+    pub context: Context,
+    /// Commitments made by the prover during the commit phase of the protocol.
+    pub commitments: Commitments,
+    /// Trace and constraint polynomial evaluations at an out-of-domain point.
+    pub ood_frame: OodFrame,
+    /// Proof-of-work nonce for query seed grinding.
+    pub pow_nonce: u64,
+}
+
+impl ProofCertificate {
+    /// Serializes this certificate into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.context.write_into(&mut result);
+        self.commitments.write_into(&mut result);
+        self.ood_frame.write_into(&mut result);
+        result.extend_from_slice(&self.pow_nonce.to_le_bytes());
+        result
+    }
+
+    /// Returns a proof certificate read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid certificate could not be read from the specified `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let certificate = ProofCertificate {
+            context: Context::read_from(&mut source)?,
+            commitments: Commitments::read_from(&mut source)?,
+            ood_frame: OodFrame::read_from(&mut source)?,
+            pow_nonce: source.read_u64()?,
+        };
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(certificate)
+    }
+}
+
+// PROOF OPENINGS
+// ================================================================================================
+/// The opening part of a STARK proof.
+///
+/// Openings contain decommitments of trace and constraint composition polynomial evaluations at
+/// the queried positions, as well as the FRI proof (and the column group proof, if present). Openings are verified against commitments
+/// contained in the corresponding [ProofCertificate].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofOpenings {
+    /// Decommitments of extended execution trace values (for all trace segments) at positions
+    /// queried by the verifier.
+    pub trace_queries: Vec<Queries>,
+    /// Decommitments of constraint composition polynomial evaluations at positions queried by
+    /// the verifier.
+    pub constraint_queries: Queries,
+    /// Low-degree proof for a DEEP composition polynomial.
+    pub fri_proof: FriProof,
+    /// Decommitments and a low-degree proof for the column group, if present.
+    pub column_group: Option<ColumnGroupProof>,
+}
+
+impl ProofOpenings {
+    /// Serializes these openings into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        // the number of trace segments is small, and thus, always fits into a single byte
+        result.push(self.trace_queries.len() as u8);
+        self.trace_queries.write_into(&mut result);
+        self.constraint_queries.write_into(&mut result);
+        self.fri_proof.write_into(&mut result);
+        if let Some(column_group) = &self.column_group {
+            column_group.write_into(&mut result);
+        }
+        result
+    }
+
+    /// Returns proof openings read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if valid proof openings could not be read from the specified `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+
+        let num_trace_segments = source.read_u8()? as usize;
+        let mut trace_queries = Vec::with_capacity(num_trace_segments);
+        for _ in 0..num_trace_segments {
+            trace_queries.push(Queries::read_from(&mut source)?);
+        }
+
+        let mut openings = ProofOpenings {
+            trace_queries,
+            constraint_queries: Queries::read_from(&mut source)?,
+            fri_proof: FriProof::read_from(&mut source)?,
+            column_group: None,
+        };
+        if source.has_more_bytes() {
+            openings.column_group = Some(ColumnGroupProof::read_from(&mut source)?);
+        }
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(openings)
+    }
+}
+
+// STARK PROOF SPLITTING
+// ================================================================================================
+
+impl StarkProof {
+    /// Splits this proof into a certificate (containing commitments, out-of-domain evaluations,
+    /// and the proof-of-work nonce) and openings (containing query decommitments and the FRI
+    /// proof).
+    pub fn split(self) -> (ProofCertificate, ProofOpenings) {
+        let certificate = ProofCertificate {
+            context: self.context,
+            commitments: self.commitments,
+            ood_frame: self.ood_frame,
+            pow_nonce: self.pow_nonce,
+        };
+        let openings = ProofOpenings {
+            trace_queries: self.trace_queries,
+            constraint_queries: self.constraint_queries,
+            fri_proof: self.fri_proof,
+            column_group: self.column_group,
+        };
+        (certificate, openings)
+    }
+
+    /// Joins the provided certificate and openings into a single proof.
+    ///
+    /// # Panics
+    /// Panics if the number of trace queries in the `openings` is not equal to the number of
+    /// trace segments specified by the context of the `certificate`.
+    pub fn join(certificate: ProofCertificate, openings: ProofOpenings) -> Self {
+        let proof = StarkProof::from_parts(
+            certificate.context,
+            certificate.commitments,
+            openings.trace_queries,
+            openings.constraint_queries,
+            certificate.ood_frame,
+            openings.fri_proof,
+            certificate.pow_nonce,
+        );
+        match openings.column_group {
+            Some(column_group) => proof.with_column_group(column_group),
+            None => proof,
+        }
+    }
+}
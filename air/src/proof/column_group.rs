@@ -0,0 +1,55 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Queries;
+use fri::FriProof;
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// COLUMN GROUP PROOF
+// ================================================================================================
+/// Decommitments and a low-degree proof for a group of trace columns committed to at a lower
+/// blowup factor than the rest of the execution trace.
+///
+/// The commitment to the extended column group and commitments to the layers of its FRI proof are
+/// included into the [Commitments](super::Commitments) of the proof: the column group commitment
+/// follows the commitment to the main trace segment, and the FRI layer commitments follow the FRI
+/// layer commitments of the DEEP composition polynomial.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ColumnGroupProof {
+    /// Decommitments of extended column group values at positions of the column group domain
+    /// queried by the verifier.
+    pub queries: Queries,
+    /// Low-degree proof for the DEEP composition polynomial of the column group.
+    pub fri_proof: FriProof,
+}
+
+impl ColumnGroupProof {
+    /// Returns a new column group proof assembled from the specified parts.
+    pub fn new(queries: Queries, fri_proof: FriProof) -> Self {
+        ColumnGroupProof { queries, fri_proof }
+    }
+}
+
+impl Serializable for ColumnGroupProof {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.queries.write_into(target);
+        self.fri_proof.write_into(target);
+    }
+}
+
+impl Deserializable for ColumnGroupProof {
+    /// Reads a column group proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid column group proof could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(ColumnGroupProof {
+            queries: Queries::read_from(source)?,
+            fri_proof: FriProof::read_from(source)?,
+        })
+    }
+}
@@ -3,13 +3,21 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{ProofOptions, TraceInfo, TraceLayout};
+use crate::{ProofOptions, TraceInfo, TraceLayout, TraceMetadata};
+use crypto::{Digest, Hasher};
 use math::{StarkField, ToElements};
 use utils::{
-    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
-    DeserializationError, Serializable,
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
 };
 
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of bytes in a prover ID.
+const MAX_PROVER_ID_BYTES: usize = u8::MAX as usize;
+
 // PROOF CONTEXT
 // ================================================================================================
 /// Basic metadata about a specific execution of a computation.
@@ -17,9 +25,11 @@ use utils::{
 pub struct Context {
     trace_layout: TraceLayout,
     trace_length: usize,
-    trace_meta: Vec<u8>,
+    trace_meta: TraceMetadata,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
+    aux_trace_hasher: Option<u32>,
+    prover_id: Option<String>,
 }
 
 impl Context {
@@ -31,12 +41,45 @@ impl Context {
         Context {
             trace_layout: trace_info.layout().clone(),
             trace_length: trace_info.length(),
-            trace_meta: trace_info.meta().to_vec(),
+            trace_meta: trace_info.meta().clone(),
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
+            aux_trace_hasher: None,
+            prover_id: None,
         }
     }
 
+    /// Returns this context with auxiliary trace segments committed to using hash function `AH`
+    /// while the main trace segment is committed to using hash function `H`.
+    ///
+    /// The ID of `AH` (see [hasher_id()]) is recorded in the context only if the trace has
+    /// auxiliary segments and `AH` is distinct from `H`.
+    pub fn with_aux_trace_hasher<H: Hasher, AH: Hasher>(mut self) -> Self {
+        let aux_hasher_id = hasher_id::<AH>();
+        self.aux_trace_hasher = (self.trace_layout.num_aux_segments() > 0
+            && aux_hasher_id != hasher_id::<H>())
+        .then_some(aux_hasher_id);
+        self
+    }
+
+    /// Returns this context with the specified identifier of the prover software recorded in it.
+    ///
+    /// The identifier (e.g., "winterfell-0.9+zk") is bound to the proof transcript, and thus, it
+    /// cannot be altered without invalidating the proof. This enables verifiers to enforce
+    /// policies on which prover builds are acceptable.
+    ///
+    /// # Panics
+    /// Panics if the identifier is longer than 255 bytes.
+    pub fn with_prover_id(mut self, prover_id: &str) -> Self {
+        assert!(
+            prover_id.len() <= MAX_PROVER_ID_BYTES,
+            "prover ID cannot be longer than {MAX_PROVER_ID_BYTES} bytes, but was {} bytes",
+            prover_id.len()
+        );
+        self.prover_id = Some(prover_id.to_string());
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -91,6 +134,28 @@ impl Context {
     pub fn options(&self) -> &ProofOptions {
         &self.options
     }
+
+    /// Returns the ID of the hash function used to commit to auxiliary trace segments, or `None`
+    /// if auxiliary trace segments are committed to using the same hash function as the main
+    /// trace segment.
+    pub fn aux_trace_hasher(&self) -> Option<u32> {
+        self.aux_trace_hasher
+    }
+
+    /// Returns the identifier of the prover software which generated a proof in this context, or
+    /// `None` if the prover did not record an identifier.
+    pub fn prover_id(&self) -> Option<&str> {
+        self.prover_id.as_deref()
+    }
+
+    /// Returns a digest of this context computed using hash function `H`.
+    ///
+    /// The digest is a hash of the canonical serialization of this context. The serialization
+    /// format is not changed in patch releases, and thus, the digest can be used as a stable
+    /// identifier of a context (e.g., to group proofs of the same computation in audit logs).
+    pub fn digest<H: Hasher>(&self) -> H::Digest {
+        H::hash(&self.to_bytes())
+    }
 }
 
 impl<E: StarkField> ToElements<E> for Context {
@@ -104,7 +169,9 @@ impl<E: StarkField> ToElements<E> for Context {
     /// - blowup factor [1 element].
     /// - number of queries [1 element].
     /// - trace length [1 element].
-    /// - trace metadata [0 or more elements].
+    /// - canonical encoding of trace metadata [0 or more elements].
+    /// - ID of the auxiliary trace hash function [0 or 1 element].
+    /// - length and bytes of the prover ID [0 or more elements].
     fn to_elements(&self) -> Vec<E> {
         // convert trace layout
         let mut result = self.trace_layout.to_elements();
@@ -119,11 +186,26 @@ impl<E: StarkField> ToElements<E> for Context {
         result.append(&mut self.options.to_elements());
         result.push(E::from(self.trace_length as u64));
 
-        // convert trace metadata to elements; this is done by breaking trace metadata into chunks
-        // of bytes which are slightly smaller than the number of bytes needed to encode a field
-        // element, and then converting these chunks into field elements.
+        // convert trace metadata to elements; this is done by breaking the canonical encoding of
+        // trace metadata into chunks of bytes which are slightly smaller than the number of bytes
+        // needed to encode a field element, and then converting these chunks into field elements.
         if !self.trace_meta.is_empty() {
-            for chunk in self.trace_meta.chunks(E::ELEMENT_BYTES - 1) {
+            for chunk in self.trace_meta.to_canonical_bytes().chunks(E::ELEMENT_BYTES - 1) {
+                result.push(bytes_to_element(chunk));
+            }
+        }
+
+        // the auxiliary trace hash function is included only if it differs from the hash function
+        // used for the main trace segment
+        if let Some(hasher_id) = self.aux_trace_hasher {
+            result.push(E::from(hasher_id));
+        }
+
+        // the prover ID is included only if it was recorded; its length is included as well so
+        // that the encoding is unambiguous
+        if let Some(prover_id) = &self.prover_id {
+            result.push(E::from(prover_id.len() as u32));
+            for chunk in prover_id.as_bytes().chunks(E::ELEMENT_BYTES - 1) {
                 result.push(bytes_to_element(chunk));
             }
         }
@@ -137,12 +219,20 @@ impl Serializable for Context {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         self.trace_layout.write_into(target);
         target.write_u8(self.trace_length.ilog2() as u8); // store as power of two
-        target.write_u16(self.trace_meta.len() as u16);
-        target.write_bytes(&self.trace_meta);
+        self.trace_meta.write_into(target);
         assert!(self.field_modulus_bytes.len() < u8::MAX as usize);
         target.write_u8(self.field_modulus_bytes.len() as u8);
         target.write_bytes(&self.field_modulus_bytes);
         self.options.write_into(target);
+        target.write_bool(self.aux_trace_hasher.is_some());
+        if let Some(hasher_id) = self.aux_trace_hasher {
+            target.write_u32(hasher_id);
+        }
+        target.write_bool(self.prover_id.is_some());
+        if let Some(prover_id) = &self.prover_id {
+            target.write_u8(prover_id.len() as u8);
+            target.write_bytes(prover_id.as_bytes());
+        }
     }
 }
 
@@ -164,15 +254,14 @@ impl Deserializable for Context {
                 trace_length
             )));
         }
-        let trace_length = 2_usize.pow(trace_length as u32);
+        let trace_length = 1_usize.checked_shl(trace_length as u32).ok_or_else(|| {
+            DeserializationError::InvalidValue(format!(
+                "trace length of 2^{trace_length} cannot be represented on this platform"
+            ))
+        })?;
 
-        // read trace metadata
-        let num_meta_bytes = source.read_u16()? as usize;
-        let trace_meta = if num_meta_bytes != 0 {
-            source.read_vec(num_meta_bytes)?
-        } else {
-            vec![]
-        };
+        // read and validate trace metadata
+        let trace_meta = TraceMetadata::read_from(source)?;
 
         // read and validate field modulus bytes
         let num_modulus_bytes = source.read_u8()? as usize;
@@ -185,6 +274,35 @@ impl Deserializable for Context {
 
         // read options
         let options = ProofOptions::read_from(source)?;
+        if trace_length.checked_mul(options.blowup_factor()).is_none() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "LDE domain size for trace length {trace_length} and blowup factor {} cannot be represented on this platform",
+                options.blowup_factor()
+            )));
+        }
+
+        // read auxiliary trace hash function ID
+        let aux_trace_hasher = if source.read_bool()? {
+            if trace_layout.num_aux_segments() == 0 {
+                return Err(DeserializationError::InvalidValue(
+                    "auxiliary trace hash function cannot be specified for a trace without auxiliary segments".to_string(),
+                ));
+            }
+            Some(source.read_u32()?)
+        } else {
+            None
+        };
+
+        // read prover ID
+        let prover_id = if source.read_bool()? {
+            let num_bytes = source.read_u8()? as usize;
+            let bytes = source.read_vec(num_bytes)?;
+            Some(String::from_utf8(bytes).map_err(|err| {
+                DeserializationError::InvalidValue(format!("prover ID is not valid UTF-8: {err}"))
+            })?)
+        } else {
+            None
+        };
 
         Ok(Context {
             trace_layout,
@@ -192,10 +310,25 @@ impl Deserializable for Context {
             trace_meta,
             field_modulus_bytes,
             options,
+            aux_trace_hasher,
+            prover_id,
         })
     }
 }
 
+// HASHER ID
+// ================================================================================================
+
+/// Returns an ID of the hash function `H`.
+///
+/// The ID is derived from the digest of a fixed message, and thus, distinct hash functions have
+/// distinct IDs with overwhelming probability. It is used to record hash functions in proof
+/// [Context] so that the verifier can check that it uses the same hash functions as the prover.
+pub fn hasher_id<H: Hasher>() -> u32 {
+    let digest = H::hash(b"winterfell hasher id").as_bytes();
+    u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -209,7 +342,7 @@ fn bytes_to_element<B: StarkField>(bytes: &[u8]) -> B {
 
     let mut buf = bytes.to_vec();
     buf.resize(B::ELEMENT_BYTES, 0);
-    let element = match B::try_from(&buf) {
+    let element = match B::from_canonical_bytes(&buf) {
         Ok(element) => element,
         Err(_) => panic!("element deserialization failed"),
     };
@@ -222,8 +355,10 @@ fn bytes_to_element<B: StarkField>(bytes: &[u8]) -> B {
 #[cfg(test)]
 mod tests {
     use super::{Context, ProofOptions, ToElements, TraceInfo};
-    use crate::{FieldExtension, TraceLayout};
+    use crate::{FieldExtension, TraceLayout, TraceMetadata};
+    use crypto::{hashers::Blake3_256, Hasher};
     use math::fields::f64::BaseElement;
+    use utils::{Deserializable, Serializable};
 
     #[test]
     fn context_to_elements() {
@@ -247,14 +382,15 @@ mod tests {
             0,
         ]);
 
-        let layout_info = u32::from_le_bytes([aux_rands, aux_width, num_aux_segments, main_width]);
+        let layout_info =
+            u64::from_le_bytes([aux_rands, 0, aux_width, 0, num_aux_segments, main_width, 0, 0]);
 
         let expected = vec![
             BaseElement::from(layout_info),
             BaseElement::from(1_u32),    // lower bits of field modulus
             BaseElement::from(u32::MAX), // upper bits of field modulus
             BaseElement::from(ext_fri),
-            BaseElement::from(grinding_factor as u32),
+            BaseElement::from(grinding_factor),
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
             BaseElement::from(trace_length as u32),
@@ -270,8 +406,46 @@ mod tests {
         );
         let layout =
             TraceLayout::new(main_width as usize, [aux_width as usize], [aux_rands as usize]);
-        let trace_info = TraceInfo::new_multi_segment(layout, trace_length, vec![]);
+        let trace_info = TraceInfo::new_multi_segment(layout, trace_length, TraceMetadata::new());
         let context = Context::new::<BaseElement>(&trace_info, options);
         assert_eq!(expected, context.to_elements());
     }
+
+    #[test]
+    fn context_prover_id() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        let trace_info = TraceInfo::new(4, 1024);
+        let context = Context::new::<BaseElement>(&trace_info, options);
+        let identified_context = context.clone().with_prover_id("winterfell-0.7+zk");
+        assert_eq!(None, context.prover_id());
+        assert_eq!(Some("winterfell-0.7+zk"), identified_context.prover_id());
+
+        // the prover ID is appended to the elements as the number of bytes followed by the bytes
+        // packed into elements
+        let elements: Vec<BaseElement> = context.to_elements();
+        let identified_elements: Vec<BaseElement> = identified_context.to_elements();
+        assert_eq!(elements[..], identified_elements[..elements.len()]);
+        assert_eq!(elements.len() + 4, identified_elements.len());
+        assert_eq!(BaseElement::from(17_u32), identified_elements[elements.len()]);
+
+        // the prover ID survives serialization
+        let bytes = identified_context.to_bytes();
+        assert_eq!(identified_context, Context::read_from_bytes(&bytes).unwrap());
+        assert_eq!(context, Context::read_from_bytes(&context.to_bytes()).unwrap());
+    }
+
+    #[test]
+    fn context_digest() {
+        type Blake3 = Blake3_256<BaseElement>;
+
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        let context = Context::new::<BaseElement>(&TraceInfo::new(4, 1024), options.clone());
+        assert_eq!(Blake3::hash(&context.to_bytes()), context.digest::<Blake3>());
+
+        // contexts which differ in any way have different digests
+        let identified_context = context.clone().with_prover_id("winterfell-0.7");
+        assert_ne!(context.digest::<Blake3>(), identified_context.digest::<Blake3>());
+        let longer_context = Context::new::<BaseElement>(&TraceInfo::new(4, 2048), options);
+        assert_ne!(context.digest::<Blake3>(), longer_context.digest::<Blake3>());
+    }
 }
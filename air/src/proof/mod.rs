@@ -14,20 +14,32 @@ use utils::{
 };
 
 mod context;
-pub use context::Context;
+pub use context::{hasher_id, Context};
 
 mod commitments;
 pub use commitments::Commitments;
 
+mod artifacts;
+pub use artifacts::VerificationArtifacts;
+
 mod queries;
 pub use queries::Queries;
 
 mod ood_frame;
-pub use ood_frame::OodFrame;
+pub use ood_frame::{OodFrame, ParsedOodFrame};
 
 mod table;
 pub use table::Table;
 
+mod certificate;
+pub use certificate::{ProofCertificate, ProofOpenings};
+
+mod column_group;
+pub use column_group::ColumnGroupProof;
+
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -65,9 +77,60 @@ pub struct StarkProof {
     pub fri_proof: FriProof,
     /// Proof-of-work nonce for query seed grinding.
     pub pow_nonce: u64,
+    /// Decommitments and a low-degree proof for the column group committed to at a lower blowup
+    /// factor; this is present only for computations which define a column group.
+    pub column_group: Option<ColumnGroupProof>,
 }
 
 impl StarkProof {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new proof assembled from the specified parts.
+    ///
+    /// This is the inverse of [into_parts()](StarkProof::into_parts), and can be used to rebuild
+    /// a proof after post-processing its parts (e.g., re-compressing query decommitments).
+    ///
+    /// # Panics
+    /// Panics if the number of trace queries is not equal to the number of trace segments
+    /// specified by the `context`.
+    pub fn from_parts(
+        context: Context,
+        commitments: Commitments,
+        trace_queries: Vec<Queries>,
+        constraint_queries: Queries,
+        ood_frame: OodFrame,
+        fri_proof: FriProof,
+        pow_nonce: u64,
+    ) -> Self {
+        assert_eq!(
+            trace_queries.len(),
+            context.trace_layout().num_segments(),
+            "expected trace queries for {} trace segments, but received {}",
+            context.trace_layout().num_segments(),
+            trace_queries.len()
+        );
+
+        StarkProof {
+            context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            pow_nonce,
+            column_group: None,
+        }
+    }
+
+    /// Returns this proof with the specified column group proof attached to it.
+    pub fn with_column_group(mut self, column_group: ColumnGroupProof) -> Self {
+        self.column_group = Some(column_group);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
     /// Returns STARK protocol parameters used to generate this proof.
     pub fn options(&self) -> &ProofOptions {
         self.context.options()
@@ -94,6 +157,77 @@ impl StarkProof {
         self.context.lde_domain_size()
     }
 
+    /// Returns basic metadata about the execution of the computation described by this proof.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns commitments made by the prover during the commit phase of the protocol.
+    pub fn commitments(&self) -> &Commitments {
+        &self.commitments
+    }
+
+    /// Returns decommitments of extended execution trace values for all trace segments.
+    pub fn trace_queries(&self) -> &[Queries] {
+        &self.trace_queries
+    }
+
+    /// Returns decommitments of constraint composition polynomial evaluations.
+    pub fn constraint_queries(&self) -> &Queries {
+        &self.constraint_queries
+    }
+
+    /// Returns trace and constraint polynomial evaluations at an out-of-domain point.
+    pub fn ood_frame(&self) -> &OodFrame {
+        &self.ood_frame
+    }
+
+    /// Returns low-degree proof for the DEEP composition polynomial.
+    pub fn fri_proof(&self) -> &FriProof {
+        &self.fri_proof
+    }
+
+    /// Returns proof-of-work nonce for query seed grinding.
+    pub fn pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
+    /// Returns decommitments and a low-degree proof for the column group, if present.
+    pub fn column_group(&self) -> Option<&ColumnGroupProof> {
+        self.column_group.as_ref()
+    }
+
+    /// Removes the column group proof from this proof and returns it.
+    pub fn take_column_group(&mut self) -> Option<ColumnGroupProof> {
+        self.column_group.take()
+    }
+
+    /// Decomposes this proof into its parts.
+    ///
+    /// The parts are returned in the following order: context, commitments, trace queries,
+    /// constraint queries, out-of-domain frame, FRI proof, and proof-of-work nonce. A proof can
+    /// be rebuilt from these parts using [from_parts()](StarkProof::from_parts).
+    ///
+    /// # Panics
+    /// Panics if the proof contains a column group proof; the column group proof must be removed
+    /// via [take_column_group()](StarkProof::take_column_group) first, and can be attached to the
+    /// rebuilt proof via [with_column_group()](StarkProof::with_column_group).
+    #[allow(clippy::type_complexity)]
+    pub fn into_parts(
+        self,
+    ) -> (Context, Commitments, Vec<Queries>, Queries, OodFrame, FriProof, u64) {
+        assert!(self.column_group.is_none(), "column group proof must be taken first");
+        (
+            self.context,
+            self.commitments,
+            self.trace_queries,
+            self.constraint_queries,
+            self.ood_frame,
+            self.fri_proof,
+            self.pow_nonce,
+        )
+    }
+
     // SECURITY LEVEL
     // --------------------------------------------------------------------------------------------
     /// Returns security level of this proof (in bits).
@@ -102,6 +236,9 @@ impl StarkProof {
     /// security level is returned. Usually, the number of queries needed for provable security is
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
     /// security level.
+    ///
+    /// Query security is derived from the blowup factor of the LDE domain (over which the FRI
+    /// protocol is executed), regardless of the blowup factor of the constraint evaluation domain.
     pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
         if conjectured {
             get_conjectured_security(
@@ -125,6 +262,19 @@ impl StarkProof {
         }
     }
 
+    // DIGEST
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a digest of this proof computed using hash function `H`.
+    ///
+    /// The digest is a hash of the canonical serialization of this proof (see
+    /// [to_bytes()](StarkProof::to_bytes)). The serialization format is not changed in patch
+    /// releases, and thus, the digest can be used as a stable identifier of a proof (e.g., for
+    /// deduplication, caching, or audit logging in verification services).
+    pub fn digest<H: Hasher>(&self) -> H::Digest {
+        H::hash(&self.to_bytes())
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
@@ -138,6 +288,11 @@ impl StarkProof {
         self.ood_frame.write_into(&mut result);
         self.fri_proof.write_into(&mut result);
         result.extend_from_slice(&self.pow_nonce.to_le_bytes());
+        // the column group proof is optional, and thus, is written only when present; this keeps
+        // serialization of proofs without a column group unchanged
+        if let Some(column_group) = &self.column_group {
+            column_group.write_into(&mut result);
+        }
         result
     }
 
@@ -162,7 +317,7 @@ impl StarkProof {
         }
 
         // parse the rest of the proof
-        let proof = StarkProof {
+        let mut proof = StarkProof {
             context,
             commitments,
             trace_queries,
@@ -170,7 +325,11 @@ impl StarkProof {
             ood_frame: OodFrame::read_from(&mut source)?,
             fri_proof: FriProof::read_from(&mut source)?,
             pow_nonce: source.read_u64()?,
+            column_group: None,
         };
+        if source.has_more_bytes() {
+            proof.column_group = Some(ColumnGroupProof::read_from(&mut source)?);
+        }
         if source.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
@@ -5,14 +5,15 @@
 
 use math::FieldElement;
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable, SliceReader,
 };
 
-// TYPE ALIASES
+// CONSTANTS
 // ================================================================================================
 
-type ParsedOodFrame<E> = (Vec<E>, Vec<E>);
+/// Version of the out-of-domain frame serialization format.
+const OOD_FRAME_VERSION: u8 = 3;
 
 // OUT-OF-DOMAIN FRAME
 // ================================================================================================
@@ -25,12 +26,74 @@ type ParsedOodFrame<E> = (Vec<E>, Vec<E>);
 ///
 /// where *z* is an out-of-domain point and *g* is the generator of the trace domain.
 ///
-/// Internally, the evaluations are stored as a sequence of bytes. Thus, to retrieve the
-/// evaluations, [parse()](OodFrame::parse) function should be used.
+/// If [OodConstraintBatching::Combined](crate::OodConstraintBatching::Combined) is specified in
+/// proof options, constraint evaluations consist of a single evaluation of the full constraint
+/// composition polynomial at *z* instead of evaluations of individual columns.
+///
+/// Internally, the evaluations are stored as a sequence of bytes. Trace evaluations are written
+/// in separate sections for the main and the auxiliary trace segments, and each section (as well
+/// as constraint evaluations) is prefixed with its width. Thus, the frame can be parsed without
+/// knowing the shape of the trace in advance; to retrieve the evaluations, the
+/// [parse()](OodFrame::parse) function should be used.
+///
+/// # Layout
+/// For a frame of $k$ trace states (rows) $r_0, ..., r_{k-1}$, where $r_i\[j\]$ is the evaluation
+/// of the $j$-th trace polynomial at the $i$-th point of the frame, trace evaluations are written
+/// as follows (all integers are written in little-endian byte order):
+///
+/// 1. Format version (1 byte), currently 3.
+/// 2. Number of trace states $k$ (1 byte).
+/// 3. Width of the main trace segment $m$ (2 bytes).
+/// 4. Main trace section: $r_0\[0\], ..., r_{k-1}\[0\], r_0\[1\], ..., r_{k-1}\[m - 1\]$; that is,
+///    evaluations of each column at all points of the frame are written next to each other, and
+///    columns are written in order.
+/// 5. Combined width of the auxiliary trace segments $a$ (2 bytes).
+/// 6. Auxiliary trace section: same as the main trace section but for columns
+///    $m, ..., m + a - 1$.
+///
+/// Constraint evaluations are written as the number of evaluations (2 bytes) followed by the
+/// evaluations in order. All field elements are written using their canonical serialization.
+///
+/// The same sequence of trace evaluations (without widths and the version) is used to reseed
+/// the public coin (see [set_trace_states()](OodFrame::set_trace_states)).
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct OodFrame {
-    trace_states: Vec<u8>,
-    evaluations: Vec<u8>,
+    pub(super) trace_states: Vec<u8>,
+    pub(super) evaluations: Vec<u8>,
+}
+
+/// Evaluations parsed from an [OodFrame].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParsedOodFrame<E: FieldElement> {
+    /// Number of trace states in the frame (i.e., the number of points at which trace
+    /// polynomials were evaluated).
+    pub frame_size: usize,
+    /// Number of columns in the main trace segment.
+    pub main_trace_width: usize,
+    /// Number of columns in all auxiliary trace segments.
+    pub aux_trace_width: usize,
+    /// Evaluations of the main trace polynomials followed by evaluations of the auxiliary trace
+    /// polynomials, where evaluations of each polynomial at all points of the frame are stored
+    /// next to each other.
+    pub trace_states: Vec<E>,
+    /// Evaluations of constraint composition column polynomials at the out-of-domain point.
+    pub constraint_evaluations: Vec<E>,
+}
+
+impl<E: FieldElement> ParsedOodFrame<E> {
+    /// Returns trace states of this frame as rows, such that the $i$-th row contains evaluations
+    /// of all main trace polynomials followed by evaluations of all auxiliary trace polynomials
+    /// at the $i$-th point of the frame.
+    ///
+    /// This is the exact inverse of the transformation applied by
+    /// [OodFrame::set_trace_states()]; that is, the returned rows are the same as the rows passed
+    /// to that function.
+    pub fn trace_rows(&self) -> Vec<Vec<E>> {
+        let width = self.main_trace_width + self.aux_trace_width;
+        (0..self.frame_size)
+            .map(|i| (0..width).map(|j| self.trace_states[j * self.frame_size + i]).collect())
+            .collect()
+    }
 }
 
 impl OodFrame {
@@ -41,14 +104,29 @@ impl OodFrame {
     /// version of the out-of-domain frame with the rows interleaved. This is done so that reseeding
     /// of the random coin needs to be done only once as opposed to once per each row.
     ///
+    /// The first `main_trace_width` values of each state are written into the main trace section
+    /// of the frame, and the remaining values are written into the auxiliary trace section (see
+    /// [OodFrame#layout] for the exact layout). The returned vector contains values of the main
+    /// trace section followed by the values of the auxiliary trace section.
+    ///
     /// # Panics
-    /// Panics if evaluation frame has already been set.
-    pub fn set_trace_states<E: FieldElement>(&mut self, trace_states: &[Vec<E>]) -> Vec<E> {
+    /// Panics if:
+    /// * Evaluation frame has already been set.
+    /// * `main_trace_width` is greater than the width of the provided trace states.
+    pub fn set_trace_states<E: FieldElement>(
+        &mut self,
+        trace_states: &[Vec<E>],
+        main_trace_width: usize,
+    ) -> Vec<E> {
         assert!(self.trace_states.is_empty(), "trace sates have already been set");
 
         // save the evaluations with the current and next evaluations interleaved for each polynomial
         let frame_size = trace_states.len();
         let width = trace_states[0].len();
+        assert!(
+            main_trace_width <= width,
+            "main trace width {main_trace_width} is greater than trace state width {width}"
+        );
 
         let mut result = vec![];
         for i in 0..width {
@@ -57,8 +135,16 @@ impl OodFrame {
             }
         }
         debug_assert!(frame_size <= u8::MAX as usize);
+        debug_assert!(width <= u16::MAX as usize);
+        self.trace_states.write_u8(OOD_FRAME_VERSION);
         self.trace_states.write_u8(frame_size as u8);
-        result.write_into(&mut self.trace_states);
+
+        // write main and auxiliary trace sections, each prefixed with its width
+        let (main_states, aux_states) = result.split_at(main_trace_width * frame_size);
+        self.trace_states.write_u16(main_trace_width as u16);
+        E::write_batch_into(main_states, &mut self.trace_states);
+        self.trace_states.write_u16((width - main_trace_width) as u16);
+        E::write_batch_into(aux_states, &mut self.trace_states);
 
         result
     }
@@ -72,50 +158,74 @@ impl OodFrame {
     pub fn set_constraint_evaluations<E: FieldElement>(&mut self, evaluations: &[E]) {
         assert!(self.evaluations.is_empty(), "constraint evaluations have already been set");
         assert!(!evaluations.is_empty(), "cannot set to empty constraint evaluations");
-        evaluations.write_into(&mut self.evaluations)
+        debug_assert!(evaluations.len() <= u16::MAX as usize);
+        self.evaluations.write_u16(evaluations.len() as u16);
+        E::write_batch_into(evaluations, &mut self.evaluations)
     }
 
     // PARSER
     // --------------------------------------------------------------------------------------------
-    /// Returns main and auxiliary (if any) trace evaluation frames and a vector of out-of-domain
-    /// constraint evaluations contained in `self`.
+    /// Returns trace evaluations and constraint evaluations contained in `self`, together with
+    /// the shape of the frame as recorded by the prover.
     ///
-    /// # Panics
-    /// Panics if either `main_trace_width` or `num_evaluations` are equal to zero.
+    /// The caller is expected to check that the shape of the parsed frame matches the shape
+    /// expected for a given computation.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * Valid [EvaluationFrame]s for the specified `main_trace_width` and `aux_trace_width`
-    ///   could not be parsed from the internal bytes.
-    /// * A vector of evaluations specified by `num_evaluations` could not be parsed from the
-    ///   internal bytes.
+    /// * The frame was serialized using an unsupported version of the format.
+    /// * The frame contains no trace states, an empty main trace section, or no constraint
+    ///   evaluations.
+    /// * Trace or constraint evaluations could not be parsed from the internal bytes.
     /// * Any unconsumed bytes remained after the parsing was complete.
-    pub fn parse<E: FieldElement>(
-        self,
-        main_trace_width: usize,
-        aux_trace_width: usize,
-        num_evaluations: usize,
-    ) -> Result<ParsedOodFrame<E>, DeserializationError> {
-        assert!(main_trace_width > 0, "trace width cannot be zero");
-        assert!(num_evaluations > 0, "number of evaluations cannot be zero");
-
-        // parse main and auxiliary trace evaluation frames
+    pub fn parse<E: FieldElement>(self) -> Result<ParsedOodFrame<E>, DeserializationError> {
+        // parse main and auxiliary trace sections
         let mut reader = SliceReader::new(&self.trace_states);
+        let version = reader.read_u8()?;
+        if version != OOD_FRAME_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "OOD frame version {version} is not supported; expected version {OOD_FRAME_VERSION}"
+            )));
+        }
         let frame_size = reader.read_u8()? as usize;
-        let trace =
-            E::read_batch_from(&mut reader, (main_trace_width + aux_trace_width) * frame_size)?;
+        if frame_size == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "OOD frame must contain at least one trace state".to_string(),
+            ));
+        }
+        let main_trace_width = reader.read_u16()? as usize;
+        if main_trace_width == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "main trace section of OOD frame cannot be empty".to_string(),
+            ));
+        }
+        let mut trace_states = E::read_batch_from(&mut reader, main_trace_width * frame_size)?;
+        let aux_trace_width = reader.read_u16()? as usize;
+        trace_states.extend(E::read_batch_from(&mut reader, aux_trace_width * frame_size)?);
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
         // parse the constraint evaluations
         let mut reader = SliceReader::new(&self.evaluations);
-        let evaluations = E::read_batch_from(&mut reader, num_evaluations)?;
+        let num_evaluations = reader.read_u16()? as usize;
+        if num_evaluations == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "OOD frame must contain at least one constraint evaluation".to_string(),
+            ));
+        }
+        let constraint_evaluations = E::read_batch_from(&mut reader, num_evaluations)?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        Ok((trace, evaluations))
+        Ok(ParsedOodFrame {
+            frame_size,
+            main_trace_width,
+            aux_trace_width,
+            trace_states,
+            constraint_evaluations,
+        })
     }
 }
 
@@ -123,7 +233,7 @@ impl Serializable for OodFrame {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write trace rows
-        target.write_u16(self.trace_states.len() as u16);
+        target.write_u32(self.trace_states.len() as u32);
         target.write_bytes(&self.trace_states);
 
         // write constraint evaluations row
@@ -139,7 +249,7 @@ impl Deserializable for OodFrame {
     /// Returns an error of a valid OOD frame could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read trace rows
-        let num_trace_state_bytes = source.read_u16()? as usize;
+        let num_trace_state_bytes = source.read_u32()? as usize;
         let trace_states = source.read_vec(num_trace_state_bytes)?;
 
         // read constraint evaluations row
@@ -152,3 +262,78 @@ impl Deserializable for OodFrame {
         })
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{OodFrame, OOD_FRAME_VERSION};
+    use math::fields::f64::BaseElement;
+    use proptest::prelude::*;
+    use utils::{collections::Vec, Deserializable, DeserializationError, Serializable};
+
+    #[test]
+    fn ood_frame_sections() {
+        let [a0, a1, b0, b1, c0, c1] = [1, 2, 3, 4, 5, 6].map(BaseElement::new);
+        let mut frame = OodFrame::default();
+        let states = frame.set_trace_states(&[vec![a0, b0, c0], vec![a1, b1, c1]], 2);
+        assert_eq!(vec![a0, a1, b0, b1, c0, c1], states);
+        frame.set_constraint_evaluations(&[a0, c1]);
+
+        let frame = OodFrame::read_from_bytes(&frame.to_bytes()).unwrap();
+        let parsed = frame.clone().parse::<BaseElement>().unwrap();
+        assert_eq!(2, parsed.frame_size);
+        assert_eq!(2, parsed.main_trace_width);
+        assert_eq!(1, parsed.aux_trace_width);
+        assert_eq!(states, parsed.trace_states);
+        assert_eq!(vec![vec![a0, b0, c0], vec![a1, b1, c1]], parsed.trace_rows());
+        assert_eq!(vec![a0, c1], parsed.constraint_evaluations);
+
+        // trace evaluations are laid out column by column, with main and auxiliary sections
+        // prefixed by their widths
+        let mut expected = vec![OOD_FRAME_VERSION, 2, 2, 0];
+        [a0, a1, b0, b1].iter().for_each(|v| v.write_into(&mut expected));
+        expected.extend_from_slice(&[1, 0]);
+        [c0, c1].iter().for_each(|v| v.write_into(&mut expected));
+        assert_eq!(expected, frame.trace_states);
+
+        // frames serialized using a different version of the format are rejected
+        let mut invalid = frame;
+        invalid.trace_states[0] = OOD_FRAME_VERSION - 1;
+        assert!(matches!(
+            invalid.parse::<BaseElement>(),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn ood_frame_round_trip(
+            frame_size in 1..4usize,
+            main_trace_width in 1..8usize,
+            aux_trace_width in 0..8usize,
+            values in prop::collection::vec(any::<u64>(), 4 * 16),
+            evaluations in prop::collection::vec(any::<u64>(), 1..8),
+        ) {
+            let width = main_trace_width + aux_trace_width;
+            let rows = (0..frame_size)
+                .map(|i| (0..width).map(|j| BaseElement::new(values[i * 16 + j])).collect())
+                .collect::<Vec<Vec<_>>>();
+            let evaluations = evaluations.into_iter().map(BaseElement::new).collect::<Vec<_>>();
+
+            let mut frame = OodFrame::default();
+            let states = frame.set_trace_states(&rows, main_trace_width);
+            frame.set_constraint_evaluations(&evaluations);
+
+            let frame = OodFrame::read_from_bytes(&frame.to_bytes()).unwrap();
+            let parsed = frame.parse::<BaseElement>().unwrap();
+            prop_assert_eq!(frame_size, parsed.frame_size);
+            prop_assert_eq!(main_trace_width, parsed.main_trace_width);
+            prop_assert_eq!(aux_trace_width, parsed.aux_trace_width);
+            prop_assert_eq!(&states, &parsed.trace_states);
+            prop_assert_eq!(rows, parsed.trace_rows());
+            prop_assert_eq!(evaluations, parsed.constraint_evaluations);
+        }
+    }
+}
@@ -0,0 +1,570 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains protobuf messages for STARK proofs and conversions between these messages and
+//! [StarkProof](super::StarkProof) and its components.
+//!
+//! The messages mirror definitions in the `proto/proof.proto` schema of this crate, and can be
+//! encoded and decoded via [prost::Message] trait. For example:
+//!
+//! ```ignore
+//! use winter_air::proof::{protobuf, protobuf::prost::Message, StarkProof};
+//!
+//! let message_bytes = protobuf::StarkProof::from(&proof).encode_to_vec();
+//! let message = protobuf::StarkProof::decode(message_bytes.as_slice())?;
+//! let proof = StarkProof::try_from(message)?;
+//! ```
+//!
+//! Conversion of a message back into a proof component performs the same validation as
+//! deserialization of the component from bytes.
+
+use crate::{air::NUM_AUX_SEGMENTS, proof};
+use utils::{ByteWriter, Deserializable, DeserializationError, Serializable};
+
+pub use fri::protobuf::{FriProof, FriProofLayer};
+pub use prost;
+
+// STARK PROOF MESSAGE
+// ================================================================================================
+
+/// Protobuf message for a [StarkProof](super::StarkProof).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct StarkProof {
+    /// Basic metadata about the execution of the computation described by the proof.
+    #[prost(message, optional, tag = "1")]
+    pub context: Option<Context>,
+    /// Concatenated commitments made by the prover during the commit phase of the protocol.
+    #[prost(bytes = "vec", tag = "2")]
+    pub commitments: Vec<u8>,
+    /// Decommitments of trace values for each trace segment.
+    #[prost(message, repeated, tag = "3")]
+    pub trace_queries: Vec<Queries>,
+    /// Decommitments of constraint composition polynomial evaluations.
+    #[prost(message, optional, tag = "4")]
+    pub constraint_queries: Option<Queries>,
+    /// Trace and constraint polynomial evaluations at an out-of-domain point.
+    #[prost(message, optional, tag = "5")]
+    pub ood_frame: Option<OodFrame>,
+    /// Low-degree proof for a DEEP composition polynomial.
+    #[prost(message, optional, tag = "6")]
+    pub fri_proof: Option<FriProof>,
+    /// Proof-of-work nonce for query seed grinding.
+    #[prost(uint64, tag = "7")]
+    pub pow_nonce: u64,
+    /// Decommitments and a low-degree proof for the column group; present only for computations
+    /// which define a column group.
+    #[prost(message, optional, tag = "8")]
+    pub column_group: Option<ColumnGroupProof>,
+}
+
+impl From<&proof::StarkProof> for StarkProof {
+    fn from(proof: &proof::StarkProof) -> Self {
+        Self {
+            context: Some(Context::from(&proof.context)),
+            commitments: proof.commitments.0.clone(),
+            trace_queries: proof.trace_queries.iter().map(Queries::from).collect(),
+            constraint_queries: Some(Queries::from(&proof.constraint_queries)),
+            ood_frame: Some(OodFrame::from(&proof.ood_frame)),
+            fri_proof: Some(FriProof::from(&proof.fri_proof)),
+            pow_nonce: proof.pow_nonce,
+            column_group: proof.column_group.as_ref().map(ColumnGroupProof::from),
+        }
+    }
+}
+
+impl TryFrom<StarkProof> for proof::StarkProof {
+    type Error = DeserializationError;
+
+    /// Converts a protobuf message into a STARK proof.
+    ///
+    /// # Errors
+    /// Returns an error if the message does not describe a valid STARK proof.
+    fn try_from(message: StarkProof) -> Result<Self, Self::Error> {
+        let context = required(message.context, "context")?;
+        let num_trace_segments = required(context.trace_layout.as_ref(), "trace layout")?
+            .aux_segment_widths
+            .len()
+            + 1;
+        if message.trace_queries.len() != num_trace_segments {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected trace queries for {num_trace_segments} trace segments, but received {}",
+                message.trace_queries.len()
+            )));
+        }
+
+        let mut bytes = Vec::new();
+        context.write_canonical(&mut bytes)?;
+        write_bytes(&mut bytes, &message.commitments, 2, "commitments")?;
+        for queries in message.trace_queries.iter() {
+            queries.write_canonical(&mut bytes)?;
+        }
+        required(message.constraint_queries, "constraint queries")?.write_canonical(&mut bytes)?;
+        required(message.ood_frame, "OOD frame")?.write_canonical(&mut bytes)?;
+        let fri_proof = fri::FriProof::try_from(required(message.fri_proof, "FRI proof")?)?;
+        fri_proof.write_into(&mut bytes);
+        bytes.extend_from_slice(&message.pow_nonce.to_le_bytes());
+        if let Some(column_group) = message.column_group {
+            column_group.write_canonical(&mut bytes)?;
+        }
+
+        proof::StarkProof::from_bytes(&bytes)
+    }
+}
+
+// CONTEXT MESSAGE
+// ================================================================================================
+
+/// Protobuf message for a proof [Context](super::Context).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct Context {
+    /// Layout of the execution trace.
+    #[prost(message, optional, tag = "1")]
+    pub trace_layout: Option<TraceLayout>,
+    /// Length of the execution trace; must be a power of two.
+    #[prost(uint64, tag = "2")]
+    pub trace_length: u64,
+    /// Canonical encoding of the trace metadata.
+    #[prost(bytes = "vec", tag = "3")]
+    pub trace_meta: Vec<u8>,
+    /// Little-endian encoding of the modulus of the field in which the computation was executed.
+    #[prost(bytes = "vec", tag = "4")]
+    pub field_modulus: Vec<u8>,
+    /// Parameters of the STARK protocol used to generate the proof.
+    #[prost(message, optional, tag = "5")]
+    pub options: Option<ProofOptions>,
+    /// ID of the hash function used to commit to auxiliary trace segments; absent if auxiliary
+    /// trace segments are committed to using the same hash function as the main trace segment.
+    #[prost(uint32, optional, tag = "6")]
+    pub aux_trace_hasher: Option<u32>,
+    /// Identifier of the prover software which generated the proof; absent if the prover did not
+    /// record an identifier.
+    #[prost(string, optional, tag = "7")]
+    pub prover_id: Option<String>,
+}
+
+impl Context {
+    /// Writes this message into `target` using the serialization format of [proof::Context].
+    fn write_canonical(&self, target: &mut Vec<u8>) -> Result<(), DeserializationError> {
+        required(self.trace_layout.as_ref(), "trace layout")?.write_canonical(target)?;
+
+        if !self.trace_length.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length must be a power of two, but was {}",
+                self.trace_length
+            )));
+        }
+        target.push(self.trace_length.ilog2() as u8);
+
+        write_bytes(target, &self.trace_meta, 2, "trace metadata")?;
+        write_bytes(target, &self.field_modulus, 1, "field modulus")?;
+        required(self.options.as_ref(), "proof options")?.write_canonical(target)?;
+
+        target.write_bool(self.aux_trace_hasher.is_some());
+        if let Some(hasher_id) = self.aux_trace_hasher {
+            target.write_u32(hasher_id);
+        }
+
+        target.write_bool(self.prover_id.is_some());
+        if let Some(prover_id) = &self.prover_id {
+            write_bytes(target, prover_id.as_bytes(), 1, "prover ID")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&proof::Context> for Context {
+    fn from(context: &proof::Context) -> Self {
+        Self {
+            trace_layout: Some(TraceLayout::from(context.trace_layout())),
+            trace_length: context.trace_length() as u64,
+            trace_meta: context.get_trace_info().meta().to_canonical_bytes(),
+            field_modulus: context.field_modulus_bytes().to_vec(),
+            options: Some(ProofOptions::from(context.options())),
+            aux_trace_hasher: context.aux_trace_hasher(),
+            prover_id: context.prover_id().map(|id| id.to_string()),
+        }
+    }
+}
+
+impl TryFrom<Context> for proof::Context {
+    type Error = DeserializationError;
+
+    /// Converts a protobuf message into a proof context.
+    ///
+    /// # Errors
+    /// Returns an error if the message does not describe a valid proof context.
+    fn try_from(message: Context) -> Result<Self, Self::Error> {
+        let mut bytes = Vec::new();
+        message.write_canonical(&mut bytes)?;
+        proof::Context::read_from_bytes(&bytes)
+    }
+}
+
+// TRACE LAYOUT MESSAGE
+// ================================================================================================
+
+/// Protobuf message for a [TraceLayout](crate::TraceLayout).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct TraceLayout {
+    /// Number of columns in the main segment of the trace.
+    #[prost(uint32, tag = "1")]
+    pub main_segment_width: u32,
+    /// Number of columns in each of the non-empty auxiliary trace segments.
+    #[prost(uint32, repeated, tag = "2")]
+    pub aux_segment_widths: Vec<u32>,
+    /// Number of random elements required by each of the non-empty auxiliary trace segments.
+    #[prost(uint32, repeated, tag = "3")]
+    pub aux_segment_rands: Vec<u32>,
+}
+
+impl TraceLayout {
+    /// Writes this message into `target` using the serialization format of
+    /// [TraceLayout](crate::TraceLayout).
+    fn write_canonical(&self, target: &mut Vec<u8>) -> Result<(), DeserializationError> {
+        if self.aux_segment_widths.len() != self.aux_segment_rands.len() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of auxiliary segment widths ({}) must match the number of auxiliary \
+                segment random element counts ({})",
+                self.aux_segment_widths.len(),
+                self.aux_segment_rands.len()
+            )));
+        }
+        if self.aux_segment_widths.len() > NUM_AUX_SEGMENTS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of auxiliary segments cannot exceed {NUM_AUX_SEGMENTS}, but was {}",
+                self.aux_segment_widths.len()
+            )));
+        }
+
+        if self.aux_segment_widths.contains(&0) {
+            return Err(DeserializationError::InvalidValue(
+                "auxiliary trace segments cannot be empty".to_string(),
+            ));
+        }
+
+        target.write_u16(to_u16(self.main_segment_width, "main trace segment width")?);
+        for i in 0..NUM_AUX_SEGMENTS {
+            let width = self.aux_segment_widths.get(i).copied().unwrap_or(0);
+            target.write_u16(to_u16(width, "auxiliary trace segment width")?);
+        }
+        for i in 0..NUM_AUX_SEGMENTS {
+            let num_rands = self.aux_segment_rands.get(i).copied().unwrap_or(0);
+            target.write_u16(to_u16(num_rands, "number of auxiliary segment random elements")?);
+        }
+        Ok(())
+    }
+}
+
+impl From<&crate::TraceLayout> for TraceLayout {
+    fn from(layout: &crate::TraceLayout) -> Self {
+        let num_aux_segments = layout.num_aux_segments();
+        Self {
+            main_segment_width: layout.main_trace_width() as u32,
+            aux_segment_widths: (0..num_aux_segments)
+                .map(|i| layout.get_aux_segment_width(i) as u32)
+                .collect(),
+            aux_segment_rands: (0..num_aux_segments)
+                .map(|i| layout.get_aux_segment_rand_elements(i) as u32)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<TraceLayout> for crate::TraceLayout {
+    type Error = DeserializationError;
+
+    /// Converts a protobuf message into a trace layout.
+    ///
+    /// # Errors
+    /// Returns an error if the message does not describe a valid trace layout.
+    fn try_from(message: TraceLayout) -> Result<Self, Self::Error> {
+        let mut bytes = Vec::new();
+        message.write_canonical(&mut bytes)?;
+        crate::TraceLayout::read_from_bytes(&bytes)
+    }
+}
+
+// PROOF OPTIONS MESSAGE
+// ================================================================================================
+
+/// Protobuf message for [ProofOptions](crate::ProofOptions).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ProofOptions {
+    /// Number of queries made by the verifier.
+    #[prost(uint32, tag = "1")]
+    pub num_queries: u32,
+    /// Factor by which the execution trace is extended.
+    #[prost(uint32, tag = "2")]
+    pub blowup_factor: u32,
+    /// Number of bits of proof-of-work required for query seed grinding.
+    #[prost(uint32, tag = "3")]
+    pub grinding_factor: u32,
+    /// Degree of the field extension: 1 (none), 2 (quadratic), or 3 (cubic).
+    #[prost(uint32, tag = "4")]
+    pub field_extension: u32,
+    /// Factor by which the degree of a polynomial is reduced with each FRI layer.
+    #[prost(uint32, tag = "5")]
+    pub fri_folding_factor: u32,
+    /// Maximum allowed degree of the FRI remainder polynomial.
+    #[prost(uint32, tag = "6")]
+    pub fri_remainder_max_degree: u32,
+    /// Method for batching constraints and trace polynomials: 0 (linear) or 1 (algebraic).
+    #[prost(uint32, tag = "7")]
+    pub batching_method: u32,
+    /// Encoding of constraint commitment leaves: 0 (interleaved), 1 (concatenated), 2 (padded
+    /// interleaved), or 3 (padded concatenated).
+    #[prost(uint32, tag = "8")]
+    pub constraint_leaf_encoding: u32,
+    /// Whether the commitment to the first FRI layer is skipped.
+    #[prost(bool, tag = "9")]
+    pub skip_first_fri_layer_commitment: bool,
+    /// Way of sending OOD constraint evaluations: 0 (per column) or 1 (combined).
+    #[prost(uint32, tag = "10")]
+    pub ood_constraint_batching: u32,
+}
+
+impl ProofOptions {
+    /// Writes this message into `target` using the serialization format of
+    /// [ProofOptions](crate::ProofOptions).
+    fn write_canonical(&self, target: &mut Vec<u8>) -> Result<(), DeserializationError> {
+        target.push(to_u8(self.num_queries, "number of queries")?);
+        target.push(to_u8(self.blowup_factor, "blowup factor")?);
+        target.push(to_u8(self.grinding_factor, "grinding factor")?);
+        target.push(to_u8(self.field_extension, "field extension")?);
+        target.push(to_u8(self.fri_folding_factor, "FRI folding factor")?);
+        target.push(to_u8(self.fri_remainder_max_degree, "FRI remainder max degree")?);
+        target.push(to_u8(self.batching_method, "batching method")?);
+        target.push(to_u8(self.constraint_leaf_encoding, "constraint leaf encoding")?);
+        target.push(self.skip_first_fri_layer_commitment as u8);
+        target.push(to_u8(self.ood_constraint_batching, "OOD constraint batching")?);
+        Ok(())
+    }
+}
+
+impl From<&crate::ProofOptions> for ProofOptions {
+    fn from(options: &crate::ProofOptions) -> Self {
+        let fri_options = options.to_fri_options();
+        Self {
+            num_queries: options.num_queries() as u32,
+            blowup_factor: options.blowup_factor() as u32,
+            grinding_factor: options.grinding_factor(),
+            field_extension: options.field_extension() as u32,
+            fri_folding_factor: fri_options.folding_factor() as u32,
+            fri_remainder_max_degree: fri_options.remainder_max_degree() as u32,
+            batching_method: options.batching_method() as u32,
+            constraint_leaf_encoding: options.constraint_leaf_encoding() as u32,
+            skip_first_fri_layer_commitment: options.skip_first_fri_layer_commitment(),
+            ood_constraint_batching: options.ood_constraint_batching() as u32,
+        }
+    }
+}
+
+impl TryFrom<ProofOptions> for crate::ProofOptions {
+    type Error = DeserializationError;
+
+    /// Converts a protobuf message into proof options.
+    ///
+    /// # Errors
+    /// Returns an error if the message contains values which cannot be encoded in proof options.
+    ///
+    /// # Panics
+    /// Panics if any of the values is outside of the range accepted by
+    /// [ProofOptions::new()](crate::ProofOptions::new()).
+    fn try_from(message: ProofOptions) -> Result<Self, Self::Error> {
+        let mut bytes = Vec::new();
+        message.write_canonical(&mut bytes)?;
+        crate::ProofOptions::read_from_bytes(&bytes)
+    }
+}
+
+// COLUMN GROUP PROOF MESSAGE
+// ================================================================================================
+
+/// Protobuf message for a [ColumnGroupProof](super::ColumnGroupProof).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ColumnGroupProof {
+    /// Decommitments of column group values.
+    #[prost(message, optional, tag = "1")]
+    pub queries: Option<Queries>,
+    /// Low-degree proof for the DEEP composition polynomial of the column group.
+    #[prost(message, optional, tag = "2")]
+    pub fri_proof: Option<FriProof>,
+}
+
+impl ColumnGroupProof {
+    /// Writes this message into `target` using the serialization format of
+    /// [proof::ColumnGroupProof].
+    fn write_canonical(self, target: &mut Vec<u8>) -> Result<(), DeserializationError> {
+        required(self.queries, "column group queries")?.write_canonical(target)?;
+        let fri_proof =
+            fri::FriProof::try_from(required(self.fri_proof, "column group FRI proof")?)?;
+        fri_proof.write_into(target);
+        Ok(())
+    }
+}
+
+impl From<&proof::ColumnGroupProof> for ColumnGroupProof {
+    fn from(proof: &proof::ColumnGroupProof) -> Self {
+        Self {
+            queries: Some(Queries::from(&proof.queries)),
+            fri_proof: Some(FriProof::from(&proof.fri_proof)),
+        }
+    }
+}
+
+// QUERIES MESSAGE
+// ================================================================================================
+
+/// Protobuf message for a set of [Queries](super::Queries).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct Queries {
+    /// Serialized values of the queried rows.
+    #[prost(bytes = "vec", tag = "1")]
+    pub values: Vec<u8>,
+    /// Serialized batch Merkle proof for the queried rows.
+    #[prost(bytes = "vec", tag = "2")]
+    pub paths: Vec<u8>,
+    /// Number of bytes used to serialize values of a single queried row.
+    #[prost(uint32, tag = "3")]
+    pub query_size: u32,
+}
+
+impl Queries {
+    /// Writes this message into `target` using the serialization format of [proof::Queries].
+    fn write_canonical(&self, target: &mut Vec<u8>) -> Result<(), DeserializationError> {
+        let query_size = self.query_size as usize;
+        if query_size == 0 || self.values.len() % query_size != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "{} query value bytes cannot be split into queries of {} bytes",
+                self.values.len(),
+                query_size
+            )));
+        }
+        target.write_u32(self.query_size);
+        target.write_u32((self.values.len() / query_size) as u32);
+        target.write_bytes(&self.values);
+        write_bytes(target, &self.paths, 4, "query paths")
+    }
+}
+
+impl From<&proof::Queries> for Queries {
+    fn from(queries: &proof::Queries) -> Self {
+        Self {
+            values: queries.values.clone(),
+            paths: queries.paths.clone(),
+            query_size: queries.query_size as u32,
+        }
+    }
+}
+
+impl TryFrom<Queries> for proof::Queries {
+    type Error = DeserializationError;
+
+    /// Converts a protobuf message into a set of queries.
+    ///
+    /// # Errors
+    /// Returns an error if the message does not describe a valid set of queries.
+    fn try_from(message: Queries) -> Result<Self, Self::Error> {
+        let mut bytes = Vec::new();
+        message.write_canonical(&mut bytes)?;
+        proof::Queries::read_from_bytes(&bytes)
+    }
+}
+
+// OOD FRAME MESSAGE
+// ================================================================================================
+
+/// Protobuf message for an [OodFrame](super::OodFrame).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct OodFrame {
+    /// Serialized trace states at the out-of-domain point and the next point.
+    #[prost(bytes = "vec", tag = "1")]
+    pub trace_states: Vec<u8>,
+    /// Serialized constraint composition column evaluations at the out-of-domain point.
+    #[prost(bytes = "vec", tag = "2")]
+    pub evaluations: Vec<u8>,
+}
+
+impl OodFrame {
+    /// Writes this message into `target` using the serialization format of [proof::OodFrame].
+    fn write_canonical(&self, target: &mut Vec<u8>) -> Result<(), DeserializationError> {
+        write_bytes(target, &self.trace_states, 4, "OOD trace states")?;
+        write_bytes(target, &self.evaluations, 2, "OOD constraint evaluations")
+    }
+}
+
+impl From<&proof::OodFrame> for OodFrame {
+    fn from(frame: &proof::OodFrame) -> Self {
+        Self {
+            trace_states: frame.trace_states.clone(),
+            evaluations: frame.evaluations.clone(),
+        }
+    }
+}
+
+impl TryFrom<OodFrame> for proof::OodFrame {
+    type Error = DeserializationError;
+
+    /// Converts a protobuf message into an out-of-domain frame.
+    ///
+    /// # Errors
+    /// Returns an error if the message does not describe a valid out-of-domain frame.
+    fn try_from(message: OodFrame) -> Result<Self, Self::Error> {
+        let mut bytes = Vec::new();
+        message.write_canonical(&mut bytes)?;
+        proof::OodFrame::read_from_bytes(&bytes)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the value of a required message field, or an error if the field is not set.
+fn required<T>(value: Option<T>, name: &str) -> Result<T, DeserializationError> {
+    value.ok_or_else(|| DeserializationError::InvalidValue(format!("{name} must be set")))
+}
+
+/// Converts `value` into a u8, or returns an error if the value does not fit into a u8.
+fn to_u8(value: u32, name: &str) -> Result<u8, DeserializationError> {
+    u8::try_from(value).map_err(|_| {
+        DeserializationError::InvalidValue(format!(
+            "{name} cannot be greater than {}, but was {value}",
+            u8::MAX
+        ))
+    })
+}
+
+/// Converts `value` into a u16, or returns an error if the value does not fit into a u16.
+fn to_u16(value: u32, name: &str) -> Result<u16, DeserializationError> {
+    u16::try_from(value).map_err(|_| {
+        DeserializationError::InvalidValue(format!(
+            "{name} cannot be greater than {}, but was {value}",
+            u16::MAX
+        ))
+    })
+}
+
+/// Writes `bytes` into `target` prefixed by their length encoded with `prefix_size` bytes.
+///
+/// # Errors
+/// Returns an error if the length of `bytes` cannot be encoded with `prefix_size` bytes.
+fn write_bytes(
+    target: &mut Vec<u8>,
+    bytes: &[u8],
+    prefix_size: usize,
+    name: &str,
+) -> Result<(), DeserializationError> {
+    debug_assert!(prefix_size <= 4, "prefix size cannot exceed 4 bytes");
+    let len = bytes.len() as u64;
+    if len >= 1 << (8 * prefix_size) {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{name} cannot be longer than {} bytes, but was {len} bytes",
+            (1_u64 << (8 * prefix_size)) - 1
+        )));
+    }
+    target.extend_from_slice(&len.to_le_bytes()[..prefix_size]);
+    target.extend_from_slice(bytes);
+    Ok(())
+}
@@ -4,11 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::Table;
+use crate::{ConstraintLeafEncoding, RowHashStrategy};
 use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::FieldElement;
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    collections::Vec, string::ToString, vector_from_fn, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable, SliceReader,
 };
 
 // QUERIES
@@ -27,10 +28,18 @@ use utils::{
 /// Internally, all Merkle paths and query values are stored as a sequence of bytes. Thus, to
 /// retrieve query values and the corresponding Merkle authentication paths,
 /// [parse()](Queries::parse) function should be used.
+///
+/// Values of all queries are serialized using the same number of bytes, and this number is
+/// recorded together with the number of queries. Thus, values of the $i$-th query are located at
+/// offset $i \cdot s$ from the start of the values section (where $s$ is the size of a single
+/// query), and can be retrieved via [get_query_values()](Queries::get_query_values) without
+/// parsing values of other queries. In serialized form, the values section starts right after
+/// the 8-byte header containing the query size and the number of queries.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Queries {
-    paths: Vec<u8>,
-    values: Vec<u8>,
+    pub(super) paths: Vec<u8>,
+    pub(super) values: Vec<u8>,
+    pub(super) query_size: usize,
 }
 
 impl Queries {
@@ -72,8 +81,61 @@ impl Queries {
         // serialize internal nodes of the batch Merkle proof; we care about internal nodes only
         // because leaf nodes can be reconstructed from hashes of query values
         let paths = merkle_proof.serialize_nodes();
+        let query_size = elements_per_query * E::ELEMENT_BYTES;
+
+        Queries {
+            paths,
+            values,
+            query_size,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
 
-        Queries { paths, values }
+    /// Returns the number of queries contained in this struct.
+    pub fn num_queries(&self) -> usize {
+        self.values.len() / self.query_size
+    }
+
+    /// Returns the number of bytes used to serialize values of a single query.
+    pub fn query_size(&self) -> usize {
+        self.query_size
+    }
+
+    /// Returns values of the query at the specified index without parsing values of any other
+    /// queries.
+    ///
+    /// Note that the returned values are not authenticated; to authenticate them against a
+    /// commitment, all queries must be parsed via [parse()](Queries::parse) or a similar
+    /// function.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `index` is greater than or equal to the number of queries.
+    /// * The size of a query is not a multiple of the size of an element in field `E`.
+    /// * Query values could not be parsed into elements of field `E`.
+    pub fn get_query_values<E: FieldElement>(
+        &self,
+        index: usize,
+    ) -> Result<Vec<E>, DeserializationError> {
+        if index >= self.num_queries() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "query index {index} is out of bounds for {} queries",
+                self.num_queries()
+            )));
+        }
+        if self.query_size % E::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "query size of {} bytes is not a multiple of field element size {}",
+                self.query_size,
+                E::ELEMENT_BYTES
+            )));
+        }
+
+        let offset = index * self.query_size;
+        let mut reader = SliceReader::new(&self.values[offset..offset + self.query_size]);
+        E::read_batch_from(&mut reader, self.query_size / E::ELEMENT_BYTES)
     }
 
     // PARSER
@@ -95,6 +157,78 @@ impl Queries {
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.parse_with_row_hash(
+            domain_size,
+            num_queries,
+            values_per_query,
+            RowHashStrategy::FullRow,
+        )
+    }
+
+    /// Convert internally stored bytes into a set of query values and the corresponding Merkle
+    /// authentication paths; query values are hashed into leaves of the Merkle tree using the
+    /// specified row hash strategy.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `num_queries` is zero.
+    /// * `values_per_query` is zero.
+    pub fn parse_with_row_hash<H, E>(
+        self,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+        row_hash: RowHashStrategy,
+    ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.parse_with_leaf_hasher(domain_size, num_queries, values_per_query, |row| {
+            row_hash.hash_row::<H, E>(row)
+        })
+    }
+
+    /// Convert internally stored bytes into a set of query values and the corresponding Merkle
+    /// authentication paths; query values are packed into leaves of the Merkle tree using the
+    /// specified constraint leaf encoding.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `num_queries` is zero.
+    /// * `values_per_query` is zero.
+    pub fn parse_with_leaf_encoding<H, E>(
+        self,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+        encoding: ConstraintLeafEncoding,
+    ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.parse_with_leaf_hasher(domain_size, num_queries, values_per_query, |row| {
+            encoding.hash_leaf::<H, E>(row)
+        })
+    }
+
+    /// Parses query values and Merkle authentication paths using the provided function to hash
+    /// values of each query into a leaf of the Merkle tree.
+    fn parse_with_leaf_hasher<H, E, F>(
+        self,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+        hash_leaf: F,
+    ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        F: Fn(&[E]) -> H::Digest + Sync,
     {
         assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
         assert!(num_queries > 0, "there must be at least one query");
@@ -102,6 +236,12 @@ impl Queries {
 
         // make sure we have enough bytes to read the expected number of queries
         let num_query_bytes = E::ELEMENT_BYTES * values_per_query;
+        if self.query_size != num_query_bytes {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {} bytes per query, but was {}",
+                num_query_bytes, self.query_size
+            )));
+        }
         let expected_bytes = num_queries * num_query_bytes;
         if self.values.len() != expected_bytes {
             return Err(DeserializationError::InvalidValue(format!(
@@ -114,7 +254,7 @@ impl Queries {
         // read bytes corresponding to each query, convert them into field elements,
         // and also hash them to build leaf nodes of the batch Merkle proof
         let query_values = Table::<E>::from_bytes(&self.values, num_queries, values_per_query)?;
-        let hashed_queries = query_values.rows().map(|row| H::hash_elements(row)).collect();
+        let hashed_queries = vector_from_fn(num_queries, |i| hash_leaf(query_values.get_row(i)));
 
         // build batch Merkle proof
         let mut reader = SliceReader::new(&self.paths);
@@ -131,8 +271,10 @@ impl Queries {
 impl Serializable for Queries {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        // write value bytes
-        target.write_u32(self.values.len() as u32);
+        // write value bytes prefixed by the size of a single query and the number of queries;
+        // this way, values of any query can be located without reading other queries
+        target.write_u32(self.query_size as u32);
+        target.write_u32(self.num_queries() as u32);
         target.write_bytes(&self.values);
 
         // write path bytes
@@ -148,13 +290,77 @@ impl Deserializable for Queries {
     /// Returns an error of a valid query struct could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read values
-        let num_value_bytes = source.read_u32()?;
-        let values = source.read_vec(num_value_bytes as usize)?;
+        let query_size = source.read_u32()? as usize;
+        if query_size == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "query size must be greater than zero".to_string(),
+            ));
+        }
+        let num_queries = source.read_u32()? as usize;
+        let num_value_bytes = num_queries.checked_mul(query_size).ok_or_else(|| {
+            DeserializationError::InvalidValue("query values are too large".to_string())
+        })?;
+        let values = source.read_vec(num_value_bytes)?;
 
         // read paths
         let num_paths_bytes = source.read_u32()?;
         let paths = source.read_vec(num_paths_bytes as usize)?;
 
-        Ok(Queries { paths, values })
+        Ok(Queries {
+            paths,
+            values,
+            query_size,
+        })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::Queries;
+    use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
+    use math::fields::f64::BaseElement;
+    use utils::{collections::Vec, Deserializable, DeserializationError, Serializable};
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    #[test]
+    fn queries_random_access() {
+        let rows = (0..8u64)
+            .map(|i| vec![BaseElement::new(i), BaseElement::new(i * i)])
+            .collect::<Vec<_>>();
+        let leaves = rows.iter().map(|row| Blake3::hash_elements(row)).collect();
+        let tree = MerkleTree::<Blake3>::new(leaves).unwrap();
+        let positions = [1, 5, 6];
+        let proof = tree.prove_batch(&positions).unwrap();
+        let query_values = positions.iter().map(|&p| rows[p].clone()).collect();
+
+        let queries = Queries::new(proof, query_values);
+        let queries = Queries::read_from_bytes(&queries.to_bytes()).unwrap();
+        assert_eq!(3, queries.num_queries());
+        assert_eq!(16, queries.query_size());
+        for (i, &position) in positions.iter().enumerate() {
+            assert_eq!(rows[position], queries.get_query_values::<BaseElement>(i).unwrap());
+        }
+        assert!(matches!(
+            queries.get_query_values::<BaseElement>(3),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+
+        // values of the i-th query start at offset 8 + i * query_size
+        let bytes = queries.to_bytes();
+        assert_eq!(16u32.to_le_bytes(), bytes[..4]);
+        assert_eq!(3u32.to_le_bytes(), bytes[4..8]);
+        assert_eq!(rows[5][0].to_bytes(), bytes[24..32]);
+
+        // a query size which does not match the expected number of values is rejected
+        assert!(matches!(
+            queries.clone().parse::<Blake3, BaseElement>(8, 3, 1),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+        let (_, table) = queries.parse::<Blake3, BaseElement>(8, 3, 2).unwrap();
+        assert_eq!(rows[6], table.get_row(2));
     }
 }
@@ -7,13 +7,18 @@ use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion
 use math::fields::f128;
 use rand_utils::rand_value;
 use winter_crypto::{
-    hashers::{Blake3_256, GriffinJive64_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256},
+    hashers::{
+        Blake3_256, GriffinJive64_256, Rp62_248, Rp64_256, RpJive64_256, Sha2_256, Sha3_256,
+    },
     Hasher,
 };
 
 type Blake3 = Blake3_256<f128::BaseElement>;
 type Blake3Digest = <Blake3 as Hasher>::Digest;
 
+type Sha2 = Sha2_256<f128::BaseElement>;
+type Sha2Digest = <Sha2 as Hasher>::Digest;
+
 type Sha3 = Sha3_256<f128::BaseElement>;
 type Sha3Digest = <Sha3 as Hasher>::Digest;
 
@@ -40,6 +45,24 @@ fn blake3(c: &mut Criterion) {
     });
 }
 
+fn sha2(c: &mut Criterion) {
+    let v: [Sha2Digest; 2] = [Sha2::hash(&[1u8]), Sha2::hash(&[2u8])];
+    c.bench_function("hash_sha2 (cached)", |bench| bench.iter(|| Sha2::merge(black_box(&v))));
+
+    c.bench_function("hash_sha2 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Sha2::hash(&rand_value::<u64>().to_le_bytes()),
+                    Sha2::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Sha2::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 fn sha3(c: &mut Criterion) {
     let v: [Sha3Digest; 2] = [Sha3::hash(&[1u8]), Sha3::hash(&[2u8])];
     c.bench_function("hash_sha3 (cached)", |bench| bench.iter(|| Sha3::merge(black_box(&v))));
@@ -139,5 +162,14 @@ fn griffin_jive256(c: &mut Criterion) {
     });
 }
 
-criterion_group!(hash_group, blake3, sha3, rescue248, rescue256, rescue_jive256, griffin_jive256,);
+criterion_group!(
+    hash_group,
+    blake3,
+    sha2,
+    sha3,
+    rescue248,
+    rescue256,
+    rescue_jive256,
+    griffin_jive256,
+);
 criterion_main!(hash_group);
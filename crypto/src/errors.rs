@@ -23,6 +23,9 @@ pub enum MerkleTreeError {
     TooFewLeafIndexes,
     /// Too many leaf index were provided for a batch Merkle proof.
     TooManyLeafIndexes(usize, usize),
+    /// A range of leaves for a range Merkle proof was empty; the range was specified by its start
+    /// (inclusive) and end (exclusive) indexes.
+    InvalidLeafRange(usize, usize),
     /// Merkle proof is not valid for the specified position(s).
     InvalidProof,
 }
@@ -54,6 +57,12 @@ impl fmt::Display for MerkleTreeError {
                     "number of leaf indexes cannot exceed {max_indexes}, but {num_indexes} was provided"
                 )
             }
+            Self::InvalidLeafRange(start, end) => {
+                write!(
+                    f,
+                    "a range of leaves cannot be empty, but range {start}..{end} was provided"
+                )
+            }
             Self::InvalidProof => {
                 write!(f, "Merkle proof is invalid")
             }
@@ -89,3 +98,36 @@ impl fmt::Display for RandomCoinError {
         }
     }
 }
+
+// TRANSCRIPT ERROR
+// ================================================================================================
+
+/// Defines errors which can occur when contributing to a shared transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptError {
+    /// A participant index was greater than or equal to the number of participants.
+    ParticipantOutOfBounds(usize, usize),
+    /// A participant with the specified index contributed more than one commitment.
+    DuplicateContribution(usize),
+    /// A participant with the specified index has not contributed a commitment.
+    MissingContribution(usize),
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParticipantOutOfBounds(num_participants, participant) => {
+                write!(
+                    f,
+                    "participant index must be smaller than {num_participants}, but was {participant}"
+                )
+            }
+            Self::DuplicateContribution(participant) => {
+                write!(f, "participant {participant} has already contributed a commitment")
+            }
+            Self::MissingContribution(participant) => {
+                write!(f, "participant {participant} has not contributed a commitment")
+            }
+        }
+    }
+}
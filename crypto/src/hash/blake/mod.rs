@@ -44,14 +44,14 @@ impl<B: StarkField> ElementHasher for Blake3_256<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        if B::IS_CANONICAL {
-            // when element's internal and canonical representations are the same, we can hash
-            // element bytes directly
+        if B::IS_CANONICAL && cfg!(target_endian = "little") {
+            // when element's internal and canonical representations are the same, and the target
+            // stores values in little-endian byte order, we can hash element bytes directly
             let bytes = E::elements_as_bytes(elements);
             ByteDigest(*blake3::hash(bytes).as_bytes())
         } else {
-            // when elements' internal and canonical representations differ, we need to serialize
-            // them before hashing
+            // otherwise, we need to serialize elements before hashing to make sure the hash is
+            // computed over their canonical little-endian encodings
             let mut hasher = BlakeHasher::new();
             hasher.write(elements);
             ByteDigest(hasher.finalize())
@@ -96,15 +96,15 @@ impl<B: StarkField> ElementHasher for Blake3_192<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        if B::IS_CANONICAL {
-            // when element's internal and canonical representations are the same, we can hash
-            // element bytes directly
+        if B::IS_CANONICAL && cfg!(target_endian = "little") {
+            // when element's internal and canonical representations are the same, and the target
+            // stores values in little-endian byte order, we can hash element bytes directly
             let bytes = E::elements_as_bytes(elements);
             let result = blake3::hash(bytes);
             ByteDigest(result.as_bytes()[..24].try_into().unwrap())
         } else {
-            // when elements' internal and canonical representations differ, we need to serialize
-            // them before hashing
+            // otherwise, we need to serialize elements before hashing to make sure the hash is
+            // computed over their canonical little-endian encodings
             let mut hasher = BlakeHasher::new();
             hasher.write(elements);
             let result = hasher.finalize();
@@ -4,8 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{Blake3_256, ElementHasher, Hasher};
-use math::{fields::f62::BaseElement, FieldElement};
+use math::{
+    fields::{f128, f62::BaseElement},
+    FieldElement,
+};
 use rand_utils::rand_array;
+use utils::Serializable;
 
 #[test]
 fn hash_padding() {
@@ -28,3 +32,19 @@ fn hash_elements_padding() {
     let r2 = Blake3_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn hash_elements_canonical_encoding() {
+    // hashing elements must be equivalent to hashing their canonical little-endian encodings,
+    // including for fields where element bytes are hashed directly
+    let e1: [f128::BaseElement; 4] = rand_array();
+    let expected = Blake3_256::<f128::BaseElement>::hash(
+        &e1.iter().flat_map(|e| e.to_bytes()).collect::<Vec<_>>(),
+    );
+    assert_eq!(expected, Blake3_256::hash_elements(&e1));
+
+    let e2: [BaseElement; 4] = rand_array();
+    let expected =
+        Blake3_256::<BaseElement>::hash(&e2.iter().flat_map(|e| e.to_bytes()).collect::<Vec<_>>());
+    assert_eq!(expected, Blake3_256::hash_elements(&e2));
+}
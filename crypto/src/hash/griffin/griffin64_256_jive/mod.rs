@@ -53,7 +53,7 @@ const NUM_ROUNDS: usize = 7;
 ///   margin used in the specifications (a 20% margin rounds up to 8 rounds). The primary
 ///   motivation for this is that having the number of rounds be one less than a power of two
 ///   simplifies AIR design for computations involving the hash function.
-/// *  When hashing a sequence of elements, implement the Hirose padding rule. However, it also
+/// * When hashing a sequence of elements, implement the Hirose padding rule. However, it also
 ///   means that our instantiation of Griffin cannot be used in a stream mode as the number
 ///   of elements to be hashed must be known upfront.
 /// * Instead of using the suggested matrix as described in Griffin paper, we use a methodology
@@ -5,13 +5,17 @@
 
 use core::{fmt::Debug, slice};
 use math::{FieldElement, StarkField};
-use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, Deserializable, DeserializationError, Serializable,
+};
 
 mod blake;
 pub use blake::{Blake3_192, Blake3_256};
 
 mod sha;
-pub use sha::Sha3_256;
+pub use sha::{Sha2_256, Sha3_256};
 
 mod mds;
 
@@ -21,6 +25,9 @@ pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
 mod griffin;
 pub use griffin::GriffinJive64_256;
 
+mod truncated;
+pub use truncated::Truncated;
+
 // HASHER TRAITS
 // ================================================================================================
 
@@ -57,6 +64,12 @@ pub trait ElementHasher: Hasher {
     type BaseField: StarkField;
 
     /// Returns a hash of the provided field elements.
+    ///
+    /// Elements in an extension of the base field are hashed as sequences of their base field
+    /// coordinates. That is, hashing a slice of extension field elements yields the same digest
+    /// as hashing the slice of base field elements obtained via
+    /// [FieldElement::slice_as_base_elements()], and thus, slices of extension field elements do
+    /// not need to be flattened before hashing.
     fn hash_elements<E>(elements: &[E]) -> Self::Digest
     where
         E: FieldElement<BaseField = Self::BaseField>;
@@ -76,11 +89,43 @@ pub trait Digest:
     /// upper limit on the possible digest size. For digests which are smaller than 32 bytes, the
     /// unused bytes should be set to 0.
     fn as_bytes(&self) -> [u8; 32];
+
+    /// Returns this digest encoded as a lowercase hex string.
+    ///
+    /// The string encodes serialized bytes of the digest, and thus, contains two hex characters
+    /// for each byte written by [Serializable::write_into()].
+    fn to_hex(&self) -> String {
+        bytes_to_hex(&self.to_bytes())
+    }
+
+    /// Parses a digest from the provided hex string.
+    ///
+    /// The string is expected to encode serialized bytes of the digest (as returned by
+    /// [Digest::to_hex()]), optionally prefixed with `0x`; both lowercase and uppercase hex
+    /// characters are accepted.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The string is not a valid hex string.
+    /// * The number of encoded bytes is different from the serialized size of the digest.
+    /// * The encoded bytes are not a canonical serialization of a digest.
+    fn from_hex(hex: &str) -> Result<Self, DeserializationError> {
+        let bytes = hex_to_bytes(hex)?;
+        let digest = Self::read_from_bytes(&bytes)?;
+        if digest.to_bytes() != bytes {
+            return Err(DeserializationError::InvalidValue(
+                "hex string is not a canonical encoding of a digest".to_string(),
+            ));
+        }
+        Ok(digest)
+    }
 }
 
 // BYTE DIGEST
 // ================================================================================================
 
+/// A digest consisting of `N` bytes; this is the output type of byte-oriented hash functions
+/// (e.g., BLAKE3 and SHA3).
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct ByteDigest<const N: usize>([u8; N]);
 
@@ -112,6 +157,24 @@ impl<const N: usize> Digest for ByteDigest<N> {
     }
 }
 
+impl<const N: usize> AsRef<[u8]> for ByteDigest<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for ByteDigest<N> {
+    fn from(value: [u8; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: usize> From<ByteDigest<N>> for [u8; N] {
+    fn from(value: ByteDigest<N>) -> Self {
+        value.0
+    }
+}
+
 impl<const N: usize> Default for ByteDigest<N> {
     fn default() -> Self {
         ByteDigest([0; N])
@@ -130,9 +193,55 @@ impl<const N: usize> Deserializable for ByteDigest<N> {
     }
 }
 
+// HEX ENCODING
+// ================================================================================================
+
+/// Encodes the provided bytes as a lowercase hex string.
+fn bytes_to_hex(bytes: &[u8]) -> String {
+    const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        result.push(HEX_CHARS[(byte >> 4) as usize] as char);
+        result.push(HEX_CHARS[(byte & 0xf) as usize] as char);
+    }
+    result
+}
+
+/// Decodes the provided hex string (optionally prefixed with `0x`) into a vector of bytes.
+fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, DeserializationError> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+    if hex.len() % 2 != 0 {
+        return Err(DeserializationError::InvalidValue(
+            "hex string must contain an even number of characters".to_string(),
+        ));
+    }
+
+    let decode_char = |c: u8| match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(DeserializationError::InvalidValue(format!(
+            "invalid hex character '{}'",
+            c as char
+        ))),
+    };
+    hex.chunks(2)
+        .map(|pair| Ok((decode_char(pair[0])? << 4) | decode_char(pair[1])?))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ByteDigest, Digest};
+    use super::{
+        Blake3_192, Blake3_256, ByteDigest, Digest, ElementHasher, GriffinJive64_256, Rp62_248,
+        Rp64_256, RpJive64_256, Sha2_256, Sha3_256,
+    };
+    use math::{
+        fields::{f128, f62, f64, CubeExtension, QuadExtension},
+        FieldElement,
+    };
+    use rand_utils::rand_vector;
+    use utils::DeserializationError;
 
     #[test]
     fn byte_digest_as_bytes() {
@@ -144,4 +253,56 @@ mod tests {
         expected[31] = 0;
         assert_eq!(expected, d.as_bytes());
     }
+
+    #[test]
+    fn hash_extension_elements() {
+        fn check<H: ElementHasher, E: FieldElement<BaseField = H::BaseField>>() {
+            let elements = rand_vector::<E>(7);
+            let base_elements = E::slice_as_base_elements(&elements);
+            assert_eq!(H::hash_elements(&elements), H::hash_elements(base_elements));
+        }
+
+        check::<Blake3_256<f64::BaseElement>, QuadExtension<f64::BaseElement>>();
+        check::<Blake3_256<f64::BaseElement>, CubeExtension<f64::BaseElement>>();
+        check::<Blake3_192<f62::BaseElement>, QuadExtension<f62::BaseElement>>();
+        check::<Sha3_256<f128::BaseElement>, QuadExtension<f128::BaseElement>>();
+        check::<Sha2_256<f62::BaseElement>, CubeExtension<f62::BaseElement>>();
+        check::<Rp64_256, QuadExtension<f64::BaseElement>>();
+        check::<Rp64_256, CubeExtension<f64::BaseElement>>();
+        check::<RpJive64_256, CubeExtension<f64::BaseElement>>();
+        check::<GriffinJive64_256, QuadExtension<f64::BaseElement>>();
+        check::<Rp62_248, QuadExtension<f62::BaseElement>>();
+    }
+
+    #[test]
+    fn byte_digest_hex() {
+        let mut bytes = [0_u8; 24];
+        bytes.iter_mut().enumerate().for_each(|(i, b)| *b = (i * 11) as u8);
+        let d = ByteDigest::new(bytes);
+
+        let hex = d.to_hex();
+        assert_eq!("000b16212c37424d58636e79848f9aa5b0bbc6d1dce7f2fd", hex);
+        assert_eq!(d, ByteDigest::from_hex(&hex).unwrap());
+        assert_eq!(d, ByteDigest::from_hex(&format!("0x{}", hex.to_uppercase())).unwrap());
+        assert_eq!(bytes, <[u8; 24]>::from(d));
+        assert_eq!(&bytes[..], d.as_ref());
+
+        // malformed strings and strings of a wrong length are rejected
+        assert!(matches!(
+            ByteDigest::<24>::from_hex(&hex[1..]),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            ByteDigest::<24>::from_hex(&hex.replace('b', "x")),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            ByteDigest::<24>::from_hex(&hex[2..]),
+            Err(DeserializationError::UnexpectedEOF)
+        ));
+        assert!(matches!(
+            ByteDigest::<24>::from_hex(&format!("{hex}00")),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
 }
@@ -6,7 +6,9 @@
 use super::{Digest, DIGEST_SIZE};
 use core::slice;
 use math::{fields::f62::BaseElement, StarkField};
-use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use utils::{
+    string::ToString, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // DIGEST TRAIT IMPLEMENTATIONS
 // ================================================================================================
@@ -68,16 +70,46 @@ impl Deserializable for ElementDigest {
         let v5 = source.read_u16()?;
         let v6 = source.read_u8()?;
 
-        let e1 = BaseElement::new(v1 & 0x3FFFFFFFFFFFFFFF);
-        let e2 = BaseElement::new(((v2 << 4) >> 2) | (v1 >> 62) & 0x3FFFFFFFFFFFFFFF);
-        let e3 = BaseElement::new(((v3 << 6) >> 2) | (v2 >> 60) & 0x3FFFFFFFFFFFFFFF);
-        let e4 =
-            BaseElement::new(v3 >> 58 | (v4 as u64) << 6 | (v5 as u64) << 38 | (v6 as u64) << 54);
+        // reject non-canonical encodings of digest elements so that each digest has a single
+        // valid encoding
+        let e1 = BaseElement::try_from_repr(v1 & 0x3FFFFFFFFFFFFFFF)?;
+        let e2 = BaseElement::try_from_repr(((v2 << 4) >> 2) | (v1 >> 62) & 0x3FFFFFFFFFFFFFFF)?;
+        let e3 = BaseElement::try_from_repr(((v3 << 6) >> 2) | (v2 >> 60) & 0x3FFFFFFFFFFFFFFF)?;
+        let e4 = BaseElement::try_from_repr(
+            v3 >> 58 | (v4 as u64) << 6 | (v5 as u64) << 38 | (v6 as u64) << 54,
+        )?;
 
         Ok(Self([e1, e2, e3, e4]))
     }
 }
 
+impl From<ElementDigest> for [u8; 31] {
+    fn from(value: ElementDigest) -> Self {
+        let mut result = [0; 31];
+        result.copy_from_slice(&value.as_bytes()[..31]);
+        result
+    }
+}
+
+impl TryFrom<[u8; 31]> for ElementDigest {
+    type Error = DeserializationError;
+
+    /// Converts the provided bytes into a digest.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a canonical encoding of a digest (i.e., if any of the
+    /// encoded elements is not smaller than the field modulus).
+    fn try_from(bytes: [u8; 31]) -> Result<Self, Self::Error> {
+        let digest = Self::read_from_bytes(&bytes)?;
+        if <[u8; 31]>::from(digest) != bytes {
+            return Err(DeserializationError::InvalidValue(
+                "bytes are not a canonical encoding of a digest".to_string(),
+            ));
+        }
+        Ok(digest)
+    }
+}
+
 // TESTS
 // ================================================================================================
 
@@ -85,6 +117,7 @@ impl Deserializable for ElementDigest {
 mod tests {
 
     use super::ElementDigest;
+    use crate::Digest;
     use rand_utils::rand_array;
     use utils::{Deserializable, Serializable, SliceReader};
 
@@ -101,4 +134,19 @@ mod tests {
 
         assert_eq!(d1, d2);
     }
+
+    #[test]
+    fn digest_conversions() {
+        let d1 = ElementDigest(rand_array());
+
+        let bytes = <[u8; 31]>::from(d1);
+        assert_eq!(d1, ElementDigest::try_from(bytes).unwrap());
+        assert_eq!(d1, ElementDigest::from_hex(&d1.to_hex()).unwrap());
+        assert_eq!(62, d1.to_hex().len());
+
+        // encodings of elements which are not smaller than the field modulus are rejected
+        assert!(ElementDigest::try_from([255; 31]).is_err());
+        assert!(ElementDigest::from_hex(&"ff".repeat(31)).is_err());
+        assert!(ElementDigest::read_from_bytes(&[255; 31]).is_err());
+    }
 }
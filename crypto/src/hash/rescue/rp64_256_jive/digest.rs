@@ -6,7 +6,9 @@
 use super::{Digest, DIGEST_SIZE};
 use core::slice;
 use math::{fields::f64::BaseElement, StarkField};
-use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use utils::{
+    string::ToString, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // DIGEST TRAIT IMPLEMENTATIONS
 // ================================================================================================
@@ -57,11 +59,12 @@ impl Serializable for ElementDigest {
 
 impl Deserializable for ElementDigest {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        // TODO: check if the field elements are valid?
-        let e1 = BaseElement::new(source.read_u64()?);
-        let e2 = BaseElement::new(source.read_u64()?);
-        let e3 = BaseElement::new(source.read_u64()?);
-        let e4 = BaseElement::new(source.read_u64()?);
+        // reject non-canonical encodings of digest elements so that each digest has a single
+        // valid encoding
+        let e1 = BaseElement::try_from_repr(source.read_u64()?)?;
+        let e2 = BaseElement::try_from_repr(source.read_u64()?)?;
+        let e3 = BaseElement::try_from_repr(source.read_u64()?)?;
+        let e4 = BaseElement::try_from_repr(source.read_u64()?)?;
 
         Ok(Self([e1, e2, e3, e4]))
     }
@@ -85,6 +88,25 @@ impl From<ElementDigest> for [u8; 32] {
     }
 }
 
+impl TryFrom<[u8; 32]> for ElementDigest {
+    type Error = DeserializationError;
+
+    /// Converts the provided bytes into a digest.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a canonical encoding of a digest (i.e., if any of the
+    /// encoded elements is not smaller than the field modulus).
+    fn try_from(bytes: [u8; 32]) -> Result<Self, Self::Error> {
+        let digest = Self::read_from_bytes(&bytes)?;
+        if digest.as_bytes() != bytes {
+            return Err(DeserializationError::InvalidValue(
+                "bytes are not a canonical encoding of a digest".to_string(),
+            ));
+        }
+        Ok(digest)
+    }
+}
+
 // TESTS
 // ================================================================================================
 
@@ -9,6 +9,9 @@ use math::{FieldElement, StarkField};
 use sha3::Digest;
 use utils::ByteWriter;
 
+#[cfg(test)]
+mod tests;
+
 // SHA3 WITH 256-BIT OUTPUT
 // ================================================================================================
 
@@ -41,15 +44,67 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        if B::IS_CANONICAL {
-            // when element's internal and canonical representations are the same, we can hash
-            // element bytes directly
+        if B::IS_CANONICAL && cfg!(target_endian = "little") {
+            // when element's internal and canonical representations are the same, and the target
+            // stores values in little-endian byte order, we can hash element bytes directly
             let bytes = E::elements_as_bytes(elements);
             ByteDigest(sha3::Sha3_256::digest(bytes).into())
         } else {
-            // when elements' internal and canonical representations differ, we need to serialize
-            // them before hashing
-            let mut hasher = ShaHasher::new();
+            // otherwise, we need to serialize elements before hashing to make sure the hash is
+            // computed over their canonical little-endian encodings
+            let mut hasher = ShaHasher::<sha3::Sha3_256>::new();
+            hasher.write(elements);
+            ByteDigest(hasher.finalize())
+        }
+    }
+}
+
+// SHA2 WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for SHA-256 hash function (from the SHA2
+/// family).
+///
+/// On x86_64 CPUs supporting SHA extensions (SHA-NI) and on AArch64 CPUs supporting ARMv8
+/// cryptography extensions, the hash function is computed using hardware-accelerated
+/// instructions; availability of these instructions is detected at runtime, and a portable
+/// software implementation is used when they are not available.
+pub struct Sha2_256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Sha2_256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha2::Sha256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha2::Sha256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(sha2::Sha256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Sha2_256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL && cfg!(target_endian = "little") {
+            // when element's internal and canonical representations are the same, and the target
+            // stores values in little-endian byte order, we can hash element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(sha2::Sha256::digest(bytes).into())
+        } else {
+            // otherwise, we need to serialize elements before hashing to make sure the hash is
+            // computed over their canonical little-endian encodings
+            let mut hasher = ShaHasher::<sha2::Sha256>::new();
             hasher.write(elements);
             ByteDigest(hasher.finalize())
         }
@@ -59,20 +114,22 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
 // SHA HASHER
 // ================================================================================================
 
-/// Wrapper around SHA3 hasher to implement [ByteWriter] trait for it.
-struct ShaHasher(sha3::Sha3_256);
+/// Wrapper around SHA2 and SHA3 hashers to implement [ByteWriter] trait for them.
+struct ShaHasher<D: Digest>(D);
 
-impl ShaHasher {
+impl<D: Digest> ShaHasher<D> {
     pub fn new() -> Self {
-        Self(sha3::Sha3_256::new())
+        Self(D::new())
     }
 
     pub fn finalize(self) -> [u8; 32] {
-        self.0.finalize().into()
+        let mut result = [0; 32];
+        result.copy_from_slice(&self.0.finalize());
+        result
     }
 }
 
-impl ByteWriter for ShaHasher {
+impl<D: Digest> ByteWriter for ShaHasher<D> {
     fn write_u8(&mut self, value: u8) {
         self.0.update([value]);
     }
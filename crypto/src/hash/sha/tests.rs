@@ -0,0 +1,33 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, Sha2_256};
+use crate::Digest;
+use math::fields::{f128, f62};
+use rand_utils::rand_array;
+use utils::Serializable;
+
+#[test]
+fn sha2_256_known_answer() {
+    let expected = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    assert_eq!(expected, Sha2_256::<f128::BaseElement>::hash(b"abc").to_hex());
+}
+
+#[test]
+fn sha2_256_hash_elements() {
+    // elements of a field with canonical internal representation are hashed as their bytes
+    let e1: [f128::BaseElement; 4] = rand_array();
+    let expected = Sha2_256::<f128::BaseElement>::hash(
+        &e1.iter().flat_map(|e| e.to_bytes()).collect::<Vec<_>>(),
+    );
+    assert_eq!(expected, Sha2_256::hash_elements(&e1));
+
+    // elements of a field with non-canonical internal representation are serialized first
+    let e2: [f62::BaseElement; 4] = rand_array();
+    let expected = Sha2_256::<f62::BaseElement>::hash(
+        &e2.iter().flat_map(|e| e.to_bytes()).collect::<Vec<_>>(),
+    );
+    assert_eq!(expected, Sha2_256::hash_elements(&e2));
+}
@@ -0,0 +1,239 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteDigest, Digest, ElementHasher, Hasher};
+use core::marker::PhantomData;
+use math::FieldElement;
+use utils::{Deserializable, Serializable, SliceReader};
+
+// TRUNCATED HASHER
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait which truncates digests of hash function
+/// `H` to `N` bytes.
+///
+/// Truncated digests reduce the size of commitments and Merkle authentication paths, and thus,
+/// the size of STARK proofs. This is primarily useful when proofs are verified in environments in
+/// which proof size dominates verification cost (e.g., when proofs are submitted as calldata of
+/// blockchain transactions).
+///
+/// Truncating digests to `N` bytes reduces collision resistance of the hash function to `4 * N`
+/// bits, and [COLLISION_RESISTANCE](Hasher::COLLISION_RESISTANCE) of this hasher is set
+/// accordingly. Since collision resistance bounds the security level of STARK proofs, the
+/// security level reported for proofs generated with a truncated hasher is reduced as well. For
+/// example, truncating digests to 20 bytes limits security level of proofs to at most 80 bits.
+///
+/// Digests are truncated by taking the first `N` bytes of [Digest::as_bytes()]. To merge truncated
+/// digests, each of them is first extended back into a digest of `H` by padding it with zeros,
+/// and the extended digests are then merged using [Hasher::merge()] of `H` (and similarly for
+/// [Hasher::merge_with_int()]). Thus, algebraic hash functions (e.g., [Rp64_256](super::Rp64_256))
+/// retain their efficient 2-to-1 compression when their digests are truncated. However, since
+/// the truncated bytes are not available to the verifier, merged digests differ from digests
+/// obtained by merging full digests of `H`.
+///
+/// `N` must be at least 16 and must not exceed the number of bytes in digests of `H` (as implied
+/// by collision resistance of `H`); using this hasher with an invalid `N` results in a
+/// compile-time error.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Truncated<H: Hasher, const N: usize>(PhantomData<H>);
+
+impl<H: Hasher, const N: usize> Truncated<H, N> {
+    /// Evaluates to unit if `N` is a valid number of bytes for truncated digests of `H`, and
+    /// fails to evaluate otherwise.
+    const VALID_DIGEST_SIZE: () = assert!(
+        N >= 16 && N * 4 <= H::COLLISION_RESISTANCE as usize,
+        "invalid truncated digest size"
+    );
+
+    /// Truncates the provided digest of `H` to `N` bytes.
+    fn truncate(digest: H::Digest) -> ByteDigest<N> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_DIGEST_SIZE;
+        let mut result = [0; N];
+        result.copy_from_slice(&digest.as_bytes()[..N]);
+        ByteDigest::new(result)
+    }
+
+    /// Extends the provided truncated digest into a digest of `H` by padding it with zeros.
+    fn extend(digest: &ByteDigest<N>) -> H::Digest {
+        // serialized digests of H may be longer than the 32 bytes returned by Digest::as_bytes()
+        let mut bytes = H::Digest::default().to_bytes();
+        bytes[..N].copy_from_slice(digest.as_ref());
+        bytes[N..].fill(0);
+        // zero-padding a prefix of a valid digest encoding may only reduce the values encoded in
+        // it, and thus, the padded bytes always encode a valid digest
+        H::Digest::read_from(&mut SliceReader::new(&bytes))
+            .expect("zero-padded truncated digest is not a valid digest")
+    }
+}
+
+impl<H: Hasher, const N: usize> Hasher for Truncated<H, N> {
+    type Digest = ByteDigest<N>;
+
+    const COLLISION_RESISTANCE: u32 = N as u32 * 4;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        Self::truncate(H::hash(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        Self::truncate(H::merge(&[Self::extend(&values[0]), Self::extend(&values[1])]))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        Self::truncate(H::merge_with_int(Self::extend(&seed), value))
+    }
+}
+
+impl<H: ElementHasher, const N: usize> ElementHasher for Truncated<H, N> {
+    type BaseField = H::BaseField;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        Self::truncate(H::hash_elements(elements))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{Hasher, Truncated};
+    use crate::{
+        hashers::{Blake3_256, Rp62_248, Rp64_256},
+        Digest, ElementHasher, MerkleTree,
+    };
+    use math::{fields::f64::BaseElement, FieldElement};
+    use rand_utils::rand_array;
+    use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+    type Blake3_160 = Truncated<Blake3_256<BaseElement>, 20>;
+
+    #[test]
+    fn truncated_digests() {
+        assert_eq!(80, Blake3_160::COLLISION_RESISTANCE);
+        assert_eq!(96, Truncated::<Rp64_256, 24>::COLLISION_RESISTANCE);
+
+        // truncated digests are prefixes of digests of the underlying hash function
+        let elements: [BaseElement; 8] = rand_array();
+        let expected = Blake3_256::<BaseElement>::hash_elements(&elements);
+        let actual = Blake3_160::hash_elements(&elements);
+        assert_eq!(expected.as_ref()[..20], actual.as_ref()[..]);
+
+        let expected = Rp64_256::hash(b"abc");
+        let actual = Truncated::<Rp64_256, 24>::hash(b"abc");
+        assert_eq!(crate::Digest::as_bytes(&expected)[..24], actual.as_ref()[..]);
+
+        // digests are merged by applying the 2-to-1 compression of the underlying hash function
+        // to zero-padded digests; a 24-byte prefix of an Rp64_256 digest holds 3 elements
+        let pad = |digest: &<Rp64_256 as Hasher>::Digest| {
+            let e = digest.as_elements();
+            <Rp64_256 as Hasher>::Digest::new([e[0], e[1], e[2], BaseElement::ZERO])
+        };
+        let (a, b) = (Rp64_256::hash(b"a"), Rp64_256::hash(b"b"));
+        let expected = Rp64_256::merge(&[pad(&a), pad(&b)]);
+        let actual = Truncated::<Rp64_256, 24>::merge(&[
+            Truncated::<Rp64_256, 24>::hash(b"a"),
+            Truncated::<Rp64_256, 24>::hash(b"b"),
+        ]);
+        assert_eq!(crate::Digest::as_bytes(&expected)[..24], actual.as_ref()[..]);
+
+        // merging with an integer depends on the integer
+        let seed = Blake3_160::hash(b"seed");
+        assert_ne!(Blake3_160::merge_with_int(seed, 1), Blake3_160::merge_with_int(seed, 2));
+    }
+
+    #[test]
+    fn truncated_merkle_tree() {
+        check_merkle_tree::<Blake3_160>();
+        check_merkle_tree::<Truncated<Rp64_256, 20>>();
+        check_merkle_tree::<Truncated<Rp62_248, 31>>();
+        check_merkle_tree::<Truncated<Blake3_384, 24>>();
+    }
+
+    #[test]
+    fn truncated_wide_digests() {
+        // digests are extended to the serialized size of digests of the underlying hash function
+        type Blake3_192 = Truncated<Blake3_384, 24>;
+        let (a, b) = (Blake3_192::hash(b"a"), Blake3_192::hash(b"b"));
+        let pad = |digest: &<Blake3_192 as Hasher>::Digest| {
+            let mut bytes = [0; 48];
+            bytes[..24].copy_from_slice(digest.as_ref());
+            Digest384(bytes)
+        };
+        let expected = Blake3_384::merge(&[pad(&a), pad(&b)]);
+        assert_eq!(expected.0[..24], Blake3_192::merge(&[a, b]).as_ref()[..]);
+    }
+
+    fn check_merkle_tree<H: Hasher>() {
+        let leaves = (0..16_u8).map(|i| H::hash(&[i])).collect::<Vec<_>>();
+        let tree = MerkleTree::<H>::new(leaves).unwrap();
+
+        let proof = tree.prove_batch(&[1, 6, 11]).unwrap();
+        assert!(MerkleTree::<H>::verify_batch(tree.root(), &[1, 6, 11], &proof).is_ok());
+    }
+
+    // WIDE HASHER
+    // --------------------------------------------------------------------------------------------
+
+    /// Hasher with 48-byte digests, which are serialized into more bytes than returned by
+    /// [Digest::as_bytes()].
+    struct Blake3_384;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Digest384([u8; 48]);
+
+    impl Default for Digest384 {
+        fn default() -> Self {
+            Digest384([0; 48])
+        }
+    }
+
+    impl Digest for Digest384 {
+        fn as_bytes(&self) -> [u8; 32] {
+            let mut result = [0; 32];
+            result.copy_from_slice(&self.0[..32]);
+            result
+        }
+    }
+
+    impl Serializable for Digest384 {
+        fn write_into<W: ByteWriter>(&self, target: &mut W) {
+            target.write_bytes(&self.0);
+        }
+    }
+
+    impl Deserializable for Digest384 {
+        fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+            Ok(Digest384(source.read_array()?))
+        }
+    }
+
+    impl Hasher for Blake3_384 {
+        type Digest = Digest384;
+
+        const COLLISION_RESISTANCE: u32 = 192;
+
+        fn hash(bytes: &[u8]) -> Self::Digest {
+            let mut result = [0; 48];
+            blake3::Hasher::new().update(bytes).finalize_xof().fill(&mut result);
+            Digest384(result)
+        }
+
+        fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+            let mut bytes = [0; 96];
+            bytes[..48].copy_from_slice(&values[0].0);
+            bytes[48..].copy_from_slice(&values[1].0);
+            Self::hash(&bytes)
+        }
+
+        fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+            let mut bytes = [0; 56];
+            bytes[..48].copy_from_slice(&seed.0);
+            bytes[48..].copy_from_slice(&value.to_le_bytes());
+            Self::hash(&bytes)
+        }
+    }
+}
@@ -23,7 +23,7 @@
 extern crate alloc;
 
 mod hash;
-pub use hash::{Digest, ElementHasher, Hasher};
+pub use hash::{ByteDigest, Digest, ElementHasher, Hasher};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
@@ -33,11 +33,13 @@ pub mod hashers {
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
     pub use super::hash::RpJive64_256;
+    pub use super::hash::Sha2_256;
     pub use super::hash::Sha3_256;
+    pub use super::hash::Truncated;
 }
 
 mod merkle;
-pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
+pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree, RangeMerkleProof};
 
 #[cfg(feature = "concurrent")]
 pub use merkle::concurrent;
@@ -45,5 +47,8 @@ pub use merkle::concurrent;
 mod random;
 pub use random::{DefaultRandomCoin, RandomCoin};
 
+mod transcript;
+pub use transcript::SharedTranscript;
+
 mod errors;
-pub use errors::{MerkleTreeError, RandomCoinError};
+pub use errors::{MerkleTreeError, RandomCoinError, TranscriptError};
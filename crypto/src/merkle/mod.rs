@@ -8,7 +8,7 @@ use core::slice;
 use utils::collections::{BTreeMap, BTreeSet, Vec};
 
 mod proofs;
-pub use proofs::BatchMerkleProof;
+pub use proofs::{BatchMerkleProof, RangeMerkleProof};
 
 #[cfg(feature = "concurrent")]
 pub mod concurrent;
@@ -52,10 +52,12 @@ mod tests;
 /// You can also use [MerkleTree::prove_batch()] method to generate inclusion proofs for multiple
 /// leaves. The advantage of the batch method is that redundant internal nodes are removed from
 /// the batch proof, thereby compressing it (we use a variation of the
-/// [Octopus](https://eprint.iacr.org/2017/933) algorithm).
+/// [Octopus](https://eprint.iacr.org/2017/933) algorithm). For a contiguous range of leaves,
+/// [MerkleTree::prove_range()] method can be used to generate a proof which contains at most
+/// two internal nodes per tree level.
 ///
-/// To verify proofs, [MerkleTree::verify()] and [MerkleTree::verify_batch()] functions can be
-/// used respectively.
+/// To verify proofs, [MerkleTree::verify()], [MerkleTree::verify_batch()], and
+/// [MerkleTree::verify_range()] functions can be used respectively.
 ///
 /// # Examples
 /// ```
@@ -268,6 +270,66 @@ impl<H: Hasher> MerkleTree<H> {
         })
     }
 
+    /// Computes a Merkle proof for a contiguous range of leaves starting at index `start`
+    /// (inclusive) and ending at index `end` (exclusive).
+    ///
+    /// The proof contains all leaves in the range, and at most two internal nodes per tree level.
+    /// For large ranges this is more compact than a batch proof for the same set of leaves, and
+    /// the number of leaves in the range is not limited.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The range is empty (i.e., `start` is greater than or equal to `end`).
+    /// * `end` is greater than the number of leaves in the tree.
+    pub fn prove_range(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<RangeMerkleProof<H>, MerkleTreeError> {
+        if start >= end {
+            return Err(MerkleTreeError::InvalidLeafRange(start, end));
+        }
+        let n = self.leaves.len();
+        if end > n {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(n, end - 1));
+        }
+
+        // leaves are addressed by indexes offset by the number of leaves; internal nodes are
+        // addressed directly by their positions in the nodes vector
+        let get_node = |index: usize| {
+            if index >= n {
+                self.leaves[index - n]
+            } else {
+                self.nodes[index]
+            }
+        };
+
+        // at every level of the tree, add the siblings of the first and the last nodes of the
+        // range to the proof (unless they are already in the range), and move up to the parents
+        let leaves = self.leaves[start..end].to_vec();
+        let mut nodes = Vec::new();
+        let mut start = start + n;
+        let mut end = end + n;
+        while start > 1 {
+            if start & 1 == 1 {
+                nodes.push(get_node(start - 1));
+                start -= 1;
+            }
+            if end & 1 == 1 {
+                nodes.push(get_node(end));
+                end += 1;
+            }
+            start >>= 1;
+            end >>= 1;
+        }
+
+        Ok(RangeMerkleProof {
+            leaves,
+            nodes,
+            depth: self.depth() as u8,
+        })
+    }
+
     // VERIFICATION METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -320,6 +382,54 @@ impl<H: Hasher> MerkleTree<H> {
         }
         Ok(())
     }
+
+    /// Returns the first of the specified `indexes` for which the Merkle path extracted from the
+    /// batch `proof` does not resolve to the specified `root`, or None if paths for all indexes
+    /// resolve to the root.
+    ///
+    /// This is intended for diagnosing failures of [MerkleTree::verify_batch()]. Since internal
+    /// nodes are shared between paths of a batch proof, an invalid leaf may also invalidate paths
+    /// for other indexes; thus, the returned index is not necessarily the index of the invalid
+    /// leaf. If paths cannot be extracted from the proof, the first of the `indexes` is returned.
+    pub fn find_invalid_index(
+        root: &H::Digest,
+        indexes: &[usize],
+        proof: &BatchMerkleProof<H>,
+    ) -> Option<usize> {
+        let proof = BatchMerkleProof::<H> {
+            leaves: proof.leaves.clone(),
+            nodes: proof.nodes.clone(),
+            depth: proof.depth,
+        };
+        let paths = match proof.into_paths(indexes) {
+            Ok(paths) => paths,
+            Err(_) => return indexes.first().copied(),
+        };
+        indexes
+            .iter()
+            .zip(paths.iter())
+            .find(|(&index, path)| Self::verify(*root, index, path).is_err())
+            .map(|(&index, _)| index)
+    }
+
+    /// Checks whether the range proof contains a contiguous range of leaves starting at the
+    /// specified `start` index.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The range proof does not contain any leaves.
+    /// * The range of leaves does not fit into the tree from which the proof was generated.
+    /// * The range proof does not resolve to the specified `root`.
+    pub fn verify_range(
+        root: &H::Digest,
+        start: usize,
+        proof: &RangeMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if *root != proof.get_root(start)? {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
 }
 
 // HELPER FUNCTIONS
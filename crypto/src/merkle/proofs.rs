@@ -475,6 +475,122 @@ impl<H: Hasher> BatchMerkleProof<H> {
     }
 }
 
+// RANGE MERKLE PROOF
+// ================================================================================================
+
+/// A Merkle proof for a contiguous range of leaves.
+///
+/// The proof contains all leaves in the range and, for each level of the tree, at most two
+/// nodes: the left sibling of the first node in the range and the right sibling of the last node
+/// in the range (each included only if it is not itself in the range). All other nodes needed to
+/// compute the tree root are derived from the leaves in the range. Thus, the size of the proof
+/// grows with the number of leaves in the range plus at most twice the depth of the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMerkleProof<H: Hasher> {
+    /// The leaves in the range being proven
+    pub leaves: Vec<H::Digest>,
+    /// Sibling nodes at the boundaries of the range, ordered from the leaf level up to the root
+    pub nodes: Vec<H::Digest>,
+    /// Depth of the leaves
+    pub depth: u8,
+}
+
+impl<H: Hasher> RangeMerkleProof<H> {
+    /// Computes a node to which all Merkle paths aggregated in this proof resolve, assuming the
+    /// range of leaves starts at the specified `start` index.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This proof does not contain any leaves.
+    /// * The range of leaves does not fit into a tree of depth specified by this proof.
+    /// * The number of nodes in this proof is not consistent with the range of leaves.
+    pub fn get_root(&self, start: usize) -> Result<H::Digest, MerkleTreeError> {
+        let end = start + self.leaves.len();
+        if self.leaves.is_empty() {
+            return Err(MerkleTreeError::InvalidLeafRange(start, end));
+        }
+        let num_leaves = 1usize << self.depth;
+        if end > num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(num_leaves, end - 1));
+        }
+
+        // indexes of nodes are offset by the number of leaves so that the root has index 1 and
+        // the children of a node with index i have indexes 2 * i and 2 * i + 1
+        let mut start = start + num_leaves;
+        let mut end = end + num_leaves;
+        let mut layer = self.leaves.clone();
+        let mut nodes = self.nodes.iter();
+        while start > 1 {
+            // extend the layer with sibling nodes so that it consists of whole sibling pairs
+            if start & 1 == 1 {
+                layer.insert(0, *nodes.next().ok_or(MerkleTreeError::InvalidProof)?);
+                start -= 1;
+            }
+            if end & 1 == 1 {
+                layer.push(*nodes.next().ok_or(MerkleTreeError::InvalidProof)?);
+                end += 1;
+            }
+
+            layer = layer.chunks(2).map(|pair| H::merge(&[pair[0], pair[1]])).collect();
+            start >>= 1;
+            end >>= 1;
+        }
+
+        if nodes.next().is_some() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(layer[0])
+    }
+
+    /// Converts all internal proof nodes into a vector of bytes.
+    pub fn serialize_nodes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+
+        // there are at most two nodes per tree level, and thus, the number of nodes always fits
+        // into a single byte
+        result.push(self.nodes.len() as u8);
+        for node in self.nodes.iter() {
+            result.append(&mut node.to_bytes());
+        }
+
+        result
+    }
+
+    /// Parses internal nodes from the provided `node_bytes`, and constructs a range Merkle proof
+    /// from these nodes, provided `leaves`, and provided tree `depth`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No leaves were provided (i.e., `leaves` is an empty vector).
+    /// * Tree `depth` was set to zero.
+    /// * `node_bytes` could not be deserialized into a valid set of internal nodes.
+    pub fn deserialize<R: ByteReader>(
+        node_bytes: &mut R,
+        leaves: Vec<H::Digest>,
+        depth: u8,
+    ) -> Result<Self, DeserializationError> {
+        if depth == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "tree depth must be greater than zero".to_string(),
+            ));
+        }
+        if leaves.is_empty() {
+            return Err(DeserializationError::InvalidValue(
+                "at lease one leaf must be provided".to_string(),
+            ));
+        }
+
+        let num_nodes = node_bytes.read_u8()? as usize;
+        let nodes = H::Digest::read_batch_from(node_bytes, num_nodes)?;
+
+        Ok(RangeMerkleProof {
+            leaves,
+            nodes,
+            depth,
+        })
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
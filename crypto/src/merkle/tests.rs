@@ -211,6 +211,33 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn find_invalid_index() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    let proof = tree.prove_batch(&[1, 3, 6]).unwrap();
+    assert_eq!(None, MerkleTree::find_invalid_index(tree.root(), &[1, 3, 6], &proof));
+
+    let mut invalid = tree.prove_batch(&[1, 3, 6]).unwrap();
+    invalid.leaves[1] = invalid.leaves[0];
+    assert!(MerkleTree::verify_batch(tree.root(), &[1, 3, 6], &invalid).is_err());
+    assert_eq!(Some(1), MerkleTree::find_invalid_index(tree.root(), &[1, 3, 6], &invalid));
+
+    // an invalid leaf at index 6 changes internal nodes on the paths for indexes 1 and 3 as well
+    let mut invalid = tree.prove_batch(&[1, 3, 6]).unwrap();
+    invalid.leaves[2] = invalid.leaves[0];
+    assert_eq!(Some(1), MerkleTree::find_invalid_index(tree.root(), &[1, 3, 6], &invalid));
+
+    // a proof for a single index is checked in isolation
+    let mut invalid = tree.prove_batch(&[6]).unwrap();
+    invalid.leaves[0] = tree.leaves()[5];
+    assert_eq!(Some(6), MerkleTree::find_invalid_index(tree.root(), &[6], &invalid));
+
+    // paths cannot be extracted for a different number of indexes
+    assert_eq!(Some(1), MerkleTree::find_invalid_index(tree.root(), &[1, 3], &proof));
+}
+
 #[test]
 fn verify_into_paths() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
@@ -235,6 +262,85 @@ fn verify_into_paths() {
     assert_eq!(proof6, result[2]);
 }
 
+#[test]
+fn prove_range() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    // a single leaf
+    let proof = tree.prove_range(1, 2).unwrap();
+    assert_eq!(leaves[1..2], proof.leaves[..]);
+    assert_eq!(tree.prove(1).unwrap()[1..], proof.nodes[..]);
+    assert_eq!(3, proof.depth);
+
+    // a range aligned with a subtree requires only one node per level above the subtree
+    let proof = tree.prove_range(4, 8).unwrap();
+    assert_eq!(leaves[4..8], proof.leaves[..]);
+    assert_eq!(
+        vec![hash_2x1(hash_2x1(leaves[0], leaves[1]), hash_2x1(leaves[2], leaves[3]))],
+        proof.nodes
+    );
+
+    // an unaligned range
+    let proof = tree.prove_range(1, 6).unwrap();
+    let expected_nodes = vec![leaves[0], hash_2x1(leaves[6], leaves[7])];
+    assert_eq!(expected_nodes, proof.nodes);
+
+    // all leaves
+    let proof = tree.prove_range(0, 8).unwrap();
+    assert_eq!(leaves, proof.leaves);
+    assert!(proof.nodes.is_empty());
+
+    // invalid ranges
+    assert!(matches!(tree.prove_range(3, 3), Err(MerkleTreeError::InvalidLeafRange(3, 3))));
+    assert!(matches!(tree.prove_range(4, 2), Err(MerkleTreeError::InvalidLeafRange(4, 2))));
+    assert!(matches!(
+        tree.prove_range(4, 9),
+        Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8))
+    ));
+}
+
+#[test]
+fn verify_range() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    let proof = tree.prove_range(1, 6).unwrap();
+    assert!(MerkleTree::verify_range(tree.root(), 1, &proof).is_ok());
+    assert!(MerkleTree::verify_range(tree.root(), 0, &proof).is_err());
+    assert!(MerkleTree::verify_range(tree.root(), 4, &proof).is_err());
+
+    // tampering with leaves or nodes invalidates the proof
+    let mut bad_proof = tree.prove_range(1, 6).unwrap();
+    bad_proof.leaves.swap(0, 1);
+    assert!(MerkleTree::verify_range(tree.root(), 1, &bad_proof).is_err());
+
+    let mut bad_proof = tree.prove_range(1, 6).unwrap();
+    bad_proof.nodes.pop();
+    assert!(MerkleTree::verify_range(tree.root(), 1, &bad_proof).is_err());
+
+    let mut bad_proof = proof;
+    bad_proof.nodes.push(bad_proof.nodes[0]);
+    assert!(MerkleTree::verify_range(tree.root(), 1, &bad_proof).is_err());
+
+    let proof = tree.prove_range(0, 8).unwrap();
+    assert!(MerkleTree::verify_range(tree.root(), 0, &proof).is_ok());
+}
+
+#[test]
+fn range_proof_serialization() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let proof = tree.prove_range(2, 7).unwrap();
+
+    let node_bytes = proof.serialize_nodes();
+    let mut reader = utils::SliceReader::new(&node_bytes);
+    let parsed =
+        RangeMerkleProof::<Blake3_256>::deserialize(&mut reader, proof.leaves.clone(), proof.depth)
+            .unwrap();
+    assert_eq!(proof, parsed);
+}
+
 proptest! {
     #[test]
     fn prove_n_verify(tree in random_blake3_merkle_tree(128),
@@ -256,6 +362,16 @@ proptest! {
         prop_assert!(MerkleTree::verify_batch(tree.root(), &indices[..], &proof).is_ok());
     }
 
+    #[test]
+    fn prove_range_n_verify(tree in random_blake3_merkle_tree(128),
+                      start in 0..128_usize, len in 1..128_usize
+    )  {
+        let end = core::cmp::min(start + len, 128);
+        let proof = tree.prove_range(start, end).unwrap();
+        prop_assert!(proof.nodes.len() <= 2 * tree.depth());
+        prop_assert!(MerkleTree::verify_range(tree.root(), start, &proof).is_ok());
+    }
+
     #[test]
     fn batch_proof_from_paths(tree in random_blake3_merkle_tree(128),
                       proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 10..20)
@@ -6,7 +6,7 @@
 use crate::{errors::RandomCoinError, Digest, ElementHasher, RandomCoin};
 use core::convert::TryInto;
 use math::{FieldElement, StarkField};
-use utils::collections::Vec;
+use utils::{collections::Vec, Serializable};
 
 // DEFAULT RANDOM COIN IMPLEMENTATION
 // ================================================================================================
@@ -153,6 +153,76 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         Err(RandomCoinError::FailedToDrawFieldElement(1000))
     }
 
+    /// Returns a vector of `num_elements` pseudo-random field elements.
+    ///
+    /// Unlike calling [draw()](RandomCoin::draw()) repeatedly, this splits every output of the
+    /// PRNG (i.e., the serialized digest, excluding any padding) into as many chunks of
+    /// `E::ELEMENT_BYTES` bytes as it can hold, and converts each chunk into a field element. Thus, for small fields, several elements are derived from a single
+    /// hash invocation. Chunks which are not valid field elements are discarded.
+    ///
+    /// # Errors
+    /// Returns an error if a valid field element could not be generated after 1000 consecutive
+    /// calls to the PRNG.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, DefaultRandomCoin, Hasher, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let seed = &[BaseElement::new(1), BaseElement::new(2), BaseElement::new(3), BaseElement::new(4)];
+    ///
+    /// let mut coin1 = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(seed);
+    /// let mut coin2 = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(seed);
+    ///
+    /// // drawing elements is deterministic
+    /// let values = coin1.draw_many::<BaseElement>(10).unwrap();
+    /// assert_eq!(10, values.len());
+    /// assert_eq!(values, coin2.draw_many::<BaseElement>(10).unwrap());
+    ///
+    /// // and subsequent draws produce different elements
+    /// assert_ne!(values, coin1.draw_many::<BaseElement>(10).unwrap());
+    /// ```
+    fn draw_many<E: FieldElement>(
+        &mut self,
+        num_elements: usize,
+    ) -> Result<Vec<E>, RandomCoinError> {
+        let mut result = Vec::with_capacity(num_elements);
+        let mut num_failed_tries = 0;
+        while result.len() < num_elements {
+            // get the next pseudo-random value and convert as many chunks of it as possible into
+            // field elements; if the digest is shorter than a single element, fall back to the
+            // same bytes as used by draw()
+            let value = self.next();
+            let digest_bytes = value.to_bytes();
+            let bytes = if digest_bytes.len() < E::ELEMENT_BYTES {
+                &value.as_bytes()[..E::ELEMENT_BYTES]
+            } else {
+                &digest_bytes[..]
+            };
+            let mut found_element = false;
+            for bytes in bytes.chunks_exact(E::ELEMENT_BYTES) {
+                if let Some(element) = E::from_random_bytes(bytes) {
+                    result.push(element);
+                    found_element = true;
+                    if result.len() == num_elements {
+                        break;
+                    }
+                }
+            }
+
+            // make sure we do not loop forever if the PRNG fails to produce valid elements
+            if found_element {
+                num_failed_tries = 0;
+            } else {
+                num_failed_tries += 1;
+                if num_failed_tries == 1000 {
+                    return Err(RandomCoinError::FailedToDrawFieldElement(1000));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Returns a vector of unique integers selected from the range [0, domain_size) after reseeding
     /// the PRNG with the specified `nonce` by setting the new seed to hash(`seed` || `nonce`).
     ///
@@ -10,6 +10,9 @@ use utils::collections::Vec;
 mod default;
 pub use default::DefaultRandomCoin;
 
+/// Domain separator prepended to data absorbed via [RandomCoin::reseed_with_bytes()].
+const RESEED_BYTES_DOMAIN: &[u8] = b"winterfell reseed bytes";
+
 // RANDOM COIN TRAIT
 // ================================================================================================
 
@@ -50,6 +53,11 @@ pub trait RandomCoin: Sync {
     /// Returns a vector of unique integers selected from the range [0, domain_size) after it reseeds
     /// the coin with a nonce.
     ///
+    /// Implementations must use rejection sampling (i.e., discard values which have already been
+    /// drawn) so that exactly `num_values` distinct integers are returned. This function is used
+    /// to draw query positions, and returning fewer distinct values would silently reduce the
+    /// security of proofs.
+    ///
     /// # Errors
     /// Returns an error if the specified number of unique integers could not be generated
     /// after 1000 calls to the PRNG.
@@ -64,4 +72,56 @@ pub trait RandomCoin: Sync {
         domain_size: usize,
         nonce: u64,
     ) -> Result<Vec<usize>, RandomCoinError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a vector of `num_elements` pseudo-random field elements.
+    ///
+    /// The default implementation calls [draw()](RandomCoin::draw()) `num_elements` times;
+    /// implementations are encouraged to override it with a more efficient procedure (e.g., one
+    /// which derives several elements from a single invocation of the underlying PRNG).
+    ///
+    /// # Errors
+    /// Returns an error if a valid field element could not be generated after 1000 calls to the
+    /// PRNG.
+    fn draw_many<E: FieldElement<BaseField = Self::BaseField>>(
+        &mut self,
+        num_elements: usize,
+    ) -> Result<Vec<E>, RandomCoinError> {
+        (0..num_elements).map(|_| self.draw()).collect()
+    }
+
+    /// Reseeds the coin with arbitrary bytes.
+    ///
+    /// The bytes are hashed together with a domain separator and their length, and the coin is
+    /// then reseeded with the resulting digest. This allows absorbing data which is not a digest
+    /// of [Self::Hasher] (e.g., external commitments or auxiliary proofs) into the transcript.
+    /// Because of the domain separator, reseeding with the bytes of a digest is not the same as
+    /// reseeding with the digest itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, DefaultRandomCoin, Digest, Hasher, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// type Blake3 = Blake3_256<BaseElement>;
+    /// let seed = &[BaseElement::new(1), BaseElement::new(2)];
+    /// let digest = Blake3::hash(&[2, 3, 4, 5]);
+    ///
+    /// let mut coin1 = DefaultRandomCoin::<Blake3>::new(seed);
+    /// let mut coin2 = DefaultRandomCoin::<Blake3>::new(seed);
+    /// coin1.reseed(digest);
+    /// coin2.reseed_with_bytes(&digest.as_bytes());
+    ///
+    /// let e1 = coin1.draw::<BaseElement>().unwrap();
+    /// let e2 = coin2.draw::<BaseElement>().unwrap();
+    /// assert_ne!(e1, e2);
+    /// ```
+    fn reseed_with_bytes(&mut self, data: &[u8]) {
+        let mut bytes = Vec::with_capacity(RESEED_BYTES_DOMAIN.len() + 8 + data.len());
+        bytes.extend_from_slice(RESEED_BYTES_DOMAIN);
+        bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(data);
+        self.reseed(Self::Hasher::hash(&bytes));
+    }
 }
@@ -0,0 +1,184 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Hasher, TranscriptError};
+use utils::collections::Vec;
+
+/// Domain separator used to initialize the state of a [SharedTranscript].
+const SHARED_TRANSCRIPT_DOMAIN: &[u8] = b"winterfell shared transcript";
+
+// SHARED TRANSCRIPT
+// ================================================================================================
+
+/// A transcript into which multiple participants (e.g., prover instances running on different
+/// machines) contribute commitments.
+///
+/// This enables protocols where several provers prove different parts of a single statement and
+/// need to derive their randomness from a common transcript. Each participant is assigned an
+/// index, and contributes exactly one commitment; once all commitments have been contributed,
+/// they are aggregated into a single digest via [SharedTranscript::finalize()]. Commitments are
+/// absorbed in the order of participant indexes regardless of the order in which they were
+/// contributed, and thus, all parties (including verifiers) derive the same aggregate digest from
+/// the same set of commitments.
+///
+/// The aggregate digest is computed as follows: the state is initialized to a hash of a domain
+/// separator and the number of participants, and then, for each commitment `c_i`, the state is
+/// updated as `state = H::merge(state, c_i)`.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{hashers::Blake3_256, Hasher, SharedTranscript};
+/// # use math::fields::f128::BaseElement;
+/// type H = Blake3_256<BaseElement>;
+///
+/// let c0 = H::hash(b"commitment of participant 0");
+/// let c1 = H::hash(b"commitment of participant 1");
+///
+/// // commitments can be contributed in any order
+/// let mut transcript = SharedTranscript::<H>::new(2);
+/// transcript.contribute(1, c1).unwrap();
+/// assert!(!transcript.is_complete());
+/// transcript.contribute(0, c0).unwrap();
+///
+/// let aggregate = transcript.finalize().unwrap();
+/// assert_eq!(aggregate, SharedTranscript::<H>::aggregate(&[c0, c1]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedTranscript<H: Hasher> {
+    contributions: Vec<Option<H::Digest>>,
+}
+
+impl<H: Hasher> SharedTranscript<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new transcript for the specified number of participants.
+    ///
+    /// # Panics
+    /// Panics if `num_participants` is zero.
+    pub fn new(num_participants: usize) -> Self {
+        assert!(num_participants > 0, "number of participants must be greater than zero");
+        Self {
+            contributions: vec![None; num_participants],
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of participants contributing to this transcript.
+    pub fn num_participants(&self) -> usize {
+        self.contributions.len()
+    }
+
+    /// Returns true if all participants have contributed their commitments.
+    pub fn is_complete(&self) -> bool {
+        self.contributions.iter().all(Option::is_some)
+    }
+
+    // CONTRIBUTION AND AGGREGATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the `commitment` contributed by the participant with the specified index.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The participant index is greater than or equal to the number of participants.
+    /// * The participant has already contributed a commitment.
+    pub fn contribute(
+        &mut self,
+        participant: usize,
+        commitment: H::Digest,
+    ) -> Result<(), TranscriptError> {
+        let num_participants = self.num_participants();
+        let slot = self
+            .contributions
+            .get_mut(participant)
+            .ok_or(TranscriptError::ParticipantOutOfBounds(num_participants, participant))?;
+        if slot.is_some() {
+            return Err(TranscriptError::DuplicateContribution(participant));
+        }
+        *slot = Some(commitment);
+        Ok(())
+    }
+
+    /// Returns a digest aggregating commitments of all participants in the order of participant
+    /// indexes.
+    ///
+    /// # Errors
+    /// Returns an error if any of the participants has not contributed a commitment yet; the
+    /// error specifies the index of the first such participant.
+    pub fn finalize(&self) -> Result<H::Digest, TranscriptError> {
+        let commitments = self
+            .contributions
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c.ok_or(TranscriptError::MissingContribution(i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::aggregate(&commitments))
+    }
+
+    /// Returns a digest aggregating the specified `commitments` of all participants; the
+    /// commitments must be ordered by participant index.
+    ///
+    /// This is the same digest as the one returned from [SharedTranscript::finalize()] after all
+    /// commitments have been contributed, and can be used by parties which receive all
+    /// commitments at once (e.g., verifiers).
+    pub fn aggregate(commitments: &[H::Digest]) -> H::Digest {
+        let mut seed = Vec::with_capacity(SHARED_TRANSCRIPT_DOMAIN.len() + 8);
+        seed.extend_from_slice(SHARED_TRANSCRIPT_DOMAIN);
+        seed.extend_from_slice(&(commitments.len() as u64).to_le_bytes());
+        commitments
+            .iter()
+            .fold(H::hash(&seed), |state, &commitment| H::merge(&[state, commitment]))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{SharedTranscript, TranscriptError};
+    use crate::{hash::Blake3_256, Hasher};
+    use math::fields::f128::BaseElement;
+
+    type H = Blake3_256<BaseElement>;
+
+    #[test]
+    fn shared_transcript_ordering() {
+        let commitments = [H::hash(b"a"), H::hash(b"b"), H::hash(b"c")];
+
+        // the aggregate does not depend on the order of contributions
+        let mut transcript = SharedTranscript::<H>::new(3);
+        for i in [2, 0, 1] {
+            assert_eq!(None, transcript.finalize().ok());
+            transcript.contribute(i, commitments[i]).unwrap();
+        }
+        assert!(transcript.is_complete());
+        let aggregate = transcript.finalize().unwrap();
+        assert_eq!(SharedTranscript::<H>::aggregate(&commitments), aggregate);
+
+        // but does depend on the order of participants
+        let swapped = [commitments[1], commitments[0], commitments[2]];
+        assert_ne!(SharedTranscript::<H>::aggregate(&swapped), aggregate);
+    }
+
+    #[test]
+    fn shared_transcript_errors() {
+        let mut transcript = SharedTranscript::<H>::new(2);
+        assert_eq!(Err(TranscriptError::MissingContribution(0)), transcript.finalize());
+
+        transcript.contribute(0, H::hash(b"a")).unwrap();
+        assert_eq!(Err(TranscriptError::MissingContribution(1)), transcript.finalize());
+        assert_eq!(
+            Err(TranscriptError::DuplicateContribution(0)),
+            transcript.contribute(0, H::hash(b"b"))
+        );
+        assert_eq!(
+            Err(TranscriptError::ParticipantOutOfBounds(2, 2)),
+            transcript.contribute(2, H::hash(b"b"))
+        );
+    }
+}
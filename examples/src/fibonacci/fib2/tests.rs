@@ -3,7 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Blake3_256};
+use super::{super::utils::build_proof_options, Blake3_256, FibAir, FibProver};
+use winterfell::{BatchingMethod, ConstraintLeafEncoding, ProofOptions, Prover};
 
 #[test]
 fn fib2_test_basic_proof_verification() {
@@ -22,3 +23,82 @@ fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Blake3_256>::new(16, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_basic_proof_verification_algebraic_batching() {
+    let options = build_proof_options(true).with_batching_method(BatchingMethod::Algebraic);
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(16, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_fri_folding_factors() {
+    use winterfell::FieldExtension;
+
+    for folding_factor in [2, 4, 8, 16] {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, folding_factor, 7);
+        let fib = Box::new(super::FibExample::<Blake3_256>::new(1024, options));
+        crate::tests::test_basic_proof_verification(fib);
+    }
+}
+
+#[test]
+fn fib2_test_constraint_leaf_encodings() {
+    for encoding in [
+        ConstraintLeafEncoding::Interleaved,
+        ConstraintLeafEncoding::Concatenated,
+        ConstraintLeafEncoding::InterleavedPadded,
+        ConstraintLeafEncoding::ConcatenatedPadded,
+    ] {
+        let options = build_proof_options(true).with_constraint_leaf_encoding(encoding);
+        let fib = Box::new(super::FibExample::<Blake3_256>::new(16, options));
+        crate::tests::test_basic_proof_verification(fib);
+    }
+}
+
+#[test]
+fn fib2_test_skip_first_fri_layer_commitment() {
+    use crate::Example;
+
+    let prover = FibProver::<Blake3_256>::new(build_proof_options(false));
+    let proof = prover.prove(prover.build_trace(1024)).unwrap();
+
+    // with the first FRI layer commitment skipped, the proof contains one fewer FRI layer, and
+    // trace and constraint commitments are opened at all positions of the queried cosets
+    let options = build_proof_options(false).with_skip_first_fri_layer_commitment(true);
+    let fib = super::FibExample::<Blake3_256>::new(1024, options.clone());
+    let skipped_proof = fib.prove();
+    assert_eq!(proof.fri_proof.num_layers(), skipped_proof.fri_proof.num_layers() + 1);
+    assert_eq!(112, options.num_query_positions());
+    assert!(fib.verify(skipped_proof).is_ok());
+
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(1024, options));
+    crate::tests::test_proof_mutations_rejected(fib);
+}
+
+#[test]
+fn fib2_test_verification_with_pub_inputs() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(16, build_proof_options(false)));
+    crate::tests::test_verification_with_pub_inputs(fib);
+}
+
+#[test]
+fn fib2_test_proof_mutations_rejected() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(16, build_proof_options(true)));
+    crate::tests::test_proof_mutations_rejected(fib);
+}
+
+#[test]
+fn fib2_test_air_consistency() {
+    let prover = FibProver::<Blake3_256>::new(build_proof_options(false));
+    let trace = prover.build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    winterfell::testing::check_air::<FibAir, _>(vec![trace], &[pub_inputs]);
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn fib2_test_protobuf_round_trip() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(16, build_proof_options(true)));
+    crate::tests::test_protobuf_round_trip(fib);
+}
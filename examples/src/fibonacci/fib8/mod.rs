@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
+use crate::utils::{elements_to_ints, ints_to_elements};
 use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
 use log::debug;
@@ -124,4 +125,14 @@ where
             self.result + BaseElement::ONE,
         )
     }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        elements_to_ints(&[self.result])
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let result = ints_to_elements(pub_inputs, 1)?[0];
+        winterfell::verify::<Fib8Air, H, DefaultRandomCoin<H>>(proof, result)
+            .map_err(|err| err.to_string())
+    }
 }
@@ -0,0 +1,133 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, ExtensionOf, FieldElement, ProofOptions, TABLE_SIZE, TRACE_WIDTH};
+use crate::utils::are_equal;
+use winterfell::{
+    Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// FIBONACCI WITH LOGUP AIR
+// ================================================================================================
+
+/// AIR for a Fibonacci-like sequence with offsets range-checked via a LogUp lookup.
+///
+/// The main trace segment consists of 4 columns: two consecutive terms of the sequence, the
+/// offset added at the current step, and the multiplicity $m$ of the lookup table entry at the
+/// current step. The lookup table $t$ is defined by a periodic column containing all values in
+/// $[0, 16)$.
+///
+/// The auxiliary trace segment consists of a single running sum column $s$ such that $s_0 = 0$
+/// and:
+/// $$
+/// s_{i+1} = s_i + \frac{1}{\alpha - v_i} - \frac{m_i}{\alpha - t_i}
+/// $$
+/// where $v_i$ is the offset at step $i$, and $\alpha$ is a random element drawn by the verifier
+/// after the main trace segment has been committed to. If the last value of the running sum is
+/// $0$, all offsets are in the table with high probability.
+pub struct FibLogUpAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibLogUpAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
+        let main_degrees =
+            vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![TransitionConstraintDegree::with_cycles(2, vec![TABLE_SIZE])];
+        assert_eq!(TRACE_WIDTH, trace_info.layout().main_trace_width());
+        FibLogUpAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                3,
+                2,
+                options,
+            ),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 4 field elements
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // constraints of the sequence (1 term per step):
+        // s_{0, i+1} = s_{1, i}
+        // s_{1, i+1} = s_{0, i} + s_{1, i} + v_i
+        result[0] = are_equal(next[0], current[1]);
+        result[1] = are_equal(next[1], current[0] + current[1] + current[2]);
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let main_current = main_frame.current();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+
+        let alpha = aux_rand_elements.segment_reader(0).read_element();
+        let value = alpha - E::from(main_current[2]);
+        let table_value = alpha - E::from(periodic_values[0]);
+        let multiplicity = E::from(main_current[3]);
+
+        // enforce the running sum update with denominators multiplied out:
+        // (s_{i+1} - s_i) * (α - v_i) * (α - t_i) = (α - t_i) - m_i * (α - v_i)
+        result[0] = are_equal(
+            (aux_next[0] - aux_current[0]) * value * table_value,
+            table_value - multiplicity * value,
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the sequence should start with two ones and terminate with the expected result
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, Self::BaseField::ONE),
+            Assertion::single(1, 0, Self::BaseField::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        // the running sum should start and end with zero
+        let last_step = self.trace_length() - 1;
+        vec![Assertion::single(0, 0, E::ZERO), Assertion::single(0, last_step, E::ZERO)]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![(0..TABLE_SIZE as u128).map(BaseElement::new).collect()]
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::{elements_to_ints, ints_to_elements};
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+    DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover, StarkProof, Trace,
+    VerifierError,
+};
+
+mod air;
+use air::FibLogUpAir;
+
+mod prover;
+use prover::FibLogUpProver;
+
+mod trace;
+use trace::FibLogUpTrace;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns in the main segment of the trace: two sequence terms, an offset value, and
+/// a multiplicity of the lookup table entry at the current row.
+const TRACE_WIDTH: usize = 4;
+
+/// Number of entries in the lookup table; the table contains all values in `[0, TABLE_SIZE)`.
+const TABLE_SIZE: usize = 16;
+
+// FIBONACCI WITH LOGUP EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    sequence_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(FibLogUpExample::<Blake3_192>::new(sequence_length, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(FibLogUpExample::<Blake3_256>::new(sequence_length, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(FibLogUpExample::<Sha3_256>::new(sequence_length, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+/// This example proves computation of a Fibonacci-like sequence in which a small offset is added
+/// to every term: $x_{i+2} = x_i + x_{i+1} + v_i$, where each offset $v_i$ is in $[0, 16)$.
+///
+/// Offsets are private to the prover. That they are in the expected range is enforced via a
+/// LogUp lookup into a table of all values in $[0, 16)$, which is built in an auxiliary trace
+/// segment. Thus, this example demonstrates how to build and constrain an auxiliary trace
+/// segment end to end.
+pub struct FibLogUpExample<H: ElementHasher> {
+    options: ProofOptions,
+    sequence_length: usize,
+    result: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibLogUpExample<H> {
+    pub fn new(sequence_length: usize, options: ProofOptions) -> Self {
+        assert!(sequence_length.is_power_of_two(), "sequence length must be a power of 2");
+        assert!(
+            sequence_length >= 2 * TABLE_SIZE,
+            "sequence length must be at least {}",
+            2 * TABLE_SIZE
+        );
+
+        // compute the sequence
+        let now = Instant::now();
+        let result = compute_result(sequence_length);
+        debug!(
+            "Computed Fibonacci sequence with offsets up to {}th term in {} ms",
+            sequence_length,
+            now.elapsed().as_millis()
+        );
+
+        FibLogUpExample {
+            options,
+            sequence_length,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for FibLogUpExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for computing Fibonacci sequence with offsets up to {}th term\n\
+            ---------------------",
+            self.sequence_length
+        );
+
+        // create a prover
+        let prover = FibLogUpProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.sequence_length);
+
+        let trace_width = trace.main_trace_width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<FibLogUpAir, H, DefaultRandomCoin<H>>(proof, self.result)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<FibLogUpAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.result + BaseElement::ONE,
+        )
+    }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        elements_to_ints(&[self.result])
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let result = ints_to_elements(pub_inputs, 1)?[0];
+        winterfell::verify::<FibLogUpAir, H, DefaultRandomCoin<H>>(proof, result)
+            .map_err(|err| err.to_string())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the offset added to the sequence at the specified step.
+///
+/// Offsets are not uniformly distributed so that entries of the lookup table are used with
+/// different multiplicities.
+fn get_offset(step: usize) -> usize {
+    (step % TABLE_SIZE).pow(2) % TABLE_SIZE
+}
+
+/// Returns the last term of the sequence computed by an execution trace with the specified
+/// number of rows. The first two terms of the sequence are ONE, and each row of the trace
+/// advances the sequence by one term.
+fn compute_result(num_rows: usize) -> BaseElement {
+    let mut t0 = BaseElement::ONE;
+    let mut t1 = BaseElement::ONE;
+
+    for step in 0..(num_rows - 1) {
+        let t2 = t0 + t1 + BaseElement::from(get_offset(step) as u64);
+        t0 = t1;
+        t1 = t2;
+    }
+
+    t1
+}
@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_offset, BaseElement, DefaultConstraintEvaluator, DefaultRandomCoin, DefaultTraceLde,
+    ElementHasher, FibLogUpAir, FibLogUpTrace, FieldElement, PhantomData, ProofOptions, Prover,
+    Trace, TABLE_SIZE,
+};
+
+// FIBONACCI WITH LOGUP PROVER
+// ================================================================================================
+
+pub struct FibLogUpProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibLogUpProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds the main segment of an execution trace for computing a Fibonacci sequence with
+    /// offsets of the specified length such that each row advances the sequence by 1 term.
+    ///
+    /// Multiplicities of all lookup table entries are recorded in the first [TABLE_SIZE] rows of
+    /// the trace, and are zero in all other rows.
+    pub fn build_trace(&self, sequence_length: usize) -> FibLogUpTrace {
+        assert!(sequence_length.is_power_of_two(), "sequence length must be a power of 2");
+        assert!(
+            sequence_length >= 2 * TABLE_SIZE,
+            "sequence length must be at least {}",
+            2 * TABLE_SIZE
+        );
+
+        let mut t0 = vec![BaseElement::ONE; sequence_length];
+        let mut t1 = vec![BaseElement::ONE; sequence_length];
+        let mut offsets = vec![BaseElement::ZERO; sequence_length];
+        let mut multiplicities = vec![BaseElement::ZERO; sequence_length];
+
+        // offsets in the last row are not used by the computation, and thus, are not looked up
+        for step in 0..sequence_length - 1 {
+            let offset = get_offset(step);
+            offsets[step] = BaseElement::from(offset as u64);
+            multiplicities[offset] += BaseElement::ONE;
+
+            t0[step + 1] = t1[step];
+            t1[step + 1] = t0[step] + t1[step] + offsets[step];
+        }
+
+        FibLogUpTrace::new(vec![t0, t1, offsets, multiplicities])
+    }
+}
+
+impl<H: ElementHasher> Prover for FibLogUpProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = FibLogUpAir;
+    type Trace = FibLogUpTrace;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        let last_step = trace.length() - 1;
+        trace.get(1, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: winterfell::AuxTraceRandElements<E>,
+        composition_coefficients: winterfell::ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
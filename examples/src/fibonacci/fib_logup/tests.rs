@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    super::utils::build_proof_options, BaseElement, Blake3_256, DefaultConstraintEvaluator,
+    DefaultRandomCoin, DefaultTraceLde, FibLogUpAir, FibLogUpProver, FibLogUpTrace, FieldElement,
+    ProofOptions, TRACE_WIDTH,
+};
+use winterfell::{
+    AuxTraceError, AuxTraceRandElements, ColMatrix, ConstraintCompositionCoefficients,
+    EvaluationFrame, Prover, ProverError, Trace, TraceLayout, TraceMetadata,
+};
+
+#[test]
+fn fib_logup_test_basic_proof_verification() {
+    let fib = Box::new(super::FibLogUpExample::<Blake3_256>::new(64, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_logup_test_basic_proof_verification_extension() {
+    let fib = Box::new(super::FibLogUpExample::<Blake3_256>::new(64, build_proof_options(true)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_logup_test_basic_proof_verification_fail() {
+    let fib = Box::new(super::FibLogUpExample::<Blake3_256>::new(64, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib_logup_test_invalid_multiplicities() {
+    let prover = FibLogUpProver::<Blake3_256>::new(build_proof_options(false));
+    let trace = prover.build_trace(64);
+
+    // record an extra lookup of the first table entry which is not matched by any offset
+    let mut columns: Vec<Vec<_>> = (0..TRACE_WIDTH)
+        .map(|col| (0..trace.length()).map(|step| trace.get(col, step)).collect())
+        .collect();
+    columns[3][0] += FieldElement::ONE;
+
+    let result = prover.prove(FibLogUpTrace::new(columns));
+    assert!(matches!(
+        result,
+        Err(ProverError::AuxTraceBuildFailed(0, AuxTraceError::InvalidWitness(_)))
+    ));
+}
+
+#[test]
+fn fib_logup_test_aux_segment_width_mismatch() {
+    let options = build_proof_options(false);
+    let trace = FibLogUpProver::<Blake3_256>::new(options.clone()).build_trace(64);
+
+    // the auxiliary segment built by the trace has one more column than declared by its layout
+    let result = WideAuxSegmentProver(options).prove(WideAuxSegmentTrace(trace));
+    assert_eq!(
+        Err(ProverError::TraceWidthMismatch {
+            segment: 1,
+            expected: 1,
+            actual: 2
+        }),
+        result.map(|_| ())
+    );
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Wraps a valid trace but duplicates the column of its auxiliary segment.
+struct WideAuxSegmentTrace(FibLogUpTrace);
+
+impl Trace for WideAuxSegmentTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        self.0.layout()
+    }
+
+    fn length(&self) -> usize {
+        self.0.length()
+    }
+
+    fn meta(&self) -> &TraceMetadata {
+        self.0.meta()
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<BaseElement>) {
+        self.0.read_main_frame(row_idx, frame)
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        self.0.main_segment()
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Result<ColMatrix<E>, AuxTraceError>
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        let column = self.0.build_aux_segment(aux_segments, rand_elements)?.get_column(0).to_vec();
+        Ok(ColMatrix::new(vec![column.clone(), column]))
+    }
+}
+
+struct WideAuxSegmentProver(ProofOptions);
+
+impl Prover for WideAuxSegmentProver {
+    type BaseField = BaseElement;
+    type Air = FibLogUpAir;
+    type Trace = WideAuxSegmentTrace;
+    type HashFn = Blake3_256;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.0.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.0
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, TABLE_SIZE, TRACE_WIDTH};
+use winterfell::{
+    math::batch_inversion, AuxRandElementsReader, AuxTraceError, ColMatrix, EvaluationFrame, Trace,
+    TraceLayout, TraceMetadata,
+};
+
+// FIBONACCI WITH LOGUP TRACE
+// ================================================================================================
+
+/// Execution trace for the Fibonacci with LogUp example.
+///
+/// The main segment of the trace is built by the prover upfront. The auxiliary segment, which
+/// consists of the LogUp running sum column, is built once the verifier has sent the random
+/// element $\alpha$ (see [FibLogUpAir](super::FibLogUpAir) for the definition of the column).
+pub struct FibLogUpTrace {
+    layout: TraceLayout,
+    main_segment: ColMatrix<BaseElement>,
+    meta: TraceMetadata,
+}
+
+impl FibLogUpTrace {
+    /// Returns a new trace instantiated from the provided columns of the main trace segment.
+    pub fn new(columns: Vec<Vec<BaseElement>>) -> Self {
+        assert_eq!(TRACE_WIDTH, columns.len());
+        Self {
+            layout: TraceLayout::new(TRACE_WIDTH, [1], [1]),
+            main_segment: ColMatrix::new(columns),
+            meta: TraceMetadata::new(),
+        }
+    }
+
+    /// Returns the value of the main trace segment at the specified column and step.
+    pub fn get(&self, column: usize, step: usize) -> BaseElement {
+        self.main_segment.get(column, step)
+    }
+}
+
+impl Trace for FibLogUpTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    fn meta(&self) -> &TraceMetadata {
+        &self.meta
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.main_segment.read_row_into(row_idx, frame.current_mut());
+        self.main_segment.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Result<ColMatrix<E>, AuxTraceError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // there is only one auxiliary segment
+        if !aux_segments.is_empty() {
+            return Err(AuxTraceError::UndefinedSegment(aux_segments.len()));
+        }
+
+        let alpha = AuxRandElementsReader::new(rand_elements).read_element();
+        let num_steps = self.length() - 1;
+
+        // compute inverses of (α - v_i) and (α - t_i) for all steps at once; values in the last
+        // row are not included into the running sum
+        let mut denominators = Vec::with_capacity(2 * num_steps);
+        for step in 0..num_steps {
+            let table_value = BaseElement::from((step % TABLE_SIZE) as u64);
+            denominators.push(alpha - E::from(self.get(2, step)));
+            denominators.push(alpha - E::from(table_value));
+        }
+        let inv_denominators = batch_inversion(&denominators);
+
+        // build the running sum column
+        let mut running_sum = Vec::with_capacity(self.length());
+        running_sum.push(E::ZERO);
+        for (step, inv) in inv_denominators.chunks(2).enumerate() {
+            let multiplicity = E::from(self.get(3, step));
+            running_sum.push(running_sum[step] + inv[0] - multiplicity * inv[1]);
+        }
+
+        // the running sum closes only if multiplicities match the looked up values
+        if running_sum[num_steps] != E::ZERO {
+            return Err(AuxTraceError::InvalidWitness(
+                "multiplicities are inconsistent with looked up values".to_string(),
+            ));
+        }
+
+        Ok(ColMatrix::new(vec![running_sum]))
+    }
+}
@@ -22,3 +22,9 @@ fn fib_small_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Rp64_256>::new(128, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib_small_test_proof_mutations_rejected() {
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(128, build_proof_options(true)));
+    crate::tests::test_proof_mutations_rejected(fib);
+}
@@ -9,6 +9,7 @@ use super::{
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use core_utils::flatten_slice_elements;
 use winterfell::{
+    crypto::ElementHasher,
     math::{fields::f128::BaseElement, FieldElement, ToElements},
     Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
     TransitionConstraintDegree,
@@ -95,6 +96,14 @@ impl Air for LamportAggregateAir {
         &self.context
     }
 
+    fn get_pub_inputs_commitment<H: ElementHasher<BaseField = Self::BaseField>>(
+        pub_inputs: &Self::PublicInputs,
+    ) -> Option<H::Digest> {
+        // public keys and messages grow linearly with the number of signatures; so, instead of
+        // seeding the public coin with all of them, we seed it with their hash
+        Some(H::hash_elements(&pub_inputs.to_elements()))
+    }
+
     fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
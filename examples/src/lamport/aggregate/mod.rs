@@ -6,13 +6,14 @@
 use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
+use crate::utils::{elements_to_ints, ints_to_elements};
 use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::{DefaultRandomCoin, ElementHasher},
-    math::{fields::f128::BaseElement, get_power_series, FieldElement, StarkField},
+    math::{fields::f128::BaseElement, get_power_series, FieldElement, StarkField, ToElements},
     DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover, StarkProof, Trace,
     TraceTable, VerifierError,
 };
@@ -23,6 +24,9 @@ use air::{LamportAggregateAir, PublicInputs};
 mod prover;
 use prover::LamportAggregateProver;
 
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -158,4 +162,25 @@ where
         };
         winterfell::verify::<LamportAggregateAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
     }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        let pub_inputs = PublicInputs {
+            pub_keys: self.pub_keys.clone(),
+            messages: self.messages.clone(),
+        };
+        elements_to_ints(&pub_inputs.to_elements())
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        // public inputs contain a public key and a message for each of the signatures
+        let num_signatures = self.pub_keys.len();
+        let inputs = ints_to_elements(pub_inputs, 4 * num_signatures)?;
+        let (pub_keys, messages) = inputs.split_at(2 * num_signatures);
+        let pub_inputs = PublicInputs {
+            pub_keys: pub_keys.chunks(2).map(|key| [key[0], key[1]]).collect(),
+            messages: messages.chunks(2).map(|msg| [msg[0], msg[1]]).collect(),
+        };
+        winterfell::verify::<LamportAggregateAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+            .map_err(|err| err.to_string())
+    }
 }
@@ -7,13 +7,14 @@ use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH as HASH_CYCLE_LENGTH,
     NUM_HASH_ROUNDS,
 };
+use crate::utils::{elements_to_ints, ints_to_elements};
 use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::{DefaultRandomCoin, ElementHasher},
-    math::{fields::f128::BaseElement, get_power_series, FieldElement, StarkField},
+    math::{fields::f128::BaseElement, get_power_series, FieldElement, StarkField, ToElements},
     DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover, StarkProof, Trace,
     TraceTable, VerifierError,
 };
@@ -164,6 +165,28 @@ where
         };
         winterfell::verify::<LamportThresholdAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
     }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        let pub_inputs = PublicInputs {
+            pub_key_root: self.pub_key.root().to_elements(),
+            num_pub_keys: self.pub_key.num_keys(),
+            num_signatures: self.signatures.len(),
+            message: self.message,
+        };
+        elements_to_ints(&pub_inputs.to_elements())
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let inputs: Vec<BaseElement> = ints_to_elements(pub_inputs, 6)?;
+        let pub_inputs = PublicInputs {
+            pub_key_root: [inputs[0], inputs[1]],
+            num_pub_keys: inputs[2].as_int() as usize,
+            num_signatures: inputs[3].as_int() as usize,
+            message: [inputs[4], inputs[5]],
+        };
+        winterfell::verify::<LamportThresholdAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+            .map_err(|err| err.to_string())
+    }
 }
 
 // HELPER FUNCTIONS
@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use structopt::StructOpt;
 use winterfell::{
     crypto::hashers::{GriffinJive64_256, Rp64_256, RpJive64_256},
@@ -15,9 +17,12 @@ pub mod fibonacci;
 pub mod lamport;
 #[cfg(feature = "std")]
 pub mod merkle;
+pub mod ram;
 pub mod rescue;
 #[cfg(feature = "std")]
 pub mod rescue_raps;
+#[cfg(feature = "std")]
+pub mod schnorr;
 pub mod utils;
 pub mod vdf;
 
@@ -35,16 +40,26 @@ pub trait Example {
     fn prove(&self) -> StarkProof;
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError>;
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError>;
+
+    /// Returns public inputs of this example encoded as a list of integers.
+    fn get_pub_inputs(&self) -> Vec<u128>;
+
+    /// Verifies the proof against the provided public inputs rather than the public inputs of
+    /// this example; the inputs must be encoded as returned by [Example::get_pub_inputs()].
+    ///
+    /// # Errors
+    /// Returns an error if the public inputs are malformed, or if the proof is not valid.
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String>;
 }
 
 // EXAMPLE OPTIONS
 // ================================================================================================
 
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Debug, Clone)]
 #[structopt(name = "winterfell", about = "Winterfell examples")]
 pub struct ExampleOptions {
     #[structopt(subcommand)]
-    pub example: ExampleType,
+    pub command: Command,
 
     /// Hash function used in the protocol
     #[structopt(short = "h", long = "hash_fn", default_value = "blake3_256")]
@@ -72,6 +87,17 @@ pub struct ExampleOptions {
 }
 
 impl ExampleOptions {
+    /// Returns these options with the hash function set to the specified one.
+    pub fn with_hash_fn(mut self, hash_fn: &str) -> Self {
+        self.hash_fn = hash_fn.to_string();
+        self
+    }
+
+    /// Returns the name of the hash function specified by these options.
+    pub fn hash_fn(&self) -> &str {
+        &self.hash_fn
+    }
+
     pub fn to_proof_options(&self, q: usize, b: usize) -> (ProofOptions, HashFunction) {
         let num_queries = self.num_queries.unwrap_or(q);
         let blowup_factor = self.blowup_factor.unwrap_or(b);
@@ -121,7 +147,44 @@ impl ExampleOptions {
     }
 }
 
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Debug, Clone)]
+pub enum Command {
+    /// Generate a proof for an example and write the proof and its public inputs into files
+    Prove {
+        /// File into which the proof is written
+        #[structopt(long = "out", parse(from_os_str), default_value = "proof.bin")]
+        out: PathBuf,
+
+        /// File into which public inputs of the proof are written
+        #[structopt(long = "pub-inputs", parse(from_os_str), default_value = "pub_inputs.json")]
+        pub_inputs: PathBuf,
+
+        #[structopt(subcommand)]
+        example: ExampleType,
+    },
+    /// Verify a proof read from a file against public inputs read from another file
+    Verify {
+        /// File from which the proof is read
+        #[structopt(long = "proof", parse(from_os_str))]
+        proof: PathBuf,
+
+        /// File from which public inputs of the proof are read
+        #[structopt(long = "pub-inputs", parse(from_os_str))]
+        pub_inputs: PathBuf,
+    },
+    /// Print out parameters of a proof read from a file
+    Inspect {
+        /// File from which the proof is read
+        #[structopt(parse(from_os_str))]
+        proof: PathBuf,
+    },
+    /// Generate and verify a proof for an example in the same process
+    #[structopt(flatten)]
+    Run(ExampleType),
+}
+
+#[derive(StructOpt, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
 //#[structopt(about = "available examples")]
 pub enum ExampleType {
     /// Compute a Fibonacci sequence using trace table with 2 registers
@@ -148,6 +211,12 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1048576")]
         sequence_length: usize,
     },
+    /// Compute a Fibonacci sequence with offsets range-checked via a LogUp lookup
+    FibLogup {
+        /// Length of Fibonacci sequence; must be a power of two
+        #[structopt(short = "n", default_value = "65536")]
+        sequence_length: usize,
+    },
     /// Compute a Fibonacci sequence using trace table with 2 registers in `f64` field.
     FibSmall {
         /// Length of Fibonacci sequence; must be a power of two
@@ -179,6 +248,12 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1024")]
         chain_length: usize,
     },
+    /// Prove consistency of reads and writes against a random access memory
+    Ram {
+        /// Number of memory operations; must be one less than a power of two
+        #[structopt(short = "n", default_value = "65535")]
+        num_ops: usize,
+    },
     /// Compute a root of a Merkle path using Rescue hash function
     #[cfg(feature = "std")]
     Merkle {
@@ -200,6 +275,53 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "3")]
         num_signers: usize,
     },
+    /// Verify a Schnorr signature defined over a non-native prime field
+    #[cfg(feature = "std")]
+    Schnorr,
+}
+
+// PROOF STATEMENT
+// ================================================================================================
+
+/// A statement proven by one of the examples.
+///
+/// The statement consists of the example (together with its parameters) which generated the
+/// proof, the hash function used in the protocol, and public inputs of the proof. It is written
+/// alongside the proof so that the proof could be verified in a different process.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProofStatement {
+    /// Example which generated the proof.
+    pub example: ExampleType,
+    /// Name of the hash function used in the protocol.
+    pub hash_fn: String,
+    /// Public inputs of the proof encoded as decimal strings (see [Example::get_pub_inputs()]).
+    pub pub_inputs: Vec<String>,
+}
+
+impl ProofStatement {
+    /// Returns a new statement for the specified example, hash function, and public inputs.
+    pub fn new(example: ExampleType, hash_fn: &str, pub_inputs: &[u128]) -> Self {
+        Self {
+            example,
+            hash_fn: hash_fn.to_string(),
+            pub_inputs: pub_inputs.iter().map(|value| value.to_string()).collect(),
+        }
+    }
+
+    /// Returns public inputs of this statement decoded into integers.
+    ///
+    /// # Errors
+    /// Returns an error if any of the public inputs is not a valid decimal integer.
+    pub fn get_pub_inputs(&self) -> Result<Vec<u128>, String> {
+        self.pub_inputs
+            .iter()
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("'{value}' is not a valid public input value"))
+            })
+            .collect()
+    }
 }
 
 /// Defines a set of hash functions available for the provided examples. Some examples may not
@@ -4,14 +4,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use log::debug;
-use std::io::Write;
 use std::time::Instant;
+use std::{fs, io::Write};
 use structopt::StructOpt;
 use winterfell::StarkProof;
 
-use examples::{fibonacci, rescue, vdf, ExampleOptions, ExampleType};
+use examples::{
+    fibonacci, ram, rescue, vdf, Command, Example, ExampleOptions, ExampleType, ProofStatement,
+};
 #[cfg(feature = "std")]
-use examples::{lamport, merkle, rescue_raps};
+use examples::{lamport, merkle, rescue_raps, schnorr};
 
 // EXAMPLE RUNNER
 // ================================================================================================
@@ -28,56 +30,166 @@ fn main() {
 
     debug!("============================================================");
 
-    // instantiate and prepare the example
-    let example = match options.example {
+    match options.command.clone() {
+        Command::Run(example_type) => {
+            let example = build_example(&options, example_type);
+            let proof = generate_proof(&options, example.as_ref());
+
+            // verify the proof
+            debug!("---------------------");
+            let parsed_proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+            assert_eq!(proof, parsed_proof);
+            let now = Instant::now();
+            match example.verify(proof) {
+                Ok(_) => {
+                    debug!("Proof verified in {:.1} ms", now.elapsed().as_micros() as f64 / 1000f64)
+                }
+                Err(msg) => debug!("Failed to verify proof: {}", msg),
+            }
+        }
+        Command::Prove {
+            out,
+            pub_inputs,
+            example: example_type,
+        } => {
+            let example = build_example(&options, example_type.clone());
+            let proof = generate_proof(&options, example.as_ref());
+
+            // write the proof and its public inputs into files
+            debug!("---------------------");
+            fs::write(&out, proof.to_bytes()).expect("failed to write the proof");
+            debug!("Proof written to {}", out.display());
+
+            let statement =
+                ProofStatement::new(example_type, options.hash_fn(), &example.get_pub_inputs());
+            let statement =
+                serde_json::to_string_pretty(&statement).expect("failed to encode public inputs");
+            fs::write(&pub_inputs, statement).expect("failed to write public inputs");
+            debug!("Public inputs written to {}", pub_inputs.display());
+        }
+        Command::Verify { proof, pub_inputs } => {
+            // read the proof and its public inputs from files
+            let proof_bytes = fs::read(&proof).expect("failed to read the proof");
+            let proof = StarkProof::from_bytes(&proof_bytes).expect("failed to parse the proof");
+            let statement = fs::read_to_string(&pub_inputs).expect("failed to read public inputs");
+            let statement: ProofStatement =
+                serde_json::from_str(&statement).expect("failed to parse public inputs");
+
+            // the proof is verified using the hash function specified in the statement
+            let options = options.with_hash_fn(&statement.hash_fn);
+            let example = build_example(&options, statement.example.clone());
+            print_proof_summary(&options, &proof, &proof_bytes);
+
+            debug!("---------------------");
+            let now = Instant::now();
+            let result = statement
+                .get_pub_inputs()
+                .and_then(|pub_inputs| example.verify_with_pub_inputs(proof, &pub_inputs));
+            match result {
+                Ok(_) => {
+                    debug!("Proof verified in {:.1} ms", now.elapsed().as_micros() as f64 / 1000f64)
+                }
+                Err(msg) => debug!("Failed to verify proof: {}", msg),
+            }
+        }
+        Command::Inspect { proof } => {
+            let proof_bytes = fs::read(&proof).expect("failed to read the proof");
+            let proof = StarkProof::from_bytes(&proof_bytes).expect("failed to parse the proof");
+
+            let layout = proof.trace_layout();
+            let proof_options = proof.options();
+            let fri_options = proof_options.to_fri_options();
+            debug!("Trace length: 2^{}", proof.trace_length().ilog2());
+            debug!(
+                "Trace width: {} main columns, {} auxiliary columns in {} segments",
+                layout.main_trace_width(),
+                layout.aux_trace_width(),
+                layout.num_aux_segments()
+            );
+            debug!("Field modulus: {} bits", proof.context.num_modulus_bits());
+            debug!("Field extension: {:?}", proof_options.field_extension());
+            debug!("LDE domain size: 2^{}", proof.lde_domain_size().ilog2());
+            debug!("Number of queries: {}", proof_options.num_queries());
+            debug!("Blowup factor: {}", proof_options.blowup_factor());
+            debug!("Grinding factor: {} bits", proof_options.grinding_factor());
+            debug!(
+                "FRI folding factor: {}, max remainder degree: {}",
+                fri_options.folding_factor(),
+                fri_options.remainder_max_degree()
+            );
+            print_proof_summary(&options, &proof, &proof_bytes);
+        }
+    }
+    debug!("============================================================");
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Instantiates the specified example.
+fn build_example(options: &ExampleOptions, example: ExampleType) -> Box<dyn Example> {
+    match example {
         ExampleType::Fib { sequence_length } => {
-            fibonacci::fib2::get_example(&options, sequence_length)
+            fibonacci::fib2::get_example(options, sequence_length)
         }
         ExampleType::Fib8 { sequence_length } => {
-            fibonacci::fib8::get_example(&options, sequence_length)
+            fibonacci::fib8::get_example(options, sequence_length)
         }
         ExampleType::Mulfib { sequence_length } => {
-            fibonacci::mulfib2::get_example(&options, sequence_length)
+            fibonacci::mulfib2::get_example(options, sequence_length)
         }
         ExampleType::Mulfib8 { sequence_length } => {
-            fibonacci::mulfib8::get_example(&options, sequence_length)
+            fibonacci::mulfib8::get_example(options, sequence_length)
+        }
+        ExampleType::FibLogup { sequence_length } => {
+            fibonacci::fib_logup::get_example(options, sequence_length)
         }
         ExampleType::FibSmall { sequence_length } => {
-            fibonacci::fib_small::get_example(&options, sequence_length)
+            fibonacci::fib_small::get_example(options, sequence_length)
         }
-        ExampleType::Vdf { num_steps } => vdf::regular::get_example(&options, num_steps),
-        ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(&options, num_steps),
-        ExampleType::Rescue { chain_length } => rescue::get_example(&options, chain_length),
+        ExampleType::Vdf { num_steps } => vdf::regular::get_example(options, num_steps),
+        ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(options, num_steps),
+        ExampleType::Rescue { chain_length } => rescue::get_example(options, chain_length),
         #[cfg(feature = "std")]
-        ExampleType::RescueRaps { chain_length } => {
-            rescue_raps::get_example(&options, chain_length)
-        }
+        ExampleType::RescueRaps { chain_length } => rescue_raps::get_example(options, chain_length),
+        ExampleType::Ram { num_ops } => ram::get_example(options, num_ops),
         #[cfg(feature = "std")]
-        ExampleType::Merkle { tree_depth } => merkle::get_example(&options, tree_depth),
+        ExampleType::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
         #[cfg(feature = "std")]
         ExampleType::LamportA { num_signatures } => {
-            lamport::aggregate::get_example(&options, num_signatures)
+            lamport::aggregate::get_example(options, num_signatures)
         }
         #[cfg(feature = "std")]
         ExampleType::LamportT { num_signers } => {
-            lamport::threshold::get_example(&options, num_signers)
+            lamport::threshold::get_example(options, num_signers)
         }
+        #[cfg(feature = "std")]
+        ExampleType::Schnorr => schnorr::get_example(options),
     }
-    .expect("The example failed to initialize.");
+    .expect("The example failed to initialize.")
+}
 
-    // generate proof
+/// Generates a proof for the provided example and prints out basic info about the proof.
+fn generate_proof(options: &ExampleOptions, example: &dyn Example) -> StarkProof {
     let now = Instant::now();
-    let example = example.as_ref();
     let proof = example.prove();
     debug!("---------------------\nProof generated in {} ms", now.elapsed().as_millis());
 
-    let proof_bytes = proof.to_bytes();
+    print_proof_summary(options, &proof, &proof.to_bytes());
+    proof
+}
+
+/// Prints out size, security level, and hash of the provided proof.
+///
+/// Security level is computed under the assumption that the proof was generated using the hash
+/// function specified by the options.
+fn print_proof_summary(options: &ExampleOptions, proof: &StarkProof, proof_bytes: &[u8]) {
     debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
-    let conjectured_security_level = options.get_proof_security_level(&proof, true);
+    let conjectured_security_level = options.get_proof_security_level(proof, true);
 
     #[cfg(feature = "std")]
     {
-        let proven_security_level = options.get_proof_security_level(&proof, false);
+        let proven_security_level = options.get_proof_security_level(proof, false);
         debug!(
             "Proof security: {} bits ({} proven)",
             conjectured_security_level, proven_security_level,
@@ -88,16 +200,5 @@ fn main() {
     debug!("Proof security: {} bits", conjectured_security_level);
 
     #[cfg(feature = "std")]
-    debug!("Proof hash: {}", hex::encode(blake3::hash(&proof_bytes).as_bytes()));
-
-    // verify the proof
-    debug!("---------------------");
-    let parsed_proof = StarkProof::from_bytes(&proof_bytes).unwrap();
-    assert_eq!(proof, parsed_proof);
-    let now = Instant::now();
-    match example.verify(proof) {
-        Ok(_) => debug!("Proof verified in {:.1} ms", now.elapsed().as_micros() as f64 / 1000f64),
-        Err(msg) => debug!("Failed to verify proof: {}", msg),
-    }
-    debug!("============================================================");
+    debug!("Proof hash: {}", hex::encode(blake3::hash(proof_bytes).as_bytes()));
 }
@@ -7,6 +7,7 @@ use crate::utils::rescue::{
     self, CYCLE_LENGTH as HASH_CYCLE_LEN, NUM_ROUNDS as NUM_HASH_ROUNDS,
     STATE_WIDTH as HASH_STATE_WIDTH,
 };
+use crate::utils::{elements_to_ints, ints_to_elements};
 use crate::{
     utils::rescue::{Hash, Rescue128},
     Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256,
@@ -147,6 +148,19 @@ where
         };
         winterfell::verify::<MerkleAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
     }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        elements_to_ints(&self.tree_root.to_elements())
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let inputs = ints_to_elements(pub_inputs, 2)?;
+        let pub_inputs = PublicInputs {
+            tree_root: [inputs[0], inputs[1]],
+        };
+        winterfell::verify::<MerkleAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+            .map_err(|err| err.to_string())
+    }
 }
 
 // HELPER FUNCTIONS
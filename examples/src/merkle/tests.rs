@@ -24,6 +24,12 @@ fn merkle_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(merkle);
 }
 
+#[test]
+fn merkle_test_verification_with_pub_inputs() {
+    let merkle = Box::new(super::MerkleExample::<Blake3_256>::new(7, build_options(false)));
+    crate::tests::test_verification_with_pub_inputs(merkle);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -0,0 +1,167 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_permutation_arg, get_lookup_value, BaseElement, ExtensionOf, FieldElement,
+    PermutationArg, ProofOptions, CLK_COL, FIRST_ACCESS_COL, LOOKUP_COL, MULTIPLICITY_COL, OP_COL,
+    READ_SUM_COL, SORTED_ADDR_COL, SORTED_OP_COL, SORTED_VALUE_COL, TRACE_WIDTH, VALUE_COL,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// RAM AIR
+// ================================================================================================
+
+/// AIR for the read/write memory consistency example.
+///
+/// The auxiliary trace segment consists of two columns: the running product column of the
+/// permutation argument between executed and sorted memory accesses, and the running sum column
+/// $s$ of the LogUp argument such that $s_0 = 0$ and:
+/// $$
+/// s_{i+1} = s_i + \frac{1}{\alpha - d_i} - \frac{m_i}{\alpha - c_i}
+/// $$
+/// where $d_i$ is the looked up address or clock cycle difference at step $i$, $c_i$ is the clock
+/// cycle at step $i$, $m_i$ is the multiplicity of $c_i$, and $\alpha$ is a random element drawn
+/// by the verifier.
+pub struct RamAir {
+    context: AirContext<BaseElement>,
+    permutation: PermutationArg,
+    read_sum: BaseElement,
+}
+
+impl Air for RamAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
+        let permutation = build_permutation_arg();
+
+        let mut main_degrees = vec![TransitionConstraintDegree::new(1)];
+        main_degrees.extend(vec![TransitionConstraintDegree::new(2); 6]);
+        let aux_degrees =
+            vec![permutation.transition_constraint_degree(), TransitionConstraintDegree::new(4)];
+        assert_eq!(TRACE_WIDTH, trace_info.layout().main_trace_width());
+        RamAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                5,
+                4,
+                options,
+            ),
+            permutation,
+            read_sum: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 11 field elements
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // clock cycles of executed accesses are incremented by one at every step
+        result[0] = are_equal(next[CLK_COL], current[CLK_COL] + E::ONE);
+
+        // an operation is either a read (0) or a write (1), and values returned by reads are
+        // accumulated into the read sum
+        let op = current[OP_COL];
+        result[1] = is_binary(op);
+        result[2] =
+            are_equal(next[READ_SUM_COL], current[READ_SUM_COL] + not(op) * current[VALUE_COL]);
+
+        // the first access flag is binary, and the address can change only when the flag is set
+        let first_access = next[FIRST_ACCESS_COL];
+        result[3] = is_binary(first_access);
+        result[4] = not(first_access) * are_equal(next[SORTED_ADDR_COL], current[SORTED_ADDR_COL]);
+
+        // the first access to an address must be a write
+        let sorted_op = next[SORTED_OP_COL];
+        result[5] = first_access * not(sorted_op);
+
+        // a read must return the same value as the preceding access to the same address
+        result[6] = not(sorted_op) * are_equal(next[SORTED_VALUE_COL], current[SORTED_VALUE_COL]);
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        // randomness is consumed in the same order in which the trace builder consumes it
+        let mut rand_elements = aux_rand_elements.segment_reader(0);
+        let permutation_elements =
+            rand_elements.read_elements(self.permutation.num_rand_elements());
+        let alpha = rand_elements.read_element();
+        rand_elements.finish();
+
+        // sorted accesses must be a permutation of executed accesses
+        result[0] =
+            self.permutation
+                .evaluate_transition(main_frame, aux_frame, permutation_elements);
+
+        // enforce the running sum update with denominators multiplied out:
+        // (s_{i+1} - s_i) * (α - d_i) * (α - c_i) = (α - c_i) - m_i * (α - d_i)
+        let main_current = main_frame.current();
+        let value = alpha - E::from(get_lookup_value(main_current, main_frame.next()));
+        let table_value = alpha - E::from(main_current[CLK_COL]);
+        let multiplicity = E::from(main_current[MULTIPLICITY_COL]);
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        result[1] = are_equal(
+            (aux_next[LOOKUP_COL] - aux_current[LOOKUP_COL]) * value * table_value,
+            table_value - multiplicity * value,
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the clock and the read sum start at zero, and the read sum must terminate with the
+        // expected value; the first sorted access must be a write, and is the first access to
+        // its address
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(CLK_COL, 0, BaseElement::ZERO),
+            Assertion::single(READ_SUM_COL, 0, BaseElement::ZERO),
+            Assertion::single(READ_SUM_COL, last_step, self.read_sum),
+            Assertion::single(SORTED_OP_COL, 0, BaseElement::ONE),
+            Assertion::single(FIRST_ACCESS_COL, 0, BaseElement::ONE),
+        ]
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        // the running product must start and end with one, and the running sum must start and
+        // end with zero
+        let last_step = self.trace_length() - 1;
+        let mut result = self.permutation.get_assertions(self.trace_length());
+        result.push(Assertion::single(LOOKUP_COL, 0, E::ZERO));
+        result.push(Assertion::single(LOOKUP_COL, last_step, E::ZERO));
+        result
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::{elements_to_ints, ints_to_elements};
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+    DefaultConstraintEvaluator, DefaultTraceLde, PermutationArg, PermutationArgBuilder,
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+use air::RamAir;
+
+mod prover;
+use prover::RamProver;
+
+mod trace;
+use trace::RamTrace;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const TRACE_WIDTH: usize = 11;
+
+// columns describing memory accesses in the order in which they were executed
+const CLK_COL: usize = 0;
+const OP_COL: usize = 1;
+const ADDR_COL: usize = 2;
+const VALUE_COL: usize = 3;
+const READ_SUM_COL: usize = 4;
+
+// columns describing memory accesses sorted by address and then by clock cycle
+const SORTED_CLK_COL: usize = 5;
+const SORTED_OP_COL: usize = 6;
+const SORTED_ADDR_COL: usize = 7;
+const SORTED_VALUE_COL: usize = 8;
+const FIRST_ACCESS_COL: usize = 9;
+
+// multiplicities of clock cycle values looked up by the ordering constraints
+const MULTIPLICITY_COL: usize = 10;
+
+// columns of the auxiliary trace segment
+const PERMUTATION_COL: usize = 0;
+const LOOKUP_COL: usize = 1;
+
+/// Number of distinct memory cells accessed by the generated program.
+const NUM_CELLS: u64 = 8;
+
+/// Distance between addresses of consecutive memory cells accessed by the generated program.
+const ADDR_STRIDE: u64 = 5;
+
+// RAM EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: &ExampleOptions, num_ops: usize) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(RamExample::<Blake3_192>::new(num_ops, options))),
+        HashFunction::Blake3_256 => Ok(Box::new(RamExample::<Blake3_256>::new(num_ops, options))),
+        HashFunction::Sha3_256 => Ok(Box::new(RamExample::<Sha3_256>::new(num_ops, options))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+/// This example proves that a sequence of reads and writes against a random access memory is
+/// consistent: every read returns the value most recently written to the same address.
+///
+/// The sequence of memory operations is private to the prover; the only public input is the sum
+/// of all values returned by reads.
+///
+/// Consistency is checked using the sorted memory technique. The prover commits to memory
+/// accesses twice: once in the order of execution, and once sorted by address and then by clock
+/// cycle. A permutation argument in the auxiliary trace segment enforces that both lists contain
+/// the same accesses, and simple local constraints against the sorted list enforce that:
+/// * The first access to every address is a write.
+/// * A read returns the same value as the preceding access to the same address.
+/// * Addresses are strictly increasing between groups, and clock cycles are strictly increasing
+///   within each group of accesses to the same address.
+///
+/// The last property is enforced by looking up the differences between adjacent addresses and
+/// clock cycles (minus one) in the clock column via a LogUp argument. Since the clock column
+/// contains all values in $[0, n - 1)$, where $n$ is the length of the trace, this acts as a range
+/// check which requires no extra table columns.
+pub struct RamExample<H: ElementHasher> {
+    options: ProofOptions,
+    operations: Vec<MemoryOp>,
+    read_sum: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RamExample<H> {
+    pub fn new(num_ops: usize, options: ProofOptions) -> Self {
+        assert!(
+            (num_ops + 1).is_power_of_two(),
+            "number of memory operations must be one less than a power of 2"
+        );
+        assert!(num_ops >= 15, "number of memory operations must be at least 15");
+
+        // generate the program and execute it
+        let now = Instant::now();
+        let operations = generate_operations(num_ops);
+        let read_sum = operations
+            .iter()
+            .zip(execute(&operations))
+            .filter(|(op, _)| matches!(op, MemoryOp::Read(_)))
+            .fold(BaseElement::ZERO, |sum, (_, value)| sum + value);
+        debug!(
+            "Generated and executed {} memory operations in {} ms",
+            num_ops,
+            now.elapsed().as_millis()
+        );
+
+        RamExample {
+            options,
+            operations,
+            read_sum,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for RamExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for consistency of {} memory operations\n\
+            ---------------------",
+            self.operations.len()
+        );
+
+        // create a prover
+        let prover = RamProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.operations);
+
+        let trace_width = trace.main_trace_width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<RamAir, H, DefaultRandomCoin<H>>(proof, self.read_sum)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<RamAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.read_sum + BaseElement::ONE,
+        )
+    }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        elements_to_ints(&[self.read_sum])
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let read_sum = ints_to_elements(pub_inputs, 1)?[0];
+        winterfell::verify::<RamAir, H, DefaultRandomCoin<H>>(proof, read_sum)
+            .map_err(|err| err.to_string())
+    }
+}
+
+// MEMORY OPERATIONS
+// ================================================================================================
+
+/// A single operation against a random access memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryOp {
+    /// Reads a value from the specified address.
+    Read(u64),
+    /// Writes a value into the specified address.
+    Write(u64, BaseElement),
+}
+
+impl MemoryOp {
+    /// Returns the address accessed by this operation.
+    pub fn addr(&self) -> u64 {
+        match self {
+            Self::Read(addr) => *addr,
+            Self::Write(addr, _) => *addr,
+        }
+    }
+
+    /// Returns ONE if this operation is a write, and ZERO otherwise.
+    pub fn op_flag(&self) -> BaseElement {
+        match self {
+            Self::Read(_) => BaseElement::ZERO,
+            Self::Write(..) => BaseElement::ONE,
+        }
+    }
+}
+
+/// Executes the provided memory operations against an initially empty memory, and returns the
+/// value accessed by each operation.
+///
+/// # Panics
+/// Panics if an address is read before it was written to.
+pub fn execute(operations: &[MemoryOp]) -> Vec<BaseElement> {
+    let mut memory = std::collections::BTreeMap::new();
+    operations
+        .iter()
+        .map(|op| match *op {
+            MemoryOp::Read(addr) => *memory
+                .get(&addr)
+                .unwrap_or_else(|| panic!("address {addr} was read before being written to")),
+            MemoryOp::Write(addr, value) => {
+                memory.insert(addr, value);
+                value
+            }
+        })
+        .collect()
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a program consisting of the specified number of memory operations.
+///
+/// The program first writes into each of the [NUM_CELLS] memory cells, and then interleaves
+/// reads and writes of these cells.
+fn generate_operations(num_ops: usize) -> Vec<MemoryOp> {
+    (0..num_ops as u64)
+        .map(|step| {
+            if step < NUM_CELLS {
+                MemoryOp::Write(step * ADDR_STRIDE, BaseElement::from(step))
+            } else {
+                let addr = ((step * 5 + step / 3) % NUM_CELLS) * ADDR_STRIDE;
+                if step % 3 == 0 {
+                    MemoryOp::Write(addr, BaseElement::from(step * step + 1))
+                } else {
+                    MemoryOp::Read(addr)
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns the permutation argument which enforces that the sorted list of memory accesses is
+/// a permutation of the list of memory accesses in the order of execution.
+fn build_permutation_arg() -> PermutationArg {
+    PermutationArgBuilder::new(PERMUTATION_COL)
+        .with_left(vec![CLK_COL, OP_COL, ADDR_COL, VALUE_COL])
+        .with_right(vec![SORTED_CLK_COL, SORTED_OP_COL, SORTED_ADDR_COL, SORTED_VALUE_COL])
+        .build()
+}
+
+/// Returns the value looked up in the clock column for the transition between the provided rows.
+///
+/// If the next row starts a new group of accesses, this is the difference between the addresses
+/// of the rows minus one; otherwise, this is the difference between the clock cycles of the rows
+/// minus one. In both cases, the value is in the clock column only if the difference is positive.
+fn get_lookup_value<E: FieldElement>(current: &[E], next: &[E]) -> E {
+    let first_access = next[FIRST_ACCESS_COL];
+    let addr_delta = next[SORTED_ADDR_COL] - current[SORTED_ADDR_COL];
+    let clk_delta = next[SORTED_CLK_COL] - current[SORTED_CLK_COL];
+    first_access * addr_delta + (E::ONE - first_access) * clk_delta - E::ONE
+}
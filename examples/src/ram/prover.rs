@@ -0,0 +1,153 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    execute, BaseElement, DefaultConstraintEvaluator, DefaultRandomCoin, DefaultTraceLde,
+    ElementHasher, FieldElement, MemoryOp, PhantomData, ProofOptions, Prover, RamAir, RamTrace,
+    Trace, CLK_COL, FIRST_ACCESS_COL, MULTIPLICITY_COL, READ_SUM_COL, SORTED_CLK_COL, TRACE_WIDTH,
+};
+
+// RAM PROVER
+// ================================================================================================
+
+pub struct RamProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RamProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds the main segment of an execution trace for the specified memory operations such
+    /// that each row of the trace describes a single memory access.
+    ///
+    /// The last row of the trace is not included into the permutation and the lookup arguments;
+    /// it describes a write of ZERO to the address immediately following the largest accessed
+    /// address, so that it satisfies the transition constraints of the sorted part of the trace.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of operations is not one less than a power of two.
+    /// * An address is read before it was written to.
+    /// * A difference between two adjacent addresses, or between two adjacent clock cycles of
+    ///   accesses to the same address, is not smaller than the number of operations.
+    pub fn build_trace(&self, operations: &[MemoryOp]) -> RamTrace {
+        let trace_length = operations.len() + 1;
+        assert!(
+            trace_length.is_power_of_two(),
+            "number of memory operations must be one less than a power of 2"
+        );
+        let values = execute(operations);
+
+        // describe each access as (address, clock cycle, operation, value) tuple; the last row of
+        // the trace describes a write of ZERO to the address following the largest accessed one
+        let mut accesses = operations
+            .iter()
+            .zip(values.iter())
+            .enumerate()
+            .map(|(clk, (op, &value))| (op.addr(), clk as u64, op.op_flag(), value))
+            .collect::<Vec<_>>();
+        let last_step = trace_length - 1;
+        let padding_addr = accesses.iter().map(|access| access.0).max().unwrap() + 1;
+        let padding = (padding_addr, last_step as u64, BaseElement::ONE, BaseElement::ZERO);
+
+        // build the executed part of the trace
+        let mut trace = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+        let mut read_sum = BaseElement::ZERO;
+        for (step, &access) in accesses.iter().enumerate() {
+            write_access(&mut trace, CLK_COL, step, access);
+            trace[READ_SUM_COL][step] = read_sum;
+            read_sum += (BaseElement::ONE - access.2) * access.3;
+        }
+        write_access(&mut trace, CLK_COL, last_step, padding);
+        trace[READ_SUM_COL][last_step] = read_sum;
+
+        // build the sorted part of the trace; the first access to each address is flagged
+        accesses.sort_unstable_by_key(|&(addr, clk, ..)| (addr, clk));
+        accesses.push(padding);
+        let mut multiplicities = vec![0u64; trace_length];
+        for (step, &access) in accesses.iter().enumerate() {
+            write_access(&mut trace, SORTED_CLK_COL, step, access);
+            let (addr, clk, ..) = access;
+            let first_access = step == 0 || accesses[step - 1].0 != addr;
+            trace[FIRST_ACCESS_COL][step] = BaseElement::from(first_access as u64);
+
+            // record the value looked up by the ordering constraints of the previous step
+            if step > 0 {
+                let (prev_addr, prev_clk, ..) = accesses[step - 1];
+                let delta = if first_access {
+                    addr - prev_addr - 1
+                } else {
+                    clk - prev_clk - 1
+                } as usize;
+                assert!(
+                    delta < trace_length - 1,
+                    "difference between adjacent addresses or clock cycles is too large"
+                );
+                multiplicities[delta] += 1;
+            }
+        }
+        trace[MULTIPLICITY_COL] = multiplicities.into_iter().map(BaseElement::from).collect();
+
+        RamTrace::new(trace)
+    }
+}
+
+impl<H: ElementHasher> Prover for RamProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = RamAir;
+    type Trace = RamTrace;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        let last_step = trace.length() - 1;
+        trace.get(READ_SUM_COL, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: winterfell::AuxTraceRandElements<E>,
+        composition_coefficients: winterfell::ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes a memory access into the specified step of the trace; the clock, operation, address,
+/// and value of the access are written into 4 consecutive columns starting with `first_col`.
+fn write_access(
+    trace: &mut [Vec<BaseElement>],
+    first_col: usize,
+    step: usize,
+    (addr, clk, op, value): (u64, u64, BaseElement, BaseElement),
+) {
+    trace[first_col][step] = BaseElement::from(clk);
+    trace[first_col + 1][step] = op;
+    trace[first_col + 2][step] = BaseElement::from(addr);
+    trace[first_col + 3][step] = value;
+}
@@ -0,0 +1,45 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{generate_operations, Blake3_256, Prover, RamAir, RamProver};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn ram_test_basic_proof_verification() {
+    let ram = Box::new(super::RamExample::<Blake3_256>::new(63, build_options(false)));
+    crate::tests::test_basic_proof_verification(ram);
+}
+
+#[test]
+fn ram_test_basic_proof_verification_extension() {
+    let ram = Box::new(super::RamExample::<Blake3_256>::new(63, build_options(true)));
+    crate::tests::test_basic_proof_verification(ram);
+}
+
+#[test]
+fn ram_test_basic_proof_verification_fail() {
+    let ram = Box::new(super::RamExample::<Blake3_256>::new(63, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(ram);
+}
+
+#[test]
+fn ram_test_air_consistency() {
+    let prover = RamProver::<Blake3_256>::new(build_options(false));
+    let traces = vec![
+        prover.build_trace(&generate_operations(15)),
+        prover.build_trace(&generate_operations(63)),
+    ];
+    let pub_inputs = traces.iter().map(|trace| prover.get_pub_inputs(trace)).collect::<Vec<_>>();
+    winterfell::testing::check_air::<RamAir, _>(traces, &pub_inputs);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 7)
+}
@@ -0,0 +1,124 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_permutation_arg, get_lookup_value, BaseElement, FieldElement, PermutationArg, CLK_COL,
+    MULTIPLICITY_COL, TRACE_WIDTH,
+};
+use winterfell::{
+    math::batch_inversion, AuxRandElementsReader, AuxTraceError, ColMatrix, EvaluationFrame, Trace,
+    TraceLayout, TraceMetadata,
+};
+
+// RAM TRACE
+// ================================================================================================
+
+/// Execution trace for the read/write memory consistency example.
+///
+/// The main segment of the trace is built by the prover upfront. The auxiliary segment, which
+/// consists of the running product column of the permutation argument and the LogUp running sum
+/// column, is built once the verifier has sent the random elements (see [RamAir](super::RamAir)
+/// for the definition of the columns).
+pub struct RamTrace {
+    layout: TraceLayout,
+    main_segment: ColMatrix<BaseElement>,
+    meta: TraceMetadata,
+    permutation: PermutationArg,
+}
+
+impl RamTrace {
+    /// Returns a new trace instantiated from the provided columns of the main trace segment.
+    pub fn new(columns: Vec<Vec<BaseElement>>) -> Self {
+        assert_eq!(TRACE_WIDTH, columns.len());
+        let permutation = build_permutation_arg();
+        Self {
+            layout: TraceLayout::new(TRACE_WIDTH, [2], [permutation.num_rand_elements() + 1]),
+            main_segment: ColMatrix::new(columns),
+            meta: TraceMetadata::new(),
+            permutation,
+        }
+    }
+
+    /// Returns the value of the main trace segment at the specified column and step.
+    pub fn get(&self, column: usize, step: usize) -> BaseElement {
+        self.main_segment.get(column, step)
+    }
+}
+
+impl Trace for RamTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    fn meta(&self) -> &TraceMetadata {
+        &self.meta
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.main_segment.read_row_into(row_idx, frame.current_mut());
+        self.main_segment.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Result<ColMatrix<E>, AuxTraceError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // there is only one auxiliary segment
+        if !aux_segments.is_empty() {
+            return Err(AuxTraceError::UndefinedSegment(aux_segments.len()));
+        }
+
+        // the permutation argument consumes the first random elements, followed by the LogUp
+        // challenge α
+        let mut rand_elements = AuxRandElementsReader::new(rand_elements);
+        let permutation_elements =
+            rand_elements.read_elements(self.permutation.num_rand_elements());
+        let alpha = rand_elements.read_element();
+        rand_elements.finish();
+
+        // build the running product column of the permutation argument
+        let main_trace = self.main_segment.columns().collect::<Vec<_>>();
+        let running_product = self.permutation.build_aux_column(&main_trace, permutation_elements);
+
+        // compute inverses of (α - d_i) and (α - c_i) for all steps at once; values in the last
+        // row are not included into the running sum
+        let num_steps = self.length() - 1;
+        let mut current = vec![BaseElement::ZERO; TRACE_WIDTH];
+        let mut next = vec![BaseElement::ZERO; TRACE_WIDTH];
+        let mut denominators = Vec::with_capacity(2 * num_steps);
+        for step in 0..num_steps {
+            self.main_segment.read_row_into(step, &mut current);
+            self.main_segment.read_row_into(step + 1, &mut next);
+            denominators.push(alpha - E::from(get_lookup_value(&current, &next)));
+            denominators.push(alpha - E::from(current[CLK_COL]));
+        }
+        let inv_denominators = batch_inversion(&denominators);
+
+        // build the running sum column of the LogUp argument
+        let mut running_sum = Vec::with_capacity(self.length());
+        running_sum.push(E::ZERO);
+        for (step, inv) in inv_denominators.chunks(2).enumerate() {
+            let multiplicity = E::from(self.get(MULTIPLICITY_COL, step));
+            running_sum.push(running_sum[step] + inv[0] - multiplicity * inv[1]);
+        }
+
+        Ok(ColMatrix::new(vec![running_product, running_sum]))
+    }
+}
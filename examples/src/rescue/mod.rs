@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::utils::{elements_to_ints, ints_to_elements};
 use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
 use log::debug;
@@ -136,6 +137,20 @@ where
         };
         winterfell::verify::<RescueAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
     }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        elements_to_ints(&[self.seed[0], self.seed[1], self.result[0], self.result[1]])
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let inputs = ints_to_elements(pub_inputs, 4)?;
+        let pub_inputs = PublicInputs {
+            seed: [inputs[0], inputs[1]],
+            result: [inputs[2], inputs[3]],
+        };
+        winterfell::verify::<RescueAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+            .map_err(|err| err.to_string())
+    }
 }
 
 // HELPER FUNCTIONS
@@ -117,7 +117,7 @@ fn add_constants(state: &mut [BaseElement], ark: &[BaseElement], offset: usize)
 #[allow(clippy::needless_range_loop)]
 fn apply_sbox<E: FieldElement>(state: &mut [E]) {
     for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(ALPHA.into());
+        state[i] = state[i].exp_const::<ALPHA>();
     }
 }
 
@@ -125,7 +125,7 @@ fn apply_sbox<E: FieldElement>(state: &mut [E]) {
 #[allow(clippy::needless_range_loop)]
 fn apply_inv_sbox(state: &mut [BaseElement]) {
     for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(INV_ALPHA);
+        state[i] = state[i].cube_root();
     }
 }
 
@@ -165,8 +165,7 @@ fn apply_inv_mds<E: FieldElement + From<BaseElement>>(state: &mut [E]) {
 
 // RESCUE CONSTANTS
 // ================================================================================================
-const ALPHA: u32 = 3;
-const INV_ALPHA: u128 = 226854911280625642308916371969163307691;
+const ALPHA: u64 = 3;
 
 const MDS: [BaseElement; STATE_WIDTH * STATE_WIDTH] = [
     BaseElement::new(340282366920938463463374557953744960808),
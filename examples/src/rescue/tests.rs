@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use winterfell::{FieldExtension, OodConstraintBatching, ProofOptions};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -24,6 +24,25 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_small_blowup_proof_verification() {
+    // the blowup factor is smaller than the blowup factor needed to evaluate constraints of
+    // degree 3; soundness is recovered by using more queries
+    let options = ProofOptions::new(56, 2, 0, FieldExtension::Quadratic, 4, 31);
+    let rescue_eg = Box::new(super::RescueExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_combined_ood_constraint_batching() {
+    let options = build_options(true).with_ood_constraint_batching(OodConstraintBatching::Combined);
+    let rescue_eg = Box::new(super::RescueExample::<Blake3_256>::new(128, options.clone()));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+
+    let rescue_eg = Box::new(super::RescueExample::<Blake3_256>::new(128, options));
+    crate::tests::test_proof_mutations_rejected(rescue_eg);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -3,10 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use core_utils::{collections::Vec, uninit_vector};
 use winterfell::{
     math::{FieldElement, StarkField},
-    ColMatrix, EvaluationFrame, Trace, TraceInfo, TraceLayout,
+    AuxTraceError, ColMatrix, EvaluationFrame, Trace, TraceInfo, TraceLayout, TraceMetadata,
 };
 
 // RAP TRACE TABLE
@@ -15,8 +14,8 @@ use winterfell::{
 ///
 /// This implementation should be sufficient for most use cases.
 /// To create a trace table, you can use [RapTraceTable::new()] function, which takes trace
-/// width and length as parameters. This function will allocate memory for the trace, but will not
-/// fill it with data. To fill the execution trace, you can use the [fill()](RapTraceTable::fill)
+/// width and length as parameters. This function will allocate memory for the trace and initialize
+/// it to all zeros. To fill the execution trace, you can use the [fill()](RapTraceTable::fill)
 /// method, which takes two closures as parameters:
 ///
 /// 1. The first closure is responsible for initializing the first state of the computation
@@ -26,11 +25,11 @@ use winterfell::{
 ///
 /// You can also use [RapTraceTable::with_meta()] function to create a blank execution trace.
 /// This function work just like [RapTraceTable::new()] function, but also takes a metadata
-/// parameter which can be an arbitrary sequence of bytes up to 64KB in size.
+/// parameter which is a typed key-value map (see [TraceMetadata]) up to 64KB in size.
 pub struct RapTraceTable<B: StarkField> {
     layout: TraceLayout,
     trace: ColMatrix<B>,
-    meta: Vec<u8>,
+    meta: TraceMetadata,
 }
 
 impl<B: StarkField> RapTraceTable<B> {
@@ -39,31 +38,30 @@ impl<B: StarkField> RapTraceTable<B> {
 
     /// Creates a new execution trace of the specified width and length.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
-    /// expected that the trace will be filled using one of the data mutator methods.
+    /// All cells of the trace are initialized to zeros. It is expected that the trace will be
+    /// filled using one of the data mutator methods.
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
     ///   `B`, or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
-        Self::with_meta(width, length, vec![])
+        Self::with_meta(width, length, TraceMetadata::new())
     }
 
     /// Creates a new execution trace of the specified width and length, and with the specified
     /// metadata.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
-    /// expected that the trace will be filled using one of the data mutator methods.
+    /// All cells of the trace are initialized to zeros. It is expected that the trace will be
+    /// filled using one of the data mutator methods.
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
     ///   field `B`, or is not a power of two.
-    /// * Length of `meta` is greater than 65535;
-    pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
+    pub fn with_meta(width: usize, length: usize, meta: TraceMetadata) -> Self {
         assert!(width > 0, "execution trace must consist of at least one column");
         assert!(
             width <= TraceInfo::MAX_TRACE_WIDTH,
@@ -84,14 +82,8 @@ impl<B: StarkField> RapTraceTable<B> {
             B::TWO_ADICITY,
             length.ilog2()
         );
-        assert!(
-            meta.len() <= TraceInfo::MAX_META_LENGTH,
-            "number of metadata bytes cannot be greater than {}, but was {}",
-            TraceInfo::MAX_META_LENGTH,
-            meta.len()
-        );
 
-        let columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+        let columns = vec![vec![B::ZERO; length]; width];
         Self {
             layout: TraceLayout::new(width, [3], [3]),
             trace: ColMatrix::new(columns),
@@ -166,7 +158,7 @@ impl<B: StarkField> Trace for RapTraceTable<B> {
         self.trace.num_rows()
     }
 
-    fn meta(&self) -> &[u8] {
+    fn meta(&self) -> &TraceMetadata {
         &self.meta
     }
 
@@ -184,48 +176,59 @@ impl<B: StarkField> Trace for RapTraceTable<B> {
         &mut self,
         aux_segments: &[ColMatrix<E>],
         rand_elements: &[E],
-    ) -> Option<ColMatrix<E>>
+    ) -> Result<ColMatrix<E>, AuxTraceError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
         // We only have one auxiliary segment for this example
         if !aux_segments.is_empty() {
-            return None;
+            return Err(AuxTraceError::UndefinedSegment(aux_segments.len()));
         }
 
-        let mut current_row = unsafe { uninit_vector(self.width()) };
-        let mut next_row = unsafe { uninit_vector(self.width()) };
-        self.read_row_into(0, &mut current_row);
-        let mut aux_columns = vec![vec![E::ZERO; self.length()]; self.aux_trace_width()];
-
-        // Columns storing the copied values for the permutation argument are not necessary, but
-        // help understanding the construction of RAPs and are kept for illustrative purposes.
-        aux_columns[0][0] =
-            rand_elements[0] * current_row[0].into() + rand_elements[1] * current_row[1].into();
-        aux_columns[1][0] =
-            rand_elements[0] * current_row[4].into() + rand_elements[1] * current_row[5].into();
-
-        // Permutation argument column
-        aux_columns[2][0] = E::ONE;
-
-        for index in 1..self.length() {
-            // At every last step before a new hash iteration,
-            // copy the permuted values into the auxiliary columns
-            if (index % super::CYCLE_LENGTH) == super::NUM_HASH_ROUNDS {
-                self.read_row_into(index, &mut current_row);
-                self.read_row_into(index + 1, &mut next_row);
-
-                aux_columns[0][index] = rand_elements[0] * (next_row[0] - current_row[0]).into()
-                    + rand_elements[1] * (next_row[1] - current_row[1]).into();
-                aux_columns[1][index] = rand_elements[0] * (next_row[4] - current_row[4]).into()
-                    + rand_elements[1] * (next_row[5] - current_row[5]).into();
-            }
-
-            let num = aux_columns[0][index - 1] + rand_elements[2];
-            let denom = aux_columns[1][index - 1] + rand_elements[2];
-            aux_columns[2][index] = aux_columns[2][index - 1] * num * denom.inv();
-        }
+        let mut current_row = vec![B::ZERO; self.width()];
+        let mut next_row = vec![B::ZERO; self.width()];
+        let aux_segment = ColMatrix::from_row_fn(
+            self.aux_trace_width(),
+            self.length(),
+            |index, row: &mut [E]| {
+                if index == 0 {
+                    self.read_row_into(0, &mut current_row);
+
+                    // Columns storing the copied values for the permutation argument are not
+                    // necessary, but help understanding the construction of RAPs and are kept for
+                    // illustrative purposes.
+                    row[0] = rand_elements[0] * current_row[0].into()
+                        + rand_elements[1] * current_row[1].into();
+                    row[1] = rand_elements[0] * current_row[4].into()
+                        + rand_elements[1] * current_row[5].into();
+
+                    // Permutation argument column
+                    row[2] = E::ONE;
+                    return;
+                }
+
+                // The row still holds values of the previous row at this point
+                let num = row[0] + rand_elements[2];
+                let denom = row[1] + rand_elements[2];
+                row[2] = row[2] * num * denom.inv();
+
+                // At every last step before a new hash iteration,
+                // copy the permuted values into the auxiliary columns
+                if (index % super::CYCLE_LENGTH) == super::NUM_HASH_ROUNDS {
+                    self.read_row_into(index, &mut current_row);
+                    self.read_row_into(index + 1, &mut next_row);
+
+                    row[0] = rand_elements[0] * (next_row[0] - current_row[0]).into()
+                        + rand_elements[1] * (next_row[1] - current_row[1]).into();
+                    row[1] = rand_elements[0] * (next_row[4] - current_row[4]).into()
+                        + rand_elements[1] * (next_row[5] - current_row[5]).into();
+                } else {
+                    row[0] = E::ZERO;
+                    row[1] = E::ZERO;
+                }
+            },
+        );
 
-        Some(ColMatrix::new(aux_columns))
+        Ok(aux_segment)
     }
 }
@@ -3,8 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::utils::{elements_to_ints, ints_to_elements};
 use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
+use core_utils::flatten_slice_elements;
 use log::debug;
 use rand_utils::rand_array;
 use std::time::Instant;
@@ -147,6 +149,19 @@ where
         };
         winterfell::verify::<RescueRapsAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
     }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        elements_to_ints(flatten_slice_elements(&self.result))
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let inputs = ints_to_elements(pub_inputs, 4)?;
+        let pub_inputs = PublicInputs {
+            result: [[inputs[0], inputs[1]], [inputs[2], inputs[3]]],
+        };
+        winterfell::verify::<RescueRapsAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+            .map_err(|err| err.to_string())
+    }
 }
 
 // HELPER FUNCTIONS
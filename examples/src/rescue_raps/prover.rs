@@ -8,18 +8,22 @@ use super::{
     DefaultRandomCoin, DefaultTraceLde, ElementHasher, FieldElement, PhantomData, ProofOptions,
     Prover, PublicInputs, RapTraceTable, RescueRapsAir, Trace, CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
+use winterfell::{crypto::Hasher, math::fft::CpuFftBackend};
 
 // RESCUE PROVER
 // ================================================================================================
 /// This example constructs a proof for correct execution of 2 hash chains simultaneously.
 /// In order to demonstrate the power of RAPs, the two hash chains have seeds that are
 /// permutations of each other.
-pub struct RescueRapsProver<H: ElementHasher> {
+///
+/// The auxiliary trace segment is committed to using hash function `AH`; by default, this is the
+/// same hash function as the one used for all other commitments.
+pub struct RescueRapsProver<H: ElementHasher, AH: ElementHasher = H> {
     options: ProofOptions,
-    _hasher: PhantomData<H>,
+    _hasher: PhantomData<(H, AH)>,
 }
 
-impl<H: ElementHasher> RescueRapsProver<H> {
+impl<H: ElementHasher, AH: ElementHasher> RescueRapsProver<H, AH> {
     pub fn new(options: ProofOptions) -> Self {
         Self {
             options,
@@ -89,16 +93,18 @@ impl<H: ElementHasher> RescueRapsProver<H> {
     }
 }
 
-impl<H: ElementHasher> Prover for RescueRapsProver<H>
+impl<H, AH> Prover for RescueRapsProver<H, AH>
 where
     H: ElementHasher<BaseField = BaseElement>,
+    AH: ElementHasher<BaseField = BaseElement> + Hasher<Digest = H::Digest>,
 {
     type BaseField = BaseElement;
     type Air = RescueRapsAir;
     type Trace = RapTraceTable<BaseElement>;
     type HashFn = H;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
-    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultTraceLde<E, Self::HashFn, CpuFftBackend, AH>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
         DefaultConstraintEvaluator<'a, Self::Air, E>;
 
@@ -3,8 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{Blake3_256, PublicInputs, RescueRapsAir, RescueRapsProver, Sha3_256};
+use winterfell::{crypto::DefaultRandomCoin, FieldExtension, ProofOptions, Prover, VerifierError};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -26,6 +26,56 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_proof_mutations_rejected() {
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, build_options(true)));
+    crate::tests::test_proof_mutations_rejected(rescue_eg);
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn rescue_test_protobuf_round_trip() {
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, build_options(true)));
+    crate::tests::test_protobuf_round_trip(rescue_eg);
+}
+
+#[test]
+fn rescue_test_small_blowup_proof_verification() {
+    // the blowup factor is smaller than the blowup factor needed to evaluate constraints of
+    // degree 3; soundness is recovered by using more queries
+    let options = ProofOptions::new(56, 2, 0, FieldExtension::Quadratic, 4, 31);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_aux_segment_hasher() {
+    let example = super::RescueRapsExample::<Blake3_256>::new(128, build_options(true));
+    let prover = RescueRapsProver::<Blake3_256, Sha3_256>::new(build_options(true));
+    let trace = prover.build_trace(&example.seeds, &example.permuted_seeds, example.result);
+    let proof = prover.prove(trace).unwrap();
+    assert!(proof.context.aux_trace_hasher().is_some());
+
+    let pub_inputs = || PublicInputs {
+        result: example.result,
+    };
+    winterfell::verify_with_aux_hasher::<
+        RescueRapsAir,
+        Blake3_256,
+        Sha3_256,
+        DefaultRandomCoin<Blake3_256>,
+    >(proof.clone(), pub_inputs())
+    .unwrap();
+
+    // a verifier which expects the auxiliary segment to be committed to using the main hash
+    // function rejects the proof
+    let result = winterfell::verify::<RescueRapsAir, Blake3_256, DefaultRandomCoin<Blake3_256>>(
+        proof,
+        pub_inputs(),
+    );
+    assert_eq!(Err(VerifierError::InconsistentAuxTraceHasher), result);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
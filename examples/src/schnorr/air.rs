@@ -0,0 +1,186 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_range_check, get_challenge, inv_mod, BaseElement, ExtensionOf, FieldElement,
+    ProofOptions, RangeCheck, Signature, ACTIVE_COL, EXPONENT_BITS, E_ACC_COL, E_BIT_COL,
+    GENERATOR, LIMB_BITS, MODULUS, QUOTIENT_COLS, S_ACC_COL, S_BIT_COL, TRACE_WIDTH, VALUE_COL,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Clone)]
+pub struct PublicInputs {
+    pub pub_key: u64,
+    pub message: [BaseElement; 2],
+    pub signature: Signature,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![
+            BaseElement::from(self.pub_key),
+            self.message[0],
+            self.message[1],
+            BaseElement::from(self.signature.r),
+            BaseElement::from(self.signature.s),
+        ]
+    }
+}
+
+// SCHNORR SIGNATURE AIR
+// ================================================================================================
+
+pub struct SchnorrAir {
+    context: AirContext<BaseElement>,
+    range_check: RangeCheck,
+    pub_key_inv: BaseElement,
+    r: BaseElement,
+    s: BaseElement,
+    e: BaseElement,
+}
+
+impl Air for SchnorrAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let range_check = build_range_check();
+
+        let mut main_degrees = vec![TransitionConstraintDegree::new(2); 5];
+        main_degrees.push(TransitionConstraintDegree::new(5));
+        main_degrees.extend(range_check.main_constraint_degrees());
+        let aux_degrees = vec![range_check.aux_constraint_degree()];
+        assert_eq!(TRACE_WIDTH, trace_info.layout().main_trace_width());
+
+        // the challenge is computed by the verifier from the public inputs
+        let e = get_challenge(pub_inputs.signature.r, pub_inputs.pub_key, pub_inputs.message);
+        SchnorrAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                11,
+                2,
+                options,
+            ),
+            range_check,
+            pub_key_inv: BaseElement::from(inv_mod(pub_inputs.pub_key)),
+            r: BaseElement::from(pub_inputs.signature.r),
+            s: BaseElement::from(pub_inputs.signature.s),
+            e: BaseElement::from(e),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 11 field elements
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // the chiplet is active in the first EXPONENT_BITS rows, and once it becomes inactive,
+        // it stays inactive until the end of the trace
+        let active = current[ACTIVE_COL];
+        let inactive = not(active);
+        result[0] = inactive * next[ACTIVE_COL];
+
+        // exponent bits must be binary, and the exponents are accumulated from their bits
+        // starting with the most significant one; once the chiplet becomes inactive, the
+        // accumulated values are copied to the next row
+        result[1] = is_binary(current[S_BIT_COL]);
+        result[2] = is_binary(current[E_BIT_COL]);
+        result[3] = active
+            * are_equal(next[S_ACC_COL], current[S_ACC_COL].double() + current[S_BIT_COL])
+            + inactive * are_equal(next[S_ACC_COL], current[S_ACC_COL]);
+        result[4] = active
+            * are_equal(next[E_ACC_COL], current[E_ACC_COL].double() + current[E_BIT_COL])
+            + inactive * are_equal(next[E_ACC_COL], current[E_ACC_COL]);
+
+        // a single step of the square-and-multiply algorithm computing g^s * y^-e mod p:
+        // x_{i+1} = x_i^2 * g^{s_i} * y^{-e_i} mod p
+        // the reduction modulo p is expressed via the quotient q as:
+        // x_i^2 * g^{s_i} * y^{-e_i} = q * p + x_{i+1}
+        let g_factor = E::ONE + current[S_BIT_COL] * E::from(BaseElement::from(GENERATOR - 1));
+        let y_factor = E::ONE + current[E_BIT_COL] * (E::from(self.pub_key_inv) - E::ONE);
+        let limb_shift = E::from(BaseElement::from(1u64 << LIMB_BITS));
+        let quotient = QUOTIENT_COLS
+            .iter()
+            .rev()
+            .fold(E::ZERO, |acc, &column| acc * limb_shift + current[column]);
+        result[5] = active
+            * are_equal(
+                current[VALUE_COL].square() * g_factor * y_factor,
+                quotient * E::from(BaseElement::from(MODULUS)) + next[VALUE_COL],
+            )
+            + inactive * are_equal(next[VALUE_COL], current[VALUE_COL]);
+
+        // constraints of the range check chiplet
+        self.range_check.evaluate_main_transition(frame, &mut result[6..]);
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        result[0] = self.range_check.evaluate_aux_transition(
+            main_frame,
+            aux_frame,
+            aux_rand_elements.get_segment_elements(0),
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the exponentiation chiplet must be active for exactly EXPONENT_BITS steps, must
+        // start with the value ONE and both exponents set to ZERO, and must terminate with
+        // x = r, and exponents equal to s and e; this means that g^s * y^-e = r, or
+        // equivalently, g^s = r * y^e, which is the Schnorr verification equation
+        let last_step = self.trace_length() - 1;
+        let mut result = vec![
+            Assertion::single(ACTIVE_COL, 0, BaseElement::ONE),
+            Assertion::single(ACTIVE_COL, EXPONENT_BITS - 1, BaseElement::ONE),
+            Assertion::single(ACTIVE_COL, EXPONENT_BITS, BaseElement::ZERO),
+            Assertion::single(VALUE_COL, 0, BaseElement::ONE),
+            Assertion::single(VALUE_COL, last_step, self.r),
+            Assertion::single(S_ACC_COL, 0, BaseElement::ZERO),
+            Assertion::single(S_ACC_COL, last_step, self.s),
+            Assertion::single(E_ACC_COL, 0, BaseElement::ZERO),
+            Assertion::single(E_ACC_COL, last_step, self.e),
+        ];
+        result.extend(self.range_check.get_main_assertions(self.trace_length()));
+        result
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        self.range_check.get_aux_assertions(self.trace_length())
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::{elements_to_ints, ints_to_elements};
+use crate::{Example, ExampleOptions, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f64::BaseElement, ExtensionOf, FieldElement, StarkField, ToElements},
+    DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover, RangeCheck,
+    RangeCheckBuilder, StarkProof, Trace, VerifierError,
+};
+
+mod signature;
+use signature::{
+    get_challenge, inv_mod, message_to_elements, verify, PrivateKey, Signature, EXPONENT_BITS,
+    GENERATOR, MODULUS,
+};
+
+mod air;
+use air::{PublicInputs, SchnorrAir};
+
+mod prover;
+use prover::SchnorrProver;
+
+mod trace;
+use trace::SchnorrTrace;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+
+const TRACE_WIDTH: usize = 11;
+
+// columns of the exponentiation chiplet
+const ACTIVE_COL: usize = 0;
+const VALUE_COL: usize = 1;
+const S_BIT_COL: usize = 2;
+const E_BIT_COL: usize = 3;
+const S_ACC_COL: usize = 4;
+const E_ACC_COL: usize = 5;
+const QUOTIENT_COLS: [usize; 3] = [6, 7, 8];
+
+// columns of the range check chiplet
+const RANGE_TABLE_COL: usize = 9;
+const RANGE_MULTIPLICITY_COL: usize = 10;
+
+/// Number of bits in each of the 16-bit limbs of the quotient.
+const LIMB_BITS: u32 = 16;
+
+// SCHNORR SIGNATURE EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: &ExampleOptions) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(SchnorrExample::<Blake3_192>::new(options))),
+        HashFunction::Blake3_256 => Ok(Box::new(SchnorrExample::<Blake3_256>::new(options))),
+        HashFunction::Sha3_256 => Ok(Box::new(SchnorrExample::<Sha3_256>::new(options))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+/// This example proves verification of a Schnorr signature defined over a prime field which is
+/// different from the field of the STARK (i.e., the signature is verified using non-native field
+/// arithmetic).
+///
+/// The execution trace is split into two chiplets which occupy different columns of the trace:
+/// * The exponentiation chiplet computes $g^s \cdot y^{-e}$ modulo the signature field modulus
+///   $p$ using a single square-and-multiply pass over bits of both exponents. Every modular
+///   multiplication is expressed as an integer equation $a = q \cdot p + r$, where the quotient
+///   $q$ is decomposed into 16-bit limbs.
+/// * The range check chiplet enforces that the limbs of all quotients, as well as all
+///   intermediate values, are 16-bit values. This guarantees that both sides of the integer
+///   equations above are smaller than the modulus of the STARK field (which is $2^{64} - 2^{32} +
+///   1$), and thus, the equations hold over integers and not just over the STARK field.
+///
+/// Parameters of the signature scheme are tiny, and thus, the signatures are not secure. The
+/// example is meant to illustrate the structure of a non-native arithmetic AIR.
+pub struct SchnorrExample<H: ElementHasher> {
+    options: ProofOptions,
+    pub_key: u64,
+    message: [BaseElement; 2],
+    signature: Signature,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SchnorrExample<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        // generate a private/public key pair and sign a message
+        let now = Instant::now();
+        let private_key = PrivateKey::from_seed([1; 32]);
+        let pub_key = private_key.pub_key();
+        let message = message_to_elements("test message".as_bytes());
+        let signature = private_key.sign(message);
+        debug!("Generated a key pair and signed a message in {} ms", now.elapsed().as_millis());
+
+        // verify the signature
+        let now = Instant::now();
+        assert!(verify(pub_key, message, signature));
+        debug!("Verified the signature in {} ms", now.elapsed().as_millis());
+
+        SchnorrExample {
+            options,
+            pub_key,
+            message,
+            signature,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for SchnorrExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for verifying a Schnorr signature over a {}-bit field\n\
+            ---------------------",
+            MODULUS.ilog2() + 1
+        );
+
+        // create a prover
+        let prover = SchnorrProver::<H>::new(
+            self.pub_key,
+            self.message,
+            self.signature,
+            self.options.clone(),
+        );
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace();
+
+        let trace_width = trace.main_trace_width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            pub_key: self.pub_key,
+            message: self.message,
+            signature: self.signature,
+        };
+        winterfell::verify::<SchnorrAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            pub_key: self.pub_key,
+            message: [self.message[1], self.message[0]],
+            signature: self.signature,
+        };
+        winterfell::verify::<SchnorrAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+    }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        let pub_inputs = PublicInputs {
+            pub_key: self.pub_key,
+            message: self.message,
+            signature: self.signature,
+        };
+        elements_to_ints(&pub_inputs.to_elements())
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let inputs: Vec<BaseElement> = ints_to_elements(pub_inputs, 5)?;
+        let pub_inputs = PublicInputs {
+            pub_key: inputs[0].as_int(),
+            message: [inputs[1], inputs[2]],
+            signature: Signature {
+                r: inputs[3].as_int(),
+                s: inputs[4].as_int(),
+            },
+        };
+        winterfell::verify::<SchnorrAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+            .map_err(|err| err.to_string())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the range check used by this example.
+///
+/// All intermediate values and all quotient limbs of the exponentiation chiplet are range-checked
+/// to be 16-bit values.
+fn build_range_check() -> RangeCheck {
+    QUOTIENT_COLS
+        .iter()
+        .fold(
+            RangeCheckBuilder::new(RANGE_TABLE_COL, RANGE_MULTIPLICITY_COL, 0).check_u16(VALUE_COL),
+            |builder, &column| builder.check_u16(column),
+        )
+        .build()
+}
@@ -0,0 +1,144 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_range_check, get_challenge, inv_mod, BaseElement, DefaultConstraintEvaluator,
+    DefaultRandomCoin, DefaultTraceLde, ElementHasher, FieldElement, PhantomData, ProofOptions,
+    Prover, PublicInputs, SchnorrAir, SchnorrTrace, Signature, ACTIVE_COL, EXPONENT_BITS,
+    E_ACC_COL, E_BIT_COL, GENERATOR, LIMB_BITS, MODULUS, QUOTIENT_COLS, S_ACC_COL, S_BIT_COL,
+    TRACE_WIDTH, VALUE_COL,
+};
+use winterfell::MIN_RANGE_CHECK_TRACE_LENGTH;
+
+// SCHNORR SIGNATURE PROVER
+// ================================================================================================
+
+pub struct SchnorrProver<H: ElementHasher> {
+    pub_inputs: PublicInputs,
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SchnorrProver<H> {
+    pub fn new(
+        pub_key: u64,
+        message: [BaseElement; 2],
+        signature: Signature,
+        options: ProofOptions,
+    ) -> Self {
+        let pub_inputs = PublicInputs {
+            pub_key,
+            message,
+            signature,
+        };
+        Self {
+            pub_inputs,
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for verifying the specified signature.
+    ///
+    /// The exponentiation chiplet occupies the first [EXPONENT_BITS] + 1 rows of the trace, and
+    /// the range table of the range check chiplet occupies the first 2^16 rows of the trace.
+    /// Thus, the length of the trace is determined by the range check chiplet.
+    pub fn build_trace(&self) -> SchnorrTrace {
+        let PublicInputs {
+            pub_key,
+            message,
+            signature,
+        } = self.pub_inputs;
+        let trace_length = MIN_RANGE_CHECK_TRACE_LENGTH;
+        let mut trace = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+
+        let e = get_challenge(signature.r, pub_key, message);
+        let pub_key_inv = inv_mod(pub_key);
+
+        // fill the exponentiation chiplet
+        let mut value = 1;
+        let mut s_acc = 0;
+        let mut e_acc = 0;
+        for step in 0..EXPONENT_BITS {
+            let s_bit = (signature.s >> (EXPONENT_BITS - 1 - step)) & 1;
+            let e_bit = (e >> (EXPONENT_BITS - 1 - step)) & 1;
+
+            // the product fits into 64 bits because all values are smaller than 2^16
+            let mut product = value * value;
+            if s_bit == 1 {
+                product *= GENERATOR;
+            }
+            if e_bit == 1 {
+                product *= pub_key_inv;
+            }
+            let quotient = product / MODULUS;
+
+            let mut row = [BaseElement::ZERO; TRACE_WIDTH];
+            row[ACTIVE_COL] = BaseElement::ONE;
+            row[VALUE_COL] = BaseElement::from(value);
+            row[S_BIT_COL] = BaseElement::from(s_bit);
+            row[E_BIT_COL] = BaseElement::from(e_bit);
+            row[S_ACC_COL] = BaseElement::from(s_acc);
+            row[E_ACC_COL] = BaseElement::from(e_acc);
+            for (i, &column) in QUOTIENT_COLS.iter().enumerate() {
+                let limb = (quotient >> (i as u32 * LIMB_BITS)) & ((1 << LIMB_BITS) - 1);
+                row[column] = BaseElement::from(limb);
+            }
+            for (column, value) in trace.iter_mut().zip(row) {
+                column[step] = value;
+            }
+
+            value = product % MODULUS;
+            s_acc = 2 * s_acc + s_bit;
+            e_acc = 2 * e_acc + e_bit;
+        }
+        debug_assert_eq!(signature.r, value);
+
+        // once the chiplet becomes inactive, its results are copied until the end of the trace
+        trace[VALUE_COL][EXPONENT_BITS..].fill(BaseElement::from(value));
+        trace[S_ACC_COL][EXPONENT_BITS..].fill(BaseElement::from(s_acc));
+        trace[E_ACC_COL][EXPONENT_BITS..].fill(BaseElement::from(e_acc));
+
+        // fill the range check chiplet
+        let range_check = build_range_check();
+        range_check.fill_main_columns(&mut trace);
+
+        SchnorrTrace::new(trace, range_check)
+    }
+}
+
+impl<H: ElementHasher> Prover for SchnorrProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = SchnorrAir;
+    type Trace = SchnorrTrace;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        self.pub_inputs.clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: winterfell::AuxTraceRandElements<E>,
+        composition_coefficients: winterfell::ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
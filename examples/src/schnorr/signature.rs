@@ -0,0 +1,134 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::convert::TryInto;
+use winterfell::math::{fields::f64::BaseElement, StarkField};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Modulus of the prime field over which signatures are defined.
+///
+/// The modulus is deliberately tiny so that signature arithmetic is easy to follow; signatures
+/// over this field provide no security.
+pub const MODULUS: u64 = 65521;
+
+/// Generator of the signature group.
+pub const GENERATOR: u64 = 17;
+
+/// Number of bits needed to represent exponents used in signatures.
+pub const EXPONENT_BITS: usize = 16;
+
+/// Order of the multiplicative group of the field; all exponents are reduced modulo this value.
+const GROUP_ORDER: u64 = MODULUS - 1;
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+pub struct PrivateKey {
+    sec_key: u64,
+    pub_key: u64,
+}
+
+/// A Schnorr signature $(r, s)$ such that $g^s = r \cdot y^e$, where $y$ is the public key of
+/// the signer and $e$ is the challenge derived from $r$, $y$, and the signed message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    pub r: u64,
+    pub s: u64,
+}
+
+// PRIVATE KEY IMPLEMENTATION
+// ================================================================================================
+
+impl PrivateKey {
+    /// Returns a private key generated from the specified `seed`.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let sec_key = hash_to_exponent(&seed);
+        PrivateKey {
+            sec_key,
+            pub_key: pow_mod(GENERATOR, sec_key),
+        }
+    }
+
+    /// Returns a public key corresponding to this private key.
+    pub fn pub_key(&self) -> u64 {
+        self.pub_key
+    }
+
+    /// Signs the specified `message` with this private key.
+    ///
+    /// The nonce is derived deterministically from the secret key and the message.
+    pub fn sign(&self, message: [BaseElement; 2]) -> Signature {
+        let mut nonce_seed = self.sec_key.to_le_bytes().to_vec();
+        nonce_seed.extend_from_slice(&message_to_bytes(message));
+        let nonce = hash_to_exponent(&nonce_seed);
+
+        let r = pow_mod(GENERATOR, nonce);
+        let e = get_challenge(r, self.pub_key, message);
+        let s = (nonce + self.sec_key * e) % GROUP_ORDER;
+
+        Signature { r, s }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the specified signature was generated by signing the specified message with
+/// a private key corresponding to the specified public key.
+pub fn verify(pub_key: u64, message: [BaseElement; 2], sig: Signature) -> bool {
+    let e = get_challenge(sig.r, pub_key, message);
+    pow_mod(GENERATOR, sig.s) == mul_mod(sig.r, pow_mod(pub_key, e))
+}
+
+/// Returns the challenge for a signature with the specified `r` value, computed by hashing `r`
+/// together with the public key of the signer and the signed message.
+pub fn get_challenge(r: u64, pub_key: u64, message: [BaseElement; 2]) -> u64 {
+    let mut data = r.to_le_bytes().to_vec();
+    data.extend_from_slice(&pub_key.to_le_bytes());
+    data.extend_from_slice(&message_to_bytes(message));
+    hash_to_exponent(&data)
+}
+
+/// Reduces the specified message to two field elements.
+pub fn message_to_elements(message: &[u8]) -> [BaseElement; 2] {
+    let hash = *blake3::hash(message).as_bytes();
+    let m0 = u64::from_le_bytes(hash[..8].try_into().unwrap());
+    let m1 = u64::from_le_bytes(hash[8..16].try_into().unwrap());
+    [BaseElement::from(m0), BaseElement::from(m1)]
+}
+
+/// Returns `base` raised to the power `exp` modulo [MODULUS].
+pub fn pow_mod(base: u64, mut exp: u64) -> u64 {
+    let mut result = 1;
+    let mut base = base % MODULUS;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base);
+        }
+        base = mul_mod(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Returns the multiplicative inverse of `value` modulo [MODULUS].
+pub fn inv_mod(value: u64) -> u64 {
+    pow_mod(value, MODULUS - 2)
+}
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    (a * b) % MODULUS
+}
+
+fn hash_to_exponent(data: &[u8]) -> u64 {
+    let hash = *blake3::hash(data).as_bytes();
+    u64::from_le_bytes(hash[..8].try_into().unwrap()) % GROUP_ORDER
+}
+
+fn message_to_bytes(message: [BaseElement; 2]) -> Vec<u8> {
+    message.iter().flat_map(|m| m.as_int().to_le_bytes()).collect()
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Blake3_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn schnorr_test_basic_proof_verification() {
+    let schnorr = Box::new(super::SchnorrExample::<Blake3_256>::new(build_options(false)));
+    crate::tests::test_basic_proof_verification(schnorr);
+}
+
+#[test]
+fn schnorr_test_basic_proof_verification_fail() {
+    let schnorr = Box::new(super::SchnorrExample::<Blake3_256>::new(build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(schnorr);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 7)
+}
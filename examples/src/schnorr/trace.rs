@@ -0,0 +1,78 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, RangeCheck, TRACE_WIDTH};
+use winterfell::{AuxTraceError, ColMatrix, EvaluationFrame, Trace, TraceLayout, TraceMetadata};
+
+// SCHNORR SIGNATURE TRACE
+// ================================================================================================
+
+/// Execution trace for the Schnorr signature example.
+///
+/// The main segment of the trace contains columns of both the exponentiation and the range check
+/// chiplets. The auxiliary segment consists of the running sum column of the range check.
+pub struct SchnorrTrace {
+    layout: TraceLayout,
+    main_segment: ColMatrix<BaseElement>,
+    meta: TraceMetadata,
+    range_check: RangeCheck,
+}
+
+impl SchnorrTrace {
+    /// Returns a new trace instantiated from the provided columns of the main trace segment.
+    pub fn new(columns: Vec<Vec<BaseElement>>, range_check: RangeCheck) -> Self {
+        assert_eq!(TRACE_WIDTH, columns.len());
+        Self {
+            layout: TraceLayout::new(TRACE_WIDTH, [1], [range_check.num_rand_elements()]),
+            main_segment: ColMatrix::new(columns),
+            meta: TraceMetadata::new(),
+            range_check,
+        }
+    }
+}
+
+impl Trace for SchnorrTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    fn meta(&self) -> &TraceMetadata {
+        &self.meta
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.main_segment.read_row_into(row_idx, frame.current_mut());
+        self.main_segment.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Result<ColMatrix<E>, AuxTraceError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // there is only one auxiliary segment
+        if !aux_segments.is_empty() {
+            return Err(AuxTraceError::UndefinedSegment(aux_segments.len()));
+        }
+
+        let main_trace = self.main_segment.columns().collect::<Vec<_>>();
+        let running_sum = self.range_check.build_aux_column(&main_trace, rand_elements);
+        Ok(ColMatrix::new(vec![running_sum]))
+    }
+}
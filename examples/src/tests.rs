@@ -15,3 +15,47 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+pub fn test_proof_mutations_rejected(e: Box<dyn Example>) {
+    use winterfell::testing::{check_proof_mutations, MutationFailure};
+
+    // AIRs of the examples assert that the trace width specified in the proof is the width of
+    // their execution trace; thus, mutations of the trace layout are expected to cause panics
+    let proof = e.prove();
+    let failures = check_proof_mutations(&proof, 16, |proof| e.verify(proof))
+        .into_iter()
+        .filter(|failure| {
+            !matches!(failure, MutationFailure::VerifierPanic(m, _) if m.section == "trace_layout")
+        })
+        .map(|failure| failure.to_string())
+        .collect::<Vec<_>>();
+    assert!(
+        failures.is_empty(),
+        "proof mutations were not rejected:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[cfg(feature = "protobuf")]
+pub fn test_protobuf_round_trip(e: Box<dyn Example>) {
+    use winterfell::{protobuf, protobuf::prost::Message, StarkProof};
+
+    let proof = e.prove();
+    let message_bytes = protobuf::StarkProof::from(&proof).encode_to_vec();
+    let message = protobuf::StarkProof::decode(message_bytes.as_slice()).unwrap();
+    let decoded_proof = StarkProof::try_from(message).unwrap();
+    assert_eq!(proof.to_bytes(), decoded_proof.to_bytes());
+    assert!(e.verify(decoded_proof).is_ok());
+}
+
+pub fn test_verification_with_pub_inputs(e: Box<dyn Example>) {
+    let proof = e.prove();
+    let pub_inputs = e.get_pub_inputs();
+    assert!(e.verify_with_pub_inputs(proof.clone(), &pub_inputs).is_ok());
+
+    // verification against modified or malformed public inputs should fail
+    let mut wrong_inputs = pub_inputs.clone();
+    wrong_inputs[0] ^= 1;
+    assert!(e.verify_with_pub_inputs(proof.clone(), &wrong_inputs).is_err());
+    assert!(e.verify_with_pub_inputs(proof, &pub_inputs[1..]).is_err());
+}
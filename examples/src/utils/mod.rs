@@ -54,6 +54,55 @@ impl<E: FieldElement> EvaluationResult<E> for Vec<E> {
     }
 }
 
+// PUBLIC INPUT ENCODING
+// ================================================================================================
+
+/// Encodes the provided field elements as integers.
+///
+/// Each element is encoded as the little-endian integer represented by its serialized bytes; for
+/// the fields used in the examples, this is the canonical integer value of the element.
+pub fn elements_to_ints<B: StarkField>(elements: &[B]) -> Vec<u128> {
+    elements
+        .iter()
+        .map(|element| {
+            let mut bytes = [0u8; 16];
+            bytes[..B::ELEMENT_BYTES].copy_from_slice(&element.to_bytes());
+            u128::from_le_bytes(bytes)
+        })
+        .collect()
+}
+
+/// Decodes the provided integers into field elements; this is the inverse of
+/// [elements_to_ints()].
+///
+/// # Errors
+/// Returns an error if:
+/// * The number of integers is not equal to `num_elements`.
+/// * Any of the integers does not encode a valid field element.
+pub fn ints_to_elements<B: StarkField>(
+    values: &[u128],
+    num_elements: usize,
+) -> Result<Vec<B>, String> {
+    if values.len() != num_elements {
+        return Err(format!(
+            "expected {num_elements} public input elements, but {} were provided",
+            values.len()
+        ));
+    }
+
+    values
+        .iter()
+        .map(|&value| {
+            let bytes = value.to_le_bytes();
+            if bytes[B::ELEMENT_BYTES..].iter().any(|&byte| byte != 0) {
+                return Err(format!("{value} is not a valid field element"));
+            }
+            B::read_from_bytes(&bytes[..B::ELEMENT_BYTES])
+                .map_err(|_| format!("{value} is not a valid field element"))
+        })
+        .collect()
+}
+
 // OTHER FUNCTIONS
 // ================================================================================================
 
@@ -7,7 +7,7 @@ use crate::utils::{are_equal, EvaluationResult};
 use core::slice;
 use winterfell::{
     crypto::{Digest, Hasher},
-    math::{fields::f128::BaseElement, FieldElement},
+    math::{fields::f128::BaseElement, FieldElement, StarkField},
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
 };
 
@@ -149,9 +149,9 @@ impl Hash {
 
 impl Digest for Hash {
     fn as_bytes(&self) -> [u8; 32] {
-        let bytes = BaseElement::elements_as_bytes(&self.0);
         let mut result = [0; 32];
-        result[..bytes.len()].copy_from_slice(bytes);
+        result[..16].copy_from_slice(&self.0[0].as_int().to_le_bytes());
+        result[16..].copy_from_slice(&self.0[1].as_int().to_le_bytes());
         result
     }
 }
@@ -268,7 +268,7 @@ pub fn get_round_constants() -> Vec<Vec<BaseElement>> {
 #[allow(clippy::needless_range_loop)]
 fn apply_sbox<E: FieldElement>(state: &mut [E]) {
     for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(ALPHA.into());
+        state[i] = state[i].exp_const::<ALPHA>();
     }
 }
 
@@ -276,7 +276,7 @@ fn apply_sbox<E: FieldElement>(state: &mut [E]) {
 #[allow(clippy::needless_range_loop)]
 fn apply_inv_sbox(state: &mut [BaseElement]) {
     for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(INV_ALPHA);
+        state[i] = state[i].fifth_root();
     }
 }
 
@@ -317,10 +317,9 @@ fn apply_inv_mds<E: FieldElement + From<BaseElement>>(state: &mut [E]) {
 // CONSTANTS
 // ================================================================================================
 
-/// S-Box and Inverse S-Box powers;
+/// S-Box power; the inverse S-Box is computed as the fifth root of the state elements;
 /// computed using algorithm 6 from <https://eprint.iacr.org/2020/1143.pdf>
-const ALPHA: u32 = 5;
-const INV_ALPHA: u128 = 272225893536750770770699646362995969229;
+const ALPHA: u64 = 5;
 
 /// Rescue MDS matrix
 /// Computed using algorithm 4 from <https://eprint.iacr.org/2020/1143.pdf>
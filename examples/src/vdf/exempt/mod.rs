@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::utils::{elements_to_ints, ints_to_elements};
 use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
 use log::debug;
@@ -27,7 +28,6 @@ mod tests;
 // ================================================================================================
 
 const ALPHA: u64 = 3;
-const INV_ALPHA: u128 = 226854911280625642308916371969163307691;
 const FORTY_TWO: BaseElement = BaseElement::new(42);
 
 // VDF EXAMPLE
@@ -128,6 +128,20 @@ where
         };
         winterfell::verify::<VdfAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
     }
+
+    fn get_pub_inputs(&self) -> Vec<u128> {
+        elements_to_ints(&[self.seed, self.result])
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u128]) -> Result<(), String> {
+        let inputs = ints_to_elements(pub_inputs, 2)?;
+        let pub_inputs = VdfInputs {
+            seed: inputs[0],
+            result: inputs[1],
+        };
+        winterfell::verify::<VdfAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+            .map_err(|err| err.to_string())
+    }
 }
 
 // VDF FUNCTION
@@ -136,7 +150,7 @@ where
 fn execute_vdf(seed: BaseElement, n: usize) -> BaseElement {
     let mut state = seed;
     for _ in 0..(n - 1) {
-        state = (state - FORTY_TWO).exp(INV_ALPHA);
+        state = (state - FORTY_TWO).cube_root();
     }
     state
 }
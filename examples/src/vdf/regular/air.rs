@@ -55,7 +55,7 @@ impl Air for VdfAir {
         let current_state = frame.current()[0];
         let next_state = frame.next()[0];
 
-        result[0] = current_state - (next_state.exp(ALPHA.into()) + FORTY_TWO.into());
+        result[0] = current_state - (next_state.exp_const::<ALPHA>() + FORTY_TWO.into());
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
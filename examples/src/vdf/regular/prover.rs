@@ -6,7 +6,7 @@
 use super::{
     BaseElement, DefaultConstraintEvaluator, DefaultRandomCoin, DefaultTraceLde, ElementHasher,
     FieldElement, PhantomData, ProofOptions, Prover, Trace, TraceTable, VdfAir, VdfInputs,
-    FORTY_TWO, INV_ALPHA,
+    FORTY_TWO,
 };
 
 // VDF PROVER
@@ -31,7 +31,7 @@ impl<H: ElementHasher> VdfProver<H> {
 
         trace.push(state);
         for _ in 0..(n - 1) {
-            state = (state - FORTY_TWO).exp(INV_ALPHA);
+            state = (state - FORTY_TWO).cube_root();
             trace.push(state);
         }
 
@@ -6,6 +6,7 @@
 use core::fmt;
 
 use crypto::RandomCoinError;
+use utils::string::String;
 
 // VERIFIER ERROR
 // ================================================================================================
@@ -16,24 +17,56 @@ pub enum VerifierError {
     /// Attempt to draw a random value from a public coin failed.
     RandomCoinError(RandomCoinError),
     /// Folding factor specified for the protocol is not supported. Currently, supported folding
-    /// factors are: 4, 8, and 16.
+    /// factors are: 2, 4, 8, and 16.
     UnsupportedFoldingFactor(usize),
     /// Number of query positions does not match the number of provided evaluations.
     NumPositionEvaluationMismatch(usize, usize),
-    /// Evaluations at queried positions did not match layer commitment made by the prover.
-    LayerCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at one of the layers.
-    InvalidLayerFolding(usize),
+    /// Number of FRI layer commitments is inconsistent with the number of FRI layers implied by
+    /// the protocol options.
+    NumLayerCommitmentsMismatch(usize, usize),
+    /// Evaluations at queried positions did not match layer commitment made by the prover; the
+    /// error specifies the layer depth and the first position (in the layer commitment) for
+    /// which the Merkle authentication path did not resolve to the commitment.
+    LayerCommitmentMismatch(usize, usize),
+    /// Degree-respecting projection was not performed correctly at one of the layers; the error
+    /// specifies the layer depth and the queried position (in the layer domain) at which the
+    /// evaluation was inconsistent with the previous layer.
+    InvalidLayerFolding(usize, usize),
     /// FRI remainder did not match the commitment.
     RemainderCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at the last layer.
-    InvalidRemainderFolding,
+    /// Degree-respecting projection was not performed correctly at the last layer; the error
+    /// specifies the queried position (in the remainder domain) at which the evaluation was
+    /// inconsistent with the remainder polynomial.
+    InvalidRemainderFolding(usize),
     /// FRI remainder expected degree is greater than number of remainder values.
     RemainderDegreeNotValid,
     /// FRI remainder degree is greater than the polynomial degree expected for the last layer.
     RemainderDegreeMismatch(usize),
     /// Polynomial degree at one of the FRI layers could not be divided evenly by the folding factor.
     DegreeTruncation(usize, usize, usize),
+    /// Number of partitions used during FRI proof generation is greater than the number of
+    /// partitions supported by the evaluation domain.
+    NumPartitionsNotValid(usize, usize),
+    /// Commitment to the first FRI layer was skipped, but evaluations of the first layer were not
+    /// provided at the specified position of a queried coset.
+    FirstLayerEvaluationMissing(usize),
+    /// FRI proof could not be parsed into layer queries and the remainder.
+    ProofDeserializationError(String),
+    /// Number of opening points does not match the number of claimed values.
+    NumOpeningValuesMismatch(usize, usize),
+    /// Opening point at the specified index is in the evaluation domain of the commitment.
+    OpeningPointInDomain(usize),
+    /// Evaluations of a committed polynomial did not match the commitment; the error specifies
+    /// the first position for which the Merkle authentication path did not resolve to the
+    /// commitment.
+    EvaluationCommitmentMismatch(usize),
+    /// Number of opening points is greater than the number of points supported by the commitment
+    /// scheme.
+    NumOpeningPointsNotValid(usize, usize),
+    /// Opening point at the specified index is the same as another opening point.
+    DuplicateOpeningPoint(usize),
+    /// Opening proof is not consistent with the commitment and the claimed values.
+    InvalidOpeningProof,
 }
 
 impl fmt::Display for VerifierError {
@@ -49,17 +82,20 @@ impl fmt::Display for VerifierError {
             Self::NumPositionEvaluationMismatch(num_positions, num_evaluations) => write!(f,
                 "the number of query positions must be the same as the number of polynomial evaluations, but {num_positions} and {num_evaluations} were provided"
             ),
-            Self::LayerCommitmentMismatch => {
-                write!(f, "FRI queries did not match layer commitment made by the prover")
+            Self::NumLayerCommitmentsMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI layer commitments, but {actual} were provided")
             }
-            Self::InvalidLayerFolding(layer) => {
-                write!(f, "degree-respecting projection is not consistent at layer {layer}")
+            Self::LayerCommitmentMismatch(layer, position) => {
+                write!(f, "FRI query at position {position} did not match commitment to layer {layer}")
+            }
+            Self::InvalidLayerFolding(layer, position) => {
+                write!(f, "degree-respecting projection is not consistent at layer {layer} for query at position {position}")
             }
             Self::RemainderCommitmentMismatch => {
                 write!(f, "FRI remainder did not match the commitment")
             }
-            Self::InvalidRemainderFolding => {
-                write!(f, "degree-respecting projection is inconsistent at the last FRI layer")
+            Self::InvalidRemainderFolding(position) => {
+                write!(f, "degree-respecting projection is inconsistent at the last FRI layer for query at position {position}")
             }
             Self::RemainderDegreeNotValid => {
                 write!(f, "FRI remainder expected degree is greater than number of remainder values")
@@ -70,6 +106,33 @@ impl fmt::Display for VerifierError {
             Self::DegreeTruncation(degree, folding, layer) => {
                 write!(f, "degree reduction from {degree} by {folding} at layer {layer} results in degree truncation")
             }
+            Self::NumPartitionsNotValid(num_partitions, max_partitions) => {
+                write!(f, "FRI proof was generated using {num_partitions} partitions, but at most {max_partitions} partitions are supported")
+            }
+            Self::FirstLayerEvaluationMissing(position) => {
+                write!(f, "evaluation of the uncommitted first FRI layer at position {position} was not provided")
+            }
+            Self::ProofDeserializationError(msg) => {
+                write!(f, "FRI proof deserialization failed: {msg}")
+            }
+            Self::NumOpeningValuesMismatch(num_points, num_values) => write!(f,
+                "the number of opening points must be the same as the number of claimed values, but {num_points} and {num_values} were provided"
+            ),
+            Self::OpeningPointInDomain(index) => {
+                write!(f, "opening point at index {index} is in the evaluation domain")
+            }
+            Self::EvaluationCommitmentMismatch(position) => {
+                write!(f, "evaluation at position {position} did not match the polynomial commitment")
+            }
+            Self::NumOpeningPointsNotValid(num_points, max_points) => {
+                write!(f, "a commitment can be opened at most at {max_points} points, but {num_points} were provided")
+            }
+            Self::DuplicateOpeningPoint(index) => {
+                write!(f, "opening point at index {index} is duplicated")
+            }
+            Self::InvalidOpeningProof => {
+                write!(f, "opening proof is not consistent with the polynomial commitment")
+            }
         }
     }
 }
@@ -87,35 +87,80 @@ where
     B: StarkField,
     E: FieldElement<BaseField = B>,
 {
-    // build offset inverses and twiddles used during polynomial interpolation
-    let inv_offsets = get_inv_offsets(values.len(), domain_offset, N);
-    let inv_twiddles = get_inv_twiddles::<B>(N);
-    let len_offset = E::inv((N as u64).into());
-
     let mut result = unsafe { uninit_vector(values.len()) };
-    iter_mut!(result)
-        .zip(values)
-        .zip(inv_offsets)
-        .for_each(|((result, values), domain_offset)| {
-            // interpolate the values into a polynomial; this is similar to interpolation with
-            // offset implemented in math::fft module
-            let mut poly = *values;
-            serial_fft(&mut poly, &inv_twiddles);
-
-            let mut offset = len_offset;
-            let domain_offset = E::from(domain_offset);
-            for coeff in poly.iter_mut() {
-                *coeff *= offset;
-                offset *= domain_offset;
-            }
-
-            // evaluate the polynomial at alpha, and save the result
-            *result = polynom::eval(&poly, alpha)
-        });
+    fold_rows(&mut result, domain_offset, alpha, |i| values[i]);
+    result
+}
 
+/// Applies degree-respecting projection to evaluations of a polynomial stored in natural order.
+///
+/// This is equivalent to [apply_drp()], but does not require the `evaluations` to be transposed.
+/// Instead, evaluations needed to compute the $i$-th evaluation in the folded domain are read
+/// from positions $i, i + n / N, ..., i + (N - 1) \cdot n / N$ of `evaluations`, where $n$ is the
+/// number of evaluations. This avoids allocating a transposed copy of the evaluations.
+///
+/// ```
+/// # use math::{StarkField, FieldElement, fields::f128::BaseElement};
+/// # use rand_utils::{rand_value, rand_vector};
+/// # use utils::transpose_slice;
+/// # use winter_fri::folding::{apply_drp, apply_drp_strided};
+/// let evaluations: Vec<BaseElement> = rand_vector(64);
+/// let offset = BaseElement::GENERATOR;
+/// let alpha: BaseElement = rand_value();
+///
+/// let transposed_evaluations = transpose_slice::<BaseElement, 4>(&evaluations);
+/// assert_eq!(
+///     apply_drp(&transposed_evaluations, offset, alpha),
+///     apply_drp_strided::<_, _, 4>(&evaluations, offset, alpha)
+/// );
+/// ```
+///
+/// # Panics
+/// Panics if the number of `evaluations` is not divisible by `N`.
+pub fn apply_drp_strided<B, E, const N: usize>(
+    evaluations: &[E],
+    domain_offset: B,
+    alpha: E,
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let mut result = unsafe { uninit_vector(evaluations.len() / N) };
+    apply_drp_strided_into::<B, E, N>(evaluations, &mut result, domain_offset, alpha);
     result
 }
 
+/// Applies degree-respecting projection to evaluations of a polynomial stored in natural order
+/// and writes the results into `result`.
+///
+/// This is the same as [apply_drp_strided()], but allows the caller to reuse a buffer for the
+/// folded evaluations.
+///
+/// # Panics
+/// Panics if the number of `evaluations` is not equal to `result.len()` * `N`.
+pub(crate) fn apply_drp_strided_into<B, E, const N: usize>(
+    evaluations: &[E],
+    result: &mut [E],
+    domain_offset: B,
+    alpha: E,
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let row_count = result.len();
+    assert_eq!(
+        row_count * N,
+        evaluations.len(),
+        "number of evaluations must be {}, but was {}",
+        row_count * N,
+        evaluations.len()
+    );
+    fold_rows(result, domain_offset, alpha, |i| -> [E; N] {
+        core::array::from_fn(|j| evaluations[i + j * row_count])
+    });
+}
+
 // POSITION FOLDING
 // ================================================================================================
 /// Maps positions in the source domain, to positions in the folded domain.
@@ -174,9 +219,113 @@ pub fn fold_positions(
     result
 }
 
+/// Maps positions in the source domain to positions in each of the `num_layers` successively
+/// folded domains.
+///
+/// The $i$-th element of the result contains positions in the domain obtained by folding the
+/// source domain $i + 1$ times (see [fold_positions()]). These are also the indexes of the leaves
+/// which need to be opened in the Merkle tree of the $i$-th FRI layer, assuming the layer was
+/// committed to as a single partition.
+///
+/// Folded positions are computed once for all layers, and thus, can be shared between the prover
+/// (when building FRI layer proofs) and verifiers (including recursive verifiers) which need to
+/// know which rows were opened at each layer.
+///
+/// ```
+/// # use winter_fri::folding::{fold_positions, fold_positions_by_layer};
+/// let positions = [1, 9, 12, 20];
+/// let layer_positions = fold_positions_by_layer(&positions, 64, 4, 2);
+///
+/// assert_eq!(vec![1, 9, 12, 4], layer_positions[0]);
+/// assert_eq!(fold_positions(&layer_positions[0], 16, 4), layer_positions[1]);
+/// ```
+pub fn fold_positions_by_layer(
+    positions: &[usize],
+    source_domain_size: usize,
+    folding_factor: usize,
+    num_layers: usize,
+) -> Vec<Vec<usize>> {
+    let mut result: Vec<Vec<usize>> = Vec::with_capacity(num_layers);
+    let mut domain_size = source_domain_size;
+    for i in 0..num_layers {
+        let source_positions = if i == 0 { positions } else { &result[i - 1] };
+        let folded_positions = fold_positions(source_positions, domain_size, folding_factor);
+        result.push(folded_positions);
+        domain_size /= folding_factor;
+    }
+    result
+}
+
+/// Maps positions in a folded domain to all positions in the source domain which fold into them.
+///
+/// This is the inverse of [fold_positions()]: for each position $p$ in the domain obtained by
+/// folding the source domain, the result contains positions $p + j \cdot n / k$ for
+/// $j = 0, ..., k - 1$, where $n$ is the size of the source domain and $k$ is the folding factor.
+/// Positions in the result are grouped by the folded position they map to, in the order in which
+/// folded positions appear in `positions`.
+///
+/// ```
+/// # use winter_fri::folding::{fold_positions, unfold_positions};
+/// let positions = [1, 6];
+/// let source_positions = unfold_positions(&positions, 32, 4);
+///
+/// assert_eq!(vec![1, 9, 17, 25, 6, 14, 22, 30], source_positions);
+/// assert_eq!(positions.to_vec(), fold_positions(&source_positions, 32, 4));
+/// ```
+pub fn unfold_positions(
+    positions: &[usize],
+    source_domain_size: usize,
+    folding_factor: usize,
+) -> Vec<usize> {
+    let target_domain_size = source_domain_size / folding_factor;
+
+    let mut result = Vec::with_capacity(positions.len() * folding_factor);
+    for &position in positions {
+        debug_assert!(position < target_domain_size, "position {position} is out of bounds");
+        result.extend((0..folding_factor).map(|j| position + j * target_domain_size));
+    }
+
+    result
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Applies degree-respecting projection to `result.len()` rows of `N` evaluations each, where
+/// `row` returns the evaluations of the row at the specified index, and writes the results into
+/// `result`.
+fn fold_rows<B, E, F, const N: usize>(result: &mut [E], domain_offset: B, alpha: E, row: F)
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    F: Fn(usize) -> [E; N] + Send + Sync,
+{
+    // build offset inverses and twiddles used during polynomial interpolation
+    let inv_offsets = get_inv_offsets(result.len(), domain_offset, N);
+    let inv_twiddles = get_inv_twiddles::<B>(N);
+    let len_offset = E::inv((N as u64).into());
+
+    iter_mut!(result)
+        .enumerate()
+        .zip(inv_offsets)
+        .for_each(|((i, result), domain_offset)| {
+            // interpolate the values into a polynomial; this is similar to interpolation with
+            // offset implemented in math::fft module
+            let mut poly = row(i);
+            serial_fft(&mut poly, &inv_twiddles);
+
+            let mut offset = len_offset;
+            let domain_offset = E::from(domain_offset);
+            for coeff in poly.iter_mut() {
+                *coeff *= offset;
+                offset *= domain_offset;
+            }
+
+            // evaluate the polynomial at alpha, and save the result
+            *result = polynom::eval(&poly, alpha)
+        });
+}
+
 fn get_inv_offsets<B>(domain_size: usize, domain_offset: B, folding_factor: usize) -> Vec<B>
 where
     B: StarkField,
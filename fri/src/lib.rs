@@ -44,6 +44,13 @@
 //!    are provided to the [verify()](FriVerifier::verify()) function directly. The values at
 //!    remaining layers, the verifier reads from the specified verifier channel.
 //!
+//! # Polynomial commitments
+//! The [pcs] module provides a [PolynomialCommitment](pcs::PolynomialCommitment) trait and its
+//! FRI-based implementation [FriPcs](pcs::FriPcs) which can be used independently of the STARK
+//! protocol.
+//! When the `kzg` feature is enabled, the module also provides a KZG-based implementation of the
+//! same trait over BN254 and BLS12-381 curves.
+//!
 //! # Protocol parameters
 //! The current implementation supports executing FRI protocol with dynamically configurable
 //! parameters including:
@@ -84,4 +91,9 @@ pub use proof::FriProof;
 mod errors;
 pub use errors::VerifierError;
 
+pub mod pcs;
+
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+
 pub mod utils;
@@ -14,6 +14,7 @@ pub struct FriOptions {
     folding_factor: usize,
     remainder_max_degree: usize,
     blowup_factor: usize,
+    skip_first_layer_commitment: bool,
 }
 
 impl FriOptions {
@@ -40,9 +41,45 @@ impl FriOptions {
             folding_factor,
             remainder_max_degree,
             blowup_factor,
+            skip_first_layer_commitment: false,
         }
     }
 
+    /// Returns these options updated to use the specified maximum degree of the remainder
+    /// polynomial.
+    ///
+    /// The remainder polynomial is sent to the verifier in the clear (in coefficient form), and
+    /// the verifier checks its degree directly. Thus, a larger remainder degree results in fewer
+    /// FRI layers (and fewer Merkle authentication paths per query), at the expense of sending
+    /// more remainder coefficients. For example, with a domain of size $2^{20}$, blowup factor 8,
+    /// and folding factor 4, the remainder degree of 7 requires 7 FRI layers, while the remainder
+    /// degree of 255 requires only 5 FRI layers.
+    ///
+    /// # Panics
+    /// Panics if `max_remainder_degree` is not one less than a power of two.
+    pub fn with_max_remainder_degree(mut self, max_remainder_degree: usize) -> Self {
+        assert!(
+            (max_remainder_degree + 1).is_power_of_two(),
+            "max remainder degree must be one less than a power of two, but was {max_remainder_degree}"
+        );
+        self.remainder_max_degree = max_remainder_degree;
+        self
+    }
+
+    /// Returns these options updated to skip (or not) the commitment to the first FRI layer.
+    ///
+    /// When the commitment is skipped, the prover does not build a Merkle tree for the first FRI
+    /// layer, and the verifier derives the values of the first layer at the queried positions
+    /// from evaluations provided to it directly. This is useful when these evaluations are already
+    /// authenticated against some other commitment (e.g., DEEP composition polynomial evaluations
+    /// computed from opened trace and constraint values). In this case, the verifier must be
+    /// given evaluations at all positions of the first layer which fold into the queried
+    /// positions of the second layer.
+    pub fn with_skip_first_layer_commitment(mut self, skip: bool) -> Self {
+        self.skip_first_layer_commitment = skip;
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
@@ -91,4 +128,54 @@ impl FriOptions {
         }
         result
     }
+
+    /// Returns true if the commitment to the first FRI layer is skipped.
+    pub fn skip_first_layer_commitment(&self) -> bool {
+        self.skip_first_layer_commitment
+    }
+
+    /// Computes and returns the number of FRI layers committed to by the prover for a domain of
+    /// the specified size.
+    ///
+    /// This is the same as the number of FRI layers, unless the commitment to the first FRI layer
+    /// is skipped, in which case it is one less.
+    pub fn num_committed_layers(&self, domain_size: usize) -> usize {
+        let num_layers = self.num_fri_layers(domain_size);
+        if self.skip_first_layer_commitment && num_layers > 0 {
+            num_layers - 1
+        } else {
+            num_layers
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::FriOptions;
+
+    #[test]
+    fn num_fri_layers() {
+        let domain_size = 1 << 20;
+        let options = FriOptions::new(8, 4, 7);
+        assert_eq!(7, options.num_fri_layers(domain_size));
+
+        let options = options.with_max_remainder_degree(255);
+        assert_eq!(255, options.remainder_max_degree());
+        assert_eq!(5, options.num_fri_layers(domain_size));
+        assert_eq!(5, options.num_committed_layers(domain_size));
+
+        let options = options.with_skip_first_layer_commitment(true);
+        assert_eq!(5, options.num_fri_layers(domain_size));
+        assert_eq!(4, options.num_committed_layers(domain_size));
+        assert_eq!(0, options.num_committed_layers(8 * 256));
+    }
+
+    #[test]
+    #[should_panic(expected = "max remainder degree must be one less than a power of two")]
+    fn max_remainder_degree_not_valid() {
+        let _ = FriOptions::new(8, 4, 7).with_max_remainder_degree(100);
+    }
 }
@@ -0,0 +1,231 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains a KZG polynomial commitment scheme over pairing-friendly elliptic curves.
+
+use super::PolynomialCommitment;
+use crate::VerifierError;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, Zero};
+use utils::collections::Vec;
+
+// TYPE ALIASES
+// ================================================================================================
+
+/// KZG commitment scheme over the BN254 curve.
+pub type KzgBn254 = Kzg<ark_bn254::Bn254>;
+
+/// KZG commitment scheme over the BLS12-381 curve.
+pub type KzgBls12_381 = Kzg<ark_bls12_381::Bls12_381>;
+
+// KZG POLYNOMIAL COMMITMENT
+// ================================================================================================
+
+/// Implements the KZG polynomial commitment scheme over a pairing-friendly curve `P`.
+///
+/// The scheme is instantiated from a structured reference string (SRS) consisting of powers of a
+/// secret τ in both source groups of the pairing. A polynomial *p* is committed to as [*p(τ)*]₁.
+/// To open the commitment at points *z_i*, the prover computes the quotient *q* of dividing *p* by
+/// the vanishing polynomial *Z(x)* = ∏(*x* - *z_i*) and sends [*q(τ)*]₁ as the proof. The verifier
+/// interpolates the claimed values into a polynomial *I* and checks that
+/// e(*C* - [*I(τ)*]₁, [1]₂) = e(π, [*Z(τ)*]₂).
+///
+/// Thus, the number of G1 powers in the SRS bounds the size of committed polynomials, and the
+/// number of G2 powers bounds the number of points at which a commitment can be opened at once.
+pub struct Kzg<P: Pairing> {
+    powers_of_g1: Vec<P::G1Affine>,
+    powers_of_g2: Vec<P::G2Affine>,
+}
+
+impl<P: Pairing> Kzg<P> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new KZG commitment scheme instantiated from the specified SRS.
+    ///
+    /// `powers_of_g1` must contain [τ^i]₁ for all i in 0..`poly_size`, and `powers_of_g2` must
+    /// contain [τ^i]₂ for all i in 0..=`max_points`.
+    ///
+    /// # Panics
+    /// Panics if `powers_of_g1` is empty or `powers_of_g2` contains fewer than two elements.
+    pub fn new(powers_of_g1: Vec<P::G1Affine>, powers_of_g2: Vec<P::G2Affine>) -> Self {
+        assert!(!powers_of_g1.is_empty(), "at least one power of G1 must be provided");
+        assert!(powers_of_g2.len() >= 2, "at least two powers of G2 must be provided");
+        Kzg {
+            powers_of_g1,
+            powers_of_g2,
+        }
+    }
+
+    /// Returns a new KZG commitment scheme with an SRS generated from the specified `tau`.
+    ///
+    /// This is intended for testing purposes only: anyone who knows τ can open a commitment to
+    /// arbitrary values.
+    ///
+    /// # Panics
+    /// Panics if `poly_size` or `max_points` is zero.
+    pub fn setup_insecure(poly_size: usize, max_points: usize, tau: P::ScalarField) -> Self {
+        assert!(poly_size > 0, "polynomial size must be greater than zero");
+        assert!(max_points > 0, "max number of opening points must be greater than zero");
+
+        let powers_of_tau = (0..poly_size.max(max_points + 1))
+            .scan(P::ScalarField::one(), |power, _| {
+                let result = *power;
+                *power *= tau;
+                Some(result)
+            })
+            .collect::<Vec<_>>();
+
+        let g1 = P::G1Affine::generator();
+        let powers_of_g1 = powers_of_tau[..poly_size].iter().map(|&t| g1 * t).collect::<Vec<_>>();
+        let g2 = P::G2Affine::generator();
+        let powers_of_g2 =
+            powers_of_tau[..max_points + 1].iter().map(|&t| g2 * t).collect::<Vec<_>>();
+
+        Self::new(P::G1::normalize_batch(&powers_of_g1), P::G2::normalize_batch(&powers_of_g2))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the maximum number of points at which a commitment can be opened at once.
+    pub fn max_points(&self) -> usize {
+        self.powers_of_g2.len() - 1
+    }
+}
+
+impl<P: Pairing> PolynomialCommitment<P::ScalarField> for Kzg<P> {
+    type Commitment = P::G1Affine;
+    type ProverData = Vec<P::ScalarField>;
+    type Proof = P::G1Affine;
+    type Error = VerifierError;
+
+    fn poly_size(&self) -> usize {
+        self.powers_of_g1.len()
+    }
+
+    fn commit(&self, poly: &[P::ScalarField]) -> (Self::Commitment, Self::ProverData) {
+        assert!(
+            poly.len() <= self.poly_size(),
+            "polynomial cannot have more than {} coefficients, but had {}",
+            self.poly_size(),
+            poly.len()
+        );
+        let commitment = P::G1::msm_unchecked(&self.powers_of_g1[..poly.len()], poly);
+        (commitment.into_affine(), poly.to_vec())
+    }
+
+    fn open(
+        &self,
+        data: &Self::ProverData,
+        points: &[P::ScalarField],
+    ) -> (Vec<P::ScalarField>, Self::Proof) {
+        assert!(
+            points.len() <= self.max_points(),
+            "cannot open a commitment at more than {} points, but {} were requested",
+            self.max_points(),
+            points.len()
+        );
+        let values = points.iter().map(|&z| eval(data, z)).collect();
+
+        // dividing by each (x - z_i) in turn and dropping remainders yields the quotient of
+        // dividing the polynomial by the vanishing polynomial of all points
+        let quotient = points.iter().fold(data.clone(), |poly, &z| divide_by_linear(&poly, z));
+        let proof = P::G1::msm_unchecked(&self.powers_of_g1[..quotient.len()], &quotient);
+
+        (values, proof.into_affine())
+    }
+
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        points: &[P::ScalarField],
+        values: &[P::ScalarField],
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        if points.len() != values.len() {
+            return Err(VerifierError::NumOpeningValuesMismatch(points.len(), values.len()));
+        }
+        let max_points = self.max_points().min(self.poly_size());
+        if points.len() > max_points {
+            return Err(VerifierError::NumOpeningPointsNotValid(points.len(), max_points));
+        }
+
+        let vanishing_poly = points
+            .iter()
+            .fold(vec![P::ScalarField::one()], |poly, &z| mul_by_linear(&poly, z));
+        let interpolated_poly = interpolate(points, values)?;
+
+        let vanishing_commitment =
+            P::G2::msm_unchecked(&self.powers_of_g2[..vanishing_poly.len()], &vanishing_poly);
+        let interpolated_commitment =
+            P::G1::msm_unchecked(&self.powers_of_g1[..interpolated_poly.len()], &interpolated_poly);
+
+        let lhs = commitment.into_group() - interpolated_commitment;
+        let result = P::multi_pairing(
+            [lhs.into_affine(), (-proof.into_group()).into_affine()],
+            [self.powers_of_g2[0], vanishing_commitment.into_affine()],
+        );
+        if !result.is_zero() {
+            return Err(VerifierError::InvalidOpeningProof);
+        }
+
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates polynomial `p` at `x` using Horner's method.
+fn eval<F: Field>(p: &[F], x: F) -> F {
+    p.iter().rev().fold(F::zero(), |acc, &coeff| acc * x + coeff)
+}
+
+/// Returns the quotient of dividing polynomial `p` by (x - `z`); the remainder is dropped.
+fn divide_by_linear<F: Field>(p: &[F], z: F) -> Vec<F> {
+    if p.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![F::zero(); p.len() - 1];
+    let mut carry = F::zero();
+    for i in (1..p.len()).rev() {
+        carry = p[i] + carry * z;
+        result[i - 1] = carry;
+    }
+    result
+}
+
+/// Returns the product of polynomial `p` and (x - `z`).
+fn mul_by_linear<F: Field>(p: &[F], z: F) -> Vec<F> {
+    let mut result = vec![F::zero(); p.len() + 1];
+    for (i, &coeff) in p.iter().enumerate() {
+        result[i + 1] += coeff;
+        result[i] -= coeff * z;
+    }
+    result
+}
+
+/// Returns coefficients of the polynomial which evaluates to `values` at `points`.
+///
+/// Returns an error if `points` contain duplicates.
+fn interpolate<F: Field>(points: &[F], values: &[F]) -> Result<Vec<F>, VerifierError> {
+    let mut result = vec![F::zero(); points.len()];
+    for (i, (&xi, &yi)) in points.iter().zip(values).enumerate() {
+        let mut basis = vec![F::one()];
+        let mut denominator = F::one();
+        for (j, &xj) in points.iter().enumerate() {
+            if i != j {
+                basis = mul_by_linear(&basis, xj);
+                denominator *= xi - xj;
+            }
+        }
+        let scale = yi * denominator.inverse().ok_or(VerifierError::DuplicateOpeningPoint(i))?;
+        for (r, &b) in result.iter_mut().zip(&basis) {
+            *r += b * scale;
+        }
+    }
+    Ok(result)
+}
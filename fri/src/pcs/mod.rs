@@ -0,0 +1,393 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains a polynomial commitment scheme built on top of the FRI protocol.
+
+use crate::{
+    DefaultProverChannel, DefaultVerifierChannel, FriOptions, FriProof, FriProver, FriVerifier,
+    VerifierError,
+};
+use core::marker::PhantomData;
+use crypto::{BatchMerkleProof, ElementHasher, MerkleTree, RandomCoin};
+use math::{batch_inversion, fft, polynom, FieldElement, StarkField};
+use utils::{collections::Vec, string::ToString};
+
+#[cfg(feature = "kzg")]
+mod kzg;
+#[cfg(feature = "kzg")]
+pub use kzg::{Kzg, KzgBls12_381, KzgBn254};
+
+#[cfg(test)]
+mod tests;
+
+// POLYNOMIAL COMMITMENT TRAIT
+// ================================================================================================
+
+/// Defines an interface for a polynomial commitment scheme.
+///
+/// A polynomial commitment scheme allows a prover to commit to a polynomial, and later to prove
+/// that the committed polynomial evaluates to claimed values at a set of points chosen after the
+/// commitment was made.
+///
+/// The polynomial coefficients are elements of type `E`. For FRI-based commitments, these are
+/// elements of a STARK field, while pairing-based commitments operate over the scalar field of the
+/// underlying curve.
+///
+/// Implementations of this trait are non-interactive: any randomness needed to open a commitment
+/// is derived from the commitment, the opening points, and the claimed values.
+pub trait PolynomialCommitment<E> {
+    /// Commitment to a polynomial sent to the verifier.
+    type Commitment: Clone;
+
+    /// Data retained by the prover to open a commitment.
+    type ProverData;
+
+    /// Proof that a committed polynomial evaluates to claimed values at a set of points.
+    type Proof;
+
+    /// Error which can occur during verification of an opening proof.
+    type Error;
+
+    /// Returns the maximum number of coefficients in a polynomial which can be committed to.
+    fn poly_size(&self) -> usize;
+
+    /// Commits to the polynomial specified by its coefficients `poly`.
+    ///
+    /// Returns the commitment together with the data the prover needs to open it later.
+    ///
+    /// # Panics
+    /// Panics if `poly` has more than [poly_size()](PolynomialCommitment::poly_size())
+    /// coefficients.
+    fn commit(&self, poly: &[E]) -> (Self::Commitment, Self::ProverData);
+
+    /// Opens the committed polynomial at the specified `points`.
+    ///
+    /// Returns evaluations of the polynomial at the `points` together with a proof that these
+    /// evaluations are consistent with the commitment.
+    ///
+    /// # Panics
+    /// Panics if an opening could not be constructed for the specified `points`.
+    fn open(&self, data: &Self::ProverData, points: &[E]) -> (Vec<E>, Self::Proof);
+
+    /// Verifies that the polynomial committed to by `commitment` evaluates to `values` at the
+    /// specified `points`.
+    ///
+    /// # Errors
+    /// Returns an error if the `proof` is not a valid opening of the `commitment`.
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        points: &[E],
+        values: &[E],
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error>;
+}
+
+// FRI POLYNOMIAL COMMITMENT
+// ================================================================================================
+
+/// Implements a polynomial commitment scheme based on DEEP-FRI.
+///
+/// A polynomial is committed to by evaluating it over the FRI evaluation domain (a coset of size
+/// `poly_size` * `blowup_factor`) and building a Merkle tree from these evaluations. To open the
+/// commitment at points *z_i*, the prover combines quotients (*p(x)* - *p(z_i)*) / (*x* - *z_i*)
+/// into a single polynomial using powers of a random value γ, and proves via FRI that the result
+/// is a polynomial of degree smaller than `poly_size`. The verifier checks evaluations of the
+/// combined quotient at the queried positions against the commitment to the original polynomial.
+///
+/// All randomness is drawn from a public coin of type `R` seeded with the commitment, the opening
+/// points, and the claimed values. Since the FRI degree check is performed on the quotient, a
+/// valid opening guarantees that the committed evaluations are close to a polynomial of degree
+/// at most `poly_size`.
+///
+/// FRI options which skip the commitment to the first FRI layer are not supported.
+pub struct FriPcs<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    options: FriOptions,
+    poly_size: usize,
+    num_queries: usize,
+    _field_element: PhantomData<E>,
+    _public_coin: PhantomData<R>,
+}
+
+/// Data retained by the prover after committing to a polynomial with [FriPcs].
+pub struct FriPcsProverData<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    poly: Vec<E>,
+    evaluations: Vec<E>,
+    tree: MerkleTree<H>,
+}
+
+/// Opening proof produced by [FriPcs].
+pub struct FriPcsProof<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    /// Evaluations of the committed polynomial at the queried positions.
+    pub evaluations: Vec<E>,
+    /// Merkle authentication paths for the queried evaluations.
+    pub evaluation_proof: BatchMerkleProof<H>,
+    /// Commitments to the FRI layers of the combined quotient polynomial.
+    pub fri_commitments: Vec<H::Digest>,
+    /// FRI proof for the combined quotient polynomial.
+    pub fri_proof: FriProof,
+}
+
+impl<E, H, R> FriPcs<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    /// Returns a new FRI-based polynomial commitment scheme for polynomials with at most
+    /// `poly_size` coefficients; `num_queries` specifies the number of positions queried when
+    /// a commitment is opened.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `poly_size` is not a power of two or is smaller than the folding factor.
+    /// * `num_queries` is zero, or is greater than or equal to the size of the evaluation domain.
+    /// * `options` specify that the commitment to the first FRI layer should be skipped.
+    pub fn new(options: FriOptions, poly_size: usize, num_queries: usize) -> Self {
+        assert!(poly_size.is_power_of_two(), "polynomial size must be a power of two");
+        assert!(
+            poly_size >= options.folding_factor(),
+            "polynomial size must be at least {}, but was {poly_size}",
+            options.folding_factor()
+        );
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        assert!(
+            num_queries < poly_size * options.blowup_factor(),
+            "number of queries must be smaller than the evaluation domain size"
+        );
+        assert!(
+            !options.skip_first_layer_commitment(),
+            "skipping the first FRI layer commitment is not supported"
+        );
+        FriPcs {
+            options,
+            poly_size,
+            num_queries,
+            _field_element: PhantomData,
+            _public_coin: PhantomData,
+        }
+    }
+
+    /// Returns the size of the domain over which committed polynomials are evaluated.
+    pub fn domain_size(&self) -> usize {
+        self.poly_size * self.options.blowup_factor()
+    }
+
+    /// Returns the number of positions queried when a commitment is opened.
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    /// Returns FRI options used by this commitment scheme.
+    pub fn options(&self) -> &FriOptions {
+        &self.options
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a public coin seeded with the commitment, opening points and claimed values, and
+    /// the random value γ drawn from it.
+    fn build_coin(
+        &self,
+        commitment: &H::Digest,
+        points: &[E],
+        values: &[E],
+    ) -> Result<(R, E), VerifierError> {
+        let mut public_coin = R::new(&[]);
+        public_coin.reseed(*commitment);
+        public_coin.reseed(H::hash_elements(points));
+        public_coin.reseed(H::hash_elements(values));
+        let gamma = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+        Ok((public_coin, gamma))
+    }
+
+    /// Returns true if `point` belongs to the evaluation domain of this commitment scheme.
+    fn is_in_domain(&self, point: E) -> bool {
+        let domain_size = self.domain_size() as u64;
+        let offset = E::from(self.options.domain_offset::<E::BaseField>());
+        point.exp_vartime(domain_size.into()) == offset.exp_vartime(domain_size.into())
+    }
+}
+
+impl<E, H, R> PolynomialCommitment<E> for FriPcs<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    type Commitment = H::Digest;
+    type ProverData = FriPcsProverData<E, H>;
+    type Proof = FriPcsProof<E, H>;
+    type Error = VerifierError;
+
+    fn poly_size(&self) -> usize {
+        self.poly_size
+    }
+
+    fn commit(&self, poly: &[E]) -> (Self::Commitment, Self::ProverData) {
+        assert!(
+            poly.len() <= self.poly_size,
+            "polynomial cannot have more than {} coefficients, but had {}",
+            self.poly_size,
+            poly.len()
+        );
+        let mut poly = poly.to_vec();
+        poly.resize(self.poly_size, E::ZERO);
+
+        let twiddles = fft::get_twiddles::<E::BaseField>(self.poly_size);
+        let evaluations = fft::evaluate_poly_with_offset(
+            &poly,
+            &twiddles,
+            self.options.domain_offset(),
+            self.options.blowup_factor(),
+        );
+
+        let leaves = evaluations.iter().map(|&e| H::hash_elements(&[e])).collect();
+        let tree = MerkleTree::<H>::new(leaves).expect("failed to build evaluation tree");
+        (
+            *tree.root(),
+            FriPcsProverData {
+                poly,
+                evaluations,
+                tree,
+            },
+        )
+    }
+
+    fn open(&self, data: &Self::ProverData, points: &[E]) -> (Vec<E>, Self::Proof) {
+        assert!(
+            points.iter().all(|&z| !self.is_in_domain(z)),
+            "opening points must not be in the evaluation domain"
+        );
+        let values = points.iter().map(|&z| polynom::eval(&data.poly, z)).collect::<Vec<_>>();
+        let (public_coin, gamma) = self
+            .build_coin(data.tree.root(), points, &values)
+            .expect("failed to draw opening coefficient");
+
+        // evaluate the combined quotient over the evaluation domain
+        let domain_size = self.domain_size();
+        let domain = math::get_power_series_with_offset(
+            E::BaseField::get_root_of_unity(domain_size.ilog2()),
+            self.options.domain_offset(),
+            domain_size,
+        );
+        let mut quotient = vec![E::ZERO; domain_size];
+        let mut coefficient = E::ONE;
+        for (&z, &value) in points.iter().zip(values.iter()) {
+            let denominators =
+                batch_inversion(&domain.iter().map(|&x| E::from(x) - z).collect::<Vec<_>>());
+            for ((q, &p), &d) in quotient.iter_mut().zip(&data.evaluations).zip(&denominators) {
+                *q += coefficient * (p - value) * d;
+            }
+            coefficient *= gamma;
+        }
+
+        // prove that the combined quotient is a polynomial of the expected degree
+        let mut channel = DefaultProverChannel::<E, H, R>::with_public_coin(
+            public_coin,
+            domain_size,
+            self.num_queries,
+        );
+        let mut prover = FriProver::new(self.options.clone());
+        prover.build_layers(&mut channel, quotient);
+        let positions = channel.draw_query_positions(0);
+        let fri_proof = prover.build_proof(&positions);
+
+        let evaluations = positions.iter().map(|&p| data.evaluations[p]).collect();
+        let evaluation_proof =
+            data.tree.prove_batch(&positions).expect("failed to build evaluation proof");
+
+        let proof = FriPcsProof {
+            evaluations,
+            evaluation_proof,
+            fri_commitments: channel.layer_commitments().to_vec(),
+            fri_proof,
+        };
+        (values, proof)
+    }
+
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        points: &[E],
+        values: &[E],
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        if points.len() != values.len() {
+            return Err(VerifierError::NumOpeningValuesMismatch(points.len(), values.len()));
+        }
+        if let Some(idx) = points.iter().position(|&z| self.is_in_domain(z)) {
+            return Err(VerifierError::OpeningPointInDomain(idx));
+        }
+        let (mut public_coin, gamma) = self.build_coin(commitment, points, values)?;
+
+        // execute the FRI commit phase against the commitments to the combined quotient, and
+        // draw query positions after it
+        let domain_size = self.domain_size();
+        let mut channel = DefaultVerifierChannel::<E, H>::new(
+            proof.fri_proof.clone(),
+            proof.fri_commitments.clone(),
+            domain_size,
+            self.options.folding_factor(),
+        )
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let fri_verifier = FriVerifier::new(
+            &mut channel,
+            &mut public_coin,
+            self.options.clone(),
+            self.poly_size - 1,
+        )?;
+        let positions = public_coin
+            .draw_integers(self.num_queries, domain_size, 0)
+            .map_err(VerifierError::RandomCoinError)?;
+
+        // make sure the queried evaluations are consistent with the commitment
+        if proof.evaluations.len() != positions.len() {
+            return Err(VerifierError::NumPositionEvaluationMismatch(
+                positions.len(),
+                proof.evaluations.len(),
+            ));
+        }
+        let leaves = proof.evaluations.iter().map(|&e| H::hash_elements(&[e])).collect::<Vec<_>>();
+        if leaves != proof.evaluation_proof.leaves {
+            return Err(VerifierError::EvaluationCommitmentMismatch(positions[0]));
+        }
+        MerkleTree::verify_batch(commitment, &positions, &proof.evaluation_proof).map_err(
+            |_| {
+                let position =
+                    MerkleTree::find_invalid_index(commitment, &positions, &proof.evaluation_proof)
+                        .unwrap_or(positions[0]);
+                VerifierError::EvaluationCommitmentMismatch(position)
+            },
+        )?;
+
+        // compute evaluations of the combined quotient at the queried positions and check them
+        // against the FRI proof
+        let domain_generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
+        let offset = self.options.domain_offset::<E::BaseField>();
+        let mut quotient = vec![E::ZERO; positions.len()];
+        let mut coefficient = E::ONE;
+        for (&z, &value) in points.iter().zip(values) {
+            let denominators = batch_inversion(
+                &positions
+                    .iter()
+                    .map(|&p| E::from(offset * domain_generator.exp_vartime((p as u64).into())) - z)
+                    .collect::<Vec<_>>(),
+            );
+            for ((q, &p), &d) in quotient.iter_mut().zip(&proof.evaluations).zip(&denominators) {
+                *q += coefficient * (p - value) * d;
+            }
+            coefficient *= gamma;
+        }
+
+        fri_verifier.verify(&mut channel, &quotient, &positions)
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{FriPcs, PolynomialCommitment};
+use crate::{FriOptions, VerifierError};
+use crypto::{hashers::Blake3_256, DefaultRandomCoin};
+use math::{fields::f128::BaseElement, polynom, FieldElement};
+use utils::collections::Vec;
+
+type Blake3 = Blake3_256<BaseElement>;
+type Pcs = FriPcs<BaseElement, Blake3, DefaultRandomCoin<Blake3>>;
+
+// COMMIT/OPEN/VERIFY TESTS
+// ================================================================================================
+
+#[test]
+fn fri_pcs_open_verify() {
+    let pcs = Pcs::new(FriOptions::new(8, 4, 7), 1 << 10, 32);
+    let poly = build_poly(1 << 10);
+    let (commitment, data) = pcs.commit(&poly);
+
+    let points = [BaseElement::new(5), BaseElement::new(12345)];
+    let (values, proof) = pcs.open(&data, &points);
+    assert_eq!(polynom::eval(&poly, points[0]), values[0]);
+    assert_eq!(polynom::eval(&poly, points[1]), values[1]);
+    assert_eq!(Ok(()), pcs.verify(&commitment, &points, &values, &proof));
+
+    // a smaller polynomial can be committed to as well
+    let (commitment, data) = pcs.commit(&poly[..100]);
+    let (values, proof) = pcs.open(&data, &points[..1]);
+    assert_eq!(polynom::eval(&poly[..100], points[0]), values[0]);
+    assert_eq!(Ok(()), pcs.verify(&commitment, &points[..1], &values, &proof));
+}
+
+#[test]
+fn fri_pcs_reject_invalid_opening() {
+    let pcs = Pcs::new(FriOptions::new(8, 4, 7), 1 << 10, 32);
+    let poly = build_poly(1 << 10);
+    let (commitment, data) = pcs.commit(&poly);
+
+    let points = [BaseElement::new(5), BaseElement::new(12345)];
+    let (values, proof) = pcs.open(&data, &points);
+
+    // wrong claimed value
+    let mut wrong_values = values.clone();
+    wrong_values[1] += BaseElement::ONE;
+    assert!(pcs.verify(&commitment, &points, &wrong_values, &proof).is_err());
+
+    // wrong number of values
+    assert_eq!(
+        Err(VerifierError::NumOpeningValuesMismatch(2, 1)),
+        pcs.verify(&commitment, &points, &values[..1], &proof)
+    );
+
+    // opening point in the evaluation domain
+    let domain_point = [pcs.options().domain_offset::<BaseElement>(), points[1]];
+    assert_eq!(
+        Err(VerifierError::OpeningPointInDomain(0)),
+        pcs.verify(&commitment, &domain_point, &values, &proof)
+    );
+
+    // proof for a different commitment
+    let (other_commitment, _) = pcs.commit(&poly[..100]);
+    assert!(pcs.verify(&other_commitment, &points, &values, &proof).is_err());
+
+    // evaluations which do not match the commitment
+    let mut proof = proof;
+    proof.evaluations[0] += BaseElement::ONE;
+    assert!(matches!(
+        pcs.verify(&commitment, &points, &values, &proof),
+        Err(VerifierError::EvaluationCommitmentMismatch(_))
+    ));
+}
+
+#[cfg(feature = "kzg")]
+#[test]
+fn kzg_open_verify() {
+    use super::{KzgBls12_381, KzgBn254};
+    use ark_ff::One;
+
+    fn open_verify<F: ark_ff::PrimeField, P: PolynomialCommitment<F, Error = VerifierError>>(
+        pcs: P,
+    ) {
+        let poly = (0..pcs.poly_size() as u64).map(|i| F::from(i * i + 7)).collect::<Vec<_>>();
+        let (commitment, data) = pcs.commit(&poly);
+
+        let points = [F::from(5u64), F::from(12345u64), F::from(7u64)];
+        let (values, proof) = pcs.open(&data, &points);
+        assert_eq!(Ok(()), pcs.verify(&commitment, &points, &values, &proof));
+
+        // wrong claimed value
+        let mut wrong_values = values.clone();
+        wrong_values[1] += F::one();
+        assert_eq!(
+            Err(VerifierError::InvalidOpeningProof),
+            pcs.verify(&commitment, &points, &wrong_values, &proof)
+        );
+
+        // duplicate points
+        let duplicate_points = [points[0], points[1], points[0]];
+        assert_eq!(
+            Err(VerifierError::DuplicateOpeningPoint(0)),
+            pcs.verify(&commitment, &duplicate_points, &values, &proof)
+        );
+
+        // proof for a different commitment
+        let (other_commitment, _) = pcs.commit(&poly[..10]);
+        assert_eq!(
+            Err(VerifierError::InvalidOpeningProof),
+            pcs.verify(&other_commitment, &points, &values, &proof)
+        );
+    }
+
+    open_verify(KzgBn254::setup_insecure(64, 4, ark_bn254::Fr::from(123456789u64)));
+    open_verify(KzgBls12_381::setup_insecure(64, 3, ark_bls12_381::Fr::from(987654321u64)));
+
+    // a commitment cannot be opened at more points than supported by the SRS
+    let pcs = KzgBn254::setup_insecure(64, 2, ark_bn254::Fr::one());
+    let points = [ark_bn254::Fr::from(1u64); 3];
+    let (commitment, data) = pcs.commit(&[ark_bn254::Fr::one()]);
+    let (_, proof) = pcs.open(&data, &points[..2]);
+    assert_eq!(
+        Err(VerifierError::NumOpeningPointsNotValid(3, 2)),
+        pcs.verify(&commitment, &points, &points, &proof)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_poly(size: usize) -> Vec<BaseElement> {
+    (0..size as u128).map(|i| BaseElement::new(i * i + 7)).collect()
+}
@@ -6,7 +6,7 @@
 use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::FieldElement;
 use utils::{
-    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    collections::Vec, string::ToString, vector_from_fn, ByteReader, ByteWriter, Deserializable,
     DeserializationError, Serializable, SliceReader,
 };
 
@@ -30,9 +30,9 @@ use utils::{
 /// and [parse_remainder()](FriProof::parse_remainder()) methods can be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FriProof {
-    layers: Vec<FriProofLayer>,
-    remainder: Vec<u8>,
-    num_partitions: u8, // stored as power of 2
+    pub(crate) layers: Vec<FriProofLayer>,
+    pub(crate) remainder: Vec<u8>,
+    pub(crate) num_partitions: u8, // stored as power of 2
 }
 
 impl FriProof {
@@ -102,6 +102,10 @@ impl FriProof {
     /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
     /// authentication paths for each query (grouped into batch Merkle proofs).
     ///
+    /// If the commitment to the first FRI layer was skipped, the proof does not contain this
+    /// layer, and `domain_size` must be the size of the domain of the second FRI layer (i.e., the
+    /// evaluation domain size divided by the folding factor).
+    ///
     /// # Panics
     /// Panics if:
     /// * `domain_size` is not a power of two.
@@ -202,8 +206,13 @@ impl Deserializable for FriProof {
         let num_remainder_bytes = source.read_u16()? as usize;
         let remainder = source.read_vec(num_remainder_bytes)?;
 
-        // read number of partitions
+        // read number of partitions (stored as a power of two)
         let num_partitions = source.read_u8()?;
+        if num_partitions as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of partitions of 2^{num_partitions} cannot be represented on this platform"
+            )));
+        }
 
         Ok(FriProof {
             layers,
@@ -218,8 +227,8 @@ impl Deserializable for FriProof {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FriProofLayer {
-    values: Vec<u8>,
-    paths: Vec<u8>,
+    pub(crate) values: Vec<u8>,
+    pub(crate) paths: Vec<u8>,
 }
 
 impl FriProofLayer {
@@ -290,17 +299,14 @@ impl FriProofLayer {
                 "a FRI layer must contain at least one query".to_string(),
             ));
         }
-        let mut hashed_queries = vec![H::Digest::default(); num_queries];
-        let mut query_values = Vec::with_capacity(num_queries * folding_factor);
 
-        // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
+        // read bytes corresponding to all queries, convert them into field elements, and then
+        // hash values of each query to build leaf nodes of the batch Merkle proof
         let mut reader = SliceReader::new(&self.values);
-        for query_hash in hashed_queries.iter_mut() {
-            let mut qe = E::read_batch_from(&mut reader, folding_factor)?;
-            *query_hash = H::hash_elements(&qe);
-            query_values.append(&mut qe);
-        }
+        let query_values = E::read_batch_from(&mut reader, num_queries * folding_factor)?;
+        let hashed_queries = vector_from_fn(num_queries, |i| {
+            H::hash_elements(&query_values[i * folding_factor..(i + 1) * folding_factor])
+        });
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
@@ -0,0 +1,135 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains protobuf messages for FRI proofs and conversions between these messages and
+//! [FriProof](crate::FriProof).
+//!
+//! The messages mirror `FriProof` and `FriProofLayer` definitions in the `proof.proto` schema
+//! which is located in the `proto` directory of the `winter-air` crate. Messages are encoded and
+//! decoded via [prost::Message] trait.
+//!
+//! Conversion of a message back into a [FriProof](crate::FriProof) performs the same validation
+//! as deserialization of a proof from bytes.
+
+use crate::proof;
+use utils::{Deserializable, DeserializationError};
+
+// FRI PROOF MESSAGE
+// ================================================================================================
+
+/// Protobuf message for a [FriProof](crate::FriProof).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct FriProof {
+    /// Queried values and Merkle authentication paths for each FRI layer.
+    #[prost(message, repeated, tag = "1")]
+    pub layers: Vec<FriProofLayer>,
+    /// Serialized coefficients of the remainder polynomial.
+    #[prost(bytes = "vec", tag = "2")]
+    pub remainder: Vec<u8>,
+    /// Number of partitions into which the FRI domain was split; must be a power of two.
+    #[prost(uint32, tag = "3")]
+    pub num_partitions: u32,
+}
+
+impl From<&proof::FriProof> for FriProof {
+    fn from(proof: &proof::FriProof) -> Self {
+        Self {
+            layers: proof.layers.iter().map(FriProofLayer::from).collect(),
+            remainder: proof.remainder.clone(),
+            num_partitions: proof.num_partitions() as u32,
+        }
+    }
+}
+
+impl TryFrom<FriProof> for proof::FriProof {
+    type Error = DeserializationError;
+
+    /// Converts a protobuf message into a FRI proof.
+    ///
+    /// # Errors
+    /// Returns an error if the message does not describe a valid FRI proof.
+    fn try_from(message: FriProof) -> Result<Self, Self::Error> {
+        let mut bytes = Vec::new();
+        write_len(&mut bytes, message.layers.len(), 1, "number of FRI layers")?;
+        for layer in message.layers.iter() {
+            write_bytes(&mut bytes, &layer.values, 4, "FRI layer values")?;
+            write_bytes(&mut bytes, &layer.paths, 4, "FRI layer paths")?;
+        }
+        write_bytes(&mut bytes, &message.remainder, 2, "FRI remainder")?;
+
+        if !message.num_partitions.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of FRI partitions must be a power of two, but was {}",
+                message.num_partitions
+            )));
+        }
+        bytes.push(message.num_partitions.ilog2() as u8);
+
+        proof::FriProof::read_from_bytes(&bytes)
+    }
+}
+
+// FRI PROOF LAYER MESSAGE
+// ================================================================================================
+
+/// Protobuf message for a single layer of a [FriProof](crate::FriProof).
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct FriProofLayer {
+    /// Serialized values of the layer at queried positions.
+    #[prost(bytes = "vec", tag = "1")]
+    pub values: Vec<u8>,
+    /// Serialized batch Merkle proof for the queried values.
+    #[prost(bytes = "vec", tag = "2")]
+    pub paths: Vec<u8>,
+}
+
+impl From<&proof::FriProofLayer> for FriProofLayer {
+    fn from(layer: &proof::FriProofLayer) -> Self {
+        Self {
+            values: layer.values.clone(),
+            paths: layer.paths.clone(),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes `bytes` into `target` prefixed by their length encoded with `prefix_size` bytes.
+///
+/// # Errors
+/// Returns an error if the length of `bytes` cannot be encoded with `prefix_size` bytes.
+fn write_bytes(
+    target: &mut Vec<u8>,
+    bytes: &[u8],
+    prefix_size: usize,
+    name: &str,
+) -> Result<(), DeserializationError> {
+    write_len(target, bytes.len(), prefix_size, name)?;
+    target.extend_from_slice(bytes);
+    Ok(())
+}
+
+/// Writes `len` into `target` as a little-endian integer of `prefix_size` bytes.
+///
+/// # Errors
+/// Returns an error if `len` cannot be encoded with `prefix_size` bytes.
+fn write_len(
+    target: &mut Vec<u8>,
+    len: usize,
+    prefix_size: usize,
+    name: &str,
+) -> Result<(), DeserializationError> {
+    debug_assert!(prefix_size <= 4, "prefix size cannot exceed 4 bytes");
+    let len = len as u64;
+    if len >= 1 << (8 * prefix_size) {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{name} cannot be greater than {}, but was {len}",
+            (1_u64 << (8 * prefix_size)) - 1
+        )));
+    }
+    target.extend_from_slice(&len.to_le_bytes()[..prefix_size]);
+    Ok(())
+}
@@ -80,6 +80,20 @@ where
     /// * `domain_size` is smaller than 8 or is not a power of two.
     /// * `num_queries` is zero.
     pub fn new(domain_size: usize, num_queries: usize) -> Self {
+        Self::with_public_coin(RandomCoin::new(&[]), domain_size, num_queries)
+    }
+
+    /// Returns a new prover channel which draws random values from the specified `public_coin`.
+    ///
+    /// This allows the FRI prover to be used as a component of a protocol which maintains its own
+    /// transcript: the coin can be seeded with any data written into the transcript before the
+    /// FRI commit phase starts.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is smaller than 8 or is not a power of two.
+    /// * `num_queries` is zero.
+    pub fn with_public_coin(public_coin: R, domain_size: usize, num_queries: usize) -> Self {
         assert!(domain_size >= 8, "domain size must be at least 8, but was {domain_size}");
         assert!(
             domain_size.is_power_of_two(),
@@ -87,7 +101,7 @@ where
         );
         assert!(num_queries > 0, "number of queries must be greater than zero");
         DefaultProverChannel {
-            public_coin: RandomCoin::new(&[]),
+            public_coin,
             commitments: Vec::new(),
             domain_size,
             num_queries,
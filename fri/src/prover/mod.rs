@@ -4,15 +4,18 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    folding::{apply_drp, fold_positions},
+    folding::{apply_drp_strided_into, fold_positions, fold_positions_by_layer},
     proof::{FriProof, FriProofLayer},
-    utils::hash_values,
+    utils::hash_values_strided,
     FriOptions,
 };
 use core::marker::PhantomData;
 use crypto::{ElementHasher, Hasher, MerkleTree};
-use math::{fft, FieldElement, StarkField};
-use utils::{collections::Vec, flatten_vector_elements, group_slice_elements, transpose_slice};
+use math::{
+    fft::{self, CpuFftBackend, FftBackend},
+    FieldElement, StarkField,
+};
+use utils::collections::Vec;
 
 mod channel;
 pub use channel::{DefaultProverChannel, ProverChannel};
@@ -64,8 +67,10 @@ mod tests;
 /// sends it to the prover. In the non-interactive version, α is pseudo-randomly generated based
 /// on the values the prover has written into the channel up to that point.
 ///
-/// The prover keeps all FRI layers (consisting of evaluations and corresponding Merkle trees) in
-/// its internal state.
+/// The prover keeps Merkle trees of all FRI layers, the evaluations of the first layer, and the
+/// α values drawn for each layer in its internal state. Evaluations of all other layers are
+/// discarded as soon as the next layer is built; to fold a layer, the prover reuses two scratch
+/// buffers in a ping-pong fashion rather than allocating a new vector per layer.
 ///
 /// # Query phase
 /// In the query phase, which is executed via [build_proof()](FriProver::build_proof()) function,
@@ -80,28 +85,46 @@ mod tests;
 ///
 /// Since the positions are drawn from domain *D*, they apply directly only to the first FRI
 /// layer. To map these positions to the positions in all subsequent layers, the prover uses
-/// [fold_positions] procedure.
+/// [fold_positions_by_layer] procedure.
+///
+/// Since evaluations of FRI layers (other than the first one) are not retained during the commit
+/// phase, the prover re-derives them from the first layer using the α values drawn during the
+/// commit phase. This re-folding is cheap compared to building layer Merkle trees.
 ///
 /// After the proof is generated, the prover deletes all internally stored FRI layers.
 ///
 /// Calling [build_layers()](FriProver::build_layers()) when the internal state is dirty, or
 /// calling [build_proof()](FriProver::build_proof()) on a clean state will result in a panic.
-pub struct FriProver<B, E, C, H>
+///
+/// # FFT backend
+/// The FFT needed to interpolate the remainder polynomial is performed by the [FftBackend]
+/// specified via the `Fft` type parameter. By default, this is [CpuFftBackend]; a prover using a
+/// different backend can be instantiated via [FriProver::with_fft_backend()] function.
+pub struct FriProver<B, E, C, H, Fft = CpuFftBackend>
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
     C: ProverChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = B>,
+    Fft: FftBackend,
 {
     options: FriOptions,
-    layers: Vec<FriLayer<B, E, H>>,
+    domain_size: usize,
+    evaluations: Vec<E>,
+    alphas: Vec<E>,
+    layers: Vec<FriLayer<B, H>>,
     remainder_poly: FriRemainder<E>,
     _channel: PhantomData<C>,
+    _fft_backend: PhantomData<Fft>,
 }
 
-struct FriLayer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
+/// A committed FRI layer.
+///
+/// The Merkle tree commits to rows of a matrix with `folding_factor` columns formed by layer
+/// evaluations (in natural order) in column-major order. The evaluations themselves are not
+/// retained.
+struct FriLayer<B: StarkField, H: Hasher> {
     tree: MerkleTree<H>,
-    evaluations: Vec<E>,
     _base_field: PhantomData<B>,
 }
 
@@ -120,12 +143,33 @@ where
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new FRI prover instantiated with the provided `options`.
+    ///
+    /// The returned prover performs FFTs on the CPU.
     pub fn new(options: FriOptions) -> Self {
+        Self::with_fft_backend(options)
+    }
+}
+
+impl<B, E, C, H, Fft> FriProver<B, E, C, H, Fft>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    Fft: FftBackend,
+{
+    /// Returns a new FRI prover instantiated with the provided `options` which performs FFTs
+    /// using the backend specified by the `Fft` type parameter.
+    pub fn with_fft_backend(options: FriOptions) -> Self {
         FriProver {
             options,
+            domain_size: 0,
+            evaluations: Vec::new(),
+            alphas: Vec::new(),
             layers: Vec::new(),
             remainder_poly: FriRemainder(vec![]),
             _channel: PhantomData,
+            _fft_backend: PhantomData,
         }
     }
 
@@ -142,14 +186,19 @@ where
         self.options.domain_offset()
     }
 
-    /// Returns number of FRI layers computed during the last execution of the
+    /// Returns number of FRI layers committed to during the last execution of the
     /// [build_layers()](FriProver::build_layers()) method.
+    ///
+    /// If the commitment to the first FRI layer is skipped, this layer is not counted.
     pub fn num_layers(&self) -> usize {
         self.layers.len()
     }
 
     /// Clears a vector of internally stored layers.
     pub fn reset(&mut self) {
+        self.domain_size = 0;
+        self.evaluations.clear();
+        self.alphas.clear();
         self.layers.clear();
         self.remainder_poly.0.clear();
     }
@@ -167,57 +216,92 @@ where
     /// and the root of this tree is written into the channel. After this the prover draws a random
     /// field element α from the channel, and uses it in the next application of the DRP.
     ///
+    /// If the commitment to the first FRI layer is skipped, no Merkle tree is built for the first
+    /// layer (and nothing is written into the channel), but α is still drawn from the channel
+    /// before the DRP is applied to the first layer.
+    ///
     /// # Panics
     /// Panics if the prover state is dirty (the vector of layers is not empty).
-    pub fn build_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+    pub fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
         assert!(
             self.layers.is_empty(),
             "a prior proof generation request has not been completed yet"
         );
 
+        self.domain_size = evaluations.len();
+        let num_layers = self.options.num_fri_layers(evaluations.len());
+        if num_layers == 0 {
+            let mut evaluations = evaluations;
+            self.set_remainder(channel, &mut evaluations);
+            return;
+        }
+
         // reduce the degree by folding_factor at each iteration until the remaining polynomial
-        // has small enough degree
-        for _ in 0..self.options.num_fri_layers(evaluations.len()) {
+        // has small enough degree; the first layer is folded into the first scratch buffer, and
+        // after that, each layer is folded from one scratch buffer into the other
+        let mut buffers = [Vec::new(), Vec::new()];
+        for depth in 0..num_layers {
+            let commit = depth > 0 || !self.options.skip_first_layer_commitment();
+            let (source, target) = get_layer_buffers(&evaluations, &mut buffers, depth);
             match self.folding_factor() {
-                2 => self.build_layer::<2>(channel, &mut evaluations),
-                4 => self.build_layer::<4>(channel, &mut evaluations),
-                8 => self.build_layer::<8>(channel, &mut evaluations),
-                16 => self.build_layer::<16>(channel, &mut evaluations),
+                2 => self.build_layer::<2>(channel, source, target, commit),
+                4 => self.build_layer::<4>(channel, source, target, commit),
+                8 => self.build_layer::<8>(channel, source, target, commit),
+                16 => self.build_layer::<16>(channel, source, target, commit),
                 _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
             }
         }
 
-        self.set_remainder(channel, &mut evaluations);
+        self.set_remainder(channel, &mut buffers[(num_layers - 1) % 2]);
+        self.evaluations = evaluations;
     }
 
     /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
-    /// alpha from the channel and use it to perform degree-respecting projection.
-    fn build_layer<const N: usize>(&mut self, channel: &mut C, evaluations: &mut Vec<E>) {
-        // commit to the evaluations at the current layer; we do this by first transposing the
-        // evaluations into a matrix of N columns, and then building a Merkle tree from the
-        // rows of this matrix; we do this so that we could de-commit to N values with a single
-        // Merkle authentication path.
-        let transposed_evaluations = transpose_slice(evaluations);
-        let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
-        let evaluation_tree =
-            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
-        channel.commit_fri_layer(*evaluation_tree.root());
+    /// alpha from the channel and using it to perform degree-respecting projection; the folded
+    /// evaluations are written into `folded_evaluations`.
+    ///
+    /// If `commit` is false, the evaluations are folded without being committed to, and the layer
+    /// is not retained by the prover.
+    fn build_layer<const N: usize>(
+        &mut self,
+        channel: &mut C,
+        evaluations: &[E],
+        folded_evaluations: &mut Vec<E>,
+        commit: bool,
+    ) {
+        // commit to the evaluations at the current layer; we do this by interpreting the
+        // evaluations as a matrix of N columns, and then building a Merkle tree from the rows of
+        // this matrix; we do this so that we could de-commit to N values with a single Merkle
+        // authentication path. the matrix is never materialized to avoid copying evaluations.
+        if commit {
+            let hashed_evaluations = hash_values_strided::<H, E, N>(evaluations);
+            let evaluation_tree = MerkleTree::<H>::new(hashed_evaluations)
+                .expect("failed to construct FRI layer tree");
+            channel.commit_fri_layer(*evaluation_tree.root());
+            self.layers.push(FriLayer {
+                tree: evaluation_tree,
+                _base_field: PhantomData,
+            });
+        }
 
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
-        // projection to reduce the degree of evaluations by N
+        // projection to reduce the degree of evaluations by N; alpha is retained so that the
+        // evaluations of the folded layer can be re-derived during the query phase
         let alpha = channel.draw_fri_alpha();
-        *evaluations = apply_drp(&transposed_evaluations, self.domain_offset(), alpha);
-        self.layers.push(FriLayer {
-            tree: evaluation_tree,
-            evaluations: flatten_vector_elements(transposed_evaluations),
-            _base_field: PhantomData,
-        });
+        folded_evaluations.resize(evaluations.len() / N, E::ZERO);
+        apply_drp_strided_into::<B, E, N>(
+            evaluations,
+            folded_evaluations,
+            self.domain_offset(),
+            alpha,
+        );
+        self.alphas.push(alpha);
     }
 
     /// Creates remainder polynomial in coefficient form from a vector of `evaluations` over a domain.
     fn set_remainder(&mut self, channel: &mut C, evaluations: &mut [E]) {
         let inv_twiddles = fft::get_inv_twiddles(evaluations.len());
-        fft::interpolate_poly_with_offset(evaluations, &inv_twiddles, self.options.domain_offset());
+        Fft::interpolate_poly_with_offset(evaluations, &inv_twiddles, self.options.domain_offset());
         let remainder_poly_size = evaluations.len() / self.options.blowup_factor();
         let remainder_poly = evaluations[..remainder_poly_size].to_vec();
         let commitment = <H as ElementHasher>::hash_elements(&remainder_poly);
@@ -239,31 +323,27 @@ where
     pub fn build_proof(&mut self, positions: &[usize]) -> FriProof {
         assert!(!self.remainder_poly.0.is_empty(), "FRI layers have not been built yet");
 
-        let mut layers = Vec::with_capacity(self.layers.len());
-
-        if !self.layers.is_empty() {
-            let mut positions = positions.to_vec();
-            let mut domain_size = self.layers[0].evaluations.len();
-            let folding_factor = self.options.folding_factor();
-
-            // for all FRI layers, except the last one, record tree root, determine a set of query
-            // positions, and query the layer at these positions.
-            for i in 0..self.layers.len() {
-                positions = fold_positions(&positions, domain_size, folding_factor);
-
-                // sort of a static dispatch for folding_factor parameter
-                let proof_layer = match folding_factor {
-                    2 => query_layer::<B, E, H, 2>(&self.layers[i], &positions),
-                    4 => query_layer::<B, E, H, 4>(&self.layers[i], &positions),
-                    8 => query_layer::<B, E, H, 8>(&self.layers[i], &positions),
-                    16 => query_layer::<B, E, H, 16>(&self.layers[i], &positions),
-                    _ => unimplemented!("folding factor {} is not supported", folding_factor),
-                };
-
-                layers.push(proof_layer);
-                domain_size /= folding_factor;
-            }
-        }
+        let folding_factor = self.options.folding_factor();
+
+        // if the commitment to the first layer was skipped, the verifier derives values of this
+        // layer on its own; thus, we start by mapping positions into the domain of the first
+        // committed layer
+        let (positions, domain_size) =
+            if self.layers.len() < self.options.num_fri_layers(self.domain_size) {
+                let positions = fold_positions(positions, self.domain_size, folding_factor);
+                (positions, self.domain_size / folding_factor)
+            } else {
+                (positions.to_vec(), self.domain_size)
+            };
+
+        // sort of a static dispatch for folding_factor parameter
+        let layers = match folding_factor {
+            2 => self.query_layers::<2>(&positions, domain_size),
+            4 => self.query_layers::<4>(&positions, domain_size),
+            8 => self.query_layers::<8>(&positions, domain_size),
+            16 => self.query_layers::<16>(&positions, domain_size),
+            _ => unimplemented!("folding factor {} is not supported", folding_factor),
+        };
 
         // use the remaining polynomial values directly as proof
         let remainder = self.remainder_poly.0.clone();
@@ -273,15 +353,88 @@ where
 
         FriProof::new(layers, remainder, 1)
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Queries all committed FRI layers at the specified `positions` in the domain of the first
+    /// committed layer.
+    ///
+    /// Evaluations of FRI layers are re-derived from the evaluations of the first layer using the
+    /// α values drawn during the commit phase; as in the commit phase, only the evaluations of
+    /// the current and the next layers are kept in memory at any given time.
+    fn query_layers<const N: usize>(
+        &self,
+        positions: &[usize],
+        domain_size: usize,
+    ) -> Vec<FriProofLayer> {
+        if self.layers.is_empty() {
+            return Vec::new();
+        }
+
+        // fold query positions for all layers at once; positions for a given layer are the
+        // indexes of the rows opened in that layer
+        let layer_positions = fold_positions_by_layer(positions, domain_size, N, self.layers.len());
+
+        // if the commitment to the first layer was skipped, the first layer is folded but is
+        // not queried
+        let num_skipped_layers = self.alphas.len() - self.layers.len();
+
+        let mut result = Vec::with_capacity(self.layers.len());
+        let mut buffers = [Vec::new(), Vec::new()];
+        for (depth, &alpha) in self.alphas.iter().enumerate() {
+            let (evaluations, folded_evaluations) =
+                get_layer_buffers(&self.evaluations, &mut buffers, depth);
+
+            if let Some(layer_idx) = depth.checked_sub(num_skipped_layers) {
+                let layer = &self.layers[layer_idx];
+                let positions = &layer_positions[layer_idx];
+                result.push(query_layer::<B, E, H, N>(layer, evaluations, positions));
+            }
+
+            // the last committed layer does not need to be folded since the remainder is
+            // already known
+            if depth + 1 < self.alphas.len() {
+                folded_evaluations.resize(evaluations.len() / N, E::ZERO);
+                apply_drp_strided_into::<B, E, N>(
+                    evaluations,
+                    folded_evaluations,
+                    self.domain_offset(),
+                    alpha,
+                );
+            }
+        }
+
+        result
+    }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
-/// Builds a single proof layer by querying the evaluations of the passed in FRI layer at the
+/// Returns evaluations of the FRI layer at the specified depth together with the buffer into
+/// which evaluations of the next layer should be written.
+///
+/// The first layer is always `evaluations`; evaluations of all subsequent layers alternate
+/// between the two `buffers`.
+fn get_layer_buffers<'a, E>(
+    evaluations: &'a [E],
+    buffers: &'a mut [Vec<E>; 2],
+    depth: usize,
+) -> (&'a [E], &'a mut Vec<E>) {
+    let [even, odd] = buffers;
+    match depth {
+        0 => (evaluations, even),
+        _ if depth % 2 == 1 => (even, odd),
+        _ => (odd, even),
+    }
+}
+
+/// Builds a single proof layer by querying the `evaluations` of the passed in FRI layer at the
 /// specified positions.
 fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N: usize>(
-    layer: &FriLayer<B, E, H>,
+    layer: &FriLayer<B, H>,
+    evaluations: &[E],
     positions: &[usize],
 ) -> FriProofLayer {
     // build Merkle authentication paths for all query positions
@@ -291,12 +444,12 @@ fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N
         .expect("failed to generate a Merkle proof for FRI layer queries");
 
     // build a list of polynomial evaluations at each position; since evaluations in FRI layers
-    // are stored in transposed form, a position refers to N evaluations which are committed
-    // in a single leaf
-    let evaluations: &[[E; N]] = group_slice_elements(&layer.evaluations);
+    // are committed to as rows of a matrix with N columns, a position refers to N evaluations
+    // which are committed in a single leaf
+    let row_count = evaluations.len() / N;
     let mut queried_values: Vec<[E; N]> = Vec::with_capacity(positions.len());
     for &position in positions.iter() {
-        queried_values.push(evaluations[position]);
+        queried_values.push(core::array::from_fn(|j| evaluations[position + j * row_count]));
     }
 
     FriProofLayer::new(queried_values, proof)
@@ -5,6 +5,7 @@
 
 use super::{DefaultProverChannel, FriProver};
 use crate::{
+    folding::{fold_positions, unfold_positions},
     verifier::{DefaultVerifierChannel, FriVerifier},
     FriOptions, FriProof, VerifierError,
 };
@@ -35,6 +36,222 @@ fn fri_folding_4() {
     fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
 }
 
+#[test]
+fn fri_folding_8() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 3;
+    let max_remainder_degree = 7;
+    fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
+}
+
+#[test]
+fn fri_folding_16() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 4;
+    let max_remainder_degree = 7;
+    fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
+}
+
+#[test]
+fn fri_remainder_degree_exceeds_max() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+
+    // build a proof with a large remainder
+    let options = FriOptions::new(lde_blowup, 4, 7).with_max_remainder_degree(255);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let mut prover = FriProver::new(options);
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    // the proof should be rejected by a verifier which expects a smaller remainder (and thus,
+    // more FRI layers)
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let result = verify_proof(
+        proof,
+        channel.layer_commitments().to_vec(),
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert!(matches!(result, Err(VerifierError::NumLayerCommitmentsMismatch(6, 3))));
+}
+
+#[test]
+fn fri_skip_first_layer_commitment() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let domain_size = trace_length * lde_blowup;
+
+    let options = FriOptions::new(lde_blowup, 4, 7).with_skip_first_layer_commitment(true);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    assert_eq!(options.num_committed_layers(domain_size), prover.num_layers());
+    assert_eq!(prover.num_layers() + 1, channel.layer_commitments().len());
+
+    // open all positions of the first layer which fold into the queried positions
+    let positions = fold_positions(&channel.draw_query_positions(0), domain_size, 4);
+    let positions = unfold_positions(&positions, domain_size, 4);
+    let proof = prover.build_proof(&positions);
+
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        trace_length - 1,
+        domain_size / 4,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // the proof should be rejected if some positions of a queried coset are not provided
+    let missing_position = positions[positions.len() - 1];
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size / 4,
+        &positions[..positions.len() - 1],
+        &options,
+    );
+    assert_eq!(Err(VerifierError::FirstLayerEvaluationMissing(missing_position)), result);
+}
+
+#[test]
+fn fri_verify_from_commitments() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let domain_size = trace_length * lde_blowup;
+
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    // run the commit phase with commitments and a public coin supplied by the caller
+    let commitments = channel.layer_commitments().to_vec();
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = FriVerifier::<_, DefaultVerifierChannel<_, Blake3>, _, _>::from_commitments(
+        commitments.clone(),
+        &mut coin,
+        options.clone(),
+        trace_length - 1,
+    )
+    .unwrap();
+
+    let queried_evaluations = positions.iter().map(|&p| evaluations[p]).collect::<Vec<_>>();
+    let mut verifier_channel =
+        DefaultVerifierChannel::new(proof.clone(), vec![], domain_size, 4).unwrap();
+    let result = verifier.verify(&mut verifier_channel, &queried_evaluations, &positions);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // a coin seeded with a different transcript draws different α values
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[BaseElement::ONE]);
+    let verifier = FriVerifier::<_, DefaultVerifierChannel<_, Blake3>, _, _>::from_commitments(
+        commitments,
+        &mut coin,
+        options,
+        trace_length - 1,
+    )
+    .unwrap();
+    let mut verifier_channel = DefaultVerifierChannel::new(proof, vec![], domain_size, 4).unwrap();
+    let result = verifier.verify(&mut verifier_channel, &queried_evaluations, &positions);
+    assert!(result.is_err());
+}
+
+#[test]
+fn fri_verbose_verification() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let domain_size = trace_length * lde_blowup;
+
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = FriVerifier::<_, DefaultVerifierChannel<_, Blake3>, _, _>::from_commitments(
+        channel.layer_commitments().to_vec(),
+        &mut coin,
+        options,
+        trace_length - 1,
+    )
+    .unwrap();
+    let build_channel =
+        || DefaultVerifierChannel::new(proof.clone(), vec![], domain_size, 4).unwrap();
+
+    let mut queried_evaluations = positions.iter().map(|&p| evaluations[p]).collect::<Vec<_>>();
+    assert_eq!(
+        Ok(()),
+        verifier.verify_verbose(&mut build_channel(), &queried_evaluations, &positions)
+    );
+
+    // tamper with evaluations of two queries
+    queried_evaluations[1] += BaseElement::ONE;
+    queried_evaluations[3] += BaseElement::ONE;
+
+    // by default, only the first inconsistency is reported
+    let result = verifier.verify(&mut build_channel(), &queried_evaluations, &positions);
+    assert_eq!(Err(VerifierError::InvalidLayerFolding(0, positions[1])), result);
+
+    // in verbose mode, all inconsistencies are reported
+    let result = verifier.verify_verbose(&mut build_channel(), &queried_evaluations, &positions);
+    assert_eq!(
+        Err(vec![
+            VerifierError::InvalidLayerFolding(0, positions[1]),
+            VerifierError::InvalidLayerFolding(0, positions[3]),
+        ]),
+        result
+    );
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn fri_proof_protobuf_round_trip() {
+    use crate::protobuf;
+    use prost::Message;
+
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let mut prover = FriProver::new(options);
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    let message_bytes = protobuf::FriProof::from(&proof).encode_to_vec();
+    let message = protobuf::FriProof::decode(message_bytes.as_slice()).unwrap();
+    assert_eq!(proof, FriProof::try_from(message.clone()).unwrap());
+
+    // a message with an invalid number of partitions should be rejected
+    let message = protobuf::FriProof {
+        num_partitions: 3,
+        ..message
+    };
+    assert!(FriProof::try_from(message).is_err());
+}
+
 // TEST UTILS
 // ================================================================================================
 
@@ -49,3 +49,40 @@ where
     });
     result
 }
+
+/// Hashes each row of the evaluation matrix implied by the provided `values` and returns a vector
+/// of resulting hashes.
+///
+/// The values are interpreted as a matrix with `N` columns stored in column-major order. That is,
+/// the $i$-th row consists of values at positions $i, i + n / N, ..., i + (N - 1) \cdot n / N$,
+/// where $n$ is the number of values. Thus, this is equivalent to [hash_values()] applied to the
+/// transposed `values`, but does not require the transposed copy to be allocated.
+///
+/// ```
+/// # use crypto::hashers::Blake3_256;
+/// # use math::fields::f128::BaseElement;
+/// # use rand_utils::rand_vector;
+/// # use utils::transpose_slice;
+/// # use winter_fri::utils::{hash_values, hash_values_strided};
+/// type Blake3 = Blake3_256<BaseElement>;
+/// let values: Vec<BaseElement> = rand_vector(64);
+///
+/// let transposed_values = transpose_slice::<BaseElement, 4>(&values);
+/// assert_eq!(
+///     hash_values::<Blake3, _, 4>(&transposed_values),
+///     hash_values_strided::<Blake3, _, 4>(&values)
+/// );
+/// ```
+pub fn hash_values_strided<H, E, const N: usize>(values: &[E]) -> Vec<H::Digest>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let row_count = values.len() / N;
+    let mut result: Vec<H::Digest> = unsafe { uninit_vector(row_count) };
+    iter_mut!(result, 1024).enumerate().for_each(|(i, r)| {
+        let row: [E; N] = core::array::from_fn(|j| values[i + j * row_count]);
+        *r = H::hash_elements(&row);
+    });
+    result
+}
@@ -71,23 +71,51 @@ pub trait VerifierChannel<E: FieldElement> {
     /// Returns FRI query values at the specified positions from the current FRI layer and advances
     /// layer pointer by one.
     ///
-    /// This also checks if the values are valid against the provided FRI layer commitment.
+    /// This also checks if the values are valid against the provided FRI layer commitment;
+    /// `layer_depth` is the depth of the current layer and is used only for error reporting.
     ///
     /// # Errors
-    /// Returns an error if query values did not match layer commitment.
+    /// Returns an error if query values did not match layer commitment; the error specifies the
+    /// layer depth and the first position for which the Merkle authentication path did not
+    /// resolve to the commitment.
     fn read_layer_queries<const N: usize>(
         &mut self,
+        layer_depth: usize,
         positions: &[usize],
         commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
     ) -> Result<Vec<[E; N]>, VerifierError> {
+        match self.read_layer_queries_unchecked(layer_depth, positions, commitment) {
+            (layer_queries, None) => Ok(layer_queries),
+            (_, Some(err)) => Err(err),
+        }
+    }
+
+    /// Returns FRI query values at the specified positions from the current FRI layer and advances
+    /// layer pointer by one.
+    ///
+    /// This is the same as [read_layer_queries()](VerifierChannel::read_layer_queries()), but
+    /// query values are returned even if they did not match layer commitment; in such a case, the
+    /// error describing the mismatch is returned together with the values. This allows the
+    /// verifier to continue checking subsequent FRI layers for diagnostic purposes.
+    fn read_layer_queries_unchecked<const N: usize>(
+        &mut self,
+        layer_depth: usize,
+        positions: &[usize],
+        commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
+    ) -> (Vec<[E; N]>, Option<VerifierError>) {
         let layer_proof = self.take_next_fri_layer_proof();
-        MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
-            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+        let err = MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
+            .err()
+            .map(|_| {
+                let position = MerkleTree::find_invalid_index(commitment, positions, &layer_proof)
+                    .unwrap_or(positions[0]);
+                VerifierError::LayerCommitmentMismatch(layer_depth, position)
+            });
 
         // TODO: make sure layer queries hash into leaves of layer proof
 
         let layer_queries = self.take_next_fri_layer_queries();
-        Ok(group_vector_elements(layer_queries))
+        (group_vector_elements(layer_queries), err)
     }
 
     /// Returns FRI remainder polynomial read from this channel.
@@ -122,6 +150,9 @@ where
 {
     /// Builds a new verifier channel from the specified [FriProof].
     ///
+    /// If the commitment to the first FRI layer was skipped, `domain_size` must be the size of the
+    /// domain of the second FRI layer (see [FriProof::parse_layers()]).
+    ///
     /// # Errors
     /// Returns an error if the specified `proof` could not be parsed correctly.
     pub fn new(
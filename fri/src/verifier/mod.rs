@@ -5,10 +5,12 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
-use core::{convert::TryInto, marker::PhantomData, mem};
+use crate::{
+    folding::fold_positions_by_layer, utils::map_positions_to_indexes, FriOptions, VerifierError,
+};
+use core::marker::PhantomData;
 use crypto::{ElementHasher, RandomCoin};
-use math::{polynom, FieldElement, StarkField};
+use math::{fft, polynom, FieldElement, StarkField};
 use utils::collections::Vec;
 
 mod channel;
@@ -41,7 +43,10 @@ pub use channel::{DefaultVerifierChannel, VerifierChannel};
 /// from the prover (via [VerifierChannel]). After each received commitment, the verifier
 /// draws a random value α from the entire field, and sends it to the prover. In the
 /// non-interactive version of the protocol, α values are derived pseudo-randomly from FRI
-/// layer commitments.
+/// layer commitments. When FRI is used as a component of a larger protocol with its own
+/// transcript, the commit phase can also be executed via
+/// [from_commitments()](FriVerifier::from_commitments()) function, which takes layer commitments
+/// directly and draws α values from a caller-provided public coin.
 ///
 /// # Query phase
 /// During the query phase, which is executed via [verify()](FriVerifier::verify()) function,
@@ -68,7 +73,6 @@ where
     layer_commitments: Vec<H::Digest>,
     layer_alphas: Vec<E>,
     options: FriOptions,
-    num_partitions: usize,
     _channel: PhantomData<C>,
     _public_coin: PhantomData<R>,
 }
@@ -94,37 +98,81 @@ where
     /// The verifier stores layer commitments and corresponding α values in its internal state,
     /// and, thus, an instance of FRI verifier can be used to verify only a single proof.
     ///
+    /// If the commitment to the first FRI layer is skipped, no commitment is read for this layer,
+    /// and α for the first layer is drawn from the coin without updating it.
+    ///
     /// # Errors
     /// Returns an error if:
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
+    /// * The number of layer commitments read from the channel is inconsistent with the number
+    ///   of FRI layers implied by `options`.
     /// * An error was encountered while drawing a random α value from the coin.
     pub fn new(
         channel: &mut C,
         public_coin: &mut R,
         options: FriOptions,
         max_poly_degree: usize,
+    ) -> Result<Self, VerifierError> {
+        let layer_commitments = channel.read_fri_layer_commitments();
+        Self::from_commitments(layer_commitments, public_coin, options, max_poly_degree)
+    }
+
+    /// Returns a new instance of FRI verifier created from the specified layer commitments and
+    /// parameters.
+    ///
+    /// This is the same as [new()](FriVerifier::new()), but instead of reading FRI layer
+    /// commitments from a channel, these are provided by the caller.
+    /// This allows protocols which maintain their own transcript to run the commit phase of FRI
+    /// directly: the `public_coin` can be seeded with arbitrary data before this function is
+    /// called, and α values are drawn from it after it is updated with each layer commitment.
+    ///
+    /// The query phase still needs a channel to read layer openings from; a
+    /// [DefaultVerifierChannel] built from a [FriProof](crate::FriProof) can be used for this.
+    ///
+    /// # Errors
+    /// Returns an error under the same conditions as [new()](FriVerifier::new()).
+    pub fn from_commitments(
+        layer_commitments: Vec<H::Digest>,
+        public_coin: &mut R,
+        options: FriOptions,
+        max_poly_degree: usize,
     ) -> Result<Self, VerifierError> {
         // infer evaluation domain info
         let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
         let domain_generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
 
-        let num_partitions = channel.read_fri_num_partitions();
+        // make sure the number of layer commitments is consistent with the number of FRI layers
+        // (plus the remainder) implied by the options; this ensures that the remainder polynomial
+        // was computed in accordance with the max remainder degree
+        let num_layers = options.num_fri_layers(domain_size);
+        let num_committed_layers = options.num_committed_layers(domain_size);
+        if layer_commitments.len() != num_committed_layers + 1 {
+            return Err(VerifierError::NumLayerCommitmentsMismatch(
+                num_committed_layers + 1,
+                layer_commitments.len(),
+            ));
+        }
 
-        // read layer commitments from the channel and use them to build a list of alphas
-        let layer_commitments = channel.read_fri_layer_commitments();
-        let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
+        let mut layer_alphas = Vec::with_capacity(num_layers + 1);
         let mut max_degree_plus_1 = max_poly_degree + 1;
-        for (depth, commitment) in layer_commitments.iter().enumerate() {
-            public_coin.reseed(*commitment);
+
+        // if the commitment to the first layer was skipped, there is no commitment to update the
+        // coin with before drawing α for this layer
+        let num_skipped_layers = num_layers - num_committed_layers;
+        let commitments = core::iter::repeat(None)
+            .take(num_skipped_layers)
+            .chain(layer_commitments.iter().map(Some));
+        for (depth, commitment) in commitments.enumerate() {
+            if let Some(commitment) = commitment {
+                public_coin.reseed(*commitment);
+            }
             let alpha = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
             layer_alphas.push(alpha);
 
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
-            if depth != layer_commitments.len() - 1
-                && max_degree_plus_1 % options.folding_factor() != 0
-            {
+            if depth != num_layers && max_degree_plus_1 % options.folding_factor() != 0 {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
                     options.folding_factor(),
@@ -141,7 +189,6 @@ where
             layer_commitments,
             layer_alphas,
             options,
-            num_partitions,
             _channel: PhantomData,
             _public_coin: PhantomData,
         })
@@ -164,13 +211,6 @@ where
         self.domain_size
     }
 
-    /// Returns number of partitions used during FRI proof generation.
-    ///
-    /// For non-distributed proof generation, number of partitions is usually set to 1.
-    pub fn num_partitions(&self) -> usize {
-        self.num_partitions
-    }
-
     /// Returns protocol configuration options for this verifier.
     pub fn options(&self) -> &FriOptions {
         &self.options
@@ -189,11 +229,23 @@ where
     /// for these positions.
     ///
     /// Evaluations of layer polynomials for all subsequent FRI layers the verifier reads from the
-    /// specified `channel`.
+    /// specified `channel`. The number of partitions used during proof generation is read from
+    /// the `channel` as well, as it affects only how the queried values are located in the layer
+    /// commitments.
+    ///
+    /// If the commitment to the first FRI layer is skipped, values of the first layer are not read
+    /// from the channel. Instead, `positions` must contain all positions which fold into the
+    /// queried positions of the second layer (see
+    /// [unfold_positions()](crate::folding::unfold_positions)), and the caller is responsible for
+    /// making sure that `evaluations` at these positions are authentic.
     ///
     /// # Errors
     /// Returns an error if:
     /// * The length of `evaluations` is not equal to the length of `positions`.
+    /// * The number of partitions read from the channel is greater than the size of the domain
+    ///   of the last folded FRI layer.
+    /// * The commitment to the first FRI layer is skipped, and `positions` do not cover all
+    ///   positions of the first layer which fold into the queried positions of the second layer.
     /// * An unsupported folding factor was specified by the `options` for this verifier.
     /// * Decommitments to polynomial evaluations don't match the commitment value at any of the
     ///   FRI layers.
@@ -207,84 +259,187 @@ where
         evaluations: &[E],
         positions: &[usize],
     ) -> Result<(), VerifierError> {
+        self.verify_with_mode(channel, evaluations, positions, false)
+            .map_err(|mut errors| errors.remove(0))
+    }
+
+    /// Executes the query phase of the FRI protocol in verbose mode.
+    ///
+    /// This is the same as [verify()](FriVerifier::verify()), but instead of returning as soon as
+    /// the first inconsistency is detected, the verifier keeps checking the remaining queries and
+    /// FRI layers, and returns all detected inconsistencies in the order in which they were
+    /// encountered. Each error identifies the layer and the position of the query at which the
+    /// inconsistency was detected; thus, this mode is intended for diagnosing invalid proofs.
+    ///
+    /// Errors which make further checks meaningless (e.g., a malformed remainder or an
+    /// unsupported folding factor) still terminate verification immediately.
+    ///
+    /// # Errors
+    /// Returns a non-empty list of errors under the same conditions as
+    /// [verify()](FriVerifier::verify()).
+    pub fn verify_verbose(
+        &self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), Vec<VerifierError>> {
+        self.verify_with_mode(channel, evaluations, positions, true)
+    }
+
+    /// Dispatches the query phase of the FRI protocol to the implementation for the folding
+    /// factor of this verifier; if `verbose` is false, the returned list contains a single error.
+    fn verify_with_mode(
+        &self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+        verbose: bool,
+    ) -> Result<(), Vec<VerifierError>> {
         if evaluations.len() != positions.len() {
-            return Err(VerifierError::NumPositionEvaluationMismatch(
+            return Err(vec![VerifierError::NumPositionEvaluationMismatch(
                 positions.len(),
                 evaluations.len(),
-            ));
+            )]);
+        }
+
+        // make sure every folded layer can be split into the specified number of partitions; if
+        // there are no folded layers, partitions are not used and only a single one is allowed
+        let num_partitions = channel.read_fri_num_partitions();
+        let num_layers = self.options.num_fri_layers(self.domain_size);
+        let max_partitions = if num_layers == 0 {
+            1
+        } else {
+            self.domain_size / self.options.folding_factor().pow(num_layers as u32)
+        };
+        if num_partitions > max_partitions {
+            return Err(vec![VerifierError::NumPartitionsNotValid(num_partitions, max_partitions)]);
         }
 
         // static dispatch for folding factor parameter
         let folding_factor = self.options.folding_factor();
-        match folding_factor {
-            2 => self.verify_generic::<2>(channel, evaluations, positions),
-            4 => self.verify_generic::<4>(channel, evaluations, positions),
-            8 => self.verify_generic::<8>(channel, evaluations, positions),
-            16 => self.verify_generic::<16>(channel, evaluations, positions),
+        let mut errors = ErrorCollector::new(verbose);
+        let result = match folding_factor {
+            2 => self.verify_generic::<2>(
+                channel,
+                evaluations,
+                positions,
+                num_partitions,
+                &mut errors,
+            ),
+            4 => self.verify_generic::<4>(
+                channel,
+                evaluations,
+                positions,
+                num_partitions,
+                &mut errors,
+            ),
+            8 => self.verify_generic::<8>(
+                channel,
+                evaluations,
+                positions,
+                num_partitions,
+                &mut errors,
+            ),
+            16 => self.verify_generic::<16>(
+                channel,
+                evaluations,
+                positions,
+                num_partitions,
+                &mut errors,
+            ),
             _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
-        }
+        };
+        errors.finish(result)
     }
 
     /// This is the actual implementation of the verification procedure described above, but it
     /// also takes folding factor as a generic parameter N.
+    ///
+    /// Inconsistencies in individual queries are reported to the `errors` collector; an error is
+    /// returned only if verification cannot proceed.
     fn verify_generic<const N: usize>(
         &self,
         channel: &mut C,
         evaluations: &[E],
         positions: &[usize],
+        num_partitions: usize,
+        errors: &mut ErrorCollector,
     ) -> Result<(), VerifierError> {
-        // pre-compute roots of unity used in computing x coordinates in the folded domain
-        let folding_roots = (0..N)
-            .map(|i| self.domain_generator.exp_vartime(((self.domain_size / N * i) as u64).into()))
-            .collect::<Vec<_>>();
+        // pre-compute inverse twiddles used to interpolate each row of N values into a polynomial;
+        // values in a row are evaluations over a coset of the subgroup of N-th roots of unity
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(N);
 
         // 1 ----- verify the recursive components of the FRI proof -----------------------------------
         let mut domain_generator = self.domain_generator;
         let mut domain_size = self.domain_size;
         let mut max_degree_plus_1 = self.max_poly_degree + 1;
-        let mut positions = positions.to_vec();
         let mut evaluations = evaluations.to_vec();
 
-        for depth in 0..self.options.num_fri_layers(self.domain_size) {
-            // determine which evaluations were queried in the folded layer
-            let mut folded_positions =
-                fold_positions(&positions, domain_size, self.options.folding_factor());
-            // determine where these evaluations are in the commitment Merkle tree
-            let position_indexes = map_positions_to_indexes(
-                &folded_positions,
-                domain_size,
-                self.options.folding_factor(),
-                self.num_partitions,
-            );
-            // read query values from the specified indexes in the Merkle tree
-            let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
+        // determine which evaluations were queried in each of the folded layers
+        let layer_positions = fold_positions_by_layer(
+            positions,
+            self.domain_size,
+            self.options.folding_factor(),
+            self.options.num_fri_layers(self.domain_size),
+        );
+
+        // layers which were not committed to precede all committed layers
+        let num_skipped_layers = layer_positions.len() + 1 - self.layer_commitments.len();
+
+        let mut positions = positions;
+        for (depth, folded_positions) in layer_positions.iter().enumerate() {
+            let layer_values = if depth < num_skipped_layers {
+                // the layer was not committed to; build query values directly from the provided
+                // evaluations
+                get_layer_values::<E, N>(&evaluations, positions, folded_positions, domain_size)?
+            } else {
+                // determine where the queried evaluations are in the commitment Merkle tree
+                let position_indexes = map_positions_to_indexes(
+                    folded_positions,
+                    domain_size,
+                    self.options.folding_factor(),
+                    num_partitions,
+                );
+                // read query values from the specified indexes in the Merkle tree
+                let layer_commitment = self.layer_commitments[depth - num_skipped_layers];
+                let (layer_values, err) = channel.read_layer_queries_unchecked(
+                    depth,
+                    &position_indexes,
+                    &layer_commitment,
+                );
+                if let Some(err) = err {
+                    errors.report(err)?;
+                }
+                layer_values
+            };
+
+            // make sure the evaluations are consistent with the values read from the layer
             let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
+                get_query_values::<E, N>(&layer_values, positions, folded_positions, domain_size);
+            for ((&position, evaluation), query_value) in
+                positions.iter().zip(&evaluations).zip(query_values)
+            {
+                if *evaluation != query_value {
+                    errors.report(VerifierError::InvalidLayerFolding(depth, position))?;
+                }
             }
 
-            // build a set of x coordinates for each row polynomial
-            #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp_vartime((i as u64).into()) * self.options.domain_offset();
-                folding_roots.iter()
-                    .map(|&r| E::from(xe * r))
-                    .collect::<Vec<_>>().try_into().unwrap()
-            })
-            .collect::<Vec<_>>();
-
-            // interpolate x and y values into row polynomials
-            let row_polys = polynom::interpolate_batch(&xs, &layer_values);
-
-            // calculate the pseudo-random value used for linear combination in layer folding
+            // interpolate each row of values into a polynomial over the coset defined by the
+            // corresponding folded position, and evaluate the polynomial at the pseudo-random value
+            // used for linear combination in layer folding; the results are the values of the
+            // next layer at the folded positions
             let alpha = self.layer_alphas[depth];
-
-            // check that when the polynomials are evaluated at alpha, the result is equal to
-            // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+            let domain_offset = self.options.domain_offset::<E::BaseField>();
+            evaluations = folded_positions
+                .iter()
+                .zip(&layer_values)
+                .map(|(&i, row)| {
+                    let offset = domain_generator.exp_vartime((i as u64).into()) * domain_offset;
+                    let mut row_poly = row.to_vec();
+                    fft::interpolate_poly_with_offset(&mut row_poly, &inv_twiddles, offset);
+                    polynom::eval(&row_poly, alpha)
+                })
+                .collect();
 
             // make sure next degree reduction does not result in degree truncation
             if max_degree_plus_1 % N != 0 {
@@ -295,7 +450,7 @@ where
             domain_generator = domain_generator.exp_vartime((N as u32).into());
             max_degree_plus_1 /= N;
             domain_size /= N;
-            mem::swap(&mut positions, &mut folded_positions);
+            positions = folded_positions;
         }
 
         // 2 ----- verify the remainder polynomial of the FRI proof -------------------------------
@@ -314,7 +469,7 @@ where
                 offset * domain_generator.exp_vartime((position as u64).into()),
             );
             if comp_eval != evaluation {
-                return Err(VerifierError::InvalidRemainderFolding);
+                errors.report(VerifierError::InvalidRemainderFolding(position))?;
             }
         }
 
@@ -322,6 +477,49 @@ where
     }
 }
 
+// ERROR COLLECTOR
+// ================================================================================================
+
+/// Accumulates errors detected during the query phase of the FRI protocol.
+///
+/// In non-verbose mode, reporting an error aborts verification; in verbose mode, errors are
+/// recorded and verification continues.
+struct ErrorCollector {
+    verbose: bool,
+    errors: Vec<VerifierError>,
+}
+
+impl ErrorCollector {
+    fn new(verbose: bool) -> Self {
+        ErrorCollector {
+            verbose,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Records the specified error; returns the error back if verification should be aborted.
+    fn report(&mut self, error: VerifierError) -> Result<(), VerifierError> {
+        if self.verbose {
+            self.errors.push(error);
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Returns all recorded errors followed by the error which terminated verification (if any).
+    fn finish(mut self, result: Result<(), VerifierError>) -> Result<(), Vec<VerifierError>> {
+        if let Err(err) = result {
+            self.errors.push(err);
+        }
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 fn get_query_values<E: FieldElement, const N: usize>(
@@ -342,6 +540,33 @@ fn get_query_values<E: FieldElement, const N: usize>(
     result
 }
 
+/// Groups `evaluations` at `positions` into rows of `N` values each, such that the row for every
+/// position in `folded_positions` contains all evaluations which fold into that position.
+fn get_layer_values<E: FieldElement, const N: usize>(
+    evaluations: &[E],
+    positions: &[usize],
+    folded_positions: &[usize],
+    domain_size: usize,
+) -> Result<Vec<[E; N]>, VerifierError> {
+    let row_length = domain_size / N;
+
+    let mut result = Vec::with_capacity(folded_positions.len());
+    for &folded_position in folded_positions {
+        let mut row = [E::ZERO; N];
+        for (i, value) in row.iter_mut().enumerate() {
+            let position = folded_position + i * row_length;
+            let idx = positions
+                .iter()
+                .position(|&v| v == position)
+                .ok_or(VerifierError::FirstLayerEvaluationMissing(position))?;
+            *value = evaluations[idx];
+        }
+        result.push(row);
+    }
+
+    Ok(result)
+}
+
 // Evaluates a polynomial with coefficients in an extension field at a point in the base field.
 pub fn eval_horner<E>(p: &[E], x: E::BaseField) -> E
 where
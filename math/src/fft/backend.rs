@@ -0,0 +1,113 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::field::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// FFT BACKEND
+// ================================================================================================
+/// Defines an implementation of FFT-based polynomial evaluation and interpolation.
+///
+/// All methods of this trait have default implementations which delegate to the CPU functions of
+/// the [fft](crate::fft) module, and thus, [CpuFftBackend] is just an empty implementation of
+/// this trait. A hardware-accelerated backend (e.g., one which offloads NTTs to a GPU via CUDA or
+/// Metal) can override any subset of the methods; the semantics of each method must be identical
+/// to the semantics of the corresponding function of the [fft](crate::fft) module.
+///
+/// Backends are selected at the type level: components which perform FFTs (e.g., trace
+/// low-degree extension in the prover, or the FRI prover) are parameterized by a type
+/// implementing this trait, and default to [CpuFftBackend].
+pub trait FftBackend: Send + Sync {
+    /// Evaluates polynomial `p` in-place over the domain of length `p.len()`.
+    ///
+    /// See [evaluate_poly()](crate::fft::evaluate_poly) for details.
+    fn evaluate_poly<B, E>(p: &mut [E], twiddles: &[B])
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+    {
+        super::evaluate_poly(p, twiddles)
+    }
+
+    /// Evaluates polynomial `p` over the domain of length `p.len()` * `blowup_factor` shifted by
+    /// `domain_offset` and returns the result.
+    ///
+    /// See [evaluate_poly_with_offset()](crate::fft::evaluate_poly_with_offset) for details.
+    fn evaluate_poly_with_offset<B, E>(
+        p: &[E],
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) -> Vec<E>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+    {
+        super::evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor)
+    }
+
+    /// Evaluates all polynomials in `polys` over the domain of length `polys[i].len()` *
+    /// `blowup_factor` shifted by `domain_offset`; all polynomials must have the same length.
+    ///
+    /// Returns `None` if this backend does not provide a batched implementation of polynomial
+    /// evaluation. In such a case, the caller is expected to fall back onto its own evaluation
+    /// strategy (e.g., the prover evaluates polynomials directly into a row-major matrix on the
+    /// CPU). Backends for which evaluating many polynomials at once is cheaper than evaluating
+    /// them one by one (e.g., due to the cost of transferring data to a GPU) should override this
+    /// method.
+    ///
+    /// The default implementation returns `None`.
+    fn evaluate_polys_with_offset<B, E>(
+        _polys: &[&[E]],
+        _twiddles: &[B],
+        _domain_offset: B,
+        _blowup_factor: usize,
+    ) -> Option<Vec<Vec<E>>>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+    {
+        None
+    }
+
+    /// Interpolates `evaluations` over the domain of length `evaluations.len()` into a polynomial
+    /// in coefficient form in-place.
+    ///
+    /// See [interpolate_poly()](crate::fft::interpolate_poly) for details.
+    fn interpolate_poly<B, E>(evaluations: &mut [E], inv_twiddles: &[B])
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+    {
+        super::interpolate_poly(evaluations, inv_twiddles)
+    }
+
+    /// Interpolates `evaluations` over the domain of length `evaluations.len()` shifted by
+    /// `domain_offset` into a polynomial in coefficient form in-place.
+    ///
+    /// See [interpolate_poly_with_offset()](crate::fft::interpolate_poly_with_offset) for
+    /// details.
+    fn interpolate_poly_with_offset<B, E>(
+        evaluations: &mut [E],
+        inv_twiddles: &[B],
+        domain_offset: B,
+    ) where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+    {
+        super::interpolate_poly_with_offset(evaluations, inv_twiddles, domain_offset)
+    }
+}
+
+// CPU BACKEND
+// ================================================================================================
+
+/// The default [FftBackend] which executes FFTs on the CPU.
+///
+/// When `concurrent` feature is enabled, FFTs are executed in multiple threads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuFftBackend;
+
+impl FftBackend for CpuFftBackend {}
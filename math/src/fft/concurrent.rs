@@ -122,7 +122,7 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
     // generator of the domain should be in the middle of twiddles
     let n = values.len();
     let g = twiddles[twiddles.len() / 2];
-    debug_assert_eq!(g.exp((n as u32).into()), E::BaseField::ONE);
+    debug_assert_eq!(g.exp((n as u64).into()), E::BaseField::ONE);
 
     let inner_len = 1_usize << (n.ilog2() / 2);
     let outer_len = n / inner_len;
@@ -145,7 +145,7 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
     values.par_chunks_mut(outer_len).enumerate().for_each(|(i, row)| {
         if i > 0 {
             let i = super::permute_index(inner_len, i);
-            let inner_twiddle = g.exp((i as u32).into());
+            let inner_twiddle = g.exp((i as u64).into());
             let mut outer_twiddle = inner_twiddle;
             for element in row.iter_mut().skip(1) {
                 *element = (*element).mul_base(outer_twiddle);
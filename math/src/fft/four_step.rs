@@ -0,0 +1,210 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Four-step FFT for very large domains.
+//!
+//! The input of size `n` is viewed as a matrix with `r` rows and `c` columns (where `r * c = n`
+//! and `r` is either `c` or `c / 2`), and the FFT is computed as follows:
+//! 1. Length-`r` FFTs are applied to all columns of the matrix.
+//! 2. Every element of the matrix at position `(i, j)` is multiplied by `g^(i * j)`, where `g` is
+//!    the generator of the domain of size `n`.
+//! 3. Length-`c` FFTs are applied to all rows of the matrix.
+//! 4. The matrix is transposed.
+//!
+//! To keep memory accesses sequential, the matrix is transposed before step 1 and after step 2
+//! so that all sub-FFTs are applied to contiguous rows. Each sub-FFT touches only `sqrt(n)`
+//! elements and thus fits into CPU caches, which is not the case for the recursive algorithms
+//! when `n` is large. When `concurrent` feature is enabled, sub-FFTs and transpositions are
+//! executed in multiple threads.
+
+use super::fft_inputs::FftInputs;
+use crate::field::{FieldElement, StarkField};
+use utils::{batch_iter_mut, uninit_vector};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Domains of this size or larger are transformed using the four-step algorithm.
+#[cfg(not(test))]
+pub const MIN_FOUR_STEP_SIZE: usize = 1 << 24;
+
+/// In tests, the threshold is lowered so that the four-step paths of the public FFT functions
+/// can be exercised on small domains.
+#[cfg(test)]
+pub const MIN_FOUR_STEP_SIZE: usize = 1 << 11;
+
+/// Size of a square block of elements moved together during matrix transposition.
+const TRANSPOSE_BLOCK_SIZE: usize = 16;
+
+// FOUR-STEP FFT
+// ================================================================================================
+
+/// Evaluates polynomial `p` in-place over the domain of length `p.len()`; the results are in
+/// natural order.
+pub fn evaluate_poly<B, E>(p: &mut [E], twiddles: &[B])
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    fft(p, twiddles);
+}
+
+/// Evaluates polynomial `p` over the domain of length `p.len() * blowup_factor` shifted by
+/// `domain_offset`; the results are in natural order.
+///
+/// The domain is split into `blowup_factor` cosets of size `p.len()`, the polynomial is
+/// evaluated over each coset using the four-step algorithm, and the evaluations are then
+/// interleaved to put them in the order of the full domain.
+pub fn evaluate_poly_with_offset<B, E>(
+    p: &[E],
+    twiddles: &[B],
+    domain_offset: B,
+    blowup_factor: usize,
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let domain_size = p.len() * blowup_factor;
+    let g = B::get_root_of_unity(domain_size.ilog2());
+
+    // evaluation at position k of coset j is the evaluation at domain_offset * g^(j + k * b)
+    let mut cosets = unsafe { uninit_vector::<E>(domain_size) };
+    let mut coset_offset = domain_offset;
+    for coset in cosets.chunks_mut(p.len()) {
+        coset.copy_from_slice(p);
+        shift_by_series(coset, B::ONE, coset_offset);
+        fft(coset, twiddles);
+        coset_offset *= g;
+    }
+
+    let mut result = unsafe { uninit_vector::<E>(domain_size) };
+    transpose(&cosets, &mut result, blowup_factor, p.len());
+    result
+}
+
+/// Interpolates `evaluations` over the domain of length `evaluations.len()` into a polynomial
+/// in coefficient form in-place.
+pub fn interpolate_poly<B, E>(evaluations: &mut [E], inv_twiddles: &[B])
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    fft(evaluations, inv_twiddles);
+    let inv_length = B::inv((evaluations.len() as u64).into());
+    shift_by_series(evaluations, inv_length, B::ONE);
+}
+
+/// Interpolates `evaluations` over the domain of length `evaluations.len()` and shifted by
+/// `domain_offset` into a polynomial in coefficient form in-place.
+pub fn interpolate_poly_with_offset<B, E>(
+    evaluations: &mut [E],
+    inv_twiddles: &[B],
+    domain_offset: B,
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    fft(evaluations, inv_twiddles);
+    let inv_length = B::inv((evaluations.len() as u64).into());
+    shift_by_series(evaluations, inv_length, B::inv(domain_offset));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes the FFT of `values` defined by `twiddles` and writes the results back into `values`
+/// in natural order.
+///
+/// `twiddles` are expected to be in the form returned by [get_twiddles()](super::get_twiddles)
+/// (or [get_inv_twiddles()](super::get_inv_twiddles)) for the domain of size `values.len()`. In
+/// this form, the first `m / 2` twiddles are the twiddles for the domain of size `m`, and thus,
+/// twiddles for sub-FFTs are just prefixes of `twiddles`.
+fn fft<B, E>(values: &mut [E], twiddles: &[B])
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let n = values.len();
+    debug_assert!(n >= 4, "four-step FFT requires at least 4 values");
+
+    let num_rows = 1 << (n.ilog2() / 2);
+    let num_cols = n / num_rows;
+
+    // twiddles are in bit-reversed order, and thus, g^1 is located at position n / 4
+    let root = twiddles[n / 4];
+
+    let mut buffer = unsafe { uninit_vector::<E>(n) };
+
+    // transpose the matrix so that its columns become rows, and apply FFT to each of the rows;
+    // then multiply element at position (j, i) of the transposed matrix by g^(i * j)
+    transpose(values, &mut buffer, num_rows, num_cols);
+    for_each_row(&mut buffer, num_rows, |j, row| {
+        row.fft_in_place(&twiddles[..num_rows / 2]);
+        row.permute();
+        row.shift_by_series(B::ONE, root.exp((j as u64).into()));
+    });
+
+    // transpose the matrix back and apply FFT to each of its rows
+    transpose(&buffer, values, num_cols, num_rows);
+    for_each_row(values, num_cols, |_, row| {
+        row.fft_in_place(&twiddles[..num_cols / 2]);
+        row.permute();
+    });
+
+    // transpose the matrix once more to put the results in natural order
+    transpose(values, &mut buffer, num_rows, num_cols);
+    values.copy_from_slice(&buffer);
+}
+
+/// Writes the transpose of `source`, which is a row-major matrix with the specified number of
+/// rows and columns, into `target`.
+fn transpose<E: FieldElement>(source: &[E], target: &mut [E], num_rows: usize, num_cols: usize) {
+    debug_assert_eq!(source.len(), num_rows * num_cols);
+    debug_assert_eq!(target.len(), num_rows * num_cols);
+
+    // each block of target rows is filled in square tiles to keep reads from the source local
+    let block_size = TRANSPOSE_BLOCK_SIZE.min(num_rows).min(num_cols);
+    for_each_row(target, num_rows * block_size, |block_idx, block| {
+        let first_col = block_idx * block_size;
+        for row_offset in (0..num_rows).step_by(block_size) {
+            for i in 0..block_size {
+                let target_row = &mut block[i * num_rows..(i + 1) * num_rows];
+                for j in row_offset..row_offset + block_size {
+                    target_row[j] = source[j * num_cols + first_col + i];
+                }
+            }
+        }
+    });
+}
+
+/// Multiplies the element at position i of `values` by `offset * increment^i`.
+fn shift_by_series<B, E>(values: &mut [E], offset: B, increment: B)
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    batch_iter_mut!(values, 1024, |batch: &mut [E], batch_offset: usize| {
+        let start = offset * increment.exp((batch_offset as u64).into());
+        batch.shift_by_series(start, increment);
+    });
+}
+
+/// Applies `op` to each row of the row-major matrix `values` with rows of the specified length;
+/// `op` receives the index of a row as its first argument.
+fn for_each_row<E, F>(values: &mut [E], row_len: usize, op: F)
+where
+    E: FieldElement,
+    F: Fn(usize, &mut [E]) + Send + Sync,
+{
+    #[cfg(not(feature = "concurrent"))]
+    values.chunks_mut(row_len).enumerate().for_each(|(i, row)| op(i, row));
+
+    #[cfg(feature = "concurrent")]
+    values.par_chunks_mut(row_len).enumerate().for_each(|(i, row)| op(i, row));
+}
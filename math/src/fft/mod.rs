@@ -10,6 +10,14 @@
 //! As compared to evaluation and interpolation functions available in the `polynom` module,
 //! these functions are much more efficient: their runtime complexity is O(`n` log `n`), where
 //! `n` is the domain size.
+//!
+//! For very large domains (2<sup>24</sup> elements or more), evaluation and interpolation functions
+//! use the four-step FFT algorithm, which splits the transform into many small FFTs which fit into
+//! CPU caches.
+//!
+//! Components which need to perform many FFTs (e.g., the prover) can be parameterized by an
+//! [FftBackend]; this allows replacing the default CPU implementation ([CpuFftBackend]) with a
+//! hardware-accelerated one.
 
 use crate::{
     fft::fft_inputs::FftInputs,
@@ -21,10 +29,18 @@ pub mod fft_inputs;
 pub mod real_u64;
 mod serial;
 
+mod four_step;
+
+mod backend;
+pub use backend::{CpuFftBackend, FftBackend};
+
 #[cfg(feature = "concurrent")]
 mod concurrent;
 
-use utils::collections::Vec;
+use utils::{collections::Vec, iter_mut};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 #[cfg(test)]
 mod tests;
@@ -101,9 +117,12 @@ where
         p.len()
     );
 
-    // when `concurrent` feature is enabled, run the concurrent version of the function; unless
-    // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
+    // for very large polynomials, use the four-step algorithm which makes better use of CPU
+    // caches; otherwise, when `concurrent` feature is enabled, run the concurrent version of the
+    // function; unless the polynomial is small, then don't bother with the concurrent version
+    if p.len() >= four_step::MIN_FOUR_STEP_SIZE {
+        four_step::evaluate_poly(p, twiddles);
+    } else if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
         #[cfg(feature = "concurrent")]
         concurrent::evaluate_poly(p, twiddles);
     } else {
@@ -195,9 +214,12 @@ where
     #[allow(unused_assignments)]
     let mut result = Vec::new();
 
-    // when `concurrent` feature is enabled, run the concurrent version of the function; unless
-    // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
+    // for very large polynomials, use the four-step algorithm which makes better use of CPU
+    // caches; otherwise, when `concurrent` feature is enabled, run the concurrent version of the
+    // function; unless the polynomial is small, then don't bother with the concurrent version
+    if p.len() >= four_step::MIN_FOUR_STEP_SIZE {
+        result = four_step::evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor);
+    } else if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
         #[cfg(feature = "concurrent")]
         {
             result =
@@ -285,9 +307,13 @@ where
         evaluations.len()
     );
 
-    // when `concurrent` feature is enabled, run the concurrent version of interpolate_poly;
-    // unless the number of evaluations is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && evaluations.len() >= MIN_CONCURRENT_SIZE {
+    // for a very large number of evaluations, use the four-step algorithm which makes better use
+    // of CPU caches; otherwise, when `concurrent` feature is enabled, run the concurrent version
+    // of interpolate_poly; unless the number of evaluations is small, then don't bother with the
+    // concurrent version
+    if evaluations.len() >= four_step::MIN_FOUR_STEP_SIZE {
+        four_step::interpolate_poly(evaluations, inv_twiddles);
+    } else if cfg!(feature = "concurrent") && evaluations.len() >= MIN_CONCURRENT_SIZE {
         #[cfg(feature = "concurrent")]
         concurrent::interpolate_poly(evaluations, inv_twiddles);
     } else {
@@ -377,9 +403,13 @@ pub fn interpolate_poly_with_offset<B, E>(
     );
     assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
 
-    // when `concurrent` feature is enabled, run the concurrent version of the function; unless
-    // the polynomial is small, then don't bother with the concurrent version
-    if cfg!(feature = "concurrent") && evaluations.len() >= MIN_CONCURRENT_SIZE {
+    // for a very large number of evaluations, use the four-step algorithm which makes better use
+    // of CPU caches; otherwise, when `concurrent` feature is enabled, run the concurrent version
+    // of the function; unless the polynomial is small, then don't bother with the concurrent
+    // version
+    if evaluations.len() >= four_step::MIN_FOUR_STEP_SIZE {
+        four_step::interpolate_poly_with_offset(evaluations, inv_twiddles, domain_offset);
+    } else if cfg!(feature = "concurrent") && evaluations.len() >= MIN_CONCURRENT_SIZE {
         #[cfg(feature = "concurrent")]
         concurrent::interpolate_poly_with_offset(evaluations, inv_twiddles, domain_offset);
     } else {
@@ -387,6 +417,156 @@ pub fn interpolate_poly_with_offset<B, E>(
     }
 }
 
+// COSET EVALUATION AND INTERPOLATION
+// ================================================================================================
+
+/// Evaluates a polynomial on all points of a multiplicative coset defined by the specified
+/// `offset` using the FFT algorithm.
+///
+/// Specifically, evaluates polynomial `p` at points `offset` * g<sup>i</sup> for i in
+/// 0..`p.len()`, where g is the generator of the multiplicative subgroup of size `p.len()`. The
+/// evaluation is done in-place, and upon completion, `p` contains evaluations of the polynomial
+/// in natural order.
+///
+/// Unlike [evaluate_poly_with_offset()], this function does not expand the domain; to evaluate a
+/// polynomial over a larger domain, `p` can be padded with zeros to the desired domain size.
+///
+/// The `twiddles` needed for evaluation can be obtained via `fft::get_twiddles()` function using
+/// `p.len()` as the domain size parameter.
+///
+/// When `concurrent` feature is enabled, the evaluation is done in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` is not a power of two.
+/// * Length of `twiddles` is not `p.len()` / 2.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size `p.len()`.
+/// * `offset` is ZERO.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{polynom, fft::*, get_power_series_with_offset};
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField};
+/// # use rand_utils::rand_vector;
+/// let n = 1024;
+/// let offset = BaseElement::GENERATOR;
+///
+/// // build a random polynomial
+/// let mut p: Vec<BaseElement> = rand_vector(n);
+///
+/// // evaluate the polynomial over the coset using regular polynomial evaluation
+/// let g = BaseElement::get_root_of_unity(n.ilog2());
+/// let coset = get_power_series_with_offset(g, offset, n);
+/// let expected = polynom::eval_many(&p, &coset);
+///
+/// // evaluate the polynomial over the coset using FFT-based evaluation
+/// let twiddles = get_twiddles::<BaseElement>(p.len());
+/// evaluate_poly_on_coset(&mut p, offset, &twiddles);
+///
+/// assert_eq!(expected, p);
+/// ```
+pub fn evaluate_poly_on_coset<B, E>(p: &mut [E], offset: B, twiddles: &[B])
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert_ne!(offset, B::ZERO, "coset offset cannot be zero");
+
+    // p(offset * x) is a polynomial with coefficients c_i * offset^i; thus, evaluating it over
+    // the subgroup is the same as evaluating p(x) over the coset
+    p.shift_by_series(B::ONE, offset);
+    evaluate_poly(p, twiddles);
+}
+
+/// Interpolates evaluations of a polynomial over a multiplicative coset defined by the specified
+/// `offset` into a polynomial in coefficient form using the FFT algorithm.
+///
+/// This is the inverse of [evaluate_poly_on_coset()]: `evaluations` are expected to be in natural
+/// order, and the interpolation is done in-place. This function is equivalent to
+/// [interpolate_poly_with_offset()] and is provided so that the order of its parameters matches
+/// [evaluate_poly_on_coset()].
+///
+/// The `inv_twiddles` needed for interpolation can be obtained via `fft::get_inv_twiddles()`
+/// function using `evaluations.len()` as the domain size parameter.
+///
+/// When `concurrent` feature is enabled, the interpolation is done in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `evaluations` is not a power of two.
+/// * Length of `inv_twiddles` is not `evaluations.len()` / 2.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `evaluations.len()`.
+/// * `offset` is ZERO.
+pub fn interpolate_poly_on_coset<B, E>(evaluations: &mut [E], offset: B, inv_twiddles: &[B])
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    interpolate_poly_with_offset(evaluations, inv_twiddles, offset);
+}
+
+/// Evaluates a polynomial over multiple multiplicative cosets defined by the specified `offsets`
+/// using the FFT algorithm.
+///
+/// Returns a vector of evaluations for each of the offsets; the evaluations for the i-th offset
+/// are the same as would be computed by [evaluate_poly_on_coset()] for a copy of `p` and
+/// `offsets[i]`. All cosets have size `p.len()`, and thus, the same `twiddles` are used for all
+/// of them.
+///
+/// When `concurrent` feature is enabled, the cosets are evaluated in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` is not a power of two.
+/// * Length of `twiddles` is not `p.len()` / 2.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size `p.len()`.
+/// * Any of the `offsets` is ZERO.
+pub fn evaluate_poly_on_cosets<B, E>(p: &[E], offsets: &[B], twiddles: &[B]) -> Vec<Vec<E>>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let mut result = offsets.iter().map(|_| p.to_vec()).collect::<Vec<_>>();
+    iter_mut!(result).zip(offsets).for_each(|(evaluations, &offset)| {
+        evaluate_poly_on_coset(evaluations, offset, twiddles);
+    });
+    result
+}
+
+/// Interpolates multiple sets of evaluations, each over its own multiplicative coset, into
+/// polynomials in coefficient form using the FFT algorithm.
+///
+/// The i-th set of `evaluations` is interpolated in-place over the coset defined by `offsets[i]`
+/// in the same way as done by [interpolate_poly_on_coset()]. All sets of evaluations must have the
+/// same length, and thus, the same `inv_twiddles` are used for all of them.
+///
+/// When `concurrent` feature is enabled, the interpolation is done in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * The number of `evaluations` sets is not equal to the number of `offsets`.
+/// * Length of any set of evaluations is not a power of two or is not `inv_twiddles.len()` * 2.
+/// * Any of the `offsets` is ZERO.
+pub fn interpolate_poly_on_cosets<B, E>(
+    evaluations: &mut [Vec<E>],
+    offsets: &[B],
+    inv_twiddles: &[B],
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert_eq!(
+        evaluations.len(),
+        offsets.len(),
+        "number of evaluation sets must be equal to the number of offsets, but was {}",
+        evaluations.len()
+    );
+    iter_mut!(evaluations).zip(offsets).for_each(|(evaluations, &offset)| {
+        interpolate_poly_on_coset(evaluations, offset, inv_twiddles);
+    });
+}
+
 // RAW FFT ALGORITHM
 // ================================================================================================
 
@@ -500,7 +680,7 @@ where
         "multiplicative subgroup of size {domain_size} does not exist in the specified base field"
     );
     let root = B::get_root_of_unity(domain_size.ilog2());
-    let inv_root = root.exp((domain_size as u32 - 1).into());
+    let inv_root = root.exp(((domain_size - 1) as u64).into());
     let mut inv_twiddles = get_power_series(inv_root, domain_size / 2);
     permute(&mut inv_twiddles);
     inv_twiddles
@@ -5,9 +5,9 @@
 
 use crate::{
     fft::fft_inputs::FftInputs,
-    field::{f128::BaseElement, StarkField},
+    field::{f128::BaseElement, FieldElement, StarkField},
     polynom,
-    utils::get_power_series,
+    utils::{get_power_series, get_power_series_with_offset},
 };
 use rand_utils::rand_vector;
 use utils::collections::Vec;
@@ -70,6 +70,96 @@ fn fft_get_twiddles() {
     assert_eq!(expected, twiddles);
 }
 
+#[test]
+fn fft_four_step() {
+    // check domains where the number of rows and columns are the same and where they differ
+    for n in [16_usize, 2048, 4096] {
+        let p: Vec<BaseElement> = rand_vector(n);
+        let twiddles = super::get_twiddles::<BaseElement>(n);
+        let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+
+        let mut expected = p.clone();
+        super::serial::evaluate_poly(&mut expected, &twiddles);
+        let mut actual = p.clone();
+        super::four_step::evaluate_poly(&mut actual, &twiddles);
+        assert_eq!(expected, actual);
+
+        super::four_step::interpolate_poly(&mut actual, &inv_twiddles);
+        assert_eq!(p, actual);
+
+        let offset = BaseElement::GENERATOR;
+        let mut expected = p.clone();
+        super::serial::interpolate_poly_with_offset(&mut expected, &inv_twiddles, offset);
+        let mut actual = p.clone();
+        super::four_step::interpolate_poly_with_offset(&mut actual, &inv_twiddles, offset);
+        assert_eq!(expected, actual);
+
+        let blowup_factor = 4;
+        let expected =
+            super::serial::evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+        let actual =
+            super::four_step::evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+        assert_eq!(expected, actual);
+    }
+
+    // the public functions dispatch to the four-step algorithm for large domains
+    let n = super::four_step::MIN_FOUR_STEP_SIZE;
+    let p: Vec<BaseElement> = rand_vector(n);
+    let twiddles = super::get_twiddles::<BaseElement>(n);
+    let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+    let offset = BaseElement::GENERATOR;
+
+    let mut expected = p.clone();
+    super::serial::evaluate_poly(&mut expected, &twiddles);
+    let mut actual = p.clone();
+    super::evaluate_poly(&mut actual, &twiddles);
+    assert_eq!(expected, actual);
+
+    super::interpolate_poly(&mut actual, &inv_twiddles);
+    assert_eq!(p, actual);
+
+    let blowup_factor = 2;
+    let expected = super::serial::evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+    let actual = super::evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+    assert_eq!(expected, actual);
+
+    let mut actual = p.clone();
+    super::evaluate_poly_on_coset(&mut actual, offset, &twiddles);
+    super::interpolate_poly_with_offset(&mut actual, &inv_twiddles, offset);
+    assert_eq!(p, actual);
+}
+
+// COSET EVALUATION AND INTERPOLATION
+// ================================================================================================
+
+#[test]
+fn fft_on_cosets() {
+    let n = 64_usize;
+    let g = BaseElement::get_root_of_unity(n.ilog2());
+    let offsets = [BaseElement::GENERATOR, BaseElement::new(7), g.exp(3)];
+    let p: Vec<BaseElement> = rand_vector(n);
+
+    let twiddles = super::get_twiddles::<BaseElement>(n);
+    let mut evaluations = super::evaluate_poly_on_cosets(&p, &offsets, &twiddles);
+    for (coset_evaluations, &offset) in evaluations.iter().zip(offsets.iter()) {
+        let coset = get_power_series_with_offset(g, offset, n);
+        assert_eq!(polynom::eval_many(&p, &coset), *coset_evaluations);
+    }
+
+    // evaluating over a coset with offset 1 is the same as evaluating over the subgroup
+    let mut expected = p.clone();
+    super::evaluate_poly(&mut expected, &twiddles);
+    let mut actual = p.clone();
+    super::evaluate_poly_on_coset(&mut actual, BaseElement::ONE, &twiddles);
+    assert_eq!(expected, actual);
+
+    let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+    super::interpolate_poly_on_cosets(&mut evaluations, &offsets, &inv_twiddles);
+    for coefficients in evaluations {
+        assert_eq!(p, coefficients);
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -331,7 +331,7 @@ impl<B: ExtensibleField<3>> From<u8> for CubeExtension<B> {
     }
 }
 
-impl<'a, B: ExtensibleField<3>> TryFrom<&'a [u8]> for CubeExtension<B> {
+impl<B: ExtensibleField<3>> TryFrom<&[u8]> for CubeExtension<B> {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -358,7 +358,7 @@ impl<'a, B: ExtensibleField<3>> TryFrom<&'a [u8]> for CubeExtension<B> {
 
 impl<B: ExtensibleField<3>> AsBytes for CubeExtension<B> {
     fn as_bytes(&self) -> &[u8] {
-        // TODO: take endianness into account
+        // the resulting bytes are in the native byte order of the target
         let self_ptr: *const Self = self;
         unsafe { slice::from_raw_parts(self_ptr as *const u8, Self::ELEMENT_BYTES) }
     }
@@ -447,12 +447,12 @@ mod tests {
         ];
 
         let mut expected = vec![];
-        expected.extend_from_slice(&source[0].0.inner().to_le_bytes());
-        expected.extend_from_slice(&source[0].1.inner().to_le_bytes());
-        expected.extend_from_slice(&source[0].2.inner().to_le_bytes());
-        expected.extend_from_slice(&source[1].0.inner().to_le_bytes());
-        expected.extend_from_slice(&source[1].1.inner().to_le_bytes());
-        expected.extend_from_slice(&source[1].2.inner().to_le_bytes());
+        expected.extend_from_slice(&source[0].0.inner().to_ne_bytes());
+        expected.extend_from_slice(&source[0].1.inner().to_ne_bytes());
+        expected.extend_from_slice(&source[0].2.inner().to_ne_bytes());
+        expected.extend_from_slice(&source[1].0.inner().to_ne_bytes());
+        expected.extend_from_slice(&source[1].1.inner().to_ne_bytes());
+        expected.extend_from_slice(&source[1].2.inner().to_ne_bytes());
 
         assert_eq!(expected, CubeExtension::<BaseElement>::elements_as_bytes(&source));
     }
@@ -465,13 +465,13 @@ mod tests {
         ];
 
         let mut bytes = vec![];
-        bytes.extend_from_slice(&elements[0].0.inner().to_le_bytes());
-        bytes.extend_from_slice(&elements[0].1.inner().to_le_bytes());
-        bytes.extend_from_slice(&elements[0].2.inner().to_le_bytes());
-        bytes.extend_from_slice(&elements[1].0.inner().to_le_bytes());
-        bytes.extend_from_slice(&elements[1].1.inner().to_le_bytes());
-        bytes.extend_from_slice(&elements[1].2.inner().to_le_bytes());
-        bytes.extend_from_slice(&BaseElement::new(5).inner().to_le_bytes());
+        bytes.extend_from_slice(&elements[0].0.inner().to_ne_bytes());
+        bytes.extend_from_slice(&elements[0].1.inner().to_ne_bytes());
+        bytes.extend_from_slice(&elements[0].2.inner().to_ne_bytes());
+        bytes.extend_from_slice(&elements[1].0.inner().to_ne_bytes());
+        bytes.extend_from_slice(&elements[1].1.inner().to_ne_bytes());
+        bytes.extend_from_slice(&elements[1].2.inner().to_ne_bytes());
+        bytes.extend_from_slice(&BaseElement::new(5).inner().to_ne_bytes());
 
         let result = unsafe { CubeExtension::<BaseElement>::bytes_as_elements(&bytes[..48]) };
         assert!(result.is_ok());
@@ -325,7 +325,7 @@ impl<B: ExtensibleField<2>> From<u8> for QuadExtension<B> {
     }
 }
 
-impl<'a, B: ExtensibleField<2>> TryFrom<&'a [u8]> for QuadExtension<B> {
+impl<B: ExtensibleField<2>> TryFrom<&[u8]> for QuadExtension<B> {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -352,7 +352,7 @@ impl<'a, B: ExtensibleField<2>> TryFrom<&'a [u8]> for QuadExtension<B> {
 
 impl<B: ExtensibleField<2>> AsBytes for QuadExtension<B> {
     fn as_bytes(&self) -> &[u8] {
-        // TODO: take endianness into account
+        // the resulting bytes are in the native byte order of the target
         let self_ptr: *const Self = self;
         unsafe { slice::from_raw_parts(self_ptr as *const u8, Self::ELEMENT_BYTES) }
     }
@@ -439,10 +439,10 @@ mod tests {
         ];
 
         let mut expected = vec![];
-        expected.extend_from_slice(&source[0].0.inner().to_le_bytes());
-        expected.extend_from_slice(&source[0].1.inner().to_le_bytes());
-        expected.extend_from_slice(&source[1].0.inner().to_le_bytes());
-        expected.extend_from_slice(&source[1].1.inner().to_le_bytes());
+        expected.extend_from_slice(&source[0].0.inner().to_ne_bytes());
+        expected.extend_from_slice(&source[0].1.inner().to_ne_bytes());
+        expected.extend_from_slice(&source[1].0.inner().to_ne_bytes());
+        expected.extend_from_slice(&source[1].1.inner().to_ne_bytes());
 
         assert_eq!(expected, QuadExtension::<BaseElement>::elements_as_bytes(&source));
     }
@@ -455,11 +455,11 @@ mod tests {
         ];
 
         let mut bytes = vec![];
-        bytes.extend_from_slice(&elements[0].0.inner().to_le_bytes());
-        bytes.extend_from_slice(&elements[0].1.inner().to_le_bytes());
-        bytes.extend_from_slice(&elements[1].0.inner().to_le_bytes());
-        bytes.extend_from_slice(&elements[1].1.inner().to_le_bytes());
-        bytes.extend_from_slice(&BaseElement::new(5).inner().to_le_bytes());
+        bytes.extend_from_slice(&elements[0].0.inner().to_ne_bytes());
+        bytes.extend_from_slice(&elements[0].1.inner().to_ne_bytes());
+        bytes.extend_from_slice(&elements[1].0.inner().to_ne_bytes());
+        bytes.extend_from_slice(&elements[1].1.inner().to_ne_bytes());
+        bytes.extend_from_slice(&BaseElement::new(5).inner().to_ne_bytes());
         let result = unsafe { QuadExtension::<BaseElement>::bytes_as_elements(&bytes[..32]) };
         assert!(result.is_ok());
         assert_eq!(elements, result.unwrap());
@@ -5,10 +5,9 @@
 
 use super::{
     AsBytes, BaseElement, ByteReader, Deserializable, DeserializationError, FieldElement,
-    StarkField, Vec, M,
+    Serializable, StarkField, Vec, C, M,
 };
 use crate::field::{ExtensionOf, QuadExtension};
-use core::convert::TryFrom;
 use num_bigint::BigUint;
 use rand_utils::{rand_value, rand_vector};
 use utils::SliceReader;
@@ -29,6 +28,7 @@ fn add() {
     let t = BaseElement::from(BaseElement::MODULUS - 1);
     assert_eq!(BaseElement::ZERO, t + BaseElement::ONE);
     assert_eq!(BaseElement::ONE, t + BaseElement::from(2u8));
+    assert_eq!(BaseElement::from(BaseElement::MODULUS - 2), t + t);
 
     // test random values
     let r1: BaseElement = rand_value();
@@ -73,6 +73,16 @@ fn mul() {
     let t = (m + 1) / 2;
     assert_eq!(BaseElement::ONE, BaseElement::from(t) * BaseElement::from(2u8));
 
+    // test values which exercise all carries in the reduction of 256-bit products
+    let edge_values = [1, 2, 1 << 64, (1 << 64) - 1, 1 << 127, C, M - C, M - (1 << 64), m - 1];
+    for &a in edge_values.iter() {
+        for &b in edge_values.iter() {
+            let (r1, r2) = (BaseElement::new(a), BaseElement::new(b));
+            let expected = (r1.to_big_uint() * r2.to_big_uint()) % BigUint::from(M);
+            assert_eq!(BaseElement::from_big_uint(expected), r1 * r2, "failed for: {a} * {b}");
+        }
+    }
+
     // test random values
     let v1: Vec<BaseElement> = rand_vector(1000);
     let v2: Vec<BaseElement> = rand_vector(1000);
@@ -103,6 +113,33 @@ fn inv() {
     }
 }
 
+#[test]
+fn exp_const() {
+    let r: BaseElement = rand_value();
+    assert_eq!(BaseElement::ONE, r.exp_const::<0>());
+    assert_eq!(r, r.exp_const::<1>());
+    assert_eq!(r.exp(3), r.exp_const::<3>());
+    assert_eq!(r.exp(5), r.exp_const::<5>());
+    assert_eq!(r.exp(7), r.exp_const::<7>());
+    assert_eq!(r.exp(u64::MAX as u128), r.exp_const::<{ u64::MAX }>());
+}
+
+#[test]
+fn roots() {
+    let inv_3 = 226854911280625642308916371969163307691;
+    let inv_5 = 272225893536750770770699646362995969229;
+
+    let x: Vec<BaseElement> = rand_vector(100);
+    for r in x {
+        assert_eq!(r.exp(inv_3), r.cube_root());
+        assert_eq!(r, r.cube_root().cube());
+        assert_eq!(r.exp(inv_5), r.fifth_root());
+        assert_eq!(r, r.fifth_root().exp_const::<5>());
+    }
+    assert_eq!(BaseElement::ZERO, BaseElement::ZERO.cube_root());
+    assert_eq!(BaseElement::ONE, BaseElement::ONE.fifth_root());
+}
+
 #[test]
 fn conjugate() {
     let a: BaseElement = rand_value();
@@ -148,6 +185,25 @@ fn quad_mul_base() {
 // ================================================================================================
 
 #[test]
+fn canonical_encoding() {
+    // canonical encodings are little-endian regardless of the native byte order of the target
+    let e = BaseElement::new(0x0102030405060708090a0b0c0d0e0f10);
+    assert_eq!(vec![16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1], e.to_bytes());
+
+    let e: BaseElement = rand_value();
+    assert_eq!(e.as_int().to_le_bytes().to_vec(), e.to_bytes());
+
+    // extension field elements are encoded as a concatenation of their coefficients
+    let e: QuadExtension<BaseElement> = rand_value();
+    let expected: Vec<u8> = QuadExtension::slice_as_base_elements(&[e])
+        .iter()
+        .flat_map(|c| c.to_bytes())
+        .collect();
+    assert_eq!(expected, e.to_bytes());
+}
+
+#[test]
+#[cfg(target_endian = "little")]
 fn elements_as_bytes() {
     let source = vec![
         BaseElement::new(1),
@@ -166,6 +222,7 @@ fn elements_as_bytes() {
 }
 
 #[test]
+#[cfg(target_endian = "little")]
 fn bytes_as_elements() {
     let bytes: Vec<u8> = vec![
         1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -252,6 +309,6 @@ impl BaseElement {
         let bytes = value.to_bytes_le();
         let mut buffer = [0u8; 16];
         buffer[0..bytes.len()].copy_from_slice(&bytes);
-        BaseElement::try_from(buffer).unwrap()
+        BaseElement::from(buffer)
     }
 }
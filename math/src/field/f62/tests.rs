@@ -239,6 +239,24 @@ fn try_from_slice() {
     assert!(result.is_err());
 }
 
+#[test]
+fn canonical_encoding() {
+    // canonical encodings are little-endian regardless of the native byte order of the target
+    let e = BaseElement::new(0x0102030405060708);
+    assert_eq!(vec![8, 7, 6, 5, 4, 3, 2, 1], e.to_bytes());
+
+    let e: BaseElement = rand_value();
+    assert_eq!(e.as_int().to_le_bytes().to_vec(), e.to_bytes());
+
+    // extension field elements are encoded as a concatenation of their coefficients
+    let e: QuadExtension<BaseElement> = rand_value();
+    let expected: Vec<u8> = QuadExtension::slice_as_base_elements(&[e])
+        .iter()
+        .flat_map(|c| c.to_bytes())
+        .collect();
+    assert_eq!(expected, e.to_bytes());
+}
+
 #[test]
 fn elements_as_bytes() {
     let source = vec![
@@ -249,10 +267,10 @@ fn elements_as_bytes() {
     ];
 
     let mut expected = vec![];
-    expected.extend_from_slice(&source[0].0.to_le_bytes());
-    expected.extend_from_slice(&source[1].0.to_le_bytes());
-    expected.extend_from_slice(&source[2].0.to_le_bytes());
-    expected.extend_from_slice(&source[3].0.to_le_bytes());
+    expected.extend_from_slice(&source[0].0.to_ne_bytes());
+    expected.extend_from_slice(&source[1].0.to_ne_bytes());
+    expected.extend_from_slice(&source[2].0.to_ne_bytes());
+    expected.extend_from_slice(&source[3].0.to_ne_bytes());
 
     assert_eq!(expected, BaseElement::elements_as_bytes(&source));
 }
@@ -267,11 +285,11 @@ fn bytes_as_elements() {
     ];
 
     let mut bytes = vec![];
-    bytes.extend_from_slice(&elements[0].0.to_le_bytes());
-    bytes.extend_from_slice(&elements[1].0.to_le_bytes());
-    bytes.extend_from_slice(&elements[2].0.to_le_bytes());
-    bytes.extend_from_slice(&elements[3].0.to_le_bytes());
-    bytes.extend_from_slice(&BaseElement::new(5).0.to_le_bytes());
+    bytes.extend_from_slice(&elements[0].0.to_ne_bytes());
+    bytes.extend_from_slice(&elements[1].0.to_ne_bytes());
+    bytes.extend_from_slice(&elements[2].0.to_ne_bytes());
+    bytes.extend_from_slice(&elements[3].0.to_ne_bytes());
+    bytes.extend_from_slice(&BaseElement::new(5).0.to_ne_bytes());
 
     let result = unsafe { BaseElement::bytes_as_elements(&bytes[..32]) };
     assert!(result.is_ok());
@@ -195,7 +195,7 @@ impl FieldElement for BaseElement {
     // --------------------------------------------------------------------------------------------
 
     fn elements_as_bytes(elements: &[Self]) -> &[u8] {
-        // TODO: take endianness into account.
+        // the resulting bytes are in the native byte order of the target
         let p = elements.as_ptr();
         let len = elements.len() * Self::ELEMENT_BYTES;
         unsafe { slice::from_raw_parts(p as *const u8, len) }
@@ -286,6 +286,15 @@ impl StarkField for BaseElement {
         let (r, c) = 0u64.overflowing_sub(b);
         r.wrapping_sub(0u32.wrapping_sub(c as u32) as u64)
     }
+
+    fn try_from_repr(value: Self::PositiveInteger) -> Result<Self, DeserializationError> {
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(Self::new(value))
+    }
 }
 
 impl Randomizable for BaseElement {
@@ -556,7 +565,7 @@ impl From<[u8; 8]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -604,7 +613,7 @@ impl From<BaseElement> for u64 {
 
 impl AsBytes for BaseElement {
     fn as_bytes(&self) -> &[u8] {
-        // TODO: take endianness into account
+        // the resulting bytes are in the native byte order of the target
         let self_ptr: *const BaseElement = self;
         unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
     }
@@ -622,13 +631,7 @@ impl Serializable for BaseElement {
 
 impl Deserializable for BaseElement {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let value = source.read_u64()?;
-        if value >= M {
-            return Err(DeserializationError::InvalidValue(format!(
-                "invalid field element: value {value} is greater than or equal to the field modulus"
-            )));
-        }
-        Ok(Self::new(value))
+        Self::try_from_repr(source.read_u64()?)
     }
 }
 
@@ -115,6 +115,36 @@ pub trait FieldElement:
         self * self * self
     }
 
+    /// Exponentiates this field element by the constant exponent `P`.
+    ///
+    /// Since the exponent is known at compile time, the square-and-multiply loop is fully
+    /// unrolled by the compiler; thus, this is considerably faster than [exp()](Self::exp) for
+    /// small exponents (e.g., S-Box exponents of arithmetization-friendly hash functions).
+    #[inline(always)]
+    #[must_use]
+    fn exp_const<const P: u64>(self) -> Self {
+        match P {
+            0 => Self::ONE,
+            1 => self,
+            2 => self.square(),
+            3 => self.cube(),
+            _ => {
+                // left-to-right square-and-multiply over the bits of the exponent, skipping the
+                // most significant bit
+                let mut result = self;
+                let mut i = u64::BITS - 1 - P.leading_zeros();
+                while i > 0 {
+                    i -= 1;
+                    result = result.square();
+                    if (P >> i) & 1 == 1 {
+                        result *= self;
+                    }
+                }
+                result
+            }
+        }
+    }
+
     /// Exponentiates this field element by `power` parameter.
     #[must_use]
     fn exp(self, power: Self::PositiveInteger) -> Self {
@@ -191,14 +221,16 @@ pub trait FieldElement:
     ///
     /// The elements may be in the internal representation rather than in the canonical
     /// representation. This conversion is intended to be zero-copy (i.e. by re-interpreting the
-    /// underlying memory).
+    /// underlying memory), and thus, the bytes are in the native byte order of the target. The
+    /// output is therefore not portable across architectures; to get a canonical little-endian
+    /// encoding of elements, they should be serialized via [Serializable].
     fn elements_as_bytes(elements: &[Self]) -> &[u8];
 
     /// Converts a list of bytes into a list of field elements.
     ///
     /// The elements are assumed to encoded in the internal representation rather than in the
-    /// canonical representation. The conversion is intended to be zero-copy (i.e. by
-    /// re-interpreting the underlying memory).
+    /// canonical representation, and to be in the native byte order of the target. The conversion
+    /// is intended to be zero-copy (i.e. by re-interpreting the underlying memory).
     ///
     /// # Errors
     /// An error is returned if:
@@ -210,6 +242,35 @@ pub trait FieldElement:
     /// field elements according to their internal representation.
     unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError>;
 
+    /// Returns a field element decoded from its canonical byte encoding.
+    ///
+    /// The canonical encoding of an element consists of exactly
+    /// [ELEMENT_BYTES](Self::ELEMENT_BYTES) bytes, and is the encoding produced by
+    /// [Serializable::write_into()]. Unlike conversions which silently reduce values modulo the
+    /// field modulus, this function accepts exactly one encoding per element, and thus, can be
+    /// used to decode untrusted data (e.g., data which is later hashed).
+    ///
+    /// # Errors
+    /// Returns an error if the number of provided bytes is not equal to `ELEMENT_BYTES`, or if
+    /// the bytes are not a canonical encoding of a field element (e.g., if any of the encoded
+    /// values is not smaller than the field modulus).
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, DeserializationError> {
+        if bytes.len() != Self::ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid number of bytes for a field element; expected {} bytes, but was {} bytes",
+                Self::ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let element = Self::read_from_bytes(bytes)?;
+        if element.to_bytes() != bytes {
+            return Err(DeserializationError::InvalidValue(
+                "bytes are not a canonical encoding of a field element".into(),
+            ));
+        }
+        Ok(element)
+    }
+
     // UTILITIES
     // --------------------------------------------------------------------------------------------
 
@@ -263,6 +324,16 @@ pub trait StarkField: FieldElement<BaseField = Self> {
 
     /// Returns a canonical integer representation of this field element.
     fn as_int(&self) -> Self::PositiveInteger;
+
+    /// Returns a field element with the specified canonical integer representation.
+    ///
+    /// This is the inverse of [as_int()](Self::as_int). Unlike conversions from integers (which
+    /// silently reduce values modulo the field modulus), this function accepts only canonical
+    /// representations.
+    ///
+    /// # Errors
+    /// Returns an error if `value` is greater than or equal to the field modulus.
+    fn try_from_repr(value: Self::PositiveInteger) -> Result<Self, DeserializationError>;
 }
 
 // EXTENSIBLE FIELD
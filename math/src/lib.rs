@@ -82,6 +82,8 @@
 //!   - [evaluate_poly_with_offset()](fft::evaluate_poly_with_offset())
 //!   - [interpolate_poly()](fft::interpolate_poly())
 //!   - [interpolate_poly_with_offset()][fft::interpolate_poly_with_offset()]
+//!   - [evaluate_poly_on_cosets()](fft::evaluate_poly_on_cosets())
+//!   - [interpolate_poly_on_cosets()](fft::interpolate_poly_on_cosets())
 //!   - [get_twiddles()](fft::get_twiddles())
 //!   - [get_inv_twiddles()](fft::get_twiddles())
 //!
@@ -11,6 +11,7 @@
 //! - Polynomial addition, subtraction, multiplication, and division.
 //! - Synthetic polynomial division for efficient division by polynomials of the form
 //!   `x`^`a` - `b`.
+//! - Evaluation and multiplication of sparse polynomials via [SparsePolynomial].
 //!
 //! In the context of this module any slice of field elements is considered to be a polynomial
 //! in reverse coefficient form. A few examples:
@@ -28,6 +29,9 @@ use crate::{field::FieldElement, utils::batch_inversion};
 use core::mem;
 use utils::{collections::Vec, group_vector_elements};
 
+mod sparse;
+pub use sparse::SparsePolynomial;
+
 #[cfg(test)]
 mod tests;
 
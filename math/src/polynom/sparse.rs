@@ -0,0 +1,213 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::field::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// SPARSE POLYNOMIAL
+// ================================================================================================
+/// A polynomial described by its non-zero terms.
+///
+/// Each term is a tuple `(degree, coefficient)`; terms are kept sorted by degree in increasing
+/// order, no two terms have the same degree, and no term has a zero coefficient. A polynomial
+/// without terms represents the zero polynomial.
+///
+/// Sparse representation is useful for polynomials of high degree with only a few non-zero
+/// coefficients, such as vanishing polynomials of the form $(x^n - c)$ and products of several
+/// such polynomials. These can be evaluated in time proportional to the number of terms rather
+/// than the degree of the polynomial.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::SparsePolynomial;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// // p(x) = (x^4 - 1) * (x^2 - 2) = x^6 - 2 * x^4 - x^2 + 2
+/// let p = SparsePolynomial::from_binomials(&[(4, BaseElement::ONE), (2, BaseElement::new(2))]);
+/// assert_eq!(6, p.degree());
+/// assert_eq!(4, p.num_terms());
+///
+/// // p(2) = 15 * 2
+/// assert_eq!(BaseElement::new(30), p.eval(BaseElement::new(2)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparsePolynomial<E: FieldElement> {
+    terms: Vec<(usize, E)>,
+}
+
+impl<E: FieldElement> SparsePolynomial<E> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new sparse polynomial built from the provided terms.
+    ///
+    /// Terms may be specified in any order; coefficients of terms with the same degree are added
+    /// together, and terms with zero coefficients are discarded.
+    pub fn new(mut terms: Vec<(usize, E)>) -> Self {
+        terms.sort_unstable_by_key(|&(degree, _)| degree);
+        let mut result: Vec<(usize, E)> = Vec::with_capacity(terms.len());
+        for (degree, coeff) in terms {
+            match result.last_mut() {
+                Some(last) if last.0 == degree => last.1 += coeff,
+                _ => result.push((degree, coeff)),
+            }
+        }
+        result.retain(|&(_, coeff)| coeff != E::ZERO);
+        SparsePolynomial { terms: result }
+    }
+
+    /// Returns a sparse polynomial built from the non-zero coefficients of the provided dense
+    /// polynomial (in reverse coefficient form).
+    pub fn from_dense(p: &[E]) -> Self {
+        let terms = p
+            .iter()
+            .enumerate()
+            .filter(|(_, &coeff)| coeff != E::ZERO)
+            .map(|(degree, &coeff)| (degree, coeff))
+            .collect();
+        SparsePolynomial { terms }
+    }
+
+    /// Returns a polynomial $(x^n - c)$, where $n$ is the specified `degree` and $c$ is the
+    /// specified `constant`.
+    ///
+    /// # Panics
+    /// Panics if `degree` is zero.
+    pub fn binomial(degree: usize, constant: E) -> Self {
+        assert!(degree > 0, "binomial degree must be greater than zero");
+        Self::new(vec![(0, -constant), (degree, E::ONE)])
+    }
+
+    /// Returns a product of polynomials $(x^{n_i} - c_i)$ for all $(n_i, c_i)$ in the specified
+    /// list of `binomials`.
+    ///
+    /// This is the form in which numerators of constraint divisors are described. For an empty
+    /// list, the constant polynomial $1$ is returned.
+    ///
+    /// # Panics
+    /// Panics if the degree of any of the binomials is zero.
+    pub fn from_binomials(binomials: &[(usize, E)]) -> Self {
+        binomials.iter().fold(Self::new(vec![(0, E::ONE)]), |acc, &(degree, constant)| {
+            acc.mul(&Self::binomial(degree, constant))
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the non-zero terms of this polynomial sorted by degree in increasing order.
+    pub fn terms(&self) -> &[(usize, E)] {
+        &self.terms
+    }
+
+    /// Returns the number of non-zero terms in this polynomial.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns the degree of this polynomial; the degree of the zero polynomial is assumed to be
+    /// zero.
+    pub fn degree(&self) -> usize {
+        self.terms.last().map_or(0, |&(degree, _)| degree)
+    }
+
+    /// Returns true if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns this polynomial in dense (reverse coefficient) form.
+    pub fn to_dense(&self) -> Vec<E> {
+        let mut result = E::zeroed_vector(self.degree() + 1);
+        for &(degree, coeff) in self.terms.iter() {
+            result[degree] = coeff;
+        }
+        result
+    }
+
+    // EVALUATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates this polynomial at the provided `x` coordinate.
+    ///
+    /// Powers of `x` are computed incrementally from one term to the next, and thus, the cost of
+    /// evaluation is logarithmic in the degree of the polynomial and linear in the number of its
+    /// terms.
+    pub fn eval<F>(&self, x: F) -> F
+    where
+        F: FieldElement + From<E>,
+    {
+        let mut result = F::ZERO;
+        let mut x_power = F::ONE;
+        let mut prev_degree = 0;
+        for &(degree, coeff) in self.terms.iter() {
+            x_power *= x.exp(((degree - prev_degree) as u64).into());
+            prev_degree = degree;
+            result += F::from(coeff) * x_power;
+        }
+        result
+    }
+
+    /// Evaluates this polynomial over a multiplicative coset of the specified size and offset.
+    ///
+    /// Specifically, returns evaluations of the polynomial at points $s \cdot g^i$ for
+    /// $i = 0, ..., n - 1$, where $n$ is the `domain_size`, $s$ is the `domain_offset`, and $g$ is
+    /// the generator of the multiplicative subgroup of size $n$. Each term is evaluated using a
+    /// single multiplication per domain point.
+    ///
+    /// # Panics
+    /// Panics if `domain_size` is not a power of two or is greater than the size of the largest
+    /// multiplicative subgroup of the base field.
+    pub fn eval_over_domain(&self, domain_size: usize, domain_offset: E::BaseField) -> Vec<E> {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two, but was {domain_size}"
+        );
+        let g = E::BaseField::get_root_of_unity(domain_size.ilog2());
+
+        let mut result = E::zeroed_vector(domain_size);
+        for &(degree, coeff) in self.terms.iter() {
+            let degree = (degree as u64).into();
+            let step = g.exp(degree);
+            let mut term = coeff.mul_base(domain_offset.exp(degree));
+            for value in result.iter_mut() {
+                *value += term;
+                term = term.mul_base(step);
+            }
+        }
+        result
+    }
+
+    // ARITHMETIC OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a product of this polynomial and the `other` sparse polynomial.
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut terms = Vec::with_capacity(self.terms.len() * other.terms.len());
+        for &(a_degree, a_coeff) in self.terms.iter() {
+            for &(b_degree, b_coeff) in other.terms.iter() {
+                terms.push((a_degree + b_degree, a_coeff * b_coeff));
+            }
+        }
+        Self::new(terms)
+    }
+
+    /// Returns a product of this polynomial and the provided dense polynomial `p` (in reverse
+    /// coefficient form); the result is a dense polynomial.
+    ///
+    /// The cost of multiplication is proportional to the number of terms in this polynomial times
+    /// the length of `p`.
+    pub fn mul_dense(&self, p: &[E]) -> Vec<E> {
+        if self.is_zero() || p.is_empty() {
+            return Vec::new();
+        }
+        let mut result = E::zeroed_vector(self.degree() + p.len());
+        for &(degree, coeff) in self.terms.iter() {
+            for (i, &p_coeff) in p.iter().enumerate() {
+                result[degree + i] += coeff * p_coeff;
+            }
+        }
+        result
+    }
+}
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::remove_leading_zeros;
+use super::{remove_leading_zeros, SparsePolynomial};
 use crate::{
     field::{f128::BaseElement, FieldElement, StarkField},
     utils::get_power_series,
@@ -235,3 +235,78 @@ fn syn_div() {
     let result = super::syn_div(&poly, 4, root.exp(4));
     assert_eq!(poly, remove_leading_zeros(&super::mul(&result, &z_poly)));
 }
+
+#[test]
+fn sparse_construction() {
+    // duplicate degrees are merged and zero terms are discarded
+    let p = SparsePolynomial::new(vec![
+        (3, BaseElement::from(2u8)),
+        (0, BaseElement::ONE),
+        (3, -BaseElement::from(2u8)),
+        (1, BaseElement::ZERO),
+        (2, BaseElement::from(5u8)),
+    ]);
+    assert_eq!(&[(0, BaseElement::ONE), (2, BaseElement::from(5u8))], p.terms());
+    assert_eq!(2, p.degree());
+
+    // dense round trip
+    let dense = vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::from(5u8)];
+    assert_eq!(p, SparsePolynomial::from_dense(&dense));
+    assert_eq!(dense, p.to_dense());
+
+    // (x^4 - 1) * (x^2 - 2) = x^6 - 2x^4 - x^2 + 2
+    let p = SparsePolynomial::from_binomials(&[(4, BaseElement::ONE), (2, BaseElement::from(2u8))]);
+    let expected = super::mul(
+        &SparsePolynomial::binomial(4, BaseElement::ONE).to_dense(),
+        &SparsePolynomial::binomial(2, BaseElement::from(2u8)).to_dense(),
+    );
+    assert_eq!(expected, p.to_dense());
+    assert_eq!(4, p.num_terms());
+
+    // empty product is the constant polynomial 1
+    assert_eq!(vec![BaseElement::ONE], SparsePolynomial::from_binomials(&[]).to_dense());
+    assert!(SparsePolynomial::<BaseElement>::new(vec![]).is_zero());
+}
+
+#[test]
+fn sparse_eval() {
+    let p = SparsePolynomial::from_binomials(&[
+        (8, BaseElement::ONE),
+        (2, BaseElement::from(3u8)),
+        (1, BaseElement::from(7u8)),
+    ]);
+    let dense = p.to_dense();
+
+    let x = BaseElement::from(11269864713250585702u128);
+    assert_eq!(super::eval(&dense, x), p.eval(x));
+
+    // evaluation over a coset of the multiplicative subgroup
+    let n = 16_usize;
+    let offset = BaseElement::GENERATOR;
+    let g = BaseElement::get_root_of_unity(n.ilog2());
+    let expected = get_power_series(g, n)
+        .into_iter()
+        .map(|x| super::eval(&dense, x * offset))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, p.eval_over_domain(n, offset));
+}
+
+#[test]
+fn sparse_mul() {
+    let a = SparsePolynomial::new(vec![
+        (0, BaseElement::from(3u8)),
+        (5, BaseElement::from(2u8)),
+        (9, BaseElement::ONE),
+    ]);
+    let b = vec![
+        BaseElement::from(384863712573444386u128),
+        BaseElement::from(7682273369345308472u128),
+        BaseElement::from(13294661765012277990u128),
+    ];
+
+    let expected = super::mul(&a.to_dense(), &b);
+    assert_eq!(expected, a.mul_dense(&b));
+
+    let b = SparsePolynomial::from_dense(&b);
+    assert_eq!(expected, a.mul(&b).to_dense());
+}
@@ -0,0 +1,195 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    AggregateAir, AggregatePublicInputs, Air, AuxTraceError, AuxTraceRandElements, ColMatrix,
+    ConstraintCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde,
+    EvaluationFrame, ProofOptions, Prover, Trace, TraceLayout, TraceMetadata,
+};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// AGGREGATE TRACE
+// ================================================================================================
+/// An execution trace of an aggregate of several statements about the same computation.
+///
+/// The trace consists of the main trace segments of all aggregated statements placed side by
+/// side, and also holds public inputs of each of the statements. An aggregate trace can be built
+/// using [AggregateProver::build_trace()].
+#[derive(Debug, Clone)]
+pub struct AggregateTrace<B: StarkField, P> {
+    layout: TraceLayout,
+    meta: TraceMetadata,
+    main_segment: ColMatrix<B>,
+    pub_inputs: Vec<P>,
+}
+
+impl<B: StarkField, P> AggregateTrace<B, P> {
+    /// Returns public inputs of the aggregated statements.
+    pub fn pub_inputs(&self) -> &[P] {
+        &self.pub_inputs
+    }
+
+    /// Returns the number of aggregated statements.
+    pub fn num_statements(&self) -> usize {
+        self.pub_inputs.len()
+    }
+}
+
+impl<B: StarkField, P> Trace for AggregateTrace<B, P> {
+    type BaseField = B;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    fn meta(&self) -> &TraceMetadata {
+        &self.meta
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.main_segment.read_row_into(row_idx, frame.current_mut());
+        self.main_segment.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<B> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        _rand_elements: &[E],
+    ) -> Result<ColMatrix<E>, AuxTraceError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        Err(AuxTraceError::UndefinedSegment(aux_segments.len()))
+    }
+}
+
+// AGGREGATE PROVER
+// ================================================================================================
+/// A prover which generates a single proof for several statements about the same computation.
+///
+/// The aggregate prover wraps a prover for individual statements, and generates proofs against
+/// [AggregateAir] instantiated with the AIR of the wrapped prover. All statements are proven
+/// using a single constraint composition polynomial and a single FRI instance, and thus, the
+/// resulting proof is significantly smaller than the sum of proofs for individual statements.
+///
+/// An aggregate proof can be verified using the regular verifier against
+/// `AggregateAir<P::Air>` and [AggregatePublicInputs] assembled from the public inputs of the
+/// individual statements.
+pub struct AggregateProver<P: Prover> {
+    prover: P,
+}
+
+impl<P: Prover> AggregateProver<P> {
+    /// Returns a new aggregate prover which uses the specified `prover` to build public inputs
+    /// of individual statements.
+    pub fn new(prover: P) -> Self {
+        Self { prover }
+    }
+
+    /// Returns the wrapped prover.
+    pub fn inner(&self) -> &P {
+        &self.prover
+    }
+
+    /// Returns an aggregate execution trace built from the provided traces.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `traces` is empty.
+    /// * Any of the traces consists of more than one segment.
+    /// * Traces have different widths, lengths, or metadata.
+    pub fn build_trace(
+        &self,
+        traces: Vec<P::Trace>,
+    ) -> AggregateTrace<P::BaseField, <P::Air as Air>::PublicInputs> {
+        assert!(!traces.is_empty(), "at least one trace must be aggregated");
+        let first = traces[0].get_info();
+        assert!(!first.is_multi_segment(), "only single-segment traces can be aggregated");
+        for trace in traces.iter().skip(1) {
+            let info = trace.get_info();
+            assert!(!info.is_multi_segment(), "only single-segment traces can be aggregated");
+            assert_eq!(
+                info.width(),
+                first.width(),
+                "all aggregated traces must have the same width"
+            );
+            assert_eq!(
+                info.length(),
+                first.length(),
+                "all aggregated traces must have the same length"
+            );
+            assert_eq!(
+                info.meta(),
+                first.meta(),
+                "all aggregated traces must have the same metadata"
+            );
+        }
+
+        let pub_inputs = traces.iter().map(|trace| self.prover.get_pub_inputs(trace)).collect();
+        let mut columns = Vec::with_capacity(first.width() * traces.len());
+        for trace in traces.iter() {
+            let segment = trace.main_segment();
+            for col_idx in 0..segment.num_cols() {
+                columns.push(segment.get_column(col_idx).to_vec());
+            }
+        }
+
+        AggregateTrace {
+            layout: TraceLayout::new(columns.len(), [0], [0]),
+            meta: first.meta().clone(),
+            main_segment: ColMatrix::new(columns),
+            pub_inputs,
+        }
+    }
+}
+
+impl<P> Prover for AggregateProver<P>
+where
+    P: Prover,
+    P::Air: 'static,
+    <P::Air as Air>::PublicInputs: Clone,
+{
+    type BaseField = P::BaseField;
+    type Air = AggregateAir<P::Air>;
+    type Trace = AggregateTrace<P::BaseField, <P::Air as Air>::PublicInputs>;
+    type HashFn = P::HashFn;
+    type RandomCoin = P::RandomCoin;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(
+        &self,
+        trace: &Self::Trace,
+    ) -> AggregatePublicInputs<<P::Air as Air>::PublicInputs> {
+        AggregatePublicInputs::new(trace.pub_inputs.clone())
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.prover.options()
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
@@ -3,12 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::ProverError;
 use air::{
     proof::{Commitments, Context, OodFrame, Queries, StarkProof},
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
 };
 use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, RandomCoin};
 use fri::{self, FriProof};
 use math::{FieldElement, ToElements};
 use utils::collections::Vec;
@@ -19,6 +20,15 @@ use utils::iterators::*;
 // TYPES AND INTERFACES
 // ================================================================================================
 
+/// A channel over which the prover commits to values and draws randomness which, in the
+/// interactive version of the protocol, would come from the verifier.
+///
+/// All values sent over the channel are absorbed into the public coin (the Fiat-Shamir
+/// transcript). Besides the messages of the STARK protocol itself, a prover can absorb additional
+/// protocol messages (e.g., commitments to auxiliary proofs) via [ProverChannel::absorb()] from
+/// within the [Prover::absorb_trace_segment_messages()](crate::Prover::absorb_trace_segment_messages)
+/// hook. For a proof to be verifiable, the verifier must absorb the same messages at the same point
+/// of the protocol.
 pub struct ProverChannel<'a, A, E, H, R>
 where
     A: Air,
@@ -35,6 +45,15 @@ where
     _field_element: PhantomData<E>,
 }
 
+/// Transcript state of a [ProverChannel] which is not bound to an AIR instance.
+pub(crate) struct ChannelState<R> {
+    public_coin: R,
+    context: Context,
+    commitments: Commitments,
+    ood_frame: OodFrame,
+    pow_nonce: u64,
+}
+
 // PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
@@ -47,19 +66,36 @@ where
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new prover channel for the specified `air` and public inputs.
-    pub fn new(air: &'a A, mut pub_inputs_elements: Vec<A::BaseField>) -> Self {
-        let context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone());
+    /// Creates a new prover channel for the specified `air` and public inputs (or a commitment to
+    /// public inputs); auxiliary trace segments are assumed to be committed to using hash
+    /// function `AH`. If `prover_id` is provided, it is recorded in the proof context.
+    pub fn new<AH: Hasher>(
+        air: &'a A,
+        mut pub_inputs_elements: Vec<A::BaseField>,
+        pub_inputs_commitment: Option<H::Digest>,
+        prover_id: Option<&str>,
+    ) -> Self {
+        let mut context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone())
+            .with_aux_trace_hasher::<H, AH>();
+        if let Some(prover_id) = prover_id {
+            context = context.with_prover_id(prover_id);
+        }
 
         // build a seed for the public coin; the initial seed is a hash of the proof context and
         // the public inputs, but as the protocol progresses, the coin will be reseeded with the
         // info sent to the verifier
         let mut coin_seed_elements = context.to_elements();
         coin_seed_elements.append(&mut pub_inputs_elements);
+        let mut public_coin = R::new(&coin_seed_elements);
+
+        // if the public inputs were committed to, reseed the coin with the commitment
+        if let Some(commitment) = pub_inputs_commitment {
+            public_coin.reseed(commitment);
+        }
 
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&coin_seed_elements),
+            public_coin,
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
@@ -68,6 +104,32 @@ where
         }
     }
 
+    /// Detaches the transcript state of this channel from the AIR instance for which the channel
+    /// was created; the channel can be restored later via [ProverChannel::attach()].
+    pub(crate) fn detach(self) -> ChannelState<R> {
+        ChannelState {
+            public_coin: self.public_coin,
+            context: self.context,
+            commitments: self.commitments,
+            ood_frame: self.ood_frame,
+            pow_nonce: self.pow_nonce,
+        }
+    }
+
+    /// Restores a channel for the specified `air` from the previously detached transcript
+    /// `state`; `air` must be the same as the AIR instance for which the channel was created.
+    pub(crate) fn attach(air: &'a A, state: ChannelState<R>) -> Self {
+        ProverChannel {
+            air,
+            public_coin: state.public_coin,
+            context: state.context,
+            commitments: state.commitments,
+            ood_frame: state.ood_frame,
+            pow_nonce: state.pow_nonce,
+            _field_element: PhantomData,
+        }
+    }
+
     // COMMITMENT METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -86,7 +148,8 @@ where
     /// Saves the evaluations of trace polynomials over the out-of-domain evaluation frame. This
     /// also reseeds the public coin with the hashes of the evaluation frame states.
     pub fn send_ood_trace_states(&mut self, trace_states: &[Vec<E>]) {
-        let result = self.ood_frame.set_trace_states(trace_states);
+        let main_trace_width = self.air.trace_layout().main_trace_width();
+        let result = self.ood_frame.set_trace_states(trace_states, main_trace_width);
         self.public_coin.reseed(H::hash_elements(&result));
     }
 
@@ -97,17 +160,40 @@ where
         self.public_coin.reseed(H::hash_elements(evaluations));
     }
 
+    /// Absorbs an additional protocol message into the public coin.
+    ///
+    /// This is intended for protocols which extend the STARK protocol with additional messages
+    /// (e.g., commitments to auxiliary proofs). The message itself is not included into the
+    /// proof, and thus, must be sent to the verifier separately.
+    pub fn absorb(&mut self, message: H::Digest) {
+        self.public_coin.reseed(message);
+    }
+
     // PUBLIC COIN METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns an element drawn uniformly at random from the public coin.
+    ///
+    /// This is intended for protocols which extend the STARK protocol with additional messages
+    /// requiring randomness from the verifier.
+    pub fn draw(&mut self) -> E {
+        self.public_coin.draw().expect("failed to draw a random element")
+    }
+
     /// Returns a set of random elements required for constructing an auxiliary trace segment with
     /// the specified index.
     ///
     /// The elements are drawn from the public coin uniformly at random.
-    pub fn get_aux_trace_segment_rand_elements(&mut self, aux_segment_idx: usize) -> Vec<E> {
+    ///
+    /// # Errors
+    /// Returns an error if the random elements could not be drawn from the public coin.
+    pub fn get_aux_trace_segment_rand_elements(
+        &mut self,
+        aux_segment_idx: usize,
+    ) -> Result<Vec<E>, ProverError> {
         self.air
             .get_aux_trace_segment_random_elements(aux_segment_idx, &mut self.public_coin)
-            .expect("failed to draw random elements for an auxiliary trace segment")
+            .map_err(|_| ProverError::AuxTraceRandElementsUnavailable(aux_segment_idx))
     }
 
     /// Returns a set of coefficients for constructing a constraint composition polynomial.
@@ -120,8 +206,11 @@ where
     }
 
     /// Returns an out-of-domain point drawn uniformly at random from the public coin.
+    ///
+    /// Points at which constraint divisors vanish or DEEP quotients are undefined are rejected,
+    /// and a new point is drawn in their place (see [Air::get_ood_point()]).
     pub fn get_ood_point(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw OOD point")
+        self.air.get_ood_point(&mut self.public_coin).expect("failed to draw OOD point")
     }
 
     /// Returns a set of coefficients for constructing a DEEP composition polynomial.
@@ -136,13 +225,42 @@ where
     /// Returns a set of positions in the LDE domain against which the evaluations of trace and
     /// constraint composition polynomials should be queried.
     ///
-    /// The positions are drawn from the public coin uniformly at random.
+    /// The positions are drawn from the public coin uniformly at random. If the commitment to the
+    /// first FRI layer is skipped, the positions are drawn from the domain of the second FRI
+    /// layer, and then mapped to all positions in the LDE domain which fold into them.
     pub fn get_query_positions(&mut self) -> Vec<usize> {
-        let num_queries = self.context.options().num_queries();
+        let options = self.context.options();
+        let num_queries = options.num_queries();
         let lde_domain_size = self.context.lde_domain_size();
-        self.public_coin
-            .draw_integers(num_queries, lde_domain_size, self.pow_nonce)
-            .expect("failed to draw query position")
+        if options.skip_first_fri_layer_commitment() {
+            let folding_factor = options.to_fri_options().folding_factor();
+            let positions = self
+                .public_coin
+                .draw_integers(num_queries, lde_domain_size / folding_factor, self.pow_nonce)
+                .expect("failed to draw query position");
+            fri::folding::unfold_positions(&positions, lde_domain_size, folding_factor)
+        } else {
+            self.public_coin
+                .draw_integers(num_queries, lde_domain_size, self.pow_nonce)
+                .expect("failed to draw query position")
+        }
+    }
+
+    /// Returns a set of positions in the column group domain against which the evaluations of
+    /// column group polynomials should be queried, or None if the computation does not define a
+    /// column group.
+    ///
+    /// The positions are drawn from the public coin uniformly at random, and must be drawn after
+    /// the positions in the LDE domain.
+    pub fn get_column_group_query_positions(&mut self) -> Option<Vec<usize>> {
+        let context = self.air.context();
+        let num_queries = context.num_column_group_queries()?;
+        let domain_size = context.column_group_domain_size()?;
+        let positions = self
+            .public_coin
+            .draw_integers(num_queries, domain_size, self.pow_nonce)
+            .expect("failed to draw column group query position");
+        Some(positions)
     }
 
     /// Determines a nonce, which when hashed with the current seed of the public coin results
@@ -175,15 +293,15 @@ where
         constraint_queries: Queries,
         fri_proof: FriProof,
     ) -> StarkProof {
-        StarkProof {
-            context: self.context,
-            commitments: self.commitments,
-            ood_frame: self.ood_frame,
+        StarkProof::from_parts(
+            self.context,
+            self.commitments,
             trace_queries,
             constraint_queries,
+            self.ood_frame,
             fri_proof,
-            pow_nonce: self.pow_nonce,
-        }
+            self.pow_nonce,
+        )
     }
 }
 
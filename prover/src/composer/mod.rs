@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{constraints::CompositionPoly, StarkDomain, TracePolyTable};
-use air::DeepCompositionCoefficients;
-use math::{add_in_place, fft, mul_acc, polynom, ExtensionOf, FieldElement, StarkField};
+use air::{deep, ColumnGroup, DeepCompositionCoefficients};
+use math::{add_in_place, fft, mul_acc, polynom, ExtensionOf, FieldElement};
 use utils::{collections::Vec, iter_mut};
 
 #[cfg(feature = "concurrent")]
@@ -60,18 +60,21 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///
     /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_trace_state`
     /// parameter.
+    ///
+    /// Columns of the `column_group` (if any) are skipped; these are composed separately via
+    /// build_column_group_poly() method.
     pub fn add_trace_polys(
         &mut self,
         trace_polys: TracePolyTable<E>,
         ood_trace_states: Vec<Vec<E>>,
+        column_group: Option<&ColumnGroup>,
     ) {
         assert!(self.coefficients.is_empty());
 
         // compute a second out-of-domain point offset from z by exactly trace generator; this
         // point defines the "next" computation state in relation to point z
         let trace_length = trace_polys.poly_size();
-        let g = E::from(E::BaseField::get_root_of_unity(trace_length.ilog2()));
-        let next_z = self.z * g;
+        let [_, next_z] = deep::get_ood_points(self.z, trace_length);
 
         // combine trace polynomials into 2 composition polynomials T'(x) and T''(x)
         let mut t1_composition = E::zeroed_vector(trace_length);
@@ -83,6 +86,11 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
         // --- merge polynomials of the main trace segment ----------------------------------------
         for poly in trace_polys.main_trace_polys() {
+            if column_group.map_or(false, |group| group.contains(i)) {
+                i += 1;
+                continue;
+            }
+
             // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
             // and add the result into composition polynomial
             acc_trace_poly::<E::BaseField, E>(
@@ -138,6 +146,53 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
         assert_eq!(self.poly_size() - 2, self.degree());
     }
 
+    /// Combines trace polynomials of the columns in the specified column group into a separate
+    /// DEEP composition polynomial, and returns the result.
+    ///
+    /// The combination is done in the same way as in add_trace_polys() method, using the same
+    /// composition coefficients. The returned polynomial does not include the constraint
+    /// composition polynomial.
+    pub fn build_column_group_poly(
+        &self,
+        column_group: &ColumnGroup,
+        trace_polys: &TracePolyTable<E>,
+        ood_trace_states: &[Vec<E>],
+    ) -> Self {
+        let trace_length = trace_polys.poly_size();
+        let [_, next_z] = deep::get_ood_points(self.z, trace_length);
+
+        let mut t1_composition = E::zeroed_vector(trace_length);
+        let mut t2_composition = E::zeroed_vector(trace_length);
+        for (i, poly) in trace_polys.main_trace_polys().enumerate() {
+            if !column_group.contains(i) {
+                continue;
+            }
+            acc_trace_poly::<E::BaseField, E>(
+                &mut t1_composition,
+                poly,
+                ood_trace_states[0][i],
+                self.cc.trace[i],
+            );
+            acc_trace_poly::<E::BaseField, E>(
+                &mut t2_composition,
+                poly,
+                ood_trace_states[1][i],
+                self.cc.trace[i],
+            );
+        }
+
+        // unlike the DEEP composition polynomial of the entire trace, the degree of the resulting
+        // polynomial may be smaller than trace_length - 2 (e.g., when all columns of the group
+        // are constant)
+        let coefficients =
+            merge_trace_compositions(vec![t1_composition, t2_composition], vec![self.z, next_z]);
+        DeepCompositionPoly {
+            coefficients,
+            cc: self.cc.clone(),
+            z: self.z,
+        }
+    }
+
     // CONSTRAINT POLYNOMIAL COMPOSITION
     // --------------------------------------------------------------------------------------------
     /// Divides out OOD point z from the constraint composition polynomial and saves the result
@@ -165,8 +220,7 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
         // Divide out the OOD point z from column polynomials
         iter_mut!(column_polys).zip(ood_evaluations).for_each(|(poly, value_at_z)| {
             // compute H'_i(x) = (H_i(x) - H_i(z)) / (x - z)
-            poly[0] -= value_at_z;
-            polynom::syn_div_in_place(poly, 1, z);
+            deep::compute_quotient_poly_in_place(poly, value_at_z, z);
         });
 
         // add H'_i(x) * cc_i for all i into the DEEP composition polynomial
@@ -189,6 +243,14 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<E: FieldElement> Drop for DeepCompositionPoly<E> {
+    /// Zeroizes coefficients of this polynomial.
+    fn drop(&mut self) {
+        crate::matrix::zeroize_values(&mut self.coefficients);
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::ColMatrix;
-use math::{polynom::degree_of, FieldElement};
+use math::{mul_acc, polynom::degree_of, FieldElement};
 use utils::collections::Vec;
 
 // COMPOSITION POLYNOMIAL
@@ -12,6 +12,7 @@ use utils::collections::Vec;
 /// Represents a composition polynomial split into columns with each column being of length equal
 /// to trace_length. Thus, for example, if the composition polynomial has degree 2N - 1, where N
 /// is the trace length, it will be stored as two columns of size N (each of degree N - 1).
+#[derive(Debug, Clone)]
 pub struct CompositionPoly<E: FieldElement> {
     data: ColMatrix<E>,
 }
@@ -65,6 +66,25 @@ impl<E: FieldElement> CompositionPoly<E> {
         self.data.evaluate_columns_at(z)
     }
 
+    /// Returns a composition polynomial with a single column computed as
+    /// $G(x) = \sum_i z^{i \cdot n} H_i(x)$, where $H_i(x)$ are the columns of this polynomial
+    /// and $n$ is the column length.
+    ///
+    /// The evaluation of the resulting column at `z` is equal to the evaluation of the full
+    /// composition polynomial at `z`.
+    pub fn combine_columns_at(self, z: E) -> Self {
+        let z_n = z.exp_vartime((self.column_len() as u64).into());
+        let mut combined = vec![E::ZERO; self.column_len()];
+        let mut weight = E::ONE;
+        for column in self.into_columns() {
+            mul_acc::<E, E>(&mut combined, &column, weight);
+            weight *= z_n;
+        }
+        CompositionPoly {
+            data: ColMatrix::new(vec![combined]),
+        }
+    }
+
     /// Returns a reference to the matrix of individual column polynomials.
     pub fn data(&self) -> &ColMatrix<E> {
         &self.data
@@ -83,6 +103,9 @@ impl<E: FieldElement> CompositionPoly<E> {
 /// in such a way that each resulting column has the same degree. For example, a polynomial
 /// a * x^3 + b * x^2 + c * x + d, can be rewritten as: (c * x + d) + x^2 * (a * x + b), and then
 /// the two columns will be: (c * x + d) and (a * x + b).
+///
+/// If the polynomial has fewer coefficients than needed to fill the specified number of columns,
+/// the remaining columns are filled with zero polynomials.
 fn segment<E: FieldElement>(
     coefficients: Vec<E>,
     trace_len: usize,
@@ -90,11 +113,13 @@ fn segment<E: FieldElement>(
 ) -> Vec<Vec<E>> {
     debug_assert!(degree_of(&coefficients) < trace_len * num_cols);
 
-    coefficients
+    let mut columns: Vec<Vec<E>> = coefficients
         .chunks(trace_len)
         .take(num_cols)
         .map(|slice| slice.to_vec())
-        .collect()
+        .collect();
+    columns.resize(num_cols, vec![E::ZERO; trace_len]);
+    columns
 }
 
 // TESTS
@@ -103,7 +128,7 @@ fn segment<E: FieldElement>(
 #[cfg(test)]
 mod tests {
 
-    use math::fields::f128::BaseElement;
+    use math::{fields::f128::BaseElement, FieldElement};
     use utils::collections::Vec;
 
     #[test]
@@ -121,4 +146,19 @@ mod tests {
 
         assert_eq!(expected, actual)
     }
+
+    #[test]
+    fn segment_with_padding() {
+        let values = (0u128..8).map(BaseElement::new).collect::<Vec<_>>();
+        let actual = super::segment(values, 4, 3);
+
+        #[rustfmt::skip]
+        let expected = vec![
+            vec![BaseElement::new(0), BaseElement::new(1), BaseElement::new(2), BaseElement::new(3)],
+            vec![BaseElement::new(4), BaseElement::new(5), BaseElement::new(6), BaseElement::new(7)],
+            vec![BaseElement::ZERO, BaseElement::ZERO, BaseElement::ZERO, BaseElement::ZERO],
+        ];
+
+        assert_eq!(expected, actual)
+    }
 }
@@ -3,8 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{CompositionPoly, ConstraintDivisor, ProverError, StarkDomain};
-use math::{batch_inversion, fft, FieldElement, StarkField};
+use super::{CompositionPoly, ConstraintDivisor, EvaluationBuffers, ProverError, StarkDomain};
+use crate::TraceLde;
+use math::{batch_inversion, fft, polynom, FieldElement, StarkField};
 use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
 
 #[cfg(debug_assertions)]
@@ -32,6 +33,8 @@ pub struct ConstraintEvaluationTable<'a, E: FieldElement> {
     aux_transition_evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
     expected_transition_degrees: Vec<usize>,
+    #[cfg(debug_assertions)]
+    transition_divisor_indexes: Vec<usize>,
 }
 
 impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
@@ -72,6 +75,15 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         let expected_transition_degrees =
             build_transition_constraint_degrees(transition_constraints, domain.trace_length());
 
+        // record which divisor applies to each transition constraint; transition constraint
+        // divisors are assumed to be at the front of the divisor list
+        let transition_divisor_indexes = transition_constraints
+            .main_constraint_divisors()
+            .iter()
+            .chain(transition_constraints.aux_constraint_divisors().iter())
+            .copied()
+            .collect();
+
         ConstraintEvaluationTable {
             evaluations: uninit_matrix(num_columns, num_rows),
             divisors,
@@ -79,6 +91,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
             main_transition_evaluations: uninit_matrix(num_tm_columns, num_rows),
             aux_transition_evaluations: uninit_matrix(num_ta_columns, num_rows),
             expected_transition_degrees,
+            transition_divisor_indexes,
         }
     }
 
@@ -105,7 +118,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     /// Break the table into the number of specified fragments. All fragments can be updated
     /// independently - e.g. in different threads.
-    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<E>> {
+    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<'_, E>> {
         let fragment_size = self.num_rows() / num_fragments;
         assert!(
             fragment_size >= MIN_FRAGMENT_SIZE,
@@ -155,29 +168,52 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     // CONSTRAINT COMPOSITION
     // --------------------------------------------------------------------------------------------
     /// Divides constraint evaluation columns by their respective divisor (in evaluation form),
-    /// combines the results into a single column, and interpolates this column into a composition
-    /// polynomial in coefficient form.
-    /// `num_cols` is the number of necessary columns (of length `trace_length`) needed to store
-    /// the coefficients of the constraint composition polynomial and is needed by
-    /// `CompositionPoly::new`.
-    pub fn into_poly(self, num_cols: usize) -> Result<CompositionPoly<E>, ProverError> {
+    /// and combines the results into a single column.
+    ///
+    /// The returned vector contains evaluations of the combined constraint polynomial over the
+    /// constraint evaluation domain.
+    pub fn combine(self) -> Vec<E> {
         // allocate memory for the combined polynomial
         let mut combined_poly = E::zeroed_vector(self.num_rows());
 
         // iterate over all columns of the constraint evaluation table, divide each column
         // by the evaluations of its corresponding divisor, and add all resulting evaluations
         // together into a single vector
-        for (column, divisor) in self.evaluations.into_iter().zip(self.divisors.iter()) {
+        for (column, divisor) in self.evaluations.iter().zip(self.divisors.iter()) {
             // divide the column by the divisor and accumulate the result into combined_poly
             acc_column(column, divisor, self.domain, &mut combined_poly);
         }
 
+        combined_poly
+    }
+
+    /// Divides constraint evaluation columns by their respective divisor (in evaluation form),
+    /// combines the results into a single column, and interpolates this column into a composition
+    /// polynomial in coefficient form.
+    /// `num_cols` is the number of necessary columns (of length `trace_length`) needed to store
+    /// the coefficients of the constraint composition polynomial and is needed by
+    /// `CompositionPoly::new`.
+    pub fn into_poly(self, num_cols: usize) -> Result<CompositionPoly<E>, ProverError> {
+        let domain = self.domain;
+
         // at this point, combined_poly contains evaluations of the combined constraint polynomial;
         // we interpolate this polynomial to transform it into coefficient form.
+        let mut combined_poly = self.combine();
         let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(combined_poly.len());
-        fft::interpolate_poly_with_offset(&mut combined_poly, &inv_twiddles, self.domain.offset());
+        fft::interpolate_poly_with_offset(&mut combined_poly, &inv_twiddles, domain.offset());
+
+        // make sure the combined polynomial fits into the composition columns; if it does not,
+        // the trace most likely does not satisfy the constraints
+        let trace_length = domain.trace_length();
+        let max_degree = trace_length * num_cols - 1;
+        let actual_degree = polynom::degree_of(&combined_poly);
+        if actual_degree > max_degree {
+            return Err(ProverError::ConstraintDegreeOverflow {
+                max_degree,
+                actual_degree,
+            });
+        }
 
-        let trace_length = self.domain.trace_length();
         Ok(CompositionPoly::new(combined_poly, trace_length, num_cols))
     }
 
@@ -186,14 +222,18 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     #[cfg(debug_assertions)]
     pub fn validate_transition_degrees(&mut self) {
-        // evaluate transition constraint divisor (which is assumed to be the first one in the
+        // evaluate transition constraint divisors (which are assumed to be at the front of the
         // divisor list) over the constraint evaluation domain. this is used later to compute
         // actual degrees of transition constraint evaluations.
-        let div_values = evaluate_divisor::<E::BaseField>(
-            &self.divisors[0],
-            self.num_rows(),
-            self.domain.offset(),
-        );
+        let num_transition_divisors =
+            self.transition_divisor_indexes.iter().max().map_or(1, |&idx| idx + 1);
+        let div_values = self.divisors[..num_transition_divisors]
+            .iter()
+            .map(|divisor| {
+                evaluate_divisor::<E::BaseField>(divisor, self.num_rows(), self.domain.offset())
+            })
+            .collect::<Vec<_>>();
+        let mut divisor_indexes = self.transition_divisor_indexes.iter();
 
         // collect actual degrees for all transition constraints by interpolating saved
         // constraint evaluations into polynomials and checking their degree; also
@@ -204,14 +244,16 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
         // first process transition constraint evaluations for the main trace segment
         for evaluations in self.main_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
+            let div_values = &div_values[*divisor_indexes.next().expect("missing divisor")];
+            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, div_values);
             actual_degrees.push(degree);
             max_degree = core::cmp::max(max_degree, degree);
         }
 
         // then process transition constraint evaluations for auxiliary trace segments
         for evaluations in self.aux_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
+            let div_values = &div_values[*divisor_indexes.next().expect("missing divisor")];
+            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, div_values);
             actual_degrees.push(degree);
             max_degree = core::cmp::max(max_degree, degree);
         }
@@ -236,6 +278,16 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<'a, E: FieldElement> Drop for ConstraintEvaluationTable<'a, E> {
+    /// Zeroizes constraint evaluations stored in this table.
+    fn drop(&mut self) {
+        for column in self.evaluations.iter_mut() {
+            crate::matrix::zeroize_values(column);
+        }
+    }
+}
+
 // TABLE FRAGMENTS
 // ================================================================================================
 
@@ -272,6 +324,58 @@ impl<'a, E: FieldElement> EvaluationTableFragment<'a, E> {
         }
     }
 
+    /// Evaluates all rows of this fragment using the provided closure and scratch buffers.
+    ///
+    /// For every row of the fragment, evaluation frames in the `buffers` are populated with data
+    /// from the extended execution `trace` (steps of the constraint evaluation domain are
+    /// translated into steps of the LDE domain), and the results buffer is reset to all zeros.
+    /// Then, `evaluate_row` is invoked with the step of the constraint evaluation domain and the
+    /// buffers; it is expected to write merged constraint evaluations into the results buffer,
+    /// which is then copied into the row. In debug mode, transition constraint evaluations are
+    /// also copied from the buffers so that their degrees can be validated later.
+    ///
+    /// The same buffers are reused for all rows, and thus, no memory is allocated per row.
+    pub fn evaluate_rows<T, F>(
+        &mut self,
+        trace: &T,
+        domain: &StarkDomain<E::BaseField>,
+        buffers: &mut EvaluationBuffers<E>,
+        mut evaluate_row: F,
+    ) where
+        T: TraceLde<E>,
+        F: FnMut(usize, &mut EvaluationBuffers<E>),
+    {
+        // this will be used to convert steps in constraint evaluation domain to steps in
+        // LDE domain
+        let lde_shift = (domain.trace_lde_domain_size() / domain.ce_domain_size()).trailing_zeros();
+
+        for i in 0..self.num_rows() {
+            let step = i + self.offset();
+
+            // update evaluation frame buffers with data from the execution trace; data in the
+            // trace is extended over the LDE domain, so, we need to convert step in constraint
+            // evaluation domain into a step in LDE domain, in case these domains are different
+            trace.read_main_trace_frame_into(step << lde_shift, &mut buffers.main_frame);
+            if let Some(aux_frame) = buffers.aux_frame.as_mut() {
+                trace.read_aux_trace_frame_into(step << lde_shift, aux_frame);
+            }
+
+            buffers.results.fill(E::ZERO);
+            evaluate_row(step, buffers);
+
+            // when in debug mode, save transition constraint evaluations
+            #[cfg(debug_assertions)]
+            self.update_transition_evaluations(
+                i,
+                &buffers.main_evaluations,
+                &buffers.aux_evaluations,
+            );
+
+            // record the result in the evaluation table
+            self.update_row(i, &buffers.results);
+        }
+    }
+
     /// Updates transition evaluations row with the provided data; available only in debug mode.
     #[cfg(debug_assertions)]
     pub fn update_transition_evaluations(
@@ -321,15 +425,12 @@ fn make_fragments<E: FieldElement>(
 
 #[allow(clippy::many_single_char_names)]
 fn acc_column<E: FieldElement>(
-    column: Vec<E>,
+    column: &[E],
     divisor: &ConstraintDivisor<E::BaseField>,
     domain: &StarkDomain<E::BaseField>,
     result: &mut [E],
 ) {
-    let numerator = divisor.numerator();
-    assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
-
-    // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b)
+    // compute inverse evaluations of the divisor's numerator, which has the form prod(x^a - b)
     let z = get_inv_evaluation(divisor, domain);
 
     // divide column values by the divisor; for boundary constraints this computed simply as
@@ -338,8 +439,8 @@ fn acc_column<E: FieldElement>(
     // denominator (exclusion point).
     if divisor.exemptions().is_empty() {
         // the column represents merged evaluations of boundary constraints, and divisor has the
-        // form of (x^a - b); thus to divide the column by the divisor, we compute: value * z,
-        // where z = 1 / (x^a - 1) and has already been computed above.
+        // form of prod(x^a - b); thus to divide the column by the divisor, we compute: value * z,
+        // where z = 1 / prod(x^a - b) and has already been computed above.
         iter_mut!(result, 1024)
             .zip(column)
             .enumerate()
@@ -351,9 +452,9 @@ fn acc_column<E: FieldElement>(
             });
     } else {
         // the column represents merged evaluations of transition constraints, and divisor has the
-        // form of (x^a - 1) / e(x), where e(x) describes the exemption points; thus, to divide
-        // the column by the divisor, we compute: value * e(x) * z, where z = 1 / (x^a - 1) and has
-        // already been computed above.
+        // form of prod(x^a - b) / e(x), where e(x) describes the exemption points; thus, to
+        // divide the column by the divisor, we compute: value * e(x) * z, where
+        // z = 1 / prod(x^a - b) and has already been computed above.
         batch_iter_mut!(
             result,
             128, // min batch size
@@ -372,42 +473,47 @@ fn acc_column<E: FieldElement>(
     }
 }
 
-/// Computes evaluations of the divisor's numerator over the domain of the specified size and offset.
+/// Computes inverse evaluations of the divisor's numerator over the constraint evaluation domain.
+///
+/// The numerator has the form $\prod_i (x^{a_i} - b_i)$. Since the constraint evaluation domain
+/// is a multiplicative coset of size $n$, each term $x^{a_i}$ repeats with period $n / gcd(a_i, n)$
+/// and the entire numerator repeats with period $n / gcd(a_0, ..., a_k, n)$; thus, only the first
+/// period of numerator values is computed and returned.
 fn get_inv_evaluation<B: StarkField>(
     divisor: &ConstraintDivisor<B>,
     domain: &StarkDomain<B>,
 ) -> Vec<B> {
     let numerator = divisor.numerator();
-    let a = numerator[0].0 as u64; // numerator degree
-    let b = numerator[0].1;
-
-    // this guarantees that we can use get_ce_x_power_at() below but limits execution trace length
-    // to be at most 2^32. in the future, we should revisit this to allow execution traces of
-    // greater length.
-    assert!(
-        a <= u32::MAX as u64,
-        "constraint divisor numerator degree cannot exceed {}, but was {}",
-        u32::MAX,
-        a
-    );
 
-    let n = domain.ce_domain_size() / a as usize;
-    let domain_offset_exp = domain.offset().exp(a.into());
+    // the domain size is a power of two, and thus, the greatest common divisor of the domain size
+    // and all numerator degrees is the largest power of two dividing all of them
+    let ce_domain_size = domain.ce_domain_size();
+    let gcd_log2 = numerator
+        .iter()
+        .map(|&(a, _)| a.trailing_zeros())
+        .fold(ce_domain_size.trailing_zeros(), u32::min);
+    let n = ce_domain_size >> gcd_log2;
+
+    let terms = numerator
+        .iter()
+        .map(|&(a, b)| (a as u64, b, domain.offset().exp((a as u64).into())))
+        .collect::<Vec<_>>();
 
-    // compute x^a - b for all x
+    // compute prod(x^a - b) for all x
     let mut evaluations = unsafe { uninit_vector(n) };
     batch_iter_mut!(
         &mut evaluations,
         128, // min batch size
         |batch: &mut [B], batch_offset: usize| {
             for (i, evaluation) in batch.iter_mut().enumerate() {
-                let x = domain.get_ce_x_power_at(batch_offset + i, a, domain_offset_exp);
-                *evaluation = x - b;
+                *evaluation = terms.iter().fold(B::ONE, |acc, &(a, b, offset_exp)| {
+                    acc * (domain.get_ce_x_power_at(batch_offset + i, a, offset_exp) - b)
+                });
             }
         }
     );
 
-    // compute 1 / (x^a - b)
+    // compute 1 / prod(x^a - b)
     batch_inversion(&evaluations)
 }
 
@@ -429,13 +535,22 @@ fn build_transition_constraint_degrees<E: FieldElement>(
     trace_length: usize,
 ) -> Vec<usize> {
     let mut result = Vec::new();
+    let divisors = constraints.divisors();
 
-    for degree in constraints.main_constraint_degrees() {
-        result.push(degree.get_evaluation_degree(trace_length) - constraints.divisor().degree())
+    for (degree, &divisor_idx) in constraints
+        .main_constraint_degrees()
+        .iter()
+        .zip(constraints.main_constraint_divisors())
+    {
+        result.push(degree.get_evaluation_degree(trace_length) - divisors[divisor_idx].degree())
     }
 
-    for degree in constraints.aux_constraint_degrees() {
-        result.push(degree.get_evaluation_degree(trace_length) - constraints.divisor().degree())
+    for (degree, &divisor_idx) in constraints
+        .aux_constraint_degrees()
+        .iter()
+        .zip(constraints.aux_constraint_divisors())
+    {
+        result.push(degree.get_evaluation_degree(trace_length) - divisors[divisor_idx].degree())
     }
 
     result
@@ -444,8 +559,8 @@ fn build_transition_constraint_degrees<E: FieldElement>(
 /// Computes the actual degree of a transition polynomial described by the provided evaluations.
 ///
 /// The degree is computed as follows:
-/// - First, we divide the polynomial evaluations by the evaluations of transition constraint
-///   divisor (`div_values`). This is needed because it is possible for the numerator portions of
+/// - First, we divide the polynomial evaluations by the evaluations of the divisor of the
+///   transition constraint (`div_values`). This is needed because it is possible for the numerator portions of
 ///   transition constraints to have a degree which is larger than the size of the evaluation
 ///   domain (and thus, interpolating the numerator would yield an incorrect result). However,
 ///   once the divisor values are divided out, the degree of the resulting polynomial should be
@@ -468,8 +583,8 @@ fn get_transition_poly_degree<E: FieldElement>(
 }
 
 /// Evaluates constraint divisor over the specified domain. This is similar to [get_inv_evaluation]
-/// function above but uses a more straight-forward but less efficient evaluation methodology and
-/// also does not invert the results.
+/// function above but uses a more straight-forward but less efficient evaluation methodology (the
+/// numerator is expanded into a sparse polynomial) and also does not invert the results.
 #[cfg(debug_assertions)]
 fn evaluate_divisor<E: FieldElement>(
     divisor: &ConstraintDivisor<E::BaseField>,
@@ -478,5 +593,10 @@ fn evaluate_divisor<E: FieldElement>(
 ) -> Vec<E> {
     let g = E::BaseField::get_root_of_unity(domain_size.trailing_zeros());
     let domain = math::get_power_series_with_offset(g, domain_offset, domain_size);
-    domain.into_iter().map(|x| E::from(divisor.evaluate_at(x))).collect()
+    let numerator = divisor.numerator_poly().eval_over_domain(domain_size, domain_offset);
+    domain
+        .into_iter()
+        .zip(numerator)
+        .map(|(x, z)| E::from(z / divisor.evaluate_exemptions_at(x)))
+        .collect()
 }
@@ -0,0 +1,65 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{Air, EvaluationFrame};
+use math::FieldElement;
+use utils::collections::Vec;
+
+// EVALUATION BUFFERS
+// ================================================================================================
+
+/// Scratch buffers used to evaluate constraints over rows of an evaluation table fragment.
+///
+/// The buffers are allocated once per fragment and are reused for every row of the fragment (see
+/// [EvaluationTableFragment::evaluate_rows()](crate::EvaluationTableFragment::evaluate_rows)).
+/// Thus, constraint evaluators do not need to allocate any memory on a per-row basis, which
+/// matters for very wide execution traces.
+pub struct EvaluationBuffers<E: FieldElement> {
+    /// Current and next rows of the main trace segment at the step being evaluated.
+    pub main_frame: EvaluationFrame<E::BaseField>,
+    /// Current and next rows of all auxiliary trace segments at the step being evaluated; this is
+    /// None if the execution trace does not have auxiliary segments.
+    pub aux_frame: Option<EvaluationFrame<E>>,
+    /// Evaluations of transition constraints against the main trace segment.
+    pub main_evaluations: Vec<E::BaseField>,
+    /// Evaluations of transition constraints against auxiliary trace segments.
+    pub aux_evaluations: Vec<E>,
+    /// Merged constraint evaluations at the step being evaluated; one value per column of the
+    /// evaluation table.
+    pub results: Vec<E>,
+}
+
+impl<E: FieldElement> EvaluationBuffers<E> {
+    /// Returns new buffers sized for evaluating constraints of the specified AIR into an
+    /// evaluation table with `num_columns` columns.
+    pub fn new<A: Air<BaseField = E::BaseField>>(air: &A, num_columns: usize) -> Self {
+        let layout = air.trace_layout();
+        let context = air.context();
+        Self {
+            main_frame: EvaluationFrame::new(layout.main_trace_width()),
+            aux_frame: (layout.num_aux_segments() > 0)
+                .then(|| EvaluationFrame::new(layout.aux_trace_width())),
+            main_evaluations: vec![E::BaseField::ZERO; context.num_main_transition_constraints()],
+            aux_evaluations: vec![E::ZERO; context.num_aux_transition_constraints()],
+            results: vec![E::ZERO; num_columns],
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<E: FieldElement> Drop for EvaluationBuffers<E> {
+    /// Zeroizes trace rows and constraint evaluations stored in these buffers.
+    fn drop(&mut self) {
+        crate::matrix::zeroize_values(self.main_frame.current_mut());
+        crate::matrix::zeroize_values(self.main_frame.next_mut());
+        if let Some(aux_frame) = self.aux_frame.as_mut() {
+            crate::matrix::zeroize_values(aux_frame.current_mut());
+            crate::matrix::zeroize_values(aux_frame.next_mut());
+        }
+        crate::matrix::zeroize_values(&mut self.main_evaluations);
+        crate::matrix::zeroize_values(&mut self.aux_evaluations);
+        crate::matrix::zeroize_values(&mut self.results);
+    }
+}
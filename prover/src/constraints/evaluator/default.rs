@@ -5,14 +5,13 @@
 
 use super::{
     super::EvaluationTableFragment, BoundaryConstraints, ConstraintEvaluationTable,
-    ConstraintEvaluator, PeriodicValueTable, StarkDomain, TraceLde,
+    ConstraintEvaluator, EvaluationBuffers, PeriodicValueTable, StarkDomain, TraceLde,
 };
 use air::{
     Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame,
     TransitionConstraints,
 };
 use math::FieldElement;
-use utils::iter_mut;
 
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
@@ -23,6 +22,15 @@ use utils::{iterators::*, rayon};
 #[cfg(feature = "concurrent")]
 const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 
+/// Number of fragments per worker thread into which the evaluation table is broken when
+/// constraints are evaluated concurrently.
+#[cfg(feature = "concurrent")]
+const FRAGMENTS_PER_THREAD: usize = 16;
+
+/// Minimum number of rows in a fragment when constraints are evaluated concurrently.
+#[cfg(feature = "concurrent")]
+const MIN_CONCURRENT_FRAGMENT_SIZE: usize = 256;
+
 // DEFAULT CONSTRAINT EVALUATOR
 // ================================================================================================
 
@@ -34,14 +42,19 @@ const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 /// [ConstraintEvaluationTable] will contain as many columns as there are unique constraint domains.
 ///
 /// When `concurrent` feature is enabled, the extended execution trace is split into sets of
-/// sequential evaluation frames (called fragments), and frames in each fragment are evaluated
-/// in separate threads.
+/// sequential evaluation frames (called fragments), and fragments are evaluated in separate
+/// threads. The trace is split into many more fragments than there are threads, and fragments are
+/// distributed among threads dynamically (via work-stealing); thus, if the cost of evaluating
+/// constraints varies from row to row, threads which finish early pick up the remaining fragments
+/// instead of idling.
 pub struct DefaultConstraintEvaluator<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> {
     air: &'a A,
     boundary_constraints: BoundaryConstraints<E>,
     transition_constraints: TransitionConstraints<E>,
     aux_rand_elements: AuxTraceRandElements<E>,
     periodic_values: PeriodicValueTable<E::BaseField>,
+    #[cfg(debug_assertions)]
+    validate_degrees: bool,
 }
 
 impl<'a, A, E> ConstraintEvaluator<'a, E> for DefaultConstraintEvaluator<'a, A, E>
@@ -58,14 +71,14 @@ where
     ) -> ConstraintEvaluationTable<'a, E> {
         assert_eq!(
             trace.trace_len(),
-            domain.lde_domain_size(),
+            domain.trace_lde_domain_size(),
             "extended trace length is not consistent with evaluation domain"
         );
 
-        // build a list of constraint divisors; currently, all transition constraints have the same
-        // divisor which we put at the front of the list; boundary constraint divisors are appended
-        // after that
-        let mut divisors = vec![self.transition_constraints.divisor().clone()];
+        // build a list of constraint divisors; transition constraint divisors go at the front of
+        // the list (the default transition divisor is always the first one); boundary constraint
+        // divisors are appended after that
+        let mut divisors = self.transition_constraints.divisors().to_vec();
         divisors.append(&mut self.boundary_constraints.get_divisors());
 
         // allocate space for constraint evaluations; when we are in debug mode, we also allocate
@@ -77,36 +90,36 @@ where
         let mut evaluation_table =
             ConstraintEvaluationTable::<E>::new(domain, divisors, &self.transition_constraints);
 
-        // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
-        // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
-        // then don't bother with concurrent evaluation
-
+        // when `concurrent` feature is not enabled, evaluate the whole table as a single fragment
         #[cfg(not(feature = "concurrent"))]
-        let num_fragments = 1;
+        {
+            let mut buffers = EvaluationBuffers::new(self.air, evaluation_table.num_columns());
+            for fragment in evaluation_table.fragments(1).iter_mut() {
+                self.evaluate_fragment(trace, domain, fragment, &mut buffers);
+            }
+        }
 
+        // when `concurrent` feature is enabled, break the evaluation table into many small
+        // fragments which are picked up by threads dynamically, so that rows which are more
+        // expensive to evaluate do not leave other threads idle; unless the constraint evaluation
+        // domain is small, then don't bother with concurrent evaluation. evaluation buffers are
+        // allocated once per worker and are reused across all fragments processed by the worker.
         #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
-            rayon::current_num_threads().next_power_of_two()
-        } else {
-            1
-        };
-
-        // evaluate constraints for each fragment; if the trace consist of multiple segments
-        // we evaluate constraints for all segments. otherwise, we evaluate constraints only
-        // for the main segment.
-        let mut fragments = evaluation_table.fragments(num_fragments);
-        iter_mut!(fragments).for_each(|fragment| {
-            if self.air.trace_info().is_multi_segment() {
-                self.evaluate_fragment_full(trace, domain, fragment);
-            } else {
-                self.evaluate_fragment_main(trace, domain, fragment);
-            }
-        });
+        {
+            let num_fragments = get_num_concurrent_fragments(domain.ce_domain_size());
+            let num_columns = evaluation_table.num_columns();
+            evaluation_table.fragments(num_fragments).par_iter_mut().for_each_init(
+                || EvaluationBuffers::new(self.air, num_columns),
+                |buffers, fragment| self.evaluate_fragment(trace, domain, fragment, buffers),
+            );
+        }
 
         // when in debug mode, make sure expected transition constraint degrees align with
         // actual degrees we got during constraint evaluation
         #[cfg(debug_assertions)]
-        evaluation_table.validate_transition_degrees();
+        if self.validate_degrees {
+            evaluation_table.validate_transition_degrees();
+        }
 
         evaluation_table
     }
@@ -145,116 +158,82 @@ where
             transition_constraints,
             aux_rand_elements,
             periodic_values,
+            #[cfg(debug_assertions)]
+            validate_degrees: true,
         }
     }
 
+    /// Returns this evaluator updated to skip checking that actual degrees of transition
+    /// constraints match their expected degrees.
+    ///
+    /// This check is performed only in debug mode, and it fails for execution traces which do not
+    /// satisfy transition constraints. Skipping it makes it possible to evaluate constraints over
+    /// such traces (e.g., when testing AIRs).
+    #[allow(unused_mut)]
+    pub fn without_degree_validation(mut self) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            self.validate_degrees = false;
+        }
+        self
+    }
+
     // EVALUATION HELPERS
     // --------------------------------------------------------------------------------------------
 
     /// Evaluates constraints for a single fragment of the evaluation table.
     ///
-    /// This evaluates constraints only over the main segment of the execution trace.
-    fn evaluate_fragment_main<T: TraceLde<E>>(
+    /// If the execution trace consists of multiple segments, constraints are evaluated over all
+    /// segments (i.e., the main segment and all auxiliary segments); otherwise, constraints are
+    /// evaluated only over the main segment.
+    ///
+    /// The provided `buffers` hold trace values and evaluation results; these are reused for
+    /// every row of the fragment.
+    fn evaluate_fragment<T: TraceLde<E>>(
         &self,
         trace: &T,
         domain: &StarkDomain<A::BaseField>,
         fragment: &mut EvaluationTableFragment<E>,
+        buffers: &mut EvaluationBuffers<E>,
     ) {
-        // initialize buffers to hold trace values and evaluation results at each step;
-        let mut main_frame = EvaluationFrame::new(trace.trace_layout().main_trace_width());
-        let mut evaluations = vec![E::ZERO; fragment.num_columns()];
-        let mut t_evaluations = vec![E::BaseField::ZERO; self.num_main_transition_constraints()];
-
-        // this will be used to convert steps in constraint evaluation domain to steps in
-        // LDE domain
-        let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
-
-        for i in 0..fragment.num_rows() {
-            let step = i + fragment.offset();
-
-            // update evaluation frame buffer with data from the execution trace; this will
-            // read current and next rows from the trace into the buffer; data in the trace
-            // table is extended over the LDE domain, so, we need to convert step in constraint
-            // evaluation domain, into a step in LDE domain, in case these domains are different
-            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
-
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer
-            evaluations[0] = self.evaluate_main_transition(&main_frame, step, &mut t_evaluations);
-
-            // when in debug mode, save transition constraint evaluations
-            #[cfg(debug_assertions)]
-            fragment.update_transition_evaluations(i, &t_evaluations, &[]);
-
-            // evaluate boundary constraints; the results go into remaining slots of the
-            // evaluations buffer
-            let main_state = main_frame.current();
-            self.boundary_constraints.evaluate_main(
-                main_state,
-                domain,
+        fragment.evaluate_rows(trace, domain, buffers, |step, buffers| {
+            // evaluate transition constraints and save the merged results into the first slots of
+            // the results buffer (one slot per transition constraint divisor); we evaluate and
+            // compose constraints in the same function, so we can just add up the results of
+            // evaluating main and auxiliary constraints.
+            let (t_results, b_results) =
+                buffers.results.split_at_mut(self.num_transition_divisors());
+            self.evaluate_main_transition(
+                &buffers.main_frame,
                 step,
-                &mut evaluations[1..],
+                &mut buffers.main_evaluations,
+                t_results,
             );
 
-            // record the result in the evaluation table
-            fragment.update_row(i, &evaluations);
-        }
-    }
-
-    /// Evaluates constraints for a single fragment of the evaluation table.
-    ///
-    /// This evaluates constraints only over all segments of the execution trace (i.e. main segment
-    /// and all auxiliary segments).
-    fn evaluate_fragment_full<T: TraceLde<E>>(
-        &self,
-        trace: &T,
-        domain: &StarkDomain<A::BaseField>,
-        fragment: &mut EvaluationTableFragment<E>,
-    ) {
-        // initialize buffers to hold trace values and evaluation results at each step
-        let mut main_frame = EvaluationFrame::new(trace.trace_layout().main_trace_width());
-        let mut aux_frame = EvaluationFrame::new(trace.trace_layout().aux_trace_width());
-        let mut tm_evaluations = vec![E::BaseField::ZERO; self.num_main_transition_constraints()];
-        let mut ta_evaluations = vec![E::ZERO; self.num_aux_transition_constraints()];
-        let mut evaluations = vec![E::ZERO; fragment.num_columns()];
-
-        // this will be used to convert steps in constraint evaluation domain to steps in
-        // LDE domain
-        let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
-
-        for i in 0..fragment.num_rows() {
-            let step = i + fragment.offset();
-
-            // read both the main and the auxiliary evaluation frames from the trace
-            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
-            trace.read_aux_trace_frame_into(step << lde_shift, &mut aux_frame);
-
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer; we evaluate and compose constraints in the same function, we
-            // can just add up the results of evaluating main and auxiliary constraints.
-            evaluations[0] = self.evaluate_main_transition(&main_frame, step, &mut tm_evaluations);
-            evaluations[0] +=
-                self.evaluate_aux_transition(&main_frame, &aux_frame, step, &mut ta_evaluations);
-
-            // when in debug mode, save transition constraint evaluations
-            #[cfg(debug_assertions)]
-            fragment.update_transition_evaluations(i, &tm_evaluations, &ta_evaluations);
+            if let Some(aux_frame) = buffers.aux_frame.as_ref() {
+                self.evaluate_aux_transition(
+                    &buffers.main_frame,
+                    aux_frame,
+                    step,
+                    &mut buffers.aux_evaluations,
+                    t_results,
+                );
+            }
 
             // evaluate boundary constraints; the results go into remaining slots of the
-            // evaluations buffer
-            let main_state = main_frame.current();
-            let aux_state = aux_frame.current();
-            self.boundary_constraints.evaluate_all(
-                main_state,
-                aux_state,
-                domain,
-                step,
-                &mut evaluations[1..],
-            );
-
-            // record the result in the evaluation table
-            fragment.update_row(i, &evaluations);
-        }
+            // results buffer
+            let main_state = buffers.main_frame.current();
+            match buffers.aux_frame.as_ref() {
+                Some(aux_frame) => {
+                    let aux_state = aux_frame.current();
+                    self.boundary_constraints
+                        .evaluate_all(main_state, aux_state, domain, step, b_results);
+                }
+                None => {
+                    self.boundary_constraints.evaluate_main(main_state, domain, step, b_results)
+                }
+            }
+        });
     }
 
     // TRANSITION CONSTRAINT EVALUATORS
@@ -263,6 +242,9 @@ where
     /// Evaluates transition constraints of the main execution trace at the specified step of the
     /// constraint evaluation domain.
     ///
+    /// Merged evaluations are added to the `results` buffer, which contains one slot per distinct
+    /// transition constraint divisor.
+    ///
     /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
     /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
     fn evaluate_main_transition(
@@ -270,28 +252,35 @@ where
         main_frame: &EvaluationFrame<E::BaseField>,
         step: usize,
         evaluations: &mut [E::BaseField],
-    ) -> E {
+        results: &mut [E],
+    ) {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::BaseField::ZERO);
 
-        // get periodic values at the evaluation step
-        let periodic_values = self.periodic_values.get_row(step);
+        // get values of periodic columns associated with the main segment at the evaluation step
+        let periodic_values = self.periodic_values.get_main_row(step);
 
         // evaluate transition constraints over the main segment of the execution trace and save
         // the results into evaluations buffer
         self.air.evaluate_transition(main_frame, periodic_values, evaluations);
 
-        // merge transition constraint evaluations into a single value and return it;
-        // we can do this here because all transition constraints have the same divisor.
-        evaluations
+        // merge transition constraint evaluations into a single value per divisor; we can do
+        // this here because all constraints in the same group have the same divisor.
+        for ((&const_eval, &coef), &divisor_idx) in evaluations
             .iter()
             .zip(self.transition_constraints.main_constraint_coef().iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef.mul_base(const_eval))
+            .zip(self.transition_constraints.main_constraint_divisors().iter())
+        {
+            results[divisor_idx] += coef.mul_base(const_eval);
+        }
     }
 
     /// Evaluates all transition constraints (i.e., for main and auxiliary trace segments) at the
     /// specified step of the constraint evaluation domain.
     ///
+    /// Merged evaluations are added to the `results` buffer, which contains one slot per distinct
+    /// transition constraint divisor.
+    ///
     /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
     /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
     fn evaluate_aux_transition(
@@ -300,7 +289,8 @@ where
         aux_frame: &EvaluationFrame<E>,
         step: usize,
         evaluations: &mut [E],
-    ) -> E {
+        results: &mut [E],
+    ) {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::ZERO);
 
@@ -317,25 +307,40 @@ where
             evaluations,
         );
 
-        // merge transition constraint evaluations into a single value and return it;
-        // we can do this here because all transition constraints have the same divisor.
-        evaluations
+        // merge transition constraint evaluations into a single value per divisor; we can do
+        // this here because all constraints in the same group have the same divisor.
+        for ((&const_eval, &coef), &divisor_idx) in evaluations
             .iter()
             .zip(self.transition_constraints.aux_constraint_coef().iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef * const_eval)
+            .zip(self.transition_constraints.aux_constraint_divisors().iter())
+        {
+            results[divisor_idx] += coef * const_eval;
+        }
     }
 
     // ACCESSORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns the number of transition constraints applied against the main segment of the
-    /// execution trace.
-    fn num_main_transition_constraints(&self) -> usize {
-        self.transition_constraints.num_main_constraints()
+    /// Returns the number of distinct divisors of transition constraints.
+    fn num_transition_divisors(&self) -> usize {
+        self.transition_constraints.num_divisors()
     }
+}
 
-    /// Returns the number of transition constraints applied against all auxiliary trace segments.
-    fn num_aux_transition_constraints(&self) -> usize {
-        self.transition_constraints.num_aux_constraints()
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of fragments into which an evaluation table for a constraint evaluation
+/// domain of the specified size should be broken for concurrent evaluation.
+///
+/// The result is a power of two such that every thread gets several fragments (for dynamic load
+/// balancing), but each fragment still contains enough rows to amortize the scheduling overhead.
+#[cfg(feature = "concurrent")]
+fn get_num_concurrent_fragments(ce_domain_size: usize) -> usize {
+    if ce_domain_size < MIN_CONCURRENT_DOMAIN_SIZE {
+        return 1;
     }
+    let max_fragments = ce_domain_size / MIN_CONCURRENT_FRAGMENT_SIZE;
+    let num_fragments = rayon::current_num_threads().next_power_of_two() * FRAGMENTS_PER_THREAD;
+    core::cmp::min(num_fragments, max_fragments)
 }
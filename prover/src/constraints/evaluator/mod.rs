@@ -13,8 +13,11 @@ pub use default::DefaultConstraintEvaluator;
 mod boundary;
 use boundary::BoundaryConstraints;
 
+mod buffers;
+pub use buffers::EvaluationBuffers;
+
 mod periodic_table;
-use periodic_table::PeriodicValueTable;
+pub use periodic_table::PeriodicValueTable;
 
 // CONSTRAINT EVALUATOR TRAIT
 // ================================================================================================
@@ -10,10 +10,19 @@ use utils::{
     uninit_vector,
 };
 
+// PERIODIC VALUE TABLE
+// ================================================================================================
+
+/// Values of all periodic columns of an AIR expanded over the constraint evaluation domain.
+///
+/// Values for the same step of the constraint evaluation domain are stored next to each other,
+/// and thus, rows of the table can be used as periodic value buffers during constraint
+/// evaluation without copying.
 pub struct PeriodicValueTable<B: StarkField> {
     values: Vec<B>,
     length: usize,
     width: usize,
+    num_main_columns: usize,
 }
 
 impl<B: StarkField> PeriodicValueTable<B> {
@@ -22,6 +31,9 @@ impl<B: StarkField> PeriodicValueTable<B> {
     /// Builds a table of periodic column values for the specified AIR. The table contains expanded
     /// values of all periodic columns normalized to the same length. This enables simple lookup
     /// into the able using step index of the constraint evaluation domain.
+    ///
+    /// Values of periodic columns associated with the main trace segment are placed at the
+    /// beginning of each row.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> PeriodicValueTable<B> {
         // get a list of polynomials describing periodic columns from AIR. if there are no
         // periodic columns return an empty table
@@ -31,6 +43,7 @@ impl<B: StarkField> PeriodicValueTable<B> {
                 values: Vec::new(),
                 length: 0,
                 width: 0,
+                num_main_columns: 0,
             };
         }
 
@@ -70,16 +83,20 @@ impl<B: StarkField> PeriodicValueTable<B> {
             values,
             length: column_length,
             width: row_width,
+            num_main_columns: air.num_main_periodic_columns(),
         }
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns true if the AIR for which this table was built has no periodic columns.
     pub fn is_empty(&self) -> bool {
         self.width == 0
     }
 
+    /// Returns values of all periodic columns at the specified step of the constraint evaluation
+    /// domain.
     pub fn get_row(&self, ce_step: usize) -> &[B] {
         if self.is_empty() {
             &[]
@@ -88,6 +105,12 @@ impl<B: StarkField> PeriodicValueTable<B> {
             &self.values[start..start + self.width]
         }
     }
+
+    /// Returns values of periodic columns associated with the main trace segment at the
+    /// specified step of the constraint evaluation domain.
+    pub fn get_main_row(&self, ce_step: usize) -> &[B] {
+        &self.get_row(ce_step)[..self.num_main_columns]
+    }
 }
 
 // TESTS
@@ -128,7 +151,7 @@ mod tests {
                 domain
                     .iter()
                     .map(|&x| {
-                        let x = x.exp((num_cycles as u32).into());
+                        let x = x.exp((num_cycles as u64).into());
                         polynom::eval(poly, x)
                     })
                     .collect::<Vec<_>>()
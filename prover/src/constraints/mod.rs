@@ -6,7 +6,9 @@
 use super::{ColMatrix, ConstraintDivisor, ProverError, RowMatrix, StarkDomain};
 
 mod evaluator;
-pub use evaluator::{ConstraintEvaluator, DefaultConstraintEvaluator};
+pub use evaluator::{
+    ConstraintEvaluator, DefaultConstraintEvaluator, EvaluationBuffers, PeriodicValueTable,
+};
 
 mod composition_poly;
 pub use composition_poly::CompositionPoly;
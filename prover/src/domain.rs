@@ -3,7 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::Air;
+use air::{Air, ColumnGroup, RowHashStrategy};
+use core::cmp;
 use math::{fft, get_power_series, StarkField};
 use utils::collections::Vec;
 
@@ -18,8 +19,8 @@ pub struct StarkDomain<B: StarkField> {
     /// [g^i for i in (0..ce_domain_size)] where g is the constraint evaluation domain generator.
     ce_domain: Vec<B>,
 
-    /// LDE domain size / constraint evaluation domain size
-    ce_to_lde_blowup: usize,
+    /// LDE domain size / trace domain size
+    lde_blowup: usize,
 
     /// A mask which can be used to compute (x % ce_domain_size) via binary AND. This takes
     /// advantage of the fact that ce_domain_size is a power of two. The mask is then simply
@@ -28,6 +29,12 @@ pub struct StarkDomain<B: StarkField> {
 
     /// Offset of the low-degree extension domain.
     domain_offset: B,
+
+    /// Group of main trace columns extended over a smaller domain than the rest of the trace.
+    column_group: Option<ColumnGroup>,
+
+    /// Strategy used to hash rows of trace LDEs into leaves of trace commitments.
+    row_hash_strategy: RowHashStrategy,
 }
 
 // STARK DOMAIN IMPLEMENTATION
@@ -45,9 +52,11 @@ impl<B: StarkField> StarkDomain<B> {
         StarkDomain {
             trace_twiddles,
             ce_domain,
-            ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
+            lde_blowup: air.lde_blowup_factor(),
             ce_domain_mod_mask: air.ce_domain_size() - 1,
             domain_offset: air.domain_offset(),
+            column_group: air.context().column_group().cloned(),
+            row_hash_strategy: air.context().row_hash_strategy(),
         }
     }
 
@@ -67,9 +76,11 @@ impl<B: StarkField> StarkDomain<B> {
         StarkDomain {
             trace_twiddles,
             ce_domain,
-            ce_to_lde_blowup: 1,
+            lde_blowup: blowup_factor,
             ce_domain_mod_mask: ce_domain_size - 1,
             domain_offset,
+            column_group: None,
+            row_hash_strategy: RowHashStrategy::default(),
         }
     }
 
@@ -93,7 +104,7 @@ impl<B: StarkField> StarkDomain<B> {
 
     /// Returns blowup factor from trace to LDE domain.
     pub fn trace_to_lde_blowup(&self) -> usize {
-        self.lde_domain_size() / self.trace_length()
+        self.lde_blowup
     }
 
     // CONSTRAINT EVALUATION DOMAIN
@@ -111,8 +122,15 @@ impl<B: StarkField> StarkDomain<B> {
     }
 
     /// Returns blowup factor from constraint evaluation to LDE domain.
+    ///
+    /// # Panics
+    /// Panics if the constraint evaluation domain is larger than the LDE domain.
     pub fn ce_to_lde_blowup(&self) -> usize {
-        self.ce_to_lde_blowup
+        assert!(
+            self.ce_domain_size() <= self.lde_domain_size(),
+            "constraint evaluation domain is larger than the LDE domain"
+        );
+        self.lde_domain_size() / self.ce_domain_size()
     }
 
     /// Returns s * g^step where g is the constraint evaluation domain generator and s is the
@@ -131,9 +149,10 @@ impl<B: StarkField> StarkDomain<B> {
     pub fn get_ce_x_power_at(&self, step: usize, power: u64, offset_exp: B) -> B {
         debug_assert_eq!(offset_exp, self.offset().exp(power.into()));
         // this computes (step * power) % ce_domain_size. even though both step and power could be
-        // 64-bit values, we are not concerned about overflow here because we are modding by a
-        // power of two. this is also the reason why we can do & ce_domain_mod_mask instead of
-        // performing the actual modulus operation.
+        // 64-bit values, we are not concerned about overflow (or truncation of power on 32-bit
+        // platforms) here because we are modding by a power of two which fits into usize. this
+        // is also the reason why we can do & ce_domain_mod_mask instead of performing the actual
+        // modulus operation.
         let index = step.wrapping_mul(power as usize) & self.ce_domain_mod_mask;
         self.ce_domain[index] * offset_exp
     }
@@ -143,11 +162,84 @@ impl<B: StarkField> StarkDomain<B> {
 
     /// Returns the size of the low-degree extension domain.
     pub fn lde_domain_size(&self) -> usize {
-        self.ce_domain_size() * self.ce_to_lde_blowup()
+        self.trace_length() * self.lde_blowup
     }
 
     /// Returns LDE domain offset.
     pub fn offset(&self) -> B {
         self.domain_offset
     }
+
+    // TRACE LDE DOMAIN
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the size of the domain over which the execution trace is extended.
+    ///
+    /// This is the larger of the LDE domain and the constraint evaluation domain, and thus, the
+    /// extended trace can be read at every step of the constraint evaluation domain.
+    pub fn trace_lde_domain_size(&self) -> usize {
+        cmp::max(self.ce_domain_size(), self.lde_domain_size())
+    }
+
+    /// Returns the distance between rows of the extended execution trace which belong to the LDE
+    /// domain.
+    ///
+    /// This is 1 unless the constraint evaluation domain is larger than the LDE domain.
+    pub fn trace_lde_stride(&self) -> usize {
+        self.trace_lde_domain_size() / self.lde_domain_size()
+    }
+
+    /// Returns the domain over which the execution trace is extended if it is different from the
+    /// LDE domain (i.e., the constraint evaluation domain is larger than the LDE domain), or None
+    /// otherwise.
+    ///
+    /// The returned domain has the same offset as the LDE domain, and thus, the LDE domain is a
+    /// subset of the returned domain.
+    pub fn trace_lde_domain(&self) -> Option<Self> {
+        if self.trace_lde_stride() == 1 {
+            return None;
+        }
+        let mut domain = Self::from_twiddles(
+            self.trace_twiddles.clone(),
+            self.trace_to_ce_blowup(),
+            self.offset(),
+        );
+        domain.row_hash_strategy = self.row_hash_strategy;
+        Some(domain)
+    }
+
+    // COLUMN GROUP DOMAIN
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the group of main trace columns which is extended over a smaller domain than the
+    /// rest of the execution trace, if the computation defines one.
+    pub fn column_group(&self) -> Option<&ColumnGroup> {
+        self.column_group.as_ref()
+    }
+
+    /// Returns the domain over which columns of the column group are extended, or None if the
+    /// computation does not define a column group.
+    ///
+    /// The returned domain has the same offset as the LDE domain, and thus, is a subset of the
+    /// LDE domain.
+    pub fn column_group_domain(&self) -> Option<Self> {
+        self.column_group.as_ref().map(|group| {
+            let mut domain = Self::from_twiddles(
+                self.trace_twiddles.clone(),
+                group.blowup_factor(),
+                self.offset(),
+            );
+            domain.row_hash_strategy = self.row_hash_strategy;
+            domain
+        })
+    }
+
+    // TRACE COMMITMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the strategy used to hash rows of trace segment LDEs into leaves of trace
+    /// commitment Merkle trees.
+    pub fn row_hash_strategy(&self) -> RowHashStrategy {
+        self.row_hash_strategy
+    }
 }
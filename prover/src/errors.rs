@@ -6,6 +6,7 @@
 //! Contains common error types for prover and verifier.
 
 use core::fmt;
+use utils::string::{String, ToString};
 
 // PROVER ERROR
 // ================================================================================================
@@ -21,6 +22,45 @@ pub enum ProverError {
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when the degree of the field in which proof generation was requested
+    /// is different from the degree of the field extension specified by proof options.
+    FieldExtensionMismatch(usize, usize),
+    /// This error occurs when the number of columns in a trace segment is different from the
+    /// number of columns specified by the trace layout. Segment 0 is the main trace segment, and
+    /// auxiliary segments have indexes starting with 1.
+    TraceWidthMismatch {
+        segment: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// This error occurs when the number of rows in a trace segment is different from the length
+    /// of the execution trace. Segment 0 is the main trace segment, and auxiliary segments have
+    /// indexes starting with 1.
+    TraceLengthMismatch {
+        segment: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// This error occurs when a domain required for proof generation is larger than the largest
+    /// multiplicative subgroup of size $2^k$ in the base field.
+    DomainTooLarge {
+        domain_size: usize,
+        max_domain_size: usize,
+    },
+    /// This error occurs when the degree of the combined constraint polynomial exceeds the degree
+    /// which can be committed to via the constraint composition columns. This usually means that
+    /// the execution trace does not satisfy the constraints, or that constraint degrees declared
+    /// by the AIR are smaller than the actual degrees of the constraints.
+    ConstraintDegreeOverflow {
+        max_degree: usize,
+        actual_degree: usize,
+    },
+    /// This error occurs when random elements for the auxiliary trace segment with the specified
+    /// index could not be drawn from the public coin.
+    AuxTraceRandElementsUnavailable(usize),
+    /// This error occurs when the auxiliary trace segment with the specified index could not be
+    /// built.
+    AuxTraceBuildFailed(usize, AuxTraceError),
 }
 
 impl fmt::Display for ProverError {
@@ -36,6 +76,94 @@ impl fmt::Display for ProverError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the specified base field")
             }
+            Self::FieldExtensionMismatch(expected, actual) => {
+                write!(f, "proof options specify field extension of degree {expected}, but proof generation was requested in a field of degree {actual}")
+            }
+            Self::TraceWidthMismatch { segment, expected, actual } => {
+                write!(f, "trace segment {segment} has an unexpected number of columns; expected {expected}, but was {actual}")
+            }
+            Self::TraceLengthMismatch { segment, expected, actual } => {
+                write!(f, "trace segment {segment} has an unexpected number of rows; expected {expected}, but was {actual}")
+            }
+            Self::DomainTooLarge { domain_size, max_domain_size } => {
+                write!(f, "domain of {domain_size} elements is larger than the largest domain supported by the base field ({max_domain_size} elements)")
+            }
+            Self::ConstraintDegreeOverflow { max_degree, actual_degree } => {
+                write!(f, "combined constraint polynomial degree cannot exceed {max_degree}, but was {actual_degree}")
+            }
+            Self::AuxTraceRandElementsUnavailable(idx) => {
+                write!(f, "failed to draw random elements for auxiliary trace segment {idx}")
+            }
+            Self::AuxTraceBuildFailed(idx, err) => {
+                write!(f, "failed to build auxiliary trace segment {idx}: {err}")
+            }
+        }
+    }
+}
+
+// AUXILIARY TRACE ERROR
+// ================================================================================================
+/// Represents an error returned by [Trace::build_aux_segment()](crate::Trace::build_aux_segment)
+/// when an auxiliary trace segment could not be built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuxTraceError {
+    /// This error occurs when the trace does not define an auxiliary segment with the specified
+    /// index.
+    UndefinedSegment(usize),
+    /// This error occurs when the witness data from which an auxiliary segment is built is
+    /// malformed (e.g., when the values of the main trace segment are inconsistent with each
+    /// other).
+    InvalidWitness(String),
+}
+
+impl fmt::Display for AuxTraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UndefinedSegment(idx) => {
+                write!(f, "auxiliary trace segment {idx} is not defined by the trace")
+            }
+            Self::InvalidWitness(msg) => {
+                write!(f, "invalid witness: {msg}")
+            }
+        }
+    }
+}
+
+impl From<MatrixError> for AuxTraceError {
+    fn from(err: MatrixError) -> Self {
+        Self::InvalidWitness(err.to_string())
+    }
+}
+
+// MATRIX ERROR
+// ================================================================================================
+/// Represents an error returned by [ColMatrixBuilder::build()](crate::ColMatrixBuilder::build)
+/// when the data appended to the builder does not form a valid matrix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatrixError {
+    /// This error occurs when the matrix does not contain any columns.
+    NoColumns,
+    /// This error occurs when the column with the specified index has a different number of rows
+    /// than the first column; the error specifies the column index, the expected number of rows,
+    /// and the actual number of rows.
+    InconsistentColumnLength(usize, usize, usize),
+    /// This error occurs when the number of rows in the matrix is not a power of two greater
+    /// than one.
+    InvalidNumRows(usize),
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoColumns => {
+                write!(f, "a matrix must contain at least one column")
+            }
+            Self::InconsistentColumnLength(col_idx, expected, actual) => {
+                write!(f, "column {col_idx} must contain {expected} rows, but contained {actual}")
+            }
+            Self::InvalidNumRows(num_rows) => {
+                write!(f, "number of rows in a matrix must be a power of two greater than one, but was {num_rows}")
+            }
         }
     }
 }
@@ -44,16 +44,25 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TraceLayout, TransitionConstraintDegree,
+    lagrange_kernel_boundary_value, lagrange_kernel_transition_divisor,
+    proof::{ColumnGroupProof, StarkProof},
+    AggregateAir, AggregatePublicInputs, Air, AirContext, Assertion, AuxRandElementsReader,
+    AuxTraceRandElements, BatchingMethod, BoundaryConstraint, BoundaryConstraintGroup, ColumnGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintLeafEncoding,
+    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, LagrangeKernelEvaluationFrame,
+    OodConstraintBatching, PeriodicColumn, PermutationArg, PermutationArgBuilder, ProofOptions,
+    RangeCheck, RangeCheckBuilder, RowHashStrategy, SelectorColumns, SelectorFlag, TraceInfo,
+    TraceLayout, TraceMetaValue, TraceMetadata, TransitionConstraintDegree,
+    MIN_RANGE_CHECK_TRACE_LENGTH,
 };
 pub use utils::{
     iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
     SliceReader,
 };
 
+#[cfg(feature = "protobuf")]
+pub use air::proof::protobuf;
+
 use fri::FriProver;
 use utils::collections::Vec;
 
@@ -65,7 +74,7 @@ use math::{
 };
 
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, RandomCoin};
 
 #[cfg(feature = "std")]
 use log::debug;
@@ -76,24 +85,40 @@ mod domain;
 pub use domain::StarkDomain;
 
 pub mod matrix;
-pub use matrix::{ColMatrix, RowMatrix};
+pub use matrix::{ColMatrix, ColMatrixBuilder, RowMatrix};
 
 mod constraints;
 pub use constraints::{
-    CompositionPoly, ConstraintCommitment, ConstraintEvaluator, DefaultConstraintEvaluator,
+    CompositionPoly, ConstraintCommitment, ConstraintEvaluationTable, ConstraintEvaluator,
+    DefaultConstraintEvaluator, EvaluationBuffers, EvaluationTableFragment, PeriodicValueTable,
 };
 
 mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
-pub use trace::{DefaultTraceLde, Trace, TraceLde, TracePolyTable, TraceTable, TraceTableFragment};
+pub use trace::{
+    build_lagrange_kernel_column, DefaultTraceLde, Trace, TraceLde, TracePolyTable, TraceTable,
+    TraceTableFragment,
+};
 
 mod channel;
-use channel::ProverChannel;
+pub use channel::ProverChannel;
+
+mod polys;
+#[cfg(feature = "export-polys")]
+pub use polys::ProofPolys;
+#[cfg(not(feature = "export-polys"))]
+use polys::ProofPolys;
+
+mod pending;
+pub use pending::PendingProof;
+
+mod aggregate;
+pub use aggregate::{AggregateProver, AggregateTrace};
 
 mod errors;
-pub use errors::ProverError;
+pub use errors::{AuxTraceError, MatrixError, ProverError};
 
 #[cfg(test)]
 pub mod tests;
@@ -104,6 +129,12 @@ pub mod tests;
 // this segment width seems to give the best performance for small fields (i.e., 64 bits)
 const DEFAULT_SEGMENT_WIDTH: usize = 8;
 
+/// FFT backend of the trace LDE used by prover `P` for extension field `E`.
+type FftBackendOf<P, E> = <<P as Prover>::TraceLde<E> as TraceLde<E>>::FftBackend;
+
+/// Hash function used by prover `P` to commit to auxiliary trace segments for extension field `E`.
+type AuxHashFnOf<P, E> = <<P as Prover>::TraceLde<E> as TraceLde<E>>::AuxHashFn;
+
 /// Defines a STARK prover for a computation.
 ///
 /// A STARK prover can be used to generate STARK proofs. The prover contains definitions of a
@@ -121,6 +152,11 @@ const DEFAULT_SEGMENT_WIDTH: usize = 8;
 /// The generated proof is built using protocol parameters defined by the [ProofOptions] struct
 /// return from [Prover::options] method.
 ///
+/// Proof generation is deterministic: all randomness used by the prover is drawn from the
+/// [RandomCoin] seeded with the transcript of the protocol. In particular, the prover does not
+/// salt commitments or add blinding rows to the execution trace, and thus, generated proofs are
+/// not zero-knowledge.
+///
 /// To further customize the prover, implementers can specify custom implementations of the
 /// [RandomCoin], [TraceLde], and [ConstraintEvaluator] associated types (default implementations
 /// of these types are provided with the prover). For example, providing custom implementations
@@ -209,6 +245,149 @@ pub trait Prover {
         }
     }
 
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace together with the polynomials committed to in the proof.
+    ///
+    /// This is the same as [prove()](Prover::prove), but the trace polynomials and the columns of
+    /// the constraint composition polynomial are retained and returned to the caller. This allows
+    /// computing additional openings of the committed data which are consistent with the proof.
+    ///
+    /// The field `E` must be the field in which the protocol is executed as specified by the
+    /// field extension in proof options.
+    ///
+    /// # Errors
+    /// Returns an error if `E` does not match the field extension specified in proof options,
+    /// or under the same conditions as [prove()](Prover::prove).
+    #[cfg(feature = "export-polys")]
+    fn prove_with_polys<E>(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(StarkProof, ProofPolys<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        check_field_extension::<Self::BaseField, E>(self.options())?;
+        let (proof, polys, _) = self.generate_proof_with_artifacts::<E>(trace, true)?;
+        Ok((proof, polys.expect("proof polynomials were not retained")))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace together with the low-degree extension of the execution trace committed to
+    /// in the proof.
+    ///
+    /// The returned trace LDE can be used to open trace commitments of the proof at arbitrary
+    /// positions of the LDE domain via [TraceLde::query()] (i.e., not only at positions chosen via
+    /// the Fiat-Shamir transform). This enables side protocols, such as data availability
+    /// sampling or cross-proof consistency checks, to reuse commitments of the proof. Such
+    /// openings can be checked via `DefaultVerifierChannel::verify_trace_openings()` in the
+    /// verifier crate.
+    ///
+    /// The field `E` must be the field in which the protocol is executed as specified by the
+    /// field extension in proof options.
+    ///
+    /// # Errors
+    /// Returns an error if `E` does not match the field extension specified in proof options,
+    /// or under the same conditions as [prove()](Prover::prove).
+    fn prove_with_trace_lde<E>(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(StarkProof, Self::TraceLde<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        check_field_extension::<Self::BaseField, E>(self.options())?;
+        let (proof, _, trace_lde) = self.generate_proof_with_artifacts::<E>(trace, false)?;
+        Ok((proof, trace_lde))
+    }
+
+    /// Performs the first phase of a two-phase proof generation procedure: commits to the main
+    /// segment of the provided execution trace and returns a [PendingProof] which can be
+    /// completed via [prove_phase2()](Prover::prove_phase2).
+    ///
+    /// Splitting proof generation into two phases enables an external interactive step between
+    /// the commitment to the main trace segment and the construction of auxiliary trace segments.
+    /// For example, a coordinator could aggregate main trace commitments (see
+    /// [PendingProof::main_trace_commitment()]) from multiple provers, and send its response back
+    /// to each prover to be bound to their transcripts in the second phase.
+    ///
+    /// The field `E` must be the field in which the protocol is executed as specified by the
+    /// field extension in proof options.
+    ///
+    /// # Errors
+    /// Returns an error if `E` does not match the field extension specified in proof options, or
+    /// if the main trace segment is not consistent with the trace layout.
+    fn prove_phase1<E>(&self, trace: Self::Trace) -> Result<PendingProof<Self, E>, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        check_field_extension::<Self::BaseField, E>(self.options())?;
+        self.commit_main_trace::<E>(trace)
+    }
+
+    /// Performs the second phase of a two-phase proof generation procedure: completes the
+    /// `pending` proof returned from [prove_phase1()](Prover::prove_phase1) and returns the
+    /// resulting STARK proof.
+    ///
+    /// The `aux_inputs` are absorbed into the proof transcript right after the main trace
+    /// segment has been committed to (and after any messages absorbed via
+    /// [absorb_trace_segment_messages()](Prover::absorb_trace_segment_messages) for the main
+    /// segment), and thus, random elements for auxiliary trace segments as well as all subsequent
+    /// randomness depend on them. The inputs are not included in the proof; for the proof to be
+    /// verifiable, the verifier must absorb the same inputs at the same point of the protocol
+    /// (e.g., via `VerifierChannel::absorb_trace_segment_messages()` in the verifier crate). If
+    /// `aux_inputs` is empty, the resulting proof is the same as the one returned from
+    /// [prove()](Prover::prove) for the same trace.
+    ///
+    /// # Errors
+    /// Returns an error if a valid STARK proof could not be generated for the pending proof.
+    fn prove_phase2<E>(
+        &self,
+        pending: PendingProof<Self, E>,
+        aux_inputs: &[<Self::HashFn as Hasher>::Digest],
+    ) -> Result<StarkProof, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.complete_proof::<E>(pending, aux_inputs, false).map(|(proof, ..)| proof)
+    }
+
+    /// Absorbs additional protocol messages into the `channel` right after the trace segment with
+    /// the specified index has been committed to.
+    ///
+    /// The main trace segment has index 0, and auxiliary trace segments have indexes starting
+    /// with 1. For all but the last trace segment, this is invoked before random elements for the
+    /// next auxiliary segment are drawn; for the last trace segment, this is invoked before
+    /// constraint composition coefficients are drawn.
+    ///
+    /// This hook enables protocols which extend the STARK protocol with additional messages
+    /// (e.g., commitments to auxiliary proofs bound to the trace) to participate in the
+    /// Fiat-Shamir transform without modifying the prover. Messages should be absorbed via
+    /// [ProverChannel::absorb()], and must be absorbed by the verifier at the same point of the
+    /// protocol (see `VerifierChannel::absorb_trace_segment_messages()` in the verifier crate).
+    ///
+    /// The default implementation does not absorb any messages.
+    fn absorb_trace_segment_messages<E>(
+        &self,
+        _trace: &Self::Trace,
+        _segment_idx: usize,
+        _channel: &mut ProverChannel<Self::Air, E, Self::HashFn, Self::RandomCoin>,
+    ) where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+    }
+
+    /// Returns an identifier of the prover software (e.g., "winterfell-0.9+zk") to be recorded
+    /// in the context of generated proofs.
+    ///
+    /// The identifier is bound to the proof transcript, and thus, verifiers can rely on it to
+    /// enforce policies on the provenance of proofs. The identifier must not be longer than 255
+    /// bytes.
+    ///
+    /// The default implementation returns `None`, in which case no identifier is recorded.
+    fn prover_id(&self) -> Option<&str> {
+        None
+    }
+
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -216,15 +395,61 @@ pub trait Prover {
     /// execution `trace` is valid against this prover's AIR.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E>(&self, mut trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E>(&self, trace: Self::Trace) -> Result<StarkProof, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.generate_proof_with_artifacts::<E>(trace, false).map(|(proof, ..)| proof)
+    }
+
+    /// Performs the actual proof generation procedure, and returns the proof together with the
+    /// low-degree extension of the execution trace; if `export_polys` is true, also returns the
+    /// polynomials committed to in the proof.
+    #[doc(hidden)]
+    #[allow(clippy::type_complexity)]
+    fn generate_proof_with_artifacts<E>(
+        &self,
+        trace: Self::Trace,
+        export_polys: bool,
+    ) -> Result<(StarkProof, Option<ProofPolys<E>>, Self::TraceLde<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let pending = self.commit_main_trace::<E>(trace)?;
+        self.complete_proof::<E>(pending, &[], export_polys)
+    }
+
+    /// Performs the first part of the proof generation procedure: instantiates the AIR for the
+    /// provided execution `trace`, and commits to the main trace segment.
+    #[doc(hidden)]
+    fn commit_main_trace<E>(&self, trace: Self::Trace) -> Result<PendingProof<Self, E>, ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
         // 0 ----- instantiate AIR and prover channel ---------------------------------------------
 
-        // serialize public inputs; these will be included in the seed for the public coin
+        // make sure the main trace segment is consistent with the trace layout, and that the LDE
+        // domain is supported by the base field; these conditions depend on user input, and thus,
+        // are reported as errors rather than as panics during AIR or domain construction
+        check_segment_shape(
+            0,
+            trace.main_segment(),
+            trace.layout().main_trace_width(),
+            trace.length(),
+        )?;
+        check_domain_size::<Self::BaseField>(
+            trace.length().saturating_mul(self.options().blowup_factor()),
+        )?;
+
+        // serialize public inputs; these will be included in the seed for the public coin. if the
+        // AIR commits to public inputs, only the commitment is used to seed the public coin.
         let pub_inputs = self.get_pub_inputs(&trace);
-        let pub_inputs_elements = pub_inputs.to_elements();
+        let pub_inputs_commitment =
+            Self::Air::get_pub_inputs_commitment::<Self::HashFn>(&pub_inputs);
+        let pub_inputs_elements = match pub_inputs_commitment {
+            Some(_) => Vec::new(),
+            None => pub_inputs.to_elements(),
+        };
 
         // create an instance of AIR for the provided parameters. this takes a generic description
         // of the computation (provided via AIR type), and creates a description of a specific
@@ -234,14 +459,18 @@ pub trait Prover {
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
-        let mut channel = ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new(
-            &air,
-            pub_inputs_elements,
-        );
+        // if auxiliary trace segments are committed to using a different hash function than the
+        // main trace segment, this is recorded in the proof context along with the prover ID.
+        let mut channel =
+            ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new::<
+                AuxHashFnOf<Self, E>,
+            >(&air, pub_inputs_elements, pub_inputs_commitment, self.prover_id());
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
 
-        // build computation domain; this is used later for polynomial evaluations
+        // build computation domain; this is used later for polynomial evaluations. the constraint
+        // evaluation domain may be larger than the LDE domain, and thus, its size is checked too
+        check_domain_size::<Self::BaseField>(air.ce_domain_size())?;
         #[cfg(feature = "std")]
         let now = Instant::now();
         let domain = StarkDomain::new(&air);
@@ -253,7 +482,7 @@ pub trait Prover {
         );
 
         // extend the main execution trace and build a Merkle tree from the extended trace
-        let (mut trace_polys, mut trace_lde): (TracePolyTable<E>, Self::TraceLde<E>) =
+        let (trace_polys, trace_lde): (TracePolyTable<E>, Self::TraceLde<E>) =
             TraceLde::new(&trace.get_info(), trace.main_segment(), &domain);
 
         // get the commitment to the main trace segment LDE
@@ -263,6 +492,58 @@ pub trait Prover {
         // the channel
         channel.commit_trace(main_trace_root);
 
+        // if the computation defines a column group, commit to the LDE of the column group right
+        // after the main trace segment
+        if air.context().column_group().is_some() {
+            let column_group_root = trace_lde
+                .get_column_group_commitment()
+                .expect("trace LDE does not support column groups");
+            channel.commit_trace(column_group_root);
+        }
+        self.absorb_trace_segment_messages(&trace, 0, &mut channel);
+
+        let channel = channel.detach();
+        Ok(PendingProof {
+            trace,
+            air,
+            channel,
+            domain,
+            trace_polys,
+            trace_lde,
+        })
+    }
+
+    /// Performs the remaining part of the proof generation procedure for a proof whose main trace
+    /// segment has been committed to; `aux_inputs` are absorbed into the proof transcript before
+    /// random elements for auxiliary trace segments are drawn. If `export_polys` is true, also
+    /// returns the polynomials committed to in the proof.
+    #[doc(hidden)]
+    #[allow(clippy::type_complexity)]
+    fn complete_proof<E>(
+        &self,
+        pending: PendingProof<Self, E>,
+        aux_inputs: &[<Self::HashFn as Hasher>::Digest],
+        export_polys: bool,
+    ) -> Result<(StarkProof, Option<ProofPolys<E>>, Self::TraceLde<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let PendingProof {
+            mut trace,
+            air,
+            channel,
+            domain,
+            mut trace_polys,
+            mut trace_lde,
+        } = pending;
+        let mut channel = ProverChannel::attach(&air, channel);
+
+        // absorb inputs provided between the two phases of proof generation (if any) into the
+        // transcript; these must be absorbed by the verifier at the same point of the protocol
+        for &input in aux_inputs {
+            channel.absorb(input);
+        }
+
         // build auxiliary trace segments (if any), and append the resulting segments to trace
         // commitment and trace polynomial table structs
         let mut aux_trace_segments = Vec::new();
@@ -272,12 +553,18 @@ pub trait Prover {
             let now = Instant::now();
 
             // draw a set of random elements required to build an auxiliary trace segment
-            let rand_elements = channel.get_aux_trace_segment_rand_elements(i);
+            let rand_elements = channel.get_aux_trace_segment_rand_elements(i)?;
 
             // build the trace segment
             let aux_segment = trace
                 .build_aux_segment(&aux_trace_segments, &rand_elements)
-                .expect("failed build auxiliary trace segment");
+                .map_err(|err| ProverError::AuxTraceBuildFailed(i, err))?;
+            check_segment_shape(
+                i + 1,
+                &aux_segment,
+                trace.layout().get_aux_segment_width(i),
+                trace.length(),
+            )?;
             #[cfg(feature = "std")]
             debug!(
                 "Built auxiliary trace segment of {} columns and 2^{} steps in {} ms",
@@ -291,8 +578,16 @@ pub trait Prover {
                 trace_lde.add_aux_segment(&aux_segment, &domain);
 
             // commit to the LDE of the extended auxiliary trace segment by writing the root of
-            // its Merkle tree into the channel
+            // its Merkle tree into the channel.
+            //
+            // note: auxiliary segments are committed to in separate Merkle trees (rather than in
+            // a single tree together with the main segment) on purpose: random elements used to
+            // build an auxiliary segment are drawn only after the preceding segments have been
+            // committed to, and queries must be opened against these earlier commitments. a tree
+            // built over the main and auxiliary segments together would not be bound to the main
+            // trace root from which the random elements were derived.
             channel.commit_trace(aux_segment_root);
+            self.absorb_trace_segment_messages(&trace, i + 1, &mut channel);
 
             trace_polys.add_aux_segment(aux_segment_polys);
             aux_trace_rand_elements.add_segment_elements(rand_elements);
@@ -368,9 +663,21 @@ pub trait Prover {
         // evaluate trace and constraint polynomials at the OOD point z, and send the results to
         // the verifier. the trace polynomials are actually evaluated over two points: z and z * g,
         // where g is the generator of the trace domain.
+        // if requested, retain the polynomials committed to so far so that they can be returned
+        // together with the proof
+        let proof_polys =
+            export_polys.then(|| ProofPolys::new(trace_polys.clone(), composition_poly.clone(), z));
+
         let ood_trace_states = trace_polys.get_ood_frame(z);
         channel.send_ood_trace_states(&ood_trace_states);
 
+        // if OOD constraint evaluations are combined, columns of the composition polynomial are
+        // merged into a single column whose evaluation at z is the evaluation of the composition
+        // polynomial at z; this column is then used in place of all columns below
+        let composition_poly = match air.options().ood_constraint_batching() {
+            OodConstraintBatching::PerColumn => composition_poly,
+            OodConstraintBatching::Combined => composition_poly.combine_columns_at(z),
+        };
         let ood_evaluations = composition_poly.evaluate_at(z);
         channel.send_ood_constraint_evaluations(&ood_evaluations);
 
@@ -379,9 +686,17 @@ pub trait Prover {
         let deep_coefficients = channel.get_deep_composition_coeffs();
         let mut deep_composition_poly = DeepCompositionPoly::new(z, deep_coefficients);
 
-        // combine all trace polynomials together and merge them into the DEEP composition
-        // polynomial
-        deep_composition_poly.add_trace_polys(trace_polys, ood_trace_states);
+        // if the computation defines a column group, combine polynomials of the group columns
+        // into a separate DEEP composition polynomial; these columns are extended over a smaller
+        // domain, and thus, their low-degree is proven via a separate FRI instance
+        let column_group = air.context().column_group();
+        let column_group_poly = column_group.map(|group| {
+            deep_composition_poly.build_column_group_poly(group, &trace_polys, &ood_trace_states)
+        });
+
+        // combine all remaining trace polynomials together and merge them into the DEEP
+        // composition polynomial
+        deep_composition_poly.add_trace_polys(trace_polys, ood_trace_states, column_group);
 
         // merge columns of constraint composition polynomial into the DEEP composition polynomial;
         deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
@@ -417,7 +732,9 @@ pub trait Prover {
         // 6 ----- compute FRI layers for the composition polynomial ------------------------------
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let mut fri_prover = FriProver::new(air.options().to_fri_options());
+        let mut fri_prover = FriProver::<_, _, _, _, FftBackendOf<Self, E>>::with_fft_backend(
+            air.options().to_fri_options(),
+        );
         fri_prover.build_layers(&mut channel, deep_evaluations);
         #[cfg(feature = "std")]
         debug!(
@@ -426,6 +743,19 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
+        // if the computation defines a column group, evaluate the DEEP composition polynomial of
+        // the group over the column group domain and compute FRI layers for it; commitments to
+        // these layers follow the commitments to the layers of the main FRI instance
+        let column_group_fri_prover = column_group_poly.map(|group_poly| {
+            let group_domain = domain.column_group_domain().expect("no column group domain");
+            let group_evaluations = group_poly.evaluate(&group_domain);
+            let fri_options = air.context().column_group_fri_options().expect("no column group");
+            let mut group_fri_prover =
+                FriProver::<_, _, _, _, FftBackendOf<Self, E>>::with_fft_backend(fri_options);
+            group_fri_prover.build_layers(&mut channel, group_evaluations);
+            group_fri_prover
+        });
+
         // 7 ----- determine query positions ------------------------------------------------------
         #[cfg(feature = "std")]
         let now = Instant::now();
@@ -458,12 +788,29 @@ pub trait Prover {
         // merged into a single value and Merkle authentication paths contain these values already
         let constraint_queries = constraint_commitment.query(&query_positions);
 
+        // query the column group at positions of the column group domain (if the computation
+        // defines a column group); positions in the column group domain are drawn after positions
+        // in the LDE domain
+        let column_group_proof = channel.get_column_group_query_positions().map(|positions| {
+            let queries = trace_lde
+                .query_column_group(&positions)
+                .expect("trace LDE does not support column groups");
+            let fri_proof = column_group_fri_prover
+                .expect("no column group FRI prover")
+                .build_proof(&positions);
+            ColumnGroupProof::new(queries, fri_proof)
+        });
+
         // build the proof object
         let proof = channel.build_proof(trace_queries, constraint_queries, fri_proof);
+        let proof = match column_group_proof {
+            Some(column_group_proof) => proof.with_column_group(column_group_proof),
+            None => proof,
+        };
         #[cfg(feature = "std")]
         debug!("Built proof object in {} ms", now.elapsed().as_millis());
 
-        Ok(proof)
+        Ok((proof, proof_polys, trace_lde))
     }
 
     /// Evaluates constraint composition polynomial over the LDE domain and builds a commitment
@@ -485,10 +832,10 @@ pub trait Prover {
         // evaluate composition polynomial columns over the LDE domain
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let composed_evaluations = RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(
-            composition_poly.data(),
-            domain,
-        );
+        let composed_evaluations = RowMatrix::evaluate_polys_over_with::<
+            DEFAULT_SEGMENT_WIDTH,
+            FftBackendOf<Self, E>,
+        >(composition_poly.data(), domain);
         #[cfg(feature = "std")]
         debug!(
             "Evaluated {} composition polynomial columns over LDE domain (2^{} elements) in {} ms",
@@ -500,7 +847,8 @@ pub trait Prover {
         // build constraint evaluation commitment
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let commitment = composed_evaluations.commit_to_rows();
+        let commitment =
+            composed_evaluations.commit_to_encoded_rows(self.options().constraint_leaf_encoding());
         let constraint_commitment = ConstraintCommitment::new(composed_evaluations, commitment);
         #[cfg(feature = "std")]
         debug!(
@@ -511,3 +859,65 @@ pub trait Prover {
         constraint_commitment
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an error if the provided trace segment does not consist of the expected number of
+/// columns and rows.
+fn check_segment_shape<E: FieldElement>(
+    segment: usize,
+    segment_data: &ColMatrix<E>,
+    expected_width: usize,
+    expected_length: usize,
+) -> Result<(), ProverError> {
+    if segment_data.num_cols() != expected_width {
+        return Err(ProverError::TraceWidthMismatch {
+            segment,
+            expected: expected_width,
+            actual: segment_data.num_cols(),
+        });
+    }
+    if segment_data.num_rows() != expected_length {
+        return Err(ProverError::TraceLengthMismatch {
+            segment,
+            expected: expected_length,
+            actual: segment_data.num_rows(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns an error if field `E` is not the field in which the protocol is executed according to
+/// the specified proof `options`, or if this field is not supported.
+fn check_field_extension<B, E>(options: &ProofOptions) -> Result<(), ProverError>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    E: FieldElement<BaseField = B>,
+{
+    let extension_degree = options.field_extension().degree() as usize;
+    if E::EXTENSION_DEGREE != extension_degree {
+        return Err(ProverError::FieldExtensionMismatch(extension_degree, E::EXTENSION_DEGREE));
+    }
+    let is_supported = match options.field_extension() {
+        FieldExtension::None => true,
+        FieldExtension::Quadratic => <QuadExtension<B>>::is_supported(),
+        FieldExtension::Cubic => <CubeExtension<B>>::is_supported(),
+    };
+    if !is_supported {
+        return Err(ProverError::UnsupportedFieldExtension(extension_degree));
+    }
+    Ok(())
+}
+
+/// Returns an error if a domain of the specified size cannot be built over base field `B`.
+fn check_domain_size<B: StarkField>(domain_size: usize) -> Result<(), ProverError> {
+    let max_domain_size = 1_usize.checked_shl(B::TWO_ADICITY).unwrap_or(usize::MAX);
+    if domain_size > max_domain_size {
+        return Err(ProverError::DomainTooLarge {
+            domain_size,
+            max_domain_size,
+        });
+    }
+    Ok(())
+}
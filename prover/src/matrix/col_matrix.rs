@@ -3,10 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::StarkDomain;
+use super::{RowMatrix, TRANSPOSE_BLOCK_SIZE};
+use crate::{MatrixError, StarkDomain};
 use core::{iter::FusedIterator, slice};
 use crypto::{ElementHasher, MerkleTree};
-use math::{fft, polynom, FieldElement};
+use math::{
+    fft::{self, CpuFftBackend, FftBackend},
+    polynom, FieldElement,
+};
 use utils::{batch_iter_mut, collections::Vec, iter, iter_mut, uninit_vector};
 
 #[cfg(feature = "concurrent")]
@@ -55,6 +59,69 @@ impl<E: FieldElement> ColMatrix<E> {
         Self { columns }
     }
 
+    /// Returns a new [Matrix] with the specified number of columns and rows, in which rows are
+    /// filled one by one by the provided closure.
+    ///
+    /// The closure receives the index of the row being filled and a mutable reference to the
+    /// row; the row contains values of the previous row (or all zeros for the first row), and its
+    /// contents are copied into the matrix after the closure returns. Memory of the matrix is not
+    /// initialized before the rows are written into it, and thus, this is a safe alternative to
+    /// allocating uninitialized columns and filling them afterwards.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_cols` is zero.
+    /// * `num_rows` is smaller than or equal to 1, or is not a power of two.
+    pub fn from_row_fn<F>(num_cols: usize, num_rows: usize, mut fill_row: F) -> Self
+    where
+        F: FnMut(usize, &mut [E]),
+    {
+        let mut columns = (0..num_cols).map(|_| Vec::with_capacity(num_rows)).collect::<Vec<_>>();
+        let mut row = vec![E::ZERO; num_cols];
+        for row_idx in 0..num_rows {
+            fill_row(row_idx, &mut row);
+            for (column, &value) in columns.iter_mut().zip(row.iter()) {
+                column.push(value);
+            }
+        }
+        Self::new(columns)
+    }
+
+    /// Returns a new [Matrix] containing the same values as the specified row-major matrix.
+    ///
+    /// Transposition is performed in blocks of columns so that rows of the source matrix are read
+    /// sequentially, while values are appended to a small number of columns at a time. When
+    /// `concurrent` feature is enabled, the blocks are transposed in multiple threads.
+    pub fn from_row_matrix(matrix: &RowMatrix<E>) -> Self {
+        let num_rows = matrix.num_rows();
+        let mut columns = (0..matrix.num_cols())
+            .map(|_| unsafe { uninit_vector(num_rows) })
+            .collect::<Vec<Vec<E>>>();
+
+        // define a closure for transposing a block of columns; every value in the block is
+        // overwritten as every row of the source matrix is copied into it
+        let transpose_block = |(block_idx, block): (usize, &mut [Vec<E>])| {
+            let col_offset = block_idx * TRANSPOSE_BLOCK_SIZE;
+            for (row_idx, row) in matrix.rows().enumerate() {
+                let values = &row[col_offset..col_offset + block.len()];
+                for (column, &value) in block.iter_mut().zip(values) {
+                    column[row_idx] = value;
+                }
+            }
+        };
+
+        #[cfg(not(feature = "concurrent"))]
+        columns.chunks_mut(TRANSPOSE_BLOCK_SIZE).enumerate().for_each(transpose_block);
+
+        #[cfg(feature = "concurrent")]
+        columns
+            .par_chunks_mut(TRANSPOSE_BLOCK_SIZE)
+            .enumerate()
+            .for_each(transpose_block);
+
+        Self::new(columns)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -156,12 +223,12 @@ impl<E: FieldElement> ColMatrix<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns an iterator over the columns of this matrix.
-    pub fn columns(&self) -> ColumnIter<E> {
+    pub fn columns(&self) -> ColumnIter<'_, E> {
         ColumnIter::new(self)
     }
 
     /// Returns a mutable iterator over the columns of this matrix.
-    pub fn columns_mut(&mut self) -> ColumnIterMut<E> {
+    pub fn columns_mut(&mut self) -> ColumnIterMut<'_, E> {
         ColumnIterMut::new(self)
     }
 
@@ -179,11 +246,20 @@ impl<E: FieldElement> ColMatrix<E> {
     /// * The resulting polynomials are returned as a single matrix where each column contains
     ///   coefficients of a degree `num_rows - 1` polynomial.
     pub fn interpolate_columns(&self) -> Self {
+        self.interpolate_columns_with::<CpuFftBackend>()
+    }
+
+    /// Interpolates columns of the matrix into polynomials in coefficient form using the
+    /// specified [FftBackend] and returns the result.
+    ///
+    /// This is the same as [ColMatrix::interpolate_columns()] but iFFTs are performed by the
+    /// `Fft` backend.
+    pub fn interpolate_columns_with<Fft: FftBackend>(&self) -> Self {
         let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
         let columns = iter!(self.columns)
             .map(|evaluations| {
                 let mut column = evaluations.clone();
-                fft::interpolate_poly(&mut column, &inv_twiddles);
+                Fft::interpolate_poly(&mut column, &inv_twiddles);
                 column
             })
             .collect();
@@ -281,8 +357,143 @@ impl<E: FieldElement> ColMatrix<E> {
     /// Returns the columns of this matrix as a list of vectors.
     ///
     /// TODO: replace this with an iterator.
-    pub fn into_columns(self) -> Vec<Vec<E>> {
-        self.columns
+    pub fn into_columns(mut self) -> Vec<Vec<E>> {
+        core::mem::take(&mut self.columns)
+    }
+
+    /// Returns a [ColMatrixBuilder] initialized with the columns of this matrix, to which more
+    /// columns and rows can be appended.
+    pub fn into_builder(self) -> ColMatrixBuilder<E> {
+        ColMatrixBuilder {
+            columns: self.into_columns(),
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<E: FieldElement> Drop for ColMatrix<E> {
+    /// Zeroizes values of this matrix.
+    fn drop(&mut self) {
+        for column in self.columns.iter_mut() {
+            super::zeroize_values(column);
+        }
+    }
+}
+
+// COLUMN MATRIX BUILDER
+// ================================================================================================
+
+/// A builder of a [ColMatrix] to which columns and blocks of rows can be appended incrementally.
+///
+/// Unlike [ColMatrix], the builder does not require its columns to be of the same length, or
+/// the number of rows to be a power of two, while data is being appended to it. This allows
+/// building a matrix (e.g., an auxiliary trace segment) without sizing all columns upfront. The
+/// restrictions of [ColMatrix] are validated once the builder is finalized via
+/// [ColMatrixBuilder::build()].
+#[derive(Debug, Clone, Default)]
+pub struct ColMatrixBuilder<E: FieldElement> {
+    columns: Vec<Vec<E>>,
+}
+
+impl<E: FieldElement> ColMatrixBuilder<E> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new builder with the specified number of empty columns.
+    pub fn new(num_cols: usize) -> Self {
+        Self::with_capacity(num_cols, 0)
+    }
+
+    /// Returns a new builder with the specified number of empty columns, each of which can hold
+    /// at least `num_rows` values without reallocating.
+    pub fn with_capacity(num_cols: usize, num_rows: usize) -> Self {
+        Self {
+            columns: (0..num_cols).map(|_| Vec::with_capacity(num_rows)).collect(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns appended to this builder so far.
+    pub fn num_cols(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the number of rows in the first column of this builder, or zero if the builder
+    /// contains no columns.
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.len())
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified column to the end of this builder.
+    pub fn append_column(&mut self, column: Vec<E>) {
+        self.columns.push(column);
+    }
+
+    /// Appends a single row to the end of all columns of this builder.
+    ///
+    /// # Panics
+    /// Panics if the length of the `row` differs from the number of columns in this builder.
+    pub fn append_row(&mut self, row: &[E]) {
+        assert_eq!(self.num_cols(), row.len(), "row length must match the number of columns");
+        for (column, &value) in self.columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+
+    /// Appends a block of rows to the end of all columns of this builder; the block is specified
+    /// by its columns.
+    ///
+    /// Columns of the block may be of different lengths; in such a case, an error is returned
+    /// when the builder is finalized.
+    ///
+    /// # Panics
+    /// Panics if the number of columns in the `block` differs from the number of columns in
+    /// this builder.
+    pub fn append_rows(&mut self, block: &[Vec<E>]) {
+        assert_eq!(self.num_cols(), block.len(), "block width must match the number of columns");
+        for (column, values) in self.columns.iter_mut().zip(block) {
+            column.extend_from_slice(values);
+        }
+    }
+
+    // FINALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a [ColMatrix] built from the columns appended to this builder.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The builder does not contain any columns.
+    /// * Not all of the columns have the same number of rows.
+    /// * The number of rows is smaller than or equal to 1, or is not a power of two.
+    pub fn build(mut self) -> Result<ColMatrix<E>, MatrixError> {
+        if self.columns.is_empty() {
+            return Err(MatrixError::NoColumns);
+        }
+        let num_rows = self.num_rows();
+        if let Some((col_idx, column)) =
+            self.columns.iter().enumerate().find(|(_, column)| column.len() != num_rows)
+        {
+            return Err(MatrixError::InconsistentColumnLength(col_idx, num_rows, column.len()));
+        }
+        if num_rows <= 1 || !num_rows.is_power_of_two() {
+            return Err(MatrixError::InvalidNumRows(num_rows));
+        }
+        Ok(ColMatrix::new(core::mem::take(&mut self.columns)))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<E: FieldElement> Drop for ColMatrixBuilder<E> {
+    /// Zeroizes values appended to this builder.
+    fn drop(&mut self) {
+        for column in self.columns.iter_mut() {
+            super::zeroize_values(column);
+        }
     }
 }
 
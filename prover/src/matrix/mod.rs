@@ -4,13 +4,35 @@
 // LICENSE file in the root directory of this source tree.
 
 mod row_matrix;
-pub use row_matrix::{build_segments, get_evaluation_offsets, RowMatrix};
+pub use row_matrix::{build_segments, get_evaluation_offsets, FrameIter, RowIter, RowMatrix};
 
 mod col_matrix;
-pub use col_matrix::{ColMatrix, ColumnIter, MultiColumnIter};
+pub use col_matrix::{ColMatrix, ColMatrixBuilder, ColumnIter, MultiColumnIter};
 
 mod segments;
 pub use segments::Segment;
 
 #[cfg(test)]
 mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of rows (or columns) transposed together when converting between [ColMatrix] and
+/// [RowMatrix].
+const TRANSPOSE_BLOCK_SIZE: usize = 64;
+
+// ZEROIZATION
+// ================================================================================================
+
+/// Overwrites the provided values with zeros in a way which is not optimized away by the compiler.
+///
+/// This is used to clear witness data from memory when the structs holding it are dropped.
+#[cfg(feature = "zeroize")]
+pub(crate) fn zeroize_values<T: Copy>(values: &mut [T]) {
+    for value in values.iter_mut() {
+        // SAFETY: this is used only for field elements (and arrays of field elements), which are
+        // plain data types for which the all-zero bit pattern is a valid value
+        unsafe { zeroize::zeroize_flat_type(value as *mut T) };
+    }
+}
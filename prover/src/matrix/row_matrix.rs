@@ -3,10 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ColMatrix, Segment};
+use super::{ColMatrix, Segment, TRANSPOSE_BLOCK_SIZE};
 use crate::StarkDomain;
+use air::{ConstraintLeafEncoding, RowHashStrategy};
+use core::iter::FusedIterator;
 use crypto::{ElementHasher, MerkleTree};
-use math::{fft, FieldElement, StarkField};
+use math::{
+    fft::{self, CpuFftBackend, FftBackend},
+    FieldElement, StarkField,
+};
 use utils::collections::Vec;
 use utils::{batch_iter_mut, flatten_vector_elements, uninit_vector};
 
@@ -82,9 +87,33 @@ impl<E: FieldElement> RowMatrix<E> {
     pub fn evaluate_polys_over<const N: usize>(
         polys: &ColMatrix<E>,
         domain: &StarkDomain<E::BaseField>,
+    ) -> Self {
+        Self::evaluate_polys_over_with::<N, CpuFftBackend>(polys, domain)
+    }
+
+    /// Returns a new [RowMatrix] constructed by evaluating the provided polynomials over the
+    /// specified [StarkDomain] using the specified [FftBackend].
+    ///
+    /// If the `Fft` backend provides batched polynomial evaluation (see
+    /// [FftBackend::evaluate_polys_with_offset()]), the polynomials are evaluated by the backend
+    /// and the results are transposed into a row-major matrix. Otherwise, this is the same as
+    /// [RowMatrix::evaluate_polys_over()].
+    pub fn evaluate_polys_over_with<const N: usize, Fft: FftBackend>(
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
     ) -> Self {
         assert!(N > 0, "batch size N must be greater than zero");
 
+        let columns = polys.columns().collect::<Vec<_>>();
+        if let Some(columns) = Fft::evaluate_polys_with_offset(
+            &columns,
+            domain.trace_twiddles(),
+            domain.offset(),
+            domain.trace_to_lde_blowup(),
+        ) {
+            return Self::from_col_matrix(&ColMatrix::new(columns));
+        }
+
         // pre-compute offsets for each row
         let poly_size = polys.num_rows();
         let offsets =
@@ -132,6 +161,49 @@ impl<E: FieldElement> RowMatrix<E> {
         }
     }
 
+    /// Returns a new [RowMatrix] containing the same values as the specified column-major matrix.
+    ///
+    /// Transposition is performed in blocks of rows so that values written into the new matrix
+    /// stay in cache while columns of the source matrix are read sequentially. When `concurrent`
+    /// feature is enabled, the blocks are transposed in multiple threads.
+    pub fn from_col_matrix(matrix: &ColMatrix<E>) -> Self {
+        let num_rows = matrix.num_rows();
+        let row_width = matrix.num_base_cols();
+        let mut data = unsafe { uninit_vector::<E::BaseField>(num_rows * row_width) };
+
+        // define a closure for transposing a block of rows; every value in the block is
+        // overwritten as every column of the source matrix is copied into it
+        let transpose_block = |(block_idx, block): (usize, &mut [E::BaseField])| {
+            let row_offset = block_idx * TRANSPOSE_BLOCK_SIZE;
+            let block_rows = block.len() / row_width;
+            for (col_idx, column) in matrix.columns().enumerate() {
+                let values = &column[row_offset..row_offset + block_rows];
+                let values = E::slice_as_base_elements(values);
+                let col_offset = col_idx * E::EXTENSION_DEGREE;
+                for (i, value) in values.chunks(E::EXTENSION_DEGREE).enumerate() {
+                    let start = i * row_width + col_offset;
+                    block[start..start + E::EXTENSION_DEGREE].copy_from_slice(value);
+                }
+            }
+        };
+
+        #[cfg(not(feature = "concurrent"))]
+        data.chunks_mut(TRANSPOSE_BLOCK_SIZE * row_width)
+            .enumerate()
+            .for_each(transpose_block);
+
+        #[cfg(feature = "concurrent")]
+        data.par_chunks_mut(TRANSPOSE_BLOCK_SIZE * row_width)
+            .enumerate()
+            .for_each(transpose_block);
+
+        RowMatrix {
+            data,
+            row_width,
+            elements_per_row: row_width,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -163,11 +235,43 @@ impl<E: FieldElement> RowMatrix<E> {
         E::slice_from_base_elements(&self.data[start..start + self.elements_per_row])
     }
 
+    /// Returns a reference to a row at the specified index in this matrix without checking
+    /// whether the index is in bounds.
+    ///
+    /// # Safety
+    /// Calling this method with `row_idx` greater than or equal to the number of rows in this
+    /// matrix is undefined behavior.
+    pub unsafe fn get_row_unchecked(&self, row_idx: usize) -> &[E] {
+        let start = row_idx * self.row_width;
+        E::slice_from_base_elements(self.data.get_unchecked(start..start + self.elements_per_row))
+    }
+
     /// Returns the data in this matrix as a slice of field elements.
     pub fn data(&self) -> &[E::BaseField] {
         &self.data
     }
 
+    // ITERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over the rows of this matrix.
+    pub fn rows(&self) -> RowIter<'_, E> {
+        RowIter::new(self)
+    }
+
+    /// Returns an iterator over evaluation frames of this matrix.
+    ///
+    /// For every row of the matrix, the iterator yields a tuple `(current, next)` where `current`
+    /// is the row itself and `next` is the row located `step` rows after it; row indexes wrap
+    /// around the end of the matrix. For example, for a trace LDE stored in this matrix, `step`
+    /// should be set to the blowup factor of the LDE domain.
+    ///
+    /// # Panics
+    /// Panics if `step` is zero or is greater than or equal to the number of rows in this matrix.
+    pub fn frames(&self, step: usize) -> FrameIter<'_, E> {
+        FrameIter::new(self, step)
+    }
+
     // COMMITMENTS
     // --------------------------------------------------------------------------------------------
 
@@ -182,9 +286,61 @@ impl<E: FieldElement> RowMatrix<E> {
     pub fn commit_to_rows<H>(&self) -> MerkleTree<H>
     where
         H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.commit_to_rows_with(RowHashStrategy::FullRow)
+    }
+
+    /// Returns a commitment to this matrix in which each row of the matrix is hashed into a leaf
+    /// of the Merkle tree using the specified row hash strategy.
+    ///
+    /// See [commit_to_rows()](Self::commit_to_rows) for details.
+    pub fn commit_to_rows_with<H>(&self, strategy: RowHashStrategy) -> MerkleTree<H>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.build_row_commitment(1, |row| strategy.hash_row::<H, E>(row))
+    }
+
+    /// Returns a commitment to every `stride`-th row of this matrix in which each committed row
+    /// is hashed into a leaf of the Merkle tree using the specified row hash strategy.
+    ///
+    /// Thus, the number of leaves in the tree is equal to the number of rows in the matrix
+    /// divided by `stride`. See [commit_to_rows()](Self::commit_to_rows) for details.
+    ///
+    /// # Panics
+    /// Panics if `stride` is zero or is not a power of two.
+    pub fn commit_to_rows_with_stride<H>(
+        &self,
+        strategy: RowHashStrategy,
+        stride: usize,
+    ) -> MerkleTree<H>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        assert!(stride.is_power_of_two(), "row stride must be a power of two");
+        self.build_row_commitment(stride, |row| strategy.hash_row::<H, E>(row))
+    }
+
+    /// Returns a commitment to this matrix in which each row of the matrix is packed into a leaf
+    /// of the Merkle tree using the specified constraint leaf encoding.
+    ///
+    /// See [commit_to_rows()](Self::commit_to_rows) for details.
+    pub fn commit_to_encoded_rows<H>(&self, encoding: ConstraintLeafEncoding) -> MerkleTree<H>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.build_row_commitment(1, |row| encoding.hash_leaf::<H, E>(row))
+    }
+
+    /// Builds a Merkle tree whose leaves are digests of every `stride`-th row of this matrix
+    /// computed using the provided hash function.
+    fn build_row_commitment<H, F>(&self, stride: usize, hash_row: F) -> MerkleTree<H>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        F: Fn(&[E]) -> H::Digest + Sync,
     {
         // allocate vector to store row hashes
-        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
+        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows() / stride) };
 
         // iterate though matrix rows, hashing each row
         batch_iter_mut!(
@@ -192,7 +348,7 @@ impl<E: FieldElement> RowMatrix<E> {
             128, // min batch size
             |batch: &mut [H::Digest], batch_offset: usize| {
                 for (i, row_hash) in batch.iter_mut().enumerate() {
-                    *row_hash = H::hash_elements(self.row(batch_offset + i));
+                    *row_hash = hash_row(self.row((batch_offset + i) * stride));
                 }
             }
         );
@@ -202,6 +358,111 @@ impl<E: FieldElement> RowMatrix<E> {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<E: FieldElement> Drop for RowMatrix<E> {
+    /// Zeroizes values of this matrix.
+    fn drop(&mut self) {
+        super::zeroize_values(&mut self.data);
+    }
+}
+
+// ROW ITERATOR
+// ================================================================================================
+
+/// Iterator over the rows of a [RowMatrix].
+pub struct RowIter<'a, E: FieldElement> {
+    matrix: &'a RowMatrix<E>,
+    cursor: usize,
+}
+
+impl<'a, E: FieldElement> RowIter<'a, E> {
+    pub fn new(matrix: &'a RowMatrix<E>) -> Self {
+        Self { matrix, cursor: 0 }
+    }
+}
+
+impl<'a, E: FieldElement> Iterator for RowIter<'a, E> {
+    type Item = &'a [E];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.matrix.num_rows() - self.cursor {
+            0 => None,
+            _ => {
+                // this is safe because the cursor is always smaller than the number of rows
+                let row = unsafe { self.matrix.get_row_unchecked(self.cursor) };
+                self.cursor += 1;
+                Some(row)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.matrix.num_rows() - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, E: FieldElement> ExactSizeIterator for RowIter<'a, E> {}
+
+impl<'a, E: FieldElement> FusedIterator for RowIter<'a, E> {}
+
+// FRAME ITERATOR
+// ================================================================================================
+
+/// Iterator over evaluation frames of a [RowMatrix]; see [RowMatrix::frames()] for details.
+pub struct FrameIter<'a, E: FieldElement> {
+    matrix: &'a RowMatrix<E>,
+    step: usize,
+    cursor: usize,
+}
+
+impl<'a, E: FieldElement> FrameIter<'a, E> {
+    /// # Panics
+    /// Panics if `step` is zero or is greater than or equal to the number of rows in the matrix.
+    pub fn new(matrix: &'a RowMatrix<E>, step: usize) -> Self {
+        assert!(step > 0, "frame step must be greater than zero");
+        assert!(
+            step < matrix.num_rows(),
+            "frame step must be smaller than {}, but was {}",
+            matrix.num_rows(),
+            step
+        );
+        Self {
+            matrix,
+            step,
+            cursor: 0,
+        }
+    }
+}
+
+impl<'a, E: FieldElement> Iterator for FrameIter<'a, E> {
+    type Item = (&'a [E], &'a [E]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let num_rows = self.matrix.num_rows();
+        match num_rows - self.cursor {
+            0 => None,
+            _ => {
+                // this is safe because both indexes are always smaller than the number of rows
+                let next_idx = (self.cursor + self.step) % num_rows;
+                let current = unsafe { self.matrix.get_row_unchecked(self.cursor) };
+                let next = unsafe { self.matrix.get_row_unchecked(next_idx) };
+                self.cursor += 1;
+                Some((current, next))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.matrix.num_rows() - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, E: FieldElement> ExactSizeIterator for FrameIter<'a, E> {}
+
+impl<'a, E: FieldElement> FusedIterator for FrameIter<'a, E> {}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -163,8 +163,8 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
     }
 
     /// Returns the underlying vector of arrays for this segment.
-    pub fn into_data(self) -> Vec<[B; N]> {
-        self.data
+    pub fn into_data(mut self) -> Vec<[B; N]> {
+        core::mem::take(&mut self.data)
     }
 
     // HELPER METHODS
@@ -178,10 +178,10 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         poly_offset: usize,
         offsets: &[B],
     ) {
-        for row_idx in 0..dest.len() {
-            for i in 0..N {
+        for (row_idx, row) in dest.iter_mut().enumerate() {
+            for (i, value) in row.iter_mut().enumerate() {
                 let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+                *value = coeff * offsets[row_idx];
             }
         }
     }
@@ -198,10 +198,10 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         offsets: &[B],
     ) {
         debug_assert!(num_polys < N);
-        for row_idx in 0..dest.len() {
-            for i in 0..num_polys {
+        for (row_idx, row) in dest.iter_mut().enumerate() {
+            for (i, value) in row.iter_mut().take(num_polys).enumerate() {
                 let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+                *value = coeff * offsets[row_idx];
             }
         }
     }
@@ -215,6 +215,14 @@ impl<B: StarkField, const N: usize> Deref for Segment<B, N> {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl<B: StarkField, const N: usize> Drop for Segment<B, N> {
+    /// Zeroizes values of this segment.
+    fn drop(&mut self) {
+        super::zeroize_values(&mut self.data);
+    }
+}
+
 // CONCURRENT FFT IMPLEMENTATION
 // ================================================================================================
 
@@ -233,7 +241,7 @@ mod concurrent {
         // generator of the domain should be in the middle of twiddles
         let n = data.len();
         let g = twiddles[twiddles.len() / 2];
-        debug_assert_eq!(g.exp((n as u32).into()), B::ONE);
+        debug_assert_eq!(g.exp((n as u64).into()), B::ONE);
 
         let inner_len = 1_usize << (n.ilog2() / 2);
         let outer_len = n / inner_len;
@@ -255,7 +263,7 @@ mod concurrent {
         data.par_chunks_mut(outer_len).enumerate().for_each(|(i, row)| {
             if i > 0 {
                 let i = permute_index(inner_len, i);
-                let inner_twiddle = g.exp_vartime((i as u32).into());
+                let inner_twiddle = g.exp_vartime((i as u64).into());
                 let mut outer_twiddle = inner_twiddle;
                 for element in row.iter_mut().skip(1) {
                     for col_idx in 0..N {
@@ -4,12 +4,17 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    math::{fields::f64::BaseElement, get_power_series, polynom, StarkField},
-    ColMatrix, RowMatrix,
+    math::{
+        fields::{f64::BaseElement, QuadExtension},
+        get_power_series, polynom, FieldElement, StarkField,
+    },
+    ColMatrix, ColMatrixBuilder, MatrixError, RowMatrix,
 };
 use rand_utils::rand_vector;
 use utils::collections::Vec;
 
+type QuadElement = QuadExtension<BaseElement>;
+
 #[test]
 fn test_eval_poly_with_offset_matrix() {
     let n = 256;
@@ -39,6 +44,125 @@ fn test_eval_poly_with_offset_matrix() {
     }
 }
 
+#[test]
+fn test_col_matrix_from_row_fn() {
+    let num_cols = 3;
+    let num_rows = 16;
+
+    // each row is derived from the previous one, starting with all zeros
+    let matrix = ColMatrix::from_row_fn(num_cols, num_rows, |row_idx, row| {
+        for (col_idx, value) in row.iter_mut().enumerate() {
+            *value += BaseElement::from((row_idx * num_cols + col_idx) as u64);
+        }
+    });
+
+    let mut expected = vec![vec![BaseElement::ZERO; num_rows]; num_cols];
+    for (col_idx, column) in expected.iter_mut().enumerate() {
+        let mut value = BaseElement::ZERO;
+        for (row_idx, cell) in column.iter_mut().enumerate() {
+            value += BaseElement::from((row_idx * num_cols + col_idx) as u64);
+            *cell = value;
+        }
+    }
+    assert_eq!(expected, matrix.into_columns());
+}
+
+#[test]
+fn test_matrix_transpose() {
+    // number of columns and rows are chosen so that neither is a multiple of the block size
+    let columns: Vec<Vec<QuadElement>> = (0..70).map(|_| rand_vector(128)).collect();
+    let col_matrix = ColMatrix::new(columns.clone());
+
+    let row_matrix = RowMatrix::from_col_matrix(&col_matrix);
+    assert_eq!(col_matrix.num_cols(), row_matrix.num_cols());
+    assert_eq!(col_matrix.num_rows(), row_matrix.num_rows());
+    for (row_idx, row) in row_matrix.rows().enumerate() {
+        assert_eq!(get_row(&columns, row_idx), row);
+    }
+
+    let result = ColMatrix::from_row_matrix(&row_matrix);
+    assert_eq!(columns, result.into_columns());
+}
+
+#[test]
+fn test_padded_row_matrix_transpose() {
+    // with batch size of 8, rows of the evaluated matrix are padded with 3 extra elements
+    let columns: Vec<Vec<BaseElement>> = (0..5).map(|_| rand_vector(64)).collect();
+    let row_matrix = RowMatrix::evaluate_polys::<8>(&ColMatrix::new(columns), 2);
+
+    let col_matrix = ColMatrix::from_row_matrix(&row_matrix);
+    assert_eq!(5, col_matrix.num_cols());
+    for (row_idx, row) in row_matrix.rows().enumerate() {
+        let mut expected = vec![BaseElement::ZERO; 5];
+        col_matrix.read_row_into(row_idx, &mut expected);
+        assert_eq!(expected, row);
+    }
+}
+
+#[test]
+fn test_row_matrix_frames() {
+    let columns: Vec<Vec<BaseElement>> = (0..3).map(|_| rand_vector(16)).collect();
+    let row_matrix = RowMatrix::from_col_matrix(&ColMatrix::new(columns.clone()));
+
+    let step = 4;
+    let frames = row_matrix.frames(step);
+    assert_eq!(16, frames.len());
+    for (row_idx, (current, next)) in frames.enumerate() {
+        assert_eq!(get_row(&columns, row_idx), current);
+        assert_eq!(get_row(&columns, (row_idx + step) % 16), next);
+        assert_eq!(row_matrix.row(row_idx), unsafe { row_matrix.get_row_unchecked(row_idx) });
+    }
+}
+
+#[test]
+fn test_col_matrix_builder() {
+    let columns: Vec<Vec<BaseElement>> = (0..4).map(|_| rand_vector(16)).collect();
+
+    // build the matrix by appending single rows and blocks of rows
+    let mut builder = ColMatrixBuilder::with_capacity(3, 16);
+    builder.append_row(&get_row(&columns[..3], 0));
+    let block: Vec<Vec<BaseElement>> = columns[..3].iter().map(|col| col[1..9].to_vec()).collect();
+    builder.append_rows(&block);
+    for row_idx in 9..16 {
+        builder.append_row(&get_row(&columns[..3], row_idx));
+    }
+    assert_eq!(16, builder.num_rows());
+
+    // append a full column and finalize the matrix
+    builder.append_column(columns[3].clone());
+    assert_eq!(4, builder.num_cols());
+    let matrix = builder.build().unwrap();
+    assert_eq!(columns, matrix.clone().into_columns());
+
+    // a matrix can be extended via a builder
+    let extra: Vec<BaseElement> = rand_vector(16);
+    let mut builder = matrix.into_builder();
+    builder.append_column(extra.clone());
+    let matrix = builder.build().unwrap();
+    assert_eq!(5, matrix.num_cols());
+    assert_eq!(extra, matrix.get_column(4));
+}
+
+#[test]
+fn test_col_matrix_builder_errors() {
+    assert_eq!(
+        Some(MatrixError::NoColumns),
+        ColMatrixBuilder::<BaseElement>::new(0).build().err()
+    );
+
+    let mut builder = ColMatrixBuilder::<BaseElement>::new(2);
+    builder.append_rows(&[rand_vector(8), rand_vector(7)]);
+    assert_eq!(Some(MatrixError::InconsistentColumnLength(1, 8, 7)), builder.build().err());
+
+    let mut builder = ColMatrixBuilder::<BaseElement>::new(2);
+    builder.append_rows(&[rand_vector(6), rand_vector(6)]);
+    assert_eq!(Some(MatrixError::InvalidNumRows(6)), builder.build().err());
+
+    let mut builder = ColMatrixBuilder::<BaseElement>::new(1);
+    builder.append_row(&[BaseElement::ONE]);
+    assert_eq!(Some(MatrixError::InvalidNumRows(1)), builder.build().err());
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -49,6 +173,6 @@ fn build_domain(size: usize) -> Vec<BaseElement> {
 }
 
 /// Returns a row of the column major matrix.
-fn get_row(columns: &[Vec<BaseElement>], row_id: usize) -> Vec<BaseElement> {
+fn get_row<E: FieldElement>(columns: &[Vec<E>], row_id: usize) -> Vec<E> {
     columns.iter().map(|col| col[row_id]).collect()
 }
@@ -0,0 +1,53 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{channel::ChannelState, Prover, StarkDomain, TraceLde, TracePolyTable};
+use crypto::Hasher;
+use math::FieldElement;
+
+// PENDING PROOF
+// ================================================================================================
+
+/// A proof for which the main trace segment has been committed to, but which has not been
+/// completed yet.
+///
+/// This is returned by [Prover::prove_phase1()] and is completed via [Prover::prove_phase2()].
+/// Between the two phases, the commitment to the main trace segment can be sent to an external
+/// party (e.g., a coordinator aggregating commitments from multiple provers), and the response of
+/// this party can be bound to the proof transcript before random elements for auxiliary trace
+/// segments are drawn.
+pub struct PendingProof<P, E>
+where
+    P: Prover + ?Sized,
+    E: FieldElement<BaseField = P::BaseField>,
+{
+    pub(crate) trace: P::Trace,
+    pub(crate) air: P::Air,
+    pub(crate) channel: ChannelState<P::RandomCoin>,
+    pub(crate) domain: StarkDomain<P::BaseField>,
+    pub(crate) trace_polys: TracePolyTable<E>,
+    pub(crate) trace_lde: P::TraceLde<E>,
+}
+
+impl<P, E> PendingProof<P, E>
+where
+    P: Prover + ?Sized,
+    E: FieldElement<BaseField = P::BaseField>,
+{
+    /// Returns the execution trace for which the proof is being generated.
+    pub fn trace(&self) -> &P::Trace {
+        &self.trace
+    }
+
+    /// Returns the AIR instance for the computation being proven.
+    pub fn air(&self) -> &P::Air {
+        &self.air
+    }
+
+    /// Returns the commitment to the LDE of the main trace segment.
+    pub fn main_trace_commitment(&self) -> <P::HashFn as Hasher>::Digest {
+        self.trace_lde.get_main_trace_commitment()
+    }
+}
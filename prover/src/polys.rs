@@ -0,0 +1,62 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{CompositionPoly, TracePolyTable};
+use math::FieldElement;
+
+// PROOF POLYNOMIALS
+// ================================================================================================
+
+/// Polynomials committed to by the prover during generation of a proof.
+///
+/// These are returned by [Prover::prove_with_polys()](crate::Prover::prove_with_polys) and can be
+/// used to compute additional openings of the committed data (e.g., for external aggregation
+/// protocols). Evaluations of these polynomials over the LDE domain are exactly the values
+/// committed to in the proof.
+#[derive(Debug, Clone)]
+pub struct ProofPolys<E: FieldElement> {
+    trace_polys: TracePolyTable<E>,
+    composition_poly: CompositionPoly<E>,
+    ood_point: E,
+}
+
+impl<E: FieldElement> ProofPolys<E> {
+    /// Returns a new set of proof polynomials.
+    pub(crate) fn new(
+        trace_polys: TracePolyTable<E>,
+        composition_poly: CompositionPoly<E>,
+        ood_point: E,
+    ) -> Self {
+        Self {
+            trace_polys,
+            composition_poly,
+            ood_point,
+        }
+    }
+
+    /// Returns polynomials of all trace segments in coefficient form.
+    pub fn trace_polys(&self) -> &TracePolyTable<E> {
+        &self.trace_polys
+    }
+
+    /// Returns columns of the constraint composition polynomial in coefficient form.
+    ///
+    /// These are the columns committed to by the prover; that is, they are not combined even if
+    /// out-of-domain constraint evaluations are sent in combined form.
+    pub fn composition_poly(&self) -> &CompositionPoly<E> {
+        &self.composition_poly
+    }
+
+    /// Returns the out-of-domain point at which the polynomials were evaluated in the proof.
+    pub fn ood_point(&self) -> E {
+        self.ood_point
+    }
+
+    /// Consumes these proof polynomials and returns trace polynomials and the constraint
+    /// composition polynomial.
+    pub fn into_parts(self) -> (TracePolyTable<E>, CompositionPoly<E>) {
+        (self.trace_polys, self.composition_poly)
+    }
+}
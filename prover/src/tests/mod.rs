@@ -3,14 +3,19 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::TraceTable;
+use crate::{
+    DefaultConstraintEvaluator, DefaultTraceLde, Prover, ProverChannel, Trace, TraceTable,
+};
 use air::{
-    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    EvaluationFrame, FieldExtension, ProofOptions, TraceInfo, TransitionConstraintDegree,
 };
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher};
 use math::{fields::f128::BaseElement, FieldElement, StarkField};
 use utils::collections::Vec;
 
+mod proving;
+
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
 
@@ -104,6 +109,133 @@ impl Air for MockAir {
     }
 }
 
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for traces built by [build_fib_trace()]: each row advances the Fibonacci sequence by two
+/// terms, and the last term of the sequence is the public input.
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+/// Prover for [FibAir] which optionally records a prover ID in generated proofs, and absorbs an
+/// extra message into the channel after committing to the main trace.
+pub struct FibProver {
+    options: ProofOptions,
+    prover_id: Option<&'static str>,
+    message: Option<<Blake3_256<BaseElement> as Hasher>::Digest>,
+}
+
+impl FibProver {
+    pub fn new(options: ProofOptions) -> Self {
+        FibProver {
+            options,
+            prover_id: None,
+            message: None,
+        }
+    }
+
+    pub fn with_prover_id(mut self, prover_id: &'static str) -> Self {
+        self.prover_id = Some(prover_id);
+        self
+    }
+
+    pub fn with_message(mut self, message: <Blake3_256<BaseElement> as Hasher>::Digest) -> Self {
+        self.message = Some(message);
+        self
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn absorb_trace_segment_messages<E>(
+        &self,
+        _trace: &Self::Trace,
+        segment_idx: usize,
+        channel: &mut ProverChannel<Self::Air, E, Self::HashFn, Self::RandomCoin>,
+    ) where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        if let (0, Some(message)) = (segment_idx, self.message) {
+            channel.absorb(message);
+        }
+    }
+
+    fn prover_id(&self) -> Option<&str> {
+        self.prover_id
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -0,0 +1,182 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, FibProver};
+use crate::{Prover, StarkProof, TraceLde};
+use air::{proof::VerificationArtifacts, FieldExtension, ProofOptions};
+use crypto::{hashers::Blake3_256, Hasher};
+use math::fields::f128::BaseElement;
+use utils::Serializable;
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// PROOF GENERATION
+// ================================================================================================
+
+#[cfg(feature = "export-polys")]
+#[test]
+fn prove_with_polys() {
+    use super::FibAir;
+    use crate::{ProverError, Trace};
+    use air::Air;
+    use math::{fields::QuadExtension, polynom, FieldElement, StarkField};
+
+    let prover = FibProver::new(build_options(false));
+    let trace = build_fib_trace(128);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let (proof, polys) = prover.prove_with_polys::<BaseElement>(trace.clone()).unwrap();
+
+    // the proof is the same as a proof generated without retaining polynomials
+    assert_eq!(proof, prover.prove(trace.clone()).unwrap());
+    let air = FibAir::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    assert_eq!(
+        air.context().num_constraint_composition_columns(),
+        polys.composition_poly().num_columns()
+    );
+
+    // trace polynomials interpolate the execution trace
+    let g = BaseElement::get_root_of_unity(trace.length().ilog2());
+    for (col_idx, poly) in polys.trace_polys().main_trace_polys().enumerate() {
+        for step in [0, 1, trace.length() - 1] {
+            let x = g.exp((step as u64).into());
+            assert_eq!(trace.get(col_idx, step), polynom::eval(poly, x));
+        }
+    }
+
+    // polynomials cannot be retained in a field different from the one specified by options
+    let result = prover.prove_with_polys::<QuadExtension<BaseElement>>(trace);
+    assert_eq!(Some(ProverError::FieldExtensionMismatch(1, 2)), result.err());
+}
+
+#[test]
+fn prove_with_trace_lde() {
+    let prover = FibProver::new(build_options(false));
+    let trace = build_fib_trace(128);
+    let (proof, trace_lde) = prover.prove_with_trace_lde::<BaseElement>(trace.clone()).unwrap();
+
+    // the proof is the same as a proof generated without retaining the trace LDE
+    assert_eq!(proof, prover.prove(trace).unwrap());
+
+    // the retained trace LDE is the one committed to in the proof
+    let num_fri_layers = proof.fri_proof.num_layers();
+    let (trace_roots, ..) = proof.commitments().clone().parse::<Blake3>(1, num_fri_layers).unwrap();
+    assert_eq!(trace_roots[0], trace_lde.get_main_trace_commitment());
+}
+
+#[test]
+fn prove_in_two_phases() {
+    let prover = FibProver::new(build_options(false));
+    let trace = build_fib_trace(128);
+
+    // without auxiliary inputs, the proof is the same as a proof generated in a single phase
+    let pending = prover.prove_phase1::<BaseElement>(trace.clone()).unwrap();
+    let proof = prover.prove_phase2(pending, &[]).unwrap();
+    assert_eq!(proof, prover.prove(trace.clone()).unwrap());
+
+    // auxiliary inputs provided between the two phases are bound to the transcript right after
+    // the main trace commitment
+    let pending = prover.prove_phase1::<BaseElement>(trace.clone()).unwrap();
+    let message = Blake3::merge(&[pending.main_trace_commitment(); 2]);
+    let proof = prover.prove_phase2(pending, &[message]).unwrap();
+    assert_ne!(proof, prover.prove(trace.clone()).unwrap());
+
+    let absorbing_prover = FibProver::new(build_options(false)).with_message(message);
+    assert_eq!(proof, absorbing_prover.prove(trace).unwrap());
+}
+
+#[test]
+fn prove_with_prover_id() {
+    const PROVER_ID: &str = "winterfell-0.7+fib";
+
+    let prover = FibProver::new(build_options(false)).with_prover_id(PROVER_ID);
+    let trace = build_fib_trace(32);
+    let proof = prover.prove(trace.clone()).unwrap();
+    assert_eq!(Some(PROVER_ID), proof.context.prover_id());
+
+    // the prover ID survives serialization
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(Some(PROVER_ID), proof.context.prover_id());
+
+    // the prover ID is bound to the transcript
+    let anonymous_proof = FibProver::new(build_options(false)).prove(trace).unwrap();
+    assert_eq!(None, anonymous_proof.context.prover_id());
+    assert_ne!(proof.commitments(), anonymous_proof.commitments());
+}
+
+// PROOF STRUCTURE
+// ================================================================================================
+
+#[test]
+fn proof_digest() {
+    let proof = FibProver::new(build_options(false)).prove(build_fib_trace(32)).unwrap();
+    let digest = proof.digest::<Blake3>();
+    assert_eq!(Blake3::hash(&proof.to_bytes()), digest);
+
+    // the digest does not change when the proof is serialized and deserialized
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(digest, proof.digest::<Blake3>());
+
+    // proofs with different content have different digests
+    let mut other_proof = proof.clone();
+    other_proof.pow_nonce += 1;
+    assert_ne!(digest, other_proof.digest::<Blake3>());
+}
+
+#[test]
+fn proof_parts_round_trip() {
+    let proof = FibProver::new(build_options(true)).prove(build_fib_trace(32)).unwrap();
+
+    let (context, commitments, trace_queries, constraint_queries, ood_frame, fri_proof, pow_nonce) =
+        proof.clone().into_parts();
+    assert_eq!(proof.pow_nonce(), pow_nonce);
+    let rebuilt = StarkProof::from_parts(
+        context,
+        commitments,
+        trace_queries,
+        constraint_queries,
+        ood_frame,
+        fri_proof,
+        pow_nonce,
+    );
+    assert_eq!(proof, rebuilt);
+}
+
+#[test]
+fn proof_verification_artifacts() {
+    let prover = FibProver::new(build_options(true));
+    let proof = prover.prove(build_fib_trace(32)).unwrap();
+    let artifacts = proof.verification_artifacts::<Blake3>().unwrap();
+
+    // artifacts contain the same commitments as the proof
+    let num_fri_layers = proof.fri_proof.num_layers();
+    let (trace_roots, constraint_root, fri_roots) =
+        proof.commitments().clone().parse::<Blake3>(1, num_fri_layers).unwrap();
+    assert_eq!(trace_roots, artifacts.trace_roots);
+    assert_eq!(None, artifacts.column_group_root);
+    assert_eq!(constraint_root, artifacts.constraint_root);
+    assert_eq!(fri_roots, artifacts.fri_roots);
+    assert_eq!(Blake3::hash(&proof.ood_frame().to_bytes()), artifacts.ood_digest);
+
+    // artifacts can be serialized independently of the proof
+    let bytes = artifacts.to_bytes();
+    assert_eq!(artifacts, VerificationArtifacts::<Blake3>::from_bytes(&bytes).unwrap());
+    assert!(VerificationArtifacts::<Blake3>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    // artifacts of a different proof differ
+    let other = prover.prove(build_fib_trace(64)).unwrap();
+    assert_ne!(artifacts, other.verification_artifacts::<Blake3>().unwrap());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 7)
+}
@@ -0,0 +1,55 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::FieldElement;
+use utils::{collections::Vec, iter_mut};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// LAGRANGE KERNEL COLUMN
+// ================================================================================================
+
+/// Returns evaluations of the Lagrange kernel `eq(x, r)` over the boolean hypercube, where `r`
+/// is specified by `rand_elements`.
+///
+/// The value in row `x` of the returned column is computed as:
+///
+/// $$
+/// eq(x, r) = \prod_{i = 0}^{n - 1} (x_i \cdot r_i + (1 - x_i) \cdot (1 - r_i))
+/// $$
+///
+/// where $x_i$ is the $i$-th least significant bit of the row index, and $n$ is the number of
+/// random elements. Thus, the column can be built as a part of an auxiliary trace segment.
+///
+/// The column is built in $n$ rounds, each of which doubles the number of computed values using
+/// a single multiplication per value. When `concurrent` feature is enabled, each round is
+/// executed in multiple threads.
+///
+/// # Panics
+/// Panics if `trace_len` is not equal to $2^n$.
+pub fn build_lagrange_kernel_column<E: FieldElement>(
+    rand_elements: &[E],
+    trace_len: usize,
+) -> Vec<E> {
+    assert!(
+        rand_elements.len() < usize::BITS as usize && trace_len == 1 << rand_elements.len(),
+        "trace length must be 2^{}, but was {}",
+        rand_elements.len(),
+        trace_len
+    );
+
+    let mut result = vec![E::ZERO; trace_len];
+    result[0] = E::ONE;
+    for (i, &r) in rand_elements.iter().enumerate() {
+        // after this round, the first 2^(i + 1) values are eq(x, r) over the first i + 1 bits
+        let (low, high) = result[..2 << i].split_at_mut(1 << i);
+        iter_mut!(low).zip(high).for_each(|(low, high)| {
+            *high = *low * r;
+            *low -= *high;
+        });
+    }
+    result
+}
@@ -3,9 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{matrix::MultiColumnIter, ColMatrix};
-use air::{Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout};
+use super::{matrix::MultiColumnIter, AuxTraceError, ColMatrix};
+use air::{Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout, TraceMetadata};
 use math::{polynom, FieldElement, StarkField};
+use utils::collections::Vec;
 
 mod trace_lde;
 pub use trace_lde::{DefaultTraceLde, TraceLde};
@@ -16,6 +17,9 @@ pub use poly_table::TracePolyTable;
 mod trace_table;
 pub use trace_table::{TraceTable, TraceTableFragment};
 
+mod lagrange;
+pub use lagrange::build_lagrange_kernel_column;
+
 #[cfg(test)]
 mod tests;
 
@@ -51,23 +55,28 @@ pub trait Trace: Sized {
     fn length(&self) -> usize;
 
     /// Returns metadata associated with this trace.
-    fn meta(&self) -> &[u8];
+    fn meta(&self) -> &TraceMetadata;
 
     /// Returns a reference to a [Matrix] describing the main segment of this trace.
     fn main_segment(&self) -> &ColMatrix<Self::BaseField>;
 
-    /// Builds and returns the next auxiliary trace segment. If there are no more segments to
-    /// build (i.e., the trace is complete), None is returned.
+    /// Builds and returns the next auxiliary trace segment.
     ///
     /// The `aux_segments` slice contains a list of auxiliary trace segments built as a result
     /// of prior invocations of this function. Thus, for example, on the first invocation,
     /// `aux_segments` will be empty; on the second invocation, it will contain a single matrix
     /// (the one built during the first invocation) etc.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This trace does not define an auxiliary segment with index `aux_segments.len()`.
+    /// * The segment could not be built from the data contained in this trace (e.g., because
+    ///   the witness is malformed).
     fn build_aux_segment<E: FieldElement<BaseField = Self::BaseField>>(
         &mut self,
         aux_segments: &[ColMatrix<E>],
         rand_elements: &[E],
-    ) -> Option<ColMatrix<E>>;
+    ) -> Result<ColMatrix<E>, AuxTraceError>;
 
     /// Reads an evaluation frame from the main trace segment at the specified row.
     fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>);
@@ -77,7 +86,7 @@ pub trait Trace: Sized {
 
     /// Returns trace info for this trace.
     fn get_info(&self) -> TraceInfo {
-        TraceInfo::new_multi_segment(self.layout().clone(), self.length(), self.meta().to_vec())
+        TraceInfo::new_multi_segment(self.layout().clone(), self.length(), self.meta().clone())
     }
 
     /// Returns the number of columns in the main segment of this trace.
@@ -160,6 +169,7 @@ pub trait Trace: Sized {
         // collect the info needed to build periodic values for a specific step
         let g = air.trace_domain_generator();
         let periodic_values_polys = air.get_periodic_column_polys();
+        let num_main_periodic_columns = air.num_main_periodic_columns();
         let mut periodic_values = vec![Self::BaseField::ZERO; periodic_values_polys.len()];
 
         // initialize buffers to hold evaluation frames and results of constraint evaluations
@@ -174,21 +184,45 @@ pub trait Trace: Sized {
             vec![Self::BaseField::ZERO; air.context().num_main_transition_constraints()];
         let mut aux_evaluations = vec![E::ZERO; air.context().num_aux_transition_constraints()];
 
-        // we check transition constraints on all steps except the last k steps, where k is the
-        // number of steps exempt from transition constraints (guaranteed to be at least 1)
-        for step in 0..self.length() - air.context().num_transition_exemptions() {
+        // get divisors of all transition constraints; a constraint is checked only at the steps
+        // at which its divisor vanishes
+        let num_main_constraints = main_evaluations.len();
+        let divisors = (0..air.context().num_transition_constraints())
+            .map(|i| air.context().get_transition_constraint_divisor(i))
+            .collect::<Vec<_>>();
+        let (main_divisors, aux_divisors) = divisors.split_at(num_main_constraints);
+
+        // unless some constraints have custom divisors, we check transition constraints on all
+        // steps except the last k steps, where k is the number of steps exempt from transition
+        // constraints (guaranteed to be at least 1)
+        let num_steps = if air.context().has_custom_transition_divisors() {
+            self.length()
+        } else {
+            self.length() - air.context().num_transition_exemptions()
+        };
+
+        for step in 0..num_steps {
             // build periodic values
             for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
                 let num_cycles = air.trace_length() / p.len();
-                let x = x.exp((num_cycles as u32).into());
+                let x = x.exp((num_cycles as u64).into());
                 *v = polynom::eval(p, x);
             }
 
             // evaluate transition constraints for the main trace segment and make sure they all
             // evaluate to zeros
             self.read_main_frame(step, &mut main_frame);
-            air.evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
-            for (i, &evaluation) in main_evaluations.iter().enumerate() {
+            air.evaluate_transition(
+                &main_frame,
+                &periodic_values[..num_main_periodic_columns],
+                &mut main_evaluations,
+            );
+            for (i, (&evaluation, divisor)) in
+                main_evaluations.iter().zip(main_divisors).enumerate()
+            {
+                if !divisor.vanishes_at(x) {
+                    continue;
+                }
                 assert!(
                     evaluation == Self::BaseField::ZERO,
                     "main transition constraint {i} did not evaluate to ZERO at step {step}"
@@ -206,7 +240,12 @@ pub trait Trace: Sized {
                     aux_rand_elements,
                     &mut aux_evaluations,
                 );
-                for (i, &evaluation) in aux_evaluations.iter().enumerate() {
+                for (i, (&evaluation, divisor)) in
+                    aux_evaluations.iter().zip(aux_divisors).enumerate()
+                {
+                    if !divisor.vanishes_at(x) {
+                        continue;
+                    }
                     assert!(
                         evaluation == E::ZERO,
                         "auxiliary transition constraint {i} did not evaluate to ZERO at step {step}"
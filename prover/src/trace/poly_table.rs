@@ -18,6 +18,7 @@ use utils::collections::Vec;
 /// Coefficients of the polynomials for the main trace segment are always in the base field.
 /// However, coefficients of the polynomials for the auxiliary trace segments may be either in the
 /// base field, or in the extension field, depending on whether extension field is being used.
+#[derive(Debug, Clone)]
 pub struct TracePolyTable<E: FieldElement> {
     main_segment_polys: ColMatrix<E::BaseField>,
     aux_segment_polys: Vec<ColMatrix<E>>,
@@ -72,12 +73,12 @@ impl<E: FieldElement> TracePolyTable<E> {
     }
 
     /// Returns an iterator over the polynomials of the main trace segment.
-    pub fn main_trace_polys(&self) -> ColumnIter<E::BaseField> {
+    pub fn main_trace_polys(&self) -> ColumnIter<'_, E::BaseField> {
         self.main_segment_polys.columns()
     }
 
     /// Returns an iterator over the polynomials of all auxiliary trace segments.
-    pub fn aux_trace_polys(&self) -> MultiColumnIter<E> {
+    pub fn aux_trace_polys(&self) -> MultiColumnIter<'_, E> {
         MultiColumnIter::new(self.aux_segment_polys.as_slice())
     }
 
@@ -3,8 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{tests::build_fib_trace, Trace};
-use math::fields::f128::BaseElement;
+use crate::{build_lagrange_kernel_column, tests::build_fib_trace, Trace};
+use math::{fields::f128::BaseElement, FieldElement};
 use utils::collections::Vec;
 
 #[test]
@@ -27,3 +27,76 @@ fn new_trace_table() {
         .collect();
     assert_eq!(expected, trace.get_column(1));
 }
+
+#[test]
+fn update_trace_table_window() {
+    let trace_length = 2048;
+    let mut trace = build_fib_trace(trace_length * 2);
+    let original = trace.clone();
+
+    // the window spans multiple chunks which may be updated in parallel
+    let rows = 5..2000;
+    trace.update_window(rows.clone(), |step, state| {
+        state[1] = state[0] + BaseElement::from(step as u32);
+    });
+    for step in 0..trace_length {
+        assert_eq!(original.get(0, step), trace.get(0, step));
+        let expected = if rows.contains(&step) {
+            original.get(0, step) + BaseElement::from(step as u32)
+        } else {
+            original.get(1, step)
+        };
+        assert_eq!(expected, trace.get(1, step));
+    }
+
+    // empty windows do not change the trace
+    let snapshot = trace.clone();
+    trace.update_window(7..7, |_, state| state[0] = BaseElement::ZERO);
+    assert_eq!(snapshot.get_column(0), trace.get_column(0));
+}
+
+#[test]
+fn set_trace_table_column_range() {
+    let mut trace = build_fib_trace(16);
+    let values = [BaseElement::from(7u8), BaseElement::from(9u8)];
+    trace.set_column_range(1, 6, &values);
+    assert_eq!(&values, &trace.get_column(1)[6..]);
+    assert_eq!(BaseElement::from(144u8), trace.get(1, 5));
+}
+
+#[test]
+#[should_panic(expected = "rows 7..9 are out of bounds for execution trace of length 8")]
+fn set_trace_table_column_range_out_of_bounds() {
+    let mut trace = build_fib_trace(16);
+    trace.set_column_range(0, 7, &[BaseElement::ONE; 2]);
+}
+
+#[test]
+fn lagrange_kernel_column() {
+    let rand_elements: Vec<BaseElement> = rand_utils::rand_vector(5);
+    let column = build_lagrange_kernel_column(&rand_elements, 32);
+
+    // compare against a direct evaluation of eq(x, r) in every row
+    for (row_idx, &value) in column.iter().enumerate() {
+        let mut expected = BaseElement::ONE;
+        for (bit_idx, &r) in rand_elements.iter().enumerate() {
+            if row_idx & (1 << bit_idx) == 0 {
+                expected *= BaseElement::ONE - r;
+            } else {
+                expected *= r;
+            }
+        }
+        assert_eq!(expected, value);
+    }
+
+    // evaluations of the Lagrange kernel over the hypercube sum up to one
+    assert_eq!(BaseElement::ONE, column.iter().fold(BaseElement::ZERO, |acc, &v| acc + v));
+    assert_eq!(vec![BaseElement::ONE], build_lagrange_kernel_column::<BaseElement>(&[], 1));
+}
+
+#[test]
+#[should_panic(expected = "trace length must be 2^3, but was 16")]
+fn lagrange_kernel_column_invalid_length() {
+    let rand_elements: Vec<BaseElement> = rand_utils::rand_vector(3);
+    build_lagrange_kernel_column(&rand_elements, 16);
+}
@@ -8,7 +8,13 @@ use super::{
     TraceInfo, TraceLayout, TraceLde, TracePolyTable, Vec,
 };
 use crate::{RowMatrix, DEFAULT_SEGMENT_WIDTH};
+use air::ColumnGroup;
+use core::marker::PhantomData;
 use crypto::MerkleTree;
+use math::{
+    fft::{CpuFftBackend, FftBackend},
+    StarkField,
+};
 
 #[cfg(feature = "std")]
 use log::debug;
@@ -28,7 +34,18 @@ mod tests;
 ///   will always be elements in the base field (even when an extension field is used).
 /// - Auxiliary segments: a list of 0 or more segments for traces generated after the prover
 ///   commits to the first trace segment. Currently, at most 1 auxiliary segment is possible.
-pub struct DefaultTraceLde<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+///
+/// Trace segments are interpolated and extended using the [FftBackend] specified by the `Fft`
+/// type parameter; by default, FFTs are performed on the CPU.
+///
+/// Auxiliary trace segments are committed to using the hash function specified by the `AH` type
+/// parameter; by default, this is the same hash function as the one used for the main segment.
+pub struct DefaultTraceLde<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    Fft: FftBackend = CpuFftBackend,
+    AH: ElementHasher<BaseField = E::BaseField> + Hasher<Digest = H::Digest> = H,
+> {
     // low-degree extension of the main segment of the trace
     main_segment_lde: RowMatrix<E::BaseField>,
     // commitment to the main segment of the trace
@@ -36,13 +53,38 @@ pub struct DefaultTraceLde<E: FieldElement, H: ElementHasher<BaseField = E::Base
     // low-degree extensions of the auxiliary segments of the trace
     aux_segment_ldes: Vec<RowMatrix<E>>,
     // commitment to the auxiliary segments of the trace
-    aux_segment_trees: Vec<MerkleTree<H>>,
+    aux_segment_trees: Vec<MerkleTree<AH>>,
+    // low-degree extension of the column group and the commitment to it; when present, columns
+    // of the group are excluded from the main segment LDE
+    column_group: Option<ColumnGroupLde<E::BaseField, H>>,
     blowup: usize,
+    // distance between rows of segment LDEs which belong to the LDE domain; this is greater than
+    // 1 only when the trace is extended over a constraint evaluation domain larger than the LDE
+    // domain
+    lde_stride: usize,
     trace_info: TraceInfo,
+    _fft_backend: PhantomData<Fft>,
+}
+
+/// Low-degree extension of a column group over the column group domain.
+struct ColumnGroupLde<B: StarkField, H: ElementHasher<BaseField = B>> {
+    lde: RowMatrix<B>,
+    tree: MerkleTree<H>,
+    // for each column of the main trace segment, specifies whether the column is in the group
+    group_flags: Vec<bool>,
+    blowup: usize,
+    // log2 of the ratio between the LDE domain size and the column group domain size
+    step_shift: u32,
 }
 
 #[cfg(test)]
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> DefaultTraceLde<E, H> {
+impl<E, H, Fft, AH> DefaultTraceLde<E, H, Fft, AH>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    Fft: FftBackend,
+    AH: ElementHasher<BaseField = E::BaseField> + Hasher<Digest = H::Digest>,
+{
     // TEST HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -65,12 +107,16 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> DefaultTraceLd
     }
 }
 
-impl<E, H> TraceLde<E> for DefaultTraceLde<E, H>
+impl<E, H, Fft, AH> TraceLde<E> for DefaultTraceLde<E, H, Fft, AH>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    Fft: FftBackend,
+    AH: ElementHasher<BaseField = E::BaseField> + Hasher<Digest = H::Digest>,
 {
     type HashFn = H;
+    type AuxHashFn = AH;
+    type FftBackend = Fft;
 
     /// Takes the main trace segment columns as input, interpolates them into polynomials in
     /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
@@ -84,9 +130,36 @@ where
         main_trace: &ColMatrix<E::BaseField>,
         domain: &StarkDomain<E::BaseField>,
     ) -> (TracePolyTable<E>, Self) {
-        // extend the main execution trace and build a Merkle tree from the extended trace
-        let (main_segment_lde, main_segment_tree, main_segment_polys) =
-            build_trace_commitment::<E, E::BaseField, H>(main_trace, domain);
+        // extend the main execution trace and build a Merkle tree from the extended trace; if
+        // the computation defines a column group, columns of the group are extended over the
+        // column group domain and are committed to in a separate Merkle tree
+        let (main_segment_lde, main_segment_tree, main_segment_polys, column_group) = match domain
+            .column_group()
+        {
+            None => {
+                let (lde, tree, polys) =
+                    build_trace_commitment::<E, E::BaseField, H, Fft>(main_trace, domain);
+                (lde, tree, polys, None)
+            }
+            Some(group) => {
+                let polys = main_trace.interpolate_columns_with::<Fft>();
+                let (group_polys, other_polys) = split_columns(&polys, group);
+                let (lde, tree) =
+                    build_lde_commitment::<E, E::BaseField, H, Fft>(&other_polys, domain);
+
+                let group_domain = domain.column_group_domain().expect("no column group domain");
+                let (group_lde, group_tree) =
+                    build_lde_commitment::<E, E::BaseField, H, Fft>(&group_polys, &group_domain);
+                let column_group = ColumnGroupLde {
+                    lde: group_lde,
+                    tree: group_tree,
+                    group_flags: (0..polys.num_cols()).map(|i| group.contains(i)).collect(),
+                    blowup: group.blowup_factor(),
+                    step_shift: (domain.trace_to_lde_blowup() / group.blowup_factor()).ilog2(),
+                };
+                (lde, tree, polys, Some(column_group))
+            }
+        };
 
         let trace_poly_table = TracePolyTable::new(main_segment_polys);
         let trace_lde = DefaultTraceLde {
@@ -94,8 +167,11 @@ where
             main_segment_tree,
             aux_segment_ldes: Vec::new(),
             aux_segment_trees: Vec::new(),
-            blowup: domain.trace_to_lde_blowup(),
+            column_group,
+            blowup: domain.trace_lde_domain_size() / domain.trace_length(),
+            lde_stride: domain.trace_lde_stride(),
             trace_info: trace_info.clone(),
+            _fft_backend: PhantomData,
         };
 
         (trace_poly_table, trace_lde)
@@ -126,7 +202,7 @@ where
     ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest) {
         // extend the auxiliary trace segment and build a Merkle tree from the extended trace
         let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
-            build_trace_commitment::<E, E, H>(aux_trace, domain);
+            build_trace_commitment::<E, E, AH, Fft>(aux_trace, domain);
 
         // check errors
         assert!(
@@ -157,8 +233,31 @@ where
         let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
 
         // copy main trace segment values into the frame
-        frame.current_mut().copy_from_slice(self.main_segment_lde.row(lde_step));
-        frame.next_mut().copy_from_slice(self.main_segment_lde.row(next_lde_step));
+        match &self.column_group {
+            None => {
+                frame.current_mut().copy_from_slice(self.main_segment_lde.row(lde_step));
+                frame.next_mut().copy_from_slice(self.main_segment_lde.row(next_lde_step));
+            }
+            Some(group) => {
+                // the column group domain is a subset of the LDE domain, and thus, the LDE step
+                // maps to a step of the column group domain as long as the step is a step of the
+                // constraint evaluation domain
+                let group_step = lde_step >> group.step_shift;
+                let next_group_step = (group_step + group.blowup) % group.lde.num_rows();
+                merge_rows(
+                    self.main_segment_lde.row(lde_step),
+                    group.lde.row(group_step),
+                    &group.group_flags,
+                    frame.current_mut(),
+                );
+                merge_rows(
+                    self.main_segment_lde.row(next_lde_step),
+                    group.lde.row(next_group_step),
+                    &group.group_flags,
+                    frame.next_mut(),
+                );
+            }
+        }
     }
 
     /// Reads current and next rows from the auxiliary trace segment into the specified frame.
@@ -184,12 +283,18 @@ where
             &self.main_segment_lde,
             &self.main_segment_tree,
             positions,
+            self.lde_stride,
         )];
 
         // build queries for auxiliary trace segments
         for (i, segment_tree) in self.aux_segment_trees.iter().enumerate() {
             let segment_lde = &self.aux_segment_ldes[i];
-            result.push(build_segment_queries(segment_lde, segment_tree, positions));
+            result.push(build_segment_queries(
+                segment_lde,
+                segment_tree,
+                positions,
+                self.lde_stride,
+            ));
         }
 
         result
@@ -209,6 +314,21 @@ where
     fn trace_layout(&self) -> &TraceLayout {
         self.trace_info.layout()
     }
+
+    /// Returns the commitment to the low-degree extension of the column group, or None if the
+    /// computation does not define a column group.
+    fn get_column_group_commitment(&self) -> Option<<Self::HashFn as Hasher>::Digest> {
+        self.column_group.as_ref().map(|group| *group.tree.root())
+    }
+
+    /// Returns rows of the extended column group at the specified positions of the column group
+    /// domain along with Merkle authentication paths, or None if the computation does not define
+    /// a column group.
+    fn query_column_group(&self, positions: &[usize]) -> Option<Queries> {
+        self.column_group
+            .as_ref()
+            .map(|group| build_segment_queries(&group.lde, &group.tree, positions, 1))
+    }
 }
 
 // HELPER FUNCTIONS
@@ -221,9 +341,13 @@ where
 /// polynomial of degree = trace_length - 1, and then evaluating the polynomial over the LDE
 /// domain.
 ///
-/// The trace commitment is computed by hashing each row of the extended execution trace, then
-/// building a Merkle tree from the resulting hashes.
-fn build_trace_commitment<E, F, H>(
+/// The trace commitment is computed by hashing each row of the extended execution trace (using
+/// the row hash strategy of the domain), then building a Merkle tree from the resulting hashes.
+///
+/// If the constraint evaluation domain is larger than the LDE domain, the trace is extended over
+/// the constraint evaluation domain, and only the rows which belong to the LDE domain are
+/// committed to.
+fn build_trace_commitment<E, F, H, Fft>(
     trace: &ColMatrix<F>,
     domain: &StarkDomain<E::BaseField>,
 ) -> (RowMatrix<F>, MerkleTree<H>, ColMatrix<F>)
@@ -231,26 +355,48 @@ where
     E: FieldElement,
     F: FieldElement<BaseField = E::BaseField>,
     H: ElementHasher<BaseField = E::BaseField>,
+    Fft: FftBackend,
+{
+    let trace_polys = trace.interpolate_columns_with::<Fft>();
+    let (trace_lde, trace_tree) = build_lde_commitment::<E, F, H, Fft>(&trace_polys, domain);
+    (trace_lde, trace_tree, trace_polys)
+}
+
+/// Evaluates the provided trace polynomials over the specified domain and builds a commitment to
+/// the evaluations.
+fn build_lde_commitment<E, F, H, Fft>(
+    trace_polys: &ColMatrix<F>,
+    domain: &StarkDomain<E::BaseField>,
+) -> (RowMatrix<F>, MerkleTree<H>)
+where
+    E: FieldElement,
+    F: FieldElement<BaseField = E::BaseField>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    Fft: FftBackend,
 {
     // extend the execution trace
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let trace_polys = trace.interpolate_columns();
-    let trace_lde = RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&trace_polys, domain);
+    let trace_lde_domain = domain.trace_lde_domain();
+    let trace_lde = RowMatrix::evaluate_polys_over_with::<DEFAULT_SEGMENT_WIDTH, Fft>(
+        trace_polys,
+        trace_lde_domain.as_ref().unwrap_or(domain),
+    );
     #[cfg(feature = "std")]
     debug!(
         "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) in {} ms",
         trace_lde.num_cols(),
         trace_polys.num_rows().ilog2(),
         trace_lde.num_rows().ilog2(),
-        domain.trace_to_lde_blowup(),
+        trace_lde.num_rows() / trace_polys.num_rows(),
         now.elapsed().as_millis()
     );
 
     // build trace commitment
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let trace_tree = trace_lde.commit_to_rows();
+    let trace_tree =
+        trace_lde.commit_to_rows_with_stride(domain.row_hash_strategy(), domain.trace_lde_stride());
     #[cfg(feature = "std")]
     debug!(
         "Computed execution trace commitment (Merkle tree of depth {}) in {} ms",
@@ -258,22 +404,54 @@ where
         now.elapsed().as_millis()
     );
 
-    (trace_lde, trace_tree, trace_polys)
+    (trace_lde, trace_tree)
+}
+
+/// Splits the provided columns into the columns of the specified group and the remaining columns.
+fn split_columns<F: FieldElement>(
+    columns: &ColMatrix<F>,
+    group: &ColumnGroup,
+) -> (ColMatrix<F>, ColMatrix<F>) {
+    let mut group_columns = Vec::with_capacity(group.num_columns());
+    let mut other_columns = Vec::with_capacity(columns.num_cols() - group.num_columns());
+    for (i, column) in columns.columns().enumerate() {
+        if group.contains(i) {
+            group_columns.push(column.to_vec());
+        } else {
+            other_columns.push(column.to_vec());
+        }
+    }
+    (ColMatrix::new(group_columns), ColMatrix::new(other_columns))
+}
+
+/// Merges a row of the main segment LDE (without column group columns) and a row of the column
+/// group LDE into a single row of the main trace segment.
+fn merge_rows<B: Copy>(other_row: &[B], group_row: &[B], group_flags: &[bool], result: &mut [B]) {
+    let mut other_values = other_row.iter();
+    let mut group_values = group_row.iter();
+    for (value, &in_group) in result.iter_mut().zip(group_flags) {
+        let source = if in_group { &mut group_values } else { &mut other_values };
+        *value = *source.next().expect("not enough values in the row");
+    }
 }
 
 fn build_segment_queries<E, H>(
     segment_lde: &RowMatrix<E>,
     segment_tree: &MerkleTree<H>,
     positions: &[usize],
+    lde_stride: usize,
 ) -> Queries
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
 {
     // for each position, get the corresponding row from the trace segment LDE and put all these
-    // rows into a single vector
-    let trace_states =
-        positions.iter().map(|&pos| segment_lde.row(pos).to_vec()).collect::<Vec<_>>();
+    // rows into a single vector; positions are in the LDE domain, and thus, are mapped to rows of
+    // the segment LDE using the stride
+    let trace_states = positions
+        .iter()
+        .map(|&pos| segment_lde.row(pos * lde_stride).to_vec())
+        .collect::<Vec<_>>();
 
     // build Merkle authentication paths to the leaves specified by positions
     let trace_proof = segment_tree
@@ -9,8 +9,9 @@ use crate::{
 };
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
-    fields::f128::BaseElement, get_power_series, get_power_series_with_offset, polynom,
-    FieldElement, StarkField,
+    fft::{self, FftBackend},
+    fields::f128::BaseElement,
+    get_power_series, get_power_series_with_offset, polynom, FieldElement, StarkField,
 };
 use utils::collections::Vec;
 
@@ -98,6 +99,34 @@ fn commit_trace_table() {
     assert_eq!(*expected_tree.root(), trace_lde.get_main_trace_commitment())
 }
 
+#[test]
+fn extend_trace_table_with_batched_fft_backend() {
+    // build the trace and the domain
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+
+    // extend the trace using the CPU backend and a backend with batched polynomial evaluation
+    let (expected_polys, expected_lde) = DefaultTraceLde::<BaseElement, Blake3>::new(
+        &trace.get_info(),
+        trace.main_segment(),
+        &domain,
+    );
+    let (trace_polys, trace_lde) = DefaultTraceLde::<BaseElement, Blake3, BatchedFft>::new(
+        &trace.get_info(),
+        trace.main_segment(),
+        &domain,
+    );
+
+    // both backends must produce the same trace polynomials, LDE, and commitment
+    assert_eq!(expected_polys.get_main_trace_poly(0), trace_polys.get_main_trace_poly(0));
+    assert_eq!(expected_polys.get_main_trace_poly(1), trace_polys.get_main_trace_poly(1));
+    assert_eq!(expected_lde.get_main_segment_column(0), trace_lde.get_main_segment_column(0));
+    assert_eq!(expected_lde.get_main_segment_column(1), trace_lde.get_main_segment_column(1));
+    assert_eq!(expected_lde.get_main_trace_commitment(), trace_lde.get_main_trace_commitment());
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -105,3 +134,27 @@ fn build_lde_domain<B: StarkField>(domain_size: usize) -> Vec<B> {
     let g = B::get_root_of_unity(domain_size.ilog2());
     get_power_series_with_offset(g, B::GENERATOR, domain_size)
 }
+
+/// An FFT backend which evaluates polynomials in batches, one polynomial at a time.
+struct BatchedFft;
+
+impl FftBackend for BatchedFft {
+    fn evaluate_polys_with_offset<B, E>(
+        polys: &[&[E]],
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) -> Option<Vec<Vec<E>>>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+    {
+        let result = polys
+            .iter()
+            .map(|poly| {
+                fft::evaluate_poly_with_offset(poly, twiddles, domain_offset, blowup_factor)
+            })
+            .collect();
+        Some(result)
+    }
+}
@@ -7,6 +7,7 @@ use super::{ColMatrix, EvaluationFrame, FieldElement, TracePolyTable};
 use crate::StarkDomain;
 use air::{proof::Queries, TraceInfo, TraceLayout};
 use crypto::{ElementHasher, Hasher};
+use math::fft::FftBackend;
 use utils::collections::Vec;
 
 mod default;
@@ -21,10 +22,30 @@ pub use default::DefaultTraceLde;
 ///   will always be elements in the base field (even when an extension field is used).
 /// - Auxiliary segments: a list of 0 or more segments for traces generated after the prover
 ///   commits to the first trace segment. Currently, at most 1 auxiliary segment is possible.
+///
+/// Rows of all trace segments must be hashed into leaves of commitment Merkle trees using the
+/// row hash strategy of the domain (see [StarkDomain::row_hash_strategy()]); otherwise, the
+/// verifier will not be able to authenticate queried trace states.
+///
+/// Trace segments are extended over the trace LDE domain (see
+/// [StarkDomain::trace_lde_domain_size()]), so that trace frames can be read at every step of the
+/// constraint evaluation domain. When the constraint evaluation domain is larger than the LDE
+/// domain, commitments and queries must cover only the rows of the extended trace which belong to
+/// the LDE domain (see [StarkDomain::trace_lde_stride()]).
 pub trait TraceLde<E: FieldElement>: Sync {
     /// The hash function used for building the Merkle tree commitments to trace segment LDEs.
     type HashFn: ElementHasher<BaseField = E::BaseField>;
 
+    /// The hash function used for building the Merkle tree commitments to auxiliary trace
+    /// segment LDEs. This hash function must produce digests of the same type as
+    /// [HashFn](TraceLde::HashFn), and is usually the same hash function.
+    type AuxHashFn: ElementHasher<BaseField = E::BaseField>
+        + Hasher<Digest = <Self::HashFn as Hasher>::Digest>;
+
+    /// The FFT backend used for interpolating and extending trace segments. The prover also uses
+    /// this backend for extending the composition polynomial and for building FRI layers.
+    type FftBackend: FftBackend;
+
     /// Takes the main trace segment columns as input, interpolates them into polynomials in
     /// coefficient form, and evaluates the polynomials over the LDE domain.
     ///
@@ -41,7 +62,7 @@ pub trait TraceLde<E: FieldElement>: Sync {
 
     /// Takes auxiliary trace segment columns as input, interpolates them into polynomials in
     /// coefficient form, evaluates the polynomials over the LDE domain, and commits to the
-    /// polynomial evaluations.
+    /// polynomial evaluations using [AuxHashFn](TraceLde::AuxHashFn).
     ///
     /// Returns a tuple containing the column polynomials in coefficient form and the commitment
     /// to the polynomial evaluations over the LDE domain.
@@ -58,6 +79,8 @@ pub trait TraceLde<E: FieldElement>: Sync {
     ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest);
 
     /// Reads current and next rows from the main trace segment into the specified frame.
+    ///
+    /// `lde_step` is a step in the trace LDE domain.
     fn read_main_trace_frame_into(
         &self,
         lde_step: usize,
@@ -69,6 +92,9 @@ pub trait TraceLde<E: FieldElement>: Sync {
 
     /// Returns trace table rows at the specified positions along with Merkle authentication paths
     /// from the commitment root to these rows.
+    ///
+    /// Positions are positions in the LDE domain. If the computation defines a column group, rows of the main trace segment do not include
+    /// columns of the column group.
     fn query(&self, positions: &[usize]) -> Vec<Queries>;
 
     /// Returns the number of rows in the execution trace.
@@ -79,4 +105,28 @@ pub trait TraceLde<E: FieldElement>: Sync {
 
     /// Returns the trace layout of the execution trace.
     fn trace_layout(&self) -> &TraceLayout;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the commitment to the low-degree extension of the column group, or None if the
+    /// computation does not define a column group.
+    ///
+    /// Columns of the column group are extended over the column group domain (see
+    /// [StarkDomain::column_group_domain()]) and are committed to separately from the rest of the
+    /// main trace segment.
+    ///
+    /// The default implementation does not support column groups, and always returns None.
+    fn get_column_group_commitment(&self) -> Option<<Self::HashFn as Hasher>::Digest> {
+        None
+    }
+
+    /// Returns rows of the extended column group at the specified positions of the column group
+    /// domain along with Merkle authentication paths from the column group commitment root to
+    /// these rows, or None if the computation does not define a column group.
+    ///
+    /// The default implementation does not support column groups, and always returns None.
+    fn query_column_group(&self, _positions: &[usize]) -> Option<Queries> {
+        None
+    }
 }
@@ -3,10 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ColMatrix, Trace};
-use air::{EvaluationFrame, TraceInfo, TraceLayout};
+use super::{AuxTraceError, ColMatrix, Trace};
+use air::{EvaluationFrame, TraceInfo, TraceLayout, TraceMetadata};
+use core::ops::Range;
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::{collections::Vec, iter_mut};
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
@@ -19,6 +20,9 @@ use utils::{iterators::*, rayon};
 
 const MIN_FRAGMENT_LENGTH: usize = 2;
 
+/// Number of rows of a window updated as a single unit of work by [TraceTable::update_window()].
+const WINDOW_CHUNK_LENGTH: usize = 1024;
+
 // TRACE TABLE
 // ================================================================================================
 /// A concrete implementation of the [Trace] trait.
@@ -35,8 +39,8 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// 2. The length of the columns must be some power of two.
 ///
 /// The other approach is to use [TraceTable::new()] function, which takes trace width and
-/// length as parameters. This function will allocate memory for the trace, but will not fill it
-/// with data. To fill the execution trace, you can use the [fill()](TraceTable::fill) method,
+/// length as parameters. This function will allocate memory for the trace and initialize it to
+/// all zeros. To fill the execution trace, you can use the [fill()](TraceTable::fill) method,
 /// which takes two closures as parameters:
 ///
 /// 1. The first closure is responsible for initializing the first state of the computation
@@ -46,7 +50,12 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 ///
 /// You can also use [TraceTable::with_meta()] function to create a blank execution trace.
 /// This function work just like [TraceTable::new()] function, but also takes a metadata
-/// parameter which can be an arbitrary sequence of bytes up to 64KB in size.
+/// parameter which is a typed key-value map (see [TraceMetadata]) up to 64KB in size.
+///
+/// Once a trace table has been filled, ranges of its rows can be updated via
+/// [update_window()](TraceTable::update_window), and ranges of individual columns can be
+/// overwritten via [set_column_range()](TraceTable::set_column_range). This is useful for
+/// columns which can be filled in only after the execution has completed.
 ///
 /// # Concurrent trace generation
 /// For computations which consist of many small independent computations, we can generate the
@@ -63,7 +72,7 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 pub struct TraceTable<B: StarkField> {
     layout: TraceLayout,
     trace: ColMatrix<B>,
-    meta: Vec<u8>,
+    meta: TraceMetadata,
 }
 
 impl<B: StarkField> TraceTable<B> {
@@ -72,31 +81,30 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Creates a new execution trace of the specified width and length.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
-    /// expected that the trace will be filled using one of the data mutator methods.
+    /// All cells of the trace are initialized to zeros. It is expected that the trace will be
+    /// filled using one of the data mutator methods.
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
     ///   `B`, or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
-        Self::with_meta(width, length, vec![])
+        Self::with_meta(width, length, TraceMetadata::new())
     }
 
     /// Creates a new execution trace of the specified width and length, and with the specified
     /// metadata.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
-    /// expected that the trace will be filled using one of the data mutator methods.
+    /// All cells of the trace are initialized to zeros. It is expected that the trace will be
+    /// filled using one of the data mutator methods.
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
     ///   field `B`, or is not a power of two.
-    /// * Length of `meta` is greater than 65535;
-    pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
+    pub fn with_meta(width: usize, length: usize, meta: TraceMetadata) -> Self {
         assert!(width > 0, "execution trace must consist of at least one column");
         assert!(
             width <= TraceInfo::MAX_TRACE_WIDTH,
@@ -117,14 +125,7 @@ impl<B: StarkField> TraceTable<B> {
             B::TWO_ADICITY,
             length.ilog2()
         );
-        assert!(
-            meta.len() <= TraceInfo::MAX_META_LENGTH,
-            "number of metadata bytes cannot be greater than {}, but was {}",
-            TraceInfo::MAX_META_LENGTH,
-            meta.len()
-        );
-
-        let columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+        let columns = vec![vec![B::ZERO; length]; width];
         Self {
             layout: TraceLayout::new(width, [0], [0]),
             trace: ColMatrix::new(columns),
@@ -136,7 +137,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * The `columns` vector is empty or has over 255 columns.
+    /// * The `columns` vector is empty or has over 65535 columns.
     /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all columns.
@@ -169,7 +170,7 @@ impl<B: StarkField> TraceTable<B> {
         Self {
             layout: TraceLayout::new(columns.len(), [0], [0]),
             trace: ColMatrix::new(columns),
-            meta: vec![],
+            meta: TraceMetadata::new(),
         }
     }
 
@@ -187,17 +188,8 @@ impl<B: StarkField> TraceTable<B> {
         self.trace.set(column, step, value)
     }
 
-    /// Updates metadata for this execution trace to the specified vector of bytes.
-    ///
-    /// # Panics
-    /// Panics if the length of `meta` is greater than 65535;
-    pub fn set_meta(&mut self, meta: Vec<u8>) {
-        assert!(
-            meta.len() <= TraceInfo::MAX_META_LENGTH,
-            "number of metadata bytes cannot be greater than {}, but was {}",
-            TraceInfo::MAX_META_LENGTH,
-            meta.len()
-        );
+    /// Updates metadata for this execution trace to the specified metadata.
+    pub fn set_meta(&mut self, meta: TraceMetadata) {
         self.meta = meta
     }
 
@@ -232,6 +224,80 @@ impl<B: StarkField> TraceTable<B> {
         self.trace.update_row(step, state);
     }
 
+    /// Updates all rows of the execution trace within the specified range of steps.
+    ///
+    /// The `update` closure is invoked for every row in the range; it receives two parameters:
+    /// - the step of the row in the execution trace.
+    /// - a mutable reference to the current state of the row; the contents of the state are
+    ///   copied back into the row after the closure returns.
+    ///
+    /// This is useful for filling in columns after the execution has completed (e.g., columns of
+    /// a sorted memory table) without taking ownership of the underlying column vectors. When the
+    /// `concurrent` feature is enabled, the rows are updated in multiple threads, and thus, the
+    /// order in which the closure is invoked for different rows is not defined.
+    ///
+    /// # Panics
+    /// Panics if the range of `rows` is out of bounds for this execution trace.
+    pub fn update_window<F>(&mut self, rows: Range<usize>, update: F)
+    where
+        F: Fn(usize, &mut [B]) + Send + Sync,
+    {
+        assert!(
+            rows.start <= rows.end && rows.end <= self.length(),
+            "rows {}..{} are out of bounds for execution trace of length {}",
+            rows.start,
+            rows.end,
+            self.length()
+        );
+        let width = self.main_trace_width();
+        let num_chunks = (rows.len() + WINDOW_CHUNK_LENGTH - 1) / WINDOW_CHUNK_LENGTH;
+
+        // split the window into chunks of rows which can be updated independently
+        let mut chunks = (0..num_chunks).map(|_| Vec::with_capacity(width)).collect::<Vec<_>>();
+        self.trace.columns_mut().for_each(|column| {
+            for (i, chunk) in column[rows.clone()].chunks_mut(WINDOW_CHUNK_LENGTH).enumerate() {
+                chunks[i].push(chunk);
+            }
+        });
+
+        iter_mut!(chunks).enumerate().for_each(|(i, chunk)| {
+            let offset = rows.start + i * WINDOW_CHUNK_LENGTH;
+            let mut state = vec![B::ZERO; width];
+            for row_idx in 0..chunk[0].len() {
+                for (value, column) in state.iter_mut().zip(chunk.iter()) {
+                    *value = column[row_idx];
+                }
+                update(offset + row_idx, &mut state);
+                for (column, &value) in chunk.iter_mut().zip(state.iter()) {
+                    column[row_idx] = value;
+                }
+            }
+        });
+    }
+
+    /// Sets values of the specified `column` starting at step `start` to the provided `values`.
+    ///
+    /// # Panics
+    /// Panics if `column` is out of bounds for this execution trace, or if the values do not fit
+    /// into the column starting at step `start`.
+    pub fn set_column_range(&mut self, column: usize, start: usize, values: &[B]) {
+        assert!(
+            column < self.width(),
+            "column index must be smaller than {}, but was {}",
+            self.width(),
+            column
+        );
+        let end = start.saturating_add(values.len());
+        assert!(
+            end <= self.length(),
+            "rows {}..{} are out of bounds for execution trace of length {}",
+            start,
+            end,
+            self.length()
+        );
+        self.trace.get_column_mut(column)[start..end].copy_from_slice(values);
+    }
+
     // FRAGMENTS
     // --------------------------------------------------------------------------------------------
 
@@ -244,7 +310,10 @@ impl<B: StarkField> TraceTable<B> {
     /// Panics if `fragment_length` is smaller than 2, greater than the length of the trace,
     /// or is not a power of two.
     #[cfg(not(feature = "concurrent"))]
-    pub fn fragments(&mut self, fragment_length: usize) -> vec::IntoIter<TraceTableFragment<B>> {
+    pub fn fragments(
+        &mut self,
+        fragment_length: usize,
+    ) -> vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_iter()
     }
 
@@ -266,7 +335,7 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
-    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
+    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<'_, B>> {
         assert!(
             fragment_length >= MIN_FRAGMENT_LENGTH,
             "fragment length must be at least {MIN_FRAGMENT_LENGTH}, but was {fragment_length}"
@@ -336,7 +405,7 @@ impl<B: StarkField> Trace for TraceTable<B> {
         self.trace.num_rows()
     }
 
-    fn meta(&self) -> &[u8] {
+    fn meta(&self) -> &TraceMetadata {
         &self.meta
     }
 
@@ -352,13 +421,13 @@ impl<B: StarkField> Trace for TraceTable<B> {
 
     fn build_aux_segment<E>(
         &mut self,
-        _aux_segments: &[ColMatrix<E>],
+        aux_segments: &[ColMatrix<E>],
         _rand_elements: &[E],
-    ) -> Option<ColMatrix<E>>
+    ) -> Result<ColMatrix<E>, AuxTraceError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
-        None
+        Err(AuxTraceError::UndefinedSegment(aux_segments.len()))
     }
 }
 
@@ -51,7 +51,9 @@ pub trait AsBytes {
     /// Returns a byte representation of `self`.
     ///
     /// This method is intended to re-interpret the underlying memory as a sequence of bytes, and
-    /// thus, should be zero-copy.
+    /// thus, should be zero-copy. Because of this, for multi-byte values the returned bytes are in
+    /// the native byte order of the target; [Serializable] should be used whenever a portable
+    /// encoding is required.
     fn as_bytes(&self) -> &[u8];
 }
 
@@ -81,6 +83,8 @@ impl<const N: usize> AsBytes for [[u8; N]] {
 /// This is usually faster than requesting a vector with initialized memory and is useful when we
 /// overwrite all contents of the vector immediately after memory allocation.
 ///
+/// Prefer [vector_from_fn()] in code which computes every element of the vector independently.
+///
 /// # Safety
 /// Using values from the returned vector before initializing them will lead to undefined behavior.
 #[allow(clippy::uninit_vec)]
@@ -90,6 +94,36 @@ pub unsafe fn uninit_vector<T>(length: usize) -> Vec<T> {
     vector
 }
 
+/// Returns a vector of the specified length in which the element at index `i` is set to `f(i)`.
+///
+/// This is a safe alternative to [uninit_vector()]: memory of the vector is not initialized
+/// before the elements are written into it, and thus, this is as fast as allocating an
+/// uninitialized vector and overwriting all of its elements.
+///
+/// When `concurrent` feature is enabled, the elements are computed in multiple threads.
+///
+/// # Examples
+/// ```
+/// # use winter_utils::vector_from_fn;
+/// let squares = vector_from_fn(4, |i| i * i);
+/// assert_eq!(vec![0, 1, 4, 9], squares);
+/// ```
+pub fn vector_from_fn<T, F>(length: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Send + Sync,
+{
+    let mut result = Vec::with_capacity(length);
+
+    #[cfg(not(feature = "concurrent"))]
+    result.extend((0..length).map(f));
+
+    #[cfg(feature = "concurrent")]
+    (0..length).into_par_iter().map(f).collect_into_vec(&mut result);
+
+    result
+}
+
 // GROUPING / UN-GROUPING FUNCTIONS
 // ================================================================================================
 
@@ -28,6 +28,16 @@ fn group_vector_elements() {
     }
 }
 
+#[test]
+fn vector_from_fn() {
+    let result = super::vector_from_fn(1025, |i| (i * 3) as u64);
+    let expected = (0..1025).map(|i| (i * 3) as u64).collect::<Vec<_>>();
+    assert_eq!(expected, result);
+
+    let result = super::vector_from_fn(0, |i| i);
+    assert!(result.is_empty());
+}
+
 // SLICE READER TESTS
 // ================================================================================================
 
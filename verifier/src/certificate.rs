@@ -0,0 +1,230 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    verify_query_phase, CommitmentPhase, DefaultVerifierChannel, PreparedAir, VerifierError,
+};
+use air::{
+    proof::{ProofCertificate, ProofOpenings, StarkProof},
+    Air,
+};
+use crypto::{ElementHasher, Hasher, RandomCoin};
+use math::{
+    fields::{CubeExtension, QuadExtension},
+    ExtensibleField, StarkField,
+};
+use utils::{collections::Vec, string::ToString};
+
+// CERTIFIED PROOF
+// ================================================================================================
+
+/// A proof certificate which passed eager verification, and against which proof openings can be
+/// verified.
+///
+/// A certified proof is returned from [verify_certificate()](crate::verify_certificate). At this
+/// point, commitments and out-of-domain evaluations contained in the certificate are known to be
+/// consistent, and the proof-of-work for the query seed is known to be valid. To complete
+/// verification, proof openings must be verified via [CertifiedProof::verify_openings()]; values
+/// derived from the certificate during eager verification (e.g., the out-of-domain point, DEEP
+/// composition coefficients, and FRI layer α values) are retained, and only the openings are
+/// checked against them.
+///
+/// Alternatively, protocols in which openings are not transmitted together with the certificate
+/// (e.g., openings are fetched lazily from a different trust domain) can complete verification
+/// via [CertifiedProof::verify_openings_with()], which requests the openings from a callback.
+pub struct CertifiedProof<A, H, R>
+where
+    A: Air,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+{
+    prepared: PreparedAir<A, H>,
+    certificate: ProofCertificate,
+    commitment_phase: CertifiedCommitmentPhase<A::BaseField, H, R>,
+    opening_request: OpeningRequest<H>,
+}
+
+impl<A, H, R> CertifiedProof<A, H, R>
+where
+    A: Air,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new certified proof for the specified prepared AIR and certificate, with openings
+    /// expected as specified by the `opening_request`.
+    pub(crate) fn new(
+        prepared: PreparedAir<A, H>,
+        certificate: ProofCertificate,
+        commitment_phase: CertifiedCommitmentPhase<A::BaseField, H, R>,
+        opening_request: OpeningRequest<H>,
+    ) -> Self {
+        Self {
+            prepared,
+            certificate,
+            commitment_phase,
+            opening_request,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the certificate of this proof.
+    pub fn certificate(&self) -> &ProofCertificate {
+        &self.certificate
+    }
+
+    /// Returns positions in the LDE domain at which trace and constraint evaluations must be
+    /// opened.
+    ///
+    /// The positions are derived from the certificate in the same way as during full proof
+    /// verification, and thus, contain no duplicates.
+    pub fn query_positions(&self) -> &[usize] {
+        &self.opening_request.query_positions
+    }
+
+    /// Returns positions in the column group domain at which column group evaluations must be
+    /// opened, or None if the computation does not define a column group.
+    pub fn column_group_query_positions(&self) -> Option<&[usize]> {
+        self.opening_request.column_group_query_positions.as_deref()
+    }
+
+    /// Returns the commitments of this proof together with the positions at which openings of
+    /// the committed values are expected.
+    pub fn opening_request(&self) -> &OpeningRequest<H> {
+        &self.opening_request
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies the provided `openings` against the certificate of this proof.
+    ///
+    /// The openings are checked against the commitments of the certificate at the query positions
+    /// of this proof; the certificate itself is not verified again. If the verification is successful, the proof assembled from the certificate and the
+    /// `openings` attests to the correct execution of the computation against the public inputs
+    /// provided when the certificate was verified. Verification of openings does not consume the
+    /// certified proof, and thus, different openings can be verified against the same
+    /// certificate (e.g., if the openings received first turned out to be invalid).
+    ///
+    /// # Errors
+    /// Returns an error if the `openings` are not valid against the certificate of this proof.
+    pub fn verify_openings(&self, openings: ProofOpenings) -> Result<(), VerifierError> {
+        let num_trace_segments = self.certificate.context.trace_layout().num_segments();
+        if openings.trace_queries.len() != num_trace_segments {
+            return Err(VerifierError::ProofDeserializationError(
+                "number of trace queries is inconsistent with the number of trace segments"
+                    .to_string(),
+            ));
+        }
+
+        // the channel takes commitments from the certificate, and thus, openings read from it are
+        // authenticated against the certified commitments
+        let air = self.prepared.air();
+        let proof = StarkProof::join(self.certificate.clone(), openings);
+        let positions = self.query_positions();
+        let column_group_positions = self.column_group_query_positions();
+        match &self.commitment_phase {
+            CertifiedCommitmentPhase::Base(phase) => {
+                let mut channel = DefaultVerifierChannel::new(air, proof)?;
+                verify_query_phase(air, phase, positions, column_group_positions, &mut channel)
+            }
+            CertifiedCommitmentPhase::Quadratic(phase) => {
+                let mut channel = DefaultVerifierChannel::new(air, proof)?;
+                verify_query_phase(air, phase, positions, column_group_positions, &mut channel)
+            }
+            CertifiedCommitmentPhase::Cubic(phase) => {
+                let mut channel = DefaultVerifierChannel::new(air, proof)?;
+                verify_query_phase(air, phase, positions, column_group_positions, &mut channel)
+            }
+        }
+    }
+
+    /// Completes verification of this proof with openings requested from the provided callback.
+    ///
+    /// The callback is invoked with the [OpeningRequest] of this proof, which contains the
+    /// commitments of the certificate and the positions at which they must be opened, and is
+    /// expected to return openings at these positions (e.g., fetched from a remote store, or
+    /// produced by a different party). The returned openings are then verified in the same way as
+    /// in [CertifiedProof::verify_openings()]; thus, the soundness of the verification does not
+    /// rely on the callback.
+    ///
+    /// # Errors
+    /// Returns the error returned by the callback, or an error if the openings returned by the
+    /// callback are not valid against the certificate of this proof.
+    pub fn verify_openings_with<F>(&self, fetch_openings: F) -> Result<(), VerifierError>
+    where
+        F: FnOnce(&OpeningRequest<H>) -> Result<ProofOpenings, VerifierError>,
+    {
+        let openings = fetch_openings(&self.opening_request)?;
+        self.verify_openings(openings)
+    }
+}
+
+// OPENING REQUEST
+// ================================================================================================
+
+/// Commitments of a certified proof together with the positions at which openings of the
+/// committed values are expected.
+///
+/// Openings of trace segments, the constraint composition polynomial, and the first FRI layer
+/// are expected at [query_positions](OpeningRequest::query_positions); openings of the column
+/// group (if any) are expected at
+/// [column_group_query_positions](OpeningRequest::column_group_query_positions).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningRequest<H: Hasher> {
+    /// Commitments to the extended execution trace segments (main segment first, followed by
+    /// auxiliary segments).
+    pub trace_roots: Vec<H::Digest>,
+    /// Commitment to the extended column group; this is present only for computations which
+    /// define a column group.
+    pub column_group_root: Option<H::Digest>,
+    /// Commitment to the evaluations of the constraint composition polynomial.
+    pub constraint_root: H::Digest,
+    /// Commitments to FRI layers (including the commitment to the remainder) for each FRI
+    /// instance; for computations which define a column group, commitments of the column group
+    /// FRI instance follow commitments of the main FRI instance.
+    pub fri_roots: Vec<Vec<H::Digest>>,
+    /// Positions in the LDE domain at which openings are expected; the positions contain no
+    /// duplicates.
+    pub query_positions: Vec<usize>,
+    /// Positions in the column group domain at which openings of the column group are expected,
+    /// or None if the computation does not define a column group.
+    pub column_group_query_positions: Option<Vec<usize>>,
+}
+
+// CERTIFIED COMMITMENT PHASE
+// ================================================================================================
+
+/// Results of the commitment phase of a certified proof, computed in the field specified by the
+/// field extension of proof options.
+pub(crate) enum CertifiedCommitmentPhase<B, H, R>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    H: ElementHasher<BaseField = B>,
+    R: RandomCoin<BaseField = B, Hasher = H>,
+{
+    Base(CommitmentPhase<B, DefaultVerifierChannel<B, H>, H, R>),
+    Quadratic(CommitmentPhase<QuadExtension<B>, DefaultVerifierChannel<QuadExtension<B>, H>, H, R>),
+    Cubic(CommitmentPhase<CubeExtension<B>, DefaultVerifierChannel<CubeExtension<B>, H>, H, R>),
+}
+
+impl<B, H, R> CertifiedCommitmentPhase<B, H, R>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    H: ElementHasher<BaseField = B>,
+    R: RandomCoin<BaseField = B, Hasher = H>,
+{
+    /// Returns the query proof-of-work nonce read during the commitment phase.
+    pub fn pow_nonce(&self) -> u64 {
+        match self {
+            Self::Base(phase) => phase.pow_nonce,
+            Self::Quadratic(phase) => phase.pow_nonce,
+            Self::Cubic(phase) => phase.pow_nonce,
+        }
+    }
+}
@@ -5,15 +5,185 @@
 
 use crate::VerifierError;
 use air::{
-    proof::{Queries, StarkProof, Table},
-    Air, EvaluationFrame,
+    proof::{
+        ColumnGroupProof, Commitments, OodFrame, ProofCertificate, Queries, StarkProof, Table,
+    },
+    Air, EvaluationFrame, OodConstraintBatching,
 };
-use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
-use fri::VerifierChannel as FriVerifierChannel;
+use core::mem;
+use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree, RandomCoin};
+use fri::{FriProof, VerifierChannel as FriVerifierChannel};
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, string::ToString};
+use utils::{collections::Vec, iter, string::ToString};
 
-// VERIFIER CHANNEL
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// COMMITMENT CHANNEL TRAIT
+// ================================================================================================
+
+/// Defines an interface for a channel over which a STARK verifier reads the data needed to verify
+/// the commitment phase of the protocol.
+///
+/// This includes all commitments sent by the prover, out-of-domain evaluations, and the
+/// proof-of-work nonce for the query seed, but none of the query openings. Thus, this trait can be
+/// implemented by sources of data which do not contain openings at all (e.g., a
+/// [ProofCertificate]). Every [VerifierChannel] must implement this trait as well.
+///
+/// Note: reading some of the values removes them from the channel. Implementations may panic if
+/// such values are read more than once, as the verifier never does this.
+pub trait CommitmentChannel<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns execution trace commitments sent by the prover.
+    ///
+    /// For computations requiring multiple trace segment, the returned slice must contain a
+    /// commitment for each trace segment.
+    fn read_trace_commitments(&self) -> &[H::Digest];
+
+    /// Returns constraint evaluation commitment sent by the prover.
+    fn read_constraint_commitment(&self) -> H::Digest;
+
+    /// Reads and removes from the channel trace polynomial evaluations at out-of-domain points
+    /// z and z * g, where g is the generator of the LDE domain.
+    ///
+    /// For computations requiring multiple trace segments, evaluations of auxiliary trace
+    /// polynomials must also be included.
+    fn read_ood_trace_frame(&mut self) -> TraceOodFrame<E>;
+
+    /// Reads and removes from the channel evaluations of composition polynomial columns at z^m,
+    /// where z is the out-of-domain point, and m is the number of composition polynomial columns.
+    fn read_ood_constraint_evaluations(&mut self) -> Vec<E>;
+
+    /// Reads and removes from the channel layer commitments of the next FRI instance.
+    ///
+    /// The main FRI instance reads its commitments first, followed by the column group FRI
+    /// instance (if any). For channels which also implement [FriVerifierChannel], this must
+    /// return the same commitments as `read_fri_layer_commitments()` of that trait.
+    fn read_fri_commitments(&mut self) -> Vec<H::Digest>;
+
+    /// Returns query proof-of-work nonce sent by the prover.
+    fn read_pow_nonce(&self) -> u64;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the commitment to the column group sent by the prover, or None if the prover did
+    /// not send one.
+    ///
+    /// The default implementation does not support column groups, and always returns None.
+    fn read_column_group_commitment(&self) -> Option<H::Digest> {
+        None
+    }
+
+    /// Absorbs additional protocol messages into the `public_coin` right after the commitment to
+    /// the trace segment with the specified index has been absorbed.
+    ///
+    /// This mirrors `Prover::absorb_trace_segment_messages()` of the prover crate: protocols which
+    /// extend the STARK protocol with additional messages (e.g., commitments to auxiliary proofs)
+    /// must absorb the same messages here as were absorbed by the prover. The main trace segment
+    /// has index 0, and auxiliary trace segments have indexes starting with 1.
+    ///
+    /// The default implementation does not absorb any messages.
+    ///
+    /// # Errors
+    /// Returns an error if the additional messages are not valid.
+    fn absorb_trace_segment_messages<R>(
+        &mut self,
+        _segment_idx: usize,
+        _public_coin: &mut R,
+    ) -> Result<(), VerifierError>
+    where
+        R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    {
+        Ok(())
+    }
+}
+
+// VERIFIER CHANNEL TRAIT
+// ================================================================================================
+
+/// Defines an interface for a channel over which a STARK verifier reads data sent by the prover.
+///
+/// This trait abstracts away the source of the data used during proof verification. The default
+/// implementation ([DefaultVerifierChannel]) reads all data from a [StarkProof]; alternative
+/// implementations can be used to, for example, lazily fetch query openings from a remote store,
+/// or to assemble a proof which was split across multiple messages. Any implementation of this
+/// trait can be passed to [perform_verification()](crate::perform_verification) to verify the
+/// data it provides.
+///
+/// Data of the commitment phase of the protocol is read via the [CommitmentChannel] trait, which
+/// the channel must implement for the hash function of the channel. The channel must also
+/// implement the [FriVerifierChannel] trait, over which the FRI verifier reads FRI queries, and
+/// the remainder. For computations which define a column group (see
+/// [ColumnGroup](air::ColumnGroup)), low-degree of the column group is proven via a separate FRI
+/// instance; data of this instance must be provided over the same channel right after the data of
+/// the main FRI instance (i.e., the second read of FRI layer commitments must return commitments
+/// of the column group FRI instance, and FRI layer queries, proofs, and the remainder of the
+/// column group FRI instance must follow those of the main FRI instance).
+///
+/// Note: reading some of the values removes them from the channel. Implementations may panic if
+/// such values are read more than once, as the verifier never does this.
+pub trait VerifierChannel<E: FieldElement>:
+    FriVerifierChannel<E> + CommitmentChannel<E, <Self as FriVerifierChannel<E>>::Hasher>
+{
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads and removes from the channel trace states at the specified positions of the LDE
+    /// domain.
+    ///
+    /// For computations requiring multiple trace segments, trace states for auxiliary segments
+    /// must be included as the second value of the returned tuple (trace states for all auxiliary
+    /// segments are merged into a single table). Otherwise, the second value must be None.
+    ///
+    /// # Errors
+    /// Returns an error if the trace states are not valid against the trace commitments sent by
+    /// the prover.
+    #[allow(clippy::type_complexity)]
+    fn read_queried_trace_states(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<(Table<E::BaseField>, Option<Table<E>>), VerifierError>;
+
+    /// Reads and removes from the channel constraint evaluations at the specified positions of
+    /// the LDE domain.
+    ///
+    /// # Errors
+    /// Returns an error if the constraint evaluations are not valid against the constraint
+    /// commitment sent by the prover.
+    fn read_constraint_evaluations(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<Table<E>, VerifierError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads and removes from the channel column group states at the specified positions of the
+    /// column group domain.
+    ///
+    /// The default implementation does not support column groups, and always returns an error.
+    ///
+    /// # Errors
+    /// Returns an error if the column group states are not available, or are not valid against
+    /// the column group commitment sent by the prover.
+    fn read_queried_column_group_states(
+        &mut self,
+        _positions: &[usize],
+    ) -> Result<Table<E::BaseField>, VerifierError> {
+        Err(VerifierError::ProofDeserializationError(
+            "column group queries are not available".to_string(),
+        ))
+    }
+}
+
+// DEFAULT VERIFIER CHANNEL
 // ================================================================================================
 
 /// A view into a [StarkProof] for a computation structured to simulate an "interactive" channel.
@@ -21,19 +191,32 @@ use utils::{collections::Vec, string::ToString};
 /// A channel is instantiated for a specific proof, which is parsed into structs over the
 /// appropriate field (specified by type parameter `E`). This also validates that the proof is
 /// well-formed in the context of the computation for the specified [Air].
-pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+///
+/// Auxiliary trace segment queries are authenticated using the hash function specified by the
+/// `AH` type parameter; by default, this is the same hash function as the one used for all other
+/// commitments.
+pub struct DefaultVerifierChannel<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    AH: ElementHasher<BaseField = E::BaseField> + Hasher<Digest = H::Digest> = H,
+> {
     // trace queries
     trace_roots: Vec<H::Digest>,
-    trace_queries: Option<TraceQueries<E, H>>,
+    trace_queries: Option<TraceQueries<E, H, AH>>,
     // constraint queries
     constraint_root: H::Digest,
     constraint_queries: Option<ConstraintQueries<E, H>>,
-    // FRI proof
-    fri_roots: Option<Vec<H::Digest>>,
+    // column group queries
+    column_group_root: Option<H::Digest>,
+    column_group_queries: Option<ColumnGroupQueries<E, H>>,
+    // FRI proofs; when the computation defines a column group, data of the column group FRI
+    // instance follows the data of the main FRI instance
+    fri_roots: Vec<Vec<H::Digest>>,
     fri_layer_proofs: Vec<BatchMerkleProof<H>>,
     fri_layer_queries: Vec<Vec<E>>,
-    fri_remainder: Option<Vec<E>>,
-    fri_num_partitions: usize,
+    fri_remainders: Vec<Vec<E>>,
+    fri_num_partitions: Vec<usize>,
+    fri_instance: usize,
     // out-of-domain frame
     ood_trace_frame: Option<TraceOodFrame<E>>,
     ood_constraint_evaluations: Option<Vec<E>>,
@@ -41,10 +224,20 @@ pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::Base
     pow_nonce: u64,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChannel<E, H> {
+impl<E, H, AH> DefaultVerifierChannel<E, H, AH>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    AH: ElementHasher<BaseField = E::BaseField> + Hasher<Digest = H::Digest>,
+{
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates and returns a new [VerifierChannel] initialized from the specified `proof`.
+    /// Creates and returns a new [DefaultVerifierChannel] initialized from the specified `proof`.
+    ///
+    /// # Errors
+    /// Returns an error if the `proof` is not well-formed in the context of the specified `air`,
+    /// or if the hash function used to commit to auxiliary trace segments (as recorded in the
+    /// proof context) is not `AH`.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         air: &A,
         proof: StarkProof,
@@ -57,58 +250,89 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             ood_frame,
             fri_proof,
             pow_nonce,
+            column_group,
         } = proof;
 
         // make sure AIR and proof base fields are the same
         if E::BaseField::get_modulus_le_bytes() != context.field_modulus_bytes() {
             return Err(VerifierError::InconsistentBaseField);
         }
-        let constraint_frame_width = air.context().num_constraint_composition_columns();
 
-        let num_trace_segments = air.trace_layout().num_segments();
-        let main_trace_width = air.trace_layout().main_trace_width();
-        let aux_trace_width = air.trace_layout().aux_trace_width();
+        // make sure auxiliary trace segments were committed to using the expected hash function
+        let expected_context = context.clone().with_aux_trace_hasher::<H, AH>();
+        if context.aux_trace_hasher() != expected_context.aux_trace_hasher() {
+            return Err(VerifierError::InconsistentAuxTraceHasher);
+        }
         let lde_domain_size = air.lde_domain_size();
         let fri_options = air.options().to_fri_options();
 
         // --- parse commitments ------------------------------------------------------------------
-        let (trace_roots, constraint_root, fri_roots) = commitments
-            .parse::<H>(num_trace_segments, fri_options.num_fri_layers(lde_domain_size))
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let (trace_roots, column_group_root, constraint_root, fri_roots) =
+            parse_commitments::<E, H, A>(air, commitments)?;
 
         // --- parse trace and constraint queries -------------------------------------------------
         let trace_queries = TraceQueries::new(trace_queries, air)?;
         let constraint_queries = ConstraintQueries::new(constraint_queries, air)?;
 
         // --- parse FRI proofs -------------------------------------------------------------------
-        let fri_num_partitions = fri_proof.num_partitions();
-        let fri_remainder = fri_proof
-            .parse_remainder()
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let (fri_layer_queries, fri_layer_proofs) = fri_proof
-            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        // if the commitment to the first FRI layer was skipped, the proof starts with the second
+        // FRI layer
+        let fri_domain_size = if fri_options.skip_first_layer_commitment() {
+            lde_domain_size / fri_options.folding_factor()
+        } else {
+            lde_domain_size
+        };
+        let mut fri_num_partitions = vec![fri_proof.num_partitions()];
+        let (mut fri_layer_queries, mut fri_layer_proofs, fri_remainder) =
+            parse_fri_proof::<E, H>(fri_proof, fri_domain_size, fri_options.folding_factor())?;
+        let mut fri_remainders = vec![fri_remainder];
+
+        // --- parse column group proof -----------------------------------------------------------
+        let column_group_queries = match (air.context().column_group(), column_group) {
+            (None, None) => None,
+            (Some(_), Some(ColumnGroupProof { queries, fri_proof })) => {
+                let domain_size =
+                    air.context().column_group_domain_size().expect("no column group");
+                fri_num_partitions.push(fri_proof.num_partitions());
+                let (layer_queries, layer_proofs, remainder) =
+                    parse_fri_proof::<E, H>(fri_proof, domain_size, fri_options.folding_factor())?;
+                fri_layer_queries.extend(layer_queries);
+                fri_layer_proofs.extend(layer_proofs);
+                fri_remainders.push(remainder);
+                Some(ColumnGroupQueries::new(queries, air)?)
+            }
+            (Some(_), None) => {
+                return Err(VerifierError::ProofDeserializationError(
+                    "column group proof is missing".to_string(),
+                ))
+            }
+            (None, Some(_)) => {
+                return Err(VerifierError::ProofDeserializationError(
+                    "unexpected column group proof".to_string(),
+                ))
+            }
+        };
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
-        let (ood_trace_evaluations, ood_constraint_evaluations) = ood_frame
-            .parse(main_trace_width, aux_trace_width, constraint_frame_width)
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let ood_trace_frame =
-            TraceOodFrame::new(ood_trace_evaluations, main_trace_width, aux_trace_width);
+        let (ood_trace_frame, ood_constraint_evaluations) = parse_ood_frame(air, ood_frame)?;
 
-        Ok(VerifierChannel {
+        Ok(DefaultVerifierChannel {
             // trace queries
             trace_roots,
             trace_queries: Some(trace_queries),
             // constraint queries
             constraint_root,
             constraint_queries: Some(constraint_queries),
-            // FRI proof
-            fri_roots: Some(fri_roots),
+            // column group queries
+            column_group_root,
+            column_group_queries,
+            // FRI proofs
+            fri_roots,
             fri_layer_proofs,
             fri_layer_queries,
-            fri_remainder: Some(fri_remainder),
+            fri_remainders,
             fri_num_partitions,
+            fri_instance: 0,
             // out-of-domain evaluation
             ood_trace_frame: Some(ood_trace_frame),
             ood_constraint_evaluations: Some(ood_constraint_evaluations),
@@ -117,96 +341,190 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         })
     }
 
-    // DATA READERS
+    // TRACE OPENINGS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns execution trace commitments sent by the prover.
+    /// Verifies that the provided trace `openings` at the specified `positions` of the LDE domain
+    /// are consistent with the trace commitments of the proof from which this channel was
+    /// created, and returns the opened trace states.
     ///
-    /// For computations requiring multiple trace segment, the returned slice will contain a
-    /// commitment for each trace segment.
-    pub fn read_trace_commitments(&self) -> &[H::Digest] {
+    /// This enables protocols built on top of a STARK proof (e.g., data availability sampling, or
+    /// cross-proof consistency checks) to open trace commitments at positions other than the ones
+    /// chosen via the Fiat-Shamir transform. The openings are expected to be produced by
+    /// `TraceLde::query()` on the trace LDE returned from `Prover::prove_with_trace_lde()` of the
+    /// prover crate; positions must be unique and must be in the same order as when the openings
+    /// were produced.
+    ///
+    /// This method does not verify the proof itself, and does not consume any data from the
+    /// channel.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of openings does not match the number of trace segments.
+    /// * Any of the openings cannot be parsed into trace states at the specified positions.
+    /// * Any of the trace states do not match the corresponding trace commitment.
+    #[allow(clippy::type_complexity)]
+    pub fn verify_trace_openings<A: Air<BaseField = E::BaseField>>(
+        &self,
+        air: &A,
+        positions: &[usize],
+        openings: Vec<Queries>,
+    ) -> Result<(Table<E::BaseField>, Option<Table<E>>), VerifierError> {
+        let num_segments = air.trace_layout().num_segments();
+        if openings.len() != num_segments {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected {num_segments} trace segment openings, but received {}",
+                openings.len()
+            )));
+        }
+        if positions.is_empty() {
+            return Err(VerifierError::ProofDeserializationError(
+                "no trace opening positions were provided".to_string(),
+            ));
+        }
+
+        let queries = TraceQueries::<E, H, AH>::with_num_queries(openings, air, positions.len())?;
+        verify_query_proofs(&self.trace_roots[0], positions, &queries.main_query_proof)
+            .map_err(VerifierError::MainTraceQueryDoesNotMatchCommitment)?;
+        for (i, (root, proof)) in
+            self.trace_roots[1..].iter().zip(&queries.aux_query_proofs).enumerate()
+        {
+            verify_query_proofs(root, positions, proof).map_err(|position| {
+                VerifierError::AuxTraceQueryDoesNotMatchCommitment(i, position)
+            })?;
+        }
+
+        Ok((queries.main_states, queries.aux_states))
+    }
+}
+
+// COMMITMENT CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+impl<E, H, AH> CommitmentChannel<E, H> for DefaultVerifierChannel<E, H, AH>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    AH: ElementHasher<BaseField = E::BaseField> + Hasher<Digest = H::Digest>,
+{
+    fn read_trace_commitments(&self) -> &[H::Digest] {
         &self.trace_roots
     }
 
-    /// Returns constraint evaluation commitment sent by the prover.
-    pub fn read_constraint_commitment(&self) -> H::Digest {
+    fn read_constraint_commitment(&self) -> H::Digest {
         self.constraint_root
     }
 
-    /// Returns trace polynomial evaluations at out-of-domain points z and z * g, where g is the
-    /// generator of the LDE domain.
-    ///
-    /// For computations requiring multiple trace segments, evaluations of auxiliary trace
-    /// polynomials are also included.
-    pub fn read_ood_trace_frame(&mut self) -> TraceOodFrame<E> {
+    fn read_ood_trace_frame(&mut self) -> TraceOodFrame<E> {
         self.ood_trace_frame.take().expect("already read")
     }
 
-    /// Returns evaluations of composition polynomial columns at z^m, where z is the out-of-domain
-    /// point, and m is the number of composition polynomial columns.
-    pub fn read_ood_constraint_evaluations(&mut self) -> Vec<E> {
+    fn read_ood_constraint_evaluations(&mut self) -> Vec<E> {
         self.ood_constraint_evaluations.take().expect("already read")
     }
 
-    /// Returns query proof-of-work nonce sent by the prover.
-    pub fn read_pow_nonce(&self) -> u64 {
+    fn read_fri_commitments(&mut self) -> Vec<H::Digest> {
+        self.read_fri_layer_commitments()
+    }
+
+    fn read_pow_nonce(&self) -> u64 {
         self.pow_nonce
     }
 
-    /// Returns trace states at the specified positions of the LDE domain. This also checks if
-    /// the trace states are valid against the trace commitment sent by the prover.
-    ///
-    /// For computations requiring multiple trace segments, trace states for auxiliary segments
-    /// are also included as the second value of the returned tuple (trace states for all auxiliary
-    /// segments are merged into a single table). Otherwise, the second value is None.
-    #[allow(clippy::type_complexity)]
-    pub fn read_queried_trace_states(
+    fn read_column_group_commitment(&self) -> Option<H::Digest> {
+        self.column_group_root
+    }
+}
+
+// VERIFIER CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+impl<E, H, AH> VerifierChannel<E> for DefaultVerifierChannel<E, H, AH>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    AH: ElementHasher<BaseField = E::BaseField> + Hasher<Digest = H::Digest>,
+{
+    fn read_queried_trace_states(
         &mut self,
         positions: &[usize],
     ) -> Result<(Table<E::BaseField>, Option<Table<E>>), VerifierError> {
         let queries = self.trace_queries.take().expect("already read");
 
-        // make sure the states included in the proof correspond to the trace commitment
-        for (root, proof) in self.trace_roots.iter().zip(queries.query_proofs.iter()) {
-            MerkleTree::verify_batch(root, positions, proof)
-                .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
-        }
+        // make sure the states included in the proof correspond to the trace commitment; when
+        // the `concurrent` feature is enabled, commitments to auxiliary trace segments are
+        // checked in parallel
+        verify_query_proofs(&self.trace_roots[0], positions, &queries.main_query_proof)
+            .map_err(VerifierError::MainTraceQueryDoesNotMatchCommitment)?;
+        iter!(self.trace_roots[1..])
+            .zip(iter!(queries.aux_query_proofs))
+            .enumerate()
+            .try_for_each(|(i, (root, proof))| {
+                verify_query_proofs(root, positions, proof).map_err(|position| {
+                    VerifierError::AuxTraceQueryDoesNotMatchCommitment(i, position)
+                })
+            })?;
 
         Ok((queries.main_states, queries.aux_states))
     }
 
-    /// Returns constraint evaluations at the specified positions of the LDE domain. This also
-    /// checks if the constraint evaluations are valid against the constraint commitment sent by
-    /// the prover.
-    pub fn read_constraint_evaluations(
+    fn read_constraint_evaluations(
         &mut self,
         positions: &[usize],
     ) -> Result<Table<E>, VerifierError> {
         let queries = self.constraint_queries.take().expect("already read");
 
-        MerkleTree::verify_batch(&self.constraint_root, positions, &queries.query_proofs)
-            .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
+        verify_query_proofs(&self.constraint_root, positions, &queries.query_proofs)
+            .map_err(VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
 
         Ok(queries.evaluations)
     }
+
+    fn read_queried_column_group_states(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<Table<E::BaseField>, VerifierError> {
+        let (root, queries) = match (self.column_group_root, self.column_group_queries.take()) {
+            (Some(root), Some(queries)) => (root, queries),
+            _ => {
+                return Err(VerifierError::ProofDeserializationError(
+                    "column group queries are not available".to_string(),
+                ))
+            }
+        };
+
+        verify_query_proofs(&root, positions, &queries.query_proofs)
+            .map_err(VerifierError::ColumnGroupQueryDoesNotMatchCommitment)?;
+
+        Ok(queries.states)
+    }
 }
 
 // FRI VERIFIER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<E, H> FriVerifierChannel<E> for VerifierChannel<E, H>
+impl<E, H, AH> FriVerifierChannel<E> for DefaultVerifierChannel<E, H, AH>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    AH: ElementHasher<BaseField = E::BaseField> + Hasher<Digest = H::Digest>,
 {
     type Hasher = H;
 
+    /// Returns the number of partitions of the FRI instance which is queried next; the main FRI
+    /// instance is queried first, followed by the column group FRI instance (if any).
     fn read_fri_num_partitions(&self) -> usize {
-        self.fri_num_partitions
+        assert!(!self.fri_remainders.is_empty(), "already read");
+        self.fri_num_partitions[self.fri_num_partitions.len() - self.fri_remainders.len()]
     }
 
+    /// Returns layer commitments of the next FRI instance; the main FRI instance reads its
+    /// commitments first, followed by the column group FRI instance (if any).
     fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
-        self.fri_roots.take().expect("already read")
+        assert!(self.fri_instance < self.fri_roots.len(), "already read");
+        let roots = mem::take(&mut self.fri_roots[self.fri_instance]);
+        self.fri_instance += 1;
+        roots
     }
 
     fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
@@ -218,8 +536,231 @@ where
     }
 
     fn take_fri_remainder(&mut self) -> Vec<E> {
-        self.fri_remainder.take().expect("already read")
+        assert!(!self.fri_remainders.is_empty(), "already read");
+        self.fri_remainders.remove(0)
+    }
+}
+
+// CERTIFICATE CHANNEL
+// ================================================================================================
+
+/// A view into a [ProofCertificate] which provides the data needed to verify the commitment phase
+/// of the protocol.
+///
+/// Query openings are not available in a certificate, and thus, this channel implements only the
+/// [CommitmentChannel] trait.
+pub(crate) struct CertificateChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    trace_roots: Vec<H::Digest>,
+    column_group_root: Option<H::Digest>,
+    constraint_root: H::Digest,
+    fri_roots: Vec<Vec<H::Digest>>,
+    fri_instance: usize,
+    ood_trace_frame: Option<TraceOodFrame<E>>,
+    ood_constraint_evaluations: Option<Vec<E>>,
+    pow_nonce: u64,
+}
+
+impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> CertificateChannel<E, H> {
+    /// Creates and returns a new [CertificateChannel] initialized from the specified
+    /// `certificate`.
+    ///
+    /// # Errors
+    /// Returns an error if the `certificate` is not well-formed in the context of the specified
+    /// `air`.
+    pub fn new<A: Air<BaseField = E::BaseField>>(
+        air: &A,
+        certificate: &ProofCertificate,
+    ) -> Result<Self, VerifierError> {
+        let ProofCertificate {
+            context,
+            commitments,
+            ood_frame,
+            pow_nonce,
+        } = certificate.clone();
+
+        // make sure AIR and proof base fields are the same
+        if E::BaseField::get_modulus_le_bytes() != context.field_modulus_bytes() {
+            return Err(VerifierError::InconsistentBaseField);
+        }
+
+        // --- parse commitments ------------------------------------------------------------------
+        let (trace_roots, column_group_root, constraint_root, fri_roots) =
+            parse_commitments::<E, H, A>(air, commitments)?;
+
+        // --- parse out-of-domain evaluation frame -----------------------------------------------
+        let (ood_trace_frame, ood_constraint_evaluations) = parse_ood_frame(air, ood_frame)?;
+
+        Ok(CertificateChannel {
+            trace_roots,
+            column_group_root,
+            constraint_root,
+            fri_roots,
+            fri_instance: 0,
+            ood_trace_frame: Some(ood_trace_frame),
+            ood_constraint_evaluations: Some(ood_constraint_evaluations),
+            pow_nonce,
+        })
+    }
+}
+
+impl<E, H> CommitmentChannel<E, H> for CertificateChannel<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn read_trace_commitments(&self) -> &[H::Digest] {
+        &self.trace_roots
+    }
+
+    fn read_constraint_commitment(&self) -> H::Digest {
+        self.constraint_root
+    }
+
+    fn read_ood_trace_frame(&mut self) -> TraceOodFrame<E> {
+        self.ood_trace_frame.take().expect("already read")
+    }
+
+    fn read_ood_constraint_evaluations(&mut self) -> Vec<E> {
+        self.ood_constraint_evaluations.take().expect("already read")
+    }
+
+    fn read_fri_commitments(&mut self) -> Vec<H::Digest> {
+        assert!(self.fri_instance < self.fri_roots.len(), "already read");
+        let roots = mem::take(&mut self.fri_roots[self.fri_instance]);
+        self.fri_instance += 1;
+        roots
+    }
+
+    fn read_pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
+    fn read_column_group_commitment(&self) -> Option<H::Digest> {
+        self.column_group_root
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses the provided `commitments` in the context of the specified `air`.
+///
+/// Returns trace segment commitments, the column group commitment (if the computation defines a
+/// column group), the constraint commitment, and FRI layer commitments for each FRI instance. The
+/// column group commitment follows the main trace segment commitment, and the FRI layer
+/// commitments of the column group FRI instance follow those of the main FRI instance.
+#[allow(clippy::type_complexity)]
+pub(crate) fn parse_commitments<E, H, A>(
+    air: &A,
+    commitments: Commitments,
+) -> Result<(Vec<H::Digest>, Option<H::Digest>, H::Digest, Vec<Vec<H::Digest>>), VerifierError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    A: Air<BaseField = E::BaseField>,
+{
+    let fri_options = air.options().to_fri_options();
+    let num_trace_segments = air.trace_layout().num_segments();
+    let num_fri_layers = fri_options.num_committed_layers(air.lde_domain_size());
+
+    // the number of column group FRI layers includes the remainder layer of the column group
+    // FRI instance
+    let context = air.context();
+    let num_column_group_fri_layers = match context.column_group_fri_options() {
+        Some(group_fri_options) => {
+            let domain_size = context.column_group_domain_size().expect("no column group");
+            group_fri_options.num_fri_layers(domain_size) + 1
+        }
+        None => 0,
+    };
+    let num_trace_roots = num_trace_segments + context.column_group().is_some() as usize;
+
+    let (mut trace_roots, constraint_root, mut fri_roots) = commitments
+        .parse::<H>(num_trace_roots, num_fri_layers + num_column_group_fri_layers)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+    let column_group_root = context.column_group().map(|_| trace_roots.remove(1));
+    let column_group_fri_roots = fri_roots.split_off(num_fri_layers + 1);
+    let mut fri_roots = vec![fri_roots];
+    if column_group_root.is_some() {
+        fri_roots.push(column_group_fri_roots);
+    }
+
+    Ok((trace_roots, column_group_root, constraint_root, fri_roots))
+}
+
+/// Parses the provided out-of-domain frame into a trace frame and constraint evaluations, and
+/// makes sure that the shape of the frame matches the shape expected by the specified `air`.
+fn parse_ood_frame<E, A>(
+    air: &A,
+    ood_frame: OodFrame,
+) -> Result<(TraceOodFrame<E>, Vec<E>), VerifierError>
+where
+    E: FieldElement,
+    A: Air<BaseField = E::BaseField>,
+{
+    let frame = ood_frame
+        .parse::<E>()
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+    let main_trace_width = air.trace_layout().main_trace_width();
+    let aux_trace_width = air.trace_layout().aux_trace_width();
+    let num_ood_constraint_evaluations = match air.options().ood_constraint_batching() {
+        OodConstraintBatching::PerColumn => air.context().num_constraint_composition_columns(),
+        OodConstraintBatching::Combined => 1,
+    };
+    let expected_shape = [
+        ("number of trace states", 2, frame.frame_size),
+        ("main trace width", main_trace_width, frame.main_trace_width),
+        ("auxiliary trace width", aux_trace_width, frame.aux_trace_width),
+        (
+            "number of constraint evaluations",
+            num_ood_constraint_evaluations,
+            frame.constraint_evaluations.len(),
+        ),
+    ];
+    for (name, expected, actual) in expected_shape {
+        if expected != actual {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected OOD frame {name} to be {expected}, but was {actual}"
+            )));
+        }
     }
+
+    let trace_frame = TraceOodFrame::new(frame.trace_states, main_trace_width, aux_trace_width);
+    Ok((trace_frame, frame.constraint_evaluations))
+}
+
+/// Parses the provided FRI proof into layer queries, layer Merkle proofs, and the remainder.
+#[allow(clippy::type_complexity)]
+fn parse_fri_proof<E, H>(
+    fri_proof: FriProof,
+    domain_size: usize,
+    folding_factor: usize,
+) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>, Vec<E>), VerifierError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let remainder = fri_proof
+        .parse_remainder()
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    let (layer_queries, layer_proofs) = fri_proof
+        .parse_layers::<H, E>(domain_size, folding_factor)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    Ok((layer_queries, layer_proofs, remainder))
+}
+
+/// Checks that Merkle authentication paths in the batch `proof` for the specified query
+/// `positions` resolve to the `root`; otherwise, returns the first position whose path does not
+/// resolve to the root.
+fn verify_query_proofs<H: Hasher>(
+    root: &H::Digest,
+    positions: &[usize],
+    proof: &BatchMerkleProof<H>,
+) -> Result<(), usize> {
+    MerkleTree::verify_batch(root, positions, proof)
+        .map_err(|_| MerkleTree::find_invalid_index(root, positions, proof).unwrap_or(positions[0]))
 }
 
 // TRACE QUERIES
@@ -227,21 +768,43 @@ where
 
 /// Container of trace query data, including:
 /// * Queried states for all trace segments.
-/// * Merkle authentication paths for all queries.
+/// * Merkle authentication paths for all queries; paths for auxiliary segment queries are built
+///   using hash function `AH`.
 ///
 /// Trace states for all auxiliary segments are stored in a single table.
-struct TraceQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    query_proofs: Vec<BatchMerkleProof<H>>,
+struct TraceQueries<E, H, AH>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    AH: ElementHasher<BaseField = E::BaseField>,
+{
+    main_query_proof: BatchMerkleProof<H>,
+    aux_query_proofs: Vec<BatchMerkleProof<AH>>,
     main_states: Table<E::BaseField>,
     aux_states: Option<Table<E>>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E, H> {
+impl<E, H, AH> TraceQueries<E, H, AH>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    AH: ElementHasher<BaseField = E::BaseField>,
+{
     /// Parses the provided trace queries into trace states in the specified field and
     /// corresponding Merkle authentication paths.
     pub fn new<A: Air<BaseField = E::BaseField>>(
+        queries: Vec<Queries>,
+        air: &A,
+    ) -> Result<Self, VerifierError> {
+        Self::with_num_queries(queries, air, air.options().num_query_positions())
+    }
+
+    /// Parses the provided trace queries made at `num_queries` positions into trace states in
+    /// the specified field and corresponding Merkle authentication paths.
+    pub fn with_num_queries<A: Air<BaseField = E::BaseField>>(
         mut queries: Vec<Queries>,
         air: &A,
+        num_queries: usize,
     ) -> Result<Self, VerifierError> {
         assert_eq!(
             queries.len(),
@@ -251,22 +814,30 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
             queries.len()
         );
 
-        let num_queries = air.options().num_queries();
+        let row_hash = air.context().row_hash_strategy();
 
         // parse main trace segment queries; parsing also validates that hashes of each table row
         // form the leaves of Merkle authentication paths in the proofs
-        let main_segment_width = air.trace_layout().main_trace_width();
+        // columns of the column group (if any) are not included in the main trace segment
+        // queries
+        let num_column_group_columns =
+            air.context().column_group().map_or(0, |group| group.num_columns());
+        let main_segment_width = air.trace_layout().main_trace_width() - num_column_group_columns;
         let main_segment_queries = queries.remove(0);
         let (main_segment_query_proofs, main_segment_states) = main_segment_queries
-            .parse::<H, E::BaseField>(air.lde_domain_size(), num_queries, main_segment_width)
+            .parse_with_row_hash::<H, E::BaseField>(
+                air.lde_domain_size(),
+                num_queries,
+                main_segment_width,
+                row_hash,
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
                     "main trace segment query deserialization failed: {err}"
                 ))
             })?;
 
-        // all query proofs will be aggregated into a single vector
-        let mut query_proofs = vec![main_segment_query_proofs];
+        let mut aux_query_proofs = Vec::new();
 
         // parse auxiliary trace segment queries (if any), and merge resulting tables into a
         // single table; parsing also validates that hashes of each table row form the leaves
@@ -276,14 +847,19 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
             for (i, segment_queries) in queries.into_iter().enumerate() {
                 let segment_width = air.trace_layout().get_aux_segment_width(i);
                 let (segment_query_proof, segment_trace_states) = segment_queries
-                    .parse::<H, E>(air.lde_domain_size(), num_queries, segment_width)
+                    .parse_with_row_hash::<AH, E>(
+                        air.lde_domain_size(),
+                        num_queries,
+                        segment_width,
+                        row_hash,
+                    )
                     .map_err(|err| {
                         VerifierError::ProofDeserializationError(format!(
                             "auxiliary trace segment query deserialization failed: {err}"
                         ))
                     })?;
 
-                query_proofs.push(segment_query_proof);
+                aux_query_proofs.push(segment_query_proof);
                 aux_trace_states.push(segment_trace_states);
             }
 
@@ -294,13 +870,57 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
         };
 
         Ok(Self {
-            query_proofs,
+            main_query_proof: main_segment_query_proofs,
+            aux_query_proofs,
             main_states: main_segment_states,
             aux_states: aux_trace_states,
         })
     }
 }
 
+// COLUMN GROUP QUERIES
+// ================================================================================================
+
+/// Container of column group query data, including:
+/// * Queried states of the column group.
+/// * Merkle authentication paths for all queries.
+struct ColumnGroupQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    query_proofs: BatchMerkleProof<H>,
+    states: Table<E::BaseField>,
+}
+
+impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ColumnGroupQueries<E, H> {
+    /// Parses the provided column group queries into states in the base field and corresponding
+    /// Merkle authentication paths.
+    pub fn new<A: Air<BaseField = E::BaseField>>(
+        queries: Queries,
+        air: &A,
+    ) -> Result<Self, VerifierError> {
+        let context = air.context();
+        let group = context.column_group().expect("no column group");
+        let num_queries = context.num_column_group_queries().expect("no column group");
+        let domain_size = context.column_group_domain_size().expect("no column group");
+
+        let (query_proofs, states) = queries
+            .parse_with_row_hash::<H, E::BaseField>(
+                domain_size,
+                num_queries,
+                group.num_columns(),
+                context.row_hash_strategy(),
+            )
+            .map_err(|err| {
+                VerifierError::ProofDeserializationError(format!(
+                    "column group query deserialization failed: {err}"
+                ))
+            })?;
+
+        Ok(Self {
+            query_proofs,
+            states,
+        })
+    }
+}
+
 // CONSTRAINT QUERIES
 // ================================================================================================
 
@@ -319,11 +939,16 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
         queries: Queries,
         air: &A,
     ) -> Result<Self, VerifierError> {
-        let num_queries = air.options().num_queries();
+        let num_queries = air.options().num_query_positions();
         let constraint_frame_width = air.context().num_constraint_composition_columns();
 
         let (query_proofs, evaluations) = queries
-            .parse::<H, E>(air.lde_domain_size(), num_queries, constraint_frame_width)
+            .parse_with_leaf_encoding::<H, E>(
+                air.lde_domain_size(),
+                num_queries,
+                constraint_frame_width,
+                air.options().constraint_leaf_encoding(),
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
                     "constraint evaluation query deserialization failed: {err}"
@@ -340,6 +965,8 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
 // TRACE OUT-OF-DOMAIN FRAME
 // ================================================================================================
 
+/// Trace polynomial evaluations at out-of-domain points z and z * g, where g is the generator of
+/// the LDE domain.
 pub struct TraceOodFrame<E: FieldElement> {
     values: Vec<E>,
     main_trace_width: usize,
@@ -347,6 +974,11 @@ pub struct TraceOodFrame<E: FieldElement> {
 }
 
 impl<E: FieldElement> TraceOodFrame<E> {
+    /// Returns a new out-of-domain frame instantiated from the provided `values`.
+    ///
+    /// The values are expected to contain evaluations of the main trace polynomials followed
+    /// by evaluations of the auxiliary trace polynomials, where evaluations at z and z * g for
+    /// each polynomial are interleaved.
     pub fn new(values: Vec<E>, main_trace_width: usize, aux_trace_width: usize) -> Self {
         Self {
             values,
@@ -355,6 +987,7 @@ impl<E: FieldElement> TraceOodFrame<E> {
         }
     }
 
+    /// Returns all out-of-domain evaluations in this frame.
     pub fn values(&self) -> &[E] {
         &self.values
     }
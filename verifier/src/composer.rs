@@ -3,39 +3,113 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::{proof::Table, Air, DeepCompositionCoefficients, EvaluationFrame};
-use math::{batch_inversion, FieldElement};
-use utils::collections::Vec;
+use air::{
+    deep, proof::Table, Air, DeepCompositionCoefficients, EvaluationFrame, OodConstraintBatching,
+};
+use math::{batch_inversion, get_power_series, FieldElement, StarkField};
+use utils::{collections::Vec, iter_mut};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 // DEEP COMPOSER
 // ================================================================================================
 
 pub struct DeepComposer<E: FieldElement> {
     cc: DeepCompositionCoefficients<E>,
+    /// Indexes of the main trace columns included in the queried main trace states.
+    main_columns: Vec<usize>,
+    main_trace_width: usize,
     x_coordinates: Vec<E>,
-    z: [E; 2],
+    /// Inverses of (x - z) and (x - z * g) for each of the query x coordinates.
+    inv_denominators: Vec<[E; 2]>,
+    /// Weights z^(i * n) used to combine constraint composition columns when OOD constraint
+    /// evaluations are combined into a single value.
+    column_weights: Option<Vec<E>>,
 }
 
 impl<E: FieldElement> DeepComposer<E> {
     /// Creates a new composer for computing DEEP composition polynomial values.
+    ///
+    /// This also computes inverses of (x - z) and (x - z * g) for all query x coordinates using a
+    /// single batch inversion, so that no inversions are needed during the composition itself.
+    ///
+    /// If the computation defines a column group, the queried main trace states are expected to
+    /// exclude columns of the column group.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         air: &A,
         query_positions: &[usize],
         z: E,
         cc: DeepCompositionCoefficients<E>,
     ) -> Self {
-        // compute LDE domain coordinates for all query positions
-        let g_lde = air.lde_domain_generator();
+        let main_columns = (0..air.trace_layout().main_trace_width())
+            .filter(|&i| air.context().column_group().map_or(true, |group| !group.contains(i)))
+            .collect();
+        Self::build(air, query_positions, air.lde_domain_size(), main_columns, z, cc)
+    }
+
+    /// Creates a new composer for computing values of the DEEP composition polynomial of the
+    /// column group at the specified positions of the column group domain.
+    ///
+    /// The queried main trace states passed to this composer are expected to contain only the
+    /// columns of the column group.
+    ///
+    /// # Panics
+    /// Panics if the computation does not define a column group.
+    pub fn for_column_group<A: Air<BaseField = E::BaseField>>(
+        air: &A,
+        query_positions: &[usize],
+        z: E,
+        cc: DeepCompositionCoefficients<E>,
+    ) -> Self {
+        let context = air.context();
+        let group = context.column_group().expect("no column group");
+        let domain_size = context.column_group_domain_size().expect("no column group");
+        Self::build(air, query_positions, domain_size, group.columns().to_vec(), z, cc)
+    }
+
+    /// Creates a new composer for query positions in a domain of the specified size; the domain
+    /// is assumed to have the same offset as the LDE domain.
+    fn build<A: Air<BaseField = E::BaseField>>(
+        air: &A,
+        query_positions: &[usize],
+        domain_size: usize,
+        main_columns: Vec<usize>,
+        z: E,
+        cc: DeepCompositionCoefficients<E>,
+    ) -> Self {
+        // compute domain coordinates for all query positions
+        let g_lde = E::BaseField::get_root_of_unity(domain_size.ilog2());
         let domain_offset = air.domain_offset();
         let x_coordinates: Vec<E> = query_positions
             .iter()
             .map(|&p| E::from(g_lde.exp_vartime((p as u64).into()) * domain_offset))
             .collect();
 
+        // if OOD constraint evaluations are combined, column i of the composition polynomial is
+        // weighted by z^(i * n) where n is the trace length
+        let column_weights = match air.options().ood_constraint_batching() {
+            OodConstraintBatching::PerColumn => None,
+            OodConstraintBatching::Combined => Some(get_power_series(
+                z.exp_vartime((air.trace_length() as u64).into()),
+                air.context().num_constraint_composition_columns(),
+            )),
+        };
+
+        // compute (x - z) and (x - z * g) for all query x coordinates, and invert them all at once
+        let z = deep::get_ood_points(z, air.trace_length());
+        let denominators: Vec<E> =
+            x_coordinates.iter().flat_map(|&x| [x - z[0], x - z[1]]).collect();
+        let inv_denominators =
+            batch_inversion(&denominators).chunks(2).map(|inv| [inv[0], inv[1]]).collect();
+
         DeepComposer {
             cc,
+            main_columns,
+            main_trace_width: air.trace_layout().main_trace_width(),
             x_coordinates,
-            z: [z, z * E::from(air.trace_domain_generator())],
+            inv_denominators,
+            column_weights,
         }
     }
 
@@ -52,6 +126,8 @@ impl<E: FieldElement> DeepComposer<E> {
     ///
     /// Note that values of T_i(z) and T_i(z * g) are received from the prover and passed into
     /// this function via the `ood_main_frame` and `ood_aux_frame` parameters.
+    ///
+    /// When the `concurrent` feature is enabled, queries are composed in parallel.
     pub fn compose_trace_columns(
         &self,
         queried_main_trace_states: Table<E::BaseField>,
@@ -59,75 +135,48 @@ impl<E: FieldElement> DeepComposer<E> {
         ood_main_frame: EvaluationFrame<E>,
         ood_aux_frame: Option<EvaluationFrame<E>>,
     ) -> Vec<E> {
-        let ood_main_trace_states = [ood_main_frame.current(), ood_main_frame.next()];
-
-        // compose columns of of the main trace segment; we do this separately for numerators of
-        // each query; we also track common denominator for each query separately; this way we can
-        // use a batch inversion in the end.
         let n = queried_main_trace_states.num_rows();
-        let mut result_num = Vec::<E>::with_capacity(n);
-        let mut result_den = Vec::<E>::with_capacity(n);
+        assert_eq!(n, self.x_coordinates.len());
 
-        for ((_, row), &x) in (0..n).zip(queried_main_trace_states.rows()).zip(&self.x_coordinates)
-        {
-            let mut t1_num = E::ZERO;
-            let mut t2_num = E::ZERO;
+        // select out-of-domain values of the main trace columns included in the queried states
+        let ood_main_current = select_columns(ood_main_frame.current(), &self.main_columns);
+        let ood_main_next = select_columns(ood_main_frame.next(), &self.main_columns);
+        let ood_main_trace_states = [ood_main_current.as_slice(), ood_main_next.as_slice()];
+        let ood_aux_trace_states = queried_aux_trace_states.as_ref().map(|_| {
+            let ood_aux_frame = ood_aux_frame.as_ref().expect("missing auxiliary OOD frame");
+            [ood_aux_frame.current(), ood_aux_frame.next()]
+        });
 
-            for (i, &value) in row.iter().enumerate() {
-                let value = E::from(value);
-                // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t1_num += (value - ood_main_trace_states[0][i]) * self.cc.trace[i];
-
-                // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t2_num += (value - ood_main_trace_states[1][i]) * self.cc.trace[i];
-            }
-            // compute the common denominator as (x - z) * (x - z * g)
-            let t1_den = x - self.z[0];
-            let t2_den = x - self.z[1];
-            result_den.push(t1_den * t2_den);
-
-            // add the numerators of T'_i(x) and T''_i(x) together; we can do this because later on
-            // we'll use the common denominator computed above.
-            result_num.push(t1_num * t2_den + t2_num * t1_den);
-        }
-
-        // if the trace has auxiliary segments, compose columns from these segments as well; we
-        // also do this separately for numerators and denominators.
-        if let Some(queried_aux_trace_states) = queried_aux_trace_states {
-            let ood_aux_frame = ood_aux_frame.expect("missing auxiliary OOD frame");
-            let ood_aux_trace_states = [ood_aux_frame.current(), ood_aux_frame.next()];
+        // composition coefficients for auxiliary trace columns follow the coefficients for the
+        // main trace columns
+        let (main_cc, aux_cc) = self.cc.trace.split_at(self.main_trace_width);
+        let main_cc = select_columns(main_cc, &self.main_columns);
 
-            // we define this offset here because composition of the main trace columns has
-            // consumed some number of composition coefficients already.
-            let cc_offset = queried_main_trace_states.num_columns();
+        let mut result = vec![E::ZERO; n];
+        // compose queries in chunks of at least 16 queries when running concurrently
+        iter_mut!(result, 16).enumerate().for_each(|(j, result)| {
+            // compose columns of the main trace segment
+            let main_row = queried_main_trace_states.get_row(j);
+            let (mut t1_num, mut t2_num) =
+                compose_row(main_row.iter().map(|&v| E::from(v)), ood_main_trace_states, &main_cc);
 
-            for ((j, row), &x) in
-                (0..n).zip(queried_aux_trace_states.rows()).zip(&self.x_coordinates)
+            // if the trace has auxiliary segments, compose columns from these segments as well
+            if let (Some(aux_states), Some(ood_aux_states)) =
+                (queried_aux_trace_states.as_ref(), ood_aux_trace_states)
             {
-                let mut t1_num = E::ZERO;
-                let mut t2_num = E::ZERO;
-                for (i, &value) in row.iter().enumerate() {
-                    // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t1_num += (value - ood_aux_trace_states[0][i]) * self.cc.trace[cc_offset + i];
-
-                    // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t2_num += (value - ood_aux_trace_states[1][i]) * self.cc.trace[cc_offset + i];
-                }
-
-                // compute the common denominators (x - z) and (x - z * g), and use the to aggregate
-                // numerators into the common numerator computed for the main trace of this query
-                let t1_den = x - self.z[0];
-                let t2_den = x - self.z[1];
-                result_num[j] += t1_num * t2_den + t2_num * t1_den;
+                let aux_row = aux_states.get_row(j);
+                let (aux_t1_num, aux_t2_num) =
+                    compose_row(aux_row.iter().copied(), ood_aux_states, aux_cc);
+                t1_num += aux_t1_num;
+                t2_num += aux_t2_num;
             }
-        }
 
-        result_den = batch_inversion(&result_den);
-        result_num.iter().zip(result_den).map(|(n, d)| *n * d).collect()
+            // divide the numerators by (x - z) and (x - z * g) and add the results together
+            let [inv_t1_den, inv_t2_den] = self.inv_denominators[j];
+            *result = t1_num * inv_t1_den + t2_num * inv_t2_den;
+        });
+
+        result
     }
 
     /// For each queried set of composition polynomial column evaluations, combine evaluations
@@ -141,34 +190,44 @@ impl<E: FieldElement> DeepComposer<E> {
     ///
     /// Note that values of H_i(z) are received from the prover and passed into this function
     /// via the `ood_evaluations` parameter.
+    ///
+    /// If OOD constraint evaluations are combined, `ood_evaluations` contains only H(z), and the
+    /// queried values are first combined into G(x) = sum(H_i(x) * z^(i * n)), where n is the trace
+    /// length; the result is then computed as (G(x) - H(z)) * cc_0 / (x - z).
+    ///
+    /// When the `concurrent` feature is enabled, queries are composed in parallel.
     pub fn compose_constraint_evaluations(
         &self,
         queried_evaluations: Table<E>,
         ood_evaluations: Vec<E>,
     ) -> Vec<E> {
-        assert_eq!(queried_evaluations.num_rows(), self.x_coordinates.len());
-
         let n = queried_evaluations.num_rows();
-        let mut result_num = Vec::<E>::with_capacity(n);
-        let mut result_den = Vec::<E>::with_capacity(n);
+        assert_eq!(n, self.x_coordinates.len());
 
-        let z = self.z[0];
+        let mut result = vec![E::ZERO; n];
+        // compose queries in chunks of at least 16 queries when running concurrently
+        iter_mut!(result, 16).enumerate().for_each(|(j, result)| {
+            let row = queried_evaluations.get_row(j);
+            if let Some(weights) = self.column_weights.as_ref() {
+                let combined = row.iter().zip(weights).fold(E::ZERO, |acc, (&v, &w)| acc + v * w);
+                *result = (combined - ood_evaluations[0])
+                    * self.cc.constraints[0]
+                    * self.inv_denominators[j][0];
+                return;
+            }
 
-        // combine composition polynomial columns separately for numerators and denominators;
-        // this way we can use batch inversion in the end.
-        for (query_values, &x) in queried_evaluations.rows().zip(&self.x_coordinates) {
             let mut composition_num = E::ZERO;
-            for (i, &evaluation) in query_values.iter().enumerate() {
+            for (i, &evaluation) in row.iter().enumerate() {
                 // compute the numerator of H'_i(x) as (H_i(x) - H_i(z)), multiply it by a
                 // composition coefficient, and add the result to the numerator aggregator
                 composition_num += (evaluation - ood_evaluations[i]) * self.cc.constraints[i];
             }
-            result_num.push(composition_num);
-            result_den.push(x - z);
-        }
 
-        result_den = batch_inversion(&result_den);
-        result_num.iter().zip(result_den).map(|(n, d)| *n * d).collect()
+            // divide the numerator by (x - z)
+            *result = composition_num * self.inv_denominators[j][0];
+        });
+
+        result
     }
 
     /// Combines trace and constraint compositions together.
@@ -185,3 +244,34 @@ impl<E: FieldElement> DeepComposer<E> {
         result
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns values at the specified column indexes.
+fn select_columns<E: Copy>(values: &[E], columns: &[usize]) -> Vec<E> {
+    columns.iter().map(|&i| values[i]).collect()
+}
+
+/// Computes numerators of T'(x) and T''(x) for a single row of queried trace values.
+///
+/// The numerators are computed as sum((T_i(x) - T_i(z)) * cc_i) and
+/// sum((T_i(x) - T_i(z * g)) * cc_i) respectively.
+fn compose_row<E, I>(row: I, ood_states: [&[E]; 2], cc: &[E]) -> (E, E)
+where
+    E: FieldElement,
+    I: Iterator<Item = E>,
+{
+    let mut t1_num = E::ZERO;
+    let mut t2_num = E::ZERO;
+    for (i, value) in row.enumerate() {
+        // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
+        // composition coefficient, and add the result to the numerator aggregator
+        t1_num += (value - ood_states[0][i]) * cc[i];
+
+        // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
+        // composition coefficient, and add the result to the numerator aggregator
+        t2_num += (value - ood_states[1][i]) * cc[i];
+    }
+    (t1_num, t2_num)
+}
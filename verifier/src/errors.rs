@@ -16,23 +16,54 @@ pub enum VerifierError {
     /// This error occurs when base field read by a verifier from a proof does not match the
     /// base field of AIR with which the verifier was instantiated.
     InconsistentBaseField,
+    /// This error occurs when the hash function which was used by the prover to commit to
+    /// auxiliary trace segments (as recorded in the proof context) is not the hash function with
+    /// which the verifier was instantiated.
+    InconsistentAuxTraceHasher,
+    /// This error occurs when trace info or proof options of a proof do not match the ones with
+    /// which a prepared AIR instance used to verify the proof was instantiated.
+    InconsistentProofContext,
     /// This error occurs when the base field in which the proof was generated does not support
     /// field extension of degree specified by the proof.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when the LDE domain implied by the trace length and the blowup factor of
+    /// a proof is larger than the largest multiplicative subgroup of size $2^k$ in the base
+    /// field. The error specifies the size of the domain and the size of the largest subgroup.
+    DomainTooLarge(usize, usize),
     /// This error occurs when a verifier cannot deserialize the specified proof.
     ProofDeserializationError(String),
     /// This error occurs when a verifier fails to draw a random value from a random coin
     /// within a specified number of tries.
     RandomCoinError,
+    /// This error occurs when a random element drawn for the auxiliary trace segment with the
+    /// specified index is zero.
+    ZeroAuxRandElement(usize),
+    /// This error occurs when the out-of-domain point drawn from the public coin lies in the
+    /// trace domain or in the evaluation domain, or when a constraint divisor vanishes at it.
+    InvalidOodPoint,
     /// This error occurs when constraints evaluated over out-of-domain trace rows do not match
     /// evaluations of the constraint composition polynomial at the out-of-domain point.
     InconsistentOodConstraintEvaluations,
-    /// This error occurs when Merkle authentication paths of trace queries do not resolve to the
-    /// execution trace commitment included in the proof.
-    TraceQueryDoesNotMatchCommitment,
+    /// This error occurs when Merkle authentication paths of main trace segment queries do not
+    /// resolve to the main trace segment commitment included in the proof. The error specifies
+    /// the first query position for which the authentication path did not resolve to the
+    /// commitment.
+    MainTraceQueryDoesNotMatchCommitment(usize),
+    /// This error occurs when Merkle authentication paths of auxiliary trace segment queries do
+    /// not resolve to the commitment to that segment included in the proof. The error specifies
+    /// the index of the auxiliary segment and the first query position for which the
+    /// authentication path did not resolve to the commitment.
+    AuxTraceQueryDoesNotMatchCommitment(usize, usize),
+    /// This error occurs when Merkle authentication paths of column group queries do not resolve
+    /// to the column group commitment included in the proof. The error specifies the first query
+    /// position (in the column group domain) for which the authentication path did not resolve
+    /// to the commitment.
+    ColumnGroupQueryDoesNotMatchCommitment(usize),
     /// This error occurs when Merkle authentication paths of constraint evaluation queries do not
-    /// resolve to the constraint evaluation commitment included in the proof.
-    ConstraintQueryDoesNotMatchCommitment,
+    /// resolve to the constraint evaluation commitment included in the proof. The error specifies
+    /// the first query position for which the authentication path did not resolve to the
+    /// commitment.
+    ConstraintQueryDoesNotMatchCommitment(usize),
     /// This error occurs when the proof-of-work nonce hashed with the current state of the public
     /// coin resolves to a value which does not meet the proof-of-work threshold specified by the
     // proof options.
@@ -41,6 +72,20 @@ pub enum VerifierError {
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when the conjectured security level of a proof is below the minimum
+    /// required by [AcceptableOptions](crate::AcceptableOptions). The error specifies the
+    /// required and the actual security levels.
+    InsufficientConjecturedSecurity(u32, u32),
+    /// This error occurs when the proven security level of a proof is below the minimum required
+    /// by [AcceptableOptions](crate::AcceptableOptions). The error specifies the required and
+    /// the actual security levels.
+    InsufficientProvenSecurity(u32, u32),
+    /// This error occurs when a proof was generated using proof options which are not in the set
+    /// of options accepted by [AcceptableOptions](crate::AcceptableOptions).
+    UnacceptableProofOptions,
+    /// This error occurs when public outputs of a computation cannot be built from the values
+    /// asserted against the execution trace of a verified proof.
+    PublicOutputsNotAsserted,
 }
 
 impl fmt::Display for VerifierError {
@@ -50,23 +95,44 @@ impl fmt::Display for VerifierError {
             Self::InconsistentBaseField =>  {
                 write!(f, "base field of the proof does not match base field of the specified AIR")
             }
+            Self::InconsistentAuxTraceHasher => {
+                write!(f, "auxiliary trace hash function of the proof does not match the hash function of the verifier")
+            }
+            Self::InconsistentProofContext => {
+                write!(f, "proof context does not match the context of the prepared AIR")
+            }
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the proof base field")
             }
+            Self::DomainTooLarge(domain_size, max_domain_size) => {
+                write!(f, "domain of {domain_size} elements is larger than the largest domain supported by the base field ({max_domain_size} elements)")
+            }
             Self::ProofDeserializationError(msg) => {
                 write!(f, "proof deserialization failed: {msg}")
             }
             Self::RandomCoinError => {
                 write!(f, "failed to draw a random value from a random coin")
             }
+            Self::ZeroAuxRandElement(segment) => {
+                write!(f, "a random element drawn for auxiliary trace segment {segment} is zero")
+            }
+            Self::InvalidOodPoint => {
+                write!(f, "out-of-domain point is not valid for the computation")
+            }
             Self::InconsistentOodConstraintEvaluations => {
                 write!(f, "constraint evaluations over the out-of-domain frame are inconsistent")
             }
-            Self::TraceQueryDoesNotMatchCommitment => {
-                write!(f, "trace query did not match the commitment")
+            Self::MainTraceQueryDoesNotMatchCommitment(position) => {
+                write!(f, "main trace query at position {position} did not match the commitment")
+            }
+            Self::AuxTraceQueryDoesNotMatchCommitment(segment, position) => {
+                write!(f, "auxiliary trace segment {segment} query at position {position} did not match the commitment")
+            }
+            Self::ColumnGroupQueryDoesNotMatchCommitment(position) => {
+                write!(f, "column group query at position {position} did not match the commitment")
             }
-            Self::ConstraintQueryDoesNotMatchCommitment => {
-                write!(f, "constraint query did not match the commitment")
+            Self::ConstraintQueryDoesNotMatchCommitment(position) => {
+                write!(f, "constraint query at position {position} did not match the commitment")
             }
             Self::QuerySeedProofOfWorkVerificationFailed => {
                 write!(f, "query seed proof-of-work verification failed")
@@ -74,6 +140,51 @@ impl fmt::Display for VerifierError {
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {err}")
             }
+            Self::InsufficientConjecturedSecurity(expected, actual) => {
+                write!(f, "conjectured security level of the proof is {actual} bits, but at least {expected} bits are required")
+            }
+            Self::InsufficientProvenSecurity(expected, actual) => {
+                write!(f, "proven security level of the proof is {actual} bits, but at least {expected} bits are required")
+            }
+            Self::UnacceptableProofOptions => {
+                write!(f, "proof options of the proof are not in the set of acceptable options")
+            }
+            Self::PublicOutputsNotAsserted => {
+                write!(f, "public outputs are not asserted against the execution trace")
+            }
+        }
+    }
+}
+
+// CHAIN ERROR
+// ================================================================================================
+/// Represents an error returned by [verify_chain()](crate::verify_chain) when verifying a chain of
+/// proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// This error occurs when a chain contains no proofs.
+    EmptyChain,
+    /// This error occurs when public inputs of the segment with the specified index are not
+    /// linked to public inputs of the previous segment according to the linking rule.
+    BrokenLink(usize),
+    /// This error occurs when the proof of the segment with the specified index fails
+    /// verification.
+    SegmentVerificationFailed(usize, VerifierError),
+}
+
+impl fmt::Display for ChainError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyChain => {
+                write!(f, "a chain must contain at least one proof")
+            }
+            Self::BrokenLink(segment) => {
+                write!(f, "inputs of segment {segment} are not linked to outputs of segment {}", segment - 1)
+            }
+            Self::SegmentVerificationFailed(segment, err) => {
+                write!(f, "verification of segment {segment} failed: {err}")
+            }
         }
     }
 }
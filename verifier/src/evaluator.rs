@@ -4,13 +4,19 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::{Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame};
-use math::{polynom, FieldElement};
+use math::FieldElement;
 use utils::collections::Vec;
 
 // CONSTRAINT EVALUATION
 // ================================================================================================
 
 /// Evaluates constraints for the specified evaluation frame.
+///
+/// The returned value is a random linear combination of all transition and boundary constraints
+/// evaluated at `x` and divided by their respective divisors, where the coefficients of the
+/// linear combination are specified by `composition_coefficients`.
+///
+/// This is a convenience wrapper around [Air::evaluate_constraints_at()].
 pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     air: &A,
     composition_coefficients: ConstraintCompositionCoefficients<E>,
@@ -19,64 +25,41 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     aux_rand_elements: AuxTraceRandElements<E>,
     x: E,
 ) -> E {
-    // 1 ----- evaluate transition constraints ----------------------------------------------------
-
-    // initialize a buffer to hold transition constraint evaluations
-    let t_constraints = air.get_transition_constraints(&composition_coefficients.transition);
-
-    // compute values of periodic columns at x
-    let periodic_values = air
-        .get_periodic_column_polys()
-        .iter()
-        .map(|poly| {
-            let num_cycles = air.trace_length() / poly.len();
-            let x = x.exp_vartime((num_cycles as u32).into());
-            polynom::eval(poly, x)
-        })
-        .collect::<Vec<_>>();
-
-    // evaluate transition constraints for the main trace segment
-    let mut t_evaluations1 = E::zeroed_vector(t_constraints.num_main_constraints());
-    air.evaluate_transition(main_trace_frame, &periodic_values, &mut t_evaluations1);
-
-    // evaluate transition constraints for auxiliary trace segments (if any)
-    let mut t_evaluations2 = E::zeroed_vector(t_constraints.num_aux_constraints());
-    if let Some(aux_trace_frame) = aux_trace_frame {
-        air.evaluate_aux_transition(
-            main_trace_frame,
-            aux_trace_frame,
-            &periodic_values,
-            &aux_rand_elements,
-            &mut t_evaluations2,
-        );
-    }
-
-    // merge all constraint evaluations into a single value by computing their random linear
-    // combination using coefficients drawn from the public coin. this also divides the result
-    // by the divisor of transition constraints.
-    let mut result = t_constraints.combine_evaluations::<E>(&t_evaluations1, &t_evaluations2, x);
-
-    // 2 ----- evaluate boundary constraints ------------------------------------------------------
-
-    // get boundary constraints grouped by common divisor from the AIR
-    let b_constraints =
-        air.get_boundary_constraints(&aux_rand_elements, &composition_coefficients.boundary);
-
-    // iterate over boundary constraint groups for the main trace segment (each group has a
-    // distinct divisor), evaluate constraints in each group and add their combination to the
-    // result
-    for group in b_constraints.main_constraints().iter() {
-        result += group.evaluate_at(main_trace_frame.current(), x);
-    }
-
-    // iterate over boundary constraint groups for auxiliary trace segments (each group has a
-    // distinct divisor), evaluate constraints in each group and add their combination to the
-    // result
-    if let Some(aux_trace_frame) = aux_trace_frame {
-        for group in b_constraints.aux_constraints().iter() {
-            result += group.evaluate_at(aux_trace_frame.current(), x);
-        }
-    }
+    evaluate_constraints_with_periodic_polys(
+        air,
+        &air.get_periodic_column_polys(),
+        composition_coefficients,
+        main_trace_frame,
+        aux_trace_frame,
+        aux_rand_elements,
+        x,
+    )
+}
 
-    result
+/// Evaluates constraints for the specified evaluation frame using the provided polynomials of
+/// periodic columns.
+///
+/// This is the same as [evaluate_constraints()], but periodic column polynomials are expected to
+/// be precomputed via [Air::get_periodic_column_polys()] rather than interpolated on each call.
+pub(crate) fn evaluate_constraints_with_periodic_polys<A, E>(
+    air: &A,
+    periodic_column_polys: &[Vec<A::BaseField>],
+    composition_coefficients: ConstraintCompositionCoefficients<E>,
+    main_trace_frame: &EvaluationFrame<E>,
+    aux_trace_frame: &Option<EvaluationFrame<E>>,
+    aux_rand_elements: AuxTraceRandElements<E>,
+    x: E,
+) -> E
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+{
+    air.evaluate_constraints_at_with_periodic_polys(
+        x,
+        periodic_column_polys,
+        main_trace_frame,
+        aux_trace_frame.as_ref(),
+        &aux_rand_elements,
+        &composition_coefficients,
+    )
 }
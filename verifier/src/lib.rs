@@ -32,39 +32,65 @@
 #[macro_use]
 extern crate alloc;
 
+use air::deep;
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    lagrange_kernel_boundary_value, lagrange_kernel_transition_divisor,
+    proof::{ProofCertificate, ProofOpenings, StarkProof, Table, VerificationArtifacts},
+    AggregateAir, AggregatePublicInputs, Air, AirContext, Assertion, AuxTraceRandElements,
+    BatchingMethod, BoundaryConstraint, BoundaryConstraintGroup, ColumnGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintLeafEncoding,
+    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, LagrangeKernelEvaluationFrame,
+    OodConstraintBatching, PeriodicColumn, PermutationArg, PermutationArgBuilder, ProofOptions,
+    RangeCheck, RangeCheckBuilder, RowHashStrategy, SelectorColumns, SelectorFlag, TraceInfo,
+    TraceMetaValue, TraceMetadata, TransitionConstraintDegree, MIN_RANGE_CHECK_TRACE_LENGTH,
 };
 
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement, ToElements,
+    FieldElement, StarkField, ToElements,
 };
 
+use utils::{collections::Vec, string::ToString};
 pub use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, RandomCoin};
 
 use fri::FriVerifier;
+pub use fri::VerifierChannel as FriVerifierChannel;
 
 mod channel;
-use channel::VerifierChannel;
+use channel::{parse_commitments, CertificateChannel};
+pub use channel::{CommitmentChannel, DefaultVerifierChannel, TraceOodFrame, VerifierChannel};
 
 mod evaluator;
-use evaluator::evaluate_constraints;
+pub use evaluator::evaluate_constraints;
+use evaluator::evaluate_constraints_with_periodic_polys;
+
+mod prepared;
+pub use prepared::PreparedAir;
+
+mod certificate;
+use certificate::CertifiedCommitmentPhase;
+pub use certificate::{CertifiedProof, OpeningRequest};
 
 mod composer;
 use composer::DeepComposer;
 
+mod options;
+pub use options::AcceptableOptions;
+
+mod outputs;
+pub use outputs::{AssertedValues, PublicOutputs};
+
 mod errors;
-pub use errors::VerifierError;
+pub use errors::{ChainError, VerifierError};
+
+#[cfg(test)]
+mod tests;
 
 // VERIFIER
 // ================================================================================================
@@ -74,11 +100,17 @@ pub use errors::VerifierError;
 /// `proof` attests to the correct execution of the computation against public inputs specified
 /// by `pub_inputs`. If the verification is successful, `Ok(())` is returned.
 ///
+/// This function expects auxiliary trace segments (if any) to be committed to using `HashFn`.
+/// Proofs in which auxiliary trace segments were committed to using a different hash function
+/// must be verified via [verify_with_aux_hasher()].
+///
 /// # Errors
 /// Returns an error if combination of the provided proof and public inputs does not attest to
 /// a correct execution of the computation. This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
+/// - Auxiliary trace segments of the proof were committed to using a hash function other than
+///   `HashFn`.
 #[rustfmt::skip]
 pub fn verify<AIR, HashFn, RandCoin>(
     proof: StarkProof,
@@ -88,39 +120,337 @@ where
     AIR: Air, 
     HashFn: ElementHasher<BaseField = AIR::BaseField>,
     RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    verify_with_aux_hasher::<AIR, HashFn, HashFn, RandCoin>(proof, pub_inputs)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// for a proof in which auxiliary trace segments were committed to using `AuxHashFn`.
+///
+/// This works the same way as [verify()], but Merkle authentication paths of auxiliary trace
+/// segment queries are checked using `AuxHashFn` rather than `HashFn`. This is useful when
+/// auxiliary trace segments are committed to using a hash function which is cheaper to verify
+/// in a specific setting (e.g., for recursive proof verification).
+///
+/// # Errors
+/// Returns an error if the proof context records a hash function for auxiliary trace segments
+/// which differs from `AuxHashFn`, or for any of the reasons described in [verify()].
+#[rustfmt::skip]
+pub fn verify_with_aux_hasher<AIR, HashFn, AuxHashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    AuxHashFn: ElementHasher<BaseField = AIR::BaseField> + Hasher<Digest = HashFn::Digest>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    verify_and_map::<AIR, HashFn, AuxHashFn, RandCoin, _, _>(proof, pub_inputs, |_| Ok(()))
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and returns public outputs of the computation.
+///
+/// This works the same way as [verify()], but once the proof has been verified, the values
+/// asserted against the main trace segment (see [Air::get_assertions()]) are converted into
+/// public outputs of type `O` via [PublicOutputs::from_asserted_values()]. This is useful for
+/// computations whose outputs are placed into designated trace cells, as the caller does not
+/// need to derive the outputs from the public inputs on their own.
+///
+/// # Errors
+/// Returns an error if the outputs cannot be built from the asserted values, or for any of the
+/// reasons described in [verify()].
+#[rustfmt::skip]
+pub fn verify_and_extract<AIR, HashFn, RandCoin, O>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<O, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    O: PublicOutputs<AIR::BaseField>,
+{
+    verify_and_map::<AIR, HashFn, HashFn, RandCoin, _, _>(proof, pub_inputs, |air| {
+        // assertions are validated against the trace during verification, and thus, building
+        // asserted values from them cannot fail at this point
+        let asserted_values = AssertedValues::new(&air.get_assertions(), air.trace_length());
+        O::from_asserted_values(&asserted_values).ok_or(VerifierError::PublicOutputsNotAsserted)
+    })
+}
+
+/// Verifies that the specified computation was executed correctly using a prepared AIR instance.
+///
+/// This works the same way as [verify()], but instead of instantiating the AIR from the proof
+/// and public inputs, uses the AIR (together with values derived from it) from the provided
+/// [PreparedAir]. This is useful when many proofs need to be verified against the same AIR
+/// instance, as the AIR is instantiated only once.
+///
+/// # Errors
+/// Returns an error if trace info or proof options of the `proof` differ from the ones with which
+/// the prepared AIR was instantiated, or if the proof does not attest to a correct execution of
+/// the computation against the public inputs of the prepared AIR (see [verify()]).
+pub fn verify_prepared<AIR, HashFn, RandCoin>(
+    prepared: &PreparedAir<AIR, HashFn>,
+    proof: StarkProof,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let air = prepared.air();
+    if proof.get_trace_info() != *air.trace_info() || proof.options() != air.options() {
+        return Err(VerifierError::InconsistentProofContext);
+    }
+
+    verify_with_air::<AIR, HashFn, HashFn, RandCoin>(
+        air,
+        prepared.periodic_column_polys(),
+        proof,
+        prepared.pub_inputs_elements(),
+        prepared.pub_inputs_commitment(),
+    )
+}
+
+/// Verifies an ordered chain of proofs, where each proof attests to a segment of a single
+/// computation.
+///
+/// Each element of `segments` contains a proof of a segment together with the public inputs of
+/// the segment. The `link` rule defines how outputs of a segment become inputs of the next
+/// segment: it is invoked with public inputs of every pair of adjacent segments, and must return
+/// true if the inputs of the second segment are a valid continuation of the first segment.
+///
+/// Links between all segments are checked before any of the proofs are verified. Polynomials of
+/// periodic columns are interpolated only when the periodic columns of a segment differ from the
+/// ones of the previous segment; thus, for chains of segments of the same computation, this is
+/// done only once.
+///
+/// # Errors
+/// Returns an error if the chain is empty, if any pair of adjacent segments is not linked
+/// according to the `link` rule, or if any of the proofs does not attest to a correct execution
+/// of its segment (see [verify()]).
+pub fn verify_chain<AIR, HashFn, RandCoin, L>(
+    segments: Vec<(StarkProof, AIR::PublicInputs)>,
+    link: L,
+) -> Result<(), ChainError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    L: Fn(&AIR::PublicInputs, &AIR::PublicInputs) -> bool,
+{
+    if segments.is_empty() {
+        return Err(ChainError::EmptyChain);
+    }
+    if let Some(i) = segments.windows(2).position(|pair| !link(&pair[0].1, &pair[1].1)) {
+        return Err(ChainError::BrokenLink(i + 1));
+    }
+
+    let mut periodic_columns = Vec::new();
+    let mut periodic_column_polys = Vec::new();
+    for (i, (proof, pub_inputs)) in segments.into_iter().enumerate() {
+        let pub_inputs_commitment = AIR::get_pub_inputs_commitment::<HashFn>(&pub_inputs);
+        let pub_inputs_elements = if pub_inputs_commitment.is_none() {
+            pub_inputs.to_elements()
+        } else {
+            Vec::new()
+        };
+
+        check_domain_size::<AIR::BaseField>(&proof.get_trace_info(), proof.options())
+            .map_err(|err| ChainError::SegmentVerificationFailed(i, err))?;
+        let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+
+        // polynomials of periodic columns depend only on the values of the columns, and thus, can
+        // be reused across segments with the same periodic columns
+        let columns = air.get_periodic_columns();
+        if i == 0 || columns != periodic_columns {
+            periodic_column_polys = air.get_periodic_column_polys();
+            periodic_columns = columns;
+        }
+
+        verify_with_air::<AIR, HashFn, HashFn, RandCoin>(
+            &air,
+            &periodic_column_polys,
+            proof,
+            &pub_inputs_elements,
+            pub_inputs_commitment,
+        )
+        .map_err(|err| ChainError::SegmentVerificationFailed(i, err))?;
+    }
+
+    Ok(())
+}
+
+/// Eagerly verifies the certificate part of a proof split via [StarkProof::split()].
+///
+/// This reads the commitments and out-of-domain evaluations from the `certificate`, checks that
+/// out-of-domain evaluations of the trace and the constraint composition polynomial are
+/// consistent with each other, and verifies the proof-of-work for the query seed. Query
+/// openings and FRI layers are not needed for this; they can be verified later using
+/// [CertifiedProof::verify_openings()] of the returned certified proof.
+///
+/// Passing this check alone does not imply that the computation was executed correctly; this
+/// becomes the case only once the openings are verified as well.
+///
+/// # Errors
+/// Returns an error if the certificate is not well-formed, or if the out-of-domain evaluations
+/// or the proof-of-work contained in the certificate are not valid.
+#[rustfmt::skip]
+pub fn verify_certificate<AIR, HashFn, RandCoin>(
+    certificate: ProofCertificate,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<CertifiedProof<AIR, HashFn, RandCoin>, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    check_domain_size::<AIR::BaseField>(
+        &certificate.context.get_trace_info(),
+        certificate.context.options(),
+    )?;
+    let prepared = PreparedAir::<AIR, HashFn>::new(
+        certificate.context.get_trace_info(),
+        pub_inputs,
+        certificate.context.options().clone(),
+    );
+    let air = prepared.air();
+
+    let mut public_coin_seed = certificate.context.to_elements();
+    public_coin_seed.extend_from_slice(prepared.pub_inputs_elements());
+    let mut public_coin =
+        build_public_coin::<RandCoin>(&public_coin_seed, prepared.pub_inputs_commitment());
+    let periodic_column_polys = prepared.periodic_column_polys();
+
+    // figure out which version of the commitment phase verification procedure to run; this works
+    // the same way as in verify_with_air(). results of the commitment phase are retained so that
+    // only the openings need to be checked once they are received.
+    let commitment_phase = match air.options().field_extension() {
+        FieldExtension::None => {
+            let mut channel = CertificateChannel::<AIR::BaseField, HashFn>::new(air, &certificate)?;
+            CertifiedCommitmentPhase::Base(verify_commitment_phase(air, periodic_column_polys, &mut channel, &mut public_coin)?)
+        },
+        FieldExtension::Quadratic => {
+            if !<QuadExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(2));
+            }
+            let mut channel = CertificateChannel::<QuadExtension<AIR::BaseField>, HashFn>::new(air, &certificate)?;
+            CertifiedCommitmentPhase::Quadratic(verify_commitment_phase(air, periodic_column_polys, &mut channel, &mut public_coin)?)
+        },
+        FieldExtension::Cubic => {
+            if !<CubeExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(3));
+            }
+            let mut channel = CertificateChannel::<CubeExtension<AIR::BaseField>, HashFn>::new(air, &certificate)?;
+            CertifiedCommitmentPhase::Cubic(verify_commitment_phase(air, periodic_column_polys, &mut channel, &mut public_coin)?)
+        },
+    };
+
+    // draw query positions at which the openings are expected, and record them together with
+    // the commitments against which the openings must be valid
+    let (query_positions, column_group_query_positions) =
+        draw_query_positions(air, &mut public_coin, commitment_phase.pow_nonce())?;
+    let (trace_roots, column_group_root, constraint_root, fri_roots) =
+        parse_commitments::<AIR::BaseField, HashFn, AIR>(air, certificate.commitments.clone())?;
+    let opening_request = OpeningRequest {
+        trace_roots,
+        column_group_root,
+        constraint_root,
+        fri_roots,
+        query_positions,
+        column_group_query_positions,
+    };
+
+    Ok(CertifiedProof::new(prepared, certificate, commitment_phase, opening_request))
+}
+
+/// Instantiates the AIR for the computation specified in the proof, verifies the proof against it,
+/// and, if the verification is successful, returns the value extracted from the AIR via `extract`.
+fn verify_and_map<AIR, HashFn, AuxHashFn, RandCoin, T, F>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    extract: F,
+) -> Result<T, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    AuxHashFn: ElementHasher<BaseField = AIR::BaseField> + Hasher<Digest = HashFn::Digest>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    F: FnOnce(&AIR) -> Result<T, VerifierError>,
 {
     // build a seed for the public coin; the initial seed is a hash of the proof context and the
     // public inputs, but as the protocol progresses, the coin will be reseeded with the info
-    // received from the prover
-    let mut public_coin_seed = proof.context.to_elements();
-    public_coin_seed.append(&mut pub_inputs.to_elements());
-    
-    // create AIR instance for the computation specified in the proof
+    // received from the prover. if the AIR commits to public inputs, the commitment is recomputed
+    // here and the coin is reseeded with it right after instantiation.
+    let pub_inputs_commitment = AIR::get_pub_inputs_commitment::<HashFn>(&pub_inputs);
+    let pub_inputs_elements = if pub_inputs_commitment.is_none() {
+        pub_inputs.to_elements()
+    } else {
+        Vec::new()
+    };
+
+    // create AIR instance for the computation specified in the proof; the domains implied by the
+    // proof are checked first, as AIR instantiation panics for domains not supported by the field
+    check_domain_size::<AIR::BaseField>(&proof.get_trace_info(), proof.options())?;
     let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let periodic_column_polys = air.get_periodic_column_polys();
+
+    verify_with_air::<AIR, HashFn, AuxHashFn, RandCoin>(
+        &air,
+        &periodic_column_polys,
+        proof,
+        &pub_inputs_elements,
+        pub_inputs_commitment,
+    )?;
+
+    extract(&air)
+}
+
+/// Verifies the proof against the provided AIR instance; the public coin is seeded with the proof
+/// context and the provided public input elements, and is then reseeded with the commitment to
+/// public inputs (if any).
+#[rustfmt::skip]
+fn verify_with_air<AIR, HashFn, AuxHashFn, RandCoin>(
+    air: &AIR,
+    periodic_column_polys: &[Vec<AIR::BaseField>],
+    proof: StarkProof,
+    pub_inputs_elements: &[AIR::BaseField],
+    pub_inputs_commitment: Option<HashFn::Digest>,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    AuxHashFn: ElementHasher<BaseField = AIR::BaseField> + Hasher<Digest = HashFn::Digest>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let mut public_coin_seed = proof.context.to_elements();
+    public_coin_seed.extend_from_slice(pub_inputs_elements);
 
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => {
-            let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(air, channel, public_coin)
+            let public_coin = build_public_coin::<RandCoin>(&public_coin_seed, pub_inputs_commitment);
+            let channel = DefaultVerifierChannel::<_, HashFn, AuxHashFn>::new(air, proof)?;
+            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin, _>(air, periodic_column_polys, channel, public_coin)
         },
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(2));
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            let public_coin = build_public_coin::<RandCoin>(&public_coin_seed, pub_inputs_commitment);
+            let channel = DefaultVerifierChannel::<_, HashFn, AuxHashFn>::new(air, proof)?;
+            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin, _>(air, periodic_column_polys, channel, public_coin)
         },
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(3));
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            let public_coin = build_public_coin::<RandCoin>(&public_coin_seed, pub_inputs_commitment);
+            let channel = DefaultVerifierChannel::<_, HashFn, AuxHashFn>::new(air, proof)?;
+            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin, _>(air, periodic_column_polys, channel, public_coin)
         },
     }
 }
@@ -129,9 +459,28 @@ where
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H, R>(
-    air: A,
-    mut channel: VerifierChannel<E, H>,
+///
+/// This is the procedure used by [verify()] with a [DefaultVerifierChannel]; it is exposed so that
+/// proofs can be verified against data provided by custom implementations of the
+/// [VerifierChannel] trait. In this case, the caller is responsible for:
+/// * Instantiating the `air` from the trace info, public inputs, and proof options of the proof,
+///   and computing `periodic_column_polys` via [Air::get_periodic_column_polys()].
+/// * Selecting the field `E` in accordance with the field extension specified by proof options.
+/// * Seeding the `public_coin` with the elements of the proof context followed by the elements
+///   of the public inputs (or, if the AIR commits to public inputs, reseeding the coin with the
+///   commitment to public inputs right after instantiation).
+///
+/// # Errors
+/// Returns an error if the data read from the `channel` does not attest to a correct execution
+/// of the computation specified by the `air`.
+///
+/// # Panics
+/// Panics if the degree of the extension field `E` is not the same as the field extension
+/// degree specified by proof options of the `air`.
+pub fn perform_verification<A, E, H, R, C>(
+    air: &A,
+    periodic_column_polys: &[Vec<A::BaseField>],
+    mut channel: C,
     mut public_coin: R,
 ) -> Result<(), VerifierError>
 where
@@ -139,6 +488,206 @@ where
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
     R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    C: VerifierChannel<E, Hasher = H>,
+{
+    assert_eq!(
+        E::EXTENSION_DEGREE,
+        air.options().field_extension().degree() as usize,
+        "extension field degree does not match field extension specified by proof options"
+    );
+
+    // 1 - 5 ----- commitment phase ----------------------------------------------------------------
+    // read and check all commitments and out-of-domain evaluations, and verify the proof-of-work;
+    // see verify_commitment_phase() for details
+    let commitment_phase =
+        verify_commitment_phase(air, periodic_column_polys, &mut channel, &mut public_coin)?;
+
+    // 6 ----- trace and constraint queries -------------------------------------------------------
+    // draw pseudo-random query positions for the LDE domain from the public coin; in the
+    // interactive version of the protocol, the verifier sends these query positions to the prover,
+    // and the prover responds with decommitments against these positions for trace and constraint
+    // composition polynomial evaluations. if the commitment to the first FRI layer is skipped,
+    // the positions are drawn from the domain of the second FRI layer, and the prover responds
+    // with decommitments against all positions in the LDE domain which fold into them.
+    let (query_positions, column_group_positions) =
+        draw_query_positions(air, &mut public_coin, commitment_phase.pow_nonce)?;
+
+    // 7 - 8 ----- query phase --------------------------------------------------------------------
+    // read and check the openings at the query positions; see verify_query_phase() for details
+    verify_query_phase(
+        air,
+        &commitment_phase,
+        &query_positions,
+        column_group_positions.as_deref(),
+        &mut channel,
+    )
+}
+
+/// Draws query positions for the LDE domain and, if the computation defines a column group, for
+/// the column group domain from the provided public coin.
+///
+/// If the commitment to the first FRI layer is skipped, positions are drawn from the domain of
+/// the second FRI layer and then unfolded into all positions of the LDE domain which fold into
+/// them. Positions for the column group domain are drawn right after the positions for the LDE
+/// domain.
+fn draw_query_positions<A, R>(
+    air: &A,
+    public_coin: &mut R,
+    pow_nonce: u64,
+) -> Result<(Vec<usize>, Option<Vec<usize>>), VerifierError>
+where
+    A: Air,
+    R: RandomCoin<BaseField = A::BaseField>,
+{
+    let options = air.options();
+    let query_positions = if options.skip_first_fri_layer_commitment() {
+        let folding_factor = options.to_fri_options().folding_factor();
+        let lde_domain_size = air.lde_domain_size();
+        let positions = public_coin
+            .draw_integers(options.num_queries(), lde_domain_size / folding_factor, pow_nonce)
+            .map_err(|_| VerifierError::RandomCoinError)?;
+        fri::folding::unfold_positions(&positions, lde_domain_size, folding_factor)
+    } else {
+        public_coin
+            .draw_integers(options.num_queries(), air.lde_domain_size(), pow_nonce)
+            .map_err(|_| VerifierError::RandomCoinError)?
+    };
+
+    let context = air.context();
+    let column_group_positions =
+        match (context.num_column_group_queries(), context.column_group_domain_size()) {
+            (Some(num_queries), Some(domain_size)) => Some(
+                public_coin
+                    .draw_integers(num_queries, domain_size, pow_nonce)
+                    .map_err(|_| VerifierError::RandomCoinError)?,
+            ),
+            _ => None,
+        };
+
+    Ok((query_positions, column_group_positions))
+}
+
+/// Verifies query openings read from the `channel` at the specified query positions against the
+/// results of the commitment phase of the protocol.
+fn verify_query_phase<A, E, H, R, C>(
+    air: &A,
+    commitment_phase: &CommitmentPhase<E, C, H, R>,
+    query_positions: &[usize],
+    column_group_positions: Option<&[usize]>,
+    channel: &mut C,
+) -> Result<(), VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    C: VerifierChannel<E, Hasher = H>,
+{
+    let CommitmentPhase {
+        z,
+        deep_coefficients,
+        ood_main_trace_frame,
+        ood_aux_trace_frame,
+        ood_constraint_evaluations,
+        fri_verifier,
+        column_group_fri_verifier,
+        ..
+    } = commitment_phase;
+
+    // read evaluations of trace and constraint composition polynomials at the queried positions;
+    // this also checks that the read values are valid against trace and constraint commitments
+    let (queried_main_trace_states, queried_aux_trace_states) =
+        channel.read_queried_trace_states(query_positions)?;
+    let queried_constraint_evaluations = channel.read_constraint_evaluations(query_positions)?;
+
+    // 7 ----- DEEP composition -------------------------------------------------------------------
+    // if the computation defines a column group, read the column group states at the queried
+    // positions of the column group domain, and compute evaluations of the DEEP composition
+    // polynomial of the column group at these positions
+    let column_group_evaluations = match column_group_positions {
+        Some(positions) => {
+            let queried_states = channel.read_queried_column_group_states(positions)?;
+            let composer =
+                DeepComposer::for_column_group(air, positions, *z, deep_coefficients.clone());
+            Some(composer.compose_trace_columns(
+                queried_states,
+                None,
+                ood_main_trace_frame.clone(),
+                None,
+            ))
+        }
+        None => None,
+    };
+
+    // compute evaluations of the DEEP composition polynomial at the queried positions
+    let composer = DeepComposer::new(air, query_positions, *z, deep_coefficients.clone());
+    let t_composition = composer.compose_trace_columns(
+        queried_main_trace_states,
+        queried_aux_trace_states,
+        ood_main_trace_frame.clone(),
+        ood_aux_trace_frame.clone(),
+    );
+    let c_composition = composer.compose_constraint_evaluations(
+        queried_constraint_evaluations,
+        ood_constraint_evaluations.clone(),
+    );
+    let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
+
+    // 8 ----- Verify low-degree proof ------------------------------------------------------------
+    // make sure that evaluations of the DEEP composition polynomial we computed in the previous
+    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
+    fri_verifier
+        .verify(channel, &deep_evaluations, query_positions)
+        .map_err(VerifierError::FriVerificationFailed)?;
+
+    // do the same for the DEEP composition polynomial of the column group (if any)
+    if let (Some(fri_verifier), Some(evaluations), Some(positions)) =
+        (column_group_fri_verifier, column_group_evaluations, column_group_positions)
+    {
+        fri_verifier
+            .verify(channel, &evaluations, positions)
+            .map_err(VerifierError::FriVerificationFailed)?;
+    }
+
+    Ok(())
+}
+
+/// Values derived during the commitment phase of verification which are needed to verify query
+/// openings.
+struct CommitmentPhase<E, C, H, R>
+where
+    E: FieldElement,
+    C: FriVerifierChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    z: E,
+    deep_coefficients: DeepCompositionCoefficients<E>,
+    ood_main_trace_frame: EvaluationFrame<E>,
+    ood_aux_trace_frame: Option<EvaluationFrame<E>>,
+    ood_constraint_evaluations: Vec<E>,
+    fri_verifier: FriVerifier<E, C, H, R>,
+    column_group_fri_verifier: Option<FriVerifier<E, C, H, R>>,
+    pow_nonce: u64,
+}
+
+/// Verifies the commitment phase of the protocol: reads all commitments and out-of-domain
+/// evaluations from the `channel`, checks consistency of out-of-domain evaluations, and verifies
+/// the proof-of-work for the query seed. Query openings are not read from the channel; they are
+/// read during the query phase from a channel of type `C`.
+fn verify_commitment_phase<A, E, H, R, CC, C>(
+    air: &A,
+    periodic_column_polys: &[Vec<A::BaseField>],
+    channel: &mut CC,
+    public_coin: &mut R,
+) -> Result<CommitmentPhase<E, C, H, R>, VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    CC: CommitmentChannel<E, H>,
+    C: FriVerifierChannel<E, Hasher = H>,
 {
     // 1 ----- trace commitment -------------------------------------------------------------------
     // Read the commitments to evaluations of the trace polynomials over the LDE domain sent by the
@@ -149,24 +698,42 @@ where
     // used to draw random elements needed to construct the next trace segment. The last trace
     // commitment is used to draw a set of random coefficients which the prover uses to compute
     // constraint composition polynomial.
-    let trace_commitments = channel.read_trace_commitments();
+    // Additional protocol messages (if any) are absorbed into the coin right after each trace
+    // commitment.
+    let trace_commitments = channel.read_trace_commitments().to_vec();
 
     // reseed the coin with the commitment to the main trace segment
     public_coin.reseed(trace_commitments[0]);
 
+    // if the computation defines a column group, reseed the coin with the commitment to the
+    // column group; the prover sends this commitment right after the main trace commitment
+    if air.context().column_group().is_some() {
+        let column_group_commitment = channel.read_column_group_commitment().ok_or_else(|| {
+            VerifierError::ProofDeserializationError(
+                "column group commitment is missing".to_string(),
+            )
+        })?;
+        public_coin.reseed(column_group_commitment);
+    }
+    channel.absorb_trace_segment_messages(0, public_coin)?;
+
     // process auxiliary trace segments (if any), to build a set of random elements for each segment
     let mut aux_trace_rand_elements = AuxTraceRandElements::<E>::new();
     for (i, commitment) in trace_commitments.iter().skip(1).enumerate() {
         let rand_elements = air
-            .get_aux_trace_segment_random_elements(i, &mut public_coin)
+            .get_aux_trace_segment_random_elements(i, public_coin)
             .map_err(|_| VerifierError::RandomCoinError)?;
+        if rand_elements.contains(&E::ZERO) {
+            return Err(VerifierError::ZeroAuxRandElement(i));
+        }
         aux_trace_rand_elements.add_segment_elements(rand_elements);
         public_coin.reseed(*commitment);
+        channel.absorb_trace_segment_messages(i + 1, public_coin)?;
     }
 
     // build random coefficients for the composition polynomial
     let constraint_coeffs = air
-        .get_constraint_composition_coefficients(&mut public_coin)
+        .get_constraint_composition_coefficients(public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 2 ----- constraint commitment --------------------------------------------------------------
@@ -174,10 +741,17 @@ where
     // domain sent by the prover, use it to update the public coin, and draw an out-of-domain point
     // z from the coin; in the interactive version of the protocol, the verifier sends this point z
     // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
-    // and sends the results back to the verifier.
+    // and sends the results back to the verifier. points at which constraint divisors vanish or
+    // DEEP quotients are undefined are rejected and redrawn by the AIR; we also make sure that
+    // the resulting point is valid, in case the AIR overrides how the point is drawn.
     let constraint_commitment = channel.read_constraint_commitment();
     public_coin.reseed(constraint_commitment);
-    let z = public_coin.draw::<E>().map_err(|_| VerifierError::RandomCoinError)?;
+    let z = air
+        .get_ood_point::<E, _>(public_coin)
+        .map_err(|_| VerifierError::RandomCoinError)?;
+    if !air.context().is_valid_ood_point(z) {
+        return Err(VerifierError::InvalidOodPoint);
+    }
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
     // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
@@ -189,8 +763,9 @@ where
     let ood_trace_frame = channel.read_ood_trace_frame();
     let ood_main_trace_frame = ood_trace_frame.main_frame();
     let ood_aux_trace_frame = ood_trace_frame.aux_frame();
-    let ood_constraint_evaluation_1 = evaluate_constraints(
-        &air,
+    let ood_constraint_evaluation_1 = evaluate_constraints_with_periodic_polys(
+        air,
+        periodic_column_polys,
         constraint_coeffs,
         &ood_main_trace_frame,
         &ood_aux_trace_frame,
@@ -206,14 +781,16 @@ where
     // the evaluation of the composition polynomial at z) using the fact that
     // H(X) = \sum_{i=0}^{m-1} X^{i * l} H_i(X).
     // Also, reseed the public coin with the OOD constraint evaluations received from the prover.
+    // If OOD constraint evaluations are combined, the prover sends H(z) directly.
     let ood_constraint_evaluations = channel.read_ood_constraint_evaluations();
-    let ood_constraint_evaluation_2 =
-        ood_constraint_evaluations
-            .iter()
-            .enumerate()
-            .fold(E::ZERO, |result, (i, &value)| {
-                result + z.exp_vartime(((i * (air.trace_length())) as u32).into()) * value
-            });
+    let ood_constraint_evaluation_2 = match air.options().ood_constraint_batching() {
+        OodConstraintBatching::PerColumn => deep::combine_composition_evaluations(
+            &ood_constraint_evaluations,
+            z,
+            air.trace_length(),
+        ),
+        OodConstraintBatching::Combined => ood_constraint_evaluations[0],
+    };
     public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
 
     // finally, make sure the values are the same
@@ -227,7 +804,7 @@ where
     // and the prover uses them to compute the DEEP composition polynomial. the prover, then
     // applies FRI protocol to the evaluations of the DEEP composition polynomial.
     let deep_coefficients = air
-        .get_deep_composition_coefficients::<E, R>(&mut public_coin)
+        .get_deep_composition_coefficients::<E, R>(public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
@@ -235,16 +812,32 @@ where
     // The verifier uses these commitments to update the public coin and draw random points alpha
     // from them; in the interactive version of the protocol, the verifier sends these alphas to
     // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
-    let fri_verifier = FriVerifier::new(
-        &mut channel,
-        &mut public_coin,
+    let fri_verifier = FriVerifier::from_commitments(
+        channel.read_fri_commitments(),
+        public_coin,
         air.options().to_fri_options(),
         air.trace_poly_degree(),
     )
     .map_err(VerifierError::FriVerificationFailed)?;
     // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
 
-    // 5 ----- trace and constraint queries -------------------------------------------------------
+    // if the computation defines a column group, instantiate a separate FRI verifier for the
+    // DEEP composition polynomial of the column group; layer commitments of this FRI instance
+    // follow the layer commitments of the main FRI instance
+    let column_group_fri_verifier = match air.context().column_group_fri_options() {
+        Some(fri_options) => Some(
+            FriVerifier::from_commitments(
+                channel.read_fri_commitments(),
+                public_coin,
+                fri_options,
+                air.trace_poly_degree(),
+            )
+            .map_err(VerifierError::FriVerificationFailed)?,
+        ),
+        None => None,
+    };
+
+    // 5 ----- query seed proof-of-work ------------------------------------------------------------
     // read proof-of-work nonce sent by the prover
     let pow_nonce = channel.read_pow_nonce();
 
@@ -253,37 +846,44 @@ where
         return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
     }
 
-    // draw pseudo-random query positions for the LDE domain from the public coin; in the
-    // interactive version of the protocol, the verifier sends these query positions to the prover,
-    // and the prover responds with decommitments against these positions for trace and constraint
-    // composition polynomial evaluations.
-    let query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size(), pow_nonce)
-        .map_err(|_| VerifierError::RandomCoinError)?;
-
-    // read evaluations of trace and constraint composition polynomials at the queried positions;
-    // this also checks that the read values are valid against trace and constraint commitments
-    let (queried_main_trace_states, queried_aux_trace_states) =
-        channel.read_queried_trace_states(&query_positions)?;
-    let queried_constraint_evaluations = channel.read_constraint_evaluations(&query_positions)?;
-
-    // 6 ----- DEEP composition -------------------------------------------------------------------
-    // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
-    let t_composition = composer.compose_trace_columns(
-        queried_main_trace_states,
-        queried_aux_trace_states,
+    Ok(CommitmentPhase {
+        z,
+        deep_coefficients,
         ood_main_trace_frame,
         ood_aux_trace_frame,
-    );
-    let c_composition = composer
-        .compose_constraint_evaluations(queried_constraint_evaluations, ood_constraint_evaluations);
-    let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
+        ood_constraint_evaluations,
+        fri_verifier,
+        column_group_fri_verifier,
+        pow_nonce,
+    })
+}
 
-    // 7 ----- Verify low-degree proof -------------------------------------------------------------
-    // make sure that evaluations of the DEEP composition polynomial we computed in the previous
-    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
-    fri_verifier
-        .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an error if the LDE domain implied by the specified trace info and proof options is
+/// larger than the largest multiplicative subgroup of size $2^k$ in the base field `B`.
+fn check_domain_size<B: StarkField>(
+    trace_info: &TraceInfo,
+    options: &ProofOptions,
+) -> Result<(), VerifierError> {
+    let domain_size = trace_info.length().saturating_mul(options.blowup_factor());
+    let max_domain_size = 1_usize.checked_shl(B::TWO_ADICITY).unwrap_or(usize::MAX);
+    if domain_size > max_domain_size {
+        return Err(VerifierError::DomainTooLarge(domain_size, max_domain_size));
+    }
+    Ok(())
+}
+
+/// Instantiates a public coin from the provided seed; if a commitment to public inputs is
+/// provided, the coin is reseeded with it.
+fn build_public_coin<R: RandomCoin>(
+    seed: &[R::BaseField],
+    pub_inputs_commitment: Option<<R::Hasher as Hasher>::Digest>,
+) -> R {
+    let mut public_coin = R::new(seed);
+    if let Some(commitment) = pub_inputs_commitment {
+        public_coin.reseed(commitment);
+    }
+    public_coin
 }
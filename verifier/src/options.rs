@@ -0,0 +1,110 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::VerifierError;
+use air::{proof::StarkProof, ProofOptions};
+use crypto::Hasher;
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// ACCEPTABLE OPTIONS
+// ================================================================================================
+
+/// Defines a policy for proof options which a verifier is willing to accept.
+///
+/// A policy can be checked against a proof via [AcceptableOptions::validate()] before the proof
+/// is verified. Policies can be serialized, and thus, they can be distributed via configuration
+/// files and audited rather than being hardcoded at each call site.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum AcceptableOptions {
+    /// Proofs with conjectured security level of at least the specified number of bits are
+    /// accepted.
+    MinConjecturedSecurity(u32),
+    /// Proofs with proven security level of at least the specified number of bits are accepted.
+    MinProvenSecurity(u32),
+    /// Only proofs generated using one of the specified sets of proof options are accepted.
+    OptionSet(Vec<ProofOptions>),
+}
+
+impl AcceptableOptions {
+    /// Checks that the `proof` satisfies this policy assuming the proof was generated using hash
+    /// function `H`.
+    ///
+    /// # Errors
+    /// Returns an error if the security level of the proof is below the minimum required by this
+    /// policy, or if the proof was generated using options which are not in the accepted set.
+    pub fn validate<H: Hasher>(&self, proof: &StarkProof) -> Result<(), VerifierError> {
+        match self {
+            Self::MinConjecturedSecurity(min_security) => {
+                let security = proof.security_level::<H>(true);
+                if security < *min_security {
+                    return Err(VerifierError::InsufficientConjecturedSecurity(
+                        *min_security,
+                        security,
+                    ));
+                }
+            }
+            Self::MinProvenSecurity(min_security) => {
+                let security = proof.security_level::<H>(false);
+                if security < *min_security {
+                    return Err(VerifierError::InsufficientProvenSecurity(*min_security, security));
+                }
+            }
+            Self::OptionSet(options) => {
+                if !options.contains(proof.options()) {
+                    return Err(VerifierError::UnacceptableProofOptions);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serializable for AcceptableOptions {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::MinConjecturedSecurity(min_security) => {
+                target.write_u8(0);
+                target.write_u32(*min_security);
+            }
+            Self::MinProvenSecurity(min_security) => {
+                target.write_u8(1);
+                target.write_u32(*min_security);
+            }
+            Self::OptionSet(options) => {
+                target.write_u8(2);
+                assert!(options.len() <= u16::MAX as usize, "too many acceptable proof options");
+                target.write_u16(options.len() as u16);
+                options.write_into(target);
+            }
+        }
+    }
+}
+
+impl Deserializable for AcceptableOptions {
+    /// Reads acceptable options from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid policy could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::MinConjecturedSecurity(source.read_u32()?)),
+            1 => Ok(Self::MinProvenSecurity(source.read_u32()?)),
+            2 => {
+                let num_options = source.read_u16()? as usize;
+                Ok(Self::OptionSet(ProofOptions::read_batch_from(source, num_options)?))
+            }
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as AcceptableOptions enum"
+            ))),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::Assertion;
+use math::StarkField;
+use utils::collections::{BTreeMap, Vec};
+
+// PUBLIC OUTPUTS
+// ================================================================================================
+
+/// Defines how public outputs of a computation are extracted from values asserted against cells
+/// of the main trace segment.
+///
+/// This is used by [verify_and_extract()](crate::verify_and_extract()) to return the outputs of a
+/// computation in a typed form once a proof for the computation has been verified.
+pub trait PublicOutputs<B: StarkField>: Sized {
+    /// Builds public outputs from the specified asserted values; returns `None` if any of the
+    /// outputs are not asserted.
+    fn from_asserted_values(values: &AssertedValues<B>) -> Option<Self>;
+}
+
+// ASSERTED VALUES
+// ================================================================================================
+
+/// Values asserted against individual cells of the main trace segment.
+///
+/// These are derived from the assertions returned by
+/// [Air::get_assertions()](air::Air::get_assertions()), and thus, once a proof has been verified,
+/// the execution trace of the computation is guaranteed to contain these values.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AssertedValues<B: StarkField> {
+    trace_length: usize,
+    values: BTreeMap<(usize, usize), B>,
+}
+
+impl<B: StarkField> AssertedValues<B> {
+    /// Returns values asserted by the specified assertions against an execution trace of the
+    /// specified length.
+    ///
+    /// # Panics
+    /// Panics if the trace length is not valid for any of the assertions.
+    pub fn new(assertions: &[Assertion<B>], trace_length: usize) -> Self {
+        let mut values = BTreeMap::new();
+        for assertion in assertions {
+            assertion.apply(trace_length, |step, value| {
+                values.insert((assertion.column(), step), value);
+            });
+        }
+        AssertedValues {
+            trace_length,
+            values,
+        }
+    }
+
+    /// Returns the length of the execution trace against which the values were asserted.
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    /// Returns the value asserted against the specified `column` at the specified `step`, or
+    /// `None` if no value was asserted against this cell.
+    pub fn get(&self, column: usize, step: usize) -> Option<B> {
+        self.values.get(&(column, step)).copied()
+    }
+
+    /// Returns the value asserted against the specified `column` at the last step of the
+    /// execution trace, or `None` if no value was asserted against this cell.
+    pub fn get_last(&self, column: usize) -> Option<B> {
+        self.get(column, self.trace_length - 1)
+    }
+
+    /// Returns values asserted against the specified `column` together with the steps at which
+    /// they were asserted; the values are sorted by step.
+    pub fn get_column(&self, column: usize) -> Vec<(usize, B)> {
+        self.values
+            .range((column, 0)..(column + 1, 0))
+            .map(|(&(_, step), &value)| (step, value))
+            .collect()
+    }
+}
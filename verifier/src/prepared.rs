@@ -0,0 +1,83 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{Air, ProofOptions, TraceInfo};
+use core::marker::PhantomData;
+use crypto::ElementHasher;
+use math::ToElements;
+use utils::collections::Vec;
+
+// PREPARED AIR
+// ================================================================================================
+
+/// An AIR instance prepared for verifying multiple proofs of the same computation.
+///
+/// A prepared AIR holds an instance of `A` together with values derived from it and from the
+/// public inputs which would otherwise be recomputed for every proof: polynomials of periodic
+/// columns, and either elements of the public inputs or a commitment to them (see
+/// [Air::get_pub_inputs_commitment()]).
+///
+/// A prepared AIR can be used to verify any number of proofs via
+/// [verify_prepared()](crate::verify_prepared) as long as the proofs were generated for the same
+/// trace info, public inputs, and proof options.
+pub struct PreparedAir<A: Air, H: ElementHasher<BaseField = A::BaseField>> {
+    air: A,
+    periodic_column_polys: Vec<Vec<A::BaseField>>,
+    pub_inputs_elements: Vec<A::BaseField>,
+    pub_inputs_commitment: Option<H::Digest>,
+    _hasher: PhantomData<H>,
+}
+
+impl<A: Air, H: ElementHasher<BaseField = A::BaseField>> PreparedAir<A, H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new AIR instance for the specified trace info, public inputs, and proof options
+    /// together with values derived from it.
+    pub fn new(trace_info: TraceInfo, pub_inputs: A::PublicInputs, options: ProofOptions) -> Self {
+        let pub_inputs_commitment = A::get_pub_inputs_commitment::<H>(&pub_inputs);
+        let pub_inputs_elements = if pub_inputs_commitment.is_none() {
+            pub_inputs.to_elements()
+        } else {
+            Vec::new()
+        };
+
+        let air = A::new(trace_info, pub_inputs, options);
+        let periodic_column_polys = air.get_periodic_column_polys();
+
+        Self {
+            air,
+            periodic_column_polys,
+            pub_inputs_elements,
+            pub_inputs_commitment,
+            _hasher: PhantomData,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the underlying AIR instance.
+    pub fn air(&self) -> &A {
+        &self.air
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns polynomials of all periodic columns of the AIR.
+    pub(crate) fn periodic_column_polys(&self) -> &[Vec<A::BaseField>] {
+        &self.periodic_column_polys
+    }
+
+    /// Returns public inputs as field elements; this is empty if the AIR commits to public inputs.
+    pub(crate) fn pub_inputs_elements(&self) -> &[A::BaseField] {
+        &self.pub_inputs_elements
+    }
+
+    /// Returns the commitment to public inputs, if the AIR commits to them.
+    pub(crate) fn pub_inputs_commitment(&self) -> Option<H::Digest> {
+        self.pub_inputs_commitment
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_options, build_trace, Blake3, FibAir, FibProver};
+use crate::{FieldExtension, ProofCertificate, ProofOpenings, ProofOptions, VerifierError};
+use crypto::DefaultRandomCoin;
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::Prover;
+
+type RandCoin = DefaultRandomCoin<Blake3>;
+
+#[test]
+fn verify_split_proof() {
+    // use non-zero grinding factor so that the proof-of-work can be checked eagerly
+    let options = ProofOptions::new(28, 8, 8, FieldExtension::Quadratic, 4, 7);
+    let prover = FibProver::<FibAir>::new(options);
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    let other_proof = prover.prove(build_trace(32)).unwrap();
+
+    // both parts of the proof can be serialized and transported separately
+    let (certificate, openings) = proof.split();
+    let certificate = ProofCertificate::from_bytes(&certificate.to_bytes()).unwrap();
+    let openings = ProofOpenings::from_bytes(&openings.to_bytes()).unwrap();
+
+    // the certificate is verified eagerly, and the openings are verified later
+    let certified =
+        crate::verify_certificate::<FibAir, Blake3, RandCoin>(certificate.clone(), pub_inputs)
+            .unwrap();
+
+    // openings of a different proof are rejected, but do not prevent verifying valid openings
+    let (_, other_openings) = other_proof.split();
+    assert!(certified.verify_openings(other_openings).is_err());
+    assert_eq!(Ok(()), certified.verify_openings(openings));
+
+    // a certificate with an invalid proof-of-work or against wrong inputs is rejected eagerly
+    let mut bad_certificate = certificate.clone();
+    bad_certificate.pow_nonce += 1;
+    let result = crate::verify_certificate::<FibAir, Blake3, RandCoin>(bad_certificate, pub_inputs);
+    assert!(matches!(result, Err(VerifierError::QuerySeedProofOfWorkVerificationFailed)));
+
+    let result = crate::verify_certificate::<FibAir, Blake3, RandCoin>(
+        certificate,
+        pub_inputs + BaseElement::ONE,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn verify_openings_with_callback() {
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    let lde_domain_size = proof.lde_domain_size();
+
+    // only the certificate is sent to the verifier; openings are checked by another party
+    let (certificate, openings) = proof.split();
+    let certified =
+        crate::verify_certificate::<FibAir, Blake3, RandCoin>(certificate, pub_inputs).unwrap();
+
+    // query positions are derived from the certificate and are exposed to the callback
+    let positions = certified.query_positions().to_vec();
+    assert!(!positions.is_empty() && positions.len() <= 28);
+    assert!(positions.iter().all(|&p| p < lde_domain_size));
+    assert!(certified.column_group_query_positions().is_none());
+
+    // the callback is given the certified commitments and the expected positions, and the
+    // openings it returns are verified against them
+    let result = certified.verify_openings_with(|request| {
+        assert_eq!(certified.opening_request(), request);
+        assert_eq!(positions, request.query_positions);
+        assert!(request.column_group_root.is_none());
+        assert_eq!(1, request.fri_roots.len());
+        Ok(openings.clone())
+    });
+    assert_eq!(Ok(()), result);
+
+    // openings for a different proof are rejected
+    let other_proof = prover.prove(build_trace(32)).unwrap();
+    let other_openings = other_proof.split().1;
+    assert!(certified.verify_openings_with(|_| Ok(other_openings)).is_err());
+
+    // errors of the callback are propagated
+    let result = certified
+        .verify_openings_with(|_| Err(VerifierError::MainTraceQueryDoesNotMatchCommitment(0)));
+    assert_eq!(Err(VerifierError::MainTraceQueryDoesNotMatchCommitment(0)), result);
+}
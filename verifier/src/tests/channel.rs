@@ -0,0 +1,143 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_options, build_trace, Blake3, FibAir, FibProver, ForwardingChannel};
+use crate::{Air, CommitmentChannel, DefaultVerifierChannel, StarkProof, VerifierError};
+use crypto::{DefaultRandomCoin, Hasher, RandomCoin, SharedTranscript};
+use math::{fields::f128::BaseElement, ToElements};
+use prover::{Prover, TraceLde};
+
+type RandCoin = DefaultRandomCoin<Blake3>;
+
+// CUSTOM CHANNELS
+// ================================================================================================
+
+#[test]
+fn verify_with_custom_channel() {
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    assert_eq!(Ok(()), verify_with_message(proof, pub_inputs, None));
+}
+
+#[test]
+fn absorb_extra_messages() {
+    let message = Blake3::hash(b"auxiliary proof commitment");
+    let prover = FibProver::<FibAir>::new(build_options(false)).with_message(message);
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    // the default verifier does not absorb the extra message, and thus, rejects the proof
+    let result = crate::verify::<FibAir, Blake3, RandCoin>(proof.clone(), pub_inputs);
+    assert!(result.is_err());
+
+    // a verifier channel absorbing the same message at the same point accepts the proof
+    assert_eq!(Ok(()), verify_with_message(proof.clone(), pub_inputs, Some(message)));
+    let other_message = Blake3::hash(b"other commitment");
+    assert!(verify_with_message(proof, pub_inputs, Some(other_message)).is_err());
+}
+
+#[test]
+fn verify_shared_transcript() {
+    // two provers prove different computations which are parts of the same statement; main
+    // trace commitments of both provers are aggregated by a coordinator
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let traces = [build_trace(64), build_trace(128)];
+    let pending = traces
+        .iter()
+        .map(|trace| prover.prove_phase1::<BaseElement>(trace.clone()).unwrap())
+        .collect::<Vec<_>>();
+    let mut transcript = SharedTranscript::<Blake3>::new(pending.len());
+    for (i, pending) in pending.iter().enumerate().rev() {
+        transcript.contribute(i, pending.main_trace_commitment()).unwrap();
+    }
+    let aggregate = transcript.finalize().unwrap();
+
+    // each prover binds the aggregate commitment to its transcript
+    let proofs = pending
+        .into_iter()
+        .map(|pending| prover.prove_phase2(pending, &[aggregate]).unwrap())
+        .collect::<Vec<_>>();
+
+    // the verifier re-computes the aggregate commitment from trace commitments of all proofs,
+    // and absorbs it into the transcript of each proof
+    let commitments = traces
+        .iter()
+        .zip(&proofs)
+        .map(|(trace, proof)| {
+            let pub_inputs = prover.get_pub_inputs(trace);
+            let air = <FibAir>::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+            let channel: DefaultVerifierChannel<BaseElement, Blake3> =
+                DefaultVerifierChannel::new(&air, proof.clone()).unwrap();
+            channel.read_trace_commitments()[0]
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(aggregate, SharedTranscript::<Blake3>::aggregate(&commitments));
+
+    for (trace, proof) in traces.iter().zip(proofs) {
+        let pub_inputs = prover.get_pub_inputs(trace);
+        assert_eq!(Ok(()), verify_with_message(proof, pub_inputs, Some(aggregate)));
+    }
+}
+
+// TRACE OPENINGS
+// ================================================================================================
+
+#[test]
+fn verify_trace_openings() {
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let trace = build_trace(64);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let (proof, trace_lde) = prover.prove_with_trace_lde::<BaseElement>(trace).unwrap();
+
+    let air = <FibAir>::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let channel = DefaultVerifierChannel::<BaseElement, Blake3, Blake3>::new(&air, proof).unwrap();
+
+    // open the trace commitment at positions which were not chosen by the verifier
+    assert_eq!(256, air.lde_domain_size());
+    let positions = [3, 100, 17, 255];
+    let (main_states, aux_states) = channel
+        .verify_trace_openings(&air, &positions, trace_lde.query(&positions))
+        .unwrap();
+    assert_eq!(positions.len(), main_states.num_rows());
+    assert_eq!(air.trace_layout().main_trace_width(), main_states.num_columns());
+    assert!(aux_states.is_none());
+
+    // openings at other positions are rejected
+    let result =
+        channel.verify_trace_openings(&air, &[3, 101, 17, 255], trace_lde.query(&positions));
+    assert!(matches!(result, Err(VerifierError::MainTraceQueryDoesNotMatchCommitment(_))));
+
+    // openings for a different number of positions are rejected
+    let result = channel.verify_trace_openings(&air, &positions[..2], trace_lde.query(&positions));
+    assert!(matches!(result, Err(VerifierError::ProofDeserializationError(_))));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Verifies the proof via a [ForwardingChannel] which absorbs the specified message after the main
+/// trace commitment; the public coin is seeded the same way as the default verifier does.
+fn verify_with_message(
+    proof: StarkProof,
+    pub_inputs: BaseElement,
+    message: Option<<Blake3 as Hasher>::Digest>,
+) -> Result<(), VerifierError> {
+    let air = <FibAir>::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let mut seed = proof.context.to_elements();
+    seed.append(&mut pub_inputs.to_elements());
+    let channel = ForwardingChannel {
+        inner: DefaultVerifierChannel::new(&air, proof).unwrap(),
+        message,
+    };
+    crate::perform_verification::<_, BaseElement, _, _, _>(
+        &air,
+        &air.get_periodic_column_polys(),
+        channel,
+        RandCoin::new(&seed),
+    )
+}
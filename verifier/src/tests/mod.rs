@@ -0,0 +1,322 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    Air, AirContext, AssertedValues, Assertion, AuxTraceRandElements, ColumnGroup,
+    CommitmentChannel, ConstraintCompositionCoefficients, DefaultVerifierChannel, EvaluationFrame,
+    FieldExtension, FriVerifierChannel, ProofOptions, PublicOutputs, RowHashStrategy, Table,
+    TraceInfo, TraceOodFrame, TransitionConstraintDegree, VerifierChannel, VerifierError,
+};
+use core::marker::PhantomData;
+use crypto::{hashers::Blake3_256, BatchMerkleProof, DefaultRandomCoin, ElementHasher, Hasher};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{
+    DefaultConstraintEvaluator, DefaultTraceLde, Prover, ProverChannel, Trace, TraceTable,
+};
+use utils::collections::Vec;
+
+mod certificate;
+mod channel;
+mod verification;
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// Fibonacci AIR for traces built by [build_trace()]: each row advances the sequence by two terms,
+/// and the last term of the sequence is the public input.
+///
+/// The context of the AIR can be customized: if `GROUP_BLOWUP` is not zero, the second trace
+/// column is placed into a column group with the specified blowup factor; if
+/// `COMPOSITION_COLUMNS` is not zero, the constraint composition polynomial is split into the
+/// specified number of columns; if `ROW_HASH_GROUP` or `ROW_HASH_TAG` is not zero, trace rows are
+/// hashed in groups of the specified number of columns, or are merged with the specified domain
+/// separation tag.
+pub struct FibAir<
+    const GROUP_BLOWUP: usize = 0,
+    const COMPOSITION_COLUMNS: usize = 0,
+    const ROW_HASH_GROUP: usize = 0,
+    const ROW_HASH_TAG: u64 = 0,
+> {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+/// Fibonacci AIR which commits to the second trace column at a lower blowup factor than the first
+/// one.
+pub type GroupedFibAir = FibAir<4>;
+
+/// Fibonacci AIR which splits the constraint composition polynomial into 4 columns.
+pub type PaddedFibAir = FibAir<0, 4>;
+
+/// Fibonacci AIR which hashes each trace column into a separate digest.
+pub type ColumnHashedFibAir = FibAir<0, 0, 1>;
+
+/// Fibonacci AIR which commits to the second trace column at a lower blowup factor, and merges
+/// digests of trace rows with a domain separation tag.
+pub type TaggedFibAir = FibAir<4, 0, 0, 7>;
+
+impl<
+        const GROUP_BLOWUP: usize,
+        const COMPOSITION_COLUMNS: usize,
+        const ROW_HASH_GROUP: usize,
+        const ROW_HASH_TAG: u64,
+    > Air for FibAir<GROUP_BLOWUP, COMPOSITION_COLUMNS, ROW_HASH_GROUP, ROW_HASH_TAG>
+{
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)];
+        let mut context = AirContext::new(trace_info, degrees, 3, options);
+        if GROUP_BLOWUP != 0 {
+            context = context.set_column_group(ColumnGroup::new(vec![1], GROUP_BLOWUP));
+        }
+        if COMPOSITION_COLUMNS != 0 {
+            context = context.set_num_constraint_composition_columns(COMPOSITION_COLUMNS);
+        }
+        if ROW_HASH_GROUP != 0 {
+            context = context.set_row_hash_strategy(RowHashStrategy::ColumnGroups(ROW_HASH_GROUP));
+        }
+        if ROW_HASH_TAG != 0 {
+            context = context.set_row_hash_strategy(RowHashStrategy::DomainSeparated(ROW_HASH_TAG));
+        }
+        FibAir { context, result }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+/// Public outputs of the Fibonacci computation.
+pub struct FibOutputs {
+    pub result: BaseElement,
+}
+
+impl PublicOutputs<BaseElement> for FibOutputs {
+    fn from_asserted_values(values: &AssertedValues<BaseElement>) -> Option<Self> {
+        Some(FibOutputs {
+            result: values.get_last(1)?,
+        })
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+/// Prover for [FibAir] which optionally absorbs an extra message into the channel after
+/// committing to the main trace.
+pub struct FibProver<A = FibAir, H: Hasher = Blake3> {
+    options: ProofOptions,
+    message: Option<H::Digest>,
+    _phantom: PhantomData<(A, H)>,
+}
+
+impl<A, H: Hasher> FibProver<A, H> {
+    pub fn new(options: ProofOptions) -> Self {
+        FibProver {
+            options,
+            message: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn with_message(mut self, message: H::Digest) -> Self {
+        self.message = Some(message);
+        self
+    }
+}
+
+impl<A, H> Prover for FibProver<A, H>
+where
+    A: Air<BaseField = BaseElement, PublicInputs = BaseElement> + 'static,
+    H: ElementHasher<BaseField = BaseElement> + 'static,
+{
+    type BaseField = BaseElement;
+    type Air = A;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn absorb_trace_segment_messages<E>(
+        &self,
+        _trace: &Self::Trace,
+        segment_idx: usize,
+        channel: &mut ProverChannel<Self::Air, E, Self::HashFn, Self::RandomCoin>,
+    ) where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        if let (0, Some(message)) = (segment_idx, self.message) {
+            channel.absorb(message);
+        }
+    }
+}
+
+// FORWARDING VERIFIER CHANNEL
+// ================================================================================================
+
+/// A verifier channel which forwards all reads to the default channel, and optionally absorbs an
+/// extra message after the main trace commitment.
+pub struct ForwardingChannel {
+    pub inner: DefaultVerifierChannel<BaseElement, Blake3>,
+    pub message: Option<<Blake3 as Hasher>::Digest>,
+}
+
+impl CommitmentChannel<BaseElement, Blake3> for ForwardingChannel {
+    fn read_trace_commitments(&self) -> &[<Blake3 as Hasher>::Digest] {
+        self.inner.read_trace_commitments()
+    }
+
+    fn read_constraint_commitment(&self) -> <Blake3 as Hasher>::Digest {
+        self.inner.read_constraint_commitment()
+    }
+
+    fn read_ood_trace_frame(&mut self) -> TraceOodFrame<BaseElement> {
+        self.inner.read_ood_trace_frame()
+    }
+
+    fn read_ood_constraint_evaluations(&mut self) -> Vec<BaseElement> {
+        self.inner.read_ood_constraint_evaluations()
+    }
+
+    fn read_fri_commitments(&mut self) -> Vec<<Blake3 as Hasher>::Digest> {
+        self.inner.read_fri_commitments()
+    }
+
+    fn read_pow_nonce(&self) -> u64 {
+        self.inner.read_pow_nonce()
+    }
+
+    fn absorb_trace_segment_messages<R>(
+        &mut self,
+        segment_idx: usize,
+        public_coin: &mut R,
+    ) -> Result<(), VerifierError>
+    where
+        R: crypto::RandomCoin<BaseField = BaseElement, Hasher = Blake3>,
+    {
+        if let (0, Some(message)) = (segment_idx, self.message) {
+            public_coin.reseed(message);
+        }
+        Ok(())
+    }
+}
+
+impl VerifierChannel<BaseElement> for ForwardingChannel {
+    fn read_queried_trace_states(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<(Table<BaseElement>, Option<Table<BaseElement>>), VerifierError> {
+        self.inner.read_queried_trace_states(positions)
+    }
+
+    fn read_constraint_evaluations(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<Table<BaseElement>, VerifierError> {
+        self.inner.read_constraint_evaluations(positions)
+    }
+}
+
+impl FriVerifierChannel<BaseElement> for ForwardingChannel {
+    type Hasher = Blake3;
+
+    fn read_fri_num_partitions(&self) -> usize {
+        self.inner.read_fri_num_partitions()
+    }
+
+    fn read_fri_layer_commitments(&mut self) -> Vec<<Blake3 as Hasher>::Digest> {
+        self.inner.read_fri_layer_commitments()
+    }
+
+    fn take_next_fri_layer_queries(&mut self) -> Vec<BaseElement> {
+        self.inner.take_next_fri_layer_queries()
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<Blake3> {
+        self.inner.take_next_fri_layer_proof()
+    }
+
+    fn take_fri_remainder(&mut self) -> Vec<BaseElement> {
+        self.inner.take_fri_remainder()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds an execution trace for computing a Fibonacci sequence of the specified length such that
+/// each row advances the sequence by 2 terms.
+pub fn build_trace(sequence_length: usize) -> TraceTable<BaseElement> {
+    assert!(sequence_length.is_power_of_two(), "sequence length must be a power of 2");
+
+    let mut trace = TraceTable::new(2, sequence_length / 2);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ONE;
+            state[1] = BaseElement::ONE;
+        },
+        |_, state| {
+            state[0] += state[1];
+            state[1] += state[0];
+        },
+    );
+    trace
+}
+
+pub fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 7)
+}
@@ -0,0 +1,320 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_options, build_trace, Blake3, ColumnHashedFibAir, FibAir, FibOutputs, FibProver,
+    GroupedFibAir, PaddedFibAir, TaggedFibAir,
+};
+use crate::{
+    AcceptableOptions, AggregateAir, AggregatePublicInputs, Air, PreparedAir, ProofCertificate,
+    ProofOpenings, StarkProof, VerifierError,
+};
+use crypto::{hashers::Truncated, DefaultRandomCoin};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{AggregateProver, Prover};
+use utils::{Deserializable, Serializable};
+
+type RandCoin = DefaultRandomCoin<Blake3>;
+
+// VERIFICATION
+// ================================================================================================
+
+#[test]
+fn verify_prepared() {
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    // the same prepared AIR can be used to verify several proofs
+    let prepared = PreparedAir::<FibAir, Blake3>::new(
+        proof.get_trace_info(),
+        pub_inputs,
+        proof.options().clone(),
+    );
+    for _ in 0..2 {
+        let result = crate::verify_prepared::<_, _, RandCoin>(&prepared, proof.clone());
+        assert_eq!(Ok(()), result);
+    }
+
+    // proofs against different public inputs are rejected
+    let wrong_inputs = PreparedAir::<FibAir, Blake3>::new(
+        proof.get_trace_info(),
+        pub_inputs + BaseElement::ONE,
+        proof.options().clone(),
+    );
+    let result = crate::verify_prepared::<_, _, RandCoin>(&wrong_inputs, proof);
+    assert!(result.is_err());
+
+    // proofs generated with different proof options are rejected before verification starts
+    let other_prover = FibProver::<FibAir>::new(build_options(true));
+    let other_proof = other_prover.prove(build_trace(16)).unwrap();
+    let result = crate::verify_prepared::<_, _, RandCoin>(&prepared, other_proof);
+    assert_eq!(Err(VerifierError::InconsistentProofContext), result);
+}
+
+#[test]
+fn verify_and_extract() {
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    let outputs = crate::verify_and_extract::<FibAir, Blake3, RandCoin, FibOutputs>(
+        proof.clone(),
+        pub_inputs,
+    )
+    .unwrap();
+    assert_eq!(pub_inputs, outputs.result);
+
+    // outputs are not extracted if the proof is not valid
+    let result = crate::verify_and_extract::<FibAir, Blake3, RandCoin, FibOutputs>(
+        proof,
+        pub_inputs + BaseElement::ONE,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn verify_with_altered_prover_id() {
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    // the prover ID is bound to the transcript, and thus, it cannot be added after the fact
+    let mut altered_proof = proof;
+    altered_proof.context = altered_proof.context.with_prover_id("winterfell-0.0");
+    let result = crate::verify::<FibAir, Blake3, RandCoin>(altered_proof, pub_inputs);
+    assert!(result.is_err());
+}
+
+#[test]
+fn acceptable_options() {
+    let options = build_options(false);
+    let proof = FibProver::<FibAir>::new(options.clone()).prove(build_trace(16)).unwrap();
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    let security = proof.security_level::<Blake3>(true);
+
+    let policy = AcceptableOptions::MinConjecturedSecurity(security);
+    assert_eq!(Ok(()), policy.validate::<Blake3>(&proof));
+    let policy = AcceptableOptions::MinConjecturedSecurity(security + 1);
+    assert_eq!(
+        Err(VerifierError::InsufficientConjecturedSecurity(security + 1, security)),
+        policy.validate::<Blake3>(&proof)
+    );
+    let policy = AcceptableOptions::MinProvenSecurity(security + 1);
+    assert!(policy.validate::<Blake3>(&proof).is_err());
+
+    let policy = AcceptableOptions::OptionSet(vec![build_options(true), options]);
+    assert_eq!(Ok(()), policy.validate::<Blake3>(&proof));
+    let policy = AcceptableOptions::OptionSet(vec![build_options(true)]);
+    assert_eq!(Err(VerifierError::UnacceptableProofOptions), policy.validate::<Blake3>(&proof));
+
+    // policies can be serialized
+    for policy in [
+        AcceptableOptions::MinConjecturedSecurity(96),
+        AcceptableOptions::MinProvenSecurity(80),
+        AcceptableOptions::OptionSet(vec![build_options(false), build_options(true)]),
+    ] {
+        assert_eq!(policy, AcceptableOptions::read_from_bytes(&policy.to_bytes()).unwrap());
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&policy).unwrap();
+            assert_eq!(policy, serde_json::from_str(&json).unwrap());
+        }
+    }
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn verify_domain_too_large() {
+    let proof = FibProver::<FibAir>::new(build_options(false)).prove(build_trace(16)).unwrap();
+    assert_eq!(8, proof.trace_length());
+
+    // the trace length is stored as a power of two right after the trace layout; a proof which
+    // claims a trace of 2^40 steps implies an LDE domain which does not exist in the field, and
+    // thus, is rejected with an error rather than a panic
+    let mut bytes = proof.to_bytes();
+    assert_eq!(3, bytes[6]);
+    bytes[6] = 40;
+    let proof = StarkProof::from_bytes(&bytes).unwrap();
+    assert_eq!(1 << 40, proof.trace_length());
+    let result = crate::verify::<FibAir, Blake3, RandCoin>(proof, BaseElement::ONE);
+    assert_eq!(Err(VerifierError::DomainTooLarge(1 << 43, 1 << 40)), result);
+}
+
+#[test]
+fn verify_query_commitment_mismatch() {
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    // flips a bit in the first queried value; value bytes follow an 8-byte header
+    let tamper = |bytes: Vec<u8>| {
+        let mut bytes = bytes;
+        bytes[8] ^= 1;
+        Deserializable::read_from_bytes(&bytes).unwrap()
+    };
+
+    let mut tampered = proof.clone();
+    tampered.trace_queries[0] = tamper(proof.trace_queries[0].to_bytes());
+    let result = crate::verify::<FibAir, Blake3, RandCoin>(tampered, pub_inputs);
+    assert!(matches!(result, Err(VerifierError::MainTraceQueryDoesNotMatchCommitment(_))));
+
+    let mut tampered = proof.clone();
+    tampered.constraint_queries = tamper(proof.constraint_queries.to_bytes());
+    let result = crate::verify::<FibAir, Blake3, RandCoin>(tampered, pub_inputs);
+    assert!(matches!(result, Err(VerifierError::ConstraintQueryDoesNotMatchCommitment(_))));
+}
+
+#[test]
+fn verify_truncated_digests() {
+    type Blake3_160 = Truncated<Blake3, 20>;
+
+    let prover = FibProver::<FibAir, Blake3_160>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    // security level of the proof is bounded by collision resistance of truncated digests
+    assert!(proof.security_level::<Blake3_160>(true) <= 80);
+    let result = crate::verify::<FibAir, Blake3_160, DefaultRandomCoin<Blake3_160>>(
+        proof.clone(),
+        pub_inputs,
+    );
+    assert_eq!(Ok(()), result);
+
+    // the proof cannot be verified with full digests
+    assert!(crate::verify::<FibAir, Blake3, RandCoin>(proof, pub_inputs).is_err());
+}
+
+#[test]
+fn verify_aggregate_proof() {
+    let prover = FibProver::<FibAir>::new(build_options(false));
+    let traces: Vec<_> = (0..4).map(|_| build_trace(64)).collect();
+
+    // the aggregate proof is much smaller than separate proofs for each statement
+    let separate_size: usize = traces
+        .iter()
+        .map(|trace| prover.prove(trace.clone()).unwrap().to_bytes().len())
+        .sum();
+
+    let prover = AggregateProver::new(prover);
+    let trace = prover.build_trace(traces);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    assert_eq!(4, pub_inputs.num_statements());
+    let proof = prover.prove(trace).unwrap();
+    assert!(proof.to_bytes().len() < separate_size / 2);
+
+    let result =
+        crate::verify::<AggregateAir<FibAir>, Blake3, RandCoin>(proof.clone(), pub_inputs.clone());
+    assert_eq!(Ok(()), result);
+
+    // a wrong public input for any of the statements invalidates the proof
+    let mut inputs = pub_inputs.inputs().to_vec();
+    inputs[2] += BaseElement::ONE;
+    let result = crate::verify::<AggregateAir<FibAir>, Blake3, RandCoin>(
+        proof,
+        AggregatePublicInputs::new(inputs),
+    );
+    assert!(result.is_err());
+}
+
+// CUSTOMIZED AIR CONTEXT
+// ================================================================================================
+
+#[test]
+fn verify_column_group_proof() {
+    let prover = FibProver::<GroupedFibAir>::new(build_options(true));
+    let trace = build_trace(256);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    assert!(proof.column_group().is_some());
+    let artifacts = proof.verification_artifacts::<Blake3>().unwrap();
+    assert!(artifacts.column_group_root.is_some());
+    assert_eq!(1, artifacts.trace_roots.len());
+
+    // column group data survives serialization of the full proof and of its split parts
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    let result = crate::verify::<GroupedFibAir, Blake3, RandCoin>(proof.clone(), pub_inputs);
+    assert_eq!(Ok(()), result);
+
+    let (certificate, openings) = proof.clone().split();
+    let certificate = ProofCertificate::from_bytes(&certificate.to_bytes()).unwrap();
+    let openings = ProofOpenings::from_bytes(&openings.to_bytes()).unwrap();
+    let certified =
+        crate::verify_certificate::<GroupedFibAir, Blake3, RandCoin>(certificate, pub_inputs)
+            .unwrap();
+    assert_eq!(Ok(()), certified.verify_openings(openings));
+
+    // a proof with missing or mismatched column group data is rejected
+    let mut missing = proof.clone();
+    missing.take_column_group();
+    let result = crate::verify::<GroupedFibAir, Blake3, RandCoin>(missing, pub_inputs);
+    assert!(result.is_err());
+
+    let other_proof = prover.prove(build_trace(512)).unwrap();
+    let mut mismatched = proof.clone();
+    mismatched.take_column_group();
+    let mismatched = mismatched.with_column_group(other_proof.column_group().unwrap().clone());
+    let result = crate::verify::<GroupedFibAir, Blake3, RandCoin>(mismatched, pub_inputs);
+    assert!(result.is_err());
+
+    // an AIR without the column group rejects the proof
+    let result = crate::verify::<FibAir, Blake3, RandCoin>(proof, pub_inputs);
+    assert!(result.is_err());
+}
+
+#[test]
+fn verify_fixed_composition_columns() {
+    let prover = FibProver::<PaddedFibAir>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    // the number of composition columns is known before the proof is generated
+    let air = PaddedFibAir::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    assert_eq!(1, air.context().min_num_constraint_composition_columns());
+    assert_eq!(4, air.num_constraint_composition_columns());
+    let ood_frame = proof.ood_frame.clone().parse::<BaseElement>().unwrap();
+    assert_eq!(4, ood_frame.constraint_evaluations.len());
+
+    let result = crate::verify::<PaddedFibAir, Blake3, RandCoin>(proof.clone(), pub_inputs);
+    assert_eq!(Ok(()), result);
+
+    // the default AIR expects a single composition column, and thus, rejects the proof
+    let result = crate::verify::<FibAir, Blake3, RandCoin>(proof, pub_inputs);
+    assert!(result.is_err());
+}
+
+#[test]
+fn verify_row_hash_strategies() {
+    // rows of the main trace segment are hashed column by column
+    let prover = FibProver::<ColumnHashedFibAir>::new(build_options(false));
+    let trace = build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    let result = crate::verify::<ColumnHashedFibAir, Blake3, RandCoin>(proof.clone(), pub_inputs);
+    assert_eq!(Ok(()), result);
+
+    // an AIR which hashes full rows rejects the proof
+    let result = crate::verify::<FibAir, Blake3, RandCoin>(proof, pub_inputs);
+    assert!(result.is_err());
+
+    // digests of rows of the main trace segment and of the column group are merged with a domain
+    // separation tag
+    let prover = FibProver::<TaggedFibAir>::new(build_options(false));
+    let trace = build_trace(256);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    let result = crate::verify::<TaggedFibAir, Blake3, RandCoin>(proof.clone(), pub_inputs);
+    assert_eq!(Ok(()), result);
+
+    let result = crate::verify::<GroupedFibAir, Blake3, RandCoin>(proof, pub_inputs);
+    assert!(result.is_err());
+}
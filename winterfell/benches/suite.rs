@@ -0,0 +1,9 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(suite, winterfell::bench::all);
+criterion_main!(suite);
@@ -0,0 +1,168 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{ExtensibleField, FieldElement, StarkField, ToElements},
+    Air, AirContext, Assertion, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, EvaluationFrame, ProofOptions, Prover, Trace,
+    TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+use core::marker::PhantomData;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns in the execution trace of the benchmarked computation.
+pub const TRACE_WIDTH: usize = 8;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of the benchmarked computation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchInputs<B: StarkField> {
+    pub seed: B,
+    pub result: B,
+}
+
+impl<B: StarkField> ToElements<B> for BenchInputs<B> {
+    fn to_elements(&self) -> Vec<B> {
+        vec![self.seed, self.result]
+    }
+}
+
+// BENCHMARK AIR
+// ================================================================================================
+
+/// AIR of the computation used in benchmarks.
+///
+/// The computation updates [TRACE_WIDTH] registers at every step as
+/// $r_{i, j+1} = r_{i, j}^3 + r_{i + 1, j}$, where $r_{i, j}$ is the value of the $i$-th register
+/// at step $j$ and register indexes wrap around. Thus, all transition constraints have degree 3.
+pub struct BenchAir<B: StarkField> {
+    context: AirContext<B>,
+    seed: B,
+    result: B,
+}
+
+impl<B> Air for BenchAir<B>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+{
+    type BaseField = B;
+    type PublicInputs = BenchInputs<B>;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BenchInputs<B>, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let degrees = vec![TransitionConstraintDegree::new(3); TRACE_WIDTH];
+        BenchAir {
+            context: AirContext::new(trace_info, degrees, 2, options),
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<B> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<B>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        for (i, result) in result.iter_mut().enumerate() {
+            *result = next[i] - (current[i].cube() + current[(i + 1) % TRACE_WIDTH]);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<B>> {
+        let last_step = self.trace_length() - 1;
+        vec![Assertion::single(0, 0, self.seed), Assertion::single(0, last_step, self.result)]
+    }
+}
+
+// BENCHMARK PROVER
+// ================================================================================================
+
+/// Prover of the computation used in benchmarks.
+pub struct BenchProver<B: StarkField, H: ElementHasher<BaseField = B>> {
+    options: ProofOptions,
+    _field: PhantomData<B>,
+    _hasher: PhantomData<H>,
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> BenchProver<B, H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _field: PhantomData,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace of the specified length for the computation starting with the
+    /// specified seed.
+    pub fn build_trace(&self, seed: B, trace_length: usize) -> TraceTable<B> {
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+        trace.fill(
+            |state| {
+                for (i, value) in state.iter_mut().enumerate() {
+                    *value = seed + B::from(i as u32);
+                }
+            },
+            |_, state| {
+                let first = state[0];
+                for i in 0..TRACE_WIDTH - 1 {
+                    state[i] = state[i].cube() + state[i + 1];
+                }
+                state[TRACE_WIDTH - 1] = state[TRACE_WIDTH - 1].cube() + first;
+            },
+        );
+        trace
+    }
+}
+
+impl<B, H> Prover for BenchProver<B, H>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    H: ElementHasher<BaseField = B>,
+{
+    type BaseField = B;
+    type Air = BenchAir<B>;
+    type Trace = TraceTable<B>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<H>;
+    type TraceLde<E: FieldElement<BaseField = B>> = DefaultTraceLde<E, H>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = B>> =
+        DefaultConstraintEvaluator<'a, BenchAir<B>, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BenchInputs<B> {
+        BenchInputs {
+            seed: trace.get(0, 0),
+            result: trace.get(0, trace.length() - 1),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a BenchAir<B>,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
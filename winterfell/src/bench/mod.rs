@@ -0,0 +1,341 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Benchmarks of the main components of the STARK protocol.
+//!
+//! This module is available only when the `bench` feature is enabled. It covers low-degree
+//! extension of the execution trace, Merkle commitment to the extended trace, constraint
+//! evaluation, FRI, and end-to-end proof generation and verification for a fixed computation
+//! (see [BenchAir]) across several fields and hash functions.
+//!
+//! Every benchmark has a stable ID of the form `{component}/{field}/{hasher}/{trace_length}`
+//! where:
+//! * `component` is one of `lde`, `merkle`, `constraints`, `fri`, `prove`, and `verify`.
+//! * `field` is one of `f64`, `f62`, and `f128`. Computations over 64-bit fields are performed
+//!   in their quadratic extensions.
+//! * `hasher` is one of `blake3_256`, `sha3_256`, and `rp64_256`; Rescue Prime is benchmarked
+//!   only with the 64-bit field. Components which do not depend on the hash function (`lde` and
+//!   `constraints`) omit this part of the ID.
+//! * `trace_length` is the length of the execution trace, e.g. `2^16`.
+//!
+//! These IDs are kept unchanged across releases so that results of different releases can be
+//! compared against each other.
+//!
+//! To run all benchmarks, execute:
+//! ```text
+//! cargo bench -p winterfell --features bench
+//! ```
+
+use crate::{
+    crypto::{
+        hashers::{Blake3_256, Rp64_256, Sha3_256},
+        DefaultRandomCoin, ElementHasher, RandomCoin,
+    },
+    math::{
+        fft,
+        fields::{f128, f62, f64, QuadExtension},
+        ExtensibleField, FieldElement, StarkField,
+    },
+    Air, AuxTraceRandElements, ConstraintEvaluator, DefaultConstraintEvaluator, DefaultTraceLde,
+    FieldExtension, ProofOptions, Prover, Trace, TraceLde,
+};
+use core::time::Duration;
+use criterion::{measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId, Criterion};
+use fri::{DefaultProverChannel, FriProver};
+use prover::{matrix::RowMatrix, StarkDomain};
+
+mod air;
+pub use air::{BenchAir, BenchInputs, BenchProver, TRACE_WIDTH};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Base 2 logarithms of the execution trace lengths used in benchmarks.
+const LOG_TRACE_LENGTHS: [u32; 2] = [12, 16];
+
+/// Number of polynomials evaluated together during low-degree extension.
+const LDE_BATCH_SIZE: usize = 8;
+
+const SAMPLE_SIZE: usize = 10;
+const MEASUREMENT_TIME: Duration = Duration::from_secs(10);
+
+// BENCHMARK RUNNER
+// ================================================================================================
+
+/// Invokes the specified benchmark function for every supported combination of field, hash
+/// function, and trace length.
+macro_rules! for_each_config {
+    ($group:expr, $bench:ident) => {
+        for log_n in LOG_TRACE_LENGTHS {
+            type F64 = QuadExtension<f64::BaseElement>;
+            type F62 = QuadExtension<f62::BaseElement>;
+            $bench::<_, F64, Blake3_256<_>>($group, "f64", "blake3_256", log_n);
+            $bench::<_, F64, Sha3_256<_>>($group, "f64", "sha3_256", log_n);
+            $bench::<_, F64, Rp64_256>($group, "f64", "rp64_256", log_n);
+            $bench::<_, F62, Blake3_256<_>>($group, "f62", "blake3_256", log_n);
+            $bench::<_, F62, Sha3_256<_>>($group, "f62", "sha3_256", log_n);
+            $bench::<_, f128::BaseElement, Blake3_256<_>>($group, "f128", "blake3_256", log_n);
+            $bench::<_, f128::BaseElement, Sha3_256<_>>($group, "f128", "sha3_256", log_n);
+        }
+    };
+}
+
+/// Runs all benchmarks in this module.
+pub fn all(c: &mut Criterion) {
+    lde(c);
+    merkle(c);
+    constraints(c);
+    fri(c);
+    prove(c);
+    verify(c);
+}
+
+/// Benchmarks low-degree extension of the main segment of the execution trace.
+pub fn lde(c: &mut Criterion) {
+    let mut group = new_group(c, "lde");
+    for_each_config!(&mut group, bench_lde);
+    group.finish();
+}
+
+/// Benchmarks building a Merkle tree from rows of the extended execution trace.
+pub fn merkle(c: &mut Criterion) {
+    let mut group = new_group(c, "merkle");
+    for_each_config!(&mut group, bench_merkle);
+    group.finish();
+}
+
+/// Benchmarks evaluation of transition and boundary constraints over the extended execution
+/// trace.
+pub fn constraints(c: &mut Criterion) {
+    let mut group = new_group(c, "constraints");
+    for_each_config!(&mut group, bench_constraints);
+    group.finish();
+}
+
+/// Benchmarks building of FRI layers for a polynomial of the same degree as the execution trace.
+pub fn fri(c: &mut Criterion) {
+    let mut group = new_group(c, "fri");
+    for_each_config!(&mut group, bench_fri);
+    group.finish();
+}
+
+/// Benchmarks end-to-end proof generation.
+pub fn prove(c: &mut Criterion) {
+    let mut group = new_group(c, "prove");
+    for_each_config!(&mut group, bench_prove);
+    group.finish();
+}
+
+/// Benchmarks end-to-end proof verification.
+pub fn verify(c: &mut Criterion) {
+    let mut group = new_group(c, "verify");
+    for_each_config!(&mut group, bench_verify);
+    group.finish();
+}
+
+// COMPONENT BENCHMARKS
+// ================================================================================================
+
+fn bench_lde<B, E, H>(group: &mut BenchmarkGroup<WallTime>, field: &str, hasher: &str, log_n: u32)
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    // the LDE does not depend on the hash function; benchmark it only once per field
+    if hasher != "blake3_256" {
+        return;
+    }
+    let setup = BenchSetup::<B, H>::new(log_n, E::EXTENSION_DEGREE);
+    let domain = StarkDomain::new(&setup.air);
+    group.bench_function(BenchmarkId::new(field, format!("2^{log_n}")), |bench| {
+        bench.iter(|| {
+            let polys = setup.trace.main_segment().interpolate_columns();
+            RowMatrix::evaluate_polys_over::<LDE_BATCH_SIZE>(&polys, &domain)
+        });
+    });
+}
+
+fn bench_merkle<B, E, H>(
+    group: &mut BenchmarkGroup<WallTime>,
+    field: &str,
+    hasher: &str,
+    log_n: u32,
+) where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    let setup = BenchSetup::<B, H>::new(log_n, E::EXTENSION_DEGREE);
+    let domain = StarkDomain::new(&setup.air);
+    let polys = setup.trace.main_segment().interpolate_columns();
+    let lde = RowMatrix::evaluate_polys_over::<LDE_BATCH_SIZE>(&polys, &domain);
+    group.bench_function(config_id(field, hasher, log_n), |bench| {
+        bench.iter(|| lde.commit_to_rows::<H>());
+    });
+}
+
+fn bench_constraints<B, E, H>(
+    group: &mut BenchmarkGroup<WallTime>,
+    field: &str,
+    hasher: &str,
+    log_n: u32,
+) where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    // constraint evaluation does not depend on the hash function; benchmark it only once per
+    // field
+    if hasher != "blake3_256" {
+        return;
+    }
+    let setup = BenchSetup::<B, H>::new(log_n, E::EXTENSION_DEGREE);
+    let domain = StarkDomain::new(&setup.air);
+    let trace_info = setup.trace.get_info();
+    let (_, trace_lde) =
+        DefaultTraceLde::<E, H>::new(&trace_info, setup.trace.main_segment(), &domain);
+    let mut public_coin = DefaultRandomCoin::<H>::new(&[]);
+    let coefficients = setup
+        .air
+        .get_constraint_composition_coefficients::<E, _>(&mut public_coin)
+        .expect("failed to draw composition coefficients");
+
+    group.bench_function(BenchmarkId::new(field, format!("2^{log_n}")), |bench| {
+        bench.iter_batched(
+            || coefficients.clone(),
+            |coefficients| {
+                let aux_rand_elements = AuxTraceRandElements::new();
+                DefaultConstraintEvaluator::new(&setup.air, aux_rand_elements, coefficients)
+                    .evaluate(&trace_lde, &domain)
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+fn bench_fri<B, E, H>(group: &mut BenchmarkGroup<WallTime>, field: &str, hasher: &str, log_n: u32)
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    let setup = BenchSetup::<B, H>::new(log_n, E::EXTENSION_DEGREE);
+    let domain = StarkDomain::new(&setup.air);
+    let options = setup.prover.options();
+    let num_queries = options.num_queries();
+
+    // evaluate the polynomial describing the first column of the trace over the LDE domain
+    let polys = setup.trace.main_segment().interpolate_columns();
+    let mut evaluations = polys.get_column(0).iter().map(|&c| E::from(c)).collect::<Vec<_>>();
+    evaluations.resize(domain.lde_domain_size(), E::ZERO);
+    let twiddles = fft::get_twiddles::<B>(domain.lde_domain_size());
+    fft::evaluate_poly(&mut evaluations, &twiddles);
+
+    let mut fri_prover = FriProver::new(options.to_fri_options());
+    group.bench_function(config_id(field, hasher, log_n), |bench| {
+        bench.iter_batched(
+            || evaluations.clone(),
+            |evaluations| {
+                let mut channel = DefaultProverChannel::<E, H, DefaultRandomCoin<H>>::new(
+                    domain.lde_domain_size(),
+                    num_queries,
+                );
+                fri_prover.build_layers(&mut channel, evaluations);
+                fri_prover.reset();
+            },
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+fn bench_prove<B, E, H>(group: &mut BenchmarkGroup<WallTime>, field: &str, hasher: &str, log_n: u32)
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    let setup = BenchSetup::<B, H>::new(log_n, E::EXTENSION_DEGREE);
+    group.bench_function(config_id(field, hasher, log_n), |bench| {
+        bench.iter_batched(
+            || setup.trace.clone(),
+            |trace| setup.prover.prove(trace).expect("failed to generate proof"),
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+fn bench_verify<B, E, H>(
+    group: &mut BenchmarkGroup<WallTime>,
+    field: &str,
+    hasher: &str,
+    log_n: u32,
+) where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    let setup = BenchSetup::<B, H>::new(log_n, E::EXTENSION_DEGREE);
+    let pub_inputs = setup.prover.get_pub_inputs(&setup.trace);
+    let proof = setup.prover.prove(setup.trace.clone()).expect("failed to generate proof");
+    group.bench_function(config_id(field, hasher, log_n), |bench| {
+        bench.iter_batched(
+            || proof.clone(),
+            |proof| {
+                crate::verify::<BenchAir<B>, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+                    .expect("failed to verify proof")
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Execution trace of the benchmarked computation together with its prover and AIR.
+struct BenchSetup<B, H>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    H: ElementHasher<BaseField = B>,
+{
+    prover: BenchProver<B, H>,
+    trace: crate::TraceTable<B>,
+    air: BenchAir<B>,
+}
+
+impl<B, H> BenchSetup<B, H>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    H: ElementHasher<BaseField = B>,
+{
+    fn new(log_n: u32, extension_degree: usize) -> Self {
+        let field_extension = match extension_degree {
+            1 => FieldExtension::None,
+            2 => FieldExtension::Quadratic,
+            3 => FieldExtension::Cubic,
+            _ => unreachable!("unsupported field extension degree"),
+        };
+        let options = ProofOptions::new(32, 8, 0, field_extension, 4, 31);
+        let prover = BenchProver::<B, H>::new(options.clone());
+        let trace = prover.build_trace(B::from(42u32), 1 << log_n);
+        let air = BenchAir::new(trace.get_info(), prover.get_pub_inputs(&trace), options);
+        Self { prover, trace, air }
+    }
+}
+
+/// Creates a new benchmark group with the specified name; the name of the group is the name of
+/// the benchmarked component.
+fn new_group<'a>(c: &'a mut Criterion, component: &str) -> BenchmarkGroup<'a, WallTime> {
+    let mut group = c.benchmark_group(component);
+    group.sample_size(SAMPLE_SIZE);
+    group.measurement_time(MEASUREMENT_TIME);
+    group
+}
+
+/// Returns the ID of a benchmark for the specified field, hash function, and trace length.
+fn config_id(field: &str, hasher: &str, log_n: u32) -> BenchmarkId {
+    BenchmarkId::new(format!("{field}/{hasher}"), format!("2^{log_n}"))
+}
@@ -0,0 +1,199 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{IvcStep, StepAir};
+use crate::{
+    crypto::{ElementHasher, RandomCoin},
+    math::StarkField,
+    Prover, ProverError, Trace, TraceInfo, TraceTable, VerifierError,
+};
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+// TRACE SPLITTING
+// ================================================================================================
+
+/// Splits an execution trace of a long computation into segments of `segment_length` rows each,
+/// such that the last row of every segment is the first row of the next segment.
+///
+/// The execution trace is specified by its `columns`; for the execution to be split into `k`
+/// segments, its length must be `k * (segment_length - 1) + 1`. Each segment can then be proven
+/// separately via [prove_segments()], and the resulting proofs can be verified via
+/// [verify_segments()].
+///
+/// # Errors
+/// Returns an error if:
+/// * `segment_length` is not a power of two, or is smaller than the minimum trace length.
+/// * No columns were provided, or the columns have different lengths.
+/// * The execution cannot be split into a whole number of segments of the specified length.
+///
+/// # Panics
+/// Panics if the number of columns exceeds the maximum trace width.
+pub fn split_trace<B: StarkField>(
+    columns: &[Vec<B>],
+    segment_length: usize,
+) -> Result<Vec<TraceTable<B>>, ContinuationError> {
+    if !segment_length.is_power_of_two() || segment_length < TraceInfo::MIN_TRACE_LENGTH {
+        return Err(ContinuationError::InvalidSegmentLength(segment_length));
+    }
+    let execution_length = columns.first().map_or(0, |column| column.len());
+    if columns.is_empty() || columns.iter().any(|column| column.len() != execution_length) {
+        return Err(ContinuationError::InconsistentColumnLengths);
+    }
+    if execution_length <= 1 || (execution_length - 1) % (segment_length - 1) != 0 {
+        return Err(ContinuationError::InvalidExecutionLength(execution_length, segment_length));
+    }
+
+    let num_segments = (execution_length - 1) / (segment_length - 1);
+    let segments = (0..num_segments)
+        .map(|i| {
+            let start = i * (segment_length - 1);
+            let segment_columns = columns
+                .iter()
+                .map(|column| column[start..start + segment_length].to_vec())
+                .collect();
+            TraceTable::init(segment_columns)
+        })
+        .collect();
+    Ok(segments)
+}
+
+// PROVING AND VERIFICATION
+// ================================================================================================
+
+/// Proves each of the provided trace `segments` using the specified `prover`, and returns the
+/// resulting proofs together with the state of the computation after each segment.
+///
+/// Segments must be stitched together: the last row of every segment must be the same as the
+/// first row of the next segment (this is the case for segments returned from [split_trace()]).
+/// The state of the computation before and after each segment is bound to the segment proof via
+/// its public inputs (see [StepAir]), and thus, [verify_segments()] can check that the segments
+/// form a single computation.
+///
+/// # Errors
+/// Returns an error if any pair of adjacent segments is not stitched together, or if a proof
+/// could not be generated for any of the segments.
+pub fn prove_segments<P>(
+    prover: &P,
+    segments: Vec<P::Trace>,
+) -> Result<Vec<IvcStep<P::BaseField>>, ContinuationError>
+where
+    P: Prover,
+    P::Air: StepAir,
+{
+    for (i, pair) in segments.windows(2).enumerate() {
+        let (current, next) = (pair[0].main_segment(), pair[1].main_segment());
+        let last_step = current.num_rows() - 1;
+        let stitched = current.num_cols() == next.num_cols()
+            && (0..current.num_cols()).all(|col| current.get(col, last_step) == next.get(col, 0));
+        if !stitched {
+            return Err(ContinuationError::SegmentsNotStitched(i));
+        }
+    }
+
+    segments
+        .into_iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let pub_inputs = prover.get_pub_inputs(&segment);
+            let state_out = <P::Air as StepAir>::state_out(&pub_inputs);
+            let proof = prover
+                .prove(segment)
+                .map_err(|err| ContinuationError::SegmentProofFailed(i, err))?;
+            Ok(IvcStep { proof, state_out })
+        })
+        .collect()
+}
+
+/// Verifies proofs of a computation split into segments starting at `initial_state`, and returns
+/// the state of the computation after the last segment.
+///
+/// Each segment is verified against public inputs which transform the state after the previous
+/// segment (or `initial_state` for the first segment) into the state claimed by the segment, and
+/// thus, the segments are verified to form a single computation.
+///
+/// # Errors
+/// Returns an error if no segments were provided, or if verification of any of the segments
+/// fails.
+pub fn verify_segments<A, H, R>(
+    initial_state: Vec<A::BaseField>,
+    segments: Vec<IvcStep<A::BaseField>>,
+) -> Result<Vec<A::BaseField>, ContinuationError>
+where
+    A: StepAir,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+{
+    if segments.is_empty() {
+        return Err(ContinuationError::NoSegments);
+    }
+
+    segments.into_iter().enumerate().try_fold(initial_state, |state, (i, segment)| {
+        let IvcStep { proof, state_out } = segment;
+        let pub_inputs = A::build_pub_inputs(&state, &state_out);
+        crate::verify::<A, H, R>(proof, pub_inputs)
+            .map_err(|err| ContinuationError::SegmentVerificationFailed(i, err))?;
+        Ok(state_out)
+    })
+}
+
+// CONTINUATION ERROR
+// ================================================================================================
+/// Represents an error returned when splitting a computation into segments, or when proving or
+/// verifying the segments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContinuationError {
+    /// This error occurs when the segment length is not a power of two or is smaller than the
+    /// minimum trace length.
+    InvalidSegmentLength(usize),
+    /// This error occurs when the columns of an execution trace are missing or have different
+    /// lengths.
+    InconsistentColumnLengths,
+    /// This error occurs when an execution of the specified length cannot be split into a whole
+    /// number of segments of the specified length.
+    InvalidExecutionLength(usize, usize),
+    /// This error occurs when the last row of the segment with the specified index differs from
+    /// the first row of the next segment.
+    SegmentsNotStitched(usize),
+    /// This error occurs when a proof for the segment with the specified index could not be
+    /// generated.
+    SegmentProofFailed(usize, ProverError),
+    /// This error occurs when no segments were provided for verification.
+    NoSegments,
+    /// This error occurs when the proof of the segment with the specified index fails
+    /// verification.
+    SegmentVerificationFailed(usize, VerifierError),
+}
+
+impl fmt::Display for ContinuationError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSegmentLength(length) => {
+                write!(f, "segment length must be a power of two and at least {}, but was {length}", TraceInfo::MIN_TRACE_LENGTH)
+            }
+            Self::InconsistentColumnLengths => {
+                write!(f, "execution trace columns must be non-empty and have the same length")
+            }
+            Self::InvalidExecutionLength(length, segment_length) => {
+                write!(f, "execution of length {length} cannot be split into segments of length {segment_length}")
+            }
+            Self::SegmentsNotStitched(segment) => {
+                write!(f, "last row of segment {segment} is not the same as the first row of segment {}", segment + 1)
+            }
+            Self::SegmentProofFailed(segment, err) => {
+                write!(f, "failed to generate proof of segment {segment}: {err}")
+            }
+            Self::NoSegments => {
+                write!(f, "at least one segment must be provided")
+            }
+            Self::SegmentVerificationFailed(segment, err) => {
+                write!(f, "verification of segment {segment} failed: {err}")
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Scaffolding for incrementally verifiable computation (IVC).
+//!
+//! A long-running computation can be split into a sequence of steps, where each step transforms
+//! a state of the computation into the next state. Each step is described by a [StepAir] and is
+//! proven separately using a [StepProver]; thus, memory required to generate proofs is bounded by
+//! the size of a single step, regardless of how many steps the computation takes.
+//!
+//! Steps are linked together via an accumulator: a hash chain which starts with a commitment to
+//! the initial state of the computation and absorbs the proof and the resulting state of every
+//! step. The accumulator which a step extends is written into the metadata of the step's
+//! execution trace under [ACCUMULATOR_META_KEY], and thus, it is bound to the step proof via the
+//! proof context.
+//!
+//! This module currently provides a reference chain-of-proofs construction: [IvcProver] emits an
+//! [IvcStep] for each step of the computation, and [IvcVerifier] verifies the steps one by one,
+//! keeping only the current state and accumulator in memory. The accumulator after the last step
+//! is a succinct commitment to the entire chain; once a step AIR capable of verifying the previous
+//! step proof is available (e.g., when the proofs are generated using an arithmetization-friendly
+//! hash function), the chain can be collapsed into the final step proof.
+//!
+//! For computations whose entire execution trace is available upfront, the [continuation]
+//! module provides helpers for splitting the trace into segments whose boundary rows are
+//! stitched together, proving the segments separately, and verifying that the segment proofs
+//! form a single computation.
+
+use crate::{
+    crypto::{Digest, ElementHasher},
+    Air, Prover, StarkProof, TraceMetadata,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+mod prover;
+pub use prover::IvcProver;
+
+mod verifier;
+pub use verifier::{IvcError, IvcVerifier};
+
+pub mod continuation;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Key under which the accumulator extended by a step is stored in the metadata of the step's
+/// execution trace.
+pub const ACCUMULATOR_META_KEY: &str = "ivc.accumulator";
+
+// STEP INTERFACE
+// ================================================================================================
+
+/// AIR for a single step of an incrementally verifiable computation.
+///
+/// Public inputs of a step consist of the state of the computation before and after the step.
+pub trait StepAir: Air {
+    /// Returns public inputs for a step which transforms `state_in` into `state_out`.
+    fn build_pub_inputs(
+        state_in: &[Self::BaseField],
+        state_out: &[Self::BaseField],
+    ) -> Self::PublicInputs;
+
+    /// Returns the state of the computation after the step described by the specified public
+    /// inputs.
+    fn state_out(pub_inputs: &Self::PublicInputs) -> Vec<Self::BaseField>;
+}
+
+/// Prover for a single step of an incrementally verifiable computation.
+pub trait StepProver: Prover
+where
+    Self::Air: StepAir,
+{
+    /// Returns an execution trace for a step of the computation starting at `state`.
+    ///
+    /// The returned trace must carry the specified metadata, which contains the accumulator
+    /// extended by this step.
+    fn build_step_trace(&self, state: &[Self::BaseField], meta: TraceMetadata) -> Self::Trace;
+}
+
+// IVC STEP
+// ================================================================================================
+
+/// A proof for a single step of an incrementally verifiable computation together with the state
+/// of the computation after the step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IvcStep<B> {
+    /// Proof of the step.
+    pub proof: StarkProof,
+    /// State of the computation after the step.
+    pub state_out: Vec<B>,
+}
+
+// ACCUMULATOR
+// ================================================================================================
+
+/// Returns the accumulator for a computation starting at the specified state.
+pub fn init_accumulator<H: ElementHasher>(initial_state: &[H::BaseField]) -> H::Digest {
+    H::hash_elements(initial_state)
+}
+
+/// Returns the accumulator obtained by extending `accumulator` with a step which has the
+/// specified proof and resulting state.
+pub fn extend_accumulator<H: ElementHasher>(
+    accumulator: H::Digest,
+    proof: &StarkProof,
+    state_out: &[H::BaseField],
+) -> H::Digest {
+    let accumulator = H::merge(&[accumulator, H::hash(&proof.to_bytes())]);
+    H::merge(&[accumulator, H::hash_elements(state_out)])
+}
+
+/// Returns trace metadata which binds a step to the specified accumulator.
+fn accumulator_meta<D: Digest>(accumulator: &D) -> TraceMetadata {
+    TraceMetadata::new().with(ACCUMULATOR_META_KEY, accumulator.as_bytes().to_vec())
+}
@@ -0,0 +1,84 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{accumulator_meta, extend_accumulator, init_accumulator, IvcStep, StepAir, StepProver};
+use crate::{crypto::Hasher, ProverError, Trace};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+// IVC PROVER
+// ================================================================================================
+/// Generates proofs for an incrementally verifiable computation one step at a time.
+///
+/// The prover keeps only the current state of the computation and the current accumulator in
+/// memory; proofs of individual steps are returned to the caller as soon as they are generated.
+pub struct IvcProver<P>
+where
+    P: StepProver,
+    P::Air: StepAir,
+{
+    prover: P,
+    state: Vec<P::BaseField>,
+    accumulator: <P::HashFn as Hasher>::Digest,
+    num_steps: usize,
+}
+
+impl<P> IvcProver<P>
+where
+    P: StepProver,
+    P::Air: StepAir,
+{
+    /// Returns a new IVC prover for a computation starting at `initial_state` and proving
+    /// individual steps using the specified step `prover`.
+    pub fn new(prover: P, initial_state: Vec<P::BaseField>) -> Self {
+        let accumulator = init_accumulator::<P::HashFn>(&initial_state);
+        Self {
+            prover,
+            state: initial_state,
+            accumulator,
+            num_steps: 0,
+        }
+    }
+
+    /// Returns the current state of the computation.
+    pub fn state(&self) -> &[P::BaseField] {
+        &self.state
+    }
+
+    /// Returns the accumulator committing to all steps proven so far.
+    pub fn accumulator(&self) -> <P::HashFn as Hasher>::Digest {
+        self.accumulator
+    }
+
+    /// Returns the number of steps proven so far.
+    pub fn num_steps(&self) -> usize {
+        self.num_steps
+    }
+
+    /// Executes the next step of the computation, and returns a proof of this step together with
+    /// the resulting state.
+    ///
+    /// # Errors
+    /// Returns an error if a proof for the step could not be generated.
+    ///
+    /// # Panics
+    /// Panics if the trace built by the step prover does not carry the metadata passed to it.
+    pub fn prove_step(&mut self) -> Result<IvcStep<P::BaseField>, ProverError> {
+        let meta = accumulator_meta(&self.accumulator);
+        let trace = self.prover.build_step_trace(&self.state, meta.clone());
+        assert_eq!(trace.meta(), &meta, "step trace must carry the accumulator metadata");
+
+        let pub_inputs = self.prover.get_pub_inputs(&trace);
+        let state_out = <P::Air as StepAir>::state_out(&pub_inputs);
+        let proof = self.prover.prove(trace)?;
+
+        self.accumulator = extend_accumulator::<P::HashFn>(self.accumulator, &proof, &state_out);
+        self.state = state_out.clone();
+        self.num_steps += 1;
+
+        Ok(IvcStep { proof, state_out })
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    continuation::{prove_segments, split_trace, verify_segments, ContinuationError},
+    IvcError, IvcProver, IvcVerifier, StepAir, StepProver,
+};
+use crate::{
+    crypto::{hashers::Blake3_256, DefaultRandomCoin},
+    math::{fields::f128::BaseElement, FieldElement, ToElements},
+    verify_chain, Air, AirContext, Assertion, AuxTraceRandElements, ChainError,
+    ConstraintCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde,
+    EvaluationFrame, FieldExtension, ProofOptions, Prover, Trace, TraceInfo, TraceMetadata,
+    TraceTable, TransitionConstraintDegree,
+};
+
+type Hasher = Blake3_256<BaseElement>;
+type RandCoin = DefaultRandomCoin<Hasher>;
+
+const STEP_LENGTH: usize = 8;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn ivc_prove_and_verify_steps() {
+    let initial_state = vec![BaseElement::ONE, BaseElement::ONE];
+    let mut prover = IvcProver::new(FibStepProver::new(), initial_state.clone());
+    let mut verifier = IvcVerifier::<FibStepAir, Hasher, RandCoin>::new(initial_state);
+
+    for _ in 0..3 {
+        let step = prover.prove_step().unwrap();
+        verifier.verify_step(step).unwrap();
+    }
+
+    assert_eq!(3, verifier.num_steps());
+    assert_eq!(prover.state(), verifier.state());
+    assert_eq!(prover.accumulator(), verifier.accumulator());
+
+    // the state after 3 steps is the pair of Fibonacci terms at positions 3 * 7 and 3 * 7 + 1
+    let (mut a, mut b) = (BaseElement::ONE, BaseElement::ONE);
+    for _ in 0..3 * (STEP_LENGTH - 1) {
+        (a, b) = (b, a + b);
+    }
+    assert_eq!(&[a, b], verifier.state());
+}
+
+#[test]
+fn ivc_reject_invalid_steps() {
+    let initial_state = vec![BaseElement::ONE, BaseElement::ONE];
+    let mut prover = IvcProver::new(FibStepProver::new(), initial_state.clone());
+    let mut verifier = IvcVerifier::<FibStepAir, Hasher, RandCoin>::new(initial_state);
+
+    let step1 = prover.prove_step().unwrap();
+    let step2 = prover.prove_step().unwrap();
+
+    // steps must be verified in order
+    let result = verifier.verify_step(step2.clone());
+    assert_eq!(Err(IvcError::AccumulatorMismatch(0)), result);
+
+    // a step claiming a wrong resulting state is rejected
+    let mut bad_step = step1.clone();
+    bad_step.state_out[1] += BaseElement::ONE;
+    let result = verifier.verify_step(bad_step);
+    assert!(matches!(result, Err(IvcError::StepVerificationFailed(0, _))));
+
+    // failed steps do not affect the verifier
+    verifier.verify_step(step1).unwrap();
+    verifier.verify_step(step2).unwrap();
+    assert_eq!(prover.accumulator(), verifier.accumulator());
+}
+
+#[test]
+fn continuation_prove_and_verify_segments() {
+    // an execution of 3 segments of STEP_LENGTH rows each, where adjacent segments share a row
+    let execution_length = 3 * (STEP_LENGTH - 1) + 1;
+    let columns = build_fib_columns(execution_length);
+
+    let segments = split_trace(&columns, STEP_LENGTH).unwrap();
+    assert_eq!(3, segments.len());
+    assert_eq!(columns[1][STEP_LENGTH - 1], segments[1].get(1, 0));
+
+    let prover = FibStepProver::new();
+    let proofs = prove_segments(&prover, segments.clone()).unwrap();
+    let initial_state = vec![BaseElement::ONE, BaseElement::ONE];
+    let final_state =
+        verify_segments::<FibStepAir, Hasher, RandCoin>(initial_state.clone(), proofs.clone())
+            .unwrap();
+    assert_eq!(
+        vec![columns[0][execution_length - 1], columns[1][execution_length - 1]],
+        final_state
+    );
+
+    // segments must be verified in order and starting at the initial state
+    let mut reordered = proofs.clone();
+    reordered.swap(0, 1);
+    let result = verify_segments::<FibStepAir, Hasher, RandCoin>(initial_state.clone(), reordered);
+    assert!(matches!(result, Err(ContinuationError::SegmentVerificationFailed(0, _))));
+    let result = verify_segments::<FibStepAir, Hasher, RandCoin>(
+        vec![BaseElement::ONE; 2],
+        proofs[1..].to_vec(),
+    );
+    assert!(matches!(result, Err(ContinuationError::SegmentVerificationFailed(0, _))));
+
+    // segments which are not stitched together cannot be proven
+    let result = prove_segments(&prover, vec![segments[0].clone(), segments[2].clone()]);
+    assert_eq!(Err(ContinuationError::SegmentsNotStitched(0)), result);
+}
+
+#[test]
+fn continuation_split_trace_errors() {
+    let columns = vec![vec![BaseElement::ONE; 15]; 2];
+    assert_eq!(
+        Err(ContinuationError::InvalidSegmentLength(6)),
+        split_trace(&columns, 6).map(|_| ())
+    );
+    assert_eq!(
+        Err(ContinuationError::InvalidExecutionLength(14, 8)),
+        split_trace(&[vec![BaseElement::ONE; 14]], 8).map(|_| ())
+    );
+    assert_eq!(
+        Err(ContinuationError::InconsistentColumnLengths),
+        split_trace(&[vec![BaseElement::ONE; 15], vec![BaseElement::ONE; 14]], 8).map(|_| ())
+    );
+    assert_eq!(2, split_trace(&columns, 8).unwrap().len());
+}
+
+#[test]
+fn verify_chain_of_segments() {
+    let execution_length = 3 * (STEP_LENGTH - 1) + 1;
+    let columns = build_fib_columns(execution_length);
+    let segments = split_trace(&columns, STEP_LENGTH).unwrap();
+
+    let prover = FibStepProver::new();
+    let chain = segments
+        .into_iter()
+        .map(|segment| (prover.get_pub_inputs(&segment), segment))
+        .map(|(pub_inputs, segment)| (prover.prove(segment).unwrap(), pub_inputs))
+        .collect::<Vec<_>>();
+    let link = |prev: &StepInputs, next: &StepInputs| prev.state_out == next.state_in;
+    verify_chain::<FibStepAir, Hasher, RandCoin, _>(chain.clone(), link).unwrap();
+
+    // segments must be linked
+    let mut reordered = chain.clone();
+    reordered.swap(1, 2);
+    let result = verify_chain::<FibStepAir, Hasher, RandCoin, _>(reordered, link);
+    assert_eq!(Err(ChainError::BrokenLink(1)), result);
+
+    // a linked segment with an invalid proof is rejected
+    let mut forged = chain.clone();
+    forged[2].0 = chain[1].0.clone();
+    let result = verify_chain::<FibStepAir, Hasher, RandCoin, _>(forged, link);
+    assert!(matches!(result, Err(ChainError::SegmentVerificationFailed(2, _))));
+
+    let result = verify_chain::<FibStepAir, Hasher, RandCoin, _>(Vec::new(), link);
+    assert_eq!(Err(ChainError::EmptyChain), result);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns columns of an execution trace of the specified length for [FibStepAir].
+fn build_fib_columns(length: usize) -> Vec<Vec<BaseElement>> {
+    let mut sequence = vec![BaseElement::ONE; 2];
+    for i in 2..length + 1 {
+        sequence.push(sequence[i - 2] + sequence[i - 1]);
+    }
+    vec![sequence[..length].to_vec(), sequence[1..].to_vec()]
+}
+
+// FIBONACCI STEP AIR
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StepInputs {
+    state_in: [BaseElement; 2],
+    state_out: [BaseElement; 2],
+}
+
+impl ToElements<BaseElement> for StepInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.state_in[0], self.state_in[1], self.state_out[0], self.state_out[1]]
+    }
+}
+
+/// Advances a pair of consecutive Fibonacci terms by one term per row.
+struct FibStepAir {
+    context: AirContext<BaseElement>,
+    inputs: StepInputs,
+}
+
+impl Air for FibStepAir {
+    type BaseField = BaseElement;
+    type PublicInputs = StepInputs;
+
+    fn new(trace_info: TraceInfo, inputs: StepInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1); 2];
+        FibStepAir {
+            context: AirContext::new(trace_info, degrees, 4, options),
+            inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - current[1];
+        result[1] = next[1] - (current[0] + current[1]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, self.inputs.state_in[0]),
+            Assertion::single(1, 0, self.inputs.state_in[1]),
+            Assertion::single(0, last_step, self.inputs.state_out[0]),
+            Assertion::single(1, last_step, self.inputs.state_out[1]),
+        ]
+    }
+}
+
+impl StepAir for FibStepAir {
+    fn build_pub_inputs(state_in: &[BaseElement], state_out: &[BaseElement]) -> StepInputs {
+        StepInputs {
+            state_in: [state_in[0], state_in[1]],
+            state_out: [state_out[0], state_out[1]],
+        }
+    }
+
+    fn state_out(pub_inputs: &StepInputs) -> Vec<BaseElement> {
+        pub_inputs.state_out.to_vec()
+    }
+}
+
+// FIBONACCI STEP PROVER
+// ================================================================================================
+
+struct FibStepProver {
+    options: ProofOptions,
+}
+
+impl FibStepProver {
+    fn new() -> Self {
+        Self {
+            options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7),
+        }
+    }
+}
+
+impl Prover for FibStepProver {
+    type BaseField = BaseElement;
+    type Air = FibStepAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Hasher;
+    type RandomCoin = RandCoin;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> StepInputs {
+        let last_step = trace.length() - 1;
+        StepInputs {
+            state_in: [trace.get(0, 0), trace.get(1, 0)],
+            state_out: [trace.get(0, last_step), trace.get(1, last_step)],
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+impl StepProver for FibStepProver {
+    fn build_step_trace(&self, state: &[BaseElement], meta: TraceMetadata) -> Self::Trace {
+        let mut trace = TraceTable::with_meta(2, STEP_LENGTH, meta);
+        trace.fill(
+            |row| row.copy_from_slice(state),
+            |_, row| {
+                let next = row[0] + row[1];
+                row[0] = row[1];
+                row[1] = next;
+            },
+        );
+        trace
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    accumulator_meta, extend_accumulator, init_accumulator, IvcStep, StepAir, ACCUMULATOR_META_KEY,
+};
+use crate::{
+    crypto::{ElementHasher, RandomCoin},
+    VerifierError,
+};
+use core::{fmt, marker::PhantomData};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+// IVC VERIFIER
+// ================================================================================================
+/// Verifies proofs of an incrementally verifiable computation one step at a time.
+///
+/// The verifier keeps only the current state of the computation and the current accumulator in
+/// memory. After all steps have been verified, the accumulator is equal to the one computed by
+/// the [IvcProver](super::IvcProver) which generated the steps.
+pub struct IvcVerifier<A, H, R>
+where
+    A: StepAir,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+{
+    state: Vec<A::BaseField>,
+    accumulator: H::Digest,
+    num_steps: usize,
+    _coin: PhantomData<R>,
+}
+
+impl<A, H, R> IvcVerifier<A, H, R>
+where
+    A: StepAir,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+{
+    /// Returns a new IVC verifier for a computation starting at `initial_state`.
+    pub fn new(initial_state: Vec<A::BaseField>) -> Self {
+        let accumulator = init_accumulator::<H>(&initial_state);
+        Self {
+            state: initial_state,
+            accumulator,
+            num_steps: 0,
+            _coin: PhantomData,
+        }
+    }
+
+    /// Returns the state of the computation after the last verified step.
+    pub fn state(&self) -> &[A::BaseField] {
+        &self.state
+    }
+
+    /// Returns the accumulator committing to all steps verified so far.
+    pub fn accumulator(&self) -> H::Digest {
+        self.accumulator
+    }
+
+    /// Returns the number of steps verified so far.
+    pub fn num_steps(&self) -> usize {
+        self.num_steps
+    }
+
+    /// Verifies the next step of the computation, and if the step is valid, advances the state of
+    /// the computation and the accumulator.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The step proof was not generated against the current accumulator.
+    /// * The step proof is not a valid proof of a transition from the current state to the state
+    ///   claimed by the step.
+    pub fn verify_step(&mut self, step: IvcStep<A::BaseField>) -> Result<(), IvcError> {
+        let IvcStep { proof, state_out } = step;
+
+        let expected_meta = accumulator_meta(&self.accumulator);
+        let trace_info = proof.get_trace_info();
+        if trace_info.meta().get_bytes(ACCUMULATOR_META_KEY)
+            != expected_meta.get_bytes(ACCUMULATOR_META_KEY)
+        {
+            return Err(IvcError::AccumulatorMismatch(self.num_steps));
+        }
+
+        let accumulator = extend_accumulator::<H>(self.accumulator, &proof, &state_out);
+        let pub_inputs = A::build_pub_inputs(&self.state, &state_out);
+        crate::verify::<A, H, R>(proof, pub_inputs)
+            .map_err(|err| IvcError::StepVerificationFailed(self.num_steps, err))?;
+
+        self.accumulator = accumulator;
+        self.state = state_out;
+        self.num_steps += 1;
+        Ok(())
+    }
+}
+
+// IVC ERROR
+// ================================================================================================
+/// Represents an error returned by the [IvcVerifier] when verifying a step of a computation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IvcError {
+    /// This error occurs when a step proof was generated against an accumulator which differs
+    /// from the current accumulator of the verifier (i.e., the step does not extend the chain of
+    /// steps verified so far).
+    AccumulatorMismatch(usize),
+    /// This error occurs when a step proof fails verification.
+    StepVerificationFailed(usize, VerifierError),
+}
+
+impl fmt::Display for IvcError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AccumulatorMismatch(step) => {
+                write!(f, "proof of step {step} was not generated against the current accumulator")
+            }
+            Self::StepVerificationFailed(step, err) => {
+                write!(f, "verification of step {step} failed: {err}")
+            }
+        }
+    }
+}
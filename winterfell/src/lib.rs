@@ -558,13 +558,42 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
 pub use prover::{
-    crypto, iterators, math, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ByteReader, ByteWriter, ColMatrix, ConstraintCompositionCoefficients,
-    ConstraintDivisor, ConstraintEvaluator, DeepCompositionCoefficients,
+    build_lagrange_kernel_column, crypto, iterators, lagrange_kernel_boundary_value,
+    lagrange_kernel_transition_divisor, math, AggregateAir, AggregateProver, AggregatePublicInputs,
+    AggregateTrace, Air, AirContext, Assertion, AuxRandElementsReader, AuxTraceError,
+    AuxTraceRandElements, BatchingMethod, BoundaryConstraint, BoundaryConstraintGroup, ByteReader,
+    ByteWriter, ColMatrix, ColMatrixBuilder, ColumnGroup, ConstraintCompositionCoefficients,
+    ConstraintDivisor, ConstraintEvaluator, ConstraintLeafEncoding, DeepCompositionCoefficients,
     DefaultConstraintEvaluator, DefaultTraceLde, Deserializable, DeserializationError,
-    EvaluationFrame, FieldExtension, ProofOptions, Prover, ProverError, Serializable, SliceReader,
-    StarkProof, Trace, TraceInfo, TraceLayout, TraceLde, TraceTable, TraceTableFragment,
-    TransitionConstraintDegree,
+    EvaluationFrame, FieldExtension, LagrangeKernelEvaluationFrame, MatrixError,
+    OodConstraintBatching, PendingProof, PeriodicColumn, PermutationArg, PermutationArgBuilder,
+    ProofOptions, Prover, ProverChannel, ProverError, RangeCheck, RangeCheckBuilder,
+    RowHashStrategy, SelectorColumns, SelectorFlag, Serializable, SliceReader, StarkProof, Trace,
+    TraceInfo, TraceLayout, TraceLde, TraceMetaValue, TraceMetadata, TraceTable,
+    TraceTableFragment, TransitionConstraintDegree, MIN_RANGE_CHECK_TRACE_LENGTH,
+};
+pub use verifier::{
+    perform_verification, verify, verify_and_extract, verify_certificate, verify_chain,
+    verify_prepared, verify_with_aux_hasher, AcceptableOptions, AssertedValues, CertifiedProof,
+    ChainError, CommitmentChannel, DefaultVerifierChannel, FriVerifierChannel, PreparedAir,
+    ProofCertificate, ProofOpenings, PublicOutputs, Table, TraceOodFrame, VerificationArtifacts,
+    VerifierChannel, VerifierError,
 };
-pub use verifier::{verify, VerifierError};
+
+#[cfg(feature = "protobuf")]
+pub use prover::protobuf;
+
+#[cfg(feature = "export-polys")]
+pub use prover::ProofPolys;
+
+pub mod ivc;
+
+#[cfg(feature = "bench")]
+pub mod bench;
+
+#[cfg(feature = "testing")]
+pub mod testing;
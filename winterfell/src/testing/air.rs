@@ -0,0 +1,214 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin},
+    math::FieldElement,
+    Air, AuxTraceRandElements, ColMatrix, ConstraintCompositionCoefficients, ConstraintEvaluator,
+    DefaultConstraintEvaluator, DefaultTraceLde, EvaluationFrame, FieldExtension, ProofOptions,
+    Trace, TraceLde,
+};
+use prover::StarkDomain;
+use rand_utils::rand_value;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of points of the constraint evaluation domain at which the evaluators are compared.
+const NUM_POINTS: usize = 32;
+
+/// Number of invalid traces derived from each of the provided traces.
+const NUM_INVALID_TRACES: usize = 4;
+
+// AIR CONSISTENCY CHECK
+// ================================================================================================
+
+/// Checks that constraints of the AIR `A` are evaluated consistently by the prover and by the
+/// verifier.
+///
+/// For each of the provided `traces` (and the public inputs at the same index), this compares
+/// evaluations of constraints by the prover-side [DefaultConstraintEvaluator] against evaluations
+/// by the verifier-side [evaluate_constraints()](verifier::evaluate_constraints) at randomly
+/// selected points of the constraint evaluation domain. Transition constraints and assertions
+/// are compared separately, in both cases as random linear combinations of all constraints
+/// divided by their respective divisors.
+///
+/// The comparison is performed for the provided trace itself as well as for several invalid
+/// traces derived from it by randomly changing values in its main and auxiliary segments, since
+/// constraints evaluate to non-zero values only over invalid traces. The provided traces are not
+/// required to be valid.
+///
+/// # Panics
+/// Panics if:
+/// * The number of traces is not the same as the number of public inputs.
+/// * For any of the traces, evaluations of constraints by the prover and by the verifier differ
+///   at any of the selected points.
+pub fn check_air<A, T>(traces: Vec<T>, pub_inputs: &[A::PublicInputs])
+where
+    A: Air,
+    A::PublicInputs: Clone,
+    T: Trace<BaseField = A::BaseField>,
+{
+    assert_eq!(
+        traces.len(),
+        pub_inputs.len(),
+        "number of traces must be the same as the number of public inputs"
+    );
+
+    for (trace_idx, (mut trace, pub_inputs)) in traces.into_iter().zip(pub_inputs).enumerate() {
+        let air = build_air::<A>(&trace, pub_inputs);
+        let domain = StarkDomain::new(&air);
+
+        // build auxiliary trace segments (if any) using random elements drawn from a randomly
+        // seeded public coin
+        let mut public_coin = DefaultRandomCoin::<Blake3_256<A::BaseField>>::new(&[rand_value()]);
+        let mut aux_segments: Vec<ColMatrix<A::BaseField>> = Vec::new();
+        let mut aux_rand_elements = AuxTraceRandElements::new();
+        for i in 0..trace.layout().num_aux_segments() {
+            let rand_elements = air
+                .get_aux_trace_segment_random_elements(i, &mut public_coin)
+                .expect("failed to draw random elements for an auxiliary trace segment");
+            let aux_segment = trace
+                .build_aux_segment(&aux_segments, &rand_elements)
+                .expect("failed to build an auxiliary trace segment");
+            aux_segments.push(aux_segment);
+            aux_rand_elements.add_segment_elements(rand_elements);
+        }
+        let coefficients = air
+            .get_constraint_composition_coefficients(&mut public_coin)
+            .expect("failed to draw constraint composition coefficients");
+
+        // compare evaluations over the provided trace and over invalid traces derived from it
+        let main_segment = trace.main_segment().clone();
+        for variant in 0..=NUM_INVALID_TRACES {
+            let mut main_segment = main_segment.clone();
+            let mut aux_segments = aux_segments.clone();
+            if variant > 0 {
+                mutate_trace(&mut main_segment, &mut aux_segments);
+            }
+
+            let trace_info = trace.get_info();
+            let (_, mut trace_lde) = DefaultTraceLde::<A::BaseField, Blake3_256<A::BaseField>>::new(
+                &trace_info,
+                &main_segment,
+                &domain,
+            );
+            for aux_segment in aux_segments.iter() {
+                trace_lde.add_aux_segment(aux_segment, &domain);
+            }
+
+            // transition constraints and assertions are compared separately so that a mismatch
+            // can be attributed to one of them
+            let transition_coefficients = ConstraintCompositionCoefficients {
+                transition: coefficients.transition.clone(),
+                boundary: vec![FieldElement::ZERO; coefficients.boundary.len()],
+            };
+            let boundary_coefficients = ConstraintCompositionCoefficients {
+                transition: vec![FieldElement::ZERO; coefficients.transition.len()],
+                boundary: coefficients.boundary.clone(),
+            };
+            for (kind, coefficients) in [
+                ("transition constraints", transition_coefficients),
+                ("assertions", boundary_coefficients),
+            ] {
+                if let Some((step, expected, actual)) =
+                    compare_evaluators(&air, &trace_lde, &domain, &aux_rand_elements, coefficients)
+                {
+                    panic!(
+                        "evaluations of {kind} diverged for trace {trace_idx} ({}) at step {step} \
+                        of the constraint evaluation domain: prover evaluated {expected}, but \
+                        verifier evaluated {actual}",
+                        if variant == 0 {
+                            "as provided".to_string()
+                        } else {
+                            format!("invalid variant {variant}")
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Instantiates the AIR for the specified trace using the smallest blowup factor which is
+/// sufficient to evaluate its constraints.
+fn build_air<A>(trace: &impl Trace<BaseField = A::BaseField>, pub_inputs: &A::PublicInputs) -> A
+where
+    A: Air,
+    A::PublicInputs: Clone,
+{
+    let build_options =
+        |blowup_factor| ProofOptions::new(1, blowup_factor, 0, FieldExtension::None, 2, 0);
+    let probe = A::new(
+        trace.get_info(),
+        pub_inputs.clone(),
+        build_options(ProofOptions::MAX_BLOWUP_FACTOR),
+    );
+    let blowup_factor = probe.ce_blowup_factor().max(ProofOptions::MIN_BLOWUP_FACTOR);
+    A::new(trace.get_info(), pub_inputs.clone(), build_options(blowup_factor))
+}
+
+/// Compares evaluations of constraints by the prover-side and the verifier-side evaluators at
+/// randomly selected points of the constraint evaluation domain; returns the first point at
+/// which the evaluations differ together with prover and verifier evaluations at this point.
+fn compare_evaluators<A: Air>(
+    air: &A,
+    trace_lde: &DefaultTraceLde<A::BaseField, Blake3_256<A::BaseField>>,
+    domain: &StarkDomain<A::BaseField>,
+    aux_rand_elements: &AuxTraceRandElements<A::BaseField>,
+    coefficients: ConstraintCompositionCoefficients<A::BaseField>,
+) -> Option<(usize, A::BaseField, A::BaseField)> {
+    // evaluate constraints over the entire constraint evaluation domain on the prover side;
+    // degree validation is skipped as it fails for invalid traces
+    let evaluations =
+        DefaultConstraintEvaluator::new(air, aux_rand_elements.clone(), coefficients.clone())
+            .without_degree_validation()
+            .evaluate(trace_lde, domain)
+            .combine();
+
+    // evaluate constraints at randomly selected points on the verifier side
+    let layout = air.trace_layout();
+    let mut main_frame = EvaluationFrame::new(layout.main_trace_width());
+    let mut aux_frame =
+        (layout.num_aux_segments() > 0).then(|| EvaluationFrame::new(layout.aux_trace_width()));
+    for _ in 0..NUM_POINTS {
+        let step = rand_value::<u64>() as usize % domain.ce_domain_size();
+        let lde_step = step * (domain.trace_lde_domain_size() / domain.ce_domain_size());
+        trace_lde.read_main_trace_frame_into(lde_step, &mut main_frame);
+        if let Some(aux_frame) = aux_frame.as_mut() {
+            trace_lde.read_aux_trace_frame_into(lde_step, aux_frame);
+        }
+
+        let value = verifier::evaluate_constraints(
+            air,
+            coefficients.clone(),
+            &main_frame,
+            &aux_frame,
+            aux_rand_elements.clone(),
+            domain.get_ce_x_at(step),
+        );
+        if value != evaluations[step] {
+            return Some((step, evaluations[step], value));
+        }
+    }
+    None
+}
+
+/// Changes a randomly selected value in the main trace segment, and in each of the auxiliary
+/// trace segments.
+fn mutate_trace<B: FieldElement>(
+    main_segment: &mut ColMatrix<B>,
+    aux_segments: &mut [ColMatrix<B>],
+) {
+    for segment in core::iter::once(main_segment).chain(aux_segments.iter_mut()) {
+        let col_idx = rand_value::<u64>() as usize % segment.num_cols();
+        let row_idx = rand_value::<u64>() as usize % segment.num_rows();
+        let value = segment.get(col_idx, row_idx);
+        segment.set(col_idx, row_idx, value + rand_value());
+    }
+}
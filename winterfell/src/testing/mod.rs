@@ -0,0 +1,18 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Utilities for testing AIRs, provers, and verifiers built with Winterfell.
+//!
+//! This module is available only when the `testing` feature is enabled.
+
+mod air;
+pub use air::check_air;
+
+mod mutation;
+pub use mutation::{
+    assert_proof_mutations_rejected, check_proof_mutations, MutationFailure, ProofMutation,
+};
+
+pub mod vectors;
@@ -0,0 +1,229 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Serializable, StarkProof, VerifierError};
+use core::{fmt, ops::Range};
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Masks XOR-ed into a proof byte to mutate it: flipping only the lowest bit produces mutations
+/// which are likely to still be parsed successfully, while flipping all bits is likely to hit
+/// length prefixes and non-canonical field element encodings.
+const MUTATION_MASKS: [u8; 2] = [0x01, 0xff];
+
+// PROOF MUTATIONS
+// ================================================================================================
+
+/// A single-byte mutation of a serialized STARK proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofMutation {
+    /// Name of the proof section containing the mutated byte.
+    pub section: &'static str,
+    /// Offset of the mutated byte from the start of the serialized proof.
+    pub offset: usize,
+    /// Mask which was XOR-ed into the mutated byte.
+    pub mask: u8,
+}
+
+/// Describes how a mutated proof failed to be rejected properly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MutationFailure {
+    /// The mutated proof was parsed and accepted by the verifier.
+    Accepted(ProofMutation),
+    /// Parsing the mutated proof resulted in a panic with the specified message.
+    DeserializationPanic(ProofMutation, String),
+    /// Verifying the mutated proof resulted in a panic with the specified message.
+    VerifierPanic(ProofMutation, String),
+}
+
+impl fmt::Display for MutationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Accepted(m) => {
+                write!(
+                    f,
+                    "mutated {} byte at offset {} (mask {:#04x}) was accepted",
+                    m.section, m.offset, m.mask
+                )
+            }
+            Self::DeserializationPanic(m, msg) => {
+                write!(
+                    f,
+                    "parsing mutated {} byte at offset {} (mask {:#04x}) panicked: {msg}",
+                    m.section, m.offset, m.mask
+                )
+            }
+            Self::VerifierPanic(m, msg) => {
+                write!(
+                    f,
+                    "verifying mutated {} byte at offset {} (mask {:#04x}) panicked: {msg}",
+                    m.section, m.offset, m.mask
+                )
+            }
+        }
+    }
+}
+
+/// Mutates bytes in each section of the serialized `proof` and checks that every mutated proof
+/// is rejected without a panic; returns the list of mutations which were not rejected properly.
+///
+/// Sections of the proof are trace layout, the rest of the proof context, commitments, trace
+/// queries, constraint queries, out-of-domain frame, FRI proof, and proof-of-work nonce. For each section, up to
+/// `max_mutations_per_section` bytes spread evenly across the section (always including its
+/// first and last bytes) are mutated one at a time. A mutated proof is considered to be rejected
+/// properly if either [StarkProof::from_bytes()] returns an error, or the parsed proof is
+/// rejected by the `verify` closure. Panics in either of these are caught and reported as
+/// failures.
+///
+/// The `verify` closure is expected to verify the proof against the same public inputs against
+/// which the original proof is valid, e.g.:
+/// ```ignore
+/// let failures = check_proof_mutations(&proof, 16, |proof| {
+///     winterfell::verify::<MyAir, Blake3_256<BaseElement>, DefaultRandomCoin<_>>(proof, pub_inputs)
+/// });
+/// assert!(failures.is_empty());
+/// ```
+///
+/// # Panics
+/// Panics if `max_mutations_per_section` is smaller than two.
+pub fn check_proof_mutations<F>(
+    proof: &StarkProof,
+    max_mutations_per_section: usize,
+    verify: F,
+) -> Vec<MutationFailure>
+where
+    F: Fn(StarkProof) -> Result<(), VerifierError>,
+{
+    assert!(
+        max_mutations_per_section >= 2,
+        "at least two mutations per proof section are required, but {max_mutations_per_section} was specified"
+    );
+
+    let proof_bytes = proof.to_bytes();
+    let mut failures = Vec::new();
+    for (section, range) in get_proof_sections(proof) {
+        for offset in get_mutation_offsets(range, max_mutations_per_section) {
+            for mask in MUTATION_MASKS {
+                let mutation = ProofMutation {
+                    section,
+                    offset,
+                    mask,
+                };
+                if let Some(failure) = check_mutation(&proof_bytes, mutation, &verify) {
+                    failures.push(failure);
+                }
+            }
+        }
+    }
+    failures
+}
+
+/// Checks that every mutation of the provided `proof` is rejected without a panic.
+///
+/// See [check_proof_mutations()] for the description of the mutations and of the `verify`
+/// closure.
+///
+/// # Panics
+/// Panics if any of the mutated proofs was accepted by the verifier, or if parsing or verifying
+/// any of the mutated proofs resulted in a panic.
+pub fn assert_proof_mutations_rejected<F>(
+    proof: &StarkProof,
+    max_mutations_per_section: usize,
+    verify: F,
+) where
+    F: Fn(StarkProof) -> Result<(), VerifierError>,
+{
+    let failures = check_proof_mutations(proof, max_mutations_per_section, verify);
+    if !failures.is_empty() {
+        let failures = failures.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        panic!("{} proof mutations were not rejected:\n{}", failures.len(), failures.join("\n"));
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Applies the specified mutation to the proof bytes, and returns a failure if the mutated proof
+/// was not rejected properly.
+fn check_mutation<F>(
+    proof_bytes: &[u8],
+    mutation: ProofMutation,
+    verify: &F,
+) -> Option<MutationFailure>
+where
+    F: Fn(StarkProof) -> Result<(), VerifierError>,
+{
+    let mut mutated_bytes = proof_bytes.to_vec();
+    mutated_bytes[mutation.offset] ^= mutation.mask;
+
+    let proof = match panic::catch_unwind(|| StarkProof::from_bytes(&mutated_bytes)) {
+        Ok(Ok(proof)) => proof,
+        Ok(Err(_)) => return None,
+        Err(err) => {
+            return Some(MutationFailure::DeserializationPanic(mutation, panic_message(err)))
+        }
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| verify(proof))) {
+        Ok(Ok(_)) => Some(MutationFailure::Accepted(mutation)),
+        Ok(Err(_)) => None,
+        Err(err) => Some(MutationFailure::VerifierPanic(mutation, panic_message(err))),
+    }
+}
+
+/// Returns names and byte ranges of all sections of the serialized proof in the order in which
+/// they are serialized by [StarkProof::to_bytes()].
+fn get_proof_sections(proof: &StarkProof) -> Vec<(&'static str, Range<usize>)> {
+    let trace_layout_size = proof.context.trace_layout().to_bytes().len();
+    let section_sizes = [
+        ("trace_layout", trace_layout_size),
+        ("context", proof.context.to_bytes().len() - trace_layout_size),
+        ("commitments", proof.commitments.to_bytes().len()),
+        ("trace_queries", proof.trace_queries.iter().map(|q| q.to_bytes().len()).sum()),
+        ("constraint_queries", proof.constraint_queries.to_bytes().len()),
+        ("ood_frame", proof.ood_frame.to_bytes().len()),
+        ("fri_proof", proof.fri_proof.to_bytes().len()),
+        ("pow_nonce", proof.pow_nonce.to_le_bytes().len()),
+    ];
+
+    let mut start = 0;
+    let mut result = Vec::with_capacity(section_sizes.len());
+    for (name, size) in section_sizes {
+        result.push((name, start..start + size));
+        start += size;
+    }
+    debug_assert_eq!(start, proof.to_bytes().len(), "proof sections do not cover the entire proof");
+    result
+}
+
+/// Returns up to `max_offsets` offsets spread evenly across the specified range; the first and
+/// the last offsets of the range are always included.
+fn get_mutation_offsets(range: Range<usize>, max_offsets: usize) -> Vec<usize> {
+    if range.len() <= max_offsets {
+        return range.collect();
+    }
+    let last = range.len() - 1;
+    let mut result = (0..max_offsets)
+        .map(|i| range.start + i * last / (max_offsets - 1))
+        .collect::<Vec<_>>();
+    result.dedup();
+    result
+}
+
+/// Extracts a message from the payload of a caught panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{ExtensibleField, FieldElement, StarkField, ToElements},
+    Air, AirContext, Assertion, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, EvaluationFrame, ProofOptions, Prover, Trace,
+    TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+use core::marker::PhantomData;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns in the execution trace of the computation.
+pub const TRACE_WIDTH: usize = 2;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of the computation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VectorInputs<B: StarkField> {
+    pub seed: B,
+    pub result: B,
+}
+
+impl<B: StarkField> ToElements<B> for VectorInputs<B> {
+    fn to_elements(&self) -> Vec<B> {
+        vec![self.seed, self.result]
+    }
+}
+
+// TEST VECTOR AIR
+// ================================================================================================
+
+/// AIR of the computation for which test vectors are generated.
+///
+/// The computation updates two registers at every step as $a_{j+1} = b_j$ and
+/// $b_{j+1} = a_j + b_j^2$, starting with $a_0 = seed$ and $b_0 = 1$. The result of the
+/// computation is the value of $b$ at the last step.
+pub struct VectorAir<B: StarkField> {
+    context: AirContext<B>,
+    seed: B,
+    result: B,
+}
+
+impl<B> Air for VectorAir<B>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+{
+    type BaseField = B;
+    type PublicInputs = VectorInputs<B>;
+
+    fn new(trace_info: TraceInfo, pub_inputs: VectorInputs<B>, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let degrees = vec![TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(2)];
+        VectorAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<B> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<B>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - current[1];
+        result[1] = next[1] - (current[0] + current[1].square());
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<B>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, self.seed),
+            Assertion::single(1, 0, B::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// TEST VECTOR PROVER
+// ================================================================================================
+
+/// Prover of the computation for which test vectors are generated.
+pub struct VectorProver<B: StarkField, H: ElementHasher<BaseField = B>> {
+    options: ProofOptions,
+    _field: PhantomData<B>,
+    _hasher: PhantomData<H>,
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> VectorProver<B, H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _field: PhantomData,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace of the specified length for the computation starting with the
+    /// specified seed.
+    pub fn build_trace(&self, seed: B, trace_length: usize) -> TraceTable<B> {
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+        trace.fill(
+            |state| {
+                state[0] = seed;
+                state[1] = B::ONE;
+            },
+            |_, state| {
+                let a = state[0];
+                state[0] = state[1];
+                state[1] = a + state[1].square();
+            },
+        );
+        trace
+    }
+}
+
+impl<B, H> Prover for VectorProver<B, H>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    H: ElementHasher<BaseField = B>,
+{
+    type BaseField = B;
+    type Air = VectorAir<B>;
+    type Trace = TraceTable<B>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<H>;
+    type TraceLde<E: FieldElement<BaseField = B>> = DefaultTraceLde<E, H>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = B>> =
+        DefaultConstraintEvaluator<'a, VectorAir<B>, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> VectorInputs<B> {
+        VectorInputs {
+            seed: trace.get(0, 0),
+            result: trace.get(1, trace.length() - 1),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a VectorAir<B>,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
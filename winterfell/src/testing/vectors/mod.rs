@@ -0,0 +1,263 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Golden test vectors for validating alternative implementations of the STARK verifier.
+//!
+//! A test vector is generated for a fixed computation (see [VectorAir]) for every supported
+//! combination of field and hash function. Besides the inputs of the verifier (proof options,
+//! public inputs, and serialized proof), every test vector contains the full transcript of the
+//! verifier's interactions with the public coin. This makes it possible to check an alternative
+//! implementation of the verifier (e.g., in Solidity, in C, or in a circuit) byte-for-byte at
+//! every step of the protocol, rather than only against the final accept/reject outcome.
+//!
+//! In the transcript, the public coin is instantiated with the seed derived from the proof
+//! context and public inputs, and then reseeded with the trace commitment, the constraint
+//! commitment, the hash of the out-of-domain frame, and FRI layer commitments. In between these,
+//! the verifier draws constraint composition coefficients, the out-of-domain point, DEEP
+//! composition coefficients, and FRI folding challenges. Lastly, proof-of-work is checked and
+//! query positions are drawn.
+//!
+//! Test vectors generated by the current version of the library are stored in the `vectors`
+//! directory of the `winterfell` crate, one JSON file per test vector.
+//!
+//! Proof-of-work nonces found by the prover depend on the order in which candidate nonces are
+//! checked; thus, proofs generated with the `concurrent` feature enabled may differ from the
+//! stored test vectors.
+
+use crate::{
+    crypto::{
+        hashers::{
+            Blake3_192, Blake3_256, GriffinJive64_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256,
+        },
+        DefaultRandomCoin, ElementHasher,
+    },
+    math::{
+        fields::{f128, f62, f64},
+        ExtensibleField, StarkField, ToElements,
+    },
+    FieldExtension, ProofOptions, Prover, Serializable,
+};
+use core::fmt::Write;
+use std::{fs, io, path::Path};
+
+mod air;
+pub use air::{VectorAir, VectorInputs, VectorProver, TRACE_WIDTH};
+
+mod transcript;
+pub use transcript::{record_transcript, RecordingCoin, TranscriptEvent};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Length of the execution trace of the computation.
+pub const TRACE_LENGTH: usize = 16;
+
+/// Initial value of the computation.
+const SEED: u32 = 3;
+
+// TEST VECTOR
+// ================================================================================================
+
+/// A test vector for a single combination of field and hash function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestVector {
+    /// Name of the field, one of `f64`, `f62`, and `f128`.
+    pub field: &'static str,
+    /// Name of the hash function, e.g. `blake3_256`.
+    pub hasher: &'static str,
+    /// Options with which the proof was generated.
+    pub options: ProofOptions,
+    /// Length of the execution trace.
+    pub trace_length: usize,
+    /// Serialized public inputs of the computation.
+    pub pub_inputs: Vec<u8>,
+    /// Interactions of the verifier with the public coin in the order in which they happened.
+    pub transcript: Vec<TranscriptEvent>,
+    /// Serialized proof.
+    pub proof: Vec<u8>,
+}
+
+impl TestVector {
+    /// Returns the name of this test vector in the form `{field}_{hasher}`.
+    pub fn name(&self) -> String {
+        format!("{}_{}", self.field, self.hasher)
+    }
+
+    /// Returns this test vector encoded as a JSON object; all byte strings are hex-encoded.
+    pub fn to_json(&self) -> String {
+        let fri_options = self.options.to_fri_options();
+        let mut json = String::new();
+        writeln!(json, "{{").unwrap();
+        writeln!(json, "  \"field\": \"{}\",", self.field).unwrap();
+        writeln!(json, "  \"hasher\": \"{}\",", self.hasher).unwrap();
+        writeln!(json, "  \"options\": {{").unwrap();
+        writeln!(json, "    \"num_queries\": {},", self.options.num_queries()).unwrap();
+        writeln!(json, "    \"blowup_factor\": {},", self.options.blowup_factor()).unwrap();
+        writeln!(json, "    \"grinding_factor\": {},", self.options.grinding_factor()).unwrap();
+        writeln!(json, "    \"field_extension\": {},", self.options.field_extension().degree())
+            .unwrap();
+        writeln!(json, "    \"fri_folding_factor\": {},", fri_options.folding_factor()).unwrap();
+        writeln!(
+            json,
+            "    \"fri_remainder_max_degree\": {},",
+            fri_options.remainder_max_degree()
+        )
+        .unwrap();
+        writeln!(json, "    \"bytes\": \"{}\"", to_hex(&self.options.to_bytes())).unwrap();
+        writeln!(json, "  }},").unwrap();
+        writeln!(json, "  \"trace_length\": {},", self.trace_length).unwrap();
+        writeln!(json, "  \"pub_inputs\": \"{}\",", to_hex(&self.pub_inputs)).unwrap();
+        writeln!(json, "  \"transcript\": [").unwrap();
+        for (i, event) in self.transcript.iter().enumerate() {
+            let separator = if i + 1 < self.transcript.len() { "," } else { "" };
+            writeln!(json, "    {}{separator}", event_to_json(event)).unwrap();
+        }
+        writeln!(json, "  ],").unwrap();
+        writeln!(json, "  \"proof\": \"{}\"", to_hex(&self.proof)).unwrap();
+        writeln!(json, "}}").unwrap();
+        json
+    }
+}
+
+// TEST VECTOR GENERATION
+// ================================================================================================
+
+/// Generates test vectors for every supported combination of field and hash function.
+///
+/// Computations over 64-bit and 62-bit fields are performed in their quadratic extensions.
+///
+/// # Panics
+/// Panics if a proof could not be generated or verified for any of the combinations.
+pub fn generate_test_vectors() -> Vec<TestVector> {
+    type F64 = f64::BaseElement;
+    type F62 = f62::BaseElement;
+    type F128 = f128::BaseElement;
+    let quadratic = FieldExtension::Quadratic;
+    let none = FieldExtension::None;
+
+    vec![
+        build_test_vector::<F64, Blake3_192<F64>>("f64", "blake3_192", quadratic),
+        build_test_vector::<F64, Blake3_256<F64>>("f64", "blake3_256", quadratic),
+        build_test_vector::<F64, Sha3_256<F64>>("f64", "sha3_256", quadratic),
+        build_test_vector::<F64, Rp64_256>("f64", "rp64_256", quadratic),
+        build_test_vector::<F64, RpJive64_256>("f64", "rp_jive64_256", quadratic),
+        build_test_vector::<F64, GriffinJive64_256>("f64", "griffin_jive64_256", quadratic),
+        build_test_vector::<F62, Blake3_192<F62>>("f62", "blake3_192", quadratic),
+        build_test_vector::<F62, Blake3_256<F62>>("f62", "blake3_256", quadratic),
+        build_test_vector::<F62, Sha3_256<F62>>("f62", "sha3_256", quadratic),
+        build_test_vector::<F62, Rp62_248>("f62", "rp62_248", quadratic),
+        build_test_vector::<F128, Blake3_192<F128>>("f128", "blake3_192", none),
+        build_test_vector::<F128, Blake3_256<F128>>("f128", "blake3_256", none),
+        build_test_vector::<F128, Sha3_256<F128>>("f128", "sha3_256", none),
+    ]
+}
+
+/// Generates test vectors for every supported combination of field and hash function, and
+/// writes each of them into a file named `{field}_{hasher}.json` in the specified directory.
+///
+/// # Errors
+/// Returns an error if the directory could not be created or any of the files could not be
+/// written.
+///
+/// # Panics
+/// Panics if a proof could not be generated or verified for any of the combinations.
+pub fn export_test_vectors(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for vector in generate_test_vectors() {
+        fs::write(dir.join(format!("{}.json", vector.name())), vector.to_json())?;
+    }
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Generates a proof for the fixed computation using the specified field and hash function, and
+/// records the transcript of its verification.
+fn build_test_vector<B, H>(
+    field: &'static str,
+    hasher: &'static str,
+    field_extension: FieldExtension,
+) -> TestVector
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + 'static,
+    H: ElementHasher<BaseField = B>,
+{
+    let options = ProofOptions::new(4, 4, 4, field_extension, 4, 3);
+    let prover = VectorProver::<B, H>::new(options.clone());
+    let trace = prover.build_trace(B::from(SEED), TRACE_LENGTH);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).expect("failed to generate a proof");
+
+    let (result, transcript) = record_transcript(|| {
+        crate::verify::<VectorAir<B>, H, RecordingCoin<DefaultRandomCoin<H>>>(
+            proof.clone(),
+            pub_inputs,
+        )
+    });
+    result.expect("failed to verify a proof");
+
+    TestVector {
+        field,
+        hasher,
+        options,
+        trace_length: TRACE_LENGTH,
+        pub_inputs: pub_inputs.to_elements().iter().flat_map(|e| e.to_bytes()).collect(),
+        transcript,
+        proof: proof.to_bytes(),
+    }
+}
+
+/// Encodes a transcript event as a single-line JSON object.
+fn event_to_json(event: &TranscriptEvent) -> String {
+    match event {
+        TranscriptEvent::Init { seed } => {
+            format!("{{ \"op\": \"init\", \"seed\": \"{}\" }}", to_hex(seed))
+        }
+        TranscriptEvent::Reseed { data } => {
+            format!("{{ \"op\": \"reseed\", \"data\": \"{}\" }}", to_hex(data))
+        }
+        TranscriptEvent::Draw { value } => {
+            format!("{{ \"op\": \"draw\", \"value\": \"{}\" }}", to_hex(value))
+        }
+        TranscriptEvent::DrawMany { values } => {
+            let values = values
+                .iter()
+                .map(|v| format!("\"{}\"", to_hex(v)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{{ \"op\": \"draw_many\", \"values\": [{values}] }}")
+        }
+        TranscriptEvent::CheckLeadingZeros {
+            nonce,
+            leading_zeros,
+        } => format!(
+            "{{ \"op\": \"check_leading_zeros\", \"nonce\": {nonce}, \
+            \"leading_zeros\": {leading_zeros} }}"
+        ),
+        TranscriptEvent::DrawIntegers {
+            nonce,
+            domain_size,
+            values,
+        } => {
+            let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ");
+            format!(
+                "{{ \"op\": \"draw_integers\", \"nonce\": {nonce}, \
+                \"domain_size\": {domain_size}, \"values\": [{values}] }}"
+            )
+        }
+    }
+}
+
+/// Encodes the specified bytes as a lowercase hex string.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut result, byte| {
+        write!(result, "{byte:02x}").unwrap();
+        result
+    })
+}
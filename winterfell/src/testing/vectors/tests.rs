@@ -0,0 +1,57 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{generate_test_vectors, TranscriptEvent};
+
+/// Checks generated test vectors against the ones stored in the `vectors` directory; set the
+/// `UPDATE_TEST_VECTORS` environment variable to overwrite the stored test vectors instead.
+#[test]
+#[cfg(not(feature = "concurrent"))]
+fn test_vectors_unchanged() {
+    use std::{env, fs, path::PathBuf};
+
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("vectors");
+    let update = env::var_os("UPDATE_TEST_VECTORS").is_some();
+    if update {
+        fs::create_dir_all(&dir).unwrap();
+    }
+
+    for vector in generate_test_vectors() {
+        let path = dir.join(format!("{}.json", vector.name()));
+        if update {
+            fs::write(&path, vector.to_json()).unwrap();
+        } else {
+            let expected = fs::read_to_string(&path).unwrap();
+            assert_eq!(expected, vector.to_json(), "test vector {} has changed", vector.name());
+        }
+    }
+}
+
+#[test]
+fn test_vectors_transcript() {
+    for vector in generate_test_vectors() {
+        let transcript = &vector.transcript;
+
+        // the transcript starts with instantiation of the public coin, and ends with drawing
+        // query positions right after checking proof-of-work
+        assert!(matches!(transcript.first(), Some(TranscriptEvent::Init { .. })));
+        let n = transcript.len();
+        match &transcript[n - 2..] {
+            [TranscriptEvent::CheckLeadingZeros {
+                nonce,
+                leading_zeros,
+            }, TranscriptEvent::DrawIntegers {
+                nonce: query_nonce,
+                values,
+                ..
+            }] => {
+                assert_eq!(nonce, query_nonce);
+                assert!(*leading_zeros >= vector.options.grinding_factor());
+                assert_eq!(vector.options.num_queries(), values.len());
+            }
+            _ => panic!("transcript of test vector {} is not terminated properly", vector.name()),
+        }
+    }
+}
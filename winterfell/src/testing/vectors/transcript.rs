@@ -0,0 +1,131 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    crypto::{Hasher, RandomCoin, RandomCoinError},
+    math::FieldElement,
+    Serializable,
+};
+use std::cell::RefCell;
+
+// TRANSCRIPT EVENTS
+// ================================================================================================
+
+/// An interaction with the public coin during proof verification.
+///
+/// Field elements and digests are encoded as returned by their [Serializable] implementations,
+/// i.e., field elements are encoded in canonical form in little-endian byte order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEvent {
+    /// The public coin was instantiated with the specified seed.
+    Init { seed: Vec<u8> },
+    /// The public coin was reseeded with the specified digest.
+    Reseed { data: Vec<u8> },
+    /// A field element (in the base field or in its extension) was drawn from the public coin.
+    Draw { value: Vec<u8> },
+    /// Several field elements (in the base field or in its extension) were drawn from the public
+    /// coin at once.
+    DrawMany { values: Vec<Vec<u8>> },
+    /// The number of leading zeros in hash(`seed` || `nonce`) was computed.
+    CheckLeadingZeros { nonce: u64, leading_zeros: u32 },
+    /// Unique integers in the range [0, `domain_size`) were drawn from the public coin after
+    /// reseeding it with the specified nonce.
+    DrawIntegers {
+        nonce: u64,
+        domain_size: usize,
+        values: Vec<usize>,
+    },
+}
+
+// TRANSCRIPT RECORDING
+// ================================================================================================
+
+std::thread_local! {
+    static TRANSCRIPT: RefCell<Vec<TranscriptEvent>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Executes the specified closure and returns its result together with all interactions with
+/// [RecordingCoin] instances which happened on the current thread during its execution.
+pub fn record_transcript<T>(f: impl FnOnce() -> T) -> (T, Vec<TranscriptEvent>) {
+    TRANSCRIPT.with(|transcript| transcript.borrow_mut().clear());
+    let result = f();
+    let transcript = TRANSCRIPT.with(|transcript| transcript.take());
+    (result, transcript)
+}
+
+fn record(event: TranscriptEvent) {
+    TRANSCRIPT.with(|transcript| transcript.borrow_mut().push(event));
+}
+
+// RECORDING COIN
+// ================================================================================================
+
+/// A public coin which records all interactions with it, and otherwise behaves exactly as the
+/// wrapped coin.
+pub struct RecordingCoin<R: RandomCoin> {
+    coin: R,
+}
+
+impl<R: RandomCoin> RandomCoin for RecordingCoin<R> {
+    type BaseField = R::BaseField;
+    type Hasher = R::Hasher;
+
+    fn new(seed: &[Self::BaseField]) -> Self {
+        record(TranscriptEvent::Init {
+            seed: seed.iter().flat_map(|element| element.to_bytes()).collect(),
+        });
+        Self { coin: R::new(seed) }
+    }
+
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest) {
+        record(TranscriptEvent::Reseed {
+            data: data.to_bytes(),
+        });
+        self.coin.reseed(data);
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        let leading_zeros = self.coin.check_leading_zeros(value);
+        record(TranscriptEvent::CheckLeadingZeros {
+            nonce: value,
+            leading_zeros,
+        });
+        leading_zeros
+    }
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        let value = self.coin.draw::<E>()?;
+        record(TranscriptEvent::Draw {
+            value: value.to_bytes(),
+        });
+        Ok(value)
+    }
+
+    fn draw_many<E: FieldElement<BaseField = Self::BaseField>>(
+        &mut self,
+        num_elements: usize,
+    ) -> Result<Vec<E>, RandomCoinError> {
+        let values = self.coin.draw_many::<E>(num_elements)?;
+        record(TranscriptEvent::DrawMany {
+            values: values.iter().map(|value| value.to_bytes()).collect(),
+        });
+        Ok(values)
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        let values = self.coin.draw_integers(num_values, domain_size, nonce)?;
+        record(TranscriptEvent::DrawIntegers {
+            nonce,
+            domain_size,
+            values: values.clone(),
+        });
+        Ok(values)
+    }
+}